@@ -13,42 +13,93 @@ use elem_key::ElemKey;
 mod list_key;
 use list_key::ListKey;
 
+pub mod core_financial;
+pub use core_financial::CoreFinancial;
+
 pub mod core_manager;
 pub use core_manager::CoreManager;
 
+pub mod core_irr;
+pub use core_irr::CoreIrr;
+
 pub mod core_utility;
 pub use core_utility::CoreUtility;
 
 pub mod elem_amortization;
 pub use elem_amortization::ElemAmortization;
 
+pub mod elem_apr_result;
+pub use elem_apr_result::ElemAprResult;
+
 pub mod elem_balance_result;
 pub use elem_balance_result::ElemBalanceResult;
 
+pub mod elem_calendar;
+pub use elem_calendar::ElemCalendar;
+
 pub mod elem_column;
 pub use elem_column::ElemColumn;
 
 pub mod elem_current_value;
 pub use elem_current_value::ElemCurrentValue;
 
+pub mod elem_dated_amount;
+pub use elem_dated_amount::ElemDatedAmount;
+
+pub mod elem_default_scenario;
+pub use elem_default_scenario::ElemDefaultScenario;
+
 pub mod elem_descriptor;
 pub use elem_descriptor::ElemDescriptor;
 
+pub mod elem_error;
+pub use elem_error::ElemError;
+
 pub mod elem_event;
 pub use elem_event::ElemEvent;
 
+pub mod elem_expr_limits;
+pub use elem_expr_limits::ElemExprLimits;
+
+pub mod elem_expr_node;
+pub use elem_expr_node::ElemExprNode;
+
+pub mod elem_expr_trace;
+pub use elem_expr_trace::ElemExprTrace;
+
 pub mod elem_extension;
 pub use elem_extension::{ElemExtension, ExtensionValue};
 
+pub mod elem_function;
+pub use elem_function::ElemFunction;
+
+pub mod elem_holiday;
+pub use elem_holiday::ElemHoliday;
+
 pub mod elem_interest_change;
 pub use elem_interest_change::ElemInterestChange;
 
+pub mod elem_irr_result;
+pub use elem_irr_result::ElemIrrResult;
+
 pub mod elem_parameter;
 pub use elem_parameter::ElemParameter;
 
+pub mod elem_payment_step;
+pub use elem_payment_step::ElemPaymentStep;
+
+pub mod elem_prepayment;
+pub use elem_prepayment::ElemPrepayment;
+
 pub mod elem_principal_change;
 pub use elem_principal_change::ElemPrincipalChange;
 
+pub mod elem_rate_index;
+pub use elem_rate_index::ElemRateIndex;
+
+pub mod elem_solver_settings;
+pub use elem_solver_settings::ElemSolverSettings;
+
 pub mod elem_statistic_helper;
 pub use elem_statistic_helper::ElemStatisticHelper;
 
@@ -73,6 +124,9 @@ pub use list_descriptor::ListDescriptor;
 pub mod list_event;
 pub use list_event::ListEvent;
 
+pub mod list_function;
+pub use list_function::ListFunction;
+
 pub mod list_parameter;
 pub use list_parameter::ListParameter;
 
@@ -82,5 +136,8 @@ pub use list_statistic_helper::ListStatisticHelper;
 pub mod list_summary;
 pub use list_summary::ListSummary;
 
+pub mod map_expression;
+pub use map_expression::MapExpression;
+
 pub mod map_symbol;
 pub use map_symbol::MapSymbol;
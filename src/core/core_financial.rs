@@ -0,0 +1,520 @@
+//! The spreadsheet compatible financial methods.
+// Copyright (c) 2021 ShiftLeft Software
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use rust_decimal::prelude::*;
+
+use crate::core::{CoreUtility, ElemDatedAmount, ElemError};
+
+pub struct CoreFinancial {}
+
+/// The spreadsheet compatible financial methods implementation.
+/// Rates are periodic rates expressed as decimal values (e.g.,
+/// 0.005 for 6% compounded monthly) and the sign convention is
+/// that of a spreadsheet: money paid out is negative and money
+/// received is positive. Payments are made at the end of each
+/// period unless due is true (i.e., at the beginning of each period).
+impl CoreFinancial {
+    /// Returns the growth factor (1 + rate) ^ periods. Whole
+    /// periods are calculated exactly with Decimal arithmetic.
+    ///
+    /// # Arguments
+    ///
+    /// * `rate` - Periodic rate.
+    /// * `periods` - Number of periods.
+    ///
+    /// # Return
+    ///
+    /// * The growth factor, otherwise None if out of range.
+    fn growth(rate: Decimal, periods: Decimal) -> Option<Decimal> {
+        let base = dec!(1.0) + rate;
+
+        if periods.fract() != dec!(0.0) {
+            let result = base.to_f64()?.powf(periods.to_f64()?);
+            if !result.is_finite() {
+                return None;
+            }
+            return Decimal::from_f64(result);
+        }
+
+        let mut exponent = periods.abs().to_u64()?;
+        let mut factor = base;
+        let mut result = dec!(1.0);
+
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = result.checked_mul(factor)?;
+            }
+            exponent >>= 1;
+            if exponent > 0 {
+                factor = factor.checked_mul(factor)?;
+            }
+        }
+
+        if periods.is_sign_negative() {
+            return dec!(1.0).checked_div(result);
+        }
+
+        Some(result)
+    }
+
+    /// Returns the payment timing factor (1 + rate) for
+    /// payments due at the beginning of each period.
+    ///
+    /// # Arguments
+    ///
+    /// * `rate` - Periodic rate.
+    /// * `due` - Payments due at the beginning of each period.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn timing(rate: Decimal, due: bool) -> Decimal {
+        if due {
+            dec!(1.0) + rate
+        } else {
+            dec!(1.0)
+        }
+    }
+
+    /// Calculates the payment of a loan or annuity (PMT).
+    ///
+    /// # Arguments
+    ///
+    /// * `rate` - Periodic rate.
+    /// * `periods` - Number of periods.
+    /// * `pv` - Present value.
+    /// * `fv` - Future value.
+    /// * `due` - Payments due at the beginning of each period.
+    ///
+    /// # Return
+    ///
+    /// * The payment if successful, otherwise error code.
+    pub fn pmt(
+        rate: Decimal,
+        periods: Decimal,
+        pv: Decimal,
+        fv: Decimal,
+        due: bool,
+    ) -> Result<Decimal, ElemError> {
+        if periods == dec!(0.0) {
+            return Err(ElemError::new(crate::ErrorType::CalcPeriods));
+        }
+
+        if rate == dec!(0.0) {
+            return Ok(-(pv + fv) / periods);
+        }
+
+        let growth = match CoreFinancial::growth(rate, periods) {
+            None => return Err(ElemError::new(crate::ErrorType::CalcPrincipal)),
+            Some(o) => o,
+        };
+
+        let annuity = CoreFinancial::timing(rate, due) * (growth - dec!(1.0));
+        if annuity == dec!(0.0) {
+            return Err(ElemError::new(crate::ErrorType::CalcPrincipal));
+        }
+
+        match (pv * growth + fv).checked_mul(rate) {
+            None => Err(ElemError::new(crate::ErrorType::CalcPrincipal)),
+            Some(o) => Ok(-o / annuity),
+        }
+    }
+
+    /// Calculates the interest portion of a payment (IPMT).
+    ///
+    /// # Arguments
+    ///
+    /// * `rate` - Periodic rate.
+    /// * `period` - The period (starting from 1).
+    /// * `periods` - Number of periods.
+    /// * `pv` - Present value.
+    /// * `fv` - Future value.
+    /// * `due` - Payments due at the beginning of each period.
+    ///
+    /// # Return
+    ///
+    /// * The interest portion if successful, otherwise error code.
+    pub fn ipmt(
+        rate: Decimal,
+        period: Decimal,
+        periods: Decimal,
+        pv: Decimal,
+        fv: Decimal,
+        due: bool,
+    ) -> Result<Decimal, ElemError> {
+        if period < dec!(1.0) || period > periods {
+            return Err(ElemError::new(crate::ErrorType::CalcPeriods));
+        }
+
+        let pmt = CoreFinancial::pmt(rate, periods, pv, fv, due)?;
+
+        if due && period == dec!(1.0) {
+            return Ok(dec!(0.0));
+        }
+
+        let balance = CoreFinancial::fv(rate, period - dec!(1.0), pmt, pv, due)?;
+
+        Ok(balance * rate / CoreFinancial::timing(rate, due))
+    }
+
+    /// Calculates the principal portion of a payment (PPMT).
+    ///
+    /// # Arguments
+    ///
+    /// * `rate` - Periodic rate.
+    /// * `period` - The period (starting from 1).
+    /// * `periods` - Number of periods.
+    /// * `pv` - Present value.
+    /// * `fv` - Future value.
+    /// * `due` - Payments due at the beginning of each period.
+    ///
+    /// # Return
+    ///
+    /// * The principal portion if successful, otherwise error code.
+    pub fn ppmt(
+        rate: Decimal,
+        period: Decimal,
+        periods: Decimal,
+        pv: Decimal,
+        fv: Decimal,
+        due: bool,
+    ) -> Result<Decimal, ElemError> {
+        let pmt = CoreFinancial::pmt(rate, periods, pv, fv, due)?;
+
+        match CoreFinancial::ipmt(rate, period, periods, pv, fv, due) {
+            Err(e) => Err(e),
+            Ok(o) => Ok(pmt - o),
+        }
+    }
+
+    /// Calculates the present value of a loan or annuity (PV).
+    ///
+    /// # Arguments
+    ///
+    /// * `rate` - Periodic rate.
+    /// * `periods` - Number of periods.
+    /// * `pmt` - Payment.
+    /// * `fv` - Future value.
+    /// * `due` - Payments due at the beginning of each period.
+    ///
+    /// # Return
+    ///
+    /// * The present value if successful, otherwise error code.
+    pub fn pv(
+        rate: Decimal,
+        periods: Decimal,
+        pmt: Decimal,
+        fv: Decimal,
+        due: bool,
+    ) -> Result<Decimal, ElemError> {
+        if rate == dec!(0.0) {
+            return Ok(-(fv + pmt * periods));
+        }
+
+        let growth = match CoreFinancial::growth(rate, periods) {
+            None => return Err(ElemError::new(crate::ErrorType::CalcPrincipal)),
+            Some(o) => o,
+        };
+
+        if growth == dec!(0.0) {
+            return Err(ElemError::new(crate::ErrorType::CalcPrincipal));
+        }
+
+        let annuity = pmt * CoreFinancial::timing(rate, due) * (growth - dec!(1.0)) / rate;
+
+        Ok(-(fv + annuity) / growth)
+    }
+
+    /// Calculates the future value of a loan or annuity (FV).
+    ///
+    /// # Arguments
+    ///
+    /// * `rate` - Periodic rate.
+    /// * `periods` - Number of periods.
+    /// * `pmt` - Payment.
+    /// * `pv` - Present value.
+    /// * `due` - Payments due at the beginning of each period.
+    ///
+    /// # Return
+    ///
+    /// * The future value if successful, otherwise error code.
+    pub fn fv(
+        rate: Decimal,
+        periods: Decimal,
+        pmt: Decimal,
+        pv: Decimal,
+        due: bool,
+    ) -> Result<Decimal, ElemError> {
+        if rate == dec!(0.0) {
+            return Ok(-(pv + pmt * periods));
+        }
+
+        let growth = match CoreFinancial::growth(rate, periods) {
+            None => return Err(ElemError::new(crate::ErrorType::CalcPrincipal)),
+            Some(o) => o,
+        };
+
+        let annuity = match (growth - dec!(1.0)).checked_mul(pmt) {
+            None => return Err(ElemError::new(crate::ErrorType::CalcPrincipal)),
+            Some(o) => o * CoreFinancial::timing(rate, due) / rate,
+        };
+
+        match pv.checked_mul(growth) {
+            None => Err(ElemError::new(crate::ErrorType::CalcPrincipal)),
+            Some(o) => Ok(-(o + annuity)),
+        }
+    }
+
+    /// Calculates the number of periods of a loan or annuity (NPER).
+    ///
+    /// # Arguments
+    ///
+    /// * `rate` - Periodic rate.
+    /// * `pmt` - Payment.
+    /// * `pv` - Present value.
+    /// * `fv` - Future value.
+    /// * `due` - Payments due at the beginning of each period.
+    ///
+    /// # Return
+    ///
+    /// * The number of periods if successful, otherwise error code.
+    pub fn nper(
+        rate: Decimal,
+        pmt: Decimal,
+        pv: Decimal,
+        fv: Decimal,
+        due: bool,
+    ) -> Result<Decimal, ElemError> {
+        if rate == dec!(0.0) {
+            if pmt == dec!(0.0) {
+                return Err(ElemError::new(crate::ErrorType::CalcPeriods));
+            }
+            return Ok(-(pv + fv) / pmt);
+        }
+
+        let annuity = pmt * CoreFinancial::timing(rate, due) / rate;
+        if annuity + pv == dec!(0.0) {
+            return Err(ElemError::new(crate::ErrorType::CalcPeriods));
+        }
+
+        let ratio = ((annuity - fv) / (annuity + pv)).to_f64().unwrap_or(0.0);
+        let base = (dec!(1.0) + rate).to_f64().unwrap_or(0.0);
+        if ratio <= 0.0 || base <= 0.0 || base == 1.0 {
+            return Err(ElemError::new(crate::ErrorType::CalcPeriods));
+        }
+
+        match Decimal::from_f64(ratio.ln() / base.ln()) {
+            None => Err(ElemError::new(crate::ErrorType::CalcPeriods)),
+            Some(o) => Ok(o),
+        }
+    }
+
+    /// Calculates the periodic rate of a loan or annuity (RATE)
+    /// with Newton's method starting from the guess.
+    ///
+    /// # Arguments
+    ///
+    /// * `periods` - Number of periods.
+    /// * `pmt` - Payment.
+    /// * `pv` - Present value.
+    /// * `fv` - Future value.
+    /// * `due` - Payments due at the beginning of each period.
+    /// * `guess` - Initial guess of the periodic rate.
+    ///
+    /// # Return
+    ///
+    /// * The periodic rate if successful, otherwise error code.
+    pub fn rate(
+        periods: Decimal,
+        pmt: Decimal,
+        pv: Decimal,
+        fv: Decimal,
+        due: bool,
+        guess: Decimal,
+    ) -> Result<Decimal, ElemError> {
+        let n = periods.to_f64().unwrap_or(0.0);
+        let pmt = pmt.to_f64().unwrap_or(0.0);
+        let pv = pv.to_f64().unwrap_or(0.0);
+        let fv = fv.to_f64().unwrap_or(0.0);
+        let t = if due { 1.0 } else { 0.0 };
+
+        if n <= 0.0 {
+            return Err(ElemError::new(crate::ErrorType::CalcPeriods));
+        }
+
+        let tolerance = CoreUtility::parse_decimal(crate::DEFAULT_TOLERANCE_CALCULATE_RATE);
+        let tol = tolerance.to_f64().unwrap_or(0.0);
+        let mut rate = guess.to_f64().unwrap_or(0.1);
+
+        for _ in 0..crate::MAXIMUM_ITERATIONS_CALCULATE_RATE {
+            let (value, derivative) = if rate.abs() < tol {
+                (
+                    pv + pmt * n + fv,
+                    pv * n + pmt * (t * n + n * (n - 1.0) / 2.0),
+                )
+            } else {
+                let growth = (1.0 + rate).powf(n);
+                let growth_derivative = n * (1.0 + rate).powf(n - 1.0);
+                let annuity = (growth - 1.0) / rate;
+                let annuity_derivative =
+                    (growth_derivative * rate - (growth - 1.0)) / (rate * rate);
+
+                (
+                    pv * growth + pmt * (1.0 + rate * t) * annuity + fv,
+                    pv * growth_derivative
+                        + pmt * (t * annuity + (1.0 + rate * t) * annuity_derivative),
+                )
+            };
+
+            if !value.is_finite() || !derivative.is_finite() || derivative == 0.0 {
+                break;
+            }
+
+            let next_rate = rate - value / derivative;
+            if next_rate <= -1.0 {
+                break;
+            }
+
+            if (next_rate - rate).abs() <= tol {
+                return match Decimal::from_f64(next_rate) {
+                    None => Err(ElemError::new(crate::ErrorType::CalcInterest)),
+                    Some(o) => Ok(o.round_dp(tolerance.scale())),
+                };
+            }
+
+            rate = next_rate;
+        }
+
+        Err(ElemError::new(crate::ErrorType::CalcInterest))
+    }
+
+    /// Calculates the net present value of periodic amounts (NPV).
+    /// The first amount is discounted one period.
+    ///
+    /// # Arguments
+    ///
+    /// * `rate` - Periodic rate.
+    /// * `values` - Amounts at the end of each period.
+    ///
+    /// # Return
+    ///
+    /// * The net present value if successful, otherwise error code.
+    pub fn npv(rate: Decimal, values: &[Decimal]) -> Result<Decimal, ElemError> {
+        if rate == dec!(-1.0) {
+            return Err(ElemError::new(crate::ErrorType::CalcInterest));
+        }
+
+        let base = dec!(1.0) + rate;
+        let mut discount = dec!(1.0);
+        let mut result = dec!(0.0);
+
+        for value in values.iter() {
+            discount = match discount.checked_div(base) {
+                None => return Err(ElemError::new(crate::ErrorType::CalcInterest)),
+                Some(o) => o,
+            };
+            result += *value * discount;
+        }
+
+        Ok(result)
+    }
+
+    /// Calculates the net present value of dated amounts (XNPV).
+    /// Each amount is discounted from the date of the first amount
+    /// by the actual days elapsed over a 365 day year. No amount can
+    /// be dated before the first amount.
+    ///
+    /// # Arguments
+    ///
+    /// * `rate` - Annual rate.
+    /// * `amounts` - Dated amounts.
+    ///
+    /// # Return
+    ///
+    /// * The net present value if successful, otherwise error code.
+    pub fn xnpv(rate: Decimal, amounts: &[ElemDatedAmount]) -> Result<Decimal, ElemError> {
+        if rate <= dec!(-1.0) {
+            return Err(ElemError::new(crate::ErrorType::CalcInterest));
+        }
+
+        let first_date = match amounts.first() {
+            None => return Ok(dec!(0.0)),
+            Some(o) => o.date(),
+        };
+
+        let mut result = dec!(0.0);
+
+        for amount in amounts.iter() {
+            if amount.date() < first_date {
+                return Err(ElemError::new(crate::ErrorType::Date));
+            }
+            let days = (amount.date() - first_date).num_days();
+            let growth = match CoreFinancial::growth(rate, Decimal::from(days) / Decimal::from(365))
+            {
+                None => return Err(ElemError::new(crate::ErrorType::CalcInterest)),
+                Some(o) => o,
+            };
+            if growth == dec!(0.0) {
+                return Err(ElemError::new(crate::ErrorType::CalcInterest));
+            }
+            result += amount.amount() / growth;
+        }
+
+        Ok(result)
+    }
+
+    /// Converts a nominal annual rate into an effective annual rate (EFFECT).
+    ///
+    /// # Arguments
+    ///
+    /// * `nominal_rate` - Nominal annual rate.
+    /// * `periods_in_year` - Compounding periods per year.
+    ///
+    /// # Return
+    ///
+    /// * The effective annual rate if successful, otherwise error code.
+    pub fn effect(nominal_rate: Decimal, periods_in_year: usize) -> Result<Decimal, ElemError> {
+        if periods_in_year == 0 || nominal_rate <= dec!(0.0) {
+            return Err(ElemError::new(crate::ErrorType::CalcInterest));
+        }
+
+        let periods = Decimal::from(periods_in_year);
+        match CoreFinancial::growth(nominal_rate / periods, periods) {
+            None => Err(ElemError::new(crate::ErrorType::CalcInterest)),
+            Some(o) => Ok(o - dec!(1.0)),
+        }
+    }
+
+    /// Converts an effective annual rate into a nominal annual rate (NOMINAL).
+    ///
+    /// # Arguments
+    ///
+    /// * `effect_rate` - Effective annual rate.
+    /// * `periods_in_year` - Compounding periods per year.
+    ///
+    /// # Return
+    ///
+    /// * The nominal annual rate if successful, otherwise error code.
+    pub fn nominal(effect_rate: Decimal, periods_in_year: usize) -> Result<Decimal, ElemError> {
+        if periods_in_year == 0 || effect_rate <= dec!(0.0) {
+            return Err(ElemError::new(crate::ErrorType::CalcInterest));
+        }
+
+        let periods = Decimal::from(periods_in_year);
+        let exponent = dec!(1.0) / periods;
+        if exponent.fract() == dec!(0.0) {
+            return Ok(effect_rate * periods); // One period per year
+        }
+
+        match CoreFinancial::growth(effect_rate, exponent) {
+            None => Err(ElemError::new(crate::ErrorType::CalcInterest)),
+            Some(o) => Ok((o - dec!(1.0)) * periods),
+        }
+    }
+}
@@ -0,0 +1,478 @@
+//! The internal rate of return methods.
+// Copyright (c) 2021 ShiftLeft Software
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use chrono::NaiveDate;
+use rust_decimal::prelude::*;
+
+use crate::core::{
+    CoreUtility, ElemDatedAmount, ElemError, ElemIrrResult, ExtensionValue, ListAmortization,
+};
+use crate::{ExtensionTrait, ListTrait};
+
+pub struct CoreIrr {}
+
+/// The internal rate of return methods implementation.
+/// Roots are bracketed by scanning the net present value over
+/// the periodic rates between -99% and MAX_CALC_IRR and each
+/// bracket is refined with Brent's method. Should no bracket be
+/// found (i.e., a root that touches but does not cross zero),
+/// Newton's method is tried from a 10% initial guess.
+impl CoreIrr {
+    /// Create the series of dated amounts of an amortization list.
+    /// Principal decreases are inflows and principal increases are
+    /// outflows (auxiliary principal changes are excluded); any
+    /// remaining balance is treated as received on the final date.
+    ///
+    /// # Arguments
+    ///
+    /// * `list_am` - Amortization list (i.e., of a balanced cashflow).
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn amortization_amounts(list_am: &ListAmortization) -> Vec<ElemDatedAmount> {
+        let mut amounts: Vec<ElemDatedAmount> = Vec::new();
+        let mut final_date = NaiveDate::MIN;
+        let mut final_balance = dec!(0.0);
+        let orig_index = list_am.index();
+
+        let mut index: usize = 0;
+        while list_am.get_element(index) {
+            let elem_am = list_am.elem_amortization();
+            final_date = elem_am.event_date();
+            final_balance = elem_am.balance();
+
+            if let ExtensionValue::PrincipalChange(o) = elem_am.elem_extension().extension_value() {
+                let amount = elem_am.principal_decrease() - elem_am.principal_increase();
+                if !o.auxiliary() && amount != dec!(0.0) {
+                    amounts.push(ElemDatedAmount::new(elem_am.event_date(), amount));
+                }
+            }
+
+            index += 1;
+        }
+
+        list_am.get_element(orig_index);
+
+        if final_balance != dec!(0.0) {
+            amounts.push(ElemDatedAmount::new(final_date, final_balance));
+        }
+
+        amounts
+    }
+
+    /// Calculates the internal rate of return of a series of dated
+    /// amounts. Time is measured in whole periods of the frequency
+    /// from the earliest date plus the odd days of the final partial
+    /// period, so regular schedules solve exactly for the periodic rate.
+    ///
+    /// # Arguments
+    ///
+    /// * `amounts` - Series of dated amounts (in any order).
+    /// * `frequency` - Frequency of the periodic rate.
+    /// * `days_in_year` - Number of days in the year.
+    /// * `tolerance` - Rate tolerance (e.g., 0.0000000001).
+    ///
+    /// # Return
+    ///
+    /// * The results from this method or an error code.
+    pub fn irr(
+        amounts: &[ElemDatedAmount],
+        frequency: crate::FrequencyType,
+        days_in_year: usize,
+        tolerance: Decimal,
+    ) -> Result<ElemIrrResult, ElemError> {
+        if frequency == crate::FrequencyType::None || amounts.is_empty() {
+            return Err(ElemError::new(crate::ErrorType::CalcInterest));
+        }
+
+        let mut sorted = amounts.to_vec();
+        sorted.sort_by_key(|o| o.date());
+
+        let first_date = sorted[0].date();
+        let days_in_frequency = CoreUtility::days_in_frequency(frequency, days_in_year) as f64;
+
+        let times: Vec<f64> = sorted
+            .iter()
+            .map(|o| {
+                let periods = CoreUtility::date_diff(first_date, o.date(), frequency, 1, false);
+                let period_date = CoreUtility::date_new(
+                    first_date,
+                    first_date,
+                    frequency,
+                    periods as usize,
+                    false,
+                );
+
+                (periods as f64)
+                    + ((CoreUtility::date_to_serial(o.date()) as f64)
+                        - (CoreUtility::date_to_serial(period_date) as f64))
+                        / days_in_frequency
+            })
+            .collect();
+
+        let (periodic_rate, roots, sign_changes, iterations) =
+            CoreIrr::solve(&sorted, &times, tolerance)?;
+
+        let nominal_rate = CoreUtility::rate_pr_to_nar(periodic_rate, frequency, days_in_year);
+        let effective_rate = CoreUtility::rate_nar_to_ear(nominal_rate, frequency, days_in_year);
+
+        Ok(ElemIrrResult::new(
+            nominal_rate,
+            effective_rate,
+            periodic_rate,
+            frequency,
+            roots,
+            sign_changes,
+            iterations,
+        ))
+    }
+
+    /// Calculates the XIRR-style yield of a series of irregularly
+    /// dated amounts. Time is measured in actual days from the
+    /// earliest date divided by the days in the year, so the rate
+    /// found is an effective annual rate (the nominal, effective and
+    /// periodic rates of the result are therefore equal).
+    ///
+    /// # Arguments
+    ///
+    /// * `amounts` - Series of dated amounts (in any order).
+    /// * `days_in_year` - Number of days in the year (e.g., 365).
+    /// * `tolerance` - Rate tolerance (e.g., 0.0000000001).
+    ///
+    /// # Return
+    ///
+    /// * The results from this method or an error code.
+    pub fn xirr(
+        amounts: &[ElemDatedAmount],
+        days_in_year: usize,
+        tolerance: Decimal,
+    ) -> Result<ElemIrrResult, ElemError> {
+        if days_in_year == 0 || amounts.is_empty() {
+            return Err(ElemError::new(crate::ErrorType::CalcInterest));
+        }
+
+        let mut sorted = amounts.to_vec();
+        sorted.sort_by_key(|o| o.date());
+
+        let first_serial = CoreUtility::date_to_serial(sorted[0].date()) as f64;
+
+        let times: Vec<f64> = sorted
+            .iter()
+            .map(|o| {
+                ((CoreUtility::date_to_serial(o.date()) as f64) - first_serial)
+                    / (days_in_year as f64)
+            })
+            .collect();
+
+        let (rate, roots, sign_changes, iterations) = CoreIrr::solve(&sorted, &times, tolerance)?;
+
+        Ok(ElemIrrResult::new(
+            rate,
+            rate,
+            rate,
+            crate::FrequencyType::OneYear,
+            roots,
+            sign_changes,
+            iterations,
+        ))
+    }
+
+    /// Returns the net present value of amounts at a periodic rate.
+    ///
+    /// # Arguments
+    ///
+    /// * `values` - Amounts.
+    /// * `times` - Time of each amount in periods.
+    /// * `rate` - Periodic rate.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn npv(values: &[f64], times: &[f64], rate: f64) -> f64 {
+        values
+            .iter()
+            .zip(times.iter())
+            .map(|(value, time)| value * (1.0 + rate).powf(-time))
+            .sum()
+    }
+
+    /// Returns the derivative of the net present value with
+    /// respect to the periodic rate.
+    ///
+    /// # Arguments
+    ///
+    /// * `values` - Amounts.
+    /// * `times` - Time of each amount in periods.
+    /// * `rate` - Periodic rate.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn npv_derivative(values: &[f64], times: &[f64], rate: f64) -> f64 {
+        values
+            .iter()
+            .zip(times.iter())
+            .map(|(value, time)| -time * value * (1.0 + rate).powf(-time - 1.0))
+            .sum()
+    }
+
+    /// Find every periodic rate that zeroes the net present value
+    /// and select the root closest to zero.
+    ///
+    /// # Arguments
+    ///
+    /// * `amounts` - Series of dated amounts (sorted by date).
+    /// * `times` - Time of each amount in periods.
+    /// * `tolerance` - Rate tolerance.
+    ///
+    /// # Return
+    ///
+    /// * The selected rate, every root, the number of sign
+    ///   changes and the iterations used, or an error code.
+    fn solve(
+        amounts: &[ElemDatedAmount],
+        times: &[f64],
+        tolerance: Decimal,
+    ) -> Result<(Decimal, Vec<Decimal>, usize, usize), ElemError> {
+        let values: Vec<f64> = amounts
+            .iter()
+            .map(|o| o.amount().to_f64().unwrap_or(0.0))
+            .collect();
+        let tol = tolerance.to_f64().unwrap_or(0.0).abs().max(f64::EPSILON);
+
+        let mut sign_changes: usize = 0;
+        let mut last_sign: f64 = 0.0;
+        for value in values.iter().filter(|o| **o != 0.0) {
+            if last_sign != 0.0 && value.signum() != last_sign {
+                sign_changes += 1;
+            }
+            last_sign = value.signum();
+        }
+
+        if sign_changes == 0 {
+            return Err(ElemError::new(crate::ErrorType::CalcInterest));
+        }
+
+        let max_rate = CoreUtility::parse_decimal(crate::MAX_CALC_IRR)
+            .to_f64()
+            .unwrap_or(10.0);
+        let low = (0.01_f64).ln();
+        let high = (1.0 + max_rate).ln();
+        let steps: usize = 400;
+
+        let mut iterations: usize = 0;
+        let mut roots: Vec<f64> = Vec::new();
+
+        let mut prev_rate = low.exp() - 1.0;
+        let mut prev_npv = CoreIrr::npv(&values, times, prev_rate);
+
+        for step in 1..=steps {
+            let rate = (low + (high - low) * (step as f64) / (steps as f64)).exp() - 1.0;
+            let npv = CoreIrr::npv(&values, times, rate);
+
+            if npv == 0.0 {
+                roots.push(rate);
+            } else if npv.is_finite()
+                && prev_npv.is_finite()
+                && prev_npv != 0.0
+                && npv.signum() != prev_npv.signum()
+            {
+                if let Some(o) =
+                    CoreIrr::brent(&values, times, prev_rate, rate, tol, &mut iterations)
+                {
+                    roots.push(o);
+                }
+            }
+
+            prev_rate = rate;
+            prev_npv = npv;
+        }
+
+        if roots.is_empty() {
+            let scale: f64 = values.iter().map(|o| o.abs()).sum();
+            if let Some(o) = CoreIrr::newton(&values, times, 0.1, tol, &mut iterations) {
+                if CoreIrr::npv(&values, times, o).abs() <= tol * scale {
+                    roots.push(o);
+                }
+            }
+        }
+
+        roots.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        roots.dedup_by(|a, b| (*a - *b).abs() <= tol * 10.0);
+
+        let selected = match roots.iter().min_by(|a, b| {
+            a.abs()
+                .partial_cmp(&b.abs())
+                .unwrap_or(std::cmp::Ordering::Equal)
+        }) {
+            None => return Err(ElemError::new(crate::ErrorType::CalcInterest)),
+            Some(o) => *o,
+        };
+
+        let decimal_digits = tolerance.scale();
+        let to_decimal = |rate: f64| -> Decimal {
+            Decimal::from_f64(rate)
+                .unwrap_or(dec!(0.0))
+                .round_dp(decimal_digits)
+        };
+
+        Ok((
+            to_decimal(selected),
+            roots.iter().map(|o| to_decimal(*o)).collect(),
+            sign_changes,
+            iterations,
+        ))
+    }
+
+    /// Find a root within a bracket using Brent's method.
+    ///
+    /// # Arguments
+    ///
+    /// * `values` - Amounts.
+    /// * `times` - Time of each amount in periods.
+    /// * `low` - Low end of the bracket.
+    /// * `high` - High end of the bracket.
+    /// * `tolerance` - Rate tolerance.
+    /// * `iterations` - Total iterations (updated).
+    ///
+    /// # Return
+    ///
+    /// * The root if found.
+    fn brent(
+        values: &[f64],
+        times: &[f64],
+        low: f64,
+        high: f64,
+        tolerance: f64,
+        iterations: &mut usize,
+    ) -> Option<f64> {
+        let mut a = low;
+        let mut b = high;
+        let mut fa = CoreIrr::npv(values, times, a);
+        let mut fb = CoreIrr::npv(values, times, b);
+        if fa * fb > 0.0 {
+            return None;
+        }
+
+        let mut c = b;
+        let mut fc = fb;
+        let mut d = b - a;
+        let mut e = d;
+
+        for _ in 0..crate::MAXIMUM_ITERATIONS_CALCULATE_IRR {
+            *iterations += 1;
+
+            if fb * fc > 0.0 {
+                c = a;
+                fc = fa;
+                d = b - a;
+                e = d;
+            }
+
+            if fc.abs() < fb.abs() {
+                a = b;
+                b = c;
+                c = a;
+                fa = fb;
+                fb = fc;
+                fc = fa;
+            }
+
+            let tol = 2.0 * f64::EPSILON * b.abs() + 0.5 * tolerance;
+            let xm = 0.5 * (c - b);
+            if xm.abs() <= tol || fb == 0.0 {
+                return Some(b);
+            }
+
+            if e.abs() >= tol && fa.abs() > fb.abs() {
+                let s = fb / fa;
+                let mut p: f64;
+                let mut q: f64;
+                if a == c {
+                    p = 2.0 * xm * s; // Secant
+                    q = 1.0 - s;
+                } else {
+                    let qa = fa / fc; // Inverse quadratic interpolation
+                    let r = fb / fc;
+                    p = s * (2.0 * xm * qa * (qa - r) - (b - a) * (r - 1.0));
+                    q = (qa - 1.0) * (r - 1.0) * (s - 1.0);
+                }
+                if p > 0.0 {
+                    q = -q;
+                }
+                p = p.abs();
+
+                if 2.0 * p < (3.0 * xm * q - (tol * q).abs()).min((e * q).abs()) {
+                    e = d;
+                    d = p / q;
+                } else {
+                    d = xm; // Bisection
+                    e = d;
+                }
+            } else {
+                d = xm; // Bisection
+                e = d;
+            }
+
+            a = b;
+            fa = fb;
+            b += if d.abs() > tol { d } else { tol.copysign(xm) };
+            fb = CoreIrr::npv(values, times, b);
+        }
+
+        None
+    }
+
+    /// Find a root from an initial guess using Newton's method.
+    ///
+    /// # Arguments
+    ///
+    /// * `values` - Amounts.
+    /// * `times` - Time of each amount in periods.
+    /// * `guess` - Initial periodic rate.
+    /// * `tolerance` - Rate tolerance.
+    /// * `iterations` - Total iterations (updated).
+    ///
+    /// # Return
+    ///
+    /// * The root if found.
+    fn newton(
+        values: &[f64],
+        times: &[f64],
+        guess: f64,
+        tolerance: f64,
+        iterations: &mut usize,
+    ) -> Option<f64> {
+        let mut rate = guess;
+
+        for _ in 0..crate::MAXIMUM_ITERATIONS_CALCULATE_IRR {
+            *iterations += 1;
+
+            let npv = CoreIrr::npv(values, times, rate);
+            let derivative = CoreIrr::npv_derivative(values, times, rate);
+            if !npv.is_finite() || !derivative.is_finite() || derivative == 0.0 {
+                return None;
+            }
+
+            let next = rate - npv / derivative;
+            if next <= -1.0 {
+                return None;
+            }
+
+            if (next - rate).abs() <= tolerance {
+                return Some(next);
+            }
+
+            rate = next;
+        }
+
+        None
+    }
+}
@@ -214,6 +214,42 @@ impl CoreManager {
             0,
         );
 
+        mgr.map_day_count_basis.add_key(
+            "Day_Count_Basis_Actual_360",
+            crate::DayCountType::Actual360 as usize,
+            0,
+        );
+
+        mgr.map_day_count_basis.add_key(
+            "Day_Count_Basis_Actual_365F",
+            crate::DayCountType::Actual365F as usize,
+            0,
+        );
+
+        mgr.map_day_count_basis.add_key(
+            "Day_Count_Basis_Actual_364",
+            crate::DayCountType::Actual364 as usize,
+            0,
+        );
+
+        mgr.map_day_count_basis.add_key(
+            "Day_Count_Basis_30_ISDA",
+            crate::DayCountType::Dc30ISDA as usize,
+            0,
+        );
+
+        mgr.map_day_count_basis.add_key(
+            "Day_Count_Basis_30E_ISDA",
+            crate::DayCountType::Dc30EISDA as usize,
+            0,
+        );
+
+        mgr.map_day_count_basis.add_key(
+            "Day_Count_Basis_Business_252",
+            crate::DayCountType::Business252 as usize,
+            0,
+        );
+
         mgr.map_frequency.add_key(
             "Frequency_1_Year",
             crate::FrequencyType::OneYear as usize,
@@ -351,33 +387,93 @@ impl CoreManager {
             0,
         );
 
+        mgr.functions.add_key(
+            "amlist(\"column\", start-date, end-date, \"event-type\")",
+            crate::FunctionType::AmList as usize,
+            0,
+        );
+
+        mgr.functions
+            .add_key("avg(list)", crate::FunctionType::Avg as usize, 0);
+
         mgr.functions.add_key(
             "cashflow(\"option\")",
             crate::FunctionType::Cashflow as usize,
             0,
         );
 
+        mgr.functions
+            .add_key("count(list)", crate::FunctionType::Count as usize, 0);
+
+        mgr.functions.add_key(
+            "dateaddmonths(date, months, eom)",
+            crate::FunctionType::DateAddMonths as usize,
+            0,
+        );
+
+        mgr.functions
+            .add_key("dateday(date)", crate::FunctionType::DateDay as usize, 0);
+
+        mgr.functions.add_key(
+            "datedayofyear(date)",
+            crate::FunctionType::DateDayOfYear as usize,
+            0,
+        );
+
         mgr.functions.add_key(
             "datediff(date1, date2, \"frequency\", crate::intervals, eom)",
             crate::FunctionType::DateDiff as usize,
             0,
         );
 
+        mgr.functions.add_key(
+            "dateendofmonth(date)",
+            crate::FunctionType::DateEndOfMonth as usize,
+            0,
+        );
+
         mgr.functions.add_key(
             "datefiscal(date, \"frequency\", crate::intervals, adjust)",
             crate::FunctionType::DateFiscal as usize,
             0,
         );
 
+        mgr.functions.add_key(
+            "dateisleapyear(date)",
+            crate::FunctionType::DateIsLeapYear as usize,
+            0,
+        );
+
+        mgr.functions.add_key(
+            "datemonth(date)",
+            crate::FunctionType::DateMonth as usize,
+            0,
+        );
+
         mgr.functions.add_key(
             "datenew(date, periods, \"frequency\", crate::intervals, eom)",
             crate::FunctionType::DateNew as usize,
             0,
         );
 
+        mgr.functions.add_key(
+            "datenextbusinessday(date, \"calendar\")",
+            crate::FunctionType::DateNextBusinessDay as usize,
+            0,
+        );
+
         mgr.functions
             .add_key("datenow()", crate::FunctionType::DateNow as usize, 0);
 
+        mgr.functions.add_key(
+            "dateweekday(date)",
+            crate::FunctionType::DateWeekday as usize,
+            0,
+        );
+
+        mgr.functions
+            .add_key("dateyear(date)", crate::FunctionType::DateYear as usize, 0);
+
         mgr.functions.add_key(
             "default(variable, value)",
             crate::FunctionType::Default as usize,
@@ -393,6 +489,21 @@ impl CoreManager {
         mgr.functions
             .add_key("decimal(value)", crate::FunctionType::Decimal as usize, 0);
 
+        mgr.functions.add_key(
+            "effect(nominal-rate, periods-per-year)",
+            crate::FunctionType::Effect as usize,
+            0,
+        );
+
+        mgr.functions.add_key(
+            "filter(list, variable, condition)",
+            crate::FunctionType::Filter as usize,
+            0,
+        );
+
+        mgr.functions
+            .add_key("first(list)", crate::FunctionType::First as usize, 0);
+
         mgr.functions
             .add_key("format(value)", crate::FunctionType::Format as usize, 0);
 
@@ -414,6 +525,12 @@ impl CoreManager {
             0,
         );
 
+        mgr.functions.add_key(
+            "fv(rate, nper, pmt, pv, type)",
+            crate::FunctionType::Fv as usize,
+            0,
+        );
+
         mgr.functions.add_key(
             "if(condition, result-if-true, result-if-false)",
             crate::FunctionType::If as usize,
@@ -423,9 +540,24 @@ impl CoreManager {
         mgr.functions
             .add_key("integer(value)", crate::FunctionType::Integer as usize, 0);
 
+        mgr.functions.add_key(
+            "ipmt(rate, per, nper, pv, fv, type)",
+            crate::FunctionType::Ipmt as usize,
+            0,
+        );
+
+        mgr.functions
+            .add_key("last(list)", crate::FunctionType::Last as usize, 0);
+
         mgr.functions
             .add_key("len(\"string\")", crate::FunctionType::Len as usize, 0);
 
+        mgr.functions.add_key(
+            "list(value1, value2)",
+            crate::FunctionType::List as usize,
+            0,
+        );
+
         mgr.functions.add_key(
             "lowercase(\"string\")",
             crate::FunctionType::Lowercase as usize,
@@ -450,15 +582,57 @@ impl CoreManager {
             0,
         );
 
+        mgr.functions.add_key(
+            "nominal(effect-rate, periods-per-year)",
+            crate::FunctionType::Nominal as usize,
+            0,
+        );
+
+        mgr.functions.add_key(
+            "nper(rate, pmt, pv, fv, type)",
+            crate::FunctionType::Nper as usize,
+            0,
+        );
+
+        mgr.functions.add_key(
+            "npv(rate, value1, value2)",
+            crate::FunctionType::Npv as usize,
+            0,
+        );
+
         mgr.functions.add_key(
             "parse(\"string\", crate::\"delimiters\", crate::name)",
             crate::FunctionType::Parse as usize,
             0,
         );
 
+        mgr.functions.add_key(
+            "pmt(rate, nper, pv, fv, type)",
+            crate::FunctionType::Pmt as usize,
+            0,
+        );
+
+        mgr.functions.add_key(
+            "ppmt(rate, per, nper, pv, fv, type)",
+            crate::FunctionType::Ppmt as usize,
+            0,
+        );
+
         mgr.functions
             .add_key("pr(number)", crate::FunctionType::Pr as usize, 0);
 
+        mgr.functions.add_key(
+            "pv(rate, nper, pmt, fv, type)",
+            crate::FunctionType::Pv as usize,
+            0,
+        );
+
+        mgr.functions.add_key(
+            "rate(nper, pmt, pv, fv, type, guess)",
+            crate::FunctionType::Rate as usize,
+            0,
+        );
+
         mgr.functions.add_key(
             "replace(\"string\", crate::\"character-from\", crate::\"character-to\")",
             crate::FunctionType::Replace as usize,
@@ -477,6 +651,9 @@ impl CoreManager {
         mgr.functions
             .add_key("set(variable, value)", crate::FunctionType::Set as usize, 0);
 
+        mgr.functions
+            .add_key("sum(list)", crate::FunctionType::Sum as usize, 0);
+
         mgr.functions
             .add_key("trim(\"string\")", crate::FunctionType::Trim as usize, 0);
 
@@ -489,6 +666,12 @@ impl CoreManager {
             0,
         );
 
+        mgr.functions.add_key(
+            "xnpv(rate, values, dates)",
+            crate::FunctionType::Xnpv as usize,
+            0,
+        );
+
         mgr.map_error
             .add_key("Error_Left_Paren", crate::ErrorType::LeftParen as usize, 0);
 
@@ -600,6 +783,24 @@ impl CoreManager {
         mgr.map_error
             .add_key("Error_Json", crate::ErrorType::Json as usize, 0);
 
+        mgr.map_error
+            .add_key("Error_Calendar", crate::ErrorType::Calendar as usize, 0);
+
+        mgr.map_error
+            .add_key("Error_Recursion", crate::ErrorType::Recursion as usize, 0);
+
+        mgr.map_error
+            .add_key("Error_List", crate::ErrorType::List as usize, 0);
+
+        mgr.map_error
+            .add_key("Error_Format", crate::ErrorType::Format as usize, 0);
+
+        mgr.map_error
+            .add_key("Error_Limit", crate::ErrorType::Limit as usize, 0);
+
+        mgr.map_error
+            .add_key("Error_RateIndex", crate::ErrorType::RateIndex as usize, 0);
+
         mgr.map_col_names
             .add_key("Sequence", crate::ColumnType::Sequence as usize, 0);
 
@@ -716,6 +917,12 @@ impl CoreManager {
         mgr.map_col_names
             .add_key("Balance", crate::ColumnType::Balance as usize, 0);
 
+        mgr.map_col_names.add_key(
+            "Deferred-interest",
+            crate::ColumnType::DeferredInterest as usize,
+            0,
+        );
+
         mgr.map_col_names.add_key(
             "Event-name",
             crate::ColumnType::EventName as usize,
@@ -7,12 +7,10 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-use chrono::{DateTime, Datelike, Local};
-use math::round;
+use chrono::{DateTime, Datelike, Local, NaiveDate};
 use rust_decimal::prelude::*;
 
-use crate::core::ListDescriptor;
-use crate::ListTrait;
+use crate::core::{ElemCalendar, ListDescriptor};
 
 pub struct CoreUtility {}
 
@@ -45,8 +43,8 @@ impl CoreUtility {
     ///
     /// # Arguments
     ///
-    /// * `date1` - First date in YYYYMMDD format.
-    /// * `date2` - Second date in YYYYMMDD format.
+    /// * `date1` - First date.
+    /// * `date2` - Second date.
     /// * `frequency` - Date frequency.
     /// * `intervals` - Number of intervals of frequency.
     /// * `eom_param` - Adjust successive dates to end of month.
@@ -56,8 +54,8 @@ impl CoreUtility {
     /// * Number of intervals (positive or negative).
 
     pub fn date_diff(
-        mut date1: usize,
-        mut date2: usize,
+        mut date1: NaiveDate,
+        mut date2: NaiveDate,
         frequency: crate::FrequencyType,
         intervals: usize,
         eom_param: bool,
@@ -84,13 +82,13 @@ impl CoreUtility {
                 * sign;
         }
 
-        let year1 = (date1 as i32) / 10000;
-        let month1 = (date1 as i32) % 10000 / 100;
-        let day1 = (date1 as i32) % 100;
+        let year1 = date1.year();
+        let month1 = date1.month() as i32;
+        let day1 = date1.day() as i32;
 
-        let year2 = (date2 as i32) / 10000;
-        let month2 = (date2 as i32) % 10000 / 100;
-        let mut day2 = (date2 as i32) % 100;
+        let year2 = date2.year();
+        let month2 = date2.month() as i32;
+        let mut day2 = date2.day() as i32;
 
         interval_count = (year2 * 12 - year1 * 12) + (month2 - month1);
         let eom = if frequency == crate::FrequencyType::HalfMonth {
@@ -128,29 +126,28 @@ impl CoreUtility {
     ///
     /// # Arguments
     ///
-    /// * `orig_date` - Optional original date in YYYYMMDD format,
-    ///     otherwise zero. Used for the half-month (semi-monthly) frequency
+    /// * `orig_date` - Original date (may be the same as date).
+    ///   Used for the half-month (semi-monthly) frequency
     ///     and when bolEOM is true.
-    /// * `date` - Date in YYYYMMDD format.
+    /// * `date` - Date.
     /// * `frequency` - Date frequency.
     /// * `intervals` - Number of intervals of frequency.
     /// * `eom_param` - Adjust successive dates to end of month.
     ///
     /// # Return
     ///
-    /// * New date in YYYYMMDD format.
-
+    /// * New date.
     pub fn date_newi(
-        orig_date: usize,
-        date: usize,
+        orig_date: NaiveDate,
+        date: NaiveDate,
         frequency: crate::FrequencyType,
         intervals: i32,
         eom_param: bool,
-    ) -> usize {
-        let mut year = (date / 10000) as i32;
-        let mut month = (date % 10000 / 100) as i32;
-        let mut day = date % 100;
-        let mut orig_day = orig_date % 100;
+    ) -> NaiveDate {
+        let mut year = date.year();
+        let mut month = date.month() as i32;
+        let mut day = date.day() as usize;
+        let mut orig_day = orig_date.day() as usize;
         if intervals == 0 {
             return date;
         }
@@ -164,10 +161,6 @@ impl CoreUtility {
             return CoreUtility::serial_to_date(new_serial as usize);
         }
 
-        if orig_day == 0 {
-            orig_day = day;
-        }
-
         match frequency {
             crate::FrequencyType::OneYear => {
                 month += intervals * 12;
@@ -251,7 +244,11 @@ impl CoreUtility {
             let eom = if frequency == crate::FrequencyType::HalfMonth {
                 orig_day >= 28 && eom_param
             } else {
-                orig_day == CoreUtility::days_in_month(orig_date / 10000, orig_date % 10000 / 100)
+                orig_day
+                    == CoreUtility::days_in_month(
+                        orig_date.year() as usize,
+                        orig_date.month() as usize,
+                    )
             };
 
             if eom {
@@ -264,69 +261,110 @@ impl CoreUtility {
             }
         }
 
-        ((year * 10000) as usize) + ((month * 100) as usize) + day
+        match CoreUtility::date_from_ymd(year as usize, month as usize, day) {
+            None if intervals < 0 => NaiveDate::MIN,
+            None => NaiveDate::MAX,
+            Some(o) => o,
+        }
     }
 
     /// Calculates a new date based upon a given date and number of intervals.
     ///
     /// # Arguments
     ///
-    /// * `orig_date` - Optional original date in YYYYMMDD format,
-    ///     otherwise zero. Used for the half-month (semi-monthly) frequency
+    /// * `orig_date` - Original date (may be the same as date).
+    ///   Used for the half-month (semi-monthly) frequency
     ///     and when bolEOM is true.
-    /// * `date` - Date in YYYYMMDD format.
+    /// * `date` - Date.
     /// * `frequency` - Date frequency.
     /// * `intervals` - Number of intervals of frequency.
     /// * `eom_param` - Adjust successive dates to end of month.
     ///
     /// # Return
     ///
-    /// * New date in YYYYMMDD format.
-
+    /// * New date.
     pub fn date_new(
-        orig_date: usize,
-        date: usize,
+        orig_date: NaiveDate,
+        date: NaiveDate,
         frequency: crate::FrequencyType,
         intervals: usize,
         eom_param: bool,
-    ) -> usize {
+    ) -> NaiveDate {
         CoreUtility::date_newi(orig_date, date, frequency, intervals as i32, eom_param)
     }
 
-    /// Returns the current date in YYYYMMDD format.
+    /// Create a date from a year, month and day.
+    ///
+    /// # Arguments
+    ///
+    /// * `year` - The year in YYYY format.
+    /// * `month` - The month in MM format.
+    /// * `day` - The day in DD format.
     ///
     /// # Return
     ///
-    /// * Current date in YYYYMMDD format.
+    /// * The date or None if the year, month and day are not a valid date.
+    pub fn date_from_ymd(year: usize, month: usize, day: usize) -> Option<NaiveDate> {
+        if year > i32::MAX as usize || month > 12 || day > 31 {
+            return None;
+        }
 
-    pub fn date_now() -> usize {
+        NaiveDate::from_ymd_opt(year as i32, month as u32, day as u32)
+    }
+
+    /// Indicates if a date can be converted to a serial number
+    /// (i.e., is on or after January 1, SERIAL_BASE_YEAR).
+    ///
+    /// # Arguments
+    ///
+    /// * `date` - The date.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn date_in_range(date: NaiveDate) -> bool {
+        date.year() >= crate::SERIAL_BASE_YEAR as i32
+    }
+
+    /// Returns the current date.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn date_now() -> NaiveDate {
         let dt: DateTime<Local> = Local::now();
-        (dt.year() as usize) * 10000 + (dt.month() as usize) * 100 + (dt.day() as usize)
+        dt.date_naive()
     }
 
-    /// Convert a date to a serial number.
+    /// Convert a date to an integer in YYYYMMDD format.
     ///
     /// # Arguments
     ///
-    /// * `intDate` - Date in YYYYMMDD format.
+    /// * `date` - The date.
     ///
     /// # Return
     ///
-    /// * Number of days since January 1, SERIAL_BASE_YEAR.
+    /// * See description.
+    pub fn date_to_integer(date: NaiveDate) -> usize {
+        (date.year() as usize) * 10000 + (date.month() as usize) * 100 + (date.day() as usize)
+    }
 
-    pub fn date_to_serial(date: usize) -> usize {
-        let year = date / 10000;
-        let month = date % 10000 / 100;
-        let day = date % 100;
+    /// Convert a date to a serial number. Dates before
+    /// January 1, SERIAL_BASE_YEAR are rejected when they enter
+    /// the engine (see date_in_range) and convert to zero.
+    ///
+    /// # Arguments
+    ///
+    /// * `date` - The date.
+    ///
+    /// # Return
+    ///
+    /// * Number of days since January 1, SERIAL_BASE_YEAR.
 
-        let val: usize = if month > 2 {
-            93 - CoreUtility::leap_year(year)
-        } else {
-            91
-        };
+    pub fn date_to_serial(date: NaiveDate) -> usize {
+        let serial = date.num_days_from_ce() - CoreUtility::serial_base();
 
-        (round::ceil(((year - crate::SERIAL_BASE_YEAR) as f64) * 365.25, 0) as usize)
-            + (3055 * (month + 2) / 100 - val + day)
+        serial.max(0) as usize
     }
 
     /// Calculates the day count factor between two dates.
@@ -350,10 +388,61 @@ impl CoreUtility {
         day_count_basis: crate::DayCountType,
         days_in_year_param: usize,
         periods_in_year: usize,
+    ) -> Decimal {
+        CoreUtility::day_count_factor_with_calendar(
+            serial1,
+            serial2,
+            day_count_basis,
+            days_in_year_param,
+            periods_in_year,
+            &ElemCalendar::new(""),
+        )
+    }
+
+    /// Calculates the day count factor between two dates using
+    /// a business day calendar for business day count bases.
+    /// (date2 - date1 must be less than or equal to 1 year.)
+    ///
+    /// # Arguments
+    ///
+    /// * `serial1` - First serial date.
+    /// * `serial2` - Second serial date.
+    /// * `day_count_basis` - Day count basis.
+    /// * `days_in_year_param` - Number of days in the year.
+    /// * `periods_in_year` - Number of periods in the year.
+    /// * `calendar` - Business day calendar.
+    ///
+    /// # Return
+    ///
+    /// * Day count factor.
+    pub fn day_count_factor_with_calendar(
+        serial1: usize,
+        serial2: usize,
+        day_count_basis: crate::DayCountType,
+        days_in_year_param: usize,
+        periods_in_year: usize,
+        calendar: &ElemCalendar,
     ) -> Decimal {
         let mut days_in_year = days_in_year_param;
         let mut days: usize;
 
+        match day_count_basis {
+            crate::DayCountType::Actual360 => {
+                return dec!(serial2 - serial1) / dec!(360);
+            }
+            crate::DayCountType::Actual365F => {
+                return dec!(serial2 - serial1) / dec!(365);
+            }
+            crate::DayCountType::Actual364 => {
+                return dec!(serial2 - serial1) / dec!(364);
+            }
+            crate::DayCountType::Business252 => {
+                days = CoreUtility::business_days(serial1, serial2, calendar);
+                return dec!(days) / dec!(252);
+            }
+            _ => {}
+        }
+
         if day_count_basis == crate::DayCountType::Periodic
             || day_count_basis == crate::DayCountType::RuleOf78
             || day_count_basis == crate::DayCountType::Actual
@@ -367,14 +456,14 @@ impl CoreUtility {
             return dec!(days) / dec!(days_in_year);
         }
         let date1 = CoreUtility::serial_to_date(serial1);
-        let year1 = (date1 / 10000) as i32;
-        let month1 = (date1 % 10000 / 100) as i32;
-        let mut day1 = (date1 % 100) as i32;
+        let year1 = date1.year();
+        let month1 = date1.month() as i32;
+        let mut day1 = date1.day() as i32;
 
         let date2 = CoreUtility::serial_to_date(serial2);
-        let mut year2 = (date2 / 10000) as i32;
-        let mut month2 = (date2 % 10000 / 100) as i32;
-        let mut day2 = (date2 % 100) as i32;
+        let mut year2 = date2.year();
+        let mut month2 = date2.month() as i32;
+        let mut day2 = date2.day() as i32;
 
         match day_count_basis {
             crate::DayCountType::ActualActualAFB => {
@@ -391,7 +480,12 @@ impl CoreUtility {
             }
             crate::DayCountType::Actual365L => {
                 // ISDA
-                let serial = CoreUtility::date_to_serial((year2 as usize) * 10000 + 101); // January 1
+                if year1 == year2 {
+                    days = serial2 - serial1;
+                    days_in_year = 365 + CoreUtility::leap_year(year1 as usize);
+                    return dec!(days) / dec!(days_in_year);
+                }
+                let serial = serial2 - (date2.ordinal0() as usize); // January 1
                 days = serial - serial1;
                 days_in_year = 365 + CoreUtility::leap_year(year1 as usize);
                 let mut dval = dec!(days) / dec!(days_in_year);
@@ -425,12 +519,75 @@ impl CoreUtility {
                 days = ((year2 - year1) * 360 + (month2 - month1) * 30 + (day2 - day1)) as usize;
                 return dec!(days) / dec!(days_in_year);
             }
+            crate::DayCountType::Dc30ISDA => {
+                let feb_end1 =
+                    month1 == 2 && day1 == (CoreUtility::days_in_month(year1 as usize, 2) as i32);
+                let feb_end2 =
+                    month2 == 2 && day2 == (CoreUtility::days_in_month(year2 as usize, 2) as i32);
+                if feb_end1 && feb_end2 {
+                    day2 = 30;
+                }
+                if feb_end1 {
+                    day1 = 30;
+                }
+                if day2 == 31 && day1 >= 30 {
+                    day2 = 30;
+                }
+                if day1 == 31 {
+                    day1 = 30;
+                }
+                days = ((year2 - year1) * 360 + (month2 - month1) * 30 + (day2 - day1)) as usize;
+                dec!(days) / dec!(360)
+            }
+            crate::DayCountType::Dc30EISDA => {
+                // The termination date exception for February is not applied
+                if day1 == (CoreUtility::days_in_month(year1 as usize, month1 as usize) as i32) {
+                    day1 = 30;
+                }
+                if day2 == (CoreUtility::days_in_month(year2 as usize, month2 as usize) as i32) {
+                    day2 = 30;
+                }
+                days = ((year2 - year1) * 360 + (month2 - month1) * 30 + (day2 - day1)) as usize;
+                dec!(days) / dec!(360)
+            }
             _ => {
                 return dec!(0.0);
             }
         }
     }
 
+    /// Returns the number of business days of a calendar (excluding
+    /// the weekend days and holidays of the calendar) from the first
+    /// serial date up to but not including the second serial date.
+    ///
+    /// # Arguments
+    ///
+    /// * `serial1` - First serial date.
+    /// * `serial2` - Second serial date.
+    /// * `calendar` - Business day calendar.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn business_days(serial1: usize, serial2: usize, calendar: &ElemCalendar) -> usize {
+        (serial1..serial2)
+            .filter(|o| calendar.is_business_day(CoreUtility::serial_to_date(*o)))
+            .count()
+    }
+
+    /// Returns the day of the week of a date.
+    ///
+    /// # Arguments
+    ///
+    /// * `date` - The date.
+    ///
+    /// # Return
+    ///
+    /// * Day of the week (0=Sunday through 6=Saturday).
+    pub fn day_of_week(date: NaiveDate) -> usize {
+        date.weekday().num_days_from_sunday() as usize
+    }
+
     /// Returns the number of days in a frequency.
     ///
     /// # Arguments
@@ -491,6 +648,75 @@ impl CoreUtility {
         days
     }
 
+    /// Returns the date of Easter Sunday (Western) in a year
+    /// (anonymous Gregorian algorithm).
+    ///
+    /// # Arguments
+    ///
+    /// * `year` - The year in YYYY format.
+    ///
+    /// # Return
+    ///
+    /// * The date or None if the year is not a valid year.
+    pub fn easter_sunday(year: usize) -> Option<NaiveDate> {
+        let a = year % 19;
+        let b = year / 100;
+        let c = year % 100;
+        let d = b / 4;
+        let e = b % 4;
+        let f = (b + 8) / 25;
+        let g = (b - f + 1) / 3;
+        let h = (19 * a + b - d - g + 15) % 30;
+        let i = c / 4;
+        let k = c % 4;
+        let l = (32 + 2 * e + 2 * i - h - k) % 7;
+        let m = (a + 11 * h + 22 * l) / 451;
+        let month = (h + l + 114 - 7 * m) / 31;
+        let day = (h + l + 114 - 7 * m) % 31 + 1;
+
+        CoreUtility::date_from_ymd(year, month, day)
+    }
+
+    /// Returns the constant value for a business day convention mnemonic.
+    ///
+    /// # Arguments
+    ///
+    /// * `text_param` - The business day convention mnemonic.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn get_business_day(text_param: &str) -> crate::BusinessDayType {
+        let text = String::from(text_param).to_lowercase();
+
+        match text.as_str() {
+            "following" => crate::BusinessDayType::Following,
+            "modified-following" => crate::BusinessDayType::ModifiedFollowing,
+            "preceding" => crate::BusinessDayType::Preceding,
+            "modified-preceding" => crate::BusinessDayType::ModifiedPreceding,
+            _ => crate::BusinessDayType::Unadjusted,
+        }
+    }
+
+    /// Returns the business day convention mnemonic for a constant value.
+    ///
+    /// # Arguments
+    ///
+    /// * `business_day` - The constant value.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn get_business_day_mnemonic(business_day: crate::BusinessDayType) -> String {
+        match business_day {
+            crate::BusinessDayType::Following => String::from("following"),
+            crate::BusinessDayType::ModifiedFollowing => String::from("modified-following"),
+            crate::BusinessDayType::Preceding => String::from("preceding"),
+            crate::BusinessDayType::ModifiedPreceding => String::from("modified-preceding"),
+            _ => String::from("unadjusted"),
+        }
+    }
+
     /// Returns the enumerated value for a function number.
     ///
     /// # Arguments
@@ -537,6 +763,9 @@ impl CoreUtility {
             x if x == crate::ColumnType::Balance as usize => crate::ColumnType::Balance,
             x if x == crate::ColumnType::EventName as usize => crate::ColumnType::EventName,
             x if x == crate::ColumnType::NextName as usize => crate::ColumnType::NextName,
+            x if x == crate::ColumnType::DeferredInterest as usize => {
+                crate::ColumnType::DeferredInterest
+            }
             x if x == crate::ColumnType::StrBal as usize => crate::ColumnType::StrBal,
             x if x == crate::ColumnType::Ear as usize => crate::ColumnType::Ear,
             x if x == crate::ColumnType::Pr as usize => crate::ColumnType::Pr,
@@ -584,6 +813,7 @@ impl CoreUtility {
             crate::ColumnType::Balance => String::from(crate::COL_LABEL_BALANCE),
             crate::ColumnType::EventName => String::from(crate::COL_LABEL_EVENT_NAME),
             crate::ColumnType::NextName => String::from(crate::COL_LABEL_NEXT_NAME),
+            crate::ColumnType::DeferredInterest => String::from(crate::COL_LABEL_DEFERRED_INTEREST),
             _ => String::from(crate::COL_LABEL_SEQUENCE),
         }
     }
@@ -606,10 +836,16 @@ impl CoreUtility {
             "actual" => crate::DayCountType::Actual,
             "actual-actual-isma" => crate::DayCountType::ActualActualISMA,
             "actual-actual-afb" => crate::DayCountType::ActualActualAFB,
-            "actual-365L" => crate::DayCountType::Actual365L,
+            "actual-365l" => crate::DayCountType::Actual365L,
+            "actual-360" => crate::DayCountType::Actual360,
+            "actual-365f" => crate::DayCountType::Actual365F,
+            "actual-364" => crate::DayCountType::Actual364,
             "30" => crate::DayCountType::Dc30,
-            "30E" => crate::DayCountType::Dc30E,
-            "30EP" => crate::DayCountType::Dc30EP,
+            "30e" => crate::DayCountType::Dc30E,
+            "30ep" => crate::DayCountType::Dc30EP,
+            "30-isda" => crate::DayCountType::Dc30ISDA,
+            "30e-isda" => crate::DayCountType::Dc30EISDA,
+            "business-252" => crate::DayCountType::Business252,
             _ => crate::DayCountType::Periodic,
         }
     }
@@ -631,9 +867,15 @@ impl CoreUtility {
             crate::DayCountType::ActualActualISMA => String::from("actual-actual-isma"),
             crate::DayCountType::ActualActualAFB => String::from("actual-actual-afb"),
             crate::DayCountType::Actual365L => String::from("actual-365L"),
+            crate::DayCountType::Actual360 => String::from("actual-360"),
+            crate::DayCountType::Actual365F => String::from("actual-365F"),
+            crate::DayCountType::Actual364 => String::from("actual-364"),
             crate::DayCountType::Dc30 => String::from("30"),
             crate::DayCountType::Dc30E => String::from("30E"),
             crate::DayCountType::Dc30EP => String::from("30EP"),
+            crate::DayCountType::Dc30ISDA => String::from("30-ISDA"),
+            crate::DayCountType::Dc30EISDA => String::from("30E-ISDA"),
+            crate::DayCountType::Business252 => String::from("business-252"),
             _ => String::from("periodic"),
         }
     }
@@ -655,9 +897,15 @@ impl CoreUtility {
             crate::DayCountType::ActualActualISMA => String::from("Act/ISMA"),
             crate::DayCountType::ActualActualAFB => String::from("Act/AFB"),
             crate::DayCountType::Actual365L => String::from("Act/365L"),
+            crate::DayCountType::Actual360 => String::from("Act/360"),
+            crate::DayCountType::Actual365F => String::from("Act/365F"),
+            crate::DayCountType::Actual364 => String::from("Act/364"),
             crate::DayCountType::Dc30 => String::from("30"),
             crate::DayCountType::Dc30E => String::from("30E"),
             crate::DayCountType::Dc30EP => String::from("30E+"),
+            crate::DayCountType::Dc30ISDA => String::from("30/ISDA"),
+            crate::DayCountType::Dc30EISDA => String::from("30E/ISDA"),
+            crate::DayCountType::Business252 => String::from("Bus/252"),
             _ => String::from("Per"),
         }
     }
@@ -689,47 +937,17 @@ impl CoreUtility {
         desc_type: &str,
         code: &str,
     ) -> String {
-        let mut value = String::from("");
-        let mut orig_list_index: usize;
-
-        match list_descriptor_event_opt {
-            None => {}
-            Some(o) => {
-                orig_list_index = o.index();
-                if o.get_element_by_name(group, name, desc_type, code, true) {
-                    value = o.value();
-                    o.get_element(orig_list_index);
-                }
-            }
-        }
-
-        if value.is_empty() {
-            match list_descriptor_cashflow_opt {
-                None => {}
-                Some(o) => {
-                    orig_list_index = o.index();
-                    if o.get_element_by_name(group, name, desc_type, code, true) {
-                        value = o.value();
-                        o.get_element(orig_list_index);
-                    }
-                }
-            }
-        }
-
-        if value.is_empty() {
-            match list_descriptor_user_opt {
-                None => {}
-                Some(o) => {
-                    orig_list_index = o.index();
-                    if o.get_element_by_name(group, name, desc_type, code, true) {
-                        value = o.value();
-                        o.get_element(orig_list_index);
-                    }
-                }
-            }
-        }
-
-        value
+        [
+            list_descriptor_event_opt,
+            list_descriptor_cashflow_opt,
+            list_descriptor_user_opt,
+        ]
+        .iter()
+        .flatten()
+        .filter_map(|o| o.get_descriptor(group, name, desc_type, code))
+        .map(|o| o.value())
+        .find(|o| !o.is_empty())
+        .unwrap_or_default()
     }
 
     /// Returns the constant value for an event type mnemonic.
@@ -884,14 +1102,39 @@ impl CoreUtility {
         match val {
             x if x == crate::FunctionType::Abs as usize => crate::FunctionType::Abs,
             x if x == crate::FunctionType::Am as usize => crate::FunctionType::Am,
+            x if x == crate::FunctionType::AmList as usize => crate::FunctionType::AmList,
+            x if x == crate::FunctionType::Avg as usize => crate::FunctionType::Avg,
             x if x == crate::FunctionType::Cashflow as usize => crate::FunctionType::Cashflow,
+            x if x == crate::FunctionType::Count as usize => crate::FunctionType::Count,
+            x if x == crate::FunctionType::DateAddMonths as usize => {
+                crate::FunctionType::DateAddMonths
+            }
+            x if x == crate::FunctionType::DateDay as usize => crate::FunctionType::DateDay,
+            x if x == crate::FunctionType::DateDayOfYear as usize => {
+                crate::FunctionType::DateDayOfYear
+            }
             x if x == crate::FunctionType::DateDiff as usize => crate::FunctionType::DateDiff,
+            x if x == crate::FunctionType::DateEndOfMonth as usize => {
+                crate::FunctionType::DateEndOfMonth
+            }
             x if x == crate::FunctionType::DateFiscal as usize => crate::FunctionType::DateFiscal,
+            x if x == crate::FunctionType::DateIsLeapYear as usize => {
+                crate::FunctionType::DateIsLeapYear
+            }
+            x if x == crate::FunctionType::DateMonth as usize => crate::FunctionType::DateMonth,
             x if x == crate::FunctionType::DateNew as usize => crate::FunctionType::DateNew,
+            x if x == crate::FunctionType::DateNextBusinessDay as usize => {
+                crate::FunctionType::DateNextBusinessDay
+            }
             x if x == crate::FunctionType::DateNow as usize => crate::FunctionType::DateNow,
+            x if x == crate::FunctionType::DateWeekday as usize => crate::FunctionType::DateWeekday,
+            x if x == crate::FunctionType::DateYear as usize => crate::FunctionType::DateYear,
             x if x == crate::FunctionType::Default as usize => crate::FunctionType::Default,
             x if x == crate::FunctionType::Descriptor as usize => crate::FunctionType::Descriptor,
             x if x == crate::FunctionType::Decimal as usize => crate::FunctionType::Decimal,
+            x if x == crate::FunctionType::Effect as usize => crate::FunctionType::Effect,
+            x if x == crate::FunctionType::Filter as usize => crate::FunctionType::Filter,
+            x if x == crate::FunctionType::First as usize => crate::FunctionType::First,
             x if x == crate::FunctionType::Format as usize => crate::FunctionType::Format,
             x if x == crate::FunctionType::FormatCurrency as usize => {
                 crate::FunctionType::FormatCurrency
@@ -900,24 +1143,37 @@ impl CoreUtility {
             x if x == crate::FunctionType::FormatNumber as usize => {
                 crate::FunctionType::FormatNumber
             }
+            x if x == crate::FunctionType::Fv as usize => crate::FunctionType::Fv,
             x if x == crate::FunctionType::If as usize => crate::FunctionType::If,
             x if x == crate::FunctionType::Integer as usize => crate::FunctionType::Integer,
+            x if x == crate::FunctionType::Ipmt as usize => crate::FunctionType::Ipmt,
+            x if x == crate::FunctionType::Last as usize => crate::FunctionType::Last,
             x if x == crate::FunctionType::Len as usize => crate::FunctionType::Len,
+            x if x == crate::FunctionType::List as usize => crate::FunctionType::List,
             x if x == crate::FunctionType::Lowercase as usize => crate::FunctionType::Lowercase,
             x if x == crate::FunctionType::Max as usize => crate::FunctionType::Max,
             x if x == crate::FunctionType::Mid as usize => crate::FunctionType::Mid,
             x if x == crate::FunctionType::Min as usize => crate::FunctionType::Min,
+            x if x == crate::FunctionType::Nominal as usize => crate::FunctionType::Nominal,
+            x if x == crate::FunctionType::Nper as usize => crate::FunctionType::Nper,
+            x if x == crate::FunctionType::Npv as usize => crate::FunctionType::Npv,
             x if x == crate::FunctionType::Parse as usize => crate::FunctionType::Parse,
+            x if x == crate::FunctionType::Pmt as usize => crate::FunctionType::Pmt,
+            x if x == crate::FunctionType::Ppmt as usize => crate::FunctionType::Ppmt,
             x if x == crate::FunctionType::Pr as usize => crate::FunctionType::Pr,
+            x if x == crate::FunctionType::Pv as usize => crate::FunctionType::Pv,
+            x if x == crate::FunctionType::Rate as usize => crate::FunctionType::Rate,
             x if x == crate::FunctionType::Replace as usize => crate::FunctionType::Replace,
             x if x == crate::FunctionType::Round as usize => crate::FunctionType::Round,
             x if x == crate::FunctionType::RoundFraction as usize => {
                 crate::FunctionType::RoundFraction
             }
             x if x == crate::FunctionType::Set as usize => crate::FunctionType::Set,
+            x if x == crate::FunctionType::Sum as usize => crate::FunctionType::Sum,
             x if x == crate::FunctionType::Trim as usize => crate::FunctionType::Trim,
             x if x == crate::FunctionType::Type as usize => crate::FunctionType::Type,
             x if x == crate::FunctionType::Uppercase as usize => crate::FunctionType::Uppercase,
+            x if x == crate::FunctionType::Xnpv as usize => crate::FunctionType::Xnpv,
             _ => crate::FunctionType::None,
         }
     }
@@ -1128,6 +1384,23 @@ impl CoreUtility {
         }
     }
 
+    /// Convert an integer in YYYYMMDD format to a date.
+    ///
+    /// # Arguments
+    ///
+    /// * `val` - Integer in YYYYMMDD format.
+    ///
+    /// # Return
+    ///
+    /// * The date or None if the integer is not a valid date
+    ///   or is before January 1, SERIAL_BASE_YEAR.
+    pub fn integer_to_date(val: usize) -> Option<NaiveDate> {
+        match CoreUtility::date_from_ymd(val / 10000, val / 100 % 100, val % 100) {
+            Some(o) if CoreUtility::date_in_range(o) => Some(o),
+            _ => None,
+        }
+    }
+
     /// Return the number of intervals in a year for a frequency.
     ///
     /// # Arguments
@@ -1179,7 +1452,7 @@ impl CoreUtility {
         1
     }
 
-    /// Parse a string and return a date value.
+    /// Parse a string in YYYY-MM-DD format and return a date value.
     ///
     /// # Arguments
     ///
@@ -1187,19 +1460,31 @@ impl CoreUtility {
     ///
     /// # Return
     ///
-    /// * See description.
-
-    pub fn parse_date(text: &str) -> usize {
+    /// * The date or None if the string is not a valid date
+    ///   or is before January 1, SERIAL_BASE_YEAR.
+    pub fn parse_date(text: &str) -> Option<NaiveDate> {
         let tokens: Vec<_> = text.split('-').collect();
         if tokens.len() != 3 {
-            return 0;
+            return None;
         }
 
-        let yyyy = tokens[0].parse::<usize>().unwrap_or(2000);
-        let mm = tokens[1].parse::<usize>().unwrap_or(1);
-        let dd = tokens[2].parse::<usize>().unwrap_or(1);
+        let yyyy = match tokens[0].trim().parse::<usize>() {
+            Err(_e) => return None,
+            Ok(o) => o,
+        };
+        let mm = match tokens[1].trim().parse::<usize>() {
+            Err(_e) => return None,
+            Ok(o) => o,
+        };
+        let dd = match tokens[2].trim().parse::<usize>() {
+            Err(_e) => return None,
+            Ok(o) => o,
+        };
 
-        yyyy * 10000 + mm * 100 + dd
+        match CoreUtility::date_from_ymd(yyyy, mm, dd) {
+            Some(o) if CoreUtility::date_in_range(o) => Some(o),
+            _ => None,
+        }
     }
 
     /// Parse a string and return a Decimal value.
@@ -1327,6 +1612,34 @@ impl CoreUtility {
         result
     }
 
+    /// Raise a Decimal value to an integer exponent
+    /// without overflowing.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value.
+    /// * `exponent` - The integer exponent.
+    ///
+    /// # Return
+    ///
+    /// * The result, otherwise None if the result overflows.
+    pub fn checked_decimal_pow(value: Decimal, mut exponent: usize) -> Option<Decimal> {
+        let mut factor = value;
+        let mut result = dec!(1.0);
+
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = result.checked_mul(factor)?;
+            }
+            exponent >>= 1;
+            if exponent > 0 {
+                factor = factor.checked_mul(factor)?;
+            }
+        }
+
+        Some(result)
+    }
+
     /// Converts a Daily Rate (DR) into a Nominal Annual Rate (NAR).
     ///
     /// # Arguments
@@ -1441,6 +1754,69 @@ impl CoreUtility {
         ) - dec!(1.0)
     }
 
+    /// Returns the positive nth root of a value.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value (must be greater than zero).
+    /// * `root` - The root (must be greater than zero).
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn decimal_root(value: Decimal, root: usize) -> Decimal {
+        if value <= dec!(0.0) || root == 0 {
+            return dec!(0.0);
+        }
+        if root == 1 {
+            return value;
+        }
+
+        // Newton's method starting above the root (Bernoulli's inequality)
+        let n = Decimal::from(root);
+        let mut result = dec!(1.0) + (value - dec!(1.0)) / n;
+
+        for _ in 0..crate::MAXIMUM_ITERATIONS_ROOT {
+            let power = match CoreUtility::checked_decimal_pow(result, root - 1) {
+                None => break,
+                Some(o) => o,
+            };
+            let next = ((n - dec!(1.0)) * result + value / power) / n;
+            if next >= result {
+                break;
+            }
+            result = next;
+        }
+
+        result
+    }
+
+    /// Returns the compounded interest factor of the Business/252
+    /// day count basis, (1 + rate) ^ (days / 252) - 1. The factor is
+    /// calculated in Decimal by compounding the daily factor,
+    /// (1 + rate) ^ (1 / 252), over the business days.
+    ///
+    /// # Arguments
+    ///
+    /// * `rate` - The annual interest rate expressed as a decimal value.
+    /// * `days` - Number of business days.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn rate_business_252(rate: Decimal, days: usize) -> Decimal {
+        if days == 0 {
+            return dec!(0.0);
+        }
+
+        let daily_factor = CoreUtility::decimal_root(dec!(1.0) + rate, 252);
+
+        match CoreUtility::checked_decimal_pow(daily_factor, days) {
+            None => dec!(0.0),
+            Some(o) => o - dec!(1.0),
+        }
+    }
+
     /// Convert a Nominal Annual Rate (NAR) into a Periodic Rate (PR).
     ///
     /// # Arguments
@@ -1551,7 +1927,7 @@ impl CoreUtility {
                 if pfract > low_round && pfract < high_round {
                     let ncmp = if decimal_digits > 0 { wfract } else { whole };
                     if ncmp % dec!(2.0) == dec!(0.0) {
-                        return whole + wfract;
+                        return whole + (wfract / dv);
                     }
                     return whole + (afract.ceil() / dv);
                 }
@@ -1587,14 +1963,14 @@ impl CoreUtility {
         fraction: Decimal,
         round_ctrl: crate::RoundType,
     ) -> Decimal {
-        if round_ctrl == crate::RoundType::None {
+        if round_ctrl == crate::RoundType::None || fraction <= dec!(0.0) {
             return value;
         }
 
         let low_round = dec!(crate::LOW_ROUND);
         let mid_round = dec!(crate::MID_ROUND);
         let high_round = dec!(crate::HIGH_ROUND);
-        let afract: Decimal = value * fraction;
+        let afract: Decimal = value / fraction;
         let wfract: Decimal = afract.floor();
         let pfract: Decimal = afract - wfract;
 
@@ -1602,7 +1978,7 @@ impl CoreUtility {
             crate::RoundType::Bankers => {
                 if pfract > low_round && pfract < high_round {
                     if wfract % dec!(2.0) == dec!(0.0) {
-                        return wfract;
+                        return wfract * fraction;
                     }
                     return afract.ceil() * fraction;
                 }
@@ -1629,25 +2005,31 @@ impl CoreUtility {
     ///
     /// # Return
     ///
-    /// * Date in YYYYMMDD format.
-
-    pub fn serial_to_date(serial: usize) -> usize {
-        let mut year = (((serial - 1) as f64) / 365.25) as usize;
-        let mut day = serial - (((year as f64) * 365.25).ceil() as usize);
+    /// * See description.
+    pub fn serial_to_date(serial: usize) -> NaiveDate {
+        let days = (serial as i64) + (CoreUtility::serial_base() as i64);
 
-        year += crate::SERIAL_BASE_YEAR;
-        let leap = CoreUtility::leap_year(year);
+        if days > i32::MAX as i64 {
+            return NaiveDate::MAX;
+        }
 
-        let val1: usize = if day > 59 + leap {
-            93 + day - leap
-        } else {
-            91 + day
-        };
+        match NaiveDate::from_num_days_from_ce_opt(days as i32) {
+            None => NaiveDate::MAX,
+            Some(o) => o,
+        }
+    }
 
-        let val2 = val1 * 100 / 3055;
-        day = val1 - (val2 * 3055 / 100);
-        let month = val2 - 2;
-        (year * 10000) + (month * 100) + day
+    /// Returns the day number (from the common era) of the day
+    /// before serial number one (January 1, SERIAL_BASE_YEAR).
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn serial_base() -> i32 {
+        match NaiveDate::from_ymd_opt(crate::SERIAL_BASE_YEAR as i32, 1, 1) {
+            None => 0,
+            Some(o) => o.num_days_from_ce() - 1,
+        }
     }
 
     /// Return a string representing the skip mask boolean array.
@@ -7,8 +7,9 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use chrono::NaiveDate;
 use rust_decimal::prelude::*;
-use std::cell::Cell;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 use super::{ElemExtension, ListDescriptor, ListParameter};
 use crate::ExtensionTrait;
@@ -16,10 +17,16 @@ use crate::ExtensionTrait;
 pub struct ElemAmortization {
     /// Event type information.
     event_type: String,
-    /// Date of the original event in YYYYMMDD format.
-    orig_date: usize,
-    /// Date of the element in YYYYMMDD format (major sort key).
-    event_date: usize,
+    /// Date of the original event.
+    orig_date: NaiveDate,
+    /// Date of the element (major sort key).
+    event_date: NaiveDate,
+    /// Scheduled date of the element before
+    /// any business day adjustment.
+    scheduled_date: NaiveDate,
+    /// Index value used to compute the rate of an
+    /// index-driven interest change (None if not applicable).
+    index_value: Option<Decimal>,
     /// Sort order within the element date (minor sort key).
     sort_order: usize,
     /// Value of the original event.
@@ -46,12 +53,28 @@ pub struct ElemAmortization {
     value_to_interest: Decimal,
     /// Value to principal.
     value_to_principal: Decimal,
+    /// Unpaid interest added to the principal balance (negative amortization).
+    deferred_interest: Decimal,
+    /// Scheduled principal repaid before any prepayment.
+    scheduled_principal: Decimal,
+    /// Principal prepaid under the prepayment model.
+    prepaid_principal: Decimal,
+    /// Balance surviving after scheduled principal, prepayments and defaults.
+    surviving_balance: Decimal,
+    /// Performing principal that defaulted under the default scenario.
+    defaulted_principal: Decimal,
+    /// Defaulted principal recovered when liquidated.
+    recovery: Decimal,
+    /// Defaulted principal lost when liquidated.
+    loss: Decimal,
+    /// Defaulted principal awaiting liquidation (delinquency pipeline).
+    delinquent_balance: Decimal,
     /// Accrued interest balance at start of element.
     acc_balance: Decimal,
     /// Beginning active balance at start of element.
     balance: Decimal,
     /// Index of the original event within the event list.
-    list_event_index: Cell<usize>,
+    list_event_index: AtomicUsize,
     /// Sequence number within the event starting from 1.
     event_sequence: usize,
     /// Sequence number of principal change with statistics set or 0 (if not applicable).
@@ -147,8 +170,8 @@ impl ElemAmortization {
 
     pub fn new(
         event_type_param: &str,
-        orig_date_param: usize,
-        event_date_param: usize,
+        orig_date_param: NaiveDate,
+        event_date_param: NaiveDate,
         sort_order_param: usize,
         orig_value_param: Decimal,
         value_param: Decimal,
@@ -175,6 +198,8 @@ impl ElemAmortization {
             event_type: String::from(event_type_param),
             orig_date: orig_date_param,
             event_date: event_date_param,
+            scheduled_date: event_date_param,
+            index_value: None,
             sort_order: sort_order_param,
             orig_value: orig_value_param,
             value: value_param,
@@ -188,9 +213,17 @@ impl ElemAmortization {
             sl_interest: sl_interest_param,
             value_to_interest: value_to_interest_param,
             value_to_principal: value_to_principal_param,
+            deferred_interest: dec!(0.0),
+            scheduled_principal: dec!(0.0),
+            prepaid_principal: dec!(0.0),
+            surviving_balance: dec!(0.0),
+            defaulted_principal: dec!(0.0),
+            recovery: dec!(0.0),
+            loss: dec!(0.0),
+            delinquent_balance: dec!(0.0),
             acc_balance: acc_balance_param,
             balance: balance_param,
-            list_event_index: Cell::new(list_event_index_param),
+            list_event_index: AtomicUsize::new(list_event_index_param),
             event_sequence: event_sequence_param,
             stat_sequence: stat_sequence_param,
             elem_extension: extension_param,
@@ -215,7 +248,7 @@ impl ElemAmortization {
     ///
     /// * See description.
 
-    pub fn orig_date(&self) -> usize {
+    pub fn orig_date(&self) -> NaiveDate {
         self.orig_date
     }
 
@@ -225,10 +258,28 @@ impl ElemAmortization {
     ///
     /// * See description.
 
-    pub fn event_date(&self) -> usize {
+    pub fn event_date(&self) -> NaiveDate {
         self.event_date
     }
 
+    /// Get the scheduled date value (before any business day adjustment).
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn scheduled_date(&self) -> NaiveDate {
+        self.scheduled_date
+    }
+
+    /// Get the index value used to compute the rate (None if not applicable).
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn index_value(&self) -> Option<Decimal> {
+        self.index_value
+    }
+
     /// Get the sort order value.
     ///
     /// # Return
@@ -359,6 +410,78 @@ impl ElemAmortization {
         self.value_to_principal
     }
 
+    /// Get the unpaid interest added to the principal balance.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn deferred_interest(&self) -> Decimal {
+        self.deferred_interest
+    }
+
+    /// Get the scheduled principal repaid before any prepayment.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn scheduled_principal(&self) -> Decimal {
+        self.scheduled_principal
+    }
+
+    /// Get the principal prepaid under the prepayment model.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn prepaid_principal(&self) -> Decimal {
+        self.prepaid_principal
+    }
+
+    /// Get the balance surviving after scheduled principal, prepayments and defaults.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn surviving_balance(&self) -> Decimal {
+        self.surviving_balance
+    }
+
+    /// Get the performing principal that defaulted under the default scenario.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn defaulted_principal(&self) -> Decimal {
+        self.defaulted_principal
+    }
+
+    /// Get the defaulted principal recovered when liquidated.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn recovery(&self) -> Decimal {
+        self.recovery
+    }
+
+    /// Get the defaulted principal lost when liquidated.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn loss(&self) -> Decimal {
+        self.loss
+    }
+
+    /// Get the defaulted principal awaiting liquidation (delinquency pipeline).
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn delinquent_balance(&self) -> Decimal {
+        self.delinquent_balance
+    }
+
     /// Get the accrued balance value.
     ///
     /// # Return
@@ -386,7 +509,7 @@ impl ElemAmortization {
     /// * See description.
 
     pub fn list_event_index(&self) -> usize {
-        self.list_event_index.get()
+        self.list_event_index.load(Ordering::Relaxed)
     }
 
     /// Get the event sequence.
@@ -445,7 +568,7 @@ impl ElemAmortization {
     ///
     /// * `original_date_param` - See description.
 
-    pub fn set_original_date(&mut self, original_date_param: usize) {
+    pub fn set_original_date(&mut self, original_date_param: NaiveDate) {
         self.orig_date = original_date_param;
     }
 
@@ -455,10 +578,28 @@ impl ElemAmortization {
     ///
     /// * `event_date_param` - See description.
 
-    pub fn set_event_date(&mut self, event_date_param: usize) {
+    pub fn set_event_date(&mut self, event_date_param: NaiveDate) {
         self.event_date = event_date_param;
     }
 
+    /// Set the scheduled date.
+    ///
+    /// # Arguments
+    ///
+    /// * `scheduled_date_param` - See description.
+    pub fn set_scheduled_date(&mut self, scheduled_date_param: NaiveDate) {
+        self.scheduled_date = scheduled_date_param;
+    }
+
+    /// Set the index value used to compute the rate.
+    ///
+    /// # Arguments
+    ///
+    /// * `index_value_param` - See description.
+    pub fn set_index_value(&mut self, index_value_param: Option<Decimal>) {
+        self.index_value = index_value_param;
+    }
+
     /// Set the sort order.
     ///
     /// # Arguments
@@ -589,6 +730,78 @@ impl ElemAmortization {
         self.value_to_principal = value_to_principal_param;
     }
 
+    /// Set the unpaid interest added to the principal balance.
+    ///
+    /// # Arguments
+    ///
+    /// * `deferred_interest_param` - See description.
+    pub fn set_deferred_interest(&mut self, deferred_interest_param: Decimal) {
+        self.deferred_interest = deferred_interest_param;
+    }
+
+    /// Set the scheduled principal repaid before any prepayment.
+    ///
+    /// # Arguments
+    ///
+    /// * `scheduled_principal_param` - See description.
+    pub fn set_scheduled_principal(&mut self, scheduled_principal_param: Decimal) {
+        self.scheduled_principal = scheduled_principal_param;
+    }
+
+    /// Set the principal prepaid under the prepayment model.
+    ///
+    /// # Arguments
+    ///
+    /// * `prepaid_principal_param` - See description.
+    pub fn set_prepaid_principal(&mut self, prepaid_principal_param: Decimal) {
+        self.prepaid_principal = prepaid_principal_param;
+    }
+
+    /// Set the balance surviving after scheduled principal, prepayments and defaults.
+    ///
+    /// # Arguments
+    ///
+    /// * `surviving_balance_param` - See description.
+    pub fn set_surviving_balance(&mut self, surviving_balance_param: Decimal) {
+        self.surviving_balance = surviving_balance_param;
+    }
+
+    /// Set the performing principal that defaulted under the default scenario.
+    ///
+    /// # Arguments
+    ///
+    /// * `defaulted_principal_param` - See description.
+    pub fn set_defaulted_principal(&mut self, defaulted_principal_param: Decimal) {
+        self.defaulted_principal = defaulted_principal_param;
+    }
+
+    /// Set the defaulted principal recovered when liquidated.
+    ///
+    /// # Arguments
+    ///
+    /// * `recovery_param` - See description.
+    pub fn set_recovery(&mut self, recovery_param: Decimal) {
+        self.recovery = recovery_param;
+    }
+
+    /// Set the defaulted principal lost when liquidated.
+    ///
+    /// # Arguments
+    ///
+    /// * `loss_param` - See description.
+    pub fn set_loss(&mut self, loss_param: Decimal) {
+        self.loss = loss_param;
+    }
+
+    /// Set the defaulted principal awaiting liquidation (delinquency pipeline).
+    ///
+    /// # Arguments
+    ///
+    /// * `delinquent_balance_param` - See description.
+    pub fn set_delinquent_balance(&mut self, delinquent_balance_param: Decimal) {
+        self.delinquent_balance = delinquent_balance_param;
+    }
+
     /// Set the accrued balance value.
     ///
     /// # Arguments
@@ -616,7 +829,8 @@ impl ElemAmortization {
     /// * `list_event_index_param` - See description.
 
     pub fn set_list_event_index(&self, list_event_index_param: usize) {
-        self.list_event_index.set(list_event_index_param);
+        self.list_event_index
+            .store(list_event_index_param, Ordering::Relaxed);
     }
 
     /// Set the event sequence.
@@ -0,0 +1,130 @@
+//! Result definition of an annual percentage rate calculation.
+// Copyright (c) 2021 ShiftLeft Software
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use rust_decimal::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct ElemAprResult {
+    /// Regulation that the annual percentage rate was calculated under.
+    apr_type: crate::AprType,
+    /// Annual percentage rate (expressed as a percentage).
+    apr: Decimal,
+    /// Unit period of the calculation (one year for the EU directive).
+    unit_period: crate::FrequencyType,
+    /// Finance charge (total of payments less the amount financed).
+    finance_charge: Decimal,
+    /// Amount financed (advances less the prepaid finance charges).
+    amount_financed: Decimal,
+    /// Total of payments (including finance charges paid after the first advance).
+    total_of_payments: Decimal,
+    /// Solver iterations used.
+    iterations: usize,
+}
+
+/// The annual percentage rate result implementation.
+impl ElemAprResult {
+    /// Create and return a new annual percentage rate result.
+    ///
+    /// # Arguments
+    ///
+    /// * `apr_type_param` - Regulation.
+    /// * `apr_param` - Annual percentage rate.
+    /// * `unit_period_param` - Unit period.
+    /// * `finance_charge_param` - Finance charge.
+    /// * `amount_financed_param` - Amount financed.
+    /// * `total_of_payments_param` - Total of payments.
+    /// * `iterations_param` - Solver iterations used.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn new(
+        apr_type_param: crate::AprType,
+        apr_param: Decimal,
+        unit_period_param: crate::FrequencyType,
+        finance_charge_param: Decimal,
+        amount_financed_param: Decimal,
+        total_of_payments_param: Decimal,
+        iterations_param: usize,
+    ) -> ElemAprResult {
+        ElemAprResult {
+            apr_type: apr_type_param,
+            apr: apr_param,
+            unit_period: unit_period_param,
+            finance_charge: finance_charge_param,
+            amount_financed: amount_financed_param,
+            total_of_payments: total_of_payments_param,
+            iterations: iterations_param,
+        }
+    }
+
+    /// Get the regulation that the annual percentage rate
+    /// was calculated under.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn apr_type(&self) -> crate::AprType {
+        self.apr_type
+    }
+
+    /// Get the annual percentage rate.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn apr(&self) -> Decimal {
+        self.apr
+    }
+
+    /// Get the unit period.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn unit_period(&self) -> crate::FrequencyType {
+        self.unit_period
+    }
+
+    /// Get the finance charge.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn finance_charge(&self) -> Decimal {
+        self.finance_charge
+    }
+
+    /// Get the amount financed.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn amount_financed(&self) -> Decimal {
+        self.amount_financed
+    }
+
+    /// Get the total of payments.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn total_of_payments(&self) -> Decimal {
+        self.total_of_payments
+    }
+
+    /// Get the solver iterations used.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn iterations(&self) -> usize {
+        self.iterations
+    }
+}
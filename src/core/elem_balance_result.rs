@@ -7,9 +7,10 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-use std::cell::Cell;
-
+use chrono::NaiveDate;
 use rust_decimal::prelude::*;
+use std::sync::atomic::{AtomicI32, AtomicUsize, Ordering};
+use std::sync::Mutex;
 
 pub struct ElemBalanceResult {
     /// Total number of TYPE_PRINCIPAL_CHANGEs with statistics set.
@@ -28,8 +29,8 @@ pub struct ElemBalanceResult {
     acc_balance: Decimal,
     /// Final balance.
     balance: Decimal,
-    /// Final balance date.
-    balance_date: usize,
+    /// Final balance date (NaiveDate::MIN until balanced).
+    balance_date: NaiveDate,
     /// Total value of TYPE_PRINCIPAL_CHANGE decreases without auxiliary set.
     prin_decrease: Decimal,
     /// Total value of TYPE_PRINCIPAL_CHANGE increases without auxiliary set.
@@ -42,6 +43,20 @@ pub struct ElemBalanceResult {
     aux_passive_decrease: Decimal,
     /// Value of passive TYPE_PRINCIPAL_CHANGE increases with auxiliary set.
     aux_passive_increase: Decimal,
+    /// Total scheduled principal repaid before any prepayment.
+    scheduled_principal_total: Decimal,
+    /// Total principal prepaid under the prepayment model.
+    prepaid_principal_total: Decimal,
+    /// Total performing principal that defaulted under the default scenario.
+    defaulted_principal_total: Decimal,
+    /// Total defaulted principal recovered when liquidated.
+    recovery_total: Decimal,
+    /// Total defaulted principal lost when liquidated.
+    loss_total: Decimal,
+    /// Final defaulted principal awaiting liquidation.
+    delinquent_balance: Decimal,
+    /// Final balance surviving after scheduled principal, prepayments and defaults.
+    surviving_balance: Decimal,
     /// -1 = Negative cashflow, 1 = Positive cashflow.
     polarity: i32,
     /// Accrued interest balance seen.
@@ -69,11 +84,15 @@ pub struct ElemBalanceResult {
     int_last_index: usize,
 
     /// Result yield.
-    result_yield: Cell<Decimal>,
+    result_yield: Mutex<Decimal>,
     /// Result decimal.
-    result_decimal: Cell<Decimal>,
+    result_decimal: Mutex<Decimal>,
     /// Result integer.
-    result_integer: Cell<i32>,
+    result_integer: AtomicI32,
+    /// Solver iterations used to calculate the result.
+    iterations: AtomicUsize,
+    /// Final remaining balance less the target value of the calculation.
+    residual: Mutex<Decimal>,
 }
 
 /// Balance result definition default implementation.
@@ -109,13 +128,20 @@ impl ElemBalanceResult {
             sl_interest_present: dec!(0.0),
             acc_balance: dec!(0.0),
             balance: dec!(0.0),
-            balance_date: 0,
+            balance_date: NaiveDate::MIN,
             prin_decrease: dec!(0.0),
             prin_increase: dec!(0.0),
             aux_active_decrease: dec!(0.0),
             aux_active_increase: dec!(0.0),
             aux_passive_decrease: dec!(0.0),
             aux_passive_increase: dec!(0.0),
+            scheduled_principal_total: dec!(0.0),
+            prepaid_principal_total: dec!(0.0),
+            defaulted_principal_total: dec!(0.0),
+            recovery_total: dec!(0.0),
+            loss_total: dec!(0.0),
+            delinquent_balance: dec!(0.0),
+            surviving_balance: dec!(0.0),
             polarity: 1,
             acc_balance_seen: false,
             rule_of_78_seen: false,
@@ -128,9 +154,11 @@ impl ElemBalanceResult {
             cur_first_pv_index: usize::MAX,
             int_first_index: usize::MAX,
             int_last_index: usize::MAX,
-            result_yield: Cell::new(dec!(0.0)),
-            result_decimal: Cell::new(dec!(0.0)),
-            result_integer: Cell::new(0),
+            result_yield: Mutex::new(dec!(0.0)),
+            result_decimal: Mutex::new(dec!(0.0)),
+            result_integer: AtomicI32::new(0),
+            iterations: AtomicUsize::new(0),
+            residual: Mutex::new(dec!(0.0)),
         }
     }
 
@@ -157,6 +185,13 @@ impl ElemBalanceResult {
             aux_active_increase: self.aux_active_increase,
             aux_passive_decrease: self.aux_passive_decrease,
             aux_passive_increase: self.aux_passive_increase,
+            scheduled_principal_total: self.scheduled_principal_total,
+            prepaid_principal_total: self.prepaid_principal_total,
+            defaulted_principal_total: self.defaulted_principal_total,
+            recovery_total: self.recovery_total,
+            loss_total: self.loss_total,
+            delinquent_balance: self.delinquent_balance,
+            surviving_balance: self.surviving_balance,
             polarity: self.polarity,
             acc_balance_seen: self.acc_balance_seen,
             rule_of_78_seen: self.rule_of_78_seen,
@@ -169,9 +204,11 @@ impl ElemBalanceResult {
             cur_first_pv_index: self.cur_first_pv_index,
             int_first_index: self.int_first_index,
             int_last_index: self.int_last_index,
-            result_yield: Cell::new(self.result_yield()),
-            result_decimal: Cell::new(self.result_decimal()),
-            result_integer: Cell::new(self.result_integer()),
+            result_yield: Mutex::new(self.result_yield()),
+            result_decimal: Mutex::new(self.result_decimal()),
+            result_integer: AtomicI32::new(self.result_integer()),
+            iterations: AtomicUsize::new(self.iterations()),
+            residual: Mutex::new(self.residual()),
         }
     }
 
@@ -186,13 +223,20 @@ impl ElemBalanceResult {
         self.sl_interest_present = dec!(0.0);
         self.acc_balance = dec!(0.0);
         self.balance = dec!(0.0);
-        self.balance_date = 0;
+        self.balance_date = NaiveDate::MIN;
         self.prin_decrease = dec!(0.0);
         self.prin_increase = dec!(0.0);
         self.aux_active_decrease = dec!(0.0);
         self.aux_active_increase = dec!(0.0);
         self.aux_passive_decrease = dec!(0.0);
         self.aux_passive_increase = dec!(0.0);
+        self.scheduled_principal_total = dec!(0.0);
+        self.prepaid_principal_total = dec!(0.0);
+        self.defaulted_principal_total = dec!(0.0);
+        self.recovery_total = dec!(0.0);
+        self.loss_total = dec!(0.0);
+        self.delinquent_balance = dec!(0.0);
+        self.surviving_balance = dec!(0.0);
         self.polarity = 1; // Positive CF
         self.acc_balance_seen = false;
         self.rule_of_78_seen = false;
@@ -294,7 +338,7 @@ impl ElemBalanceResult {
     ///
     /// * See description.
 
-    pub fn balance_date(&self) -> usize {
+    pub fn balance_date(&self) -> NaiveDate {
         self.balance_date
     }
 
@@ -358,6 +402,69 @@ impl ElemBalanceResult {
         self.aux_passive_increase
     }
 
+    /// Get the total scheduled principal repaid before any prepayment.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn scheduled_principal_total(&self) -> Decimal {
+        self.scheduled_principal_total
+    }
+
+    /// Get the total principal prepaid under the prepayment model.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn prepaid_principal_total(&self) -> Decimal {
+        self.prepaid_principal_total
+    }
+
+    /// Get the total performing principal that defaulted under the default scenario.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn defaulted_principal_total(&self) -> Decimal {
+        self.defaulted_principal_total
+    }
+
+    /// Get the total defaulted principal recovered when liquidated.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn recovery_total(&self) -> Decimal {
+        self.recovery_total
+    }
+
+    /// Get the total defaulted principal lost when liquidated.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn loss_total(&self) -> Decimal {
+        self.loss_total
+    }
+
+    /// Get the final defaulted principal awaiting liquidation.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn delinquent_balance(&self) -> Decimal {
+        self.delinquent_balance
+    }
+
+    /// Get the final balance surviving after scheduled principal, prepayments and defaults.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn surviving_balance(&self) -> Decimal {
+        self.surviving_balance
+    }
+
     /// Get the polarity of the cashflow.
     ///
     /// # Return
@@ -485,7 +592,7 @@ impl ElemBalanceResult {
     /// * See description.
 
     pub fn result_yield(&self) -> Decimal {
-        self.result_yield.get()
+        *self.result_yield.lock().unwrap()
     }
 
     /// Get the result decimal.
@@ -495,7 +602,7 @@ impl ElemBalanceResult {
     /// * See description.
 
     pub fn result_decimal(&self) -> Decimal {
-        self.result_decimal.get()
+        *self.result_decimal.lock().unwrap()
     }
 
     /// Get the result integer.
@@ -505,7 +612,25 @@ impl ElemBalanceResult {
     /// * See description.
 
     pub fn result_integer(&self) -> i32 {
-        self.result_integer.get()
+        self.result_integer.load(Ordering::Relaxed)
+    }
+
+    /// Get the solver iterations used to calculate the result.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn iterations(&self) -> usize {
+        self.iterations.load(Ordering::Relaxed)
+    }
+
+    /// Get the final remaining balance less the target value.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn residual(&self) -> Decimal {
+        *self.residual.lock().unwrap()
     }
 
     /// Increment the total number of TYPE_PRINCIPAL_CHANGEs with statistics set.
@@ -639,6 +764,51 @@ impl ElemBalanceResult {
         self.aux_passive_increase += param;
     }
 
+    /// Increment the total scheduled principal repaid before any prepayment.
+    ///
+    /// # Arguments
+    ///
+    /// * `param` - See description.
+    pub fn incr_scheduled_principal_total(&mut self, param: Decimal) {
+        self.scheduled_principal_total += param;
+    }
+
+    /// Increment the total principal prepaid under the prepayment model.
+    ///
+    /// # Arguments
+    ///
+    /// * `param` - See description.
+    pub fn incr_prepaid_principal_total(&mut self, param: Decimal) {
+        self.prepaid_principal_total += param;
+    }
+
+    /// Increment the total performing principal that defaulted under the default scenario.
+    ///
+    /// # Arguments
+    ///
+    /// * `param` - See description.
+    pub fn incr_defaulted_principal_total(&mut self, param: Decimal) {
+        self.defaulted_principal_total += param;
+    }
+
+    /// Increment the total defaulted principal recovered when liquidated.
+    ///
+    /// # Arguments
+    ///
+    /// * `param` - See description.
+    pub fn incr_recovery_total(&mut self, param: Decimal) {
+        self.recovery_total += param;
+    }
+
+    /// Increment the total defaulted principal lost when liquidated.
+    ///
+    /// # Arguments
+    ///
+    /// * `param` - See description.
+    pub fn incr_loss_total(&mut self, param: Decimal) {
+        self.loss_total += param;
+    }
+
     /// Set the final accrued interest balance.
     ///
     /// # Arguments
@@ -659,13 +829,31 @@ impl ElemBalanceResult {
         self.balance = param;
     }
 
+    /// Set the final defaulted principal awaiting liquidation.
+    ///
+    /// # Arguments
+    ///
+    /// * `param` - See description.
+    pub fn set_delinquent_balance(&mut self, param: Decimal) {
+        self.delinquent_balance = param;
+    }
+
+    /// Set the final balance surviving after scheduled principal, prepayments and defaults.
+    ///
+    /// # Arguments
+    ///
+    /// * `param` - See description.
+    pub fn set_surviving_balance(&mut self, param: Decimal) {
+        self.surviving_balance = param;
+    }
+
     /// Set the final balance date.
     ///
     /// # Arguments
     ///
     /// * `param` - See description.
 
-    pub fn set_balance_date(&mut self, param: usize) {
+    pub fn set_balance_date(&mut self, param: NaiveDate) {
         self.balance_date = param;
     }
 
@@ -796,7 +984,7 @@ impl ElemBalanceResult {
     /// * `param` - See description.
 
     pub fn set_result_yield(&self, param: Decimal) {
-        self.result_yield.set(param);
+        *self.result_yield.lock().unwrap() = param;
     }
 
     /// Set the result decimal.
@@ -806,7 +994,7 @@ impl ElemBalanceResult {
     /// * `param` - See description.
 
     pub fn set_result_decimal(&self, param: Decimal) {
-        self.result_decimal.set(param);
+        *self.result_decimal.lock().unwrap() = param;
     }
 
     /// Set the result integer.
@@ -816,6 +1004,24 @@ impl ElemBalanceResult {
     /// * `param` - See description.
 
     pub fn set_result_integer(&self, param: i32) {
-        self.result_integer.set(param);
+        self.result_integer.store(param, Ordering::Relaxed);
+    }
+
+    /// Set the solver iterations used to calculate the result.
+    ///
+    /// # Arguments
+    ///
+    /// * `param` - See description.
+    pub fn set_iterations(&self, param: usize) {
+        self.iterations.store(param, Ordering::Relaxed);
+    }
+
+    /// Set the final remaining balance less the target value.
+    ///
+    /// # Arguments
+    ///
+    /// * `param` - See description.
+    pub fn set_residual(&self, param: Decimal) {
+        *self.residual.lock().unwrap() = param;
     }
 }
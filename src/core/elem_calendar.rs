@@ -0,0 +1,417 @@
+//! The business day calendar element definition.
+// Copyright (c) 2021 ShiftLeft Software
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use chrono::{Datelike, Duration, NaiveDate};
+
+use super::{CoreUtility, ElemError, ElemHoliday};
+
+#[derive(Clone, Debug)]
+pub struct ElemCalendar {
+    /// Name of the calendar.
+    name: String,
+    /// Weekend days indexed by the day of the week (0=Sunday through 6=Saturday).
+    weekend: [bool; 7],
+    /// Holidays of the calendar.
+    holidays: Vec<ElemHoliday>,
+}
+
+/// The business day calendar element implementation.
+impl ElemCalendar {
+    /// Create and return a new calendar with a
+    /// Saturday and Sunday weekend and no holidays.
+    ///
+    /// # Arguments
+    ///
+    /// * `name_param` - Name of the calendar.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn new(name_param: &str) -> ElemCalendar {
+        ElemCalendar {
+            name: String::from(name_param),
+            weekend: [true, false, false, false, false, false, true],
+            holidays: Vec::new(),
+        }
+    }
+
+    /// Create and return a new joint calendar. A date is a
+    /// business day of the joint calendar only if it is a
+    /// business day in every one of the calendars.
+    ///
+    /// # Arguments
+    ///
+    /// * `name_param` - Name of the joint calendar.
+    /// * `calendars` - Calendars to join.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn new_joint(name_param: &str, calendars: &[&ElemCalendar]) -> ElemCalendar {
+        let mut joint = ElemCalendar {
+            name: String::from(name_param),
+            weekend: [false; 7],
+            holidays: Vec::new(),
+        };
+
+        for calendar in calendars.iter() {
+            for (index, weekend) in calendar.weekend.iter().enumerate() {
+                joint.weekend[index] |= *weekend;
+            }
+            joint.holidays.extend(calendar.holidays.iter().cloned());
+        }
+
+        joint
+    }
+
+    /// Create and return a new calendar from the events of an
+    /// iCalendar (ICS) file. Each event start date becomes a
+    /// holiday. Events with a yearly recurrence rule become
+    /// fixed holidays, or nth (or last) weekday holidays when
+    /// the rule contains BYMONTH and BYDAY.
+    ///
+    /// # Arguments
+    ///
+    /// * `name_param` - Name of the calendar.
+    /// * `text` - Contents of the iCalendar file.
+    ///
+    /// # Return
+    ///
+    /// * The calendar if successful, otherwise error code.
+    pub fn new_from_ics(name_param: &str, text: &str) -> Result<ElemCalendar, ElemError> {
+        let mut calendar = ElemCalendar::new(name_param);
+
+        // Unfold continuation lines
+        let mut lines: Vec<String> = Vec::new();
+        for line in text.lines() {
+            if let Some(o) = line.strip_prefix(' ').or_else(|| line.strip_prefix('\t')) {
+                if let Some(o2) = lines.last_mut() {
+                    o2.push_str(o);
+                }
+                continue;
+            }
+            lines.push(String::from(line.trim_end()));
+        }
+
+        let mut in_event = false;
+        let mut summary = String::from("");
+        let mut start_date: Option<NaiveDate> = None;
+        let mut rule = String::from("");
+
+        for line in lines.iter() {
+            let (prop, value) = match line.split_once(':') {
+                None => continue,
+                Some(o) => o,
+            };
+            let prop_name = match prop.split_once(';') {
+                None => prop,
+                Some(o) => o.0,
+            };
+
+            match prop_name.to_uppercase().as_str() {
+                "BEGIN" if value.eq_ignore_ascii_case("VEVENT") => {
+                    in_event = true;
+                    summary.clear();
+                    start_date = None;
+                    rule.clear();
+                }
+                "END" if value.eq_ignore_ascii_case("VEVENT") => {
+                    in_event = false;
+                    if let Some(o) = start_date {
+                        calendar.add_holiday(ElemCalendar::ics_holiday(
+                            summary.as_str(),
+                            o,
+                            rule.as_str(),
+                        )?);
+                    }
+                }
+                "SUMMARY" if in_event => {
+                    summary = String::from(value);
+                }
+                "DTSTART" if in_event => {
+                    let date = match value.split_once('T') {
+                        None => value,
+                        Some(o) => o.0,
+                    };
+                    start_date = match date.parse::<usize>() {
+                        Ok(o) if date.len() == 8 => CoreUtility::integer_to_date(o),
+                        _ => None,
+                    };
+                    if start_date.is_none() {
+                        return Err(ElemError::new(crate::ErrorType::Calendar));
+                    }
+                }
+                "RRULE" if in_event => {
+                    rule = value.to_uppercase();
+                }
+                _ => {}
+            }
+        }
+
+        Ok(calendar)
+    }
+
+    /// Create a holiday from an iCalendar event.
+    ///
+    /// # Arguments
+    ///
+    /// * `summary` - Summary of the event.
+    /// * `start_date` - Start date of the event.
+    /// * `rule` - Recurrence rule of the event (uppercase).
+    ///
+    /// # Return
+    ///
+    /// * The holiday if successful, otherwise error code.
+    fn ics_holiday(
+        summary: &str,
+        start_date: NaiveDate,
+        rule: &str,
+    ) -> Result<ElemHoliday, ElemError> {
+        let mut yearly = false;
+        let mut by_month: usize = 0;
+        let mut by_day = "";
+
+        for part in rule.split(';') {
+            let (key, value) = match part.split_once('=') {
+                None => (part, ""),
+                Some(o) => o,
+            };
+            match key {
+                "FREQ" => {
+                    yearly = value == "YEARLY";
+                }
+                "BYMONTH" => {
+                    by_month = CoreUtility::parse_integer(value);
+                }
+                "BYDAY" => {
+                    by_day = value;
+                }
+                _ => {}
+            }
+        }
+
+        if !yearly {
+            return Ok(ElemHoliday::new_date(summary, start_date));
+        }
+
+        if by_month == 0 || by_day.is_empty() {
+            return Ok(ElemHoliday::new_fixed(
+                summary,
+                start_date.month() as usize,
+                start_date.day() as usize,
+                false,
+            ));
+        }
+
+        let (week, weekday) = match ["SU", "MO", "TU", "WE", "TH", "FR", "SA"]
+            .iter()
+            .enumerate()
+            .find_map(|(index, o)| by_day.strip_suffix(o).map(|o2| (o2, index)))
+        {
+            None => return Err(ElemError::new(crate::ErrorType::Calendar)),
+            Some(o) => o,
+        };
+
+        let week = match week {
+            "" => 1,
+            _ => match week.strip_prefix('+').unwrap_or(week).parse::<i32>() {
+                Err(_e) => return Err(ElemError::new(crate::ErrorType::Calendar)),
+                Ok(o) => o,
+            },
+        };
+
+        if week < 0 {
+            Ok(ElemHoliday::new_last_weekday(summary, by_month, weekday))
+        } else {
+            Ok(ElemHoliday::new_nth_weekday(
+                summary,
+                by_month,
+                week.max(1) as usize,
+                weekday,
+            ))
+        }
+    }
+
+    /// Adjust a date according to a business day convention.
+    ///
+    /// # Arguments
+    ///
+    /// * `date` - The date.
+    /// * `business_day` - Business day convention.
+    ///
+    /// # Return
+    ///
+    /// * Adjusted date.
+    pub fn adjust_date(&self, date: NaiveDate, business_day: crate::BusinessDayType) -> NaiveDate {
+        if business_day == crate::BusinessDayType::Unadjusted
+            || self.weekend.iter().all(|o| *o)
+            || self.is_business_day(date)
+        {
+            return date;
+        }
+
+        match business_day {
+            crate::BusinessDayType::Following => self.roll_date(date, true),
+            crate::BusinessDayType::ModifiedFollowing => {
+                let new_date = self.roll_date(date, true);
+                if new_date.month() != date.month() {
+                    return self.roll_date(date, false);
+                }
+                new_date
+            }
+            crate::BusinessDayType::Preceding => self.roll_date(date, false),
+            crate::BusinessDayType::ModifiedPreceding => {
+                let new_date = self.roll_date(date, false);
+                if new_date.month() != date.month() {
+                    return self.roll_date(date, true);
+                }
+                new_date
+            }
+            _ => date,
+        }
+    }
+
+    /// Roll a date to the next or previous business day.
+    ///
+    /// # Arguments
+    ///
+    /// * `date` - The date.
+    /// * `forward` - Roll forward, otherwise roll backward.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn roll_date(&self, date: NaiveDate, forward: bool) -> NaiveDate {
+        let mut new_date = date;
+
+        while !self.is_business_day(new_date) {
+            if forward {
+                new_date += Duration::days(1);
+            } else {
+                new_date -= Duration::days(1);
+            }
+        }
+
+        new_date
+    }
+
+    /// Returns true if a date is a business day. This is the
+    /// business day test shared by date adjustment and the
+    /// Business/252 day count.
+    ///
+    /// # Arguments
+    ///
+    /// * `date` - The date.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn is_business_day(&self, date: NaiveDate) -> bool {
+        !self.is_weekend(date) && !self.is_holiday(date)
+    }
+
+    /// Returns true if a date is a holiday.
+    ///
+    /// # Arguments
+    ///
+    /// * `date` - The date.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn is_holiday(&self, date: NaiveDate) -> bool {
+        let year = date.year() as usize;
+
+        self.holidays.iter().any(|o| {
+            // Observed fixed holidays may move into the adjacent year
+            o.date_in_year(year) == Option::from(date)
+                || o.holiday_type() == crate::HolidayType::Fixed
+                    && o.observed()
+                    && (o.date_in_year(year + 1) == Option::from(date)
+                        || o.date_in_year(year.saturating_sub(1)) == Option::from(date))
+        })
+    }
+
+    /// Returns true if a date falls on a weekend.
+    ///
+    /// # Arguments
+    ///
+    /// * `date` - The date.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn is_weekend(&self, date: NaiveDate) -> bool {
+        self.weekend[CoreUtility::day_of_week(date)]
+    }
+
+    /// Add a holiday to the calendar.
+    ///
+    /// # Arguments
+    ///
+    /// * `holiday` - The holiday to add.
+    pub fn add_holiday(&mut self, holiday: ElemHoliday) {
+        self.holidays.push(holiday);
+    }
+
+    /// Get the name of the calendar.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    /// Get the weekend days (0=Sunday through 6=Saturday).
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn weekend_days(&self) -> Vec<usize> {
+        self.weekend
+            .iter()
+            .enumerate()
+            .filter(|(_, o)| **o)
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// Get the holidays of the calendar.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn holidays(&self) -> &[ElemHoliday] {
+        self.holidays.as_slice()
+    }
+
+    /// Set the name of the calendar.
+    ///
+    /// # Arguments
+    ///
+    /// * `name_param` - See description.
+    pub fn set_name(&mut self, name_param: &str) {
+        self.name = String::from(name_param);
+    }
+
+    /// Set the weekend days (0=Sunday through 6=Saturday).
+    ///
+    /// # Arguments
+    ///
+    /// * `weekend_days_param` - See description.
+    pub fn set_weekend_days(&mut self, weekend_days_param: &[usize]) {
+        self.weekend = [false; 7];
+        for day in weekend_days_param.iter() {
+            if *day < 7 {
+                self.weekend[*day] = true;
+            }
+        }
+    }
+}
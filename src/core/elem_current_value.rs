@@ -14,6 +14,10 @@ pub struct ElemCurrentValue {
     passive: bool,
     /// Designate as present value.
     present: bool,
+    /// Business day convention applied to successive dates.
+    business_day: crate::BusinessDayType,
+    /// Name of the business day calendar (empty=weekends only).
+    calendar: String,
 }
 
 /// The current value implementation.
@@ -36,6 +40,8 @@ impl ElemCurrentValue {
             eom: eom_param,
             passive: passive_param,
             present: present_param,
+            business_day: crate::BusinessDayType::Unadjusted,
+            calendar: String::from(""),
         }
     }
 
@@ -46,7 +52,12 @@ impl ElemCurrentValue {
     /// * See description.
 
     pub fn copy(&self) -> ElemCurrentValue {
-        ElemCurrentValue::new(self.eom, self.passive, self.present)
+        let mut elem_current_value = ElemCurrentValue::new(self.eom, self.passive, self.present);
+
+        elem_current_value.set_business_day(self.business_day);
+        elem_current_value.set_calendar(self.calendar.as_str());
+
+        elem_current_value
     }
 
     /// Tests if this current value object and another are equal.
@@ -62,6 +73,8 @@ impl ElemCurrentValue {
         self.eom == elem_current_value.eom
             && self.passive == elem_current_value.passive
             && self.present == elem_current_value.present
+            && self.business_day == elem_current_value.business_day
+            && self.calendar == elem_current_value.calendar
     }
 
     /// Get the value to adjust successive dates to end of month.
@@ -94,6 +107,24 @@ impl ElemCurrentValue {
         self.present
     }
 
+    /// Get the business day convention.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn business_day(&self) -> crate::BusinessDayType {
+        self.business_day
+    }
+
+    /// Get the name of the business day calendar.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn calendar(&self) -> &str {
+        self.calendar.as_str()
+    }
+
     /// Set the value to adjust successive dates to end of month.
     ///
     /// # Arguments
@@ -123,4 +154,22 @@ impl ElemCurrentValue {
     pub fn set_present(&mut self, param: bool) {
         self.present = param;
     }
+
+    /// Set the business day convention.
+    ///
+    /// # Arguments
+    ///
+    /// * `param` - See description.
+    pub fn set_business_day(&mut self, param: crate::BusinessDayType) {
+        self.business_day = param;
+    }
+
+    /// Set the name of the business day calendar.
+    ///
+    /// # Arguments
+    ///
+    /// * `param` - See description.
+    pub fn set_calendar(&mut self, param: &str) {
+        self.calendar = String::from(param);
+    }
 }
@@ -0,0 +1,57 @@
+//! The dated amount element definition.
+// Copyright (c) 2021 ShiftLeft Software
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use chrono::NaiveDate;
+use rust_decimal::prelude::*;
+
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub struct ElemDatedAmount {
+    /// Date of the amount.
+    date: NaiveDate,
+    /// Amount (negative for outflows, positive for inflows).
+    amount: Decimal,
+}
+
+/// The dated amount element implementation.
+impl ElemDatedAmount {
+    /// Create and return a new dated amount element.
+    ///
+    /// # Arguments
+    ///
+    /// * `date_param` - Date of the amount.
+    /// * `amount_param` - Amount.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn new(date_param: NaiveDate, amount_param: Decimal) -> ElemDatedAmount {
+        ElemDatedAmount {
+            date: date_param,
+            amount: amount_param,
+        }
+    }
+
+    /// Get the date.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn date(&self) -> NaiveDate {
+        self.date
+    }
+
+    /// Get the amount.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn amount(&self) -> Decimal {
+        self.amount
+    }
+}
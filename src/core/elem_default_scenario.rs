@@ -0,0 +1,116 @@
+//! The default scenario element definition.
+// Copyright (c) 2021 ShiftLeft Software
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use rust_decimal::prelude::*;
+
+use super::CoreUtility;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct ElemDefaultScenario {
+    /// Constant default rate (annual percentage).
+    cdr: Decimal,
+    /// Percentage of the defaulted principal lost when liquidated.
+    loss_severity: Decimal,
+    /// Number of payment periods between default and liquidation.
+    recovery_lag: usize,
+}
+
+/// The default scenario element implementation.
+impl ElemDefaultScenario {
+    /// Create and return a new default scenario element.
+    ///
+    /// # Arguments
+    ///
+    /// * `cdr_param` - Constant default rate (annual percentage).
+    /// * `loss_severity_param` - Loss severity (percentage).
+    /// * `recovery_lag_param` - Payment periods between default and liquidation.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn new(
+        cdr_param: Decimal,
+        loss_severity_param: Decimal,
+        recovery_lag_param: usize,
+    ) -> ElemDefaultScenario {
+        ElemDefaultScenario {
+            cdr: cdr_param,
+            loss_severity: loss_severity_param,
+            recovery_lag: recovery_lag_param,
+        }
+    }
+
+    /// Get the constant default rate.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn cdr(&self) -> Decimal {
+        self.cdr
+    }
+
+    /// Get the loss severity.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn loss_severity(&self) -> Decimal {
+        self.loss_severity
+    }
+
+    /// Get the number of payment periods between default and liquidation.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn recovery_lag(&self) -> usize {
+        self.recovery_lag
+    }
+
+    /// Get the fraction of the performing balance that defaults
+    /// in a payment period (monthly default rate when the payments
+    /// are monthly).
+    ///
+    /// # Arguments
+    ///
+    /// * `periods_in_year` - Number of payment periods in a year.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn mdr(&self, periods_in_year: usize) -> Decimal {
+        let cdr = (self.cdr / dec!(100.0)).min(dec!(1.0));
+        if cdr <= dec!(0.0) || periods_in_year == 0 {
+            return dec!(0.0);
+        }
+
+        // MDR = 1 - (1 - CDR) ^ (1 / periods in year)
+        (dec!(1.0) - CoreUtility::decimal_root(dec!(1.0) - cdr, periods_in_year))
+            .max(dec!(0.0))
+            .min(dec!(1.0))
+    }
+
+    /// Get the amount recovered when defaulted principal is liquidated
+    /// (the remainder is the loss).
+    ///
+    /// # Arguments
+    ///
+    /// * `liquidated` - Defaulted principal being liquidated.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn recovery(&self, liquidated: Decimal) -> Decimal {
+        let severity = (self.loss_severity / dec!(100.0))
+            .max(dec!(0.0))
+            .min(dec!(1.0));
+
+        liquidated * (dec!(1.0) - severity)
+    }
+}
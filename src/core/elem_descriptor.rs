@@ -7,7 +7,8 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-use std::cell::{Cell, RefCell};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::RwLock;
 
 pub struct ElemDescriptor {
     /// Group name of the descriptor.
@@ -19,13 +20,13 @@ pub struct ElemDescriptor {
     /// Code for the type of descriptor (ISO language code_ISO country code).
     code: String,
     /// Constant value or the result of an expression.
-    value: RefCell<String>,
+    value: RwLock<String>,
     /// Optional value expression.
     value_expr: String,
     /// Propagate to the next level if applicable.
     propagate: bool,
     /// Index of the event within the event list (applied by amortization).
-    list_event_index: Cell<usize>,
+    list_event_index: AtomicUsize,
 }
 
 /// The descriptor element implementation.
@@ -61,10 +62,10 @@ impl ElemDescriptor {
             name: String::from(name_param),
             desc_type: String::from(desc_type_param),
             code: String::from(code_param),
-            value: RefCell::new(String::from(value_param)),
+            value: RwLock::new(String::from(value_param)),
             value_expr: String::from(value_expr_param),
             propagate: propagate_param,
-            list_event_index: Cell::new(usize::MAX),
+            list_event_index: AtomicUsize::new(usize::MAX),
         }
     }
 
@@ -137,7 +138,7 @@ impl ElemDescriptor {
     /// * See description.
 
     pub fn value(&self) -> String {
-        self.value.borrow().clone()
+        self.value.read().unwrap().clone()
     }
 
     /// Get the descriptor value expression.
@@ -167,7 +168,7 @@ impl ElemDescriptor {
     /// * See description.
 
     pub fn list_event_index(&self) -> usize {
-        self.list_event_index.get()
+        self.list_event_index.load(Ordering::Relaxed)
     }
 
     /// Set the descriptor group.
@@ -217,8 +218,8 @@ impl ElemDescriptor {
     /// * `value_param` - See description.
 
     pub fn set_value(&self, value_param: &str) {
-        self.value.borrow_mut().clear();
-        self.value.borrow_mut().push_str(value_param);
+        self.value.write().unwrap().clear();
+        self.value.write().unwrap().push_str(value_param);
     }
 
     /// Set the descriptor value expression.
@@ -248,6 +249,7 @@ impl ElemDescriptor {
     /// * `list_event_index_param` - See description.
 
     pub fn set_list_event_index(&self, list_event_index_param: usize) {
-        self.list_event_index.set(list_event_index_param);
+        self.list_event_index
+            .store(list_event_index_param, Ordering::Relaxed);
     }
 }
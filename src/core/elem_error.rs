@@ -0,0 +1,264 @@
+//! The error element definition.
+// Copyright (c) 2021 ShiftLeft Software
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::fmt;
+
+// The context is held in boxed strings to keep Result<_, ElemError> small.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct ElemError {
+    /// Error type.
+    error_type: crate::ErrorType,
+    /// Name of the cashflow being processed (or empty).
+    cashflow_name: Box<str>,
+    /// Index of the event being processed (or usize::MAX).
+    event_index: usize,
+    /// Name of the column being processed (or empty).
+    column_name: Box<str>,
+    /// JSON pointer of the document value being processed (or empty).
+    pointer: Box<str>,
+    /// Text of the expression being evaluated (or empty).
+    expression: Box<str>,
+    /// Position of the last token scanned within the expression (or usize::MAX).
+    position: usize,
+    /// Description of the error (or empty).
+    message: Box<str>,
+}
+
+/// The error element display implementation.
+impl fmt::Display for ElemError {
+    /// Format the error and its context.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - Formatter.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?} error", self.error_type)?;
+
+        let mut context: Vec<String> = Vec::new();
+        if !self.cashflow_name.is_empty() {
+            context.push(format!("cashflow \"{}\"", self.cashflow_name));
+        }
+        if self.event_index != usize::MAX {
+            context.push(format!("event {}", self.event_index));
+        }
+        if !self.column_name.is_empty() {
+            context.push(format!("column \"{}\"", self.column_name));
+        }
+        if !self.pointer.is_empty() {
+            context.push(format!("pointer \"{}\"", self.pointer));
+        }
+        if !self.expression.is_empty() {
+            if self.position != usize::MAX {
+                context.push(format!(
+                    "expression \"{}\" at position {}",
+                    self.expression, self.position
+                ));
+            } else {
+                context.push(format!("expression \"{}\"", self.expression));
+            }
+        }
+
+        if !context.is_empty() {
+            write!(f, " ({})", context.join(", "))?;
+        }
+        if !self.message.is_empty() {
+            write!(f, ": {}", self.message)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The error element standard error implementation.
+impl std::error::Error for ElemError {}
+
+/// The error element conversion implementation.
+impl From<crate::ErrorType> for ElemError {
+    /// Create a new error element from an error type.
+    ///
+    /// # Arguments
+    ///
+    /// * `error_type_param` - Error type.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn from(error_type_param: crate::ErrorType) -> ElemError {
+        ElemError::new(error_type_param)
+    }
+}
+
+/// The error element implementation.
+impl ElemError {
+    /// Create a new error element without context.
+    ///
+    /// # Arguments
+    ///
+    /// * `error_type_param` - Error type.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn new(error_type_param: crate::ErrorType) -> ElemError {
+        ElemError {
+            error_type: error_type_param,
+            cashflow_name: Box::from(""),
+            event_index: usize::MAX,
+            column_name: Box::from(""),
+            pointer: Box::from(""),
+            expression: Box::from(""),
+            position: usize::MAX,
+            message: Box::from(""),
+        }
+    }
+
+    /// Get the error type.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn kind(&self) -> crate::ErrorType {
+        self.error_type
+    }
+
+    /// Get the name of the cashflow being processed.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn cashflow_name(&self) -> &str {
+        &self.cashflow_name
+    }
+
+    /// Get the index of the event being processed.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn event_index(&self) -> usize {
+        self.event_index
+    }
+
+    /// Get the name of the column being processed.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn column_name(&self) -> &str {
+        &self.column_name
+    }
+
+    /// Get the JSON pointer of the document value being processed.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn pointer(&self) -> &str {
+        &self.pointer
+    }
+
+    /// Get the description of the error.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Get the text of the expression being evaluated.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn expression(&self) -> &str {
+        &self.expression
+    }
+
+    /// Get the position of the last token scanned within the expression.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Set the name of the cashflow if not already set.
+    ///
+    /// # Arguments
+    ///
+    /// * `cashflow_name_param` - See description.
+    pub fn set_cashflow_name(&mut self, cashflow_name_param: &str) {
+        if self.cashflow_name.is_empty() {
+            self.cashflow_name = Box::from(cashflow_name_param);
+        }
+    }
+
+    /// Set the index of the event if not already set.
+    ///
+    /// # Arguments
+    ///
+    /// * `event_index_param` - See description.
+    pub fn set_event_index(&mut self, event_index_param: usize) {
+        if self.event_index == usize::MAX {
+            self.event_index = event_index_param;
+        }
+    }
+
+    /// Set the name of the column if not already set.
+    ///
+    /// # Arguments
+    ///
+    /// * `column_name_param` - See description.
+    pub fn set_column_name(&mut self, column_name_param: &str) {
+        if self.column_name.is_empty() {
+            self.column_name = Box::from(column_name_param);
+        }
+    }
+
+    /// Set the JSON pointer of the document value if not already set.
+    ///
+    /// # Arguments
+    ///
+    /// * `pointer_param` - See description.
+    pub fn set_pointer(&mut self, pointer_param: &str) {
+        if self.pointer.is_empty() {
+            self.pointer = Box::from(pointer_param);
+        }
+    }
+
+    /// Set the description of the error if not already set.
+    ///
+    /// # Arguments
+    ///
+    /// * `message_param` - See description.
+    pub fn set_message(&mut self, message_param: &str) {
+        if self.message.is_empty() {
+            self.message = Box::from(message_param);
+        }
+    }
+
+    /// Set the expression text and token position if not already set.
+    ///
+    /// # Arguments
+    ///
+    /// * `expression_param` - Expression text.
+    /// * `position_param` - Position of the last token scanned.
+    pub fn set_expression(&mut self, expression_param: &str, position_param: usize) {
+        if self.expression.is_empty() {
+            self.expression = Box::from(expression_param);
+            self.position = position_param;
+        }
+    }
+}
@@ -7,8 +7,10 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use chrono::NaiveDate;
 use rust_decimal::prelude::*;
-use std::cell::Cell;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
 
 use super::{ElemExtension, ListDescriptor, ListParameter};
 use crate::ExtensionTrait;
@@ -16,26 +18,26 @@ use crate::ExtensionTrait;
 pub struct ElemEvent {
     /// Event type information.
     event_type: String,
-    /// Date of the element in YYYYMMDD format (major sort key).
-    event_date: Cell<usize>,
+    /// Date of the element (major sort key).
+    event_date: Mutex<NaiveDate>,
     /// Optional date expression evaluated when created from a template.
     date_expr: String,
     /// Sort order within the element date (minor sort key).
     sort_order: usize,
     /// Value of the element.
-    value: Cell<Decimal>,
+    value: Mutex<Decimal>,
     /// Optional value expression evaluated when the amortization list is balanced.
     value_expr: String,
     /// The expression is evalulated when balanced, otherwise when expanded.
     value_expr_balance: bool,
     /// Number of periods.
-    periods: Cell<usize>,
+    periods: AtomicUsize,
     /// Optional periods expression evaluated when the event list is expanded.
     periods_expr: String,
     /// Optional periods to skip length when the event list is expanded.
-    skip_mask_len: Cell<usize>,
+    skip_mask_len: AtomicUsize,
     /// Optional periods to skip when the event list is expanded.
-    skip_mask: Cell<u128>,
+    skip_mask: Mutex<u128>,
     /// Intervals of frequency between periods (default 1).
     intervals: usize,
     /// Frequency of the element.
@@ -129,7 +131,7 @@ impl ElemEvent {
 
     pub fn new(
         event_type_param: &str,
-        event_date_param: usize,
+        event_date_param: NaiveDate,
         date_expr_param: &str,
         sort_order_param: usize,
         value_param: Decimal,
@@ -149,16 +151,16 @@ impl ElemEvent {
     ) -> ElemEvent {
         ElemEvent {
             event_type: String::from(event_type_param),
-            event_date: Cell::new(event_date_param),
+            event_date: Mutex::new(event_date_param),
             date_expr: String::from(date_expr_param),
             sort_order: sort_order_param,
-            value: Cell::new(value_param),
+            value: Mutex::new(value_param),
             value_expr: String::from(value_expr_param),
             value_expr_balance: value_expr_balance_param,
-            periods: Cell::new(periods_param),
+            periods: AtomicUsize::new(periods_param),
             periods_expr: String::from(periods_expr_param),
-            skip_mask_len: Cell::new(skip_mask_len_param),
-            skip_mask: Cell::new(skip_mask_param),
+            skip_mask_len: AtomicUsize::new(skip_mask_len_param),
+            skip_mask: Mutex::new(skip_mask_param),
             intervals: intervals_param,
             frequency: frequency_param,
             elem_extension: extension_param,
@@ -185,8 +187,8 @@ impl ElemEvent {
     ///
     /// * See description.
 
-    pub fn event_date(&self) -> usize {
-        self.event_date.get()
+    pub fn event_date(&self) -> NaiveDate {
+        *self.event_date.lock().unwrap()
     }
 
     /// Get the date expression value.
@@ -216,7 +218,7 @@ impl ElemEvent {
     /// * See description.
 
     pub fn value(&self) -> Decimal {
-        self.value.get()
+        *self.value.lock().unwrap()
     }
 
     /// Get the element value expression.
@@ -246,7 +248,7 @@ impl ElemEvent {
     /// * See description.
 
     pub fn periods(&self) -> usize {
-        self.periods.get()
+        self.periods.load(Ordering::Relaxed)
     }
 
     /// Get the periods expression value.
@@ -266,7 +268,7 @@ impl ElemEvent {
     /// * See description.
 
     pub fn skip_mask_len(&self) -> usize {
-        self.skip_mask_len.get()
+        self.skip_mask_len.load(Ordering::Relaxed)
     }
 
     /// Get the skip mask value.
@@ -276,7 +278,7 @@ impl ElemEvent {
     /// * See description.
 
     pub fn skip_mask(&self) -> u128 {
-        self.skip_mask.get()
+        *self.skip_mask.lock().unwrap()
     }
 
     /// Get the intervals value.
@@ -365,8 +367,8 @@ impl ElemEvent {
     ///
     /// * `event_date_param` - See description.
 
-    pub fn set_event_date(&self, event_date_param: usize) {
-        self.event_date.set(event_date_param);
+    pub fn set_event_date(&self, event_date_param: NaiveDate) {
+        *self.event_date.lock().unwrap() = event_date_param;
     }
 
     /// Set the date expression.
@@ -396,7 +398,7 @@ impl ElemEvent {
     /// * `value_param` - See description.
 
     pub fn set_value(&self, value_param: Decimal) {
-        self.value.set(value_param);
+        *self.value.lock().unwrap() = value_param;
     }
 
     /// Set the value expression.
@@ -426,7 +428,7 @@ impl ElemEvent {
     /// * `periods_param` - See description.
 
     pub fn set_periods(&self, periods_param: usize) {
-        self.periods.set(periods_param);
+        self.periods.store(periods_param, Ordering::Relaxed);
     }
 
     /// Set the periods expression.
@@ -446,8 +448,9 @@ impl ElemEvent {
     /// * `skip_mask_param` - See description.
 
     pub fn set_skip_mask(&self, skip_mask_len_param: usize, skip_mask_param: u128) {
-        self.skip_mask_len.set(skip_mask_len_param);
-        self.skip_mask.set(skip_mask_param);
+        self.skip_mask_len
+            .store(skip_mask_len_param, Ordering::Relaxed);
+        *self.skip_mask.lock().unwrap() = skip_mask_param;
     }
 
     /// Set the intervals.
@@ -0,0 +1,203 @@
+//! The expression limits element definition.
+// Copyright (c) 2021 ShiftLeft Software
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use rust_decimal::prelude::*;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct ElemExprLimits {
+    /// Maximum number of nodes evaluated by an expression,
+    /// including the bodies of user-defined functions (None=unlimited).
+    max_steps: Option<usize>,
+    /// Maximum nesting depth of function calls, symbol indexes
+    /// and operators (None=unlimited).
+    max_depth: Option<usize>,
+    /// Maximum length in characters of a string value (None=unlimited).
+    max_string_length: Option<usize>,
+    /// Maximum absolute value of a numeric value (None=unlimited).
+    max_magnitude: Option<Decimal>,
+}
+
+impl Default for ElemExprLimits {
+    /// Create and return a new expression limits element with
+    /// the default limits set.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn default() -> Self {
+        ElemExprLimits::new()
+    }
+}
+
+/// The expression limits element implementation.
+/// Expressions that exceed a limit fail with the
+/// Limit error. By default the number of evaluated nodes,
+/// the nesting depth and the string length are limited and
+/// numbers are limited only by the range of a decimal.
+impl ElemExprLimits {
+    /// Create and return a new expression limits element with
+    /// the default limits set.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn new() -> ElemExprLimits {
+        ElemExprLimits {
+            max_steps: Option::from(crate::DEFAULT_EXPRESSION_STEPS),
+            max_depth: Option::from(crate::DEFAULT_EXPRESSION_DEPTH),
+            max_string_length: Option::from(crate::DEFAULT_EXPRESSION_STRING_LENGTH),
+            max_magnitude: None,
+        }
+    }
+
+    /// Clear all limits. Deeply nested expressions may
+    /// exhaust the stack when no depth limit is set.
+    pub fn clear(&mut self) {
+        self.max_steps = None;
+        self.max_depth = None;
+        self.max_string_length = None;
+        self.max_magnitude = None;
+    }
+
+    /// Returns true if a number of evaluated nodes exceeds the limit.
+    ///
+    /// # Arguments
+    ///
+    /// * `steps_param` - Number of evaluated nodes.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn exceeds_steps(&self, steps_param: usize) -> bool {
+        match self.max_steps {
+            None => false,
+            Some(o) => steps_param > o,
+        }
+    }
+
+    /// Returns true if a nesting depth exceeds the limit.
+    ///
+    /// # Arguments
+    ///
+    /// * `depth_param` - Nesting depth.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn exceeds_depth(&self, depth_param: usize) -> bool {
+        match self.max_depth {
+            None => false,
+            Some(o) => depth_param > o,
+        }
+    }
+
+    /// Returns true if a string length exceeds the limit.
+    ///
+    /// # Arguments
+    ///
+    /// * `length_param` - Length of the string in characters.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn exceeds_string_length(&self, length_param: usize) -> bool {
+        match self.max_string_length {
+            None => false,
+            Some(o) => length_param > o,
+        }
+    }
+
+    /// Returns true if the absolute value of a number exceeds the limit.
+    ///
+    /// # Arguments
+    ///
+    /// * `value_param` - The number.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn exceeds_magnitude(&self, value_param: Decimal) -> bool {
+        match self.max_magnitude {
+            None => false,
+            Some(o) => value_param.abs() > o,
+        }
+    }
+
+    /// Get the maximum number of evaluated nodes.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn max_steps(&self) -> Option<usize> {
+        self.max_steps
+    }
+
+    /// Get the maximum nesting depth.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn max_depth(&self) -> Option<usize> {
+        self.max_depth
+    }
+
+    /// Get the maximum string length.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn max_string_length(&self) -> Option<usize> {
+        self.max_string_length
+    }
+
+    /// Get the maximum numeric magnitude.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn max_magnitude(&self) -> Option<Decimal> {
+        self.max_magnitude
+    }
+
+    /// Set the maximum number of evaluated nodes.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_steps_param` - See description.
+    pub fn set_max_steps(&mut self, max_steps_param: Option<usize>) {
+        self.max_steps = max_steps_param;
+    }
+
+    /// Set the maximum nesting depth.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_depth_param` - See description.
+    pub fn set_max_depth(&mut self, max_depth_param: Option<usize>) {
+        self.max_depth = max_depth_param;
+    }
+
+    /// Set the maximum string length.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_string_length_param` - See description.
+    pub fn set_max_string_length(&mut self, max_string_length_param: Option<usize>) {
+        self.max_string_length = max_string_length_param;
+    }
+
+    /// Set the maximum numeric magnitude.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_magnitude_param` - See description.
+    pub fn set_max_magnitude(&mut self, max_magnitude_param: Option<Decimal>) {
+        self.max_magnitude = max_magnitude_param;
+    }
+}
@@ -0,0 +1,296 @@
+//! The compiled expression node element definition.
+// Copyright (c) 2021 ShiftLeft Software
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use super::ElemSymbol;
+
+#[derive(Clone)]
+pub struct ElemExprNode {
+    /// Type of node.
+    node_type: crate::NodeType,
+    /// Position (starting from 0) of the node within the expression text.
+    position: usize,
+    /// Constant value (constant nodes only).
+    value: ElemSymbol,
+    /// Name of the symbol or function (symbol and function nodes only).
+    name: String,
+    /// Operator type (operator nodes only).
+    operator_type: crate::OperatorType,
+    /// Function type (function nodes only).
+    function_type: crate::FunctionType,
+    /// Operands, arguments, symbol index or sequence of child nodes.
+    nodes: Vec<ElemExprNode>,
+    /// Height of the node tree (1 for a node without child nodes).
+    height: usize,
+}
+
+/// The compiled expression node element implementation.
+impl ElemExprNode {
+    /// Create and return a new node.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_type_param` - Type of node.
+    /// * `position_param` - Position of the node within the expression text.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn new(node_type_param: crate::NodeType, position_param: usize) -> ElemExprNode {
+        ElemExprNode {
+            node_type: node_type_param,
+            position: position_param,
+            value: ElemSymbol::new(),
+            name: String::from(""),
+            operator_type: crate::OperatorType::None,
+            function_type: crate::FunctionType::None,
+            nodes: Vec::new(),
+            height: 1,
+        }
+    }
+
+    /// Create and return a new constant node.
+    ///
+    /// # Arguments
+    ///
+    /// * `value_param` - Constant value.
+    /// * `position_param` - Position of the node within the expression text.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn new_constant(value_param: ElemSymbol, position_param: usize) -> ElemExprNode {
+        let mut node = ElemExprNode::new(crate::NodeType::Constant, position_param);
+        node.value = value_param;
+
+        node
+    }
+
+    /// Create and return a new symbol reference node.
+    ///
+    /// # Arguments
+    ///
+    /// * `name_param` - Name of the symbol.
+    /// * `index_param` - Index expression of an indexed symbol (i.e., name[index]) or None.
+    /// * `position_param` - Position of the node within the expression text.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn new_symbol(
+        name_param: &str,
+        index_param: Option<ElemExprNode>,
+        position_param: usize,
+    ) -> ElemExprNode {
+        let mut node = ElemExprNode::new(crate::NodeType::Symbol, position_param);
+        node.name = String::from(name_param);
+        if let Some(o) = index_param {
+            node.height = o.height + 1;
+            node.nodes.push(o);
+        }
+
+        node
+    }
+
+    /// Create and return a new operator node.
+    ///
+    /// # Arguments
+    ///
+    /// * `operator_type_param` - Operator type.
+    /// * `nodes_param` - Operand nodes (one for unary operators, otherwise two).
+    /// * `position_param` - Position of the node within the expression text.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn new_operator(
+        operator_type_param: crate::OperatorType,
+        nodes_param: Vec<ElemExprNode>,
+        position_param: usize,
+    ) -> ElemExprNode {
+        let mut node = ElemExprNode::new(crate::NodeType::Operator, position_param);
+        node.operator_type = operator_type_param;
+        node.nodes = nodes_param;
+        node.height = ElemExprNode::height_of(&node.nodes);
+
+        node
+    }
+
+    /// Create and return a new function call node.
+    ///
+    /// # Arguments
+    ///
+    /// * `name_param` - Name of the function.
+    /// * `function_type_param` - Function type.
+    /// * `nodes_param` - Argument nodes.
+    /// * `position_param` - Position of the node within the expression text.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn new_function(
+        name_param: &str,
+        function_type_param: crate::FunctionType,
+        nodes_param: Vec<ElemExprNode>,
+        position_param: usize,
+    ) -> ElemExprNode {
+        let mut node = ElemExprNode::new(crate::NodeType::Function, position_param);
+        node.name = String::from(name_param);
+        node.function_type = function_type_param;
+        node.nodes = nodes_param;
+        node.height = ElemExprNode::height_of(&node.nodes);
+
+        node
+    }
+
+    /// Create and return a new sequence node. The result of
+    /// a sequence is the result of its last expression.
+    ///
+    /// # Arguments
+    ///
+    /// * `nodes_param` - Expression nodes.
+    /// * `position_param` - Position of the node within the expression text.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn new_sequence(nodes_param: Vec<ElemExprNode>, position_param: usize) -> ElemExprNode {
+        let mut node = ElemExprNode::new(crate::NodeType::Sequence, position_param);
+        node.nodes = nodes_param;
+        node.height = ElemExprNode::height_of(&node.nodes);
+
+        node
+    }
+
+    /// Returns the height of a node tree with the given child nodes.
+    ///
+    /// # Arguments
+    ///
+    /// * `nodes` - The child nodes.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn height_of(nodes: &[ElemExprNode]) -> usize {
+        nodes.iter().map(|o| o.height).max().unwrap_or(0) + 1
+    }
+
+    /// Tests if this node and another are equal. The positions
+    /// of the nodes within the expression text are not compared.
+    ///
+    /// # Arguments
+    ///
+    /// * `elem_expr_node` - Node to compare.
+    ///
+    /// # Return
+    ///
+    /// * True if equals, otherwise false.
+    pub fn equal(&self, elem_expr_node: &ElemExprNode) -> bool {
+        if self.node_type != elem_expr_node.node_type
+            || self.name != elem_expr_node.name
+            || self.operator_type != elem_expr_node.operator_type
+            || self.function_type != elem_expr_node.function_type
+            || self.value.sym_type() != elem_expr_node.value.sym_type()
+            || self.nodes.len() != elem_expr_node.nodes.len()
+        {
+            return false;
+        }
+
+        let equal = match self.value.sym_type() {
+            crate::TokenType::Integer => {
+                self.value.sym_integeri() == elem_expr_node.value.sym_integeri()
+            }
+            crate::TokenType::Decimal => {
+                self.value.sym_decimal() == elem_expr_node.value.sym_decimal()
+            }
+            crate::TokenType::String => {
+                self.value.sym_string() == elem_expr_node.value.sym_string()
+            }
+            _ => true,
+        };
+
+        equal
+            && self
+                .nodes
+                .iter()
+                .zip(elem_expr_node.nodes.iter())
+                .all(|(o, o2)| o.equal(o2))
+    }
+
+    /// Get the type of node.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn node_type(&self) -> crate::NodeType {
+        self.node_type
+    }
+
+    /// Get the position of the node within the expression text.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Get the constant value.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn value(&self) -> &ElemSymbol {
+        &self.value
+    }
+
+    /// Get the name of the symbol or function.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    /// Get the operator type.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn operator_type(&self) -> crate::OperatorType {
+        self.operator_type
+    }
+
+    /// Get the function type.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn function_type(&self) -> crate::FunctionType {
+        self.function_type
+    }
+
+    /// Get the child nodes.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn nodes(&self) -> &[ElemExprNode] {
+        self.nodes.as_slice()
+    }
+
+    /// Get the height of the node tree (1 for a node without child nodes).
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+}
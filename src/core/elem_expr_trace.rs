@@ -0,0 +1,183 @@
+//! The expression evaluation trace element definition.
+// Copyright (c) 2021 ShiftLeft Software
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use super::ElemSymbol;
+
+#[derive(Clone)]
+pub struct ElemExprTrace {
+    /// Type of the evaluated node.
+    node_type: crate::NodeType,
+    /// Text of the node (constant value, symbol name, operator or function name).
+    text: String,
+    /// Position (starting from 0) of the node within the expression text.
+    position: usize,
+    /// Resulting value of the node.
+    result: ElemSymbol,
+    /// Error that occurred evaluating the node (None if successful).
+    error: crate::ErrorType,
+    /// Traces of the nodes evaluated on behalf of this node, in the order
+    /// evaluated (operands, arguments, symbol index or function body).
+    nodes: Vec<ElemExprTrace>,
+}
+
+/// The expression evaluation trace element implementation.
+impl ElemExprTrace {
+    /// Create and return a new trace element.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_type_param` - Type of the evaluated node.
+    /// * `text_param` - Text of the node.
+    /// * `position_param` - Position of the node within the expression text.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn new(
+        node_type_param: crate::NodeType,
+        text_param: &str,
+        position_param: usize,
+    ) -> ElemExprTrace {
+        ElemExprTrace {
+            node_type: node_type_param,
+            text: String::from(text_param),
+            position: position_param,
+            result: ElemSymbol::new(),
+            error: crate::ErrorType::None,
+            nodes: Vec::new(),
+        }
+    }
+
+    /// Get the type of the evaluated node.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn node_type(&self) -> crate::NodeType {
+        self.node_type
+    }
+
+    /// Get the text of the node.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn text(&self) -> &str {
+        self.text.as_str()
+    }
+
+    /// Get the position of the node within the expression text.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Get the resulting value of the node.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn result(&self) -> &ElemSymbol {
+        &self.result
+    }
+
+    /// Get the error that occurred evaluating the node.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn error(&self) -> crate::ErrorType {
+        self.error
+    }
+
+    /// Get the traces of the nodes evaluated on behalf of this node.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn nodes(&self) -> &[ElemExprTrace] {
+        self.nodes.as_slice()
+    }
+
+    /// Format the trace as indented lines (one line per node).
+    ///
+    /// # Arguments
+    ///
+    /// * `level` - Indentation level of this node.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn format(&self, level: usize) -> String {
+        let value = if self.error != crate::ErrorType::None {
+            format!("error {:?}", self.error)
+        } else {
+            match self.result.sym_type() {
+                crate::TokenType::Integer => format!("{}", self.result.sym_integeri()),
+                crate::TokenType::Decimal => format!("{}", self.result.sym_decimal()),
+                crate::TokenType::String => format!("\"{}\"", self.result.sym_string()),
+                crate::TokenType::List => format!("list({})", self.result.sym_list().len()),
+                _ => String::from(""),
+            }
+        };
+
+        let mut buf = format!(
+            "{}{} @{} => {}\n",
+            "  ".repeat(level),
+            self.text,
+            self.position,
+            value
+        );
+
+        for node in self.nodes.iter() {
+            buf.push_str(node.format(level + 1).as_str());
+        }
+
+        buf
+    }
+
+    /// Add the trace of a node evaluated on behalf of this node.
+    ///
+    /// # Arguments
+    ///
+    /// * `elem_expr_trace` - See description.
+    pub fn add_node(&mut self, elem_expr_trace: ElemExprTrace) {
+        self.nodes.push(elem_expr_trace);
+    }
+
+    /// Set the text of the node.
+    ///
+    /// # Arguments
+    ///
+    /// * `text_param` - See description.
+    pub fn set_text(&mut self, text_param: &str) {
+        self.text = String::from(text_param);
+    }
+
+    /// Set the resulting value of the node.
+    ///
+    /// # Arguments
+    ///
+    /// * `result_param` - See description.
+    pub fn set_result(&mut self, result_param: ElemSymbol) {
+        self.result = result_param;
+    }
+
+    /// Set the error that occurred evaluating the node.
+    ///
+    /// # Arguments
+    ///
+    /// * `error_param` - See description.
+    pub fn set_error(&mut self, error_param: crate::ErrorType) {
+        self.error = error_param;
+    }
+}
@@ -216,6 +216,33 @@ impl ElemExtension {
         }
     }
 
+    /// Get the business day convention.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn extension_business_day(&self) -> crate::BusinessDayType {
+        match self.extension_value() {
+            ExtensionValue::PrincipalChange(o) => o.business_day(),
+            ExtensionValue::CurrentValue(o) => o.business_day(),
+            _ => crate::BusinessDayType::Unadjusted,
+        }
+    }
+
+    /// Get the name of the business day calendar.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn extension_calendar(&self) -> &str {
+        match self.extension_value() {
+            ExtensionValue::PrincipalChange(o) => o.calendar(),
+            ExtensionValue::CurrentValue(o) => o.calendar(),
+            ExtensionValue::InterestChange(o) => o.calendar(),
+            _ => "",
+        }
+    }
+
     /// Get the principal change type.
     ///
     /// # Return
@@ -294,6 +321,30 @@ impl ElemExtension {
         }
     }
 
+    /// Get the optional maximum payment change (percentage of the previous payment).
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn pc_payment_cap(&self) -> Option<Decimal> {
+        match self.extension_value() {
+            ExtensionValue::PrincipalChange(o) => o.payment_cap(),
+            _ => None,
+        }
+    }
+
+    /// Get the optional negative amortization cap (percentage of the original principal).
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn pc_neg_am_cap(&self) -> Option<Decimal> {
+        match self.extension_value() {
+            ExtensionValue::PrincipalChange(o) => o.neg_am_cap(),
+            _ => None,
+        }
+    }
+
     /// Get the value to adjust successive dates to end of month.
     ///
     /// # Return
@@ -426,6 +477,102 @@ impl ElemExtension {
         }
     }
 
+    /// Get the name of the rate index.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn ic_index(&self) -> &str {
+        match self.extension_value() {
+            ExtensionValue::InterestChange(o) => o.index(),
+            _ => "",
+        }
+    }
+
+    /// Get the margin added to the index value.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn ic_margin(&self) -> Decimal {
+        match self.extension_value() {
+            ExtensionValue::InterestChange(o) => o.margin(),
+            _ => dec!(0.0),
+        }
+    }
+
+    /// Get the optional rate reset frequency.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn ic_reset_frequency(&self) -> crate::FrequencyType {
+        match self.extension_value() {
+            ExtensionValue::InterestChange(o) => o.reset_frequency(),
+            _ => crate::FrequencyType::None,
+        }
+    }
+
+    /// Get the index lookback days.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn ic_lookback_days(&self) -> usize {
+        match self.extension_value() {
+            ExtensionValue::InterestChange(o) => o.lookback_days(),
+            _ => 0,
+        }
+    }
+
+    /// Get the optional periodic rate cap.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn ic_periodic_cap(&self) -> Option<Decimal> {
+        match self.extension_value() {
+            ExtensionValue::InterestChange(o) => o.periodic_cap(),
+            _ => None,
+        }
+    }
+
+    /// Get the optional periodic rate floor.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn ic_periodic_floor(&self) -> Option<Decimal> {
+        match self.extension_value() {
+            ExtensionValue::InterestChange(o) => o.periodic_floor(),
+            _ => None,
+        }
+    }
+
+    /// Get the optional lifetime rate cap.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn ic_lifetime_cap(&self) -> Option<Decimal> {
+        match self.extension_value() {
+            ExtensionValue::InterestChange(o) => o.lifetime_cap(),
+            _ => None,
+        }
+    }
+
+    /// Get the optional lifetime rate floor.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn ic_lifetime_floor(&self) -> Option<Decimal> {
+        match self.extension_value() {
+            ExtensionValue::InterestChange(o) => o.lifetime_floor(),
+            _ => None,
+        }
+    }
+
     /// Get the name of the statistic event.
     ///
     /// # Return
@@ -548,6 +695,28 @@ impl ElemExtension {
         }
     }
 
+    /// Set the optional maximum payment change (percentage of the previous payment).
+    ///
+    /// # Arguments
+    ///
+    /// * `param` - See description.
+    pub fn set_pc_payment_cap(&mut self, param: Option<Decimal>) {
+        if let ExtensionValue::PrincipalChange(o) = self.extension_value_mut() {
+            o.set_payment_cap(param);
+        }
+    }
+
+    /// Set the optional negative amortization cap (percentage of the original principal).
+    ///
+    /// # Arguments
+    ///
+    /// * `param` - See description.
+    pub fn set_pc_neg_am_cap(&mut self, param: Option<Decimal>) {
+        if let ExtensionValue::PrincipalChange(o) = self.extension_value_mut() {
+            o.set_neg_am_cap(param);
+        }
+    }
+
     /// Set the current value.
     ///
     /// # Arguments
@@ -691,6 +860,94 @@ impl ElemExtension {
         }
     }
 
+    /// Set the name of the rate index.
+    ///
+    /// # Arguments
+    ///
+    /// * `param` - See description.
+    pub fn set_ic_index(&mut self, param: &str) {
+        if let ExtensionValue::InterestChange(o) = self.extension_value_mut() {
+            o.set_index(param);
+        }
+    }
+
+    /// Set the margin added to the index value.
+    ///
+    /// # Arguments
+    ///
+    /// * `param` - See description.
+    pub fn set_ic_margin(&mut self, param: Decimal) {
+        if let ExtensionValue::InterestChange(o) = self.extension_value_mut() {
+            o.set_margin(param);
+        }
+    }
+
+    /// Set the optional rate reset frequency.
+    ///
+    /// # Arguments
+    ///
+    /// * `param` - See description.
+    pub fn set_ic_reset_frequency(&mut self, param: crate::FrequencyType) {
+        if let ExtensionValue::InterestChange(o) = self.extension_value_mut() {
+            o.set_reset_frequency(param);
+        }
+    }
+
+    /// Set the index lookback days.
+    ///
+    /// # Arguments
+    ///
+    /// * `param` - See description.
+    pub fn set_ic_lookback_days(&mut self, param: usize) {
+        if let ExtensionValue::InterestChange(o) = self.extension_value_mut() {
+            o.set_lookback_days(param);
+        }
+    }
+
+    /// Set the optional periodic rate cap.
+    ///
+    /// # Arguments
+    ///
+    /// * `param` - See description.
+    pub fn set_ic_periodic_cap(&mut self, param: Option<Decimal>) {
+        if let ExtensionValue::InterestChange(o) = self.extension_value_mut() {
+            o.set_periodic_cap(param);
+        }
+    }
+
+    /// Set the optional periodic rate floor.
+    ///
+    /// # Arguments
+    ///
+    /// * `param` - See description.
+    pub fn set_ic_periodic_floor(&mut self, param: Option<Decimal>) {
+        if let ExtensionValue::InterestChange(o) = self.extension_value_mut() {
+            o.set_periodic_floor(param);
+        }
+    }
+
+    /// Set the optional lifetime rate cap.
+    ///
+    /// # Arguments
+    ///
+    /// * `param` - See description.
+    pub fn set_ic_lifetime_cap(&mut self, param: Option<Decimal>) {
+        if let ExtensionValue::InterestChange(o) = self.extension_value_mut() {
+            o.set_lifetime_cap(param);
+        }
+    }
+
+    /// Set the optional lifetime rate floor.
+    ///
+    /// # Arguments
+    ///
+    /// * `param` - See description.
+    pub fn set_ic_lifetime_floor(&mut self, param: Option<Decimal>) {
+        if let ExtensionValue::InterestChange(o) = self.extension_value_mut() {
+            o.set_lifetime_floor(param);
+        }
+    }
+
     /// Set the statistic value.
     ///
     /// # Arguments
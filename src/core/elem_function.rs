@@ -0,0 +1,135 @@
+//! The user-defined function element definition.
+// Copyright (c) 2021 ShiftLeft Software
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+pub struct ElemFunction {
+    /// Name of the function.
+    name: String,
+    /// Description of the function (i.e., help).
+    description: String,
+    /// Names of the function parameters.
+    parameters: Vec<String>,
+    /// Expression body of the function.
+    expression: String,
+}
+
+/// The user-defined function element implementation.
+impl ElemFunction {
+    /// Create a new function element.
+    ///
+    /// # Arguments
+    ///
+    /// * `name_param` - Function name.
+    /// * `desc_param` - Function description.
+    /// * `parameters_param` - Names of the function parameters.
+    /// * `expression_param` - Expression body of the function.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn new(
+        name_param: &str,
+        desc_param: &str,
+        parameters_param: &[String],
+        expression_param: &str,
+    ) -> ElemFunction {
+        ElemFunction {
+            name: String::from(name_param),
+            description: String::from(desc_param),
+            parameters: parameters_param.to_vec(),
+            expression: String::from(expression_param),
+        }
+    }
+
+    /// Compare this function to the function parameter.
+    ///
+    /// # Arguments
+    ///
+    /// * `function` - Function to compare.
+    ///
+    /// # Return
+    ///
+    /// * True if equal, otherwise false.
+    pub fn equal(&self, function: &ElemFunction) -> bool {
+        self.name == function.name()
+            && self.parameters == function.parameters()
+            && self.expression == function.expression()
+    }
+
+    /// Get the function name.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    /// Get the function description.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn description(&self) -> &str {
+        self.description.as_str()
+    }
+
+    /// Get the names of the function parameters.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn parameters(&self) -> &[String] {
+        self.parameters.as_slice()
+    }
+
+    /// Get the expression body of the function.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn expression(&self) -> &str {
+        self.expression.as_str()
+    }
+
+    /// Set the function name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name_param` - See description.
+    pub fn set_name(&mut self, name_param: &str) {
+        self.name = String::from(name_param);
+    }
+
+    /// Set the function description.
+    ///
+    /// # Arguments
+    ///
+    /// * `desc_param` - See description.
+    pub fn set_description(&mut self, desc_param: &str) {
+        self.description = String::from(desc_param);
+    }
+
+    /// Set the names of the function parameters.
+    ///
+    /// # Arguments
+    ///
+    /// * `parameters_param` - See description.
+    pub fn set_parameters(&mut self, parameters_param: &[String]) {
+        self.parameters = parameters_param.to_vec();
+    }
+
+    /// Set the expression body of the function.
+    ///
+    /// # Arguments
+    ///
+    /// * `expression_param` - See description.
+    pub fn set_expression(&mut self, expression_param: &str) {
+        self.expression = String::from(expression_param);
+    }
+}
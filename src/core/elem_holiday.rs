@@ -0,0 +1,332 @@
+//! The holiday element definition.
+// Copyright (c) 2021 ShiftLeft Software
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use chrono::{Datelike, Duration, NaiveDate};
+
+use super::CoreUtility;
+
+#[derive(Clone, Debug)]
+pub struct ElemHoliday {
+    /// Name of the holiday.
+    name: String,
+    /// Type of the holiday rule.
+    holiday_type: crate::HolidayType,
+    /// Month of the holiday (1-12, not used for Easter and date holidays).
+    month: usize,
+    /// Day of the month (fixed holidays only).
+    day: usize,
+    /// Week of the month (1-5, nth weekday holidays only).
+    week: usize,
+    /// Day of the week (0=Sunday through 6=Saturday, weekday holidays only).
+    weekday: usize,
+    /// Number of days from Easter Sunday (Easter holidays only).
+    offset: i32,
+    /// Date of the holiday (date holidays only).
+    date: Option<NaiveDate>,
+    /// Fixed holidays that fall on a Saturday are observed on the preceding
+    /// Friday and those that fall on a Sunday on the following Monday.
+    observed: bool,
+}
+
+/// The holiday element implementation.
+impl ElemHoliday {
+    /// Create and return a new holiday that occurs on the
+    /// same month and day every year.
+    ///
+    /// # Arguments
+    ///
+    /// * `name_param` - Name of the holiday.
+    /// * `month_param` - Month (1-12).
+    /// * `day_param` - Day of the month.
+    /// * `observed_param` - Observe weekend holidays on the nearest weekday.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn new_fixed(
+        name_param: &str,
+        month_param: usize,
+        day_param: usize,
+        observed_param: bool,
+    ) -> ElemHoliday {
+        ElemHoliday {
+            name: String::from(name_param),
+            holiday_type: crate::HolidayType::Fixed,
+            month: month_param,
+            day: day_param,
+            week: 0,
+            weekday: 0,
+            offset: 0,
+            date: None,
+            observed: observed_param,
+        }
+    }
+
+    /// Create and return a new holiday that occurs on the
+    /// nth weekday of a month every year.
+    ///
+    /// # Arguments
+    ///
+    /// * `name_param` - Name of the holiday.
+    /// * `month_param` - Month (1-12).
+    /// * `week_param` - Week of the month (1-5).
+    /// * `weekday_param` - Day of the week (0=Sunday through 6=Saturday).
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn new_nth_weekday(
+        name_param: &str,
+        month_param: usize,
+        week_param: usize,
+        weekday_param: usize,
+    ) -> ElemHoliday {
+        ElemHoliday {
+            name: String::from(name_param),
+            holiday_type: crate::HolidayType::NthWeekday,
+            month: month_param,
+            day: 0,
+            week: week_param,
+            weekday: weekday_param,
+            offset: 0,
+            date: None,
+            observed: false,
+        }
+    }
+
+    /// Create and return a new holiday that occurs on the
+    /// last weekday of a month every year.
+    ///
+    /// # Arguments
+    ///
+    /// * `name_param` - Name of the holiday.
+    /// * `month_param` - Month (1-12).
+    /// * `weekday_param` - Day of the week (0=Sunday through 6=Saturday).
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn new_last_weekday(
+        name_param: &str,
+        month_param: usize,
+        weekday_param: usize,
+    ) -> ElemHoliday {
+        ElemHoliday {
+            name: String::from(name_param),
+            holiday_type: crate::HolidayType::LastWeekday,
+            month: month_param,
+            day: 0,
+            week: 0,
+            weekday: weekday_param,
+            offset: 0,
+            date: None,
+            observed: false,
+        }
+    }
+
+    /// Create and return a new holiday that occurs a number
+    /// of days from Easter Sunday every year.
+    ///
+    /// # Arguments
+    ///
+    /// * `name_param` - Name of the holiday.
+    /// * `offset_param` - Days from Easter Sunday (e.g., -2 for Good Friday).
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn new_easter(name_param: &str, offset_param: i32) -> ElemHoliday {
+        ElemHoliday {
+            name: String::from(name_param),
+            holiday_type: crate::HolidayType::Easter,
+            month: 0,
+            day: 0,
+            week: 0,
+            weekday: 0,
+            offset: offset_param,
+            date: None,
+            observed: false,
+        }
+    }
+
+    /// Create and return a new holiday that occurs on a single date.
+    ///
+    /// # Arguments
+    ///
+    /// * `name_param` - Name of the holiday.
+    /// * `date_param` - Date of the holiday.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn new_date(name_param: &str, date_param: NaiveDate) -> ElemHoliday {
+        ElemHoliday {
+            name: String::from(name_param),
+            holiday_type: crate::HolidayType::Date,
+            month: 0,
+            day: 0,
+            week: 0,
+            weekday: 0,
+            offset: 0,
+            date: Option::from(date_param),
+            observed: false,
+        }
+    }
+
+    /// Returns the date that the holiday is observed in a year.
+    ///
+    /// # Arguments
+    ///
+    /// * `year` - The year.
+    ///
+    /// # Return
+    ///
+    /// * Date or None if the holiday does not occur in the year.
+    pub fn date_in_year(&self, year: usize) -> Option<NaiveDate> {
+        match self.holiday_type {
+            crate::HolidayType::Fixed => {
+                if self.month < 1 || self.month > 12 {
+                    return None;
+                }
+                if self.day < 1 || self.day > CoreUtility::days_in_month(year, self.month) {
+                    return None;
+                }
+
+                let date = CoreUtility::date_from_ymd(year, self.month, self.day)?;
+                if !self.observed {
+                    return Option::from(date);
+                }
+
+                match CoreUtility::day_of_week(date) {
+                    0 => Option::from(date + Duration::days(1)),
+                    6 => Option::from(date - Duration::days(1)),
+                    _ => Option::from(date),
+                }
+            }
+            crate::HolidayType::NthWeekday => {
+                if self.month < 1 || self.month > 12 || self.week < 1 || self.weekday > 6 {
+                    return None;
+                }
+
+                let first = CoreUtility::date_from_ymd(year, self.month, 1)?;
+                let day = 1 + (self.weekday + 7 - CoreUtility::day_of_week(first)) % 7;
+                let day = day + (self.week - 1) * 7;
+                if day > CoreUtility::days_in_month(year, self.month) {
+                    return None;
+                }
+
+                CoreUtility::date_from_ymd(year, self.month, day)
+            }
+            crate::HolidayType::LastWeekday => {
+                if self.month < 1 || self.month > 12 || self.weekday > 6 {
+                    return None;
+                }
+
+                let last_day = CoreUtility::days_in_month(year, self.month);
+                let last = CoreUtility::date_from_ymd(year, self.month, last_day)?;
+                let day = last_day - (CoreUtility::day_of_week(last) + 7 - self.weekday) % 7;
+
+                CoreUtility::date_from_ymd(year, self.month, day)
+            }
+            crate::HolidayType::Easter => {
+                let date = CoreUtility::easter_sunday(year)? + Duration::days(self.offset as i64);
+                if date.year() as usize != year {
+                    return None;
+                }
+
+                Option::from(date)
+            }
+            crate::HolidayType::Date => match self.date {
+                Some(o) if o.year() as usize == year => Option::from(o),
+                _ => None,
+            },
+        }
+    }
+
+    /// Get the name of the holiday.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    /// Get the type of the holiday rule.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn holiday_type(&self) -> crate::HolidayType {
+        self.holiday_type
+    }
+
+    /// Get the month of the holiday.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn month(&self) -> usize {
+        self.month
+    }
+
+    /// Get the day of the month.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn day(&self) -> usize {
+        self.day
+    }
+
+    /// Get the week of the month.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn week(&self) -> usize {
+        self.week
+    }
+
+    /// Get the day of the week.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn weekday(&self) -> usize {
+        self.weekday
+    }
+
+    /// Get the number of days from Easter Sunday.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn offset(&self) -> i32 {
+        self.offset
+    }
+
+    /// Get the date of the holiday.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn date(&self) -> Option<NaiveDate> {
+        self.date
+    }
+
+    /// Get the observe weekend holidays on the nearest weekday value.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn observed(&self) -> bool {
+        self.observed
+    }
+}
@@ -26,6 +26,25 @@ pub struct ElemInterestChange {
     /// Greater than zero and less than one: Round to fraction
     /// Zero or greater than and equal to one: Decimal digits to round.
     round_decimal_digits: Decimal,
+    /// Optional name of the rate index (empty for a fixed rate).
+    index: String,
+    /// Margin added to the index value.
+    margin: Decimal,
+    /// Optional rate reset frequency (None resets every period).
+    reset_frequency: crate::FrequencyType,
+    /// Number of days before the reset date to observe the index.
+    lookback_days: usize,
+    /// Optional maximum rate increase per reset.
+    periodic_cap: Option<Decimal>,
+    /// Optional maximum rate decrease per reset.
+    periodic_floor: Option<Decimal>,
+    /// Optional maximum rate over the life of the loan.
+    lifetime_cap: Option<Decimal>,
+    /// Optional minimum rate over the life of the loan.
+    lifetime_floor: Option<Decimal>,
+    /// Name of the business day calendar of the Business/252
+    /// day count basis (empty=weekends only).
+    calendar: String,
 }
 
 /// The interest change implementation.
@@ -64,6 +83,15 @@ impl ElemInterestChange {
             interest_frequency: interest_frequency_param,
             round_balance: round_balance_param,
             round_decimal_digits: round_decimal_digits_param,
+            index: String::from(""),
+            margin: dec!(0.0),
+            reset_frequency: crate::FrequencyType::None,
+            lookback_days: 0,
+            periodic_cap: None,
+            periodic_floor: None,
+            lifetime_cap: None,
+            lifetime_floor: None,
+            calendar: String::from(""),
         }
     }
 
@@ -74,7 +102,7 @@ impl ElemInterestChange {
     /// * See description.
 
     pub fn copy(&self) -> ElemInterestChange {
-        ElemInterestChange::new(
+        let mut elem_interest_change = ElemInterestChange::new(
             self.method,
             self.day_count_basis,
             self.days_in_year,
@@ -82,7 +110,19 @@ impl ElemInterestChange {
             self.interest_frequency,
             self.round_balance,
             self.round_decimal_digits,
-        )
+        );
+
+        elem_interest_change.set_index(self.index.as_str());
+        elem_interest_change.set_margin(self.margin);
+        elem_interest_change.set_reset_frequency(self.reset_frequency);
+        elem_interest_change.set_lookback_days(self.lookback_days);
+        elem_interest_change.set_periodic_cap(self.periodic_cap);
+        elem_interest_change.set_periodic_floor(self.periodic_floor);
+        elem_interest_change.set_lifetime_cap(self.lifetime_cap);
+        elem_interest_change.set_lifetime_floor(self.lifetime_floor);
+        elem_interest_change.set_calendar(self.calendar.as_str());
+
+        elem_interest_change
     }
 
     /// Tests if this interest change object and another are equal.
@@ -101,6 +141,15 @@ impl ElemInterestChange {
             && self.interest_frequency == elem_interest_change.interest_frequency
             && self.round_balance == elem_interest_change.round_balance
             && self.round_decimal_digits == elem_interest_change.round_decimal_digits
+            && self.index == elem_interest_change.index
+            && self.margin == elem_interest_change.margin
+            && self.reset_frequency == elem_interest_change.reset_frequency
+            && self.lookback_days == elem_interest_change.lookback_days
+            && self.periodic_cap == elem_interest_change.periodic_cap
+            && self.periodic_floor == elem_interest_change.periodic_floor
+            && self.lifetime_cap == elem_interest_change.lifetime_cap
+            && self.lifetime_floor == elem_interest_change.lifetime_floor
+            && self.calendar == elem_interest_change.calendar
     }
 
     /// Get the interest method used.
@@ -178,6 +227,87 @@ impl ElemInterestChange {
         self.round_decimal_digits
     }
 
+    /// Get the name of the rate index (empty for a fixed rate).
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn index(&self) -> &str {
+        self.index.as_str()
+    }
+
+    /// Get the margin added to the index value.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn margin(&self) -> Decimal {
+        self.margin
+    }
+
+    /// Get the optional rate reset frequency.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn reset_frequency(&self) -> crate::FrequencyType {
+        self.reset_frequency
+    }
+
+    /// Get the number of days before the reset date to observe the index.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn lookback_days(&self) -> usize {
+        self.lookback_days
+    }
+
+    /// Get the optional maximum rate increase per reset.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn periodic_cap(&self) -> Option<Decimal> {
+        self.periodic_cap
+    }
+
+    /// Get the optional maximum rate decrease per reset.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn periodic_floor(&self) -> Option<Decimal> {
+        self.periodic_floor
+    }
+
+    /// Get the optional maximum rate over the life of the loan.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn lifetime_cap(&self) -> Option<Decimal> {
+        self.lifetime_cap
+    }
+
+    /// Get the optional minimum rate over the life of the loan.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn lifetime_floor(&self) -> Option<Decimal> {
+        self.lifetime_floor
+    }
+
+    /// Get the name of the business day calendar.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn calendar(&self) -> &str {
+        self.calendar.as_str()
+    }
+
     /// Set the interest method used.
     ///
     /// # Arguments
@@ -253,4 +383,85 @@ impl ElemInterestChange {
     pub fn set_round_decimal_digits(&mut self, round_decimal_digits_param: Decimal) {
         self.round_decimal_digits = round_decimal_digits_param;
     }
+
+    /// Set the name of the rate index (empty for a fixed rate).
+    ///
+    /// # Arguments
+    ///
+    /// * `index_param` - See description.
+    pub fn set_index(&mut self, index_param: &str) {
+        self.index = String::from(index_param);
+    }
+
+    /// Set the margin added to the index value.
+    ///
+    /// # Arguments
+    ///
+    /// * `margin_param` - See description.
+    pub fn set_margin(&mut self, margin_param: Decimal) {
+        self.margin = margin_param;
+    }
+
+    /// Set the optional rate reset frequency.
+    ///
+    /// # Arguments
+    ///
+    /// * `reset_frequency_param` - See description.
+    pub fn set_reset_frequency(&mut self, reset_frequency_param: crate::FrequencyType) {
+        self.reset_frequency = reset_frequency_param;
+    }
+
+    /// Set the number of days before the reset date to observe the index.
+    ///
+    /// # Arguments
+    ///
+    /// * `lookback_days_param` - See description.
+    pub fn set_lookback_days(&mut self, lookback_days_param: usize) {
+        self.lookback_days = lookback_days_param;
+    }
+
+    /// Set the optional maximum rate increase per reset.
+    ///
+    /// # Arguments
+    ///
+    /// * `periodic_cap_param` - See description.
+    pub fn set_periodic_cap(&mut self, periodic_cap_param: Option<Decimal>) {
+        self.periodic_cap = periodic_cap_param;
+    }
+
+    /// Set the optional maximum rate decrease per reset.
+    ///
+    /// # Arguments
+    ///
+    /// * `periodic_floor_param` - See description.
+    pub fn set_periodic_floor(&mut self, periodic_floor_param: Option<Decimal>) {
+        self.periodic_floor = periodic_floor_param;
+    }
+
+    /// Set the optional maximum rate over the life of the loan.
+    ///
+    /// # Arguments
+    ///
+    /// * `lifetime_cap_param` - See description.
+    pub fn set_lifetime_cap(&mut self, lifetime_cap_param: Option<Decimal>) {
+        self.lifetime_cap = lifetime_cap_param;
+    }
+
+    /// Set the optional minimum rate over the life of the loan.
+    ///
+    /// # Arguments
+    ///
+    /// * `lifetime_floor_param` - See description.
+    pub fn set_lifetime_floor(&mut self, lifetime_floor_param: Option<Decimal>) {
+        self.lifetime_floor = lifetime_floor_param;
+    }
+
+    /// Set the name of the business day calendar.
+    ///
+    /// # Arguments
+    ///
+    /// * `calendar_param` - See description.
+    pub fn set_calendar(&mut self, calendar_param: &str) {
+        self.calendar = String::from(calendar_param);
+    }
 }
@@ -0,0 +1,130 @@
+//! Result definition of an internal rate of return calculation.
+// Copyright (c) 2021 ShiftLeft Software
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use rust_decimal::prelude::*;
+
+#[derive(Clone, Debug)]
+pub struct ElemIrrResult {
+    /// Nominal annual rate (NAR) expressed as a decimal value.
+    nominal_rate: Decimal,
+    /// Effective annual rate (EAR) expressed as a decimal value.
+    effective_rate: Decimal,
+    /// Periodic rate (PR) expressed as a decimal value.
+    periodic_rate: Decimal,
+    /// Frequency of the periodic rate.
+    frequency: crate::FrequencyType,
+    /// Every periodic rate found that zeroes the net present value (ascending).
+    roots: Vec<Decimal>,
+    /// Number of sign changes in the series of amounts.
+    sign_changes: usize,
+    /// Total solver iterations used.
+    iterations: usize,
+}
+
+/// The internal rate of return result implementation.
+impl ElemIrrResult {
+    /// Create and return a new internal rate of return result.
+    ///
+    /// # Arguments
+    ///
+    /// * `nominal_rate_param` - Nominal annual rate.
+    /// * `effective_rate_param` - Effective annual rate.
+    /// * `periodic_rate_param` - Periodic rate.
+    /// * `frequency_param` - Frequency of the periodic rate.
+    /// * `roots_param` - Candidate periodic rates.
+    /// * `sign_changes_param` - Number of sign changes.
+    /// * `iterations_param` - Solver iterations used.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn new(
+        nominal_rate_param: Decimal,
+        effective_rate_param: Decimal,
+        periodic_rate_param: Decimal,
+        frequency_param: crate::FrequencyType,
+        roots_param: Vec<Decimal>,
+        sign_changes_param: usize,
+        iterations_param: usize,
+    ) -> ElemIrrResult {
+        ElemIrrResult {
+            nominal_rate: nominal_rate_param,
+            effective_rate: effective_rate_param,
+            periodic_rate: periodic_rate_param,
+            frequency: frequency_param,
+            roots: roots_param,
+            sign_changes: sign_changes_param,
+            iterations: iterations_param,
+        }
+    }
+
+    /// Get the nominal annual rate.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn nominal_rate(&self) -> Decimal {
+        self.nominal_rate
+    }
+
+    /// Get the effective annual rate.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn effective_rate(&self) -> Decimal {
+        self.effective_rate
+    }
+
+    /// Get the periodic rate.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn periodic_rate(&self) -> Decimal {
+        self.periodic_rate
+    }
+
+    /// Get the frequency of the periodic rate.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn frequency(&self) -> crate::FrequencyType {
+        self.frequency
+    }
+
+    /// Get the candidate periodic rates. More than one root
+    /// is possible when the amounts change sign more than once.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn roots(&self) -> &[Decimal] {
+        self.roots.as_slice()
+    }
+
+    /// Get the number of sign changes in the series of amounts.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn sign_changes(&self) -> usize {
+        self.sign_changes
+    }
+
+    /// Get the total solver iterations used.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn iterations(&self) -> usize {
+        self.iterations
+    }
+}
@@ -0,0 +1,57 @@
+//! The payment step element definition.
+// Copyright (c) 2021 ShiftLeft Software
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use rust_decimal::prelude::*;
+
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub struct ElemPaymentStep {
+    /// Number of payments in the step.
+    periods: usize,
+    /// Payment change from the previous step expressed as a percentage
+    /// (ignored for the first step).
+    change: Decimal,
+}
+
+/// The payment step element implementation.
+impl ElemPaymentStep {
+    /// Create and return a new payment step element.
+    ///
+    /// # Arguments
+    ///
+    /// * `periods_param` - Number of payments in the step.
+    /// * `change_param` - Payment change from the previous step (percentage).
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn new(periods_param: usize, change_param: Decimal) -> ElemPaymentStep {
+        ElemPaymentStep {
+            periods: periods_param,
+            change: change_param,
+        }
+    }
+
+    /// Get the number of payments in the step.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn periods(&self) -> usize {
+        self.periods
+    }
+
+    /// Get the payment change from the previous step.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn change(&self) -> Decimal {
+        self.change
+    }
+}
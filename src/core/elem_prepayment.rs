@@ -0,0 +1,135 @@
+//! The prepayment model element definition.
+// Copyright (c) 2021 ShiftLeft Software
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use rust_decimal::prelude::*;
+
+use super::CoreUtility;
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct ElemPrepayment {
+    /// Prepayment model type.
+    prepayment_type: crate::PrepaymentType,
+    /// CPR (annual percentage), SMM (periodic percentage)
+    /// or PSA speed (percentage of the standard ramp).
+    rate: Decimal,
+    /// Conditional prepayment rates (annual percentages) by
+    /// payment period for the vector model (the last rate
+    /// applies to all subsequent periods).
+    vector: Vec<Decimal>,
+}
+
+/// The prepayment model element implementation.
+impl ElemPrepayment {
+    /// Create and return a new prepayment model element.
+    ///
+    /// # Arguments
+    ///
+    /// * `prepayment_type_param` - Prepayment model type.
+    /// * `rate_param` - CPR, SMM or PSA speed (percentage).
+    /// * `vector_param` - Conditional prepayment rates by period.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn new(
+        prepayment_type_param: crate::PrepaymentType,
+        rate_param: Decimal,
+        vector_param: &[Decimal],
+    ) -> ElemPrepayment {
+        ElemPrepayment {
+            prepayment_type: prepayment_type_param,
+            rate: rate_param,
+            vector: vector_param.to_vec(),
+        }
+    }
+
+    /// Get the prepayment model type.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn prepayment_type(&self) -> crate::PrepaymentType {
+        self.prepayment_type
+    }
+
+    /// Get the CPR, SMM or PSA speed.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn rate(&self) -> Decimal {
+        self.rate
+    }
+
+    /// Get the conditional prepayment rates by period.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn vector(&self) -> &[Decimal] {
+        self.vector.as_slice()
+    }
+
+    /// Get the conditional prepayment rate (annual percentage)
+    /// for a payment period.
+    ///
+    /// # Arguments
+    ///
+    /// * `age` - Payment period (starting from 1).
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn cpr(&self, age: usize) -> Decimal {
+        match self.prepayment_type {
+            crate::PrepaymentType::Cpr => self.rate,
+            crate::PrepaymentType::Psa => {
+                // The PSA ramp rises 0.2% CPR per month to 6% at month 30
+                Decimal::from(age.min(crate::PSA_RAMP_PERIODS)) * dec!(0.2) * self.rate
+                    / dec!(100.0)
+            }
+            crate::PrepaymentType::Vector => match self.vector.get(age.max(1) - 1) {
+                None => match self.vector.last() {
+                    None => dec!(0.0),
+                    Some(o) => *o,
+                },
+                Some(o) => *o,
+            },
+            _ => dec!(0.0),
+        }
+    }
+
+    /// Get the fraction of the balance prepaid in a payment period.
+    ///
+    /// # Arguments
+    ///
+    /// * `age` - Payment period (starting from 1).
+    /// * `periods_in_year` - Number of payment periods in a year.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn smm(&self, age: usize, periods_in_year: usize) -> Decimal {
+        let smm = match self.prepayment_type {
+            crate::PrepaymentType::None => dec!(0.0),
+            crate::PrepaymentType::Smm => self.rate / dec!(100.0),
+            _ => {
+                let cpr = (self.cpr(age) / dec!(100.0)).min(dec!(1.0));
+                if cpr <= dec!(0.0) || periods_in_year == 0 {
+                    dec!(0.0)
+                } else {
+                    // SMM = 1 - (1 - CPR) ^ (1 / periods in year)
+                    dec!(1.0) - CoreUtility::decimal_root(dec!(1.0) - cpr, periods_in_year)
+                }
+            }
+        };
+
+        smm.max(dec!(0.0)).min(dec!(1.0))
+    }
+}
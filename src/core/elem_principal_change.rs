@@ -7,6 +7,8 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use rust_decimal::prelude::*;
+
 pub struct ElemPrincipalChange {
     /// Principal change type.
     pc_type: crate::PrincipalType,
@@ -20,6 +22,16 @@ pub struct ElemPrincipalChange {
     auxiliary: bool,
     /// Mark as auxiliary passive principal change event.
     aux_passive: bool,
+    /// Business day convention applied to successive dates.
+    business_day: crate::BusinessDayType,
+    /// Name of the business day calendar (empty=weekends only).
+    calendar: String,
+    /// Optional maximum payment change (expressed as a percentage
+    /// of the previous payment) when the payment is reset.
+    payment_cap: Option<Decimal>,
+    /// Optional maximum balance (expressed as a percentage of the
+    /// original principal) before the payment is recast.
+    neg_am_cap: Option<Decimal>,
 }
 
 /// The principal change implementation.
@@ -55,6 +67,10 @@ impl ElemPrincipalChange {
             balance_statistics: balance_statistics_param,
             auxiliary: auxiliary_param,
             aux_passive: aux_passive_param,
+            business_day: crate::BusinessDayType::Unadjusted,
+            calendar: String::from(""),
+            payment_cap: None,
+            neg_am_cap: None,
         }
     }
 
@@ -65,14 +81,21 @@ impl ElemPrincipalChange {
     /// * See description.
 
     pub fn copy(&self) -> ElemPrincipalChange {
-        ElemPrincipalChange::new(
+        let mut elem_principal_change = ElemPrincipalChange::new(
             self.pc_type,
             self.eom,
             self.principal_first,
             self.balance_statistics,
             self.auxiliary,
             self.aux_passive,
-        )
+        );
+
+        elem_principal_change.set_business_day(self.business_day);
+        elem_principal_change.set_calendar(self.calendar.as_str());
+        elem_principal_change.set_payment_cap(self.payment_cap);
+        elem_principal_change.set_neg_am_cap(self.neg_am_cap);
+
+        elem_principal_change
     }
 
     /// Tests if this principal change object and another are equal.
@@ -91,6 +114,10 @@ impl ElemPrincipalChange {
             && self.balance_statistics == elem_principal_change.balance_statistics
             && self.auxiliary == elem_principal_change.auxiliary
             && self.aux_passive == elem_principal_change.aux_passive
+            && self.business_day == elem_principal_change.business_day
+            && self.calendar == elem_principal_change.calendar
+            && self.payment_cap == elem_principal_change.payment_cap
+            && self.neg_am_cap == elem_principal_change.neg_am_cap
     }
 
     /// Get the principal change type.
@@ -154,6 +181,42 @@ impl ElemPrincipalChange {
         self.aux_passive
     }
 
+    /// Get the business day convention.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn business_day(&self) -> crate::BusinessDayType {
+        self.business_day
+    }
+
+    /// Get the name of the business day calendar.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn calendar(&self) -> &str {
+        self.calendar.as_str()
+    }
+
+    /// Get the optional maximum payment change (percentage of the previous payment).
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn payment_cap(&self) -> Option<Decimal> {
+        self.payment_cap
+    }
+
+    /// Get the optional negative amortization cap (percentage of the original principal).
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn neg_am_cap(&self) -> Option<Decimal> {
+        self.neg_am_cap
+    }
+
     /// Set the principal change type.
     ///
     /// # Arguments
@@ -214,4 +277,40 @@ impl ElemPrincipalChange {
     pub fn set_aux_passive(&mut self, aux_passive_param: bool) {
         self.aux_passive = aux_passive_param;
     }
+
+    /// Set the business day convention.
+    ///
+    /// # Arguments
+    ///
+    /// * `business_day_param` - See description.
+    pub fn set_business_day(&mut self, business_day_param: crate::BusinessDayType) {
+        self.business_day = business_day_param;
+    }
+
+    /// Set the name of the business day calendar.
+    ///
+    /// # Arguments
+    ///
+    /// * `calendar_param` - See description.
+    pub fn set_calendar(&mut self, calendar_param: &str) {
+        self.calendar = String::from(calendar_param);
+    }
+
+    /// Set the optional maximum payment change (percentage of the previous payment).
+    ///
+    /// # Arguments
+    ///
+    /// * `payment_cap_param` - See description.
+    pub fn set_payment_cap(&mut self, payment_cap_param: Option<Decimal>) {
+        self.payment_cap = payment_cap_param;
+    }
+
+    /// Set the optional negative amortization cap (percentage of the original principal).
+    ///
+    /// # Arguments
+    ///
+    /// * `neg_am_cap_param` - See description.
+    pub fn set_neg_am_cap(&mut self, neg_am_cap_param: Option<Decimal>) {
+        self.neg_am_cap = neg_am_cap_param;
+    }
 }
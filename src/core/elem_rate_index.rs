@@ -0,0 +1,99 @@
+//! The rate index element definition.
+// Copyright (c) 2021 ShiftLeft Software
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use chrono::NaiveDate;
+use rust_decimal::prelude::*;
+
+use super::ElemDatedAmount;
+
+#[derive(Clone, Debug)]
+pub struct ElemRateIndex {
+    /// Name of the rate index (e.g., "SOFR").
+    name: String,
+    /// Observed rates (expressed as a percentage) sorted by date.
+    values: Vec<ElemDatedAmount>,
+}
+
+/// The rate index element implementation.
+impl ElemRateIndex {
+    /// Create and return a new rate index with no observed rates.
+    ///
+    /// # Arguments
+    ///
+    /// * `name_param` - Name of the rate index.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn new(name_param: &str) -> ElemRateIndex {
+        ElemRateIndex {
+            name: String::from(name_param),
+            values: Vec::new(),
+        }
+    }
+
+    /// Get the name of the rate index.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    /// Get the observed rates sorted by date.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn values(&self) -> &[ElemDatedAmount] {
+        self.values.as_slice()
+    }
+
+    /// Add an observed rate. A rate observed on the same date is replaced.
+    ///
+    /// # Arguments
+    ///
+    /// * `date` - Date of the observation.
+    /// * `rate` - Observed rate (expressed as a percentage).
+    pub fn add_value(&mut self, date: NaiveDate, rate: Decimal) {
+        match self.values.binary_search_by(|o| o.date().cmp(&date)) {
+            Err(o) => self.values.insert(o, ElemDatedAmount::new(date, rate)),
+            Ok(o) => self.values[o] = ElemDatedAmount::new(date, rate),
+        }
+    }
+
+    /// Get the rate in effect on a date (i.e., the last rate
+    /// observed on or before the date).
+    ///
+    /// # Arguments
+    ///
+    /// * `date` - The date.
+    ///
+    /// # Return
+    ///
+    /// * The rate in effect or None if no rate was observed on or before the date.
+    pub fn value(&self, date: NaiveDate) -> Option<Decimal> {
+        let index = self.values.partition_point(|o| o.date() <= date);
+        if index == 0 {
+            return None;
+        }
+
+        Option::from(self.values[index - 1].amount())
+    }
+
+    /// Set the name of the rate index.
+    ///
+    /// # Arguments
+    ///
+    /// * `name_param` - See description.
+    pub fn set_name(&mut self, name_param: &str) {
+        self.name = String::from(name_param);
+    }
+}
@@ -0,0 +1,202 @@
+//! The solver settings element definition.
+// Copyright (c) 2021 ShiftLeft Software
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use rust_decimal::prelude::*;
+
+#[derive(Clone, Copy, Default, PartialEq, Debug)]
+pub struct ElemSolverSettings {
+    /// Maximum number of refining iterations (None=use the calculation default).
+    max_iterations: Option<usize>,
+    /// Largest absolute remaining balance difference accepted as solved (None=exact).
+    abs_tolerance: Option<Decimal>,
+    /// Largest remaining balance difference, relative to the total
+    /// principal of the cashflow, accepted as solved (None=not used).
+    rel_tolerance: Option<Decimal>,
+    /// Lower bound of the solved value (None=use the calculation default).
+    lower_bound: Option<Decimal>,
+    /// Upper bound of the solved value (None=use the calculation default).
+    upper_bound: Option<Decimal>,
+    /// Initial guess of the solved value (None=use the calculation default).
+    initial_guess: Option<Decimal>,
+}
+
+/// The solver settings element implementation.
+/// Settings that are not set fall back to the defaults of
+/// each calculation (see MAXIMUM_ITERATIONS_CALCULATE_INTEREST,
+/// MAX_CALC_INTEREST, MAX_CALC_PRINCIPAL, etc.).
+impl ElemSolverSettings {
+    /// Create and return a new solver settings element with
+    /// no settings set.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn new() -> ElemSolverSettings {
+        ElemSolverSettings::default()
+    }
+
+    /// Clear all settings.
+    pub fn clear(&mut self) {
+        *self = ElemSolverSettings::default();
+    }
+
+    /// Resolve these settings against a fallback set of settings
+    /// and return the result. Settings set in this element take
+    /// precedence over the settings of the fallback element.
+    ///
+    /// # Arguments
+    ///
+    /// * `fallback_param` - Fallback settings (e.g., user preferences).
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn resolve(&self, fallback_param: &ElemSolverSettings) -> ElemSolverSettings {
+        ElemSolverSettings {
+            max_iterations: self.max_iterations.or(fallback_param.max_iterations),
+            abs_tolerance: self.abs_tolerance.or(fallback_param.abs_tolerance),
+            rel_tolerance: self.rel_tolerance.or(fallback_param.rel_tolerance),
+            lower_bound: self.lower_bound.or(fallback_param.lower_bound),
+            upper_bound: self.upper_bound.or(fallback_param.upper_bound),
+            initial_guess: self.initial_guess.or(fallback_param.initial_guess),
+        }
+    }
+
+    /// Returns true if a remaining balance difference is
+    /// accepted as solved by these settings.
+    ///
+    /// # Arguments
+    ///
+    /// * `residual_param` - Remaining balance less the target value.
+    /// * `principal_param` - Total principal of the cashflow.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn converged(&self, residual_param: Decimal, principal_param: Decimal) -> bool {
+        let residual = residual_param.abs();
+
+        if residual <= self.abs_tolerance.unwrap_or(dec!(0.0)) {
+            return true;
+        }
+
+        match self.rel_tolerance {
+            None => false,
+            Some(o) => residual <= o * principal_param.abs(),
+        }
+    }
+
+    /// Get the maximum number of refining iterations.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn max_iterations(&self) -> Option<usize> {
+        self.max_iterations
+    }
+
+    /// Get the absolute balance tolerance.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn abs_tolerance(&self) -> Option<Decimal> {
+        self.abs_tolerance
+    }
+
+    /// Get the relative balance tolerance.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn rel_tolerance(&self) -> Option<Decimal> {
+        self.rel_tolerance
+    }
+
+    /// Get the lower bound of the solved value.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn lower_bound(&self) -> Option<Decimal> {
+        self.lower_bound
+    }
+
+    /// Get the upper bound of the solved value.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn upper_bound(&self) -> Option<Decimal> {
+        self.upper_bound
+    }
+
+    /// Get the initial guess of the solved value.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn initial_guess(&self) -> Option<Decimal> {
+        self.initial_guess
+    }
+
+    /// Set the maximum number of refining iterations.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_iterations_param` - See description.
+    pub fn set_max_iterations(&mut self, max_iterations_param: Option<usize>) {
+        self.max_iterations = max_iterations_param;
+    }
+
+    /// Set the absolute balance tolerance.
+    ///
+    /// # Arguments
+    ///
+    /// * `abs_tolerance_param` - See description.
+    pub fn set_abs_tolerance(&mut self, abs_tolerance_param: Option<Decimal>) {
+        self.abs_tolerance = abs_tolerance_param;
+    }
+
+    /// Set the relative balance tolerance.
+    ///
+    /// # Arguments
+    ///
+    /// * `rel_tolerance_param` - See description.
+    pub fn set_rel_tolerance(&mut self, rel_tolerance_param: Option<Decimal>) {
+        self.rel_tolerance = rel_tolerance_param;
+    }
+
+    /// Set the lower bound of the solved value.
+    ///
+    /// # Arguments
+    ///
+    /// * `lower_bound_param` - See description.
+    pub fn set_lower_bound(&mut self, lower_bound_param: Option<Decimal>) {
+        self.lower_bound = lower_bound_param;
+    }
+
+    /// Set the upper bound of the solved value.
+    ///
+    /// # Arguments
+    ///
+    /// * `upper_bound_param` - See description.
+    pub fn set_upper_bound(&mut self, upper_bound_param: Option<Decimal>) {
+        self.upper_bound = upper_bound_param;
+    }
+
+    /// Set the initial guess of the solved value.
+    ///
+    /// # Arguments
+    ///
+    /// * `initial_guess_param` - See description.
+    pub fn set_initial_guess(&mut self, initial_guess_param: Option<Decimal>) {
+        self.initial_guess = initial_guess_param;
+    }
+}
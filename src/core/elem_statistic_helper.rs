@@ -7,6 +7,7 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use chrono::NaiveDate;
 use rust_decimal::prelude::*;
 
 pub struct ElemStatisticHelper {
@@ -25,7 +26,7 @@ pub struct ElemStatisticHelper {
     /// Accumulated value to principal for period.
     value_to_principal: Decimal,
     /// The last statistic event date.
-    last_date: usize,
+    last_date: NaiveDate,
     /// Index of the ElemAmortization object.
     elem_am_index: usize,
 }
@@ -60,7 +61,7 @@ impl ElemStatisticHelper {
         sl_interest_param: Decimal,
         value_to_interest_param: Decimal,
         value_to_principal_param: Decimal,
-        last_date_param: usize,
+        last_date_param: NaiveDate,
         elem_am_index_param: usize,
     ) -> ElemStatisticHelper {
         ElemStatisticHelper {
@@ -152,7 +153,7 @@ impl ElemStatisticHelper {
     ///
     /// * See description.
 
-    pub fn last_date(&self) -> usize {
+    pub fn last_date(&self) -> NaiveDate {
         self.last_date
     }
 
@@ -242,7 +243,7 @@ impl ElemStatisticHelper {
     ///
     /// * `last_date_param` - See description.
 
-    pub fn set_last_date(&mut self, last_date_param: usize) {
+    pub fn set_last_date(&mut self, last_date_param: NaiveDate) {
         self.last_date = last_date_param;
     }
 
@@ -19,6 +19,8 @@ pub struct ElemSymbol {
     dec_value: Decimal,
     /// String value.
     str_value: String,
+    /// List value.
+    list_value: Vec<ElemSymbol>,
 }
 
 /// The symbol element default implementation.
@@ -50,6 +52,7 @@ impl ElemSymbol {
             int_value: 0,
             dec_value: dec!(0.0),
             str_value: String::from(""),
+            list_value: Vec::new(),
         }
     }
 
@@ -69,6 +72,7 @@ impl ElemSymbol {
             int_value: 0,
             dec_value: dec!(0.0),
             str_value: String::from(""),
+            list_value: Vec::new(),
         }
     }
 
@@ -90,6 +94,9 @@ impl ElemSymbol {
             crate::TokenType::String => {
                 sym.set_string(self.str_value.as_str());
             }
+            crate::TokenType::List => {
+                sym.set_list(self.list_value.as_slice());
+            }
             _ => {}
         }
 
@@ -147,6 +154,15 @@ impl ElemSymbol {
         self.str_value.as_str()
     }
 
+    /// Get the list value.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn sym_list(&self) -> &[ElemSymbol] {
+        self.list_value.as_slice()
+    }
+
     /// Set the type of symbol.
     ///
     /// # Arguments
@@ -158,6 +174,7 @@ impl ElemSymbol {
         self.int_value = 0;
         self.dec_value = dec!(0.0);
         self.str_value = String::from("");
+        self.list_value.clear();
     }
 
     /// Set the operator value.
@@ -171,6 +188,7 @@ impl ElemSymbol {
         self.int_value = op_value_param as i32;
         self.dec_value = dec!(0.0);
         self.str_value = String::from("");
+        self.list_value.clear();
     }
 
     /// Set the integer value.
@@ -184,6 +202,7 @@ impl ElemSymbol {
         self.int_value = int_value_param;
         self.dec_value = dec!(0.0);
         self.str_value = String::from("");
+        self.list_value.clear();
     }
 
     /// Set the integer value.
@@ -207,6 +226,7 @@ impl ElemSymbol {
         self.int_value = 0;
         self.dec_value = dec_value_param;
         self.str_value = String::from("");
+        self.list_value.clear();
     }
 
     /// Set the string value.
@@ -220,5 +240,19 @@ impl ElemSymbol {
         self.int_value = 0;
         self.dec_value = dec!(0.0);
         self.str_value = String::from(str_value_param);
+        self.list_value.clear();
+    }
+
+    /// Set the list value.
+    ///
+    /// # Arguments
+    ///
+    /// * `list_value_param` - See description.
+    pub fn set_list(&mut self, list_value_param: &[ElemSymbol]) {
+        self.sym_type = crate::TokenType::List;
+        self.int_value = 0;
+        self.dec_value = dec!(0.0);
+        self.str_value = String::from("");
+        self.list_value = list_value_param.to_vec();
     }
 }
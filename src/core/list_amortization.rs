@@ -7,9 +7,10 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use chrono::NaiveDate;
 use rust_decimal::prelude::*;
-use std::cell::Cell;
 use std::cmp::Ordering::Equal;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 use super::{ElemAmortization, ElemExtension, ListDescriptor, ListParameter};
 use crate::{ExtensionTrait, ListTrait};
@@ -19,7 +20,7 @@ pub struct ListAmortization {
     list_am: Vec<ElemAmortization>,
 
     /// The index of the currently selected amortization element.
-    list_index: Cell<usize>,
+    list_index: AtomicUsize,
 }
 
 /// List of amortization elements list implementation.
@@ -29,7 +30,7 @@ impl ListTrait for ListAmortization {
 
     fn clear(&mut self) {
         self.list_am.clear();
-        self.list_index.set(usize::MAX);
+        self.list_index.store(usize::MAX, Ordering::Relaxed);
     }
 
     /// Get the count of the amortization list.
@@ -47,7 +48,7 @@ impl ListTrait for ListAmortization {
     ///
     /// * See description.
     fn index(&self) -> usize {
-        self.list_index.get()
+        self.list_index.load(Ordering::Relaxed)
     }
     /// Select an amortization element based upon an index value.
     ///
@@ -80,7 +81,7 @@ impl ListTrait for ListAmortization {
             return false;
         }
 
-        self.list_index.set(index_param);
+        self.list_index.store(index_param, Ordering::Relaxed);
 
         true
     }
@@ -111,7 +112,7 @@ impl ListAmortization {
     pub fn new() -> ListAmortization {
         ListAmortization {
             list_am: Vec::new(),
-            list_index: Cell::new(usize::MAX),
+            list_index: AtomicUsize::new(usize::MAX),
         }
     }
 
@@ -146,8 +147,8 @@ impl ListAmortization {
     pub fn add_amortization(
         &mut self,
         event_type_param: &str,
-        orig_date_param: usize,
-        event_date_param: usize,
+        orig_date_param: NaiveDate,
+        event_date_param: NaiveDate,
         sort_order_param: usize,
         value_param: Decimal,
         value_expr_param: &str,
@@ -227,8 +228,8 @@ impl ListAmortization {
     pub fn add_amortization_ex(
         &mut self,
         event_type_param: &str,
-        orig_date_param: usize,
-        event_date_param: usize,
+        orig_date_param: NaiveDate,
+        event_date_param: NaiveDate,
         sort_order_param: usize,
         value_param: Decimal,
         value_expr_param: &str,
@@ -280,7 +281,7 @@ impl ListAmortization {
         let new_index: usize = self.list_am.len();
         self.list_am.push(new_elem);
 
-        self.list_index.set(new_index);
+        self.list_index.store(new_index, Ordering::Relaxed);
 
         true
     }
@@ -343,6 +344,16 @@ impl ListAmortization {
                 list_parameter_opt,
                 list_descriptor_opt,
             );
+            list_am.set_scheduled_date(elem.scheduled_date());
+            list_am.set_index_value(elem.index_value());
+            list_am.set_deferred_interest(elem.deferred_interest());
+            list_am.set_scheduled_principal(elem.scheduled_principal());
+            list_am.set_prepaid_principal(elem.prepaid_principal());
+            list_am.set_surviving_balance(elem.surviving_balance());
+            list_am.set_defaulted_principal(elem.defaulted_principal());
+            list_am.set_recovery(elem.recovery());
+            list_am.set_loss(elem.loss());
+            list_am.set_delinquent_balance(elem.delinquent_balance());
         }
 
         list_am
@@ -355,7 +366,7 @@ impl ListAmortization {
     /// * See description.
 
     pub fn elem_type(&self) -> crate::ExtensionType {
-        match self.list_am.get(self.list_index.get()) {
+        match self.list_am.get(self.list_index.load(Ordering::Relaxed)) {
             None => {
                 panic!("Amortization list index not set");
             }
@@ -370,7 +381,7 @@ impl ListAmortization {
     /// * See description.
 
     pub fn event_type(&self) -> &str {
-        match self.list_am.get(self.list_index.get()) {
+        match self.list_am.get(self.list_index.load(Ordering::Relaxed)) {
             None => {
                 panic!("Amortization list index not set");
             }
@@ -378,14 +389,14 @@ impl ListAmortization {
         }
     }
 
-    /// Get the date of the original event in YYYYMMDD format.
+    /// Get the date of the original event.
     ///
     /// # Return
     ///
     /// * See description.
 
-    pub fn orig_date(&self) -> usize {
-        match self.list_am.get(self.list_index.get()) {
+    pub fn orig_date(&self) -> NaiveDate {
+        match self.list_am.get(self.list_index.load(Ordering::Relaxed)) {
             None => {
                 panic!("Amortization list index not set");
             }
@@ -393,14 +404,14 @@ impl ListAmortization {
         }
     }
 
-    /// Get the date of the amortization element in YYYYMMDD format (major sort key).
+    /// Get the date of the amortization element (major sort key).
     ///
     /// # Return
     ///
     /// * See description.
 
-    pub fn event_date(&self) -> usize {
-        match self.list_am.get(self.list_index.get()) {
+    pub fn event_date(&self) -> NaiveDate {
+        match self.list_am.get(self.list_index.load(Ordering::Relaxed)) {
             None => {
                 panic!("Amortization list index not set");
             }
@@ -408,6 +419,36 @@ impl ListAmortization {
         }
     }
 
+    /// Get the scheduled date of the amortization element in
+    /// YYYYMMDD format (before any business day adjustment).
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn scheduled_date(&self) -> NaiveDate {
+        match self.list_am.get(self.list_index.load(Ordering::Relaxed)) {
+            None => {
+                panic!("Amortization list index not set");
+            }
+            Some(o) => o.scheduled_date(),
+        }
+    }
+
+    /// Get the index value used to compute the rate of an
+    /// index-driven interest change (None if not applicable).
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn index_value(&self) -> Option<Decimal> {
+        match self.list_am.get(self.list_index.load(Ordering::Relaxed)) {
+            None => {
+                panic!("Amortization list index not set");
+            }
+            Some(o) => o.index_value(),
+        }
+    }
+
     /// Get the sort order within the amortization element date (minor sort key).
     ///
     /// # Return
@@ -415,7 +456,7 @@ impl ListAmortization {
     /// * See description.
 
     pub fn sort_order(&self) -> usize {
-        match self.list_am.get(self.list_index.get()) {
+        match self.list_am.get(self.list_index.load(Ordering::Relaxed)) {
             None => {
                 panic!("Amortization list index not set");
             }
@@ -430,7 +471,7 @@ impl ListAmortization {
     /// * See description.
 
     pub fn orig_value(&self) -> Decimal {
-        match self.list_am.get(self.list_index.get()) {
+        match self.list_am.get(self.list_index.load(Ordering::Relaxed)) {
             None => {
                 panic!("Amortization list index not set");
             }
@@ -445,7 +486,7 @@ impl ListAmortization {
     /// * See description.
 
     pub fn value(&self) -> Decimal {
-        match self.list_am.get(self.list_index.get()) {
+        match self.list_am.get(self.list_index.load(Ordering::Relaxed)) {
             None => {
                 panic!("Amortization list index not set");
             }
@@ -460,7 +501,7 @@ impl ListAmortization {
     /// * See description.
 
     pub fn value_expr(&self) -> &str {
-        match self.list_am.get(self.list_index.get()) {
+        match self.list_am.get(self.list_index.load(Ordering::Relaxed)) {
             None => {
                 panic!("Amortization list index not set");
             }
@@ -475,7 +516,7 @@ impl ListAmortization {
     /// * See description.
 
     pub fn periods(&self) -> usize {
-        match self.list_am.get(self.list_index.get()) {
+        match self.list_am.get(self.list_index.load(Ordering::Relaxed)) {
             None => {
                 panic!("Amortization list index not set");
             }
@@ -490,7 +531,7 @@ impl ListAmortization {
     /// * See description.
 
     pub fn intervals(&self) -> usize {
-        match self.list_am.get(self.list_index.get()) {
+        match self.list_am.get(self.list_index.load(Ordering::Relaxed)) {
             None => {
                 panic!("Amortization list index not set");
             }
@@ -505,7 +546,7 @@ impl ListAmortization {
     /// * See description.
 
     pub fn frequency(&self) -> crate::FrequencyType {
-        match self.list_am.get(self.list_index.get()) {
+        match self.list_am.get(self.list_index.load(Ordering::Relaxed)) {
             None => {
                 panic!("Amortization list index not set");
             }
@@ -520,7 +561,7 @@ impl ListAmortization {
     /// * See description.
 
     pub fn principal_decrease(&self) -> Decimal {
-        match self.list_am.get(self.list_index.get()) {
+        match self.list_am.get(self.list_index.load(Ordering::Relaxed)) {
             None => {
                 panic!("Amortization list index not set");
             }
@@ -535,7 +576,7 @@ impl ListAmortization {
     /// * See description.
 
     pub fn principal_increase(&self) -> Decimal {
-        match self.list_am.get(self.list_index.get()) {
+        match self.list_am.get(self.list_index.load(Ordering::Relaxed)) {
             None => {
                 panic!("Amortization list index not set");
             }
@@ -550,7 +591,7 @@ impl ListAmortization {
     /// * See description.
 
     pub fn interest(&self) -> Decimal {
-        match self.list_am.get(self.list_index.get()) {
+        match self.list_am.get(self.list_index.load(Ordering::Relaxed)) {
             None => {
                 panic!("Amortization list index not set");
             }
@@ -565,7 +606,7 @@ impl ListAmortization {
     /// * See description.
 
     pub fn sl_interest(&self) -> Decimal {
-        match self.list_am.get(self.list_index.get()) {
+        match self.list_am.get(self.list_index.load(Ordering::Relaxed)) {
             None => {
                 panic!("Amortization list index not set");
             }
@@ -580,7 +621,7 @@ impl ListAmortization {
     /// * See description.
 
     pub fn value_to_interest(&self) -> Decimal {
-        match self.list_am.get(self.list_index.get()) {
+        match self.list_am.get(self.list_index.load(Ordering::Relaxed)) {
             None => {
                 panic!("Amortization list index not set");
             }
@@ -595,7 +636,7 @@ impl ListAmortization {
     /// * See description.
 
     pub fn value_to_principal(&self) -> Decimal {
-        match self.list_am.get(self.list_index.get()) {
+        match self.list_am.get(self.list_index.load(Ordering::Relaxed)) {
             None => {
                 panic!("Amortization list index not set");
             }
@@ -603,6 +644,119 @@ impl ListAmortization {
         }
     }
 
+    /// Get the unpaid interest added to the principal balance
+    /// (negative amortization).
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn deferred_interest(&self) -> Decimal {
+        match self.list_am.get(self.list_index.load(Ordering::Relaxed)) {
+            None => {
+                panic!("Amortization list index not set");
+            }
+            Some(o) => o.deferred_interest(),
+        }
+    }
+
+    /// Get the scheduled principal repaid before any prepayment.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn scheduled_principal(&self) -> Decimal {
+        match self.list_am.get(self.list_index.load(Ordering::Relaxed)) {
+            None => {
+                panic!("Amortization list index not set");
+            }
+            Some(o) => o.scheduled_principal(),
+        }
+    }
+
+    /// Get the principal prepaid under the prepayment model.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn prepaid_principal(&self) -> Decimal {
+        match self.list_am.get(self.list_index.load(Ordering::Relaxed)) {
+            None => {
+                panic!("Amortization list index not set");
+            }
+            Some(o) => o.prepaid_principal(),
+        }
+    }
+
+    /// Get the balance surviving after scheduled principal, prepayments and defaults.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn surviving_balance(&self) -> Decimal {
+        match self.list_am.get(self.list_index.load(Ordering::Relaxed)) {
+            None => {
+                panic!("Amortization list index not set");
+            }
+            Some(o) => o.surviving_balance(),
+        }
+    }
+
+    /// Get the performing principal that defaulted under the default scenario.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn defaulted_principal(&self) -> Decimal {
+        match self.list_am.get(self.list_index.load(Ordering::Relaxed)) {
+            None => {
+                panic!("Amortization list index not set");
+            }
+            Some(o) => o.defaulted_principal(),
+        }
+    }
+
+    /// Get the defaulted principal recovered when liquidated.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn recovery(&self) -> Decimal {
+        match self.list_am.get(self.list_index.load(Ordering::Relaxed)) {
+            None => {
+                panic!("Amortization list index not set");
+            }
+            Some(o) => o.recovery(),
+        }
+    }
+
+    /// Get the defaulted principal lost when liquidated.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn loss(&self) -> Decimal {
+        match self.list_am.get(self.list_index.load(Ordering::Relaxed)) {
+            None => {
+                panic!("Amortization list index not set");
+            }
+            Some(o) => o.loss(),
+        }
+    }
+
+    /// Get the defaulted principal awaiting liquidation (delinquency pipeline).
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn delinquent_balance(&self) -> Decimal {
+        match self.list_am.get(self.list_index.load(Ordering::Relaxed)) {
+            None => {
+                panic!("Amortization list index not set");
+            }
+            Some(o) => o.delinquent_balance(),
+        }
+    }
+
     /// Get the accrued interest balance.
     ///
     /// # Return
@@ -610,7 +764,7 @@ impl ListAmortization {
     /// * See description.
 
     pub fn acc_balance(&self) -> Decimal {
-        match self.list_am.get(self.list_index.get()) {
+        match self.list_am.get(self.list_index.load(Ordering::Relaxed)) {
             None => {
                 panic!("Amortization list index not set");
             }
@@ -625,7 +779,7 @@ impl ListAmortization {
     /// * See description.
 
     pub fn balance(&self) -> Decimal {
-        match self.list_am.get(self.list_index.get()) {
+        match self.list_am.get(self.list_index.load(Ordering::Relaxed)) {
             None => {
                 panic!("Amortization list index not set");
             }
@@ -640,7 +794,7 @@ impl ListAmortization {
     /// * See description.
 
     pub fn list_event_index(&self) -> usize {
-        match self.list_am.get(self.list_index.get()) {
+        match self.list_am.get(self.list_index.load(Ordering::Relaxed)) {
             None => {
                 panic!("Amortization list index not set");
             }
@@ -655,7 +809,7 @@ impl ListAmortization {
     /// * See description.
 
     pub fn event_sequence(&self) -> usize {
-        match self.list_am.get(self.list_index.get()) {
+        match self.list_am.get(self.list_index.load(Ordering::Relaxed)) {
             None => {
                 panic!("Amortization list index not set");
             }
@@ -670,7 +824,7 @@ impl ListAmortization {
     /// * See description.
 
     pub fn stat_sequence(&self) -> usize {
-        match self.list_am.get(self.list_index.get()) {
+        match self.list_am.get(self.list_index.load(Ordering::Relaxed)) {
             None => {
                 panic!("Amortization list index not set");
             }
@@ -678,6 +832,34 @@ impl ListAmortization {
         }
     }
 
+    /// Get the selected amortization element.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn elem_amortization(&self) -> &ElemAmortization {
+        match self.list_am.get(self.list_index.load(Ordering::Relaxed)) {
+            None => {
+                panic!("Amortization list index not set");
+            }
+            Some(o) => o,
+        }
+    }
+
+    /// Get an amortization element based upon an index value without
+    /// changing the currently selected element.
+    ///
+    /// # Arguments
+    ///
+    /// * `index_param` - Index value of the amortization element (starting from 0).
+    ///
+    /// # Return
+    ///
+    /// * The amortization element, otherwise None.
+    pub fn get_elem_amortization(&self, index_param: usize) -> Option<&ElemAmortization> {
+        self.list_am.get(index_param)
+    }
+
     /// Get the extension.
     ///
     /// # Return
@@ -685,7 +867,7 @@ impl ListAmortization {
     /// * See description.
 
     pub fn elem_extension(&self) -> &ElemExtension {
-        match self.list_am.get(self.list_index.get()) {
+        match self.list_am.get(self.list_index.load(Ordering::Relaxed)) {
             None => {
                 panic!("Amortization list index not set");
             }
@@ -700,7 +882,7 @@ impl ListAmortization {
     /// * See description.
 
     pub fn list_parameter(&self) -> Option<&ListParameter> {
-        match self.list_am.get(self.list_index.get()) {
+        match self.list_am.get(self.list_index.load(Ordering::Relaxed)) {
             None => {
                 panic!("Amortization list index not set");
             }
@@ -715,7 +897,7 @@ impl ListAmortization {
     /// * See description.
 
     pub fn list_descriptor(&self) -> Option<&ListDescriptor> {
-        match self.list_am.get(self.list_index.get()) {
+        match self.list_am.get(self.list_index.load(Ordering::Relaxed)) {
             None => {
                 panic!("Amortization list index not set");
             }
@@ -730,13 +912,16 @@ impl ListAmortization {
     /// * True if successful, otherwise false.
 
     pub fn remove(&mut self) -> bool {
-        if self.list_index.get() >= self.list_am.len() {
+        if self.list_index.load(Ordering::Relaxed) >= self.list_am.len() {
             return false;
         }
 
-        self.list_am.remove(self.list_index.get());
-        if self.list_index.get() > 0 {
-            self.list_index.set(self.list_index.get() - 1);
+        self.list_am.remove(self.list_index.load(Ordering::Relaxed));
+        if self.list_index.load(Ordering::Relaxed) > 0 {
+            self.list_index.store(
+                self.list_index.load(Ordering::Relaxed) - 1,
+                Ordering::Relaxed,
+            );
         }
         true
     }
@@ -784,7 +969,10 @@ impl ListAmortization {
     /// * True if successful, otherwise false.
 
     pub fn set_value(&mut self, value_param: Decimal) -> bool {
-        match self.list_am.get_mut(self.list_index.get()) {
+        match self
+            .list_am
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
             None => false,
             Some(o) => {
                 o.set_value(value_param);
@@ -816,7 +1004,10 @@ impl ListAmortization {
     /// * True if successful, otherwise false.
 
     pub fn set_principal_decrease(&mut self, principal_decrease_param: Decimal) -> bool {
-        match self.list_am.get_mut(self.list_index.get()) {
+        match self
+            .list_am
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
             None => false,
             Some(o) => {
                 o.set_principal_decrease(principal_decrease_param);
@@ -836,7 +1027,10 @@ impl ListAmortization {
     /// * True if successful, otherwise false.
 
     pub fn set_principal_increase(&mut self, principal_increase_param: Decimal) -> bool {
-        match self.list_am.get_mut(self.list_index.get()) {
+        match self
+            .list_am
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
             None => false,
             Some(o) => {
                 o.set_principal_increase(principal_increase_param);
@@ -856,7 +1050,10 @@ impl ListAmortization {
     /// * True if successful, otherwise false.
 
     pub fn set_interest(&mut self, interest_param: Decimal) -> bool {
-        match self.list_am.get_mut(self.list_index.get()) {
+        match self
+            .list_am
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
             None => false,
             Some(o) => {
                 o.set_interest(interest_param);
@@ -876,7 +1073,10 @@ impl ListAmortization {
     /// * True if successful, otherwise false.
 
     pub fn set_sl_interest(&mut self, sl_interest_param: Decimal) -> bool {
-        match self.list_am.get_mut(self.list_index.get()) {
+        match self
+            .list_am
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
             None => false,
             Some(o) => {
                 o.set_sl_interest(sl_interest_param);
@@ -896,7 +1096,10 @@ impl ListAmortization {
     /// * True if successful, otherwise false.
 
     pub fn set_value_to_interest(&mut self, value_to_interest_param: Decimal) -> bool {
-        match self.list_am.get_mut(self.list_index.get()) {
+        match self
+            .list_am
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
             None => false,
             Some(o) => {
                 o.set_value_to_interest(value_to_interest_param);
@@ -916,7 +1119,10 @@ impl ListAmortization {
     /// * True if successful, otherwise false.
 
     pub fn set_value_to_principal(&mut self, value_to_principal_param: Decimal) -> bool {
-        match self.list_am.get_mut(self.list_index.get()) {
+        match self
+            .list_am
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
             None => false,
             Some(o) => {
                 o.set_value_to_principal(value_to_principal_param);
@@ -925,6 +1131,182 @@ impl ListAmortization {
         }
     }
 
+    /// Set the unpaid interest added to the principal balance.
+    ///
+    /// # Arguments
+    ///
+    /// * `deferred_interest_param` - See description.
+    ///
+    /// # Return
+    ///
+    /// * True if successful, otherwise false.
+    pub fn set_deferred_interest(&mut self, deferred_interest_param: Decimal) -> bool {
+        match self
+            .list_am
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
+            None => false,
+            Some(o) => {
+                o.set_deferred_interest(deferred_interest_param);
+                true
+            }
+        }
+    }
+
+    /// Set the scheduled principal repaid before any prepayment.
+    ///
+    /// # Arguments
+    ///
+    /// * `scheduled_principal_param` - See description.
+    ///
+    /// # Return
+    ///
+    /// * True if successful, otherwise false.
+    pub fn set_scheduled_principal(&mut self, scheduled_principal_param: Decimal) -> bool {
+        match self
+            .list_am
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
+            None => false,
+            Some(o) => {
+                o.set_scheduled_principal(scheduled_principal_param);
+                true
+            }
+        }
+    }
+
+    /// Set the principal prepaid under the prepayment model.
+    ///
+    /// # Arguments
+    ///
+    /// * `prepaid_principal_param` - See description.
+    ///
+    /// # Return
+    ///
+    /// * True if successful, otherwise false.
+    pub fn set_prepaid_principal(&mut self, prepaid_principal_param: Decimal) -> bool {
+        match self
+            .list_am
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
+            None => false,
+            Some(o) => {
+                o.set_prepaid_principal(prepaid_principal_param);
+                true
+            }
+        }
+    }
+
+    /// Set the balance surviving after scheduled principal, prepayments and defaults.
+    ///
+    /// # Arguments
+    ///
+    /// * `surviving_balance_param` - See description.
+    ///
+    /// # Return
+    ///
+    /// * True if successful, otherwise false.
+    pub fn set_surviving_balance(&mut self, surviving_balance_param: Decimal) -> bool {
+        match self
+            .list_am
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
+            None => false,
+            Some(o) => {
+                o.set_surviving_balance(surviving_balance_param);
+                true
+            }
+        }
+    }
+
+    /// Set the performing principal that defaulted under the default scenario.
+    ///
+    /// # Arguments
+    ///
+    /// * `defaulted_principal_param` - See description.
+    ///
+    /// # Return
+    ///
+    /// * True if successful, otherwise false.
+    pub fn set_defaulted_principal(&mut self, defaulted_principal_param: Decimal) -> bool {
+        match self
+            .list_am
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
+            None => false,
+            Some(o) => {
+                o.set_defaulted_principal(defaulted_principal_param);
+                true
+            }
+        }
+    }
+
+    /// Set the defaulted principal recovered when liquidated.
+    ///
+    /// # Arguments
+    ///
+    /// * `recovery_param` - See description.
+    ///
+    /// # Return
+    ///
+    /// * True if successful, otherwise false.
+    pub fn set_recovery(&mut self, recovery_param: Decimal) -> bool {
+        match self
+            .list_am
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
+            None => false,
+            Some(o) => {
+                o.set_recovery(recovery_param);
+                true
+            }
+        }
+    }
+
+    /// Set the defaulted principal lost when liquidated.
+    ///
+    /// # Arguments
+    ///
+    /// * `loss_param` - See description.
+    ///
+    /// # Return
+    ///
+    /// * True if successful, otherwise false.
+    pub fn set_loss(&mut self, loss_param: Decimal) -> bool {
+        match self
+            .list_am
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
+            None => false,
+            Some(o) => {
+                o.set_loss(loss_param);
+                true
+            }
+        }
+    }
+
+    /// Set the defaulted principal awaiting liquidation (delinquency pipeline).
+    ///
+    /// # Arguments
+    ///
+    /// * `delinquent_balance_param` - See description.
+    ///
+    /// # Return
+    ///
+    /// * True if successful, otherwise false.
+    pub fn set_delinquent_balance(&mut self, delinquent_balance_param: Decimal) -> bool {
+        match self
+            .list_am
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
+            None => false,
+            Some(o) => {
+                o.set_delinquent_balance(delinquent_balance_param);
+                true
+            }
+        }
+    }
+
     /// Set the accrued interest balance.
     ///
     /// # Arguments
@@ -936,7 +1318,10 @@ impl ListAmortization {
     /// * True if successful, otherwise false.
 
     pub fn set_acc_balance(&mut self, acc_balance_param: Decimal) -> bool {
-        match self.list_am.get_mut(self.list_index.get()) {
+        match self
+            .list_am
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
             None => false,
             Some(o) => {
                 o.set_acc_balance(acc_balance_param);
@@ -956,7 +1341,10 @@ impl ListAmortization {
     /// * True if successful, otherwise false.
 
     pub fn set_balance(&mut self, balance_param: Decimal) -> bool {
-        match self.list_am.get_mut(self.list_index.get()) {
+        match self
+            .list_am
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
             None => false,
             Some(o) => {
                 o.set_balance(balance_param);
@@ -965,6 +1353,50 @@ impl ListAmortization {
         }
     }
 
+    /// Set the scheduled date (before any business day adjustment).
+    ///
+    /// # Arguments
+    ///
+    /// * `scheduled_date_param` - See description.
+    ///
+    /// # Return
+    ///
+    /// * True if successful, otherwise false.
+    pub fn set_scheduled_date(&mut self, scheduled_date_param: NaiveDate) -> bool {
+        match self
+            .list_am
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
+            None => false,
+            Some(o) => {
+                o.set_scheduled_date(scheduled_date_param);
+                true
+            }
+        }
+    }
+
+    /// Set the index value used to compute the rate.
+    ///
+    /// # Arguments
+    ///
+    /// * `index_value_param` - See description.
+    ///
+    /// # Return
+    ///
+    /// * True if successful, otherwise false.
+    pub fn set_index_value(&mut self, index_value_param: Option<Decimal>) -> bool {
+        match self
+            .list_am
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
+            None => false,
+            Some(o) => {
+                o.set_index_value(index_value_param);
+                true
+            }
+        }
+    }
+
     /// Set the sequence number of principal change with statistics set or 0 (if not applicable).
     ///
     /// # Arguments
@@ -976,7 +1408,10 @@ impl ListAmortization {
     /// * True if successful, otherwise false.
 
     pub fn set_stat_sequence(&mut self, stat_sequence_param: usize) -> bool {
-        match self.list_am.get_mut(self.list_index.get()) {
+        match self
+            .list_am
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
             None => false,
             Some(o) => {
                 o.set_stat_sequence(stat_sequence_param);
@@ -7,17 +7,16 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-use std::cell::Cell;
-
 use super::ElemColumn;
 use crate::ListTrait;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 pub struct ListColumn {
     /// The list of column elements.
     list_column: Vec<ElemColumn>,
 
     /// The index of the currently selected column element.
-    list_index: Cell<usize>,
+    list_index: AtomicUsize,
 }
 
 /// List of columns list implementation.
@@ -27,7 +26,7 @@ impl ListTrait for ListColumn {
 
     fn clear(&mut self) {
         self.list_column.clear();
-        self.list_index = Cell::new(usize::MAX);
+        self.list_index = AtomicUsize::new(usize::MAX);
     }
 
     /// Get the count of the columns.
@@ -47,7 +46,7 @@ impl ListTrait for ListColumn {
     /// * See description.
 
     fn index(&self) -> usize {
-        self.list_index.get()
+        self.list_index.load(Ordering::Relaxed)
     }
 
     /// Select a column based upon an index value.
@@ -85,7 +84,7 @@ impl ListTrait for ListColumn {
             return false;
         }
 
-        self.list_index.set(index_param);
+        self.list_index.store(index_param, Ordering::Relaxed);
 
         true
     }
@@ -117,7 +116,7 @@ impl ListColumn {
     pub fn new() -> ListColumn {
         ListColumn {
             list_column: Vec::new(),
-            list_index: Cell::new(usize::MAX),
+            list_index: AtomicUsize::new(usize::MAX),
         }
     }
 
@@ -176,7 +175,7 @@ impl ListColumn {
 
         let new_index: usize = self.list_column.len();
         self.list_column.push(new_elem_column);
-        self.list_index.set(new_index);
+        self.list_index.store(new_index, Ordering::Relaxed);
         true
     }
 
@@ -187,7 +186,10 @@ impl ListColumn {
     /// * See description.
 
     pub fn column(&self) -> &ElemColumn {
-        match self.list_column.get(self.list_index.get()) {
+        match self
+            .list_column
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 panic!("Column list index not set");
             }
@@ -202,7 +204,10 @@ impl ListColumn {
     /// * See description.
 
     pub fn col_name(&self) -> &str {
-        match self.list_column.get(self.list_index.get()) {
+        match self
+            .list_column
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 panic!("Column list index not set");
             }
@@ -217,7 +222,10 @@ impl ListColumn {
     /// * See description.
 
     pub fn col_name_index(&self) -> usize {
-        match self.list_column.get(self.list_index.get()) {
+        match self
+            .list_column
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 panic!("Column list index not set");
             }
@@ -232,7 +240,10 @@ impl ListColumn {
     /// * See description.
 
     pub fn col_header(&self) -> &str {
-        match self.list_column.get(self.list_index.get()) {
+        match self
+            .list_column
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 panic!("Column list index not set");
             }
@@ -247,7 +258,10 @@ impl ListColumn {
     /// * See description.
 
     pub fn col_description(&self) -> &str {
-        match self.list_column.get(self.list_index.get()) {
+        match self
+            .list_column
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 panic!("Column list index not set");
             }
@@ -262,7 +276,10 @@ impl ListColumn {
     /// * See description.
 
     pub fn group(&self) -> &str {
-        match self.list_column.get(self.list_index.get()) {
+        match self
+            .list_column
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 panic!("Column list index not set");
             }
@@ -277,7 +294,10 @@ impl ListColumn {
     /// * See description.
 
     pub fn name(&self) -> &str {
-        match self.list_column.get(self.list_index.get()) {
+        match self
+            .list_column
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 panic!("Column list index not set");
             }
@@ -292,7 +312,10 @@ impl ListColumn {
     /// * See description.
 
     pub fn col_type(&self) -> &str {
-        match self.list_column.get(self.list_index.get()) {
+        match self
+            .list_column
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 panic!("Column list index not set");
             }
@@ -307,7 +330,10 @@ impl ListColumn {
     /// * See description.
 
     pub fn code(&self) -> &str {
-        match self.list_column.get(self.list_index.get()) {
+        match self
+            .list_column
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 panic!("Column list index not set");
             }
@@ -322,7 +348,10 @@ impl ListColumn {
     /// * See description.
 
     pub fn format(&self) -> crate::FormatType {
-        match self.list_column.get(self.list_index.get()) {
+        match self
+            .list_column
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 panic!("Column list index not set");
             }
@@ -337,7 +366,10 @@ impl ListColumn {
     /// * See description.
 
     pub fn decimal_digits(&self) -> usize {
-        match self.list_column.get(self.list_index.get()) {
+        match self
+            .list_column
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 panic!("Column list index not set");
             }
@@ -352,7 +384,10 @@ impl ListColumn {
     /// * See description.
 
     pub fn column_width(&self) -> usize {
-        match self.list_column.get(self.list_index.get()) {
+        match self
+            .list_column
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 panic!("Column list index not set");
             }
@@ -367,7 +402,10 @@ impl ListColumn {
     /// * See description.
 
     pub fn column_editable(&self) -> bool {
-        match self.list_column.get(self.list_index.get()) {
+        match self
+            .list_column
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 panic!("Column list index not set");
             }
@@ -403,13 +441,17 @@ impl ListColumn {
     /// * True if successful, otherwise false.
 
     pub fn remove(&mut self) -> bool {
-        if self.list_index.get() >= self.list_column.len() {
+        if self.list_index.load(Ordering::Relaxed) >= self.list_column.len() {
             return false;
         }
 
-        self.list_column.remove(self.list_index.get());
-        if self.list_index.get() > 0 {
-            self.list_index.set(self.list_index.get() - 1);
+        self.list_column
+            .remove(self.list_index.load(Ordering::Relaxed));
+        if self.list_index.load(Ordering::Relaxed) > 0 {
+            self.list_index.store(
+                self.list_index.load(Ordering::Relaxed) - 1,
+                Ordering::Relaxed,
+            );
         }
         true
     }
@@ -7,8 +7,8 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-use std::cell::Cell;
 use std::cmp::Ordering::Equal;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 use super::ElemDescriptor;
 use crate::ListTrait;
@@ -18,7 +18,7 @@ pub struct ListDescriptor {
     list_descriptor: Vec<ElemDescriptor>,
 
     /// The index of the currently selected descriptor element.
-    list_index: Cell<usize>,
+    list_index: AtomicUsize,
 
     /// If true sort when a descriptor is added, otherwise do not sort (for bulk adds).
     sort_on_add: bool,
@@ -34,7 +34,7 @@ impl ListTrait for ListDescriptor {
 
     fn clear(&mut self) {
         self.list_descriptor.clear();
-        self.list_index.set(usize::MAX);
+        self.list_index.store(usize::MAX, Ordering::Relaxed);
         self.sort_on_add = true;
         self.sort_updated = false;
     }
@@ -56,7 +56,7 @@ impl ListTrait for ListDescriptor {
     /// * See description.
 
     fn index(&self) -> usize {
-        self.list_index.get()
+        self.list_index.load(Ordering::Relaxed)
     }
 
     /// Select a descriptor based upon an index value.
@@ -90,7 +90,7 @@ impl ListTrait for ListDescriptor {
             return false;
         }
 
-        self.list_index.set(index_param);
+        self.list_index.store(index_param, Ordering::Relaxed);
 
         true
     }
@@ -126,7 +126,7 @@ impl ListDescriptor {
     pub fn new() -> ListDescriptor {
         ListDescriptor {
             list_descriptor: Vec::new(),
-            list_index: Cell::new(usize::MAX),
+            list_index: AtomicUsize::new(usize::MAX),
             sort_on_add: true,
             sort_updated: false,
         }
@@ -197,7 +197,10 @@ impl ListDescriptor {
         }
 
         if update_element {
-            match self.list_descriptor.get_mut(self.list_index.get()) {
+            match self
+                .list_descriptor
+                .get_mut(self.list_index.load(Ordering::Relaxed))
+            {
                 None => {}
                 Some(o) => {
                     o.set_group(group_param);
@@ -236,7 +239,7 @@ impl ListDescriptor {
         }) {
             None => {}
             Some(o) => {
-                self.list_index.set(o);
+                self.list_index.store(o, Ordering::Relaxed);
             }
         }
         if !self.sort_on_add {
@@ -359,7 +362,10 @@ impl ListDescriptor {
     /// * See description.
 
     pub fn group(&self) -> &str {
-        match self.list_descriptor.get(self.list_index.get()) {
+        match self
+            .list_descriptor
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 panic!("Descriptor list index not set");
             }
@@ -374,7 +380,10 @@ impl ListDescriptor {
     /// * See description.
 
     pub fn name(&self) -> &str {
-        match self.list_descriptor.get(self.list_index.get()) {
+        match self
+            .list_descriptor
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 panic!("Descriptor list index not set");
             }
@@ -389,7 +398,10 @@ impl ListDescriptor {
     /// * See description.
 
     pub fn desc_type(&self) -> &str {
-        match self.list_descriptor.get(self.list_index.get()) {
+        match self
+            .list_descriptor
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 panic!("Descriptor list index not set");
             }
@@ -404,7 +416,10 @@ impl ListDescriptor {
     /// * See description.
 
     pub fn code(&self) -> &str {
-        match self.list_descriptor.get(self.list_index.get()) {
+        match self
+            .list_descriptor
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 panic!("Descriptor list index not set");
             }
@@ -419,7 +434,10 @@ impl ListDescriptor {
     /// * See description.
 
     pub fn value(&self) -> String {
-        match self.list_descriptor.get(self.list_index.get()) {
+        match self
+            .list_descriptor
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 panic!("Descriptor list index not set");
             }
@@ -434,7 +452,10 @@ impl ListDescriptor {
     /// * See description.
 
     pub fn value_expr(&self) -> String {
-        match self.list_descriptor.get(self.list_index.get()) {
+        match self
+            .list_descriptor
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 panic!("Descriptor list index not set");
             }
@@ -449,7 +470,10 @@ impl ListDescriptor {
     /// * See description.
 
     pub fn propagate(&self) -> bool {
-        match self.list_descriptor.get(self.list_index.get()) {
+        match self
+            .list_descriptor
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 panic!("Descriptor list index not set");
             }
@@ -464,7 +488,10 @@ impl ListDescriptor {
     /// * See description.
 
     pub fn list_event_index(&self) -> usize {
-        match self.list_descriptor.get(self.list_index.get()) {
+        match self
+            .list_descriptor
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 panic!("Descriptor list index not set");
             }
@@ -472,6 +499,34 @@ impl ListDescriptor {
         }
     }
 
+    /// Find a descriptor based upon a group, name, type, and code
+    /// without selecting it.
+    ///
+    /// # Arguments
+    ///
+    /// * `group_param` - Group to find.
+    /// * `name_param` - Name to find.
+    /// * `type_param` - Type to find.
+    /// * `code_param` - Code to find.
+    ///
+    /// # Return
+    ///
+    /// * The descriptor if found, otherwise None.
+    pub fn get_descriptor(
+        &self,
+        group_param: &str,
+        name_param: &str,
+        type_param: &str,
+        code_param: &str,
+    ) -> Option<&ElemDescriptor> {
+        self.list_descriptor.iter().find(|e| {
+            group_param == e.group()
+                && name_param == e.name()
+                && type_param == e.desc_type()
+                && code_param == e.code()
+        })
+    }
+
     /// Select a descriptor based upon a group, name, type, and code.
     ///
     /// # Arguments
@@ -516,13 +571,17 @@ impl ListDescriptor {
     /// * True if successful, otherwise false.
 
     pub fn remove(&mut self) -> bool {
-        if self.list_index.get() >= self.list_descriptor.len() {
+        if self.list_index.load(Ordering::Relaxed) >= self.list_descriptor.len() {
             return false;
         }
 
-        self.list_descriptor.remove(self.list_index.get());
-        if self.list_index.get() > 0 {
-            self.list_index.set(self.list_index.get() - 1);
+        self.list_descriptor
+            .remove(self.list_index.load(Ordering::Relaxed));
+        if self.list_index.load(Ordering::Relaxed) > 0 {
+            self.list_index.store(
+                self.list_index.load(Ordering::Relaxed) - 1,
+                Ordering::Relaxed,
+            );
         }
 
         true
@@ -544,7 +603,10 @@ impl ListDescriptor {
         let desc_type: String;
         let code: String;
 
-        match self.list_descriptor.get(self.list_index.get()) {
+        match self
+            .list_descriptor
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 return false;
             }
@@ -565,7 +627,10 @@ impl ListDescriptor {
             return false;
         }
 
-        match self.list_descriptor.get_mut(self.list_index.get()) {
+        match self
+            .list_descriptor
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
             None => {}
             Some(o) => {
                 o.set_group(group);
@@ -581,7 +646,7 @@ impl ListDescriptor {
         }) {
             None => {}
             Some(o) => {
-                self.list_index.set(o);
+                self.list_index.store(o, Ordering::Relaxed);
             }
         }
 
@@ -608,7 +673,10 @@ impl ListDescriptor {
         let desc_type: String;
         let code: String;
 
-        match self.list_descriptor.get(self.list_index.get()) {
+        match self
+            .list_descriptor
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 return false;
             }
@@ -629,7 +697,10 @@ impl ListDescriptor {
             return false;
         }
 
-        match self.list_descriptor.get_mut(self.list_index.get()) {
+        match self
+            .list_descriptor
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
             None => {}
             Some(o) => {
                 o.set_name(name);
@@ -645,7 +716,7 @@ impl ListDescriptor {
         }) {
             None => {}
             Some(o) => {
-                self.list_index.set(o);
+                self.list_index.store(o, Ordering::Relaxed);
             }
         }
 
@@ -671,7 +742,10 @@ impl ListDescriptor {
         let name: String;
         let code: String;
 
-        match self.list_descriptor.get(self.list_index.get()) {
+        match self
+            .list_descriptor
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 return false;
             }
@@ -692,7 +766,10 @@ impl ListDescriptor {
             return false;
         }
 
-        match self.list_descriptor.get_mut(self.list_index.get()) {
+        match self
+            .list_descriptor
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
             None => {}
             Some(o) => {
                 o.set_desc_type(desc_type);
@@ -708,7 +785,7 @@ impl ListDescriptor {
         }) {
             None => {}
             Some(o) => {
-                self.list_index.set(o);
+                self.list_index.store(o, Ordering::Relaxed);
             }
         }
 
@@ -734,7 +811,10 @@ impl ListDescriptor {
         let name: String;
         let desc_type: String;
 
-        match self.list_descriptor.get(self.list_index.get()) {
+        match self
+            .list_descriptor
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 return false;
             }
@@ -755,7 +835,10 @@ impl ListDescriptor {
             return false;
         }
 
-        match self.list_descriptor.get_mut(self.list_index.get()) {
+        match self
+            .list_descriptor
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
             None => {}
             Some(o) => {
                 o.set_code(code);
@@ -771,7 +854,7 @@ impl ListDescriptor {
         }) {
             None => {}
             Some(o) => {
-                self.list_index.set(o);
+                self.list_index.store(o, Ordering::Relaxed);
             }
         }
 
@@ -793,7 +876,10 @@ impl ListDescriptor {
     /// * True if successful, otherwise false.
 
     pub fn set_value(&self, value: &str) -> bool {
-        match self.list_descriptor.get(self.list_index.get()) {
+        match self
+            .list_descriptor
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
             None => false,
             Some(o) => {
                 o.set_value(value);
@@ -813,7 +899,10 @@ impl ListDescriptor {
     /// * True if successful, otherwise false.
 
     pub fn set_value_result(&mut self, value: &str) -> bool {
-        match self.list_descriptor.get(self.list_index.get()) {
+        match self
+            .list_descriptor
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
             None => false,
             Some(o) => {
                 o.set_value(value);
@@ -833,7 +922,10 @@ impl ListDescriptor {
     /// * True if successful, otherwise false.
 
     pub fn set_value_expr(&mut self, value_expr: &str) -> bool {
-        match self.list_descriptor.get_mut(self.list_index.get()) {
+        match self
+            .list_descriptor
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
             None => false,
             Some(o) => {
                 o.set_value_expr(value_expr);
@@ -853,7 +945,10 @@ impl ListDescriptor {
     /// * True if successful, otherwise false.
 
     pub fn set_propagate(&mut self, propagate: bool) -> bool {
-        match self.list_descriptor.get_mut(self.list_index.get()) {
+        match self
+            .list_descriptor
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
             None => false,
             Some(o) => {
                 o.set_propagate(propagate);
@@ -873,7 +968,10 @@ impl ListDescriptor {
     /// * True if successful, otherwise false.
 
     pub fn set_list_event_index(&self, list_event_index: usize) -> bool {
-        match self.list_descriptor.get(self.list_index.get()) {
+        match self
+            .list_descriptor
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
             None => false,
             Some(o) => {
                 o.set_list_event_index(list_event_index);
@@ -900,7 +998,10 @@ impl ListDescriptor {
         if self.sort_on_add && self.sort_updated {
             self.sort();
 
-            match self.list_descriptor.get(self.list_index.get()) {
+            match self
+                .list_descriptor
+                .get(self.list_index.load(Ordering::Relaxed))
+            {
                 None => {
                     return false;
                 }
@@ -913,7 +1014,7 @@ impl ListDescriptor {
                     }) {
                         None => {}
                         Some(o2) => {
-                            self.list_index.set(o2);
+                            self.list_index.store(o2, Ordering::Relaxed);
                         }
                     }
                 }
@@ -7,13 +7,14 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use chrono::NaiveDate;
 use rust_decimal::prelude::*;
-use std::cell::Cell;
 use std::cmp::Ordering::Equal;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 
 use super::{
-    ElemCurrentValue, ElemEvent, ElemExtension, ElemInterestChange, ElemPrincipalChange,
-    ElemStatisticValue, ListDescriptor, ListParameter,
+    CoreUtility, ElemCurrentValue, ElemError, ElemEvent, ElemExtension, ElemInterestChange,
+    ElemPrincipalChange, ElemStatisticValue, ListDescriptor, ListParameter,
 };
 use crate::{ExtensionTrait, ListTrait};
 
@@ -21,11 +22,11 @@ pub struct ListEvent {
     /// The list of events.
     list_event: Vec<ElemEvent>,
     /// The index of the currently selected event element.
-    list_index: Cell<usize>,
+    list_index: AtomicUsize,
     /// If true, sort when an event is added otherwise do not sort (for bulk adds).
-    sort_on_add: Cell<bool>,
+    sort_on_add: AtomicBool,
     /// Updated while sort_on_add was false.
-    sort_updated: Cell<bool>,
+    sort_updated: AtomicBool,
     /// Originated from cashflow.
     cashflow: bool,
 }
@@ -37,9 +38,9 @@ impl ListTrait for ListEvent {
 
     fn clear(&mut self) {
         self.list_event.clear();
-        self.list_index.set(usize::MAX);
-        self.sort_on_add.set(true);
-        self.sort_updated.set(false);
+        self.list_index.store(usize::MAX, Ordering::Relaxed);
+        self.sort_on_add.store(true, Ordering::Relaxed);
+        self.sort_updated.store(false, Ordering::Relaxed);
     }
 
     /// Get the count of the event list.
@@ -59,7 +60,7 @@ impl ListTrait for ListEvent {
     /// * See description.
 
     fn index(&self) -> usize {
-        self.list_index.get()
+        self.list_index.load(Ordering::Relaxed)
     }
 
     /// Select an event based upon an index value.
@@ -93,7 +94,7 @@ impl ListTrait for ListEvent {
             return false;
         }
 
-        self.list_index.set(index_param);
+        self.list_index.store(index_param, Ordering::Relaxed);
 
         true
     }
@@ -115,9 +116,9 @@ impl ListEvent {
     pub fn new(cashflow_param: bool) -> ListEvent {
         ListEvent {
             list_event: Vec::new(),
-            list_index: Cell::new(usize::MAX),
-            sort_on_add: Cell::new(true),
-            sort_updated: Cell::new(false),
+            list_index: AtomicUsize::new(usize::MAX),
+            sort_on_add: AtomicBool::new(true),
+            sort_updated: AtomicBool::new(false),
             cashflow: cashflow_param,
         }
     }
@@ -147,12 +148,13 @@ impl ListEvent {
     ///
     /// # Return
     ///
-    /// * True if successful, otherwise false.
+    /// * True if successful, otherwise false (including an event
+    ///   date before January 1, SERIAL_BASE_YEAR).
     #[allow(clippy::too_many_arguments)]
 
     pub fn add_event(
         &mut self,
-        event_date_param: usize,
+        event_date_param: NaiveDate,
         date_expr_param: &str,
         sort_order_param: usize,
         value_param: Decimal,
@@ -170,6 +172,10 @@ impl ListEvent {
         event_name_param: &str,
         next_name_param: &str,
     ) -> bool {
+        if !CoreUtility::date_in_range(event_date_param) {
+            return false;
+        }
+
         if list_parameter_param.is_none() {
             list_parameter_param = Option::from(ListParameter::new());
         }
@@ -201,14 +207,14 @@ impl ListEvent {
 
         self.list_event.push(new_elem_event);
 
-        if self.sort_on_add.get() {
+        if self.sort_on_add.load(Ordering::Relaxed) {
             self.sort();
         }
 
         let result = self.get_element_by_date(event_date_param, sort_order_param);
 
-        if !self.sort_on_add.get() {
-            self.sort_updated.set(true);
+        if !self.sort_on_add.load(Ordering::Relaxed) {
+            self.sort_updated.store(true, Ordering::Relaxed);
         }
 
         result
@@ -252,14 +258,10 @@ impl ListEvent {
         &self,
         list_event: &mut ListEvent,
         updating_json_param: bool,
-    ) -> Result<(), crate::ErrorType> {
-        let orig_list_index: usize = self.index();
+    ) -> Result<(), ElemError> {
         list_event.set_sort_on_add(false);
 
         for index in 0..self.count() {
-            if !self.get_element(index) {
-                break;
-            }
             let result = self.copy_event(list_event, index, updating_json_param);
             match result {
                 Err(e) => {
@@ -269,7 +271,6 @@ impl ListEvent {
             }
         }
         list_event.set_sort_on_add(true); // Sorts list
-        self.get_element(orig_list_index);
 
         Ok(())
     }
@@ -295,7 +296,7 @@ impl ListEvent {
         list_event: &mut ListEvent,
         mut count: i32,
         updating_json_param: bool,
-    ) -> Result<(), crate::ErrorType> {
+    ) -> Result<(), ElemError> {
         let mut index = self.index() as i32;
         list_event.set_sort_on_add(false);
 
@@ -347,10 +348,10 @@ impl ListEvent {
         list_event: &mut ListEvent,
         index: usize,
         updating_json_param: bool,
-    ) -> Result<(), crate::ErrorType> {
+    ) -> Result<(), ElemError> {
         match self.list_event.get(index) {
             None => {
-                return Err(crate::ErrorType::Index);
+                return Err(ElemError::new(crate::ErrorType::Index));
             }
             Some(o) => {
                 let new_extension = o.elem_extension().copy();
@@ -412,7 +413,7 @@ impl ListEvent {
     /// * See description.
 
     pub fn eom(&self) -> bool {
-        match self.list_event.get(self.list_index.get()) {
+        match self.list_event.get(self.list_index.load(Ordering::Relaxed)) {
             None => {
                 panic!("Event list index not set");
             }
@@ -427,7 +428,7 @@ impl ListEvent {
     /// * See description.
 
     pub fn elem_type(&self) -> crate::ExtensionType {
-        match self.list_event.get(self.list_index.get()) {
+        match self.list_event.get(self.list_index.load(Ordering::Relaxed)) {
             None => {
                 panic!("Event list index not set");
             }
@@ -442,7 +443,7 @@ impl ListEvent {
     /// * See description.
 
     pub fn event_type(&self) -> &str {
-        match self.list_event.get(self.list_index.get()) {
+        match self.list_event.get(self.list_index.load(Ordering::Relaxed)) {
             None => {
                 panic!("Event list index not set");
             }
@@ -450,14 +451,14 @@ impl ListEvent {
         }
     }
 
-    /// Get the date of the event in YYYYMMDD format.
+    /// Get the date of the event.
     ///
     /// # Return
     ///
     /// * See description.
 
-    pub fn event_date(&self) -> usize {
-        match self.list_event.get(self.list_index.get()) {
+    pub fn event_date(&self) -> NaiveDate {
+        match self.list_event.get(self.list_index.load(Ordering::Relaxed)) {
             None => {
                 panic!("Event list index not set");
             }
@@ -472,7 +473,7 @@ impl ListEvent {
     /// * See description.
 
     pub fn date_expr(&self) -> &str {
-        match self.list_event.get(self.list_index.get()) {
+        match self.list_event.get(self.list_index.load(Ordering::Relaxed)) {
             None => {
                 panic!("Event list index not set");
             }
@@ -487,7 +488,7 @@ impl ListEvent {
     /// * See description.
 
     pub fn sort_order(&self) -> usize {
-        match self.list_event.get(self.list_index.get()) {
+        match self.list_event.get(self.list_index.load(Ordering::Relaxed)) {
             None => {
                 panic!("Event list index not set");
             }
@@ -502,7 +503,7 @@ impl ListEvent {
     /// * See description.
 
     pub fn value(&self) -> Decimal {
-        match self.list_event.get(self.list_index.get()) {
+        match self.list_event.get(self.list_index.load(Ordering::Relaxed)) {
             None => {
                 panic!("Event list index not set");
             }
@@ -517,7 +518,7 @@ impl ListEvent {
     /// * See description.
 
     pub fn value_expr(&self) -> &str {
-        match self.list_event.get(self.list_index.get()) {
+        match self.list_event.get(self.list_index.load(Ordering::Relaxed)) {
             None => {
                 panic!("Event list index not set");
             }
@@ -532,7 +533,7 @@ impl ListEvent {
     /// * See description.
 
     pub fn value_expr_balance(&self) -> bool {
-        match self.list_event.get(self.list_index.get()) {
+        match self.list_event.get(self.list_index.load(Ordering::Relaxed)) {
             None => {
                 panic!("Event list index not set");
             }
@@ -547,7 +548,7 @@ impl ListEvent {
     /// * See description.
 
     pub fn periods(&self) -> usize {
-        match self.list_event.get(self.list_index.get()) {
+        match self.list_event.get(self.list_index.load(Ordering::Relaxed)) {
             None => {
                 panic!("Event list index not set");
             }
@@ -562,7 +563,7 @@ impl ListEvent {
     /// * See description.
 
     pub fn periods_expr(&self) -> &str {
-        match self.list_event.get(self.list_index.get()) {
+        match self.list_event.get(self.list_index.load(Ordering::Relaxed)) {
             None => {
                 panic!("Event list index not set");
             }
@@ -577,7 +578,7 @@ impl ListEvent {
     /// * See description.
 
     pub fn skip_mask_len(&self) -> usize {
-        match self.list_event.get(self.list_index.get()) {
+        match self.list_event.get(self.list_index.load(Ordering::Relaxed)) {
             None => {
                 panic!("Event list index not set");
             }
@@ -592,7 +593,7 @@ impl ListEvent {
     /// * See description.
 
     pub fn skip_mask(&self) -> u128 {
-        match self.list_event.get(self.list_index.get()) {
+        match self.list_event.get(self.list_index.load(Ordering::Relaxed)) {
             None => {
                 panic!("Event list index not set");
             }
@@ -607,7 +608,7 @@ impl ListEvent {
     /// * See description.
 
     pub fn intervals(&self) -> usize {
-        match self.list_event.get(self.list_index.get()) {
+        match self.list_event.get(self.list_index.load(Ordering::Relaxed)) {
             None => {
                 panic!("Event list index not set");
             }
@@ -622,7 +623,7 @@ impl ListEvent {
     /// * See description.
 
     pub fn frequency(&self) -> crate::FrequencyType {
-        match self.list_event.get(self.list_index.get()) {
+        match self.list_event.get(self.list_index.load(Ordering::Relaxed)) {
             None => {
                 panic!("Event list index not set");
             }
@@ -630,6 +631,20 @@ impl ListEvent {
         }
     }
 
+    /// Get the selected event element.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn elem_event(&self) -> &ElemEvent {
+        match self.list_event.get(self.list_index.load(Ordering::Relaxed)) {
+            None => {
+                panic!("Event list index not set");
+            }
+            Some(o) => o,
+        }
+    }
+
     /// Get the extension.
     ///
     /// # Return
@@ -637,7 +652,7 @@ impl ListEvent {
     /// * See description.
 
     pub fn elem_extension(&self) -> &ElemExtension {
-        match self.list_event.get(self.list_index.get()) {
+        match self.list_event.get(self.list_index.load(Ordering::Relaxed)) {
             None => {
                 panic!("Event list index not set");
             }
@@ -652,7 +667,10 @@ impl ListEvent {
     /// * See description.
 
     pub fn elem_extension_mut(&mut self) -> &mut ElemExtension {
-        match self.list_event.get_mut(self.list_index.get()) {
+        match self
+            .list_event
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 panic!("Event list index not set");
             }
@@ -667,7 +685,7 @@ impl ListEvent {
     /// * See description.
 
     pub fn list_parameter(&self) -> Option<&ListParameter> {
-        match self.list_event.get(self.list_index.get()) {
+        match self.list_event.get(self.list_index.load(Ordering::Relaxed)) {
             None => {
                 panic!("Event list index not set");
             }
@@ -682,7 +700,10 @@ impl ListEvent {
     /// * See description.
 
     pub fn list_parameter_mut(&mut self) -> Option<&mut ListParameter> {
-        match self.list_event.get_mut(self.list_index.get()) {
+        match self
+            .list_event
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 panic!("Event list index not set");
             }
@@ -697,7 +718,7 @@ impl ListEvent {
     /// * See description.
 
     pub fn list_descriptor(&self) -> Option<&ListDescriptor> {
-        match self.list_event.get(self.list_index.get()) {
+        match self.list_event.get(self.list_index.load(Ordering::Relaxed)) {
             None => {
                 panic!("Event list index not set");
             }
@@ -712,7 +733,7 @@ impl ListEvent {
     /// * See description.
 
     pub fn event_name(&self) -> &str {
-        match self.list_event.get(self.list_index.get()) {
+        match self.list_event.get(self.list_index.load(Ordering::Relaxed)) {
             None => {
                 panic!("Event list index not set");
             }
@@ -727,7 +748,7 @@ impl ListEvent {
     /// * See description.
 
     pub fn next_name(&self) -> &str {
-        match self.list_event.get(self.list_index.get()) {
+        match self.list_event.get(self.list_index.load(Ordering::Relaxed)) {
             None => {
                 panic!("Event list index not set");
             }
@@ -746,7 +767,7 @@ impl ListEvent {
     ///
     /// * True if successful, otherwise false.
 
-    pub fn get_element_by_date(&self, date_param: usize, sort_param: usize) -> bool {
+    pub fn get_element_by_date(&self, date_param: NaiveDate, sort_param: usize) -> bool {
         for (index, elem) in self.list_event.iter().enumerate() {
             if elem.event_date() == date_param && elem.sort_order() == sort_param {
                 self.set_index(index);
@@ -859,13 +880,17 @@ impl ListEvent {
     /// * True if successful, otherwise false.
 
     pub fn remove(&mut self) -> bool {
-        if self.list_index.get() >= self.list_event.len() {
+        if self.list_index.load(Ordering::Relaxed) >= self.list_event.len() {
             return false;
         }
 
-        self.list_event.remove(self.list_index.get());
-        if self.list_index.get() > 0 {
-            self.list_index.set(self.list_index.get() - 1);
+        self.list_event
+            .remove(self.list_index.load(Ordering::Relaxed));
+        if self.list_index.load(Ordering::Relaxed) > 0 {
+            self.list_index.store(
+                self.list_index.load(Ordering::Relaxed) - 1,
+                Ordering::Relaxed,
+            );
         }
 
         true
@@ -882,7 +907,10 @@ impl ListEvent {
     /// * True if successful, otherwise false.
 
     pub fn set_event_type(&mut self, event_type_param: &str) -> bool {
-        match self.list_event.get_mut(self.list_index.get()) {
+        match self
+            .list_event
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
             None => false,
             Some(o) => {
                 o.set_event_type(event_type_param);
@@ -891,7 +919,7 @@ impl ListEvent {
         }
     }
 
-    /// Set the constant date of the event in YYYYMMDD format (major sort key).
+    /// Set the constant date of the event (major sort key).
     ///
     /// # Arguments
     ///
@@ -901,11 +929,11 @@ impl ListEvent {
     ///
     /// * True if successful, otherwise false.
 
-    pub fn set_event_date(&mut self, event_date_param: usize) -> bool {
+    pub fn set_event_date(&mut self, event_date_param: NaiveDate) -> bool {
         let elem_type: crate::ExtensionType;
-        let event_date: usize;
+        let event_date: NaiveDate;
 
-        match self.list_event.get(self.list_index.get()) {
+        match self.list_event.get(self.list_index.load(Ordering::Relaxed)) {
             None => {
                 return false;
             }
@@ -918,7 +946,7 @@ impl ListEvent {
         if !self.set_date_result(event_date_param) {
             return false;
         }
-        if self.sort_on_add.get() {
+        if self.sort_on_add.load(Ordering::Relaxed) {
             self.sort();
         }
 
@@ -929,17 +957,17 @@ impl ListEvent {
         {
             None => {}
             Some(o) => {
-                self.list_index.set(o);
+                self.list_index.store(o, Ordering::Relaxed);
             }
         }
 
-        if !self.sort_on_add.get() {
-            self.sort_updated.set(true);
+        if !self.sort_on_add.load(Ordering::Relaxed) {
+            self.sort_updated.store(true, Ordering::Relaxed);
         }
         true
     }
 
-    /// Set the expression date of the event in YYYYMMDD format (major sort key).
+    /// Set the expression date of the event (major sort key).
     ///
     /// # Arguments
     ///
@@ -947,10 +975,14 @@ impl ListEvent {
     ///
     /// # Return
     ///
-    /// * True if successful, otherwise false.
+    /// * True if successful, otherwise false (including an event
+    ///   date before January 1, SERIAL_BASE_YEAR).
+    pub fn set_date_result(&self, event_date_param: NaiveDate) -> bool {
+        if !CoreUtility::date_in_range(event_date_param) {
+            return false;
+        }
 
-    pub fn set_date_result(&self, event_date_param: usize) -> bool {
-        match self.list_event.get(self.list_index.get()) {
+        match self.list_event.get(self.list_index.load(Ordering::Relaxed)) {
             None => false,
             Some(o) => {
                 o.set_event_date(event_date_param);
@@ -970,7 +1002,10 @@ impl ListEvent {
     /// * True if successful, otherwise false.
 
     pub fn set_date_expr(&mut self, date_expr_param: &str) -> bool {
-        match self.list_event.get_mut(self.list_index.get()) {
+        match self
+            .list_event
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
             None => false,
             Some(o) => {
                 o.set_date_expr(date_expr_param);
@@ -991,9 +1026,12 @@ impl ListEvent {
 
     pub fn set_sort_order(&mut self, sort_order_param: usize) -> bool {
         let elem_type: crate::ExtensionType;
-        let event_date: usize;
+        let event_date: NaiveDate;
 
-        match self.list_event.get_mut(self.list_index.get()) {
+        match self
+            .list_event
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 return false;
             }
@@ -1003,7 +1041,7 @@ impl ListEvent {
                 o.set_sort_order(sort_order_param);
             }
         }
-        if self.sort_on_add.get() {
+        if self.sort_on_add.load(Ordering::Relaxed) {
             self.sort();
         }
 
@@ -1014,12 +1052,12 @@ impl ListEvent {
         {
             None => {}
             Some(o) => {
-                self.list_index.set(o);
+                self.list_index.store(o, Ordering::Relaxed);
             }
         }
 
-        if !self.sort_on_add.get() {
-            self.sort_updated.set(true);
+        if !self.sort_on_add.load(Ordering::Relaxed) {
+            self.sort_updated.store(true, Ordering::Relaxed);
         }
         true
     }
@@ -1053,7 +1091,7 @@ impl ListEvent {
     /// * True if successful, otherwise false.
 
     pub fn set_value_result(&self, value_param: Decimal) -> bool {
-        match self.list_event.get(self.list_index.get()) {
+        match self.list_event.get(self.list_index.load(Ordering::Relaxed)) {
             None => false,
             Some(o) => {
                 o.set_value(value_param);
@@ -1091,7 +1129,10 @@ impl ListEvent {
     /// * True if successful, otherwise false.
 
     pub fn set_value_expr_result(&mut self, value_expr_param: &str) -> bool {
-        match self.list_event.get_mut(self.list_index.get()) {
+        match self
+            .list_event
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
             None => false,
             Some(o) => {
                 o.set_value_expr(value_expr_param);
@@ -1111,7 +1152,10 @@ impl ListEvent {
     /// * True if successful, otherwise false.
 
     pub fn set_value_expr_balance(&mut self, value_expr_balance_param: bool) -> bool {
-        match self.list_event.get_mut(self.list_index.get()) {
+        match self
+            .list_event
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
             None => false,
             Some(o) => {
                 o.set_value_expr_balance(value_expr_balance_param);
@@ -1149,7 +1193,7 @@ impl ListEvent {
     /// * True if successful, otherwise false.
 
     pub fn set_periods_result(&self, periods_param: usize) -> bool {
-        match self.list_event.get(self.list_index.get()) {
+        match self.list_event.get(self.list_index.load(Ordering::Relaxed)) {
             None => false,
             Some(o) => {
                 o.set_periods(periods_param);
@@ -1187,7 +1231,10 @@ impl ListEvent {
     /// * True if successful, otherwise false.
 
     pub fn set_periods_expr_result(&mut self, periods_expr_param: &str) -> bool {
-        match self.list_event.get_mut(self.list_index.get()) {
+        match self
+            .list_event
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
             None => false,
             Some(o) => {
                 o.set_periods_expr(periods_expr_param);
@@ -1208,7 +1255,7 @@ impl ListEvent {
     /// * True if successful, otherwise false.
 
     pub fn set_skip_mask(&self, skip_mask_len_param: usize, skip_mask_param: u128) -> bool {
-        match self.list_event.get(self.list_index.get()) {
+        match self.list_event.get(self.list_index.load(Ordering::Relaxed)) {
             None => false,
             Some(o) => {
                 o.set_skip_mask(skip_mask_len_param, skip_mask_param);
@@ -1228,7 +1275,10 @@ impl ListEvent {
     /// * True if successful, otherwise false.
 
     pub fn set_intervals(&mut self, intervals_param: usize) -> bool {
-        match self.list_event.get_mut(self.list_index.get()) {
+        match self
+            .list_event
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
             None => false,
             Some(o) => {
                 o.set_intervals(intervals_param);
@@ -1266,7 +1316,10 @@ impl ListEvent {
     /// * True if successful, otherwise false.
 
     pub fn set_frequency_result(&mut self, frequency_param: crate::FrequencyType) -> bool {
-        match self.list_event.get_mut(self.list_index.get()) {
+        match self
+            .list_event
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
             None => false,
             Some(o) => {
                 o.set_frequency(frequency_param);
@@ -1286,7 +1339,10 @@ impl ListEvent {
     /// * True if successful, otherwise false.
 
     pub fn set_principal_change(&mut self, principal_change_param: ElemPrincipalChange) -> bool {
-        match self.list_event.get_mut(self.list_index.get()) {
+        match self
+            .list_event
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
             None => false,
             Some(o) => {
                 o.elem_extension_mut()
@@ -1307,7 +1363,10 @@ impl ListEvent {
     /// * True if successful, otherwise false.
 
     pub fn set_current_value(&mut self, current_value_param: ElemCurrentValue) -> bool {
-        match self.list_event.get_mut(self.list_index.get()) {
+        match self
+            .list_event
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
             None => false,
             Some(o) => {
                 o.elem_extension_mut()
@@ -1328,7 +1387,10 @@ impl ListEvent {
     /// * True if successful, otherwise false.
 
     pub fn set_interest_change(&mut self, interest_change_param: ElemInterestChange) -> bool {
-        match self.list_event.get_mut(self.list_index.get()) {
+        match self
+            .list_event
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
             None => false,
             Some(o) => {
                 o.elem_extension_mut()
@@ -1349,7 +1411,10 @@ impl ListEvent {
     /// * True if successful, otherwise false.
 
     pub fn set_statistic_value(&mut self, statistic_value_param: ElemStatisticValue) -> bool {
-        match self.list_event.get_mut(self.list_index.get()) {
+        match self
+            .list_event
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
             None => false,
             Some(o) => {
                 o.elem_extension_mut()
@@ -1370,7 +1435,10 @@ impl ListEvent {
     /// * True if successful, otherwise false.
 
     pub fn set_event_name(&mut self, event_name_param: &str) -> bool {
-        match self.list_event.get_mut(self.list_index.get()) {
+        match self
+            .list_event
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
             None => false,
             Some(o) => {
                 o.set_event_name(event_name_param);
@@ -1390,7 +1458,10 @@ impl ListEvent {
     /// * True if successful, otherwise false.
 
     pub fn set_next_name(&mut self, next_name_param: &str) -> bool {
-        match self.list_event.get_mut(self.list_index.get()) {
+        match self
+            .list_event
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
             None => false,
             Some(o) => {
                 o.set_next_name(next_name_param);
@@ -1406,14 +1477,14 @@ impl ListEvent {
     /// * `sort_on_add_param` - If true sort when an event is added, otherwise do not sort (for bulk adds).
 
     pub fn set_sort_on_add(&mut self, sort_on_add_param: bool) -> bool {
-        if self.sort_on_add.get() == sort_on_add_param {
+        if self.sort_on_add.load(Ordering::Relaxed) == sort_on_add_param {
             return false;
         }
-        self.sort_on_add.set(sort_on_add_param);
-        if self.sort_on_add.get() && self.sort_updated.get() {
+        self.sort_on_add.store(sort_on_add_param, Ordering::Relaxed);
+        if self.sort_on_add.load(Ordering::Relaxed) && self.sort_updated.load(Ordering::Relaxed) {
             self.sort();
 
-            match self.list_event.get(self.list_index.get()) {
+            match self.list_event.get(self.list_index.load(Ordering::Relaxed)) {
                 None => {
                     return false;
                 }
@@ -1423,13 +1494,13 @@ impl ListEvent {
                     }) {
                         None => {}
                         Some(o2) => {
-                            self.list_index.set(o2);
+                            self.list_index.store(o2, Ordering::Relaxed);
                         }
                     }
                 }
             }
         }
-        self.sort_updated.set(false);
+        self.sort_updated.store(false, Ordering::Relaxed);
 
         true
     }
@@ -1445,11 +1516,12 @@ impl ListEvent {
     /// * True if successful, otherwise false.
 
     pub fn set_sort_updated(&self, sort_updated_param: bool) -> bool {
-        if sort_updated_param == self.sort_updated.get() {
+        if sort_updated_param == self.sort_updated.load(Ordering::Relaxed) {
             return false;
         }
 
-        self.sort_updated.set(sort_updated_param);
+        self.sort_updated
+            .store(sort_updated_param, Ordering::Relaxed);
 
         true
     }
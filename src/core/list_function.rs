@@ -0,0 +1,381 @@
+//! List of user-defined functions.
+// Copyright (c) 2021 ShiftLeft Software
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use super::ElemFunction;
+use crate::ListTrait;
+
+pub struct ListFunction {
+    /// The list of functions.
+    list_function: Vec<ElemFunction>,
+
+    /// The index of the currently selected function element.
+    list_index: AtomicUsize,
+}
+
+/// List of functions default implementation.
+impl Default for ListFunction {
+    /// Create a new function list.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn default() -> Self {
+        ListFunction::new()
+    }
+}
+
+/// List of functions list implementation.
+impl ListTrait for ListFunction {
+    /// Clear all functions from the function list.
+    fn clear(&mut self) {
+        self.list_function.clear();
+        self.list_index.store(usize::MAX, Ordering::Relaxed);
+    }
+
+    /// Get the count of the function list.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn count(&self) -> usize {
+        self.list_function.len()
+    }
+
+    /// Get the index of the selected function (starting from 0).
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn index(&self) -> usize {
+        self.list_index.load(Ordering::Relaxed)
+    }
+
+    /// Select a function based upon an index value.
+    ///
+    /// # Arguments
+    ///
+    /// * `index_param` - Index value of the function to select (starting from 0).
+    ///
+    /// # Return
+    ///
+    /// * True if successful, otherwise false.
+    fn get_element(&self, index_param: usize) -> bool {
+        if index_param >= self.list_function.len() {
+            return false;
+        }
+
+        self.set_index(index_param);
+
+        true
+    }
+
+    /// Set the list index.
+    ///
+    /// # Arguments
+    ///
+    /// * `index_param` - See description.
+    fn set_index(&self, index_param: usize) -> bool {
+        if index_param >= self.list_function.len() {
+            return false;
+        }
+
+        self.list_index.store(index_param, Ordering::Relaxed);
+
+        true
+    }
+}
+
+/// List of functions implementation.
+impl ListFunction {
+    /// Create a new function list.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn new() -> ListFunction {
+        ListFunction {
+            list_function: Vec::new(),
+            list_index: AtomicUsize::new(usize::MAX),
+        }
+    }
+
+    /// Add a new function into the function list. If a function
+    /// with the same name already exists, it is replaced.
+    ///
+    /// # Arguments
+    ///
+    /// * `name_param` - Name of the function.
+    /// * `desc_param` - Description of the function.
+    /// * `parameters_param` - Names of the function parameters.
+    /// * `expression_param` - Expression body of the function.
+    ///
+    /// # Return
+    ///
+    /// * True if successful, otherwise false.
+    pub fn add_function(
+        &mut self,
+        name_param: &str,
+        desc_param: &str,
+        parameters_param: &[String],
+        expression_param: &str,
+    ) -> bool {
+        if name_param.is_empty() {
+            return false;
+        }
+
+        let new_elem_function =
+            ElemFunction::new(name_param, desc_param, parameters_param, expression_param);
+
+        match self
+            .list_function
+            .iter()
+            .position(|e| e.name() == name_param)
+        {
+            None => {
+                self.list_function.push(new_elem_function);
+                self.list_index
+                    .store(self.list_function.len() - 1, Ordering::Relaxed);
+            }
+            Some(o) => {
+                self.list_function[o] = new_elem_function;
+                self.list_index.store(o, Ordering::Relaxed);
+            }
+        }
+
+        true
+    }
+
+    /// Performs a deep copy of this function list and returns to new function list.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn copy(&self) -> ListFunction {
+        let mut list_function = ListFunction::new();
+
+        self.copy_list_function(&mut list_function);
+
+        list_function
+    }
+
+    /// Performs a deep copy of this function list into the function list parameter.
+    /// Functions already present in the function list parameter are not replaced.
+    ///
+    /// # Arguments
+    ///
+    /// * `list_function` - The function list to copy into.
+    pub fn copy_list_function(&self, list_function: &mut ListFunction) {
+        for elem in self.list_function.iter() {
+            if list_function.get_element_by_name(elem.name(), false) {
+                continue; // Already present
+            }
+
+            list_function.add_function(
+                elem.name(),
+                elem.description(),
+                elem.parameters(),
+                elem.expression(),
+            );
+        }
+    }
+
+    /// Tests if this function list and another are equal.
+    ///
+    /// # Arguments
+    ///
+    /// * `list_function` - List to compare.
+    ///
+    /// # Return
+    ///
+    /// * True if equals, otherwise false.
+    pub fn equal(&self, list_function: &ListFunction) -> bool {
+        if self.count() != list_function.count() {
+            return false;
+        }
+
+        self.list_function
+            .iter()
+            .zip(list_function.list_function.iter())
+            .all(|(o, o2)| o.equal(o2))
+    }
+
+    /// Find and return a function by name without
+    /// changing the currently selected function.
+    ///
+    /// # Arguments
+    ///
+    /// * `name_param` - The name of the function to find.
+    ///
+    /// # Return
+    ///
+    /// * The found function, otherwise None.
+    pub fn get_function(&self, name_param: &str) -> Option<&ElemFunction> {
+        self.list_function.iter().find(|e| e.name() == name_param)
+    }
+
+    /// Select a function based upon a name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name_param` - The name of the function to select.
+    /// * `select_param` - If true select element, otherwise restore current element.
+    ///
+    /// # Return
+    ///
+    /// * True if successful, otherwise false.
+    pub fn get_element_by_name(&self, name_param: &str, select_param: bool) -> bool {
+        for (index, elem) in self.list_function.iter().enumerate() {
+            if name_param == elem.name() {
+                if select_param {
+                    self.set_index(index);
+                }
+                return true;
+            }
+        }
+
+        false
+    }
+
+    /// Get the name of the function.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn name(&self) -> &str {
+        match self
+            .list_function
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
+            None => {
+                panic!("Function list index not set");
+            }
+            Some(o) => o.name(),
+        }
+    }
+
+    /// Get the description of the function.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn description(&self) -> &str {
+        match self
+            .list_function
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
+            None => {
+                panic!("Function list index not set");
+            }
+            Some(o) => o.description(),
+        }
+    }
+
+    /// Get the names of the function parameters.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn parameters(&self) -> &[String] {
+        match self
+            .list_function
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
+            None => {
+                panic!("Function list index not set");
+            }
+            Some(o) => o.parameters(),
+        }
+    }
+
+    /// Get the expression body of the function.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn expression(&self) -> &str {
+        match self
+            .list_function
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
+            None => {
+                panic!("Function list index not set");
+            }
+            Some(o) => o.expression(),
+        }
+    }
+
+    /// Remove the selected function from the function list.
+    ///
+    /// # Return
+    ///
+    /// * True if successful, otherwise false.
+    pub fn remove(&mut self) -> bool {
+        if self.list_index.load(Ordering::Relaxed) >= self.list_function.len() {
+            return false;
+        }
+
+        self.list_function
+            .remove(self.list_index.load(Ordering::Relaxed));
+        if self.list_index.load(Ordering::Relaxed) > 0 {
+            self.list_index.store(
+                self.list_index.load(Ordering::Relaxed) - 1,
+                Ordering::Relaxed,
+            );
+        }
+
+        true
+    }
+
+    /// Set the description of the function.
+    ///
+    /// # Arguments
+    ///
+    /// * `desc_param` - See description.
+    pub fn set_description(&mut self, desc_param: &str) {
+        match self
+            .list_function
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
+            None => {}
+            Some(o) => o.set_description(desc_param),
+        }
+    }
+
+    /// Set the names of the function parameters.
+    ///
+    /// # Arguments
+    ///
+    /// * `parameters_param` - See description.
+    pub fn set_parameters(&mut self, parameters_param: &[String]) {
+        match self
+            .list_function
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
+            None => {}
+            Some(o) => o.set_parameters(parameters_param),
+        }
+    }
+
+    /// Set the expression body of the function.
+    ///
+    /// # Arguments
+    ///
+    /// * `expression_param` - See description.
+    pub fn set_expression(&mut self, expression_param: &str) {
+        match self
+            .list_function
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
+            None => {}
+            Some(o) => o.set_expression(expression_param),
+        }
+    }
+}
@@ -7,7 +7,7 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-use std::cell::Cell;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::vec::Vec;
 
 use super::ElemKey;
@@ -18,7 +18,7 @@ pub struct ListKey {
     list_key: Vec<ElemKey>,
 
     /// The index of the currently selected key element.
-    list_index: Cell<usize>,
+    list_index: AtomicUsize,
 }
 
 /// List of keys list implementation.
@@ -28,7 +28,7 @@ impl ListTrait for ListKey {
 
     fn clear(&mut self) {
         self.list_key.clear();
-        self.list_index.set(usize::MAX);
+        self.list_index.store(usize::MAX, Ordering::Relaxed);
     }
 
     /// Get the count of the key list.
@@ -48,7 +48,7 @@ impl ListTrait for ListKey {
     /// * See description.
 
     fn index(&self) -> usize {
-        self.list_index.get()
+        self.list_index.load(Ordering::Relaxed)
     }
 
     /// Select a key based upon an index.
@@ -86,7 +86,7 @@ impl ListTrait for ListKey {
             return false;
         }
 
-        self.list_index.set(index_param);
+        self.list_index.store(index_param, Ordering::Relaxed);
 
         true
     }
@@ -118,7 +118,7 @@ impl ListKey {
     pub fn new() -> ListKey {
         ListKey {
             list_key: Vec::new(),
-            list_index: Cell::new(usize::MAX),
+            list_index: AtomicUsize::new(usize::MAX),
         }
     }
 
@@ -148,7 +148,7 @@ impl ListKey {
                 return false;
             }
             Some(o) => {
-                self.list_index.set(o);
+                self.list_index.store(o, Ordering::Relaxed);
             }
         }
 
@@ -162,7 +162,7 @@ impl ListKey {
     /// * See description.
 
     pub fn key(&self) -> &str {
-        match self.list_key.get(self.list_index.get()) {
+        match self.list_key.get(self.list_index.load(Ordering::Relaxed)) {
             None => {
                 panic!("Key list index not set");
             }
@@ -177,7 +177,7 @@ impl ListKey {
     /// * See description.
 
     pub fn value(&self) -> usize {
-        match self.list_key.get(self.list_index.get()) {
+        match self.list_key.get(self.list_index.load(Ordering::Relaxed)) {
             None => {
                 panic!("Key list index not set");
             }
@@ -192,7 +192,7 @@ impl ListKey {
     /// * See description.
 
     pub fn value_ext(&self) -> usize {
-        match self.list_key.get(self.list_index.get()) {
+        match self.list_key.get(self.list_index.load(Ordering::Relaxed)) {
             None => {
                 panic!("Key list index not set");
             }
@@ -200,6 +200,36 @@ impl ListKey {
         }
     }
 
+    /// Get the first key that matches a name without
+    /// changing the currently selected key.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The name of the key to find.
+    ///
+    /// # Return
+    ///
+    /// * The found key, otherwise None.
+    pub fn get_key(&self, key: &str) -> Option<&ElemKey> {
+        self.list_key
+            .iter()
+            .find(|e| e.key().split('(').next().unwrap_or("").trim() == key)
+    }
+
+    /// Get the first key that matches a value without
+    /// changing the currently selected key.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value of the key to find.
+    ///
+    /// # Return
+    ///
+    /// * The found key, otherwise None.
+    pub fn get_key_by_value(&self, value: usize) -> Option<&ElemKey> {
+        self.list_key.iter().find(|e| e.value() == value)
+    }
+
     /// Select the first key that matches a name.
     ///
     /// # Arguments
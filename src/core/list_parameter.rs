@@ -8,7 +8,7 @@
 // except according to those terms.
 
 use rust_decimal::prelude::*;
-use std::cell::Cell;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 use super::ElemParameter;
 use crate::ListTrait;
@@ -18,7 +18,7 @@ pub struct ListParameter {
     list_parameter: Vec<ElemParameter>,
 
     /// The index of the currently selected parameter element.
-    list_index: Cell<usize>,
+    list_index: AtomicUsize,
 }
 
 /// List of parameters default implementation.
@@ -42,7 +42,7 @@ impl ListTrait for ListParameter {
 
     fn clear(&mut self) {
         self.list_parameter.clear();
-        self.list_index.set(usize::MAX);
+        self.list_index.store(usize::MAX, Ordering::Relaxed);
     }
 
     /// Get the count of the parameter list.
@@ -62,7 +62,7 @@ impl ListTrait for ListParameter {
     /// * See description.
 
     fn index(&self) -> usize {
-        self.list_index.get()
+        self.list_index.load(Ordering::Relaxed)
     }
 
     /// Select a parameter based upon an index value.
@@ -96,7 +96,7 @@ impl ListTrait for ListParameter {
             return false;
         }
 
-        self.list_index.set(index_param);
+        self.list_index.store(index_param, Ordering::Relaxed);
 
         true
     }
@@ -118,7 +118,7 @@ impl ListParameter {
     pub fn new() -> ListParameter {
         ListParameter {
             list_parameter: Vec::new(),
-            list_index: Cell::new(usize::MAX),
+            list_index: AtomicUsize::new(usize::MAX),
         }
     }
 
@@ -167,7 +167,10 @@ impl ListParameter {
             }
         }
         if update_element {
-            match self.list_parameter.get_mut(self.list_index.get()) {
+            match self
+                .list_parameter
+                .get_mut(self.list_index.load(Ordering::Relaxed))
+            {
                 None => {
                     return false;
                 }
@@ -189,7 +192,7 @@ impl ListParameter {
         {
             None => false,
             Some(o) => {
-                self.list_index.set(o);
+                self.list_index.store(o, Ordering::Relaxed);
                 true
             }
         }
@@ -298,7 +301,10 @@ impl ListParameter {
     /// * See description.
 
     pub fn name(&self) -> &str {
-        match self.list_parameter.get(self.list_index.get()) {
+        match self
+            .list_parameter
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 panic!("Parameter list index not set");
             }
@@ -313,7 +319,10 @@ impl ListParameter {
     /// * See description.
 
     pub fn label(&self) -> &str {
-        match self.list_parameter.get(self.list_index.get()) {
+        match self
+            .list_parameter
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 panic!("Parameter list index not set");
             }
@@ -328,7 +337,10 @@ impl ListParameter {
     /// * See description.
 
     pub fn description(&self) -> &str {
-        match self.list_parameter.get(self.list_index.get()) {
+        match self
+            .list_parameter
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 panic!("Parameter list index not set");
             }
@@ -344,7 +356,10 @@ impl ListParameter {
     ///     
 
     pub fn param_type(&self) -> crate::TokenType {
-        match self.list_parameter.get(self.list_index.get()) {
+        match self
+            .list_parameter
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 panic!("Parameter list index not set");
             }
@@ -359,7 +374,10 @@ impl ListParameter {
     /// * See description.
 
     pub fn param_integeri(&self) -> i32 {
-        match self.list_parameter.get(self.list_index.get()) {
+        match self
+            .list_parameter
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 panic!("Parameter list index not set");
             }
@@ -384,7 +402,10 @@ impl ListParameter {
     /// * See description.
 
     pub fn param_decimal(&self) -> Decimal {
-        match self.list_parameter.get(self.list_index.get()) {
+        match self
+            .list_parameter
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 panic!("Parameter list index not set");
             }
@@ -399,7 +420,10 @@ impl ListParameter {
     /// * See description.
 
     pub fn param_string(&self) -> &str {
-        match self.list_parameter.get(self.list_index.get()) {
+        match self
+            .list_parameter
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 panic!("Parameter list index not set");
             }
@@ -442,29 +466,38 @@ impl ListParameter {
     /// * True if successful, otherwise false.
 
     pub fn move_param(&mut self, is_up: bool) -> bool {
-        let name: String = match self.list_parameter.get_mut(self.list_index.get()) {
+        let name: String = match self
+            .list_parameter
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
             None => return false,
             Some(o) => String::from(o.name()),
         };
 
         if is_up {
-            if self.list_index.get() == 0 {
+            if self.list_index.load(Ordering::Relaxed) == 0 {
                 return false;
             }
-            let elem = self.list_parameter.remove(self.list_index.get());
-            self.list_parameter.insert(self.list_index.get() - 1, elem);
+            let elem = self
+                .list_parameter
+                .remove(self.list_index.load(Ordering::Relaxed));
+            self.list_parameter
+                .insert(self.list_index.load(Ordering::Relaxed) - 1, elem);
         } else {
-            if self.list_index.get() + 1 >= self.list_parameter.len() {
+            if self.list_index.load(Ordering::Relaxed) + 1 >= self.list_parameter.len() {
                 return false;
             }
-            let elem = self.list_parameter.remove(self.list_index.get());
-            self.list_parameter.insert(self.list_index.get() + 1, elem);
+            let elem = self
+                .list_parameter
+                .remove(self.list_index.load(Ordering::Relaxed));
+            self.list_parameter
+                .insert(self.list_index.load(Ordering::Relaxed) + 1, elem);
         }
 
         match self.list_parameter.iter().position(|e| e.name() == name) {
             None => false,
             Some(o) => {
-                self.list_index.set(o);
+                self.list_index.store(o, Ordering::Relaxed);
                 true
             }
         }
@@ -477,13 +510,17 @@ impl ListParameter {
     /// * True if successful, otherwise false.
 
     pub fn remove(&mut self) -> bool {
-        if self.list_index.get() >= self.list_parameter.len() {
+        if self.list_index.load(Ordering::Relaxed) >= self.list_parameter.len() {
             return false;
         }
 
-        self.list_parameter.remove(self.list_index.get());
-        if self.list_index.get() > 0 {
-            self.list_index.set(self.list_index.get() - 1);
+        self.list_parameter
+            .remove(self.list_index.load(Ordering::Relaxed));
+        if self.list_index.load(Ordering::Relaxed) > 0 {
+            self.list_index.store(
+                self.list_index.load(Ordering::Relaxed) - 1,
+                Ordering::Relaxed,
+            );
         }
 
         true
@@ -505,7 +542,10 @@ impl ListParameter {
             return false;
         }
 
-        match self.list_parameter.get_mut(self.list_index.get()) {
+        match self
+            .list_parameter
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
             None => false,
             Some(o) => {
                 o.set_name(name_param);
@@ -525,7 +565,10 @@ impl ListParameter {
     /// * True if successful, otherwise false.
 
     pub fn set_label(&mut self, label_param: &str) -> bool {
-        match self.list_parameter.get_mut(self.list_index.get()) {
+        match self
+            .list_parameter
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
             None => false,
             Some(o) => {
                 o.set_label(label_param);
@@ -545,7 +588,10 @@ impl ListParameter {
     /// * True if successful, otherwise false.
 
     pub fn set_description(&mut self, desc_param: &str) -> bool {
-        match self.list_parameter.get_mut(self.list_index.get()) {
+        match self
+            .list_parameter
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
             None => false,
             Some(o) => {
                 o.set_description(desc_param);
@@ -565,7 +611,10 @@ impl ListParameter {
     /// * True if successful, otherwise false.
 
     pub fn set_type(&mut self, value_param: crate::TokenType) -> bool {
-        match self.list_parameter.get_mut(self.list_index.get()) {
+        match self
+            .list_parameter
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
             None => false,
             Some(o) => {
                 o.set_type(value_param);
@@ -585,7 +634,10 @@ impl ListParameter {
     /// * True if successful, otherwise false.
 
     pub fn set_integeri(&mut self, value_param: i32) -> bool {
-        match self.list_parameter.get_mut(self.list_index.get()) {
+        match self
+            .list_parameter
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
             None => false,
             Some(o) => {
                 o.set_integeri(value_param);
@@ -619,7 +671,10 @@ impl ListParameter {
     /// * True if successful, otherwise false.
 
     pub fn set_decimal(&mut self, value_param: Decimal) -> bool {
-        match self.list_parameter.get_mut(self.list_index.get()) {
+        match self
+            .list_parameter
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
             None => false,
             Some(o) => {
                 o.set_decimal(value_param);
@@ -639,7 +694,10 @@ impl ListParameter {
     /// * True if successful, otherwise false.
 
     pub fn set_string(&mut self, value_param: &str) -> bool {
-        match self.list_parameter.get_mut(self.list_index.get()) {
+        match self
+            .list_parameter
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
             None => false,
             Some(o) => {
                 o.set_string(value_param);
@@ -7,8 +7,9 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use chrono::NaiveDate;
 use rust_decimal::prelude::*;
-use std::cell::Cell;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 use super::ElemStatisticHelper;
 use crate::ListTrait;
@@ -18,7 +19,7 @@ pub struct ListStatisticHelper {
     list_statistic_helper: Vec<ElemStatisticHelper>,
 
     /// The index of the currently selected statistic element.
-    list_index: Cell<usize>,
+    list_index: AtomicUsize,
 }
 
 /// List of statistic helper list implementation.
@@ -28,7 +29,7 @@ impl ListTrait for ListStatisticHelper {
 
     fn clear(&mut self) {
         self.list_statistic_helper.clear();
-        self.list_index.set(usize::MAX);
+        self.list_index.store(usize::MAX, Ordering::Relaxed);
     }
 
     /// Get the count of the statistic helper list.
@@ -48,7 +49,7 @@ impl ListTrait for ListStatisticHelper {
     /// * See description.
 
     fn index(&self) -> usize {
-        self.list_index.get()
+        self.list_index.load(Ordering::Relaxed)
     }
 
     /// Select a statistic based upon an index value.
@@ -86,7 +87,7 @@ impl ListTrait for ListStatisticHelper {
             return false;
         }
 
-        self.list_index.set(index_param);
+        self.list_index.store(index_param, Ordering::Relaxed);
 
         true
     }
@@ -118,7 +119,7 @@ impl ListStatisticHelper {
     pub fn new() -> ListStatisticHelper {
         ListStatisticHelper {
             list_statistic_helper: Vec::new(),
-            list_index: Cell::new(usize::MAX),
+            list_index: AtomicUsize::new(usize::MAX),
         }
     }
 
@@ -137,7 +138,7 @@ impl ListStatisticHelper {
     pub fn add_statistic_helper(
         &mut self,
         name_param: &str,
-        last_date_param: usize,
+        last_date_param: NaiveDate,
         elem_am_index_param: usize,
     ) -> bool {
         let new_elem_stat: ElemStatisticHelper = ElemStatisticHelper::new(
@@ -161,7 +162,7 @@ impl ListStatisticHelper {
         {
             None => false,
             Some(o) => {
-                self.list_index.set(o);
+                self.list_index.store(o, Ordering::Relaxed);
                 true
             }
         }
@@ -212,7 +213,10 @@ impl ListStatisticHelper {
     /// * See description.
 
     pub fn name(&self) -> &str {
-        match self.list_statistic_helper.get(self.list_index.get()) {
+        match self
+            .list_statistic_helper
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 panic!("Statistic helper list index not set");
             }
@@ -227,7 +231,10 @@ impl ListStatisticHelper {
     /// * See description.
 
     pub fn principal_decrease(&self) -> Decimal {
-        match self.list_statistic_helper.get(self.list_index.get()) {
+        match self
+            .list_statistic_helper
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 panic!("Statistic helper list index not set");
             }
@@ -242,7 +249,10 @@ impl ListStatisticHelper {
     /// * See description.
 
     pub fn principal_increase(&self) -> Decimal {
-        match self.list_statistic_helper.get(self.list_index.get()) {
+        match self
+            .list_statistic_helper
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 panic!("Statistic helper list index not set");
             }
@@ -257,7 +267,10 @@ impl ListStatisticHelper {
     /// * See description.
 
     pub fn interest(&self) -> Decimal {
-        match self.list_statistic_helper.get(self.list_index.get()) {
+        match self
+            .list_statistic_helper
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 panic!("Statistic helper list index not set");
             }
@@ -272,7 +285,10 @@ impl ListStatisticHelper {
     /// * See description.
 
     pub fn sl_interest(&self) -> Decimal {
-        match self.list_statistic_helper.get(self.list_index.get()) {
+        match self
+            .list_statistic_helper
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 panic!("Statistic helper list index not set");
             }
@@ -287,7 +303,10 @@ impl ListStatisticHelper {
     /// * See description.
 
     pub fn value_to_interest(&self) -> Decimal {
-        match self.list_statistic_helper.get(self.list_index.get()) {
+        match self
+            .list_statistic_helper
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 panic!("Statistic helper list index not set");
             }
@@ -302,7 +321,10 @@ impl ListStatisticHelper {
     /// * See description.
 
     pub fn value_to_principal(&self) -> Decimal {
-        match self.list_statistic_helper.get(self.list_index.get()) {
+        match self
+            .list_statistic_helper
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 panic!("Statistic helper list index not set");
             }
@@ -316,8 +338,11 @@ impl ListStatisticHelper {
     ///
     /// * See description.
 
-    pub fn last_date(&self) -> usize {
-        match self.list_statistic_helper.get(self.list_index.get()) {
+    pub fn last_date(&self) -> NaiveDate {
+        match self
+            .list_statistic_helper
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 panic!("Statistic helper list index not set");
             }
@@ -332,7 +357,10 @@ impl ListStatisticHelper {
     /// * See description.
 
     pub fn elem_am_index(&self) -> usize {
-        match self.list_statistic_helper.get(self.list_index.get()) {
+        match self
+            .list_statistic_helper
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 panic!("Statistic helper list index not set");
             }
@@ -371,7 +399,10 @@ impl ListStatisticHelper {
     /// * True if successful, otherwise false.
 
     pub fn incr_principal_decrease(&mut self, principal_decrease_param: Decimal) -> bool {
-        match self.list_statistic_helper.get_mut(self.list_index.get()) {
+        match self
+            .list_statistic_helper
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
             None => false,
             Some(o) => {
                 o.set_principal_decrease(o.principal_decrease() + principal_decrease_param);
@@ -391,7 +422,10 @@ impl ListStatisticHelper {
     /// * True if successful, otherwise false.
 
     pub fn incr_principal_increase(&mut self, principal_increase_param: Decimal) -> bool {
-        match self.list_statistic_helper.get_mut(self.list_index.get()) {
+        match self
+            .list_statistic_helper
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
             None => false,
             Some(o) => {
                 o.set_principal_increase(o.principal_increase() + principal_increase_param);
@@ -411,7 +445,10 @@ impl ListStatisticHelper {
     /// * True if successful, otherwise false.
 
     pub fn incr_interest(&mut self, interest_param: Decimal) -> bool {
-        match self.list_statistic_helper.get_mut(self.list_index.get()) {
+        match self
+            .list_statistic_helper
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
             None => false,
             Some(o) => {
                 o.set_interest(o.interest() + interest_param);
@@ -431,7 +468,10 @@ impl ListStatisticHelper {
     /// * True if successful, otherwise false.
 
     pub fn incr_sl_interest(&mut self, sl_interest_param: Decimal) -> bool {
-        match self.list_statistic_helper.get_mut(self.list_index.get()) {
+        match self
+            .list_statistic_helper
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
             None => false,
             Some(o) => {
                 o.set_sl_interest(o.sl_interest() + sl_interest_param);
@@ -451,7 +491,10 @@ impl ListStatisticHelper {
     /// * True if successful, otherwise false.
 
     pub fn incr_value_to_interest(&mut self, value_to_interest_param: Decimal) -> bool {
-        match self.list_statistic_helper.get_mut(self.list_index.get()) {
+        match self
+            .list_statistic_helper
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
             None => false,
             Some(o) => {
                 o.set_value_to_interest(o.value_to_interest() + value_to_interest_param);
@@ -471,7 +514,10 @@ impl ListStatisticHelper {
     /// * True if successful, otherwise false.
 
     pub fn incr_value_to_principal(&mut self, value_to_principal_param: Decimal) -> bool {
-        match self.list_statistic_helper.get_mut(self.list_index.get()) {
+        match self
+            .list_statistic_helper
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
             None => false,
             Some(o) => {
                 o.set_value_to_principal(o.value_to_principal() + value_to_principal_param);
@@ -487,13 +533,17 @@ impl ListStatisticHelper {
     /// * True if successful, otherwise false.
 
     pub fn remove(&mut self) -> bool {
-        if self.list_index.get() >= self.list_statistic_helper.len() {
+        if self.list_index.load(Ordering::Relaxed) >= self.list_statistic_helper.len() {
             return false;
         }
 
-        self.list_statistic_helper.remove(self.list_index.get());
-        if self.list_index.get() > 0 {
-            self.list_index.set(self.list_index.get() - 1);
+        self.list_statistic_helper
+            .remove(self.list_index.load(Ordering::Relaxed));
+        if self.list_index.load(Ordering::Relaxed) > 0 {
+            self.list_index.store(
+                self.list_index.load(Ordering::Relaxed) - 1,
+                Ordering::Relaxed,
+            );
         }
         true
     }
@@ -505,10 +555,13 @@ impl ListStatisticHelper {
     /// * True if successful, otherwise false.
 
     pub fn reset(&mut self) -> bool {
-        if self.list_index.get() >= self.list_statistic_helper.len() {
+        if self.list_index.load(Ordering::Relaxed) >= self.list_statistic_helper.len() {
             return false;
         }
-        match self.list_statistic_helper.get_mut(self.list_index.get()) {
+        match self
+            .list_statistic_helper
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
             None => false,
             Some(o) => {
                 o.set_principal_decrease(dec!(0.0));
@@ -532,8 +585,11 @@ impl ListStatisticHelper {
     ///
     /// * True if successful, otherwise false.
 
-    pub fn set_last_date(&mut self, last_date_param: usize) -> bool {
-        match self.list_statistic_helper.get_mut(self.list_index.get()) {
+    pub fn set_last_date(&mut self, last_date_param: NaiveDate) -> bool {
+        match self
+            .list_statistic_helper
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
             None => false,
             Some(o) => {
                 o.set_last_date(last_date_param);
@@ -553,7 +609,10 @@ impl ListStatisticHelper {
     /// * True if successful, otherwise false.
 
     pub fn set_elem_am_index(&mut self, elem_am_index_param: usize) -> bool {
-        match self.list_statistic_helper.get_mut(self.list_index.get()) {
+        match self
+            .list_statistic_helper
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
             None => false,
             Some(o) => {
                 o.set_elem_am_index(elem_am_index_param);
@@ -7,17 +7,16 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-use std::cell::Cell;
-
 use super::ElemSummary;
 use crate::ListTrait;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 pub struct ListSummary {
     /// The list of summary items.
     list_summary: Vec<ElemSummary>,
 
     /// The index of the currently selected summary item.
-    list_index: Cell<usize>,
+    list_index: AtomicUsize,
 }
 
 /// List of summary items list implementation.
@@ -27,7 +26,7 @@ impl ListTrait for ListSummary {
 
     fn clear(&mut self) {
         self.list_summary.clear();
-        self.list_index.set(usize::MAX);
+        self.list_index.store(usize::MAX, Ordering::Relaxed);
     }
 
     /// Get the count of the summary list.
@@ -47,7 +46,7 @@ impl ListTrait for ListSummary {
     /// * See description.
 
     fn index(&self) -> usize {
-        self.list_index.get()
+        self.list_index.load(Ordering::Relaxed)
     }
 
     /// Select a summary item based upon an index value.
@@ -85,7 +84,7 @@ impl ListTrait for ListSummary {
             return false;
         }
 
-        self.list_index.set(index_param);
+        self.list_index.store(index_param, Ordering::Relaxed);
 
         true
     }
@@ -117,7 +116,7 @@ impl ListSummary {
     pub fn new() -> ListSummary {
         ListSummary {
             list_summary: Vec::new(),
-            list_index: Cell::new(usize::MAX),
+            list_index: AtomicUsize::new(usize::MAX),
         }
     }
 
@@ -160,7 +159,7 @@ impl ListSummary {
         {
             None => false,
             Some(o) => {
-                self.list_index.set(o);
+                self.list_index.store(o, Ordering::Relaxed);
                 true
             }
         }
@@ -173,7 +172,10 @@ impl ListSummary {
     /// * See description.
 
     pub fn name(&self) -> &str {
-        match self.list_summary.get(self.list_index.get()) {
+        match self
+            .list_summary
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 panic!("Summary list index not set");
             }
@@ -188,7 +190,10 @@ impl ListSummary {
     /// * See description.
 
     pub fn label(&self) -> &str {
-        match self.list_summary.get(self.list_index.get()) {
+        match self
+            .list_summary
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 panic!("Summary list index not set");
             }
@@ -203,7 +208,10 @@ impl ListSummary {
     /// * See description.
 
     pub fn label_expr(&self) -> &str {
-        match self.list_summary.get(self.list_index.get()) {
+        match self
+            .list_summary
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 panic!("Summary list index not set");
             }
@@ -218,7 +226,10 @@ impl ListSummary {
     /// * See description.
 
     pub fn result(&self) -> &str {
-        match self.list_summary.get(self.list_index.get()) {
+        match self
+            .list_summary
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 panic!("Summary list index not set");
             }
@@ -233,7 +244,10 @@ impl ListSummary {
     /// * See description.
 
     pub fn result_expr(&self) -> &str {
-        match self.list_summary.get(self.list_index.get()) {
+        match self
+            .list_summary
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 panic!("Summary list index not set");
             }
@@ -248,13 +262,17 @@ impl ListSummary {
     /// * True if successful, otherwise false.
 
     pub fn remove(&mut self) -> bool {
-        if self.list_index.get() >= self.list_summary.len() {
+        if self.list_index.load(Ordering::Relaxed) >= self.list_summary.len() {
             return false;
         }
 
-        self.list_summary.remove(self.list_index.get());
-        if self.list_index.get() > 0 {
-            self.list_index.set(self.list_index.get() - 1);
+        self.list_summary
+            .remove(self.list_index.load(Ordering::Relaxed));
+        if self.list_index.load(Ordering::Relaxed) > 0 {
+            self.list_index.store(
+                self.list_index.load(Ordering::Relaxed) - 1,
+                Ordering::Relaxed,
+            );
         }
         true
     }
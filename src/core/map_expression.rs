@@ -0,0 +1,87 @@
+//! The compiled expression cache.
+// Copyright (c) 2021 ShiftLeft Software
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use super::ElemExprNode;
+
+pub struct MapExpression {
+    /// The compiled expressions keyed by expression text.
+    expression: HashMap<String, Arc<ElemExprNode>>,
+}
+
+/// The compiled expression cache default implementation.
+impl Default for MapExpression {
+    /// Create and return a new compiled expression cache.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn default() -> Self {
+        MapExpression::new()
+    }
+}
+
+/// The compiled expression cache implementation.
+impl MapExpression {
+    /// Create and return a new compiled expression cache.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn new() -> MapExpression {
+        MapExpression {
+            expression: HashMap::new(),
+        }
+    }
+
+    /// Clear all compiled expressions from the cache.
+    pub fn clear(&mut self) {
+        self.expression.clear();
+    }
+
+    /// Get the count of compiled expressions in the cache.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn count(&self) -> usize {
+        self.expression.len()
+    }
+
+    /// Find and return a compiled expression by expression text.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The expression text.
+    ///
+    /// # Return
+    ///
+    /// * The compiled expression, otherwise None.
+    pub fn get_expression(&self, text: &str) -> Option<Arc<ElemExprNode>> {
+        self.expression.get(text).map(Arc::clone)
+    }
+
+    /// Add a compiled expression to the cache. The cache is
+    /// cleared first when it holds the maximum number of
+    /// compiled expressions.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The expression text.
+    /// * `elem_expr_node` - The compiled expression.
+    pub fn add_expression(&mut self, text: &str, elem_expr_node: Arc<ElemExprNode>) {
+        if self.expression.len() >= crate::MAXIMUM_COMPILED_EXPRESSIONS {
+            self.expression.clear();
+        }
+
+        self.expression.insert(String::from(text), elem_expr_node);
+    }
+}
@@ -75,6 +75,25 @@ impl MapSymbol {
         self.symbol.get(name)
     }
 
+    /// Find and return an element of an indexed symbol
+    /// (i.e., name[index]) by the name of the symbol.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the indexed symbol to find.
+    ///
+    /// # Return
+    ///
+    /// * The found symbol, otherwise None.
+    pub fn get_indexed_symbol(&self, name: &str) -> Option<&ElemSymbol> {
+        let prefix = format!("{}[", name);
+
+        self.symbol
+            .iter()
+            .find(|(key, _)| key.starts_with(prefix.as_str()))
+            .map(|(_, value)| value)
+    }
+
     /// Find and return the mut symbol element by name.
     ///
     /// # Arguments
@@ -61,6 +61,30 @@ pub const MAXIMUM_ITERATIONS_CALCULATE_PERIODS: usize = 30;
 pub const MAXIMUM_ITERATIONS_CALCULATE_PRINCIPAL: usize = 30;
 /// Maximum iterations for calculate yield. 
 pub const MAXIMUM_ITERATIONS_CALCULATE_YIELD: usize = 30;
+/// Maximum iterations for calculate internal rate of return (per root). 
+pub const MAXIMUM_ITERATIONS_CALCULATE_IRR: usize = 100;
+/// Maximum iterations for the Decimal nth root used by Business/252. 
+pub const MAXIMUM_ITERATIONS_ROOT: usize = 100;
+/// Default rate tolerance for calculate internal rate of return. 
+pub const DEFAULT_TOLERANCE_CALCULATE_IRR: &str = "0.0000000001";
+/// Maximum periodic rate searched for internal rate of return roots. 
+pub const MAX_CALC_IRR: &str = "10.0";
+/// Maximum compiled expressions held in the expression cache. 
+pub const MAXIMUM_COMPILED_EXPRESSIONS: usize = 4096;
+/// Maximum nesting depth of user-defined function calls. 
+pub const MAXIMUM_FUNCTION_DEPTH: usize = 64;
+/// Default maximum number of nodes evaluated by an expression. 
+pub const DEFAULT_EXPRESSION_STEPS: usize = 1_000_000;
+/// Default maximum nesting depth of an expression. 
+pub const DEFAULT_EXPRESSION_DEPTH: usize = 100;
+/// Default maximum length in characters of an expression string value. 
+pub const DEFAULT_EXPRESSION_STRING_LENGTH: usize = 1_048_576;
+/// Maximum iterations for the financial rate function. 
+pub const MAXIMUM_ITERATIONS_CALCULATE_RATE: usize = 100;
+/// Default rate tolerance for the financial rate function. 
+pub const DEFAULT_TOLERANCE_CALCULATE_RATE: &str = "0.0000000001";
+/// Periods in the PSA standard prepayment ramp. 
+pub const PSA_RAMP_PERIODS: usize = 30;
 
 /// Maximum calculated principal (can be increased).
 pub const MAX_CALC_PRINCIPAL: &str = "1000000000000.0";
@@ -125,6 +149,8 @@ pub const COL_LABEL_BALANCE: &str = "Col_Label_Balance";
 pub const COL_LABEL_EVENT_NAME: &str = "Col_Label_Event_Name";
 /// Column labels - Next-name. 
 pub const COL_LABEL_NEXT_NAME: &str = "Col_Label_Next_Name";
+/// Column labels - Deferred-interest. 
+pub const COL_LABEL_DEFERRED_INTEREST: &str = "Col_Label_Deferred_Interest";
 
 /// Column indexes - StrBal. 
 pub const COL_LABEL_STRBAL: usize = 1000;
@@ -157,6 +183,8 @@ pub const GROUP_CURRENT_VALUE: &str = "CurValue";
 pub const GROUP_STATISTIC_VALUE: &str = "StatValue";
 /// Group - Interest change. 
 pub const GROUP_INTEREST_CHANGE: &str = "IntChange";
+/// Group - Fee (finance charge). 
+pub const GROUP_FEE: &str = "Fee";
 
 /// Name - Column order. 
 pub const NAME_COLUMNS: &str = "Columns";
@@ -209,6 +237,10 @@ pub const JSON_SERIALIZE_AMORTIZATION_LIST: usize = 64;
 pub const JSON_SERIALIZE_AMORTIZATION_LIST_ROLLUPS: usize = 128;
 /// Serialize cashflows with amortization list (with rollup and detail elements)
 pub const JSON_SERIALIZE_AMORTIZATION_LIST_DETAILS: usize = 256;
+/// Serialize business day calendars.
+pub const JSON_SERIALIZE_CALENDARS: usize = 512;
+/// Serialize rate indexes.
+pub const JSON_SERIALIZE_RATE_INDEXES: usize = 1024;
 
 /// Error prefix. 
 pub const ERROR_PREFIX: &str = ">> ";
@@ -85,6 +85,76 @@ pub enum DayCountType {
     Dc30E = 8,
     /// 30E+/<DaysInYear>.
     Dc30EP = 9,
+    /// Actual/360.
+    Actual360 = 10,
+    /// Actual/365 (Fixed).
+    Actual365F = 11,
+    /// Actual/364.
+    Actual364 = 12,
+    /// 30/360 (ISDA) with the February end of month rule.
+    Dc30ISDA = 13,
+    /// 30E/360 (ISDA).
+    Dc30EISDA = 14,
+    /// Business/252 (business days only).
+    Business252 = 15,
+}
+
+/// Annual percentage rate type enumeration.
+
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum AprType {
+    /// US Regulation Z (Appendix J actuarial method).
+    RegulationZ = 1,
+    /// EU Consumer Credit Directive (annual percentage rate of charge).
+    ConsumerCreditDirective = 2,
+}
+
+/// Prepayment model type enumeration.
+
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum PrepaymentType {
+    /// No prepayments.
+    None = 0,
+    /// Constant conditional prepayment rate (annual percentage).
+    Cpr = 1,
+    /// Constant single monthly mortality (periodic percentage).
+    Smm = 2,
+    /// PSA standard prepayment model (speed percentage).
+    Psa = 3,
+    /// User-supplied vector of conditional prepayment rates by period.
+    Vector = 4,
+}
+
+/// Business day convention enumeration.
+
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum BusinessDayType {
+    /// Do not adjust dates.
+    Unadjusted = 0,
+    /// Next business day.
+    Following = 1,
+    /// Next business day unless it falls in the next month (then preceding).
+    ModifiedFollowing = 2,
+    /// Previous business day.
+    Preceding = 3,
+    /// Previous business day unless it falls in the previous month (then following).
+    ModifiedPreceding = 4,
+}
+
+/// Holiday type enumeration.
+
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum HolidayType {
+    /// Same month and day every year.
+    Fixed = 1,
+    /// Nth weekday of a month (e.g., third Monday of January).
+    NthWeekday = 2,
+    /// Last weekday of a month (e.g., last Monday of May).
+    LastWeekday = 3,
+    /// Number of days from Easter Sunday (Western).
+    Easter = 4,
+    /// A single date.
+    Date = 5,
 }
 
 /// Frequency type enumeration.
@@ -147,6 +217,8 @@ pub enum TokenType {
     Decimal = 2, // Symbol table type
     /// &str token type.
     String = 3, // Symbol table type
+    /// List token type.
+    List = 4, // Symbol table type
     /// Alpha token type.
     Alpha = 21,
     /// Punctuation token type.
@@ -157,6 +229,22 @@ pub enum TokenType {
     LeftParen = 24,
 }
 
+/// Expression node type enumeration.
+
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum NodeType {
+    /// Constant (literal) node type.
+    Constant = 0,
+    /// Symbol reference node type.
+    Symbol = 1,
+    /// Operator node type.
+    Operator = 2,
+    /// Function call node type.
+    Function = 3,
+    /// Sequence of comma separated expressions node type.
+    Sequence = 4,
+}
+
 /// Format type enumeration.
 
 #[derive(Clone, Copy, Eq, PartialEq, Debug)]
@@ -279,6 +367,62 @@ pub enum FunctionType {
     Type = 29,
     /// Uppercase function.
     Uppercase = 30,
+    /// Effective annual rate (EFFECT) function.
+    Effect = 31,
+    /// Future value (FV) function.
+    Fv = 32,
+    /// Interest payment (IPMT) function.
+    Ipmt = 33,
+    /// Nominal annual rate (NOMINAL) function.
+    Nominal = 34,
+    /// Number of periods (NPER) function.
+    Nper = 35,
+    /// Net present value (NPV) function.
+    Npv = 36,
+    /// Payment (PMT) function.
+    Pmt = 37,
+    /// Principal payment (PPMT) function.
+    Ppmt = 38,
+    /// Present value (PV) function.
+    Pv = 39,
+    /// Periodic rate (RATE) function.
+    Rate = 40,
+    /// Dated net present value (XNPV) function.
+    Xnpv = 41,
+    /// Amortization list column function.
+    AmList = 42,
+    /// Average of list function.
+    Avg = 43,
+    /// Count of list function.
+    Count = 44,
+    /// Filter list function.
+    Filter = 45,
+    /// First of list function.
+    First = 46,
+    /// Last of list function.
+    Last = 47,
+    /// New list function.
+    List = 48,
+    /// Sum of list function.
+    Sum = 49,
+    /// Add months to date function.
+    DateAddMonths = 50,
+    /// Day of date function.
+    DateDay = 51,
+    /// Day of year of date function.
+    DateDayOfYear = 52,
+    /// End of month date function.
+    DateEndOfMonth = 53,
+    /// Leap year of date function.
+    DateIsLeapYear = 54,
+    /// Month of date function.
+    DateMonth = 55,
+    /// Next business day function.
+    DateNextBusinessDay = 56,
+    /// Day of week of date function.
+    DateWeekday = 57,
+    /// Year of date function.
+    DateYear = 58,
 }
 
 /// Error type enumeration.
@@ -339,6 +483,18 @@ pub enum ErrorType {
     Element = 25,
     /// Invalid Json.
     Json = 26,
+    /// Invalid calendar.
+    Calendar = 27,
+    /// Function recursion too deep.
+    Recursion = 28,
+    /// List expected.
+    List = 29,
+    /// Expression could not be formatted.
+    Format = 30,
+    /// Expression limit exceeded.
+    Limit = 31,
+    /// Invalid rate index or no rate observed.
+    RateIndex = 32,
 }
 
 /// Table type enumeration.
@@ -409,6 +565,8 @@ pub enum ColumnType {
     EventName = 25,
     /// Column names - Next-name.
     NextName = 26,
+    /// Column names - Deferred-interest.
+    DeferredInterest = 27,
     /// Column names - StrBal
     StrBal = 1000,
     /// Column names - EAR
@@ -16,6 +16,9 @@ use calc_expression::CalcExpression;
 mod calc_scan;
 use calc_scan::CalcScan;
 
+pub mod calc_compliance;
+pub use calc_compliance::CalcCompliance;
+
 pub mod calc_engine;
 pub use calc_engine::CalcEngine;
 
@@ -25,8 +28,16 @@ pub use calc_json_deserialize::CalcJsonDeserialize;
 pub mod calc_json_serialize;
 pub use calc_json_serialize::CalcJsonSerialize;
 
+pub mod calc_json_validate;
+pub use calc_json_validate::CalcJsonValidate;
+
 pub mod calc_manager;
-pub use calc_manager::CalcManager;
+pub use calc_manager::{CalcManager, NativeFunction};
+
+#[cfg(feature = "serde")]
+pub mod calc_serde;
+#[cfg(feature = "serde")]
+pub use calc_serde::CalcSeed;
 
 pub mod calc_utility;
 pub use calc_utility::CalcUtility;
@@ -47,6 +58,9 @@ pub use elem_exchange_rate::ElemExchangeRate;
 pub mod elem_preferences;
 pub use elem_preferences::ElemPreferences;
 
+pub mod elem_schema_violation;
+pub use elem_schema_violation::ElemSchemaViolation;
+
 pub mod elem_template_event;
 pub use elem_template_event::ElemTemplateEvent;
 
@@ -56,12 +70,18 @@ pub use elem_template_group::ElemTemplateGroup;
 pub mod list_locale;
 pub use list_locale::ListLocale;
 
+pub mod list_calendar;
+pub use list_calendar::ListCalendar;
+
 pub mod list_cashflow;
 pub use list_cashflow::ListCashflow;
 
 pub mod list_exchange_rate;
 pub use list_exchange_rate::ListExchangeRate;
 
+pub mod list_rate_index;
+pub use list_rate_index::ListRateIndex;
+
 pub mod list_template_event;
 pub use list_template_event::ListTemplateEvent;
 
@@ -7,38 +7,42 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use chrono::NaiveDate;
 use rust_decimal::prelude::*;
-use std::cell::{Cell, Ref, RefCell, RefMut};
-use std::rc::Rc;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Mutex;
 
 use super::{CalcExpression, CalcManager, CalcUtility};
 use crate::core::{
-    CoreUtility, ElemBalanceResult, ElemExtension, ElemSymbol, ListAmortization, ListDescriptor,
-    ListEvent, ListParameter, ListStatisticHelper,
+    CoreFinancial, CoreUtility, ElemBalanceResult, ElemCalendar, ElemDefaultScenario, ElemError,
+    ElemExtension, ElemPrepayment, ElemRateIndex, ElemSolverSettings, ElemSymbol, ListAmortization,
+    ListDescriptor, ListEvent, ListFunction, ListParameter, ListStatisticHelper,
 };
 use crate::ListTrait;
 
 pub struct CalcCalculate {
-    /// Calculator manager element.
-    calc_manager: Rc<RefCell<CalcManager>>,
-
     /// Start of fiscal year in MMDD format.
-    fiscal_year_start: Cell<usize>,
+    fiscal_year_start: AtomicUsize,
     /// Number of significant decimal digits.
-    decimal_digits: Cell<usize>,
+    decimal_digits: AtomicUsize,
     /// Cashflow descriptor list.
     list_descriptor_cashflow: Option<ListDescriptor>,
+    /// Cashflow user-defined function list.
+    list_function_cashflow: Option<ListFunction>,
 
-    /// Expression object for this calculate object.
-    calc_expression: RefCell<CalcExpression>,
     /// Last compounded amount calculated by the interest method.
-    interest: Cell<Decimal>,
+    interest: Mutex<Decimal>,
     /// Last straight-line amount calculated by the interest method.
-    sl_interest: Cell<Decimal>,
-    /// Last date that interest was factored through.
-    last_interest_date: Cell<usize>,
+    sl_interest: Mutex<Decimal>,
+    /// Last date that interest was factored through (NaiveDate::MIN if none).
+    last_interest_date: Mutex<NaiveDate>,
     /// One or more statistic events were seen in the amortization method.
-    statistic_event_seen: Cell<bool>,
+    statistic_event_seen: AtomicBool,
+    /// Prepayment model applied when the cashflow is balanced (None if not applicable).
+    prepayment: Mutex<Option<ElemPrepayment>>,
+    /// Default scenario applied when the cashflow is balanced (None if not applicable).
+    default_scenario: Mutex<Option<ElemDefaultScenario>>,
 }
 
 /// The primary calculation implementation methods.
@@ -48,65 +52,113 @@ impl CalcCalculate {
     ///
     /// # Arguments
     ///
+    /// * `calc_manager_param` - Calculation manager.
     /// * `list_descriptor_cashflow_param` - Cashflow descriptor list.
+    /// * `list_function_cashflow_param` - Cashflow user-defined function list.
     ///
     /// # Return
     ///
     /// * See description.
 
     pub fn new(
-        calc_manager_param: &Rc<RefCell<CalcManager>>,
+        calc_manager_param: &CalcManager,
         list_descriptor_cashflow_param: Option<&ListDescriptor>,
+        list_function_cashflow_param: Option<&ListFunction>,
     ) -> CalcCalculate {
-        let fys = calc_manager_param.borrow().fiscal_year_start(false) % 10000;
-        let dd = calc_manager_param.borrow().decimal_digits(false);
+        let fys = calc_manager_param.fiscal_year_start(false) % 10000;
+        let dd = calc_manager_param.decimal_digits(false);
 
         let list_descriptor: Option<ListDescriptor> = match list_descriptor_cashflow_param {
             None => None,
             Some(o) => Option::from(o.copy(false, false)),
         };
 
-        return CalcCalculate {
-            calc_manager: Rc::clone(calc_manager_param),
-            fiscal_year_start: Cell::new(fys),
-            decimal_digits: Cell::new(dd),
+        CalcCalculate {
+            fiscal_year_start: AtomicUsize::new(fys),
+            decimal_digits: AtomicUsize::new(dd),
             list_descriptor_cashflow: list_descriptor,
-            calc_expression: RefCell::new(CalcExpression::new(calc_manager_param, fys, dd)),
-            interest: Cell::new(dec!(0.0)),
-            sl_interest: Cell::new(dec!(0.0)),
-            last_interest_date: Cell::new(0),
-            statistic_event_seen: Cell::new(false),
-        };
+            list_function_cashflow: list_function_cashflow_param.map(|o| o.copy()),
+            interest: Mutex::new(dec!(0.0)),
+            sl_interest: Mutex::new(dec!(0.0)),
+            last_interest_date: Mutex::new(NaiveDate::MIN),
+            statistic_event_seen: AtomicBool::new(false),
+            prepayment: Mutex::new(None),
+            default_scenario: Mutex::new(None),
+        }
     }
 
-    /// Get the calculation manager.
+    /// Create and return a new expression with the cashflow
+    /// user-defined functions of this calculate element.
+    ///
+    /// # Arguments
+    ///
+    /// * `calc_mgr` - Calculation manager.
     ///
     /// # Return
     ///
     /// * See description.
+    fn new_expression<'a>(&self, calc_mgr: &'a CalcManager) -> CalcExpression<'a> {
+        let mut calc_expression = CalcExpression::new(
+            calc_mgr,
+            self.fiscal_year_start.load(Ordering::Relaxed),
+            self.decimal_digits.load(Ordering::Relaxed),
+        );
+        calc_expression.init_list_function(self.list_function_cashflow.as_ref());
+        calc_expression
+    }
 
-    fn calc_mgr(&self) -> Ref<CalcManager> {
-        self.calc_manager.borrow()
+    /// Get the prepayment model applied when the cashflow is balanced.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn prepayment(&self) -> Option<ElemPrepayment> {
+        self.prepayment.lock().unwrap().clone()
     }
 
-    /// Get the expression.
+    /// Get the default scenario applied when the cashflow is balanced.
     ///
     /// # Return
     ///
     /// * See description.
+    pub fn default_scenario(&self) -> Option<ElemDefaultScenario> {
+        *self.default_scenario.lock().unwrap()
+    }
 
-    fn expr(&self) -> Ref<CalcExpression> {
-        self.calc_expression.borrow()
+    /// Get the last compounded amount calculated by the interest method.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn last_interest(&self) -> Decimal {
+        *self.interest.lock().unwrap()
     }
 
-    /// Get the mutable expression.
+    /// Get the last straight-line amount calculated by the interest method.
     ///
     /// # Return
     ///
     /// * See description.
+    fn last_sl_interest(&self) -> Decimal {
+        *self.sl_interest.lock().unwrap()
+    }
+
+    /// Set the last compounded amount calculated by the interest method.
+    ///
+    /// # Arguments
+    ///
+    /// * `interest_param` - See description.
+    fn set_last_interest(&self, interest_param: Decimal) {
+        *self.interest.lock().unwrap() = interest_param;
+    }
 
-    fn expr_mut(&self) -> RefMut<CalcExpression> {
-        self.calc_expression.borrow_mut()
+    /// Set the last straight-line amount calculated by the interest method.
+    ///
+    /// # Arguments
+    ///
+    /// * `sl_interest_param` - See description.
+    fn set_last_sl_interest(&self, sl_interest_param: Decimal) {
+        *self.sl_interest.lock().unwrap() = sl_interest_param;
     }
 
     /// Performs primary calculations on an amortization cashflow.
@@ -118,6 +170,7 @@ impl CalcCalculate {
     ///
     /// # Arguments
     ///
+    /// * `calc_mgr` - Calculation manager.
     /// * `list_am` - The amortization list to balance.
     /// * `list_statistic_helper` - The list of active statistic elements.
     /// * `elem_balance_prev` - The prior results from this method (only used
@@ -134,15 +187,17 @@ impl CalcCalculate {
     ///
     /// * The results from this method or an error code.
 
+    #[allow(clippy::too_many_arguments)]
     pub fn balance_cashflow(
         &self,
+        calc_mgr: &CalcManager,
         list_am: &mut ListAmortization,
         list_statistic_helper: &mut ListStatisticHelper,
         elem_balance_prev: &ElemBalanceResult,
         include_aux_passive: bool,
         rule_of_78_balance: bool,
         optimize: bool,
-    ) -> Result<ElemBalanceResult, crate::ErrorType> {
+    ) -> Result<ElemBalanceResult, ElemError> {
         let mut balance = dec!(0.0);
         let mut sl_balance = dec!(0.0);
         let mut acc_balance = dec!(0.0);
@@ -158,13 +213,27 @@ impl CalcCalculate {
         let mut int_days_in_year = crate::DEFAULT_DAYS_IN_YEAR;
         let mut int_effective_frequency = crate::FrequencyType::None;
         let mut int_round_balance = crate::RoundType::None;
+        let mut int_calendar = ElemCalendar::new("");
         let mut total_prin_stats: usize = 0;
         let mut sum_of_the_digits: usize = 0;
-        let mut bal_date: usize = 0;
+        let mut bal_date = NaiveDate::MIN;
 
         let mut cv_present_seen: bool = false;
         let mut int_rule_of_78_seen: bool = false;
 
+        let mut orig_principal = dec!(0.0);
+        let mut accrued_interest = dec!(0.0);
+        let mut capped_payment: Option<Decimal> = None;
+        let mut capped_event_index = usize::MAX;
+        let mut rate_reset: bool = false;
+
+        let prepayment = self.prepayment();
+        let default_scenario = self.default_scenario();
+        let modelled = prepayment.is_some() || default_scenario.is_some();
+        let mut prepayment_age: usize = 0;
+        let mut survival_factor = dec!(1.0);
+        let mut delinquency_pipeline: VecDeque<Decimal> = VecDeque::new();
+
         if rule_of_78_balance {
             total_prin_stats = elem_balance_prev.prin_total();
             total_interest = elem_balance_prev.interest_total();
@@ -172,11 +241,11 @@ impl CalcCalculate {
                 sum_of_the_digits += am_index;
             }
         }
-        self.expr_mut().clear();
+        let mut calc_expression = self.new_expression(calc_mgr);
         list_statistic_helper.clear();
 
         let mut elem_balance_result = ElemBalanceResult::new();
-        self.last_interest_date.set(0);
+        *self.last_interest_date.lock().unwrap() = NaiveDate::MIN;
 
         for am_index in 0..list_am.count() {
             if !list_am.get_element(am_index) {
@@ -190,15 +259,16 @@ impl CalcCalculate {
             let value_expr = list_am.value_expr();
             let event_sequence = list_am.event_sequence();
             let list_parameter = list_am.list_parameter();
-            self.interest.set(dec!(0.0));
-            self.sl_interest.set(dec!(0.0));
-            if self.last_interest_date.get() == 0 {
-                self.last_interest_date.set(event_date);
+            self.set_last_interest(dec!(0.0));
+            self.set_last_sl_interest(dec!(0.0));
+            if *self.last_interest_date.lock().unwrap() == NaiveDate::MIN {
+                *self.last_interest_date.lock().unwrap() = event_date;
             }
 
             let orig_balance = balance;
             let orig_acc_balance = acc_balance;
-            let orig_interest_date = self.last_interest_date.get();
+            let orig_accrued_interest = accrued_interest;
+            let orig_interest_date = *self.last_interest_date.lock().unwrap();
 
             let mut stat_name = String::from("");
             let mut prin_type = crate::PrincipalType::Increase;
@@ -207,6 +277,8 @@ impl CalcCalculate {
             let mut prin_balance_statistics: bool = false;
             let mut prin_auxilary: bool = false;
             let mut prin_aux_passive: bool = false;
+            let mut prin_payment_cap: Option<Decimal> = None;
+            let mut prin_neg_am_cap: Option<Decimal> = None;
             let mut cv_passive: bool = false;
             let mut cv_present: bool = false;
             let mut stat_final: bool = false;
@@ -235,10 +307,12 @@ impl CalcCalculate {
                     prin_balance_statistics = ext.pc_balance_statistics();
                     prin_auxilary = ext.pc_auxiliary();
                     prin_aux_passive = ext.pc_aux_passive();
+                    prin_payment_cap = ext.pc_payment_cap();
+                    prin_neg_am_cap = ext.pc_neg_am_cap();
                 }
             }
 
-            if event_date > self.last_interest_date.get() {
+            if event_date > *self.last_interest_date.lock().unwrap() {
                 if !(rule_of_78_balance
                     && int_day_count_basis == crate::DayCountType::RuleOf78
                     && elem_type == crate::ExtensionType::PrincipalChange
@@ -256,36 +330,37 @@ impl CalcCalculate {
                         balance,
                         sl_balance,
                         prin_eom,
+                        &int_calendar,
                     );
                 } else {
                     // Rule of 78 interest allocation
                     let alloc =
                         (total_prin_stats - elem_balance_result.prin_total()) / sum_of_the_digits;
-                    self.interest.set(total_interest * dec!(alloc));
-                    self.sl_interest.set(dec!(0.0)); // The straight-line balance is not known when using the Rule of 78
-                    self.last_interest_date.set(event_date);
+                    self.set_last_interest(total_interest * dec!(alloc));
+                    self.set_last_sl_interest(dec!(0.0)); // The straight-line balance is not known when using the Rule of 78
+                    *self.last_interest_date.lock().unwrap() = event_date;
                 }
             }
             if int_round_balance != crate::RoundType::None {
                 if round_decimal_digits < dec!(0.0) {
-                    self.interest.set(CoreUtility::round(
-                        self.interest.get(),
-                        self.decimal_digits.get(),
+                    self.set_last_interest(CoreUtility::round(
+                        self.last_interest(),
+                        self.decimal_digits.load(Ordering::Relaxed),
                         int_round_balance,
                     ));
-                    self.sl_interest.set(CoreUtility::round(
-                        self.sl_interest.get(),
-                        self.decimal_digits.get(),
+                    self.set_last_sl_interest(CoreUtility::round(
+                        self.last_sl_interest(),
+                        self.decimal_digits.load(Ordering::Relaxed),
                         int_round_balance,
                     ));
                 } else if round_decimal_digits > dec!(0.0) && round_decimal_digits < dec!(1.0) {
-                    self.interest.set(CoreUtility::round_fraction(
-                        self.interest.get(),
+                    self.set_last_interest(CoreUtility::round_fraction(
+                        self.last_interest(),
                         round_decimal_digits,
                         int_round_balance,
                     ));
-                    self.sl_interest.set(CoreUtility::round_fraction(
-                        self.sl_interest.get(),
+                    self.set_last_sl_interest(CoreUtility::round_fraction(
+                        self.last_sl_interest(),
                         round_decimal_digits,
                         int_round_balance,
                     ));
@@ -293,13 +368,13 @@ impl CalcCalculate {
                     match round_decimal_digits.to_usize() {
                         None => {}
                         Some(o) => {
-                            self.interest.set(CoreUtility::round(
-                                self.interest.get(),
+                            self.set_last_interest(CoreUtility::round(
+                                self.last_interest(),
                                 o,
                                 int_round_balance,
                             ));
-                            self.sl_interest.set(CoreUtility::round(
-                                self.sl_interest.get(),
+                            self.set_last_sl_interest(CoreUtility::round(
+                                self.last_sl_interest(),
                                 o,
                                 int_round_balance,
                             ));
@@ -310,30 +385,26 @@ impl CalcCalculate {
 
             if !optimize && !value_expr.is_empty() {
                 // Perform late evaluation
-                self.expr_mut().init_expression(
+                calc_expression.init_expression(
                     self.list_descriptor_cashflow.as_ref(),
                     None,
                     list_parameter,
                     value_expr,
                 );
-                self.expr_mut().set_symbol_decimal("decValue", orig_value);
-                self.expr_mut()
-                    .set_symbol_decimal("decInterest", self.interest.get());
-                self.expr_mut()
-                    .set_symbol_decimal("decSLInterest", self.sl_interest.get());
-                self.expr_mut()
-                    .set_symbol_decimal("decAccBalance", acc_balance);
+                calc_expression.set_symbol_decimal("decValue", orig_value);
+                calc_expression.set_symbol_decimal("decInterest", self.last_interest());
+                calc_expression.set_symbol_decimal("decSLInterest", self.last_sl_interest());
+                calc_expression.set_symbol_decimal("decAccBalance", acc_balance);
                 // Include the interest accrued for this event
-                self.expr_mut()
-                    .set_symbol_decimal("decBalance", balance + self.interest.get());
-                self.expr_mut()
-                    .set_symbol_integer("intSequence", event_sequence);
+                calc_expression.set_symbol_decimal("decBalance", balance + self.last_interest());
+                calc_expression.set_symbol_integer("intSequence", event_sequence);
 
-                let result_symbol: ElemSymbol = match self
-                    .expr()
+                let result_symbol: ElemSymbol = match calc_expression
                     .evaluate(Option::from(&*list_am), Option::from(elem_balance_prev))
                 {
-                    Err(e) => {
+                    Err(mut e) => {
+                        e.set_event_index(list_am.list_event_index());
+                        e.set_column_name("Value-expr");
                         list_am.get_element(orig_list_index);
                         return Err(e);
                     }
@@ -357,38 +428,41 @@ impl CalcCalculate {
             }
 
             if int_method == crate::MethodType::SimpleInterest {
-                acc_balance += self.interest.get();
+                acc_balance += self.last_interest();
             } else if balance < dec!(0.0) {
                 // METHOD_ACTUARIAL
-                balance -= self.interest.get();
+                balance -= self.last_interest();
+                accrued_interest += self.last_interest();
             } else {
-                balance += self.interest.get();
+                balance += self.last_interest();
+                accrued_interest += self.last_interest();
             }
 
             match elem_type {
                 crate::ExtensionType::CurrentValue => {
-                    list_am.set_interest(self.interest.get()); // Report for passive or active
-                    list_am.set_sl_interest(self.sl_interest.get());
+                    list_am.set_interest(self.last_interest()); // Report for passive or active
+                    list_am.set_sl_interest(self.last_sl_interest());
                     list_am.set_acc_balance(acc_balance);
                     list_am.set_balance(balance);
                     if !cv_present && cv_passive {
                         // Passive event
-                        self.last_interest_date.set(orig_interest_date);
+                        *self.last_interest_date.lock().unwrap() = orig_interest_date;
                         balance = orig_balance;
                         acc_balance = orig_acc_balance;
+                        accrued_interest = orig_accrued_interest;
                     } else {
-                        elem_balance_result.incr_interest_total(self.interest.get());
-                        elem_balance_result.incr_sl_interest_total(self.sl_interest.get());
+                        elem_balance_result.incr_interest_total(self.last_interest());
+                        elem_balance_result.incr_sl_interest_total(self.last_sl_interest());
                         if cv_present_seen {
-                            elem_balance_result.incr_interest_present(self.interest.get());
-                            elem_balance_result.incr_sl_interest_present(self.sl_interest.get());
+                            elem_balance_result.incr_interest_present(self.last_interest());
+                            elem_balance_result.incr_sl_interest_present(self.last_sl_interest());
                         }
                         for statistic_index in 0..list_statistic_helper.count() {
                             if !list_statistic_helper.get_element(statistic_index) {
                                 break;
                             }
-                            list_statistic_helper.incr_interest(self.interest.get());
-                            list_statistic_helper.incr_sl_interest(self.sl_interest.get());
+                            list_statistic_helper.incr_interest(self.last_interest());
+                            list_statistic_helper.incr_sl_interest(self.last_sl_interest());
                         }
                         if !cv_present_seen && cv_present {
                             elem_balance_result.set_cur_first_pv_index(am_index);
@@ -397,6 +471,9 @@ impl CalcCalculate {
                     }
                 }
                 crate::ExtensionType::InterestChange => {
+                    if capped_payment.is_some() && list_am.value() != int_rate {
+                        rate_reset = true; // Recalculate the capped payment
+                    }
                     int_rate = list_am.value();
                     frequency = list_am.frequency();
                     int_method = list_am.elem_extension().ic_method();
@@ -405,6 +482,12 @@ impl CalcCalculate {
                     int_effective_frequency = list_am.elem_extension().ic_effective_frequency();
                     int_round_balance = list_am.elem_extension().ic_round_balance();
                     round_decimal_digits = list_am.elem_extension().ic_round_decimal_digits();
+                    if let Some(o) = calc_mgr
+                        .list_calendar()
+                        .resolve(list_am.elem_extension().extension_calendar())
+                    {
+                        int_calendar = o;
+                    }
                     if int_method == crate::MethodType::Actuarial {
                         // Fold-in any residual accrued interest balance
                         balance += if balance < dec!(0.0) {
@@ -418,22 +501,22 @@ impl CalcCalculate {
                     if !int_rule_of_78_seen {
                         int_rule_of_78_seen = int_day_count_basis == crate::DayCountType::RuleOf78;
                     }
-                    list_am.set_interest(self.interest.get());
-                    list_am.set_sl_interest(self.sl_interest.get());
+                    list_am.set_interest(self.last_interest());
+                    list_am.set_sl_interest(self.last_sl_interest());
                     list_am.set_acc_balance(acc_balance);
                     list_am.set_balance(balance);
-                    elem_balance_result.incr_interest_total(self.interest.get());
-                    elem_balance_result.incr_sl_interest_total(self.sl_interest.get());
+                    elem_balance_result.incr_interest_total(self.last_interest());
+                    elem_balance_result.incr_sl_interest_total(self.last_sl_interest());
                     if cv_present_seen {
-                        elem_balance_result.incr_interest_present(self.interest.get());
-                        elem_balance_result.incr_sl_interest_present(self.sl_interest.get());
+                        elem_balance_result.incr_interest_present(self.last_interest());
+                        elem_balance_result.incr_sl_interest_present(self.last_sl_interest());
                     }
                     for statistic_index in 0..list_statistic_helper.count() {
                         if !list_statistic_helper.get_element(statistic_index) {
                             break;
                         }
-                        list_statistic_helper.incr_interest(self.interest.get());
-                        list_statistic_helper.incr_sl_interest(self.sl_interest.get());
+                        list_statistic_helper.incr_interest(self.last_interest());
+                        list_statistic_helper.incr_sl_interest(self.last_sl_interest());
                     }
                     if elem_balance_result.int_first_index() == usize::MAX {
                         elem_balance_result.set_int_first_index(am_index);
@@ -469,12 +552,86 @@ impl CalcCalculate {
                         );
                     }
                     if !stat_final_no_element {
-                        self.last_interest_date.set(orig_interest_date); // Passive event
+                        *self.last_interest_date.lock().unwrap() = orig_interest_date; // Passive event
                         balance = orig_balance;
                         acc_balance = orig_acc_balance;
+                        accrued_interest = orig_accrued_interest;
                     }
                 }
                 _ => {
+                    let is_payment = int_method == crate::MethodType::Actuarial
+                        && !prin_auxilary
+                        && ((prin_type == crate::PrincipalType::Decrease && balance >= dec!(0.0))
+                            || (prin_type == crate::PrincipalType::Increase
+                                && balance < dec!(0.0)));
+                    if is_payment && modelled && list_am.value_expr().is_empty() {
+                        // Start from the scheduled (unscaled) payment
+                        value = orig_value;
+                    }
+                    if is_payment && (prin_payment_cap.is_some() || prin_neg_am_cap.is_some()) {
+                        if capped_event_index != list_am.list_event_index() {
+                            capped_event_index = list_am.list_event_index();
+                            capped_payment = None;
+                            rate_reset = false;
+                        }
+                        if let Some(payment) = capped_payment {
+                            value = payment;
+                            if rate_reset {
+                                let full_payment = self.amortizing_payment(
+                                    list_am,
+                                    am_index,
+                                    orig_balance.abs(),
+                                    int_rate,
+                                    int_days_in_year,
+                                );
+                                value = match prin_payment_cap {
+                                    None => full_payment,
+                                    Some(o) => {
+                                        let limit = payment * o / dec!(100.0);
+                                        full_payment.max(payment - limit).min(payment + limit)
+                                    }
+                                };
+                            }
+                        }
+                        rate_reset = false;
+                        if let Some(o) = prin_neg_am_cap {
+                            if orig_principal > dec!(0.0)
+                                && balance.abs() - value > orig_principal * o / dec!(100.0)
+                            {
+                                // Recast to the fully amortizing payment
+                                value = self.amortizing_payment(
+                                    list_am,
+                                    am_index,
+                                    orig_balance.abs(),
+                                    int_rate,
+                                    int_days_in_year,
+                                );
+                            }
+                        }
+                        value = CoreUtility::round(
+                            value,
+                            self.decimal_digits.load(Ordering::Relaxed),
+                            crate::RoundType::Bankers,
+                        );
+                        capped_payment = Some(value);
+                        list_am.set_value(value);
+                    }
+                    if is_payment && modelled {
+                        // Scale the payment to the surviving balance
+                        value = CoreUtility::round(
+                            value * survival_factor,
+                            self.decimal_digits.load(Ordering::Relaxed),
+                            crate::RoundType::Bankers,
+                        );
+                        list_am.set_value(value);
+                    }
+                    let mut deferred_interest = dec!(0.0);
+                    if is_payment {
+                        if value < accrued_interest {
+                            deferred_interest = accrued_interest - value;
+                        }
+                        accrued_interest = dec!(0.0);
+                    }
                     let mut prin_value = value;
                     if prin_type == crate::PrincipalType::Positive
                         || prin_type == crate::PrincipalType::Negative
@@ -484,9 +641,10 @@ impl CalcCalculate {
                         } else {
                             balance = -value;
                         }
-                        self.interest.set(dec!(0.0));
-                        self.sl_interest.set(dec!(0.0));
+                        self.set_last_interest(dec!(0.0));
+                        self.set_last_sl_interest(dec!(0.0));
                         acc_balance = dec!(0.0);
+                        accrued_interest = dec!(0.0);
                         if !(prin_auxilary && prin_aux_passive && !include_aux_passive) {
                             for statistic_index in 0..list_statistic_helper.count() {
                                 if !list_statistic_helper.get_element(statistic_index) {
@@ -530,7 +688,7 @@ impl CalcCalculate {
                         let bresult = balance < dec!(0.0);
                         balance += prin_value;
                         if int_method == crate::MethodType::Actuarial && bresult {
-                            prin_value -= self.interest.get();
+                            prin_value -= self.last_interest();
                             if prin_value < dec!(0.0) {
                                 prin_value = dec!(0.0);
                             }
@@ -538,10 +696,75 @@ impl CalcCalculate {
                     } else {
                         balance -= prin_value;
                     }
-                    list_am.set_interest(self.interest.get()); // Report for normal or auxiliary
-                    list_am.set_sl_interest(self.sl_interest.get());
+                    let mut scheduled_principal = dec!(0.0);
+                    let mut prepaid_principal = dec!(0.0);
+                    let mut defaulted_principal = dec!(0.0);
+                    let mut recovery = dec!(0.0);
+                    let mut loss = dec!(0.0);
+                    if is_payment && modelled {
+                        prepayment_age += 1;
+                        let periods_in_year =
+                            CoreUtility::intervals_in_year(list_am.frequency(), int_days_in_year)
+                                / list_am.intervals().max(1);
+                        scheduled_principal = (orig_balance.abs() - balance.abs()).max(dec!(0.0));
+                        if let Some(o) = default_scenario.as_ref() {
+                            let mdr = o.mdr(periods_in_year);
+                            defaulted_principal = CoreUtility::round(
+                                balance.abs() * mdr,
+                                self.decimal_digits.load(Ordering::Relaxed),
+                                crate::RoundType::Bankers,
+                            );
+                            if balance < dec!(0.0) {
+                                balance += defaulted_principal;
+                            } else {
+                                balance -= defaulted_principal;
+                            }
+                            survival_factor *= dec!(1.0) - mdr;
+                            delinquency_pipeline.push_back(defaulted_principal);
+                            if delinquency_pipeline.len() > o.recovery_lag() {
+                                // Liquidate the oldest defaults
+                                let liquidated =
+                                    delinquency_pipeline.pop_front().unwrap_or(dec!(0.0));
+                                recovery = CoreUtility::round(
+                                    o.recovery(liquidated),
+                                    self.decimal_digits.load(Ordering::Relaxed),
+                                    crate::RoundType::Bankers,
+                                );
+                                loss = liquidated - recovery;
+                            }
+                            list_am.set_delinquent_balance(delinquency_pipeline.iter().sum());
+                        }
+                        if let Some(o) = prepayment.as_ref() {
+                            let smm = o.smm(prepayment_age, periods_in_year);
+                            prepaid_principal = CoreUtility::round(
+                                balance.abs() * smm,
+                                self.decimal_digits.load(Ordering::Relaxed),
+                                crate::RoundType::Bankers,
+                            );
+                            if balance < dec!(0.0) {
+                                balance += prepaid_principal;
+                            } else {
+                                balance -= prepaid_principal;
+                            }
+                            survival_factor *= dec!(1.0) - smm;
+                        }
+                        list_am.set_surviving_balance(balance);
+                        elem_balance_result.incr_scheduled_principal_total(scheduled_principal);
+                        elem_balance_result.incr_prepaid_principal_total(prepaid_principal);
+                        elem_balance_result.incr_defaulted_principal_total(defaulted_principal);
+                        elem_balance_result.incr_recovery_total(recovery);
+                        elem_balance_result.incr_loss_total(loss);
+                    }
+                    list_am.set_scheduled_principal(scheduled_principal);
+                    list_am.set_prepaid_principal(prepaid_principal);
+                    list_am.set_defaulted_principal(defaulted_principal);
+                    list_am.set_recovery(recovery);
+                    list_am.set_loss(loss);
+                    list_am.set_interest(self.last_interest()); // Report for normal or auxiliary
+                    list_am.set_sl_interest(self.last_sl_interest());
                     list_am.set_acc_balance(acc_balance);
                     list_am.set_balance(balance);
+                    list_am.set_deferred_interest(deferred_interest);
                     let mut is_passive: bool = false;
                     if prin_auxilary {
                         if prin_aux_passive {
@@ -554,9 +777,10 @@ impl CalcCalculate {
                             }
                             if !include_aux_passive {
                                 // Passive event
-                                self.last_interest_date.set(orig_interest_date);
+                                *self.last_interest_date.lock().unwrap() = orig_interest_date;
                                 balance = orig_balance;
                                 acc_balance = orig_acc_balance;
+                                accrued_interest = orig_accrued_interest;
                                 is_passive = true;
                             }
                         } else if prin_type == crate::PrincipalType::Negative
@@ -595,8 +819,8 @@ impl CalcCalculate {
                             } else {
                                 list_statistic_helper.incr_principal_increase(value);
                             }
-                            list_statistic_helper.incr_interest(self.interest.get());
-                            list_statistic_helper.incr_sl_interest(self.sl_interest.get());
+                            list_statistic_helper.incr_interest(self.last_interest());
+                            list_statistic_helper.incr_sl_interest(self.last_sl_interest());
                             list_statistic_helper.incr_value_to_interest(value - prin_value);
                             list_statistic_helper.incr_value_to_principal(
                                 if prin_type == crate::PrincipalType::Negative
@@ -608,11 +832,11 @@ impl CalcCalculate {
                                 },
                             );
                         }
-                        elem_balance_result.incr_interest_total(self.interest.get());
-                        elem_balance_result.incr_sl_interest_total(self.sl_interest.get());
+                        elem_balance_result.incr_interest_total(self.last_interest());
+                        elem_balance_result.incr_sl_interest_total(self.last_sl_interest());
                         if cv_present_seen {
-                            elem_balance_result.incr_interest_present(self.interest.get());
-                            elem_balance_result.incr_sl_interest_present(self.sl_interest.get());
+                            elem_balance_result.incr_interest_present(self.last_interest());
+                            elem_balance_result.incr_sl_interest_present(self.last_sl_interest());
                         }
                         if prin_balance_statistics {
                             elem_balance_result.incr_prin_total(1);
@@ -633,6 +857,9 @@ impl CalcCalculate {
                         if acc_balance > dec!(0.0) {
                             elem_balance_result.set_acc_balance_seen(true);
                         }
+                        if orig_principal == dec!(0.0) {
+                            orig_principal = balance.abs();
+                        }
                         if elem_balance_result.prin_first_index() == usize::MAX {
                             elem_balance_result.set_prin_first_index(am_index);
                             if prin_type == crate::PrincipalType::Negative
@@ -656,6 +883,10 @@ impl CalcCalculate {
         elem_balance_result.set_balance(balance);
         elem_balance_result.set_balance_date(bal_date);
         elem_balance_result.set_rule_of_78_seen(int_rule_of_78_seen);
+        if modelled {
+            elem_balance_result.set_surviving_balance(balance);
+            elem_balance_result.set_delinquent_balance(delinquency_pipeline.iter().sum());
+        }
         if !cv_present_seen {
             elem_balance_result.incr_interest_present(elem_balance_result.interest_total());
             elem_balance_result.incr_sl_interest_present(elem_balance_result.sl_interest_total());
@@ -718,10 +949,12 @@ impl CalcCalculate {
     ///
     /// # Arguments
     ///
+    /// * `calc_mgr` - Calculation manager.
     /// * `list_event` - The event list to calculate.
     /// * `list_am` - The amortization list to balance.
     /// * `list_statistic_helper` - The list of active statistic elements.
     /// * `new_value` - The desired remaining balance or dec_zero.
+    /// * `solver_settings` - The solver settings.
     ///
     /// # Return
     ///
@@ -729,24 +962,26 @@ impl CalcCalculate {
 
     pub fn calculate_interest(
         &self,
+        calc_mgr: &CalcManager,
         list_event: &ListEvent,
         list_am: &mut ListAmortization,
         list_statistic_helper: &mut ListStatisticHelper,
         new_value: Decimal,
-    ) -> Result<ElemBalanceResult, crate::ErrorType> {
+        solver_settings: &ElemSolverSettings,
+    ) -> Result<ElemBalanceResult, ElemError> {
         let event_index = list_event.index();
         if event_index == usize::MAX {
-            return Err(crate::ErrorType::Index);
+            return Err(ElemError::new(crate::ErrorType::Index));
         }
 
         if list_event.elem_type() != crate::ExtensionType::InterestChange {
-            return Err(crate::ErrorType::Index);
+            return Err(ElemError::new(crate::ErrorType::Index));
         }
 
         let value_expr_am = !list_event.value_expr().is_empty() && !list_event.value_expr_balance();
-        let mut result: Result<(), crate::ErrorType>;
+        let mut result: Result<(), ElemError>;
         if !value_expr_am {
-            result = self.expand_with_list(list_event, list_am, true);
+            result = self.expand_with_list(calc_mgr, list_event, list_am, true);
             match result {
                 Err(e) => {
                     return Err(e);
@@ -760,19 +995,28 @@ impl CalcCalculate {
         let dec_two = dec!(2.0);
         let dec_ten = dec!(10.0);
         let max_calc_principal = dec!(crate::MAX_CALC_PRINCIPAL);
-        let max_calc_interest = dec!(crate::MAX_CALC_INTEREST);
+        let max_calc_interest = solver_settings
+            .upper_bound()
+            .unwrap_or(dec!(crate::MAX_CALC_INTEREST));
+        let min_calc_interest = solver_settings
+            .lower_bound()
+            .unwrap_or(dec_zero)
+            .max(dec_zero);
+        let max_iterations = solver_settings
+            .max_iterations()
+            .unwrap_or(crate::MAXIMUM_ITERATIONS_CALCULATE_INTEREST);
 
         let smallest_fraction =
             dec!(1.0) / CoreUtility::decimal_pow(dec_ten, crate::MAXIMUM_DISPLAY_DECIMAL_DIGITS);
         let decrement_fraction = smallest_fraction / dec_ten;
         let mut last_interest = dec!(-1.0);
-        let mut calc_interest = dec_ten;
+        let mut calc_interest = solver_settings.initial_guess().unwrap_or(dec_ten);
         let mut iterations: usize = 1;
         let mut adjust_down: bool;
-        while iterations <= crate::MAXIMUM_ITERATIONS_CALCULATE_INTEREST {
+        while iterations <= max_iterations {
             if value_expr_am {
                 list_event.set_value_result(calc_interest);
-                result = self.expand_with_list(list_event, list_am, true);
+                result = self.expand_with_list(calc_mgr, list_event, list_am, true);
                 match result {
                     Err(e) => {
                         return Err(e);
@@ -784,6 +1028,7 @@ impl CalcCalculate {
             }
 
             let result_balance = self.balance_cashflow(
+                calc_mgr,
                 list_am,
                 list_statistic_helper,
                 &elem_balance_result,
@@ -806,11 +1051,11 @@ impl CalcCalculate {
                     } else {
                         elem_balance_result.acc_balance()
                     },
-                self.decimal_digits.get(),
+                self.decimal_digits.load(Ordering::Relaxed),
                 crate::RoundType::Bankers,
             );
             if balance.abs() >= max_calc_principal {
-                calc_interest = dec!(2005.0);
+                calc_interest = max_calc_interest + dec!(5.0);
                 break;
             }
 
@@ -820,8 +1065,10 @@ impl CalcCalculate {
                 adjust_down = balance > new_value;
             }
 
-            if balance == new_value
-                || calc_interest > max_calc_interest
+            if solver_settings.converged(
+                balance - new_value,
+                CalcCalculate::principal_total(&elem_balance_result),
+            ) || calc_interest > max_calc_interest
                 || ((calc_interest - last_interest).abs() <= smallest_fraction
                     && if elem_balance_result.polarity() > 0 {
                         balance >= new_value
@@ -840,16 +1087,16 @@ impl CalcCalculate {
                     } else {
                         calc_interest -= (last_interest - calc_interest) * dec_two;
                     }
-                    if calc_interest < dec_zero {
-                        calc_interest = dec_zero;
+                    if calc_interest < min_calc_interest {
+                        calc_interest = min_calc_interest;
                     }
                 } else {
                     if (calc_interest - last_interest).abs() <= decrement_fraction {
                         calc_interest -= decrement_fraction;
                         orig_interest = calc_interest; // Possibly terminate loop
-                        if calc_interest < dec_zero {
-                            orig_interest = dec_zero;
-                            calc_interest = dec_zero;
+                        if calc_interest < min_calc_interest {
+                            orig_interest = min_calc_interest;
+                            calc_interest = min_calc_interest;
                         }
                     } else if calc_interest > last_interest {
                         calc_interest -= (calc_interest - last_interest) / dec_two;
@@ -895,7 +1142,7 @@ impl CalcCalculate {
             );
             if value_expr_am {
                 list_event.set_value_result(calc_interest);
-                result = self.expand_with_list(list_event, list_am, true);
+                result = self.expand_with_list(calc_mgr, list_event, list_am, true);
                 match result {
                     Err(e) => {
                         return Err(e);
@@ -907,6 +1154,7 @@ impl CalcCalculate {
             }
 
             let result_balance = self.balance_cashflow(
+                calc_mgr,
                 list_am,
                 list_statistic_helper,
                 &elem_balance_result,
@@ -929,7 +1177,7 @@ impl CalcCalculate {
                     } else {
                         elem_balance_result.acc_balance()
                     }),
-                self.decimal_digits.get(),
+                self.decimal_digits.load(Ordering::Relaxed),
                 crate::RoundType::Bankers,
             );
 
@@ -953,12 +1201,13 @@ impl CalcCalculate {
         }
 
         if calc_interest == dec_zero {
-            return Err(crate::ErrorType::CalcInterest);
+            return Err(ElemError::new(crate::ErrorType::CalcInterest));
         }
 
         list_event.set_value(calc_interest);
 
         let result_balance = self.balance_cashflow(
+            calc_mgr,
             list_am,
             list_statistic_helper,
             &elem_balance_result,
@@ -976,6 +1225,8 @@ impl CalcCalculate {
         }
 
         elem_balance_result.set_result_decimal(calc_interest);
+        elem_balance_result.set_iterations(iterations.min(max_iterations));
+        elem_balance_result.set_residual(self.balance_residual(&elem_balance_result, new_value));
 
         Ok(elem_balance_result)
     }
@@ -988,10 +1239,12 @@ impl CalcCalculate {
     ///
     /// # Arguments
     ///
+    /// * `calc_mgr` - Calculation manager.
     /// * `list_event` - The event list to calculate.
     /// * `list_am` - The amortization list to balance.
     /// * `list_statistic_helper` - The list of active statistic elements.
     /// * `new_value` - The desired remaining balance or dec_zero.
+    /// * `solver_settings` - The solver settings.
     ///
     /// # Return
     ///
@@ -999,13 +1252,15 @@ impl CalcCalculate {
 
     pub fn calculate_periods(
         &self,
+        calc_mgr: &CalcManager,
         list_event: &ListEvent,
         list_am: &mut ListAmortization,
         list_statistic_helper: &mut ListStatisticHelper,
         new_value: Decimal,
-    ) -> Result<ElemBalanceResult, crate::ErrorType> {
+        solver_settings: &ElemSolverSettings,
+    ) -> Result<ElemBalanceResult, ElemError> {
         if list_event.elem_type() == crate::ExtensionType::StatisticValue {
-            return Err(crate::ErrorType::Index);
+            return Err(ElemError::new(crate::ErrorType::Index));
         }
 
         let mut elem_balance_result = ElemBalanceResult::new();
@@ -1026,17 +1281,31 @@ impl CalcCalculate {
         let intervals_in_year =
             CoreUtility::intervals_in_year(list_event.frequency(), crate::DEFAULT_DAYS_IN_YEAR);
         let intervals = list_event.intervals();
-        let mut periods: i32 = (intervals_in_year * intervals * 10) as i32;
+        let max_periods: i32 = match solver_settings.upper_bound() {
+            None => (intervals_in_year * intervals * 100) as i32,
+            Some(o) => o.to_i32().unwrap_or(i32::MAX),
+        };
+        let min_periods: i32 = match solver_settings.lower_bound() {
+            None => 1,
+            Some(o) => o.to_i32().unwrap_or(1).max(1),
+        };
+        let max_iterations = solver_settings
+            .max_iterations()
+            .unwrap_or(crate::MAXIMUM_ITERATIONS_CALCULATE_PERIODS);
+        let mut periods: i32 = match solver_settings.initial_guess() {
+            None => (intervals_in_year * intervals * 10) as i32,
+            Some(o) => o.to_i32().unwrap_or(min_periods).max(min_periods),
+        };
         let mut last_periods: i32 = -1;
         let mut iterations: usize = 1;
         let mut last_adjust_down = false;
 
         let dec_zero = dec!(0.0);
 
-        while iterations <= crate::MAXIMUM_ITERATIONS_CALCULATE_PERIODS {
+        while iterations <= max_iterations {
             list_event.set_periods_result(periods as usize);
 
-            let result = self.expand_with_list(list_event, list_am, true);
+            let result = self.expand_with_list(calc_mgr, list_event, list_am, true);
             match result {
                 Err(e) => {
                     return Err(e);
@@ -1045,6 +1314,7 @@ impl CalcCalculate {
             }
 
             let result_balance = self.balance_cashflow(
+                calc_mgr,
                 list_am,
                 list_statistic_helper,
                 &elem_balance_result,
@@ -1067,7 +1337,7 @@ impl CalcCalculate {
                     } else {
                         elem_balance_result.acc_balance()
                     }),
-                self.decimal_digits.get(),
+                self.decimal_digits.load(Ordering::Relaxed),
                 crate::RoundType::Bankers,
             );
 
@@ -1077,8 +1347,10 @@ impl CalcCalculate {
                 balance > new_value
             };
 
-            if balance == new_value
-                || periods > (intervals_in_year * intervals * 100) as i32
+            if solver_settings.converged(
+                balance - new_value,
+                CalcCalculate::principal_total(&elem_balance_result),
+            ) || periods > max_periods
                 || ((periods - last_periods).abs() <= 1
                     && adjust_down != last_adjust_down
                     && if elem_balance_result.polarity() > 0 {
@@ -1099,14 +1371,14 @@ impl CalcCalculate {
                     } else {
                         periods -= (last_periods - periods) * 2;
                     }
-                    if periods <= 0 {
-                        periods = 1;
+                    if periods < min_periods {
+                        periods = min_periods;
                     }
                 } else {
                     if (periods - last_periods).abs() <= 1 {
                         periods -= 1;
-                        if periods < 1 {
-                            periods = 1;
+                        if periods < min_periods {
+                            periods = min_periods;
                             adjust_down = false;
                         }
                     } else if periods > last_periods {
@@ -1140,17 +1412,18 @@ impl CalcCalculate {
             last_adjust_down = adjust_down;
         }
 
-        if periods > (intervals_in_year * intervals * 100) as i32 {
+        if periods > max_periods {
             periods = 0;
         }
 
         if periods <= 0 {
-            return Err(crate::ErrorType::CalcPeriods);
+            return Err(ElemError::new(crate::ErrorType::CalcPeriods));
         }
 
         list_event.set_periods(periods as usize);
 
         let result_balance = self.balance_cashflow(
+            calc_mgr,
             list_am,
             list_statistic_helper,
             &elem_balance_result,
@@ -1168,6 +1441,8 @@ impl CalcCalculate {
         }
 
         elem_balance_result.set_result_integer(periods);
+        elem_balance_result.set_iterations(iterations.min(max_iterations));
+        elem_balance_result.set_residual(self.balance_residual(&elem_balance_result, new_value));
 
         Ok(elem_balance_result)
     }
@@ -1180,10 +1455,12 @@ impl CalcCalculate {
     ///
     /// # Arguments
     ///
+    /// * `calc_mgr` - Calculation manager.
     /// * `list_event` - The event list to calculate.
     /// * `list_am` - The amortization list to balance.
     /// * `list_statistic_helper` - The list of active statistic elements.
     /// * `new_value` - The desired remaining balance or dec_zero.
+    /// * `solver_settings` - The solver settings.
     ///
     /// # Return
     ///
@@ -1191,11 +1468,13 @@ impl CalcCalculate {
 
     pub fn calculate_principal(
         &self,
+        calc_mgr: &CalcManager,
         list_event: &ListEvent,
         list_am: &mut ListAmortization,
         list_statistic_helper: &mut ListStatisticHelper,
         new_value: Decimal,
-    ) -> Result<ElemBalanceResult, crate::ErrorType> {
+        solver_settings: &ElemSolverSettings,
+    ) -> Result<ElemBalanceResult, ElemError> {
         let mut adjust_negative = false;
         if list_event.elem_type() == crate::ExtensionType::PrincipalChange {
             let pc_type = list_event.elem_extension().pc_type();
@@ -1205,7 +1484,7 @@ impl CalcCalculate {
 
         let value_expr_am = !list_event.value_expr().is_empty() && !list_event.value_expr_balance();
         if !value_expr_am {
-            let result = self.expand_with_list(list_event, list_am, true);
+            let result = self.expand_with_list(calc_mgr, list_event, list_am, true);
             match result {
                 Err(e) => {
                     return Err(e);
@@ -1219,22 +1498,31 @@ impl CalcCalculate {
         let dec_zero = dec!(0.0);
         let dec_two = dec!(2.0);
         let dec_ten = dec!(10.0);
-        let max_calc_principal = dec!(crate::MAX_CALC_PRINCIPAL);
-
-        let decrement_fraction =
-            dec!(1.0) / CoreUtility::decimal_pow(dec_ten, self.decimal_digits.get() + 1);
+        let max_calc_principal = solver_settings
+            .upper_bound()
+            .unwrap_or(dec!(crate::MAX_CALC_PRINCIPAL));
+        let min_calc_principal = solver_settings
+            .lower_bound()
+            .unwrap_or(dec_zero)
+            .max(dec_zero);
+        let max_iterations = solver_settings
+            .max_iterations()
+            .unwrap_or(crate::MAXIMUM_ITERATIONS_CALCULATE_PRINCIPAL);
+
+        let decrement_fraction = dec!(1.0)
+            / CoreUtility::decimal_pow(dec_ten, self.decimal_digits.load(Ordering::Relaxed) + 1);
         let mut last_principal = dec!(-1.0);
-        let mut principal = dec!(20000.0); // Starting principal
+        let mut principal = solver_settings.initial_guess().unwrap_or(dec!(20000.0)); // Starting principal
         let mut orig_principal: Decimal;
         let mut iterations: usize = 1;
         let event_index = list_event.index();
         if event_index == usize::MAX
             || list_event.elem_type() != crate::ExtensionType::PrincipalChange
         {
-            return Err(crate::ErrorType::Index);
+            return Err(ElemError::new(crate::ErrorType::Index));
         }
 
-        let mut last_date: usize = 0;
+        let mut last_date = NaiveDate::MIN;
         if list_am.count() > 0 {
             let orig_index = list_am.index();
             list_am.get_element(list_am.count() - 1);
@@ -1251,10 +1539,10 @@ impl CalcCalculate {
             principal = dec_zero;
         }
 
-        while iterations <= crate::MAXIMUM_ITERATIONS_CALCULATE_PRINCIPAL {
+        while iterations <= max_iterations {
             if value_expr_am {
                 list_event.set_value_result(principal);
-                let result = self.expand_with_list(list_event, list_am, true);
+                let result = self.expand_with_list(calc_mgr, list_event, list_am, true);
                 match result {
                     Err(e) => {
                         return Err(e);
@@ -1266,6 +1554,7 @@ impl CalcCalculate {
             }
 
             let result_balance = self.balance_cashflow(
+                calc_mgr,
                 list_am,
                 list_statistic_helper,
                 &elem_balance_result,
@@ -1288,7 +1577,7 @@ impl CalcCalculate {
                     } else {
                         elem_balance_result.acc_balance()
                     }),
-                self.decimal_digits.get(),
+                self.decimal_digits.load(Ordering::Relaxed),
                 crate::RoundType::Bankers,
             );
 
@@ -1307,15 +1596,17 @@ impl CalcCalculate {
                 balance > new_value
             };
 
-            if balance == new_value
-                || principal > max_calc_principal
+            if solver_settings.converged(
+                balance - new_value,
+                CalcCalculate::principal_total(&elem_balance_result),
+            ) || principal > max_calc_principal
                 || (CoreUtility::round(
                     principal,
-                    self.decimal_digits.get(),
+                    self.decimal_digits.load(Ordering::Relaxed),
                     crate::RoundType::Bankers,
                 ) == CoreUtility::round(
                     last_principal,
-                    self.decimal_digits.get(),
+                    self.decimal_digits.load(Ordering::Relaxed),
                     crate::RoundType::Bankers,
                 ) && (if elem_balance_result.polarity() > 0 {
                     balance >= new_value
@@ -1335,16 +1626,16 @@ impl CalcCalculate {
                     } else {
                         principal -= (last_principal - principal) * dec_two;
                     }
-                    if principal < dec_zero {
-                        principal = dec_zero;
+                    if principal < min_calc_principal {
+                        principal = min_calc_principal;
                     }
                 } else {
                     if (principal - last_principal).abs() <= decrement_fraction {
                         principal -= decrement_fraction;
                         orig_principal = principal; // Possibly terminate loop
-                        if principal < dec_zero {
-                            orig_principal = dec_zero;
-                            principal = dec_zero;
+                        if principal < min_calc_principal {
+                            orig_principal = min_calc_principal;
+                            principal = min_calc_principal;
                         }
                     } else if principal > last_principal {
                         principal -= (principal - last_principal) / dec_two;
@@ -1385,12 +1676,12 @@ impl CalcCalculate {
             // Make sure that rounding does not affect the result in the wrong direction
             principal = CoreUtility::round(
                 principal,
-                self.decimal_digits.get(),
+                self.decimal_digits.load(Ordering::Relaxed),
                 crate::RoundType::Bankers,
             );
             if value_expr_am {
                 list_event.set_value_result(principal);
-                let result = self.expand_with_list(list_event, list_am, true);
+                let result = self.expand_with_list(calc_mgr, list_event, list_am, true);
                 match result {
                     Err(e) => {
                         return Err(e);
@@ -1402,6 +1693,7 @@ impl CalcCalculate {
             }
 
             let result_balance = self.balance_cashflow(
+                calc_mgr,
                 list_am,
                 list_statistic_helper,
                 &elem_balance_result,
@@ -1424,7 +1716,7 @@ impl CalcCalculate {
                     } else {
                         elem_balance_result.acc_balance()
                     }),
-                self.decimal_digits.get(),
+                self.decimal_digits.load(Ordering::Relaxed),
                 crate::RoundType::Bankers,
             );
 
@@ -1451,10 +1743,11 @@ impl CalcCalculate {
         list_event.set_value(principal);
 
         if principal == dec_zero {
-            return Err(crate::ErrorType::CalcPrincipal);
+            return Err(ElemError::new(crate::ErrorType::CalcPrincipal));
         }
 
         let result_balance = self.balance_cashflow(
+            calc_mgr,
             list_am,
             list_statistic_helper,
             &elem_balance_result,
@@ -1472,6 +1765,8 @@ impl CalcCalculate {
         }
 
         elem_balance_result.set_result_decimal(principal);
+        elem_balance_result.set_iterations(iterations.min(max_iterations));
+        elem_balance_result.set_residual(self.balance_residual(&elem_balance_result, new_value));
 
         Ok(elem_balance_result)
     }
@@ -1484,10 +1779,12 @@ impl CalcCalculate {
     ///
     /// # Arguments
     ///
+    /// * `calc_mgr` - Calculation manager.
     /// * `list_event` - The event list to calculate.
     /// * `list_am` - The amortization list to balance.
     /// * `list_statistic_helper` - The list of active statistic elements.
     /// * `new_value` - The desired remaining balance or dec_zero.
+    /// * `solver_settings` - The solver settings.
     ///
     /// # Return
     ///
@@ -1495,25 +1792,36 @@ impl CalcCalculate {
 
     pub fn calculate_yield(
         &self,
+        calc_mgr: &CalcManager,
         list_event: &ListEvent,
         list_am: &mut ListAmortization,
         list_statistic_helper: &mut ListStatisticHelper,
         new_value: Decimal,
-    ) -> Result<ElemBalanceResult, crate::ErrorType> {
+        solver_settings: &ElemSolverSettings,
+    ) -> Result<ElemBalanceResult, ElemError> {
         let dec_zero = dec!(0.0);
         let dec_two = dec!(2.0);
         let dec_ten = dec!(10.0);
         let max_calc_principal = dec!(crate::MAX_CALC_PRINCIPAL);
-        let max_calc_interest = dec!(crate::MAX_CALC_INTEREST);
+        let max_calc_interest = solver_settings
+            .upper_bound()
+            .unwrap_or(dec!(crate::MAX_CALC_INTEREST));
+        let min_calc_interest = solver_settings
+            .lower_bound()
+            .unwrap_or(dec_zero)
+            .max(dec_zero);
+        let max_iterations = solver_settings
+            .max_iterations()
+            .unwrap_or(crate::MAXIMUM_ITERATIONS_CALCULATE_YIELD);
 
         let smallest_fraction =
             dec!(1.0) / CoreUtility::decimal_pow(dec_ten, crate::MAXIMUM_DISPLAY_DECIMAL_DIGITS);
         let decrement_fraction = smallest_fraction / dec_ten;
         let mut last_interest = dec!(-1.0);
-        let mut calc_interest = dec_ten;
+        let mut calc_interest = solver_settings.initial_guess().unwrap_or(dec_ten);
         let mut iterations: usize = 1;
 
-        let result = self.expand_with_list(list_event, list_am, true);
+        let result = self.expand_with_list(calc_mgr, list_event, list_am, true);
         match result {
             Err(e) => {
                 return Err(e);
@@ -1524,6 +1832,7 @@ impl CalcCalculate {
         let mut elem_balance_result = ElemBalanceResult::new();
 
         let result_balance = self.balance_cashflow(
+            calc_mgr,
             list_am,
             list_statistic_helper,
             &elem_balance_result,
@@ -1540,10 +1849,11 @@ impl CalcCalculate {
             }
         }
 
-        while iterations <= crate::MAXIMUM_ITERATIONS_CALCULATE_YIELD {
+        while iterations <= max_iterations {
             list_am.set_all_interest_values(calc_interest);
 
             let result_balance = self.balance_cashflow(
+                calc_mgr,
                 list_am,
                 list_statistic_helper,
                 &elem_balance_result,
@@ -1566,11 +1876,11 @@ impl CalcCalculate {
                     } else {
                         elem_balance_result.acc_balance()
                     }),
-                self.decimal_digits.get(),
+                self.decimal_digits.load(Ordering::Relaxed),
                 crate::RoundType::Bankers,
             );
             if balance.abs() >= max_calc_principal {
-                calc_interest = dec!(2005.0);
+                calc_interest = max_calc_interest + dec!(5.0);
                 break;
             }
 
@@ -1580,8 +1890,10 @@ impl CalcCalculate {
                 balance > new_value
             };
 
-            if balance == new_value
-                || calc_interest > max_calc_interest
+            if solver_settings.converged(
+                balance - new_value,
+                CalcCalculate::principal_total(&elem_balance_result),
+            ) || calc_interest > max_calc_interest
                 || (calc_interest - last_interest).abs() <= smallest_fraction
                     && if elem_balance_result.polarity() > 0 {
                         balance >= new_value
@@ -1600,16 +1912,16 @@ impl CalcCalculate {
                     } else {
                         calc_interest -= (last_interest - calc_interest) * dec_two;
                     }
-                    if calc_interest < dec_zero {
-                        calc_interest = dec_zero;
+                    if calc_interest < min_calc_interest {
+                        calc_interest = min_calc_interest;
                     }
                 } else {
                     if (calc_interest - last_interest).abs() <= decrement_fraction {
                         calc_interest -= decrement_fraction;
                         orig_interest = calc_interest; // Possibly terminate loop
-                        if calc_interest < dec_zero {
-                            orig_interest = dec_zero;
-                            calc_interest = dec_zero;
+                        if calc_interest < min_calc_interest {
+                            orig_interest = min_calc_interest;
+                            calc_interest = min_calc_interest;
                         }
                     } else if calc_interest > last_interest {
                         calc_interest -= (calc_interest - last_interest) / dec_two;
@@ -1656,6 +1968,7 @@ impl CalcCalculate {
             list_am.set_all_interest_values(calc_interest);
 
             let result_balance = self.balance_cashflow(
+                calc_mgr,
                 list_am,
                 list_statistic_helper,
                 &elem_balance_result,
@@ -1678,7 +1991,7 @@ impl CalcCalculate {
                     } else {
                         elem_balance_result.acc_balance()
                     }),
-                self.decimal_digits.get(),
+                self.decimal_digits.load(Ordering::Relaxed),
                 crate::RoundType::Bankers,
             );
 
@@ -1703,20 +2016,69 @@ impl CalcCalculate {
         }
 
         if calc_interest == dec_zero {
-            return Err(crate::ErrorType::CalcInterest);
+            return Err(ElemError::new(crate::ErrorType::CalcInterest));
         }
 
         elem_balance_result.set_result_yield(calc_interest);
+        elem_balance_result.set_iterations(iterations.min(max_iterations));
+        elem_balance_result.set_residual(self.balance_residual(&elem_balance_result, new_value));
 
         Ok(elem_balance_result)
     }
 
+    /// Returns the remaining balance of a balance result, including
+    /// any accrued interest, less the desired remaining balance.
+    ///
+    /// # Arguments
+    ///
+    /// * `elem_balance_result` - The balance result.
+    /// * `new_value` - The desired remaining balance or dec_zero.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn balance_residual(
+        &self,
+        elem_balance_result: &ElemBalanceResult,
+        new_value: Decimal,
+    ) -> Decimal {
+        let balance = CoreUtility::round(
+            elem_balance_result.balance()
+                + (if elem_balance_result.balance() < dec!(0.0) {
+                    -elem_balance_result.acc_balance()
+                } else {
+                    elem_balance_result.acc_balance()
+                }),
+            self.decimal_digits.load(Ordering::Relaxed),
+            crate::RoundType::Bankers,
+        );
+
+        balance - new_value
+    }
+
+    /// Returns the total principal of a balance result that
+    /// relative solver tolerances are measured against.
+    ///
+    /// # Arguments
+    ///
+    /// * `elem_balance_result` - The balance result.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn principal_total(elem_balance_result: &ElemBalanceResult) -> Decimal {
+        elem_balance_result
+            .prin_decrease()
+            .max(elem_balance_result.prin_increase())
+    }
+
     /// Combine the amortization principal events from dec_two amortization element
     /// lists into a new amortization element list. All events except principal
     /// change and interest change events are discarded.
     ///
     /// # Arguments
     ///
+    /// * `calc_mgr` - Calculation manager.
     /// * `list_am1` - The first amortization element list to be combined.
     /// * `list_am2` - The second amortization element list to be combined.
     ///
@@ -1726,9 +2088,10 @@ impl CalcCalculate {
 
     pub fn combine_cashflow(
         &self,
+        calc_mgr: &CalcManager,
         list_am1: &ListAmortization,
         list_am2: &ListAmortization,
-    ) -> Result<ListAmortization, crate::ErrorType> {
+    ) -> Result<ListAmortization, ElemError> {
         let mut list_am_opt: Option<&ListAmortization>;
         let mut extension1_opt: Option<&ElemExtension> = None;
         let mut extension2_opt: Option<&ElemExtension> = None;
@@ -1740,12 +2103,12 @@ impl CalcCalculate {
         let mut action: usize = 3; // Initially fetch both amortization element lists
         let mut elem_type1 = crate::ExtensionType::PrincipalChange;
         let mut elem_type2 = crate::ExtensionType::PrincipalChange;
-        let mut event_date1: usize = 0;
-        let mut event_date2: usize = 0;
+        let mut event_date1 = NaiveDate::MIN;
+        let mut event_date2 = NaiveDate::MIN;
         let mut next_element1: bool = true;
         let mut next_element2: bool = true;
 
-        let updating_json = self.calc_mgr().updating_json();
+        let updating_json = calc_mgr.updating_json();
 
         let mut new_list_am = ListAmortization::new();
 
@@ -1791,12 +2154,12 @@ impl CalcCalculate {
             action = 0;
             if next_element1 && next_element2 {
                 let extension1: &ElemExtension = match extension1_opt {
-                    None => return Err(crate::ErrorType::Index),
+                    None => return Err(ElemError::new(crate::ErrorType::Index)),
                     Some(o) => o,
                 };
 
                 let extension2: &ElemExtension = match extension2_opt {
-                    None => return Err(crate::ErrorType::Index),
+                    None => return Err(ElemError::new(crate::ErrorType::Index)),
                     Some(o) => o,
                 };
 
@@ -1854,12 +2217,12 @@ impl CalcCalculate {
                 list_am_opt = Option::from(list_am2);
             }
             let list_am: &ListAmortization = match list_am_opt {
-                None => return Err(crate::ErrorType::Index),
+                None => return Err(ElemError::new(crate::ErrorType::Index)),
                 Some(o) => o,
             };
 
             let mut list_descriptor_copy: ListDescriptor = match list_am.list_descriptor() {
-                None => return Err(crate::ErrorType::Index),
+                None => return Err(ElemError::new(crate::ErrorType::Index)),
                 Some(o) => o.copy(false, updating_json),
             };
 
@@ -1872,7 +2235,7 @@ impl CalcCalculate {
                 }
                 match list_am2.list_descriptor() {
                     None => {
-                        return Err(crate::ErrorType::Index);
+                        return Err(ElemError::new(crate::ErrorType::Index));
                     }
                     Some(o) => {
                         o.copy_list_descriptor(&mut list_descriptor_copy, false, updating_json);
@@ -1883,7 +2246,7 @@ impl CalcCalculate {
             let new_elem_extension = list_am.elem_extension().copy();
 
             let list_parameter: Option<ListParameter> = match list_am.list_parameter() {
-                None => return Err(crate::ErrorType::Index),
+                None => return Err(ElemError::new(crate::ErrorType::Index)),
                 Some(o) => Option::from(o.copy(updating_json)),
             };
 
@@ -1906,6 +2269,16 @@ impl CalcCalculate {
                 list_parameter,
                 Option::from(list_descriptor_copy),
             );
+            new_list_am.set_scheduled_date(list_am.scheduled_date());
+            new_list_am.set_index_value(list_am.index_value());
+            new_list_am.set_deferred_interest(list_am.deferred_interest());
+            new_list_am.set_scheduled_principal(list_am.scheduled_principal());
+            new_list_am.set_prepaid_principal(list_am.prepaid_principal());
+            new_list_am.set_surviving_balance(list_am.surviving_balance());
+            new_list_am.set_defaulted_principal(list_am.defaulted_principal());
+            new_list_am.set_recovery(list_am.recovery());
+            new_list_am.set_loss(list_am.loss());
+            new_list_am.set_delinquent_balance(list_am.delinquent_balance());
         }
         list_am1.get_element(orig_list_index1);
         list_am2.get_element(orig_list_index2);
@@ -1943,7 +2316,7 @@ impl CalcCalculate {
         compress_descriptor: bool,
         omit_statistic_events: bool,
         updating_json: bool,
-    ) -> Result<ListAmortization, crate::ErrorType> {
+    ) -> Result<ListAmortization, ElemError> {
         if !include_rollups {
             // Just return a copy of the amortization list.
             return Ok(list_am.copy(updating_json));
@@ -1967,8 +2340,9 @@ impl CalcCalculate {
             }
 
             let new_event_type = list_am.event_type();
-            let mut new_date = list_am.event_date();
+            let mut new_date = list_am.scheduled_date();
             let orig_date = new_date;
+            let first_date = list_am.event_date();
             let new_sort = list_am.sort_order();
             let new_value = list_am.value();
             let new_value_expr = list_am.value_expr();
@@ -1978,6 +2352,14 @@ impl CalcCalculate {
             let mut new_sl_interest = list_am.sl_interest();
             let mut new_value_to_interest = list_am.value_to_interest();
             let mut new_value_to_principal = list_am.value_to_principal();
+            let mut new_deferred_interest = list_am.deferred_interest();
+            let mut new_scheduled_principal = list_am.scheduled_principal();
+            let mut new_prepaid_principal = list_am.prepaid_principal();
+            let mut new_surviving_balance = list_am.surviving_balance();
+            let mut new_defaulted_principal = list_am.defaulted_principal();
+            let mut new_recovery = list_am.recovery();
+            let mut new_loss = list_am.loss();
+            let mut new_delinquent_balance = list_am.delinquent_balance();
             let new_acc_balance = list_am.acc_balance();
             let new_balance = list_am.balance();
             let new_list_event_index = list_am.list_event_index();
@@ -1989,12 +2371,12 @@ impl CalcCalculate {
             let new_eom = new_elem_extension.extension_eom();
 
             let new_list_parameter: ListParameter = match list_am.list_parameter() {
-                None => return Err(crate::ErrorType::Index),
+                None => return Err(ElemError::new(crate::ErrorType::Index)),
                 Some(o) => o.copy(updating_json),
             };
 
             let mut new_list_descriptor: ListDescriptor = match list_am.list_descriptor() {
-                None => return Err(crate::ErrorType::Index),
+                None => return Err(ElemError::new(crate::ErrorType::Index)),
                 Some(o) => o.copy(false, updating_json),
             };
 
@@ -2003,7 +2385,7 @@ impl CalcCalculate {
                     break;
                 }
                 let elem_type = list_am.elem_type();
-                let event_date = list_am.event_date();
+                let event_date = list_am.scheduled_date();
                 let value = list_am.value();
                 let value_expr = list_am.value_expr();
                 let intervals = list_am.intervals();
@@ -2031,7 +2413,7 @@ impl CalcCalculate {
                     if !compress_descriptor {
                         match list_descriptor {
                             None => {
-                                return Err(crate::ErrorType::Index);
+                                return Err(ElemError::new(crate::ErrorType::Index));
                             }
                             Some(o) => {
                                 break_on_descriptor = !new_list_descriptor.equal(o);
@@ -2063,7 +2445,7 @@ impl CalcCalculate {
                     if compress_descriptor {
                         match list_descriptor {
                             None => {
-                                return Err(crate::ErrorType::Index);
+                                return Err(ElemError::new(crate::ErrorType::Index));
                             }
                             Some(o) => {
                                 o.copy_list_descriptor(
@@ -2095,6 +2477,14 @@ impl CalcCalculate {
                     new_sl_interest += list_am.sl_interest();
                     new_value_to_interest += list_am.value_to_interest();
                     new_value_to_principal += list_am.value_to_principal();
+                    new_deferred_interest += list_am.deferred_interest();
+                    new_scheduled_principal += list_am.scheduled_principal();
+                    new_prepaid_principal += list_am.prepaid_principal();
+                    new_surviving_balance = list_am.surviving_balance();
+                    new_defaulted_principal += list_am.defaulted_principal();
+                    new_recovery += list_am.recovery();
+                    new_loss += list_am.loss();
+                    new_delinquent_balance = list_am.delinquent_balance();
                     new_periods += 1;
                 }
                 am_index += 1;
@@ -2105,7 +2495,7 @@ impl CalcCalculate {
                 rollup_list_am.add_amortization_ex(
                     new_event_type,
                     orig_date,
-                    orig_date,
+                    first_date,
                     new_sort,
                     new_value,
                     new_value_expr,
@@ -2127,6 +2517,15 @@ impl CalcCalculate {
                     Option::from(new_list_parameter),
                     Option::from(new_list_descriptor),
                 );
+                rollup_list_am.set_scheduled_date(orig_date);
+                rollup_list_am.set_deferred_interest(new_deferred_interest);
+                rollup_list_am.set_scheduled_principal(new_scheduled_principal);
+                rollup_list_am.set_prepaid_principal(new_prepaid_principal);
+                rollup_list_am.set_surviving_balance(new_surviving_balance);
+                rollup_list_am.set_defaulted_principal(new_defaulted_principal);
+                rollup_list_am.set_recovery(new_recovery);
+                rollup_list_am.set_loss(new_loss);
+                rollup_list_am.set_delinquent_balance(new_delinquent_balance);
             }
         }
 
@@ -2177,13 +2576,13 @@ impl CalcCalculate {
                     let new_elem_extension = rollup_list_am.elem_extension().copy();
 
                     let new_list_parameter: ListParameter = match rollup_list_am.list_parameter() {
-                        None => return Err(crate::ErrorType::Index),
+                        None => return Err(ElemError::new(crate::ErrorType::Index)),
                         Some(o) => o.copy(updating_json),
                     };
 
                     let new_list_descriptor: ListDescriptor = match rollup_list_am.list_descriptor()
                     {
-                        None => return Err(crate::ErrorType::Index),
+                        None => return Err(ElemError::new(crate::ErrorType::Index)),
                         Some(o) => o.copy(false, updating_json),
                     };
 
@@ -2212,6 +2611,16 @@ impl CalcCalculate {
                         Option::from(new_list_parameter),
                         Option::from(new_list_descriptor),
                     );
+                    new_list_am.set_scheduled_date(rollup_list_am.scheduled_date());
+                    new_list_am.set_index_value(rollup_list_am.index_value());
+                    new_list_am.set_deferred_interest(rollup_list_am.deferred_interest());
+                    new_list_am.set_scheduled_principal(rollup_list_am.scheduled_principal());
+                    new_list_am.set_prepaid_principal(rollup_list_am.prepaid_principal());
+                    new_list_am.set_surviving_balance(rollup_list_am.surviving_balance());
+                    new_list_am.set_defaulted_principal(rollup_list_am.defaulted_principal());
+                    new_list_am.set_recovery(rollup_list_am.recovery());
+                    new_list_am.set_loss(rollup_list_am.loss());
+                    new_list_am.set_delinquent_balance(rollup_list_am.delinquent_balance());
 
                     rollup_periods = new_periods;
                     rollup_index += 1;
@@ -2241,12 +2650,12 @@ impl CalcCalculate {
                 let new_elem_extension = list_am.elem_extension().copy();
 
                 let new_list_parameter: ListParameter = match list_am.list_parameter() {
-                    None => return Err(crate::ErrorType::Index),
+                    None => return Err(ElemError::new(crate::ErrorType::Index)),
                     Some(o) => o.copy(updating_json),
                 };
 
                 let new_list_descriptor: ListDescriptor = match list_am.list_descriptor() {
-                    None => return Err(crate::ErrorType::Index),
+                    None => return Err(ElemError::new(crate::ErrorType::Index)),
                     Some(o) => o.copy(false, updating_json),
                 };
 
@@ -2275,6 +2684,16 @@ impl CalcCalculate {
                     Option::from(new_list_parameter),
                     Option::from(new_list_descriptor),
                 );
+                new_list_am.set_scheduled_date(list_am.scheduled_date());
+                new_list_am.set_index_value(list_am.index_value());
+                new_list_am.set_deferred_interest(list_am.deferred_interest());
+                new_list_am.set_scheduled_principal(list_am.scheduled_principal());
+                new_list_am.set_prepaid_principal(list_am.prepaid_principal());
+                new_list_am.set_surviving_balance(list_am.surviving_balance());
+                new_list_am.set_defaulted_principal(list_am.defaulted_principal());
+                new_list_am.set_recovery(list_am.recovery());
+                new_list_am.set_loss(list_am.loss());
+                new_list_am.set_delinquent_balance(list_am.delinquent_balance());
             }
 
             if rollup_periods > 0 {
@@ -2295,6 +2714,7 @@ impl CalcCalculate {
     ///
     /// # Arguments
     ///
+    /// * `calc_mgr` - Calculation manager.
     /// * `list_event` - The event list to create.
     /// * `optimize` - If true the ListDescriptor for each event
     ///     is not evaluated and statistic events are not expanded in the cashflow.
@@ -2305,12 +2725,13 @@ impl CalcCalculate {
 
     pub fn expand_cashflow(
         &self,
+        calc_mgr: &CalcManager,
         list_event: &ListEvent,
         optimize: bool,
-    ) -> Result<ListAmortization, crate::ErrorType> {
+    ) -> Result<ListAmortization, ElemError> {
         let mut list_am = ListAmortization::new();
 
-        let result = self.expand_with_list(list_event, &mut list_am, optimize);
+        let result = self.expand_with_list(calc_mgr, list_event, &mut list_am, optimize);
 
         match result {
             Err(e) => Err(e),
@@ -2325,6 +2746,7 @@ impl CalcCalculate {
     ///
     /// # Arguments
     ///
+    /// * `calc_mgr` - Calculation manager.
     /// * `list_event` - The event list to create.
     /// * `list_am` - The resulting amortization element list.
     /// * `optimize` - If true the ListDescriptor for each event
@@ -2336,17 +2758,20 @@ impl CalcCalculate {
 
     fn expand_with_list(
         &self,
+        calc_mgr: &CalcManager,
         list_event: &ListEvent,
         list_am: &mut ListAmortization,
         optimize: bool,
-    ) -> Result<(), crate::ErrorType> {
+    ) -> Result<(), ElemError> {
         let orig_list_index = list_event.index();
-        let updating_json = self.calc_mgr().updating_json();
-        self.statistic_event_seen.set(false);
+        let updating_json = calc_mgr.updating_json();
+        self.statistic_event_seen.store(false, Ordering::Relaxed);
 
-        self.expr_mut().clear();
+        let mut calc_expression = self.new_expression(calc_mgr);
         list_am.clear();
 
+        let mut last_rate: Option<Decimal> = None; // Used by periodic caps and floors
+
         let mut event_index = 0;
         while event_index < list_event.count() {
             if !list_event.get_element(event_index) {
@@ -2365,7 +2790,7 @@ impl CalcCalculate {
             let value_expr_balance = list_event.value_expr_balance();
 
             let list_parameter: ListParameter = match list_event.list_parameter() {
-                None => return Err(crate::ErrorType::Index),
+                None => return Err(ElemError::new(crate::ErrorType::Index)),
                 Some(o) => o.copy(updating_json),
             };
 
@@ -2373,18 +2798,19 @@ impl CalcCalculate {
             if list_event.periods_expr().is_empty() {
                 periods = list_event.periods();
             } else {
-                self.expr_mut().init_expression(
+                calc_expression.init_expression(
                     self.list_descriptor_cashflow.as_ref(),
                     None,
                     Option::from(&list_parameter),
                     list_event.periods_expr(),
                 );
 
-                self.expr_mut()
-                    .set_symbol_integer("intPeriods", list_event.periods());
+                calc_expression.set_symbol_integer("intPeriods", list_event.periods());
 
-                let result_symbol: ElemSymbol = match self.expr().evaluate(None, None) {
-                    Err(e) => {
+                let result_symbol: ElemSymbol = match calc_expression.evaluate(None, None) {
+                    Err(mut e) => {
+                        e.set_event_index(event_index);
+                        e.set_column_name("Periods-expr");
                         list_event.get_element(orig_list_index);
                         return Err(e);
                     }
@@ -2426,7 +2852,7 @@ impl CalcCalculate {
             let list_desc = list_event.list_descriptor();
 
             let list_descriptor: &ListDescriptor = match list_desc {
-                None => return Err(crate::ErrorType::Index),
+                None => return Err(ElemError::new(crate::ErrorType::Index)),
                 Some(o) => o,
             };
 
@@ -2443,6 +2869,63 @@ impl CalcCalculate {
 
             let new_eom = list_event.elem_extension().extension_eom();
 
+            let business_day = list_event.elem_extension().extension_business_day();
+            let mut calendar: Option<ElemCalendar> = None;
+            if business_day != crate::BusinessDayType::Unadjusted {
+                match calc_mgr
+                    .list_calendar()
+                    .resolve(list_event.elem_extension().extension_calendar())
+                {
+                    None => {
+                        let mut error = ElemError::new(crate::ErrorType::Calendar);
+                        error.set_event_index(event_index);
+                        error.set_column_name("Calendar");
+                        list_event.get_element(orig_list_index);
+                        return Err(error);
+                    }
+                    Some(o) => {
+                        calendar = Option::from(o);
+                    }
+                }
+            }
+
+            if elem_type == crate::ExtensionType::InterestChange
+                && calc_mgr
+                    .list_calendar()
+                    .resolve(list_event.elem_extension().extension_calendar())
+                    .is_none()
+            {
+                let mut error = ElemError::new(crate::ErrorType::Calendar);
+                error.set_event_index(event_index);
+                error.set_column_name("Calendar");
+                list_event.get_element(orig_list_index);
+                return Err(error);
+            }
+
+            let mut rate_index: Option<ElemRateIndex> = None;
+            if elem_type == crate::ExtensionType::InterestChange
+                && !list_event.elem_extension().ic_index().is_empty()
+            {
+                match calc_mgr
+                    .list_rate_index()
+                    .get_rate_index(list_event.elem_extension().ic_index())
+                {
+                    None => {
+                        let mut error = ElemError::new(crate::ErrorType::RateIndex);
+                        error.set_event_index(event_index);
+                        error.set_column_name("Index");
+                        list_event.get_element(orig_list_index);
+                        return Err(error);
+                    }
+                    Some(o) => {
+                        rate_index = Option::from(o.clone());
+                    }
+                }
+            }
+            let reset_frequency = list_event.elem_extension().ic_reset_frequency();
+            let mut reset_date = event_date;
+            let mut index_value: Option<Decimal> = None;
+
             match elem_type {
                 crate::ExtensionType::InterestChange => {
                     if list_event.elem_extension().ic_interest_frequency()
@@ -2455,7 +2938,7 @@ impl CalcCalculate {
                     periods = 1; // Perpetual until next statistic with the same name is seen
                     orig_periods = periods;
                     list_event.set_skip_mask(0, 0);
-                    self.statistic_event_seen.set(true);
+                    self.statistic_event_seen.store(true, Ordering::Relaxed);
                 }
                 _ => {}
             }
@@ -2463,7 +2946,7 @@ impl CalcCalculate {
             if !optimize {
                 let mut new_list_parameter = list_parameter.copy(updating_json);
                 new_list_parameter.add_parameter("intDate", "", "", updating_json);
-                new_list_parameter.set_integer(orig_date);
+                new_list_parameter.set_integer(CoreUtility::date_to_integer(orig_date));
                 new_list_parameter.add_parameter("decValue", "", "", updating_json);
                 new_list_parameter.set_decimal(orig_value);
                 new_list_parameter.add_parameter("intPeriods", "", "", updating_json);
@@ -2478,8 +2961,8 @@ impl CalcCalculate {
 
                 CalcUtility::evaluate_descriptors(
                     // Evaluate all the descriptors in the event
-                    &self.calc_manager,
-                    &self.calc_expression,
+                    calc_mgr,
+                    &mut calc_expression,
                     &new_list_parameter,
                     list_descriptor,
                 );
@@ -2494,19 +2977,20 @@ impl CalcCalculate {
                         && !(optimize && elem_type == crate::ExtensionType::StatisticValue)
                 {
                     if !value_expr.is_empty() && !value_expr_balance {
-                        self.expr_mut().init_expression(
+                        calc_expression.init_expression(
                             self.list_descriptor_cashflow.as_ref(),
                             None,
                             Option::from(&list_parameter),
                             value_expr,
                         );
 
-                        self.expr_mut().set_symbol_decimal("decValue", orig_value);
-                        self.expr_mut()
-                            .set_symbol_integer("intSequence", event_sequence);
+                        calc_expression.set_symbol_decimal("decValue", orig_value);
+                        calc_expression.set_symbol_integer("intSequence", event_sequence);
 
-                        let result_symbol: ElemSymbol = match self.expr().evaluate(None, None) {
-                            Err(e) => {
+                        let result_symbol: ElemSymbol = match calc_expression.evaluate(None, None) {
+                            Err(mut e) => {
+                                e.set_event_index(event_index);
+                                e.set_column_name("Value-expr");
                                 list_event.get_element(orig_list_index);
                                 return Err(e);
                             }
@@ -2527,6 +3011,47 @@ impl CalcCalculate {
                         }
                     }
 
+                    if let Some(o) = rate_index.as_ref() {
+                        if reset_frequency == crate::FrequencyType::None || event_date >= reset_date
+                        {
+                            if reset_frequency == crate::FrequencyType::None {
+                                reset_date = event_date;
+                            }
+
+                            match CalcCalculate::index_interest_rate(
+                                list_event.elem_extension(),
+                                o,
+                                reset_date,
+                                last_rate,
+                            ) {
+                                Err(mut e) => {
+                                    e.set_event_index(event_index);
+                                    e.set_column_name("Index");
+                                    list_event.get_element(orig_list_index);
+                                    return Err(e);
+                                }
+                                Ok((rate, index_rate)) => {
+                                    value = rate;
+                                    index_value = Option::from(index_rate);
+                                }
+                            }
+
+                            while reset_frequency != crate::FrequencyType::None
+                                && reset_date <= event_date
+                            {
+                                reset_date = CoreUtility::date_new(
+                                    orig_date,
+                                    reset_date,
+                                    reset_frequency,
+                                    1,
+                                    new_eom,
+                                );
+                            }
+                        } else if let Some(o) = last_rate {
+                            value = o; // Rate is unchanged until the next reset
+                        }
+                    }
+
                     if elem_type == crate::ExtensionType::PrincipalChange {
                         match list_event.elem_extension().pc_type() {
                             crate::PrincipalType::Negative | crate::PrincipalType::Decrease => {
@@ -2538,11 +3063,16 @@ impl CalcCalculate {
                         }
                     }
 
+                    let adjusted_date = match calendar.as_ref() {
+                        None => event_date,
+                        Some(o) => o.adjust_date(event_date, business_day),
+                    };
+
                     let new_elem_extension = list_event.elem_extension().copy();
                     list_am.add_amortization(
                         event_type,
                         orig_date,
-                        event_date,
+                        adjusted_date,
                         sort_order,
                         value,
                         if value_expr_balance { value_expr } else { "" },
@@ -2558,6 +3088,12 @@ impl CalcCalculate {
                         Option::from(list_parameter.copy(updating_json)),
                         Option::from(list_descriptor.copy(false, updating_json)),
                     );
+                    list_am.set_scheduled_date(event_date);
+
+                    if elem_type == crate::ExtensionType::InterestChange {
+                        list_am.set_index_value(index_value);
+                        last_rate = Option::from(value);
+                    }
 
                     event_sequence += 1;
                 }
@@ -2584,6 +3120,110 @@ impl CalcCalculate {
         Ok(())
     }
 
+    /// Calculate the rate of an index-driven interest change at a reset date.
+    /// The rate is the index value observed the lookback days before the
+    /// reset date plus the margin. The change from the previous rate is then
+    /// limited by the periodic cap and floor, and the result is limited by
+    /// the lifetime cap and floor.
+    ///
+    /// # Arguments
+    ///
+    /// * `elem_extension` - Interest change extension.
+    /// * `rate_index` - Rate index referenced by the interest change.
+    /// * `reset_date` - Date of the rate reset.
+    /// * `last_rate` - Previous interest change rate (None if not applicable).
+    ///
+    /// # Return
+    ///
+    /// * The new rate and the index value used, otherwise an error code.
+    fn index_interest_rate(
+        elem_extension: &ElemExtension,
+        rate_index: &ElemRateIndex,
+        reset_date: NaiveDate,
+        last_rate: Option<Decimal>,
+    ) -> Result<(Decimal, Decimal), ElemError> {
+        let observe_date =
+            reset_date - chrono::Duration::days(elem_extension.ic_lookback_days() as i64);
+
+        let index_value = match rate_index.value(observe_date) {
+            None => return Err(ElemError::new(crate::ErrorType::RateIndex)),
+            Some(o) => o,
+        };
+
+        let mut rate = index_value + elem_extension.ic_margin();
+
+        if let Some(o) = last_rate {
+            if let Some(o2) = elem_extension.ic_periodic_cap() {
+                if rate > o + o2 {
+                    rate = o + o2;
+                }
+            }
+            if let Some(o2) = elem_extension.ic_periodic_floor() {
+                if rate < o - o2 {
+                    rate = o - o2;
+                }
+            }
+        }
+
+        if let Some(o) = elem_extension.ic_lifetime_cap() {
+            if rate > o {
+                rate = o;
+            }
+        }
+        if let Some(o) = elem_extension.ic_lifetime_floor() {
+            if rate < o {
+                rate = o;
+            }
+        }
+
+        Ok((rate, index_value))
+    }
+
+    /// Calculate the level payment that fully amortizes a balance over
+    /// the remaining payments of the current principal change event.
+    /// The list position is restored to the current element upon return.
+    ///
+    /// # Arguments
+    ///
+    /// * `list_am` - The amortization list being balanced.
+    /// * `am_index` - Index of the current (payment) element.
+    /// * `balance` - Balance to amortize (before the current payment).
+    /// * `int_rate` - The current nominal annual interest rate expressed as a percentage.
+    /// * `days_in_year` - Number of days in the year.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn amortizing_payment(
+        &self,
+        list_am: &ListAmortization,
+        am_index: usize,
+        balance: Decimal,
+        int_rate: Decimal,
+        days_in_year: usize,
+    ) -> Decimal {
+        let list_event_index = list_am.list_event_index();
+        let periodic_rate =
+            CoreUtility::rate_nar_to_pr(int_rate / dec!(100.0), list_am.frequency(), days_in_year);
+
+        let mut periods: usize = 0;
+        let mut index = am_index;
+        while list_am.get_element(index) {
+            if list_am.elem_type() == crate::ExtensionType::PrincipalChange
+                && list_am.list_event_index() == list_event_index
+            {
+                periods += 1;
+            }
+            index += 1;
+        }
+        list_am.get_element(am_index);
+
+        match CoreFinancial::pmt(periodic_rate, dec!(periods), -balance, dec!(0.0), false) {
+            Err(_e) => balance,
+            Ok(o) => o,
+        }
+    }
+
     /// Calculates the compounded and straight-line interest due for the interest period.
     /// Upon return, sets the module-level variables interest and sl_interest.
     /// Based upon the previous interest date self.last_interest_date and the event_date parameter,
@@ -2606,6 +3246,7 @@ impl CalcCalculate {
     /// * `balance` - The current balance of the cashflow.
     /// * `sl_balance` - The straight-line balance of the cashflow.
     /// * `eom` - Adjust successive dates to end of month.
+    /// * `calendar` - Business day calendar used by the Business/252 day count basis.
     #[allow(clippy::too_many_arguments)]
 
     pub fn interest(
@@ -2613,17 +3254,18 @@ impl CalcCalculate {
         method: crate::MethodType,
         day_count_basis: crate::DayCountType,
         days_in_year: usize,
-        orig_date: usize,
-        event_date: usize,
+        orig_date: NaiveDate,
+        event_date: NaiveDate,
         frequency: crate::FrequencyType,
         effective_frequency: crate::FrequencyType,
         int_rate: Decimal,
         mut balance: Decimal,
         mut sl_balance: Decimal,
         eom: bool,
+        calendar: &ElemCalendar,
     ) {
-        self.interest.set(dec!(0.0));
-        self.sl_interest.set(dec!(0.0));
+        self.set_last_interest(dec!(0.0));
+        self.set_last_sl_interest(dec!(0.0));
 
         let mut nominal_rate = int_rate / dec!(100.0);
 
@@ -2638,7 +3280,8 @@ impl CalcCalculate {
         }
         let periodic_rate = CoreUtility::rate_nar_to_pr(nominal_rate, frequency, days_in_year);
 
-        let mut last_interest_serial = CoreUtility::date_to_serial(self.last_interest_date.get());
+        let mut last_interest_serial =
+            CoreUtility::date_to_serial(*self.last_interest_date.lock().unwrap());
         let event_date_serial = CoreUtility::date_to_serial(event_date);
         let mut comp_date = event_date;
         let mut comp_serial = event_date_serial;
@@ -2677,17 +3320,29 @@ impl CalcCalculate {
             comp_serial = prev_serial;
         }
         loop {
-            let day_count_factor = CoreUtility::day_count_factor(
+            let day_count_factor = CoreUtility::day_count_factor_with_calendar(
                 last_interest_serial,
                 comp_serial,
                 day_count_basis,
                 days_in_year,
                 periods_in_year,
+                calendar,
             );
+            // Business/252 compounds daily over the business days
+            let business_factor = if day_count_basis == crate::DayCountType::Business252 {
+                CoreUtility::rate_business_252(
+                    nominal_rate,
+                    CoreUtility::business_days(last_interest_serial, comp_serial, calendar),
+                )
+            } else {
+                dec!(0.0)
+            };
             let calc_interest: Decimal;
             if frequency == crate::FrequencyType::Continuous {
                 calc_interest =
                     calc_bal * CoreUtility::decimal_exp(nominal_rate * day_count_factor) - calc_bal;
+            } else if day_count_basis == crate::DayCountType::Business252 {
+                calc_interest = calc_bal * business_factor;
             } else if (day_count_basis == crate::DayCountType::Periodic
                 || day_count_basis == crate::DayCountType::RuleOf78)
                 && !stub_period
@@ -2703,23 +3358,25 @@ impl CalcCalculate {
                 calc_bal += calc_interest;
             }
             if frequency == crate::FrequencyType::Continuous {
-                self.sl_interest.set(
-                    self.sl_interest.get()
+                self.set_last_sl_interest(
+                    self.last_sl_interest()
                         + (sl_balance * CoreUtility::decimal_exp(nominal_rate * day_count_factor)
                             - sl_balance),
                 );
+            } else if day_count_basis == crate::DayCountType::Business252 {
+                self.set_last_sl_interest(self.last_sl_interest() + (sl_balance * business_factor));
             } else if (day_count_basis == crate::DayCountType::Periodic
                 || day_count_basis == crate::DayCountType::RuleOf78)
                 && !stub_period
             {
-                self.sl_interest
-                    .set(self.sl_interest.get() + (sl_balance * periodic_rate));
+                self.set_last_sl_interest(self.last_sl_interest() + (sl_balance * periodic_rate));
             } else {
-                self.sl_interest
-                    .set(self.sl_interest.get() + (sl_balance * nominal_rate * day_count_factor));
+                self.set_last_sl_interest(
+                    self.last_sl_interest() + (sl_balance * nominal_rate * day_count_factor),
+                );
             }
 
-            self.last_interest_date.set(comp_date);
+            *self.last_interest_date.lock().unwrap() = comp_date;
             last_interest_serial = comp_serial;
 
             if comp_serial >= event_date_serial {
@@ -2736,9 +3393,9 @@ impl CalcCalculate {
             stub_period = false; // By definition, not a stub period
         }
 
-        self.last_interest_date.set(event_date);
+        *self.last_interest_date.lock().unwrap() = event_date;
 
-        self.interest.set(calc_bal - balance + calc_int);
+        self.set_last_interest(calc_bal - balance + calc_int);
     }
 
     /// Merges dec_two event lists into a new event list according
@@ -2749,6 +3406,7 @@ impl CalcCalculate {
     ///
     /// # Arguments
     ///
+    /// * `calc_mgr` - Calculation manager.
     /// * `list_event1` - The first event list to be merged.
     /// * `list_event2` - The second event list to be merged.
     /// * `interest_event_action` - The action to be performed
@@ -2760,22 +3418,23 @@ impl CalcCalculate {
 
     pub fn merge_cashflow(
         &self,
+        calc_mgr: &CalcManager,
         list_event1: &ListEvent,
         list_event2: &ListEvent,
         interest_event_action: crate::MergeType,
-    ) -> Result<ListEvent, crate::ErrorType> {
+    ) -> Result<ListEvent, ElemError> {
         let orig_list_index1 = list_event1.index();
         let orig_list_index2 = list_event2.index();
         let mut event_index1: usize = 0;
         let mut event_index2: usize = 0;
         let mut action = crate::MergeType::IntAll; // Initially fetch both event lists
-        let mut event_date1: usize = 0;
-        let mut event_date2: usize = 0;
+        let mut event_date1 = NaiveDate::MIN;
+        let mut event_date2 = NaiveDate::MIN;
         let mut sort1: usize = 0;
         let mut sort2: usize = 0;
         let mut next_element1: bool = true;
         let mut next_element2: bool = true;
-        let updating_json = self.calc_mgr().updating_json();
+        let updating_json = calc_mgr.updating_json();
 
         let mut new_list_event = ListEvent::new(list_event1.cashflow());
         new_list_event.set_sort_on_add(false);
@@ -2877,6 +3536,7 @@ impl CalcCalculate {
     ///
     /// # Arguments
     ///
+    /// * `calc_mgr` - Calculation manager.
     /// * `list_am` - The amortization list to normalize.
     /// * `list_statistic_helper` - The list of active statistic elements.
     /// * `combine_principal` - Combine principal change events that are
@@ -2890,14 +3550,15 @@ impl CalcCalculate {
 
     pub fn normalize_cashflow(
         &self,
+        calc_mgr: &CalcManager,
         list_am: &ListAmortization,
         list_statistic_helper: &mut ListStatisticHelper,
         combine_principal: bool,
-    ) -> Result<ListAmortization, crate::ErrorType> {
+    ) -> Result<ListAmortization, ElemError> {
         let mut new_list_am = ListAmortization::new();
         let orig_list_index = list_am.index();
-        let mut new_date: usize = 0;
-        let updating_json = self.calc_mgr().updating_json();
+        let mut new_date = NaiveDate::MIN;
+        let updating_json = calc_mgr.updating_json();
         let mut am_index: usize = 0;
         while am_index < list_am.count() {
             if !list_am.get_element(am_index) {
@@ -2906,6 +3567,16 @@ impl CalcCalculate {
             am_index += 1;
 
             new_date = list_am.event_date();
+            let new_scheduled_date = list_am.scheduled_date();
+            let new_index_value = list_am.index_value();
+            let new_deferred_interest = list_am.deferred_interest();
+            let new_scheduled_principal = list_am.scheduled_principal();
+            let new_prepaid_principal = list_am.prepaid_principal();
+            let new_surviving_balance = list_am.surviving_balance();
+            let new_defaulted_principal = list_am.defaulted_principal();
+            let new_recovery = list_am.recovery();
+            let new_loss = list_am.loss();
+            let new_delinquent_balance = list_am.delinquent_balance();
             let new_event_type = list_am.event_type();
             let new_type = list_am.elem_type();
             let new_orig_date = list_am.orig_date();
@@ -2946,12 +3617,12 @@ impl CalcCalculate {
             let new_elem_extension = list_am.elem_extension().copy();
 
             let mut new_list_parameter: ListParameter = match list_am.list_parameter() {
-                None => return Err(crate::ErrorType::Index),
+                None => return Err(ElemError::new(crate::ErrorType::Index)),
                 Some(o) => o.copy(updating_json),
             };
 
             let mut new_list_descriptor: ListDescriptor = match list_am.list_descriptor() {
-                None => return Err(crate::ErrorType::Index),
+                None => return Err(ElemError::new(crate::ErrorType::Index)),
                 Some(o) => o.copy(false, updating_json),
             };
 
@@ -2980,12 +3651,12 @@ impl CalcCalculate {
                     let elem_extension = list_am.elem_extension().copy();
 
                     let list_parameter: ListParameter = match list_am.list_parameter() {
-                        None => return Err(crate::ErrorType::Index),
+                        None => return Err(ElemError::new(crate::ErrorType::Index)),
                         Some(o) => o.copy(updating_json),
                     };
 
                     let list_descriptor: ListDescriptor = match list_am.list_descriptor() {
-                        None => return Err(crate::ErrorType::Index),
+                        None => return Err(ElemError::new(crate::ErrorType::Index)),
                         Some(o) => o.copy(false, updating_json),
                     };
 
@@ -3039,7 +3710,7 @@ impl CalcCalculate {
                 let list_parameter = list_am.list_parameter();
                 match list_parameter {
                     None => {
-                        return Err(crate::ErrorType::Index);
+                        return Err(ElemError::new(crate::ErrorType::Index));
                     }
                     Some(o) => {
                         if !new_list_parameter.equal(o) {
@@ -3050,7 +3721,7 @@ impl CalcCalculate {
                 let list_descriptor = list_am.list_descriptor();
                 match list_descriptor {
                     None => {
-                        return Err(crate::ErrorType::Index);
+                        return Err(ElemError::new(crate::ErrorType::Index));
                     }
                     Some(o) => {
                         if !new_list_descriptor.equal(o) {
@@ -3106,6 +3777,16 @@ impl CalcCalculate {
                 Option::from(new_list_parameter.copy(updating_json)),
                 Option::from(new_list_descriptor.copy(false, updating_json)),
             );
+            new_list_am.set_scheduled_date(new_scheduled_date);
+            new_list_am.set_index_value(new_index_value);
+            new_list_am.set_deferred_interest(new_deferred_interest);
+            new_list_am.set_scheduled_principal(new_scheduled_principal);
+            new_list_am.set_prepaid_principal(new_prepaid_principal);
+            new_list_am.set_surviving_balance(new_surviving_balance);
+            new_list_am.set_defaulted_principal(new_defaulted_principal);
+            new_list_am.set_recovery(new_recovery);
+            new_list_am.set_loss(new_loss);
+            new_list_am.set_delinquent_balance(new_delinquent_balance);
         }
 
         for statistic_index in 0..list_statistic_helper.count() {
@@ -3130,12 +3811,12 @@ impl CalcCalculate {
                 let elem_extension = list_am.elem_extension().copy();
 
                 let list_parameter: ListParameter = match list_am.list_parameter() {
-                    None => return Err(crate::ErrorType::Index),
+                    None => return Err(ElemError::new(crate::ErrorType::Index)),
                     Some(o) => o.copy(updating_json),
                 };
 
                 let list_descriptor: ListDescriptor = match list_am.list_descriptor() {
-                    None => return Err(crate::ErrorType::Index),
+                    None => return Err(ElemError::new(crate::ErrorType::Index)),
                     Some(o) => o.copy(false, updating_json),
                 };
 
@@ -3175,7 +3856,8 @@ impl CalcCalculate {
     ///
     /// * `fiscal_year_start_param` - Start of fiscal year in MMDD format.
     pub fn set_fiscal_year_start(&self, fiscal_year_start_param: usize) {
-        self.fiscal_year_start.set(fiscal_year_start_param);
+        self.fiscal_year_start
+            .store(fiscal_year_start_param, Ordering::Relaxed);
     }
 
     /// Set the number of significant decimal digits.
@@ -3184,7 +3866,26 @@ impl CalcCalculate {
     ///
     /// * `decimal_digits_param` - Number of significant decimal digits.
     pub fn set_decimal_digits(&self, decimal_digits_param: usize) {
-        self.decimal_digits.set(decimal_digits_param);
+        self.decimal_digits
+            .store(decimal_digits_param, Ordering::Relaxed);
+    }
+
+    /// Set the prepayment model applied when the cashflow is balanced.
+    ///
+    /// # Arguments
+    ///
+    /// * `prepayment_param` - Prepayment model (None for no prepayments).
+    pub fn set_prepayment(&self, prepayment_param: Option<ElemPrepayment>) {
+        *self.prepayment.lock().unwrap() = prepayment_param;
+    }
+
+    /// Set the default scenario applied when the cashflow is balanced.
+    ///
+    /// # Arguments
+    ///
+    /// * `default_scenario_param` - Default scenario (None for no defaults).
+    pub fn set_default_scenario(&self, default_scenario_param: Option<ElemDefaultScenario>) {
+        *self.default_scenario.lock().unwrap() = default_scenario_param;
     }
 
     /// Splits the currently selected principal change event into
@@ -3194,6 +3895,7 @@ impl CalcCalculate {
     ///
     /// # Arguments
     ///
+    /// * `calc_mgr` - Calculation manager.
     /// * `list_event` - The event list to calculate.
     /// * `all_events` - If true split the principal change event when any
     ///     active event is seen (otherwise only split the principal change event
@@ -3205,18 +3907,19 @@ impl CalcCalculate {
 
     pub fn split_cashflow(
         &self,
+        calc_mgr: &CalcManager,
         list_event: &mut ListEvent,
         all_events: bool,
-    ) -> Result<(), crate::ErrorType> {
+    ) -> Result<(), ElemError> {
         let orig_event_index = list_event.index();
         if orig_event_index == usize::MAX {
-            return Err(crate::ErrorType::Index);
+            return Err(ElemError::new(crate::ErrorType::Index));
         }
 
         if list_event.elem_type() != crate::ExtensionType::PrincipalChange {
-            return Err(crate::ErrorType::Index);
+            return Err(ElemError::new(crate::ErrorType::Index));
         }
-        let updating_json = self.calc_mgr().updating_json();
+        let updating_json = calc_mgr.updating_json();
         let mut new_date = list_event.event_date();
         let mut end_date = new_date;
         let frequency = list_event.frequency();
@@ -3232,10 +3935,7 @@ impl CalcCalculate {
             );
         }
 
-        let list_am: ListAmortization = match self.expand_cashflow(list_event, true) {
-            Err(e) => return Err(e),
-            Ok(o) => o,
-        };
+        let list_am: ListAmortization = self.expand_cashflow(calc_mgr, list_event, true)?;
 
         let mut am_index: usize = 0;
         while am_index < list_am.count() {
@@ -3297,7 +3997,7 @@ impl CalcCalculate {
             list_periods.push(periods);
         }
         if list_periods.is_empty() {
-            return Err(crate::ErrorType::Index);
+            return Err(ElemError::new(crate::ErrorType::Index));
         }
         periods = list_periods[0];
         list_event.set_periods(periods);
@@ -3334,6 +4034,7 @@ impl CalcCalculate {
     ///
     /// # Arguments
     ///
+    /// * `calc_mgr` - Calculation manager.
     /// * `list_am` - The amortization list to transform.
     /// * `after_pv` - If true transform the events after the
     ///     current value present event. Otherwise, transform the events
@@ -3350,17 +4051,18 @@ impl CalcCalculate {
 
     pub fn transform_cashflow(
         &self,
+        calc_mgr: &CalcManager,
         list_am: &ListAmortization,
         after_pv: bool,
         omit_interest_events: bool,
         all_events: bool,
         cashflow: bool,
-    ) -> Result<ListEvent, crate::ErrorType> {
+    ) -> Result<ListEvent, ElemError> {
         let orig_list_index = list_am.index();
         let mut principal_index = usize::MAX;
         let mut interest_index = usize::MAX;
         let mut cv_present_seen = all_events;
-        let updating_json = self.calc_mgr().updating_json();
+        let updating_json = calc_mgr.updating_json();
 
         let mut new_list_event = ListEvent::new(cashflow);
         new_list_event.set_sort_on_add(false);
@@ -3391,7 +4093,7 @@ impl CalcCalculate {
             }
 
             let elem_type = list_am.elem_type();
-            let event_date = list_am.event_date();
+            let event_date = list_am.scheduled_date(); // Events are re-adjusted when expanded
             let mut sort_order = list_am.sort_order();
             let mut value = list_am.value();
             let mut periods = list_am.periods();
@@ -3429,7 +4131,7 @@ impl CalcCalculate {
 
                             match list_descriptor {
                                 None => {
-                                    return Err(crate::ErrorType::Index);
+                                    return Err(ElemError::new(crate::ErrorType::Index));
                                 }
                                 Some(o) => {
                                     o.copy_list_descriptor(
@@ -3485,7 +4187,7 @@ impl CalcCalculate {
 
                         match list_descriptor {
                             None => {
-                                return Err(crate::ErrorType::Index);
+                                return Err(ElemError::new(crate::ErrorType::Index));
                             }
                             Some(o) => {
                                 o.copy_list_descriptor(
@@ -3532,7 +4234,7 @@ impl CalcCalculate {
                             let mut new_list_descriptor = ListDescriptor::new();
                             match list_descriptor {
                                 None => {
-                                    return Err(crate::ErrorType::Index);
+                                    return Err(ElemError::new(crate::ErrorType::Index));
                                 }
                                 Some(o) => {
                                     o.copy_list_descriptor(
@@ -3573,7 +4275,7 @@ impl CalcCalculate {
 
                         match list_descriptor {
                             None => {
-                                return Err(crate::ErrorType::Index);
+                                return Err(ElemError::new(crate::ErrorType::Index));
                             }
                             Some(o) => {
                                 o.copy_list_descriptor(
@@ -3616,7 +4318,7 @@ impl CalcCalculate {
 
                         match list_descriptor {
                             None => {
-                                return Err(crate::ErrorType::Index);
+                                return Err(ElemError::new(crate::ErrorType::Index));
                             }
                             Some(o) => {
                                 o.copy_list_descriptor(
@@ -3656,7 +4358,7 @@ impl CalcCalculate {
 
                     match list_descriptor {
                         None => {
-                            return Err(crate::ErrorType::Index);
+                            return Err(ElemError::new(crate::ErrorType::Index));
                         }
                         Some(o) => {
                             o.copy_list_descriptor(&mut new_list_descriptor, false, updating_json);
@@ -3698,7 +4400,7 @@ impl CalcCalculate {
 
                         match list_descriptor {
                             None => {
-                                return Err(crate::ErrorType::Index);
+                                return Err(ElemError::new(crate::ErrorType::Index));
                             }
                             Some(o) => {
                                 o.copy_list_descriptor(
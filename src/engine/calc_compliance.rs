@@ -0,0 +1,531 @@
+//! The regulatory compliance methods.
+// Copyright (c) 2021 ShiftLeft Software
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use chrono::{Datelike, NaiveDate};
+use rust_decimal::prelude::*;
+
+use crate::core::{
+    CoreIrr, CoreUtility, ElemAprResult, ElemDatedAmount, ElemError, ElemSolverSettings,
+    ExtensionValue, ListAmortization, ListDescriptor, ListEvent,
+};
+use crate::{ExtensionTrait, ListTrait};
+
+/// The regulatory compliance methods.
+pub struct CalcCompliance {}
+
+/// The regulatory compliance methods implementation.
+/// The annual percentage rate is calculated from the amortization
+/// list of a balanced cashflow. Descriptors of the "Fee" group
+/// (see GROUP_FEE) in the cashflow descriptor list or in the
+/// descriptor lists of the events are finance charges. Fees on
+/// or before the first advance are prepaid finance charges that
+/// reduce the amount financed; later fees are paid with the
+/// payments on the event date.
+///
+/// The periodic rate is found by bisection with Decimal arithmetic.
+/// The lower and upper bounds of the solver settings are annual
+/// percentage rates (by default zero and MAX_CALC_IRR per unit
+/// period) and the absolute and relative tolerances are applied
+/// to the present value of the amounts.
+impl CalcCompliance {
+    /// Calculates the annual percentage rate of a cashflow.
+    ///
+    /// # Arguments
+    ///
+    /// * `apr_type` - Regulation to calculate under.
+    /// * `list_event` - The event list of the cashflow.
+    /// * `list_am` - The amortization list of the balanced cashflow.
+    /// * `list_descriptor_cashflow` - The cashflow descriptor list.
+    /// * `decimal_digits` - Number of significant decimal digits.
+    /// * `solver_settings` - Solver settings.
+    ///
+    /// # Return
+    ///
+    /// * The results from this method or an error code.
+    pub fn calculate_apr(
+        apr_type: crate::AprType,
+        list_event: &ListEvent,
+        list_am: &ListAmortization,
+        list_descriptor_cashflow: Option<&ListDescriptor>,
+        decimal_digits: usize,
+        solver_settings: &ElemSolverSettings,
+    ) -> Result<ElemAprResult, ElemError> {
+        let dec_zero = dec!(0.0);
+
+        let mut amounts = CoreIrr::amortization_amounts(list_am);
+        amounts.sort_by_key(|o| o.date());
+
+        let first_date = match amounts.iter().find(|o| o.amount() < dec_zero) {
+            None => return Err(ElemError::new(crate::ErrorType::CalcInterest)),
+            Some(o) => o.date(),
+        };
+
+        let mut amount_financed: Decimal = amounts
+            .iter()
+            .filter(|o| o.amount() < dec_zero)
+            .map(|o| -o.amount())
+            .sum();
+        let mut total_of_payments: Decimal = amounts
+            .iter()
+            .filter(|o| o.amount() > dec_zero)
+            .map(|o| o.amount())
+            .sum();
+
+        for fee in CalcCompliance::fees(list_event, list_descriptor_cashflow, first_date).iter() {
+            if fee.date() <= first_date {
+                amount_financed -= fee.amount();
+            } else {
+                total_of_payments += fee.amount();
+            }
+            amounts.push(ElemDatedAmount::new(
+                fee.date().max(first_date),
+                fee.amount(),
+            ));
+        }
+        amounts.sort_by_key(|o| o.date());
+
+        let unit_period = match apr_type {
+            crate::AprType::RegulationZ => CalcCompliance::unit_period(list_am),
+            crate::AprType::ConsumerCreditDirective => crate::FrequencyType::OneYear,
+        };
+
+        let (times, fractions) = match apr_type {
+            crate::AprType::RegulationZ => {
+                CalcCompliance::unit_period_times(&amounts, first_date, unit_period)
+            }
+            crate::AprType::ConsumerCreditDirective => {
+                CalcCompliance::year_times(&amounts, first_date)
+            }
+        };
+
+        let periods_in_year = match apr_type {
+            crate::AprType::RegulationZ => {
+                CoreUtility::intervals_in_year(unit_period, crate::DEFAULT_DAYS_IN_YEAR)
+            }
+            crate::AprType::ConsumerCreditDirective => 1,
+        };
+
+        let (rate, iterations) = CalcCompliance::solve(
+            &amounts,
+            &times,
+            &fractions,
+            apr_type == crate::AprType::RegulationZ,
+            periods_in_year,
+            amount_financed,
+            solver_settings,
+        )?;
+
+        let apr = CoreUtility::round(
+            rate * Decimal::from(periods_in_year) * dec!(100.0),
+            crate::MAXIMUM_DISPLAY_DECIMAL_DIGITS,
+            crate::RoundType::Bankers,
+        );
+
+        amount_financed =
+            CoreUtility::round(amount_financed, decimal_digits, crate::RoundType::Bankers);
+        total_of_payments =
+            CoreUtility::round(total_of_payments, decimal_digits, crate::RoundType::Bankers);
+
+        Ok(ElemAprResult::new(
+            apr_type,
+            apr,
+            unit_period,
+            total_of_payments - amount_financed,
+            amount_financed,
+            total_of_payments,
+            iterations,
+        ))
+    }
+
+    /// Returns the fees of the cashflow descriptor list (dated
+    /// on the first advance) and of the event descriptor lists
+    /// (dated on the event date).
+    ///
+    /// # Arguments
+    ///
+    /// * `list_event` - The event list of the cashflow.
+    /// * `list_descriptor_cashflow` - The cashflow descriptor list.
+    /// * `first_date` - Date of the first advance.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn fees(
+        list_event: &ListEvent,
+        list_descriptor_cashflow: Option<&ListDescriptor>,
+        first_date: NaiveDate,
+    ) -> Vec<ElemDatedAmount> {
+        let mut fees: Vec<ElemDatedAmount> = Vec::new();
+
+        if let Some(o) = list_descriptor_cashflow {
+            CalcCompliance::fee_descriptors(o, first_date, &mut fees);
+        }
+
+        let orig_index = list_event.index();
+
+        let mut index: usize = 0;
+        while list_event.get_element(index) {
+            if let Some(o) = list_event.list_descriptor() {
+                CalcCompliance::fee_descriptors(o, list_event.event_date(), &mut fees);
+            }
+
+            index += 1;
+        }
+
+        list_event.get_element(orig_index);
+
+        fees
+    }
+
+    /// Append the fees of a descriptor list.
+    ///
+    /// # Arguments
+    ///
+    /// * `list_descriptor` - The descriptor list.
+    /// * `date` - Date of the fees.
+    /// * `fees` - Fees found (updated).
+    fn fee_descriptors(
+        list_descriptor: &ListDescriptor,
+        date: NaiveDate,
+        fees: &mut Vec<ElemDatedAmount>,
+    ) {
+        let orig_index = list_descriptor.index();
+
+        let mut index: usize = 0;
+        while list_descriptor.get_element(index) {
+            if list_descriptor.group() == crate::GROUP_FEE {
+                let amount = CoreUtility::parse_decimal(list_descriptor.value().as_str());
+                if amount != dec!(0.0) {
+                    fees.push(ElemDatedAmount::new(date, amount));
+                }
+            }
+
+            index += 1;
+        }
+
+        list_descriptor.get_element(orig_index);
+    }
+
+    /// Returns the unit period (the most common payment
+    /// frequency) of an amortization list.
+    ///
+    /// # Arguments
+    ///
+    /// * `list_am` - The amortization list.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn unit_period(list_am: &ListAmortization) -> crate::FrequencyType {
+        let mut frequencies: Vec<(crate::FrequencyType, usize)> = Vec::new();
+        let orig_index = list_am.index();
+
+        let mut index: usize = 0;
+        while list_am.get_element(index) {
+            let elem_am = list_am.elem_amortization();
+
+            if let ExtensionValue::PrincipalChange(o) = elem_am.elem_extension().extension_value() {
+                if !o.auxiliary() && elem_am.principal_decrease() > dec!(0.0) {
+                    match frequencies
+                        .iter_mut()
+                        .find(|o2| o2.0 == elem_am.frequency())
+                    {
+                        None => frequencies.push((elem_am.frequency(), 1)),
+                        Some(o2) => o2.1 += 1,
+                    }
+                }
+            }
+
+            index += 1;
+        }
+
+        list_am.get_element(orig_index);
+
+        match frequencies
+            .iter()
+            .filter(|o| o.0 != crate::FrequencyType::None)
+            .max_by_key(|o| o.1)
+        {
+            None => crate::FrequencyType::OneMonth,
+            Some(o) => o.0,
+        }
+    }
+
+    /// Returns the time of each amount from the first advance in
+    /// full unit periods (counted back from the date of the amount)
+    /// and the fraction of a unit period of the remaining odd days
+    /// (Regulation Z Appendix J).
+    ///
+    /// # Arguments
+    ///
+    /// * `amounts` - Series of dated amounts (sorted by date).
+    /// * `first_date` - Date of the first advance.
+    /// * `unit_period` - Unit period.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn unit_period_times(
+        amounts: &[ElemDatedAmount],
+        first_date: NaiveDate,
+        unit_period: crate::FrequencyType,
+    ) -> (Vec<usize>, Vec<Decimal>) {
+        let days_in_unit_period: usize = match unit_period {
+            crate::FrequencyType::OneYear => 365,
+            crate::FrequencyType::SixMonths
+            | crate::FrequencyType::FourMonths
+            | crate::FrequencyType::ThreeMonths
+            | crate::FrequencyType::TwoMonths
+            | crate::FrequencyType::OneMonth
+            | crate::FrequencyType::HalfMonth => CoreUtility::days_in_frequency(unit_period, 360),
+            _ => CoreUtility::days_in_frequency(unit_period, crate::DEFAULT_DAYS_IN_YEAR),
+        };
+
+        let mut times: Vec<usize> = Vec::new();
+        let mut fractions: Vec<Decimal> = Vec::new();
+
+        for amount in amounts.iter() {
+            let mut periods =
+                CoreUtility::date_diff(first_date, amount.date(), unit_period, 1, false).max(0);
+            let mut period_date =
+                CoreUtility::date_newi(amount.date(), amount.date(), unit_period, -periods, false);
+            while CoreUtility::date_newi(
+                amount.date(),
+                amount.date(),
+                unit_period,
+                -(periods + 1),
+                false,
+            ) >= first_date
+            {
+                periods += 1;
+                period_date = CoreUtility::date_newi(
+                    amount.date(),
+                    amount.date(),
+                    unit_period,
+                    -periods,
+                    false,
+                );
+            }
+            while periods > 0 && period_date < first_date {
+                periods -= 1;
+                period_date = CoreUtility::date_newi(
+                    amount.date(),
+                    amount.date(),
+                    unit_period,
+                    -periods,
+                    false,
+                );
+            }
+
+            let odd_days = CoreUtility::date_to_serial(period_date)
+                .saturating_sub(CoreUtility::date_to_serial(first_date));
+
+            times.push(periods as usize);
+            fractions.push(Decimal::from(odd_days) / Decimal::from(days_in_unit_period));
+        }
+
+        (times, fractions)
+    }
+
+    /// Returns the time of each amount from the first advance in
+    /// full years and the fraction of a year. Whole months counted
+    /// from the first advance are equal months (a twelfth of a year)
+    /// and the remaining days are divided by the days in the year
+    /// (EU Consumer Credit Directive, Annex I).
+    ///
+    /// # Arguments
+    ///
+    /// * `amounts` - Series of dated amounts (sorted by date).
+    /// * `first_date` - Date of the first advance.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn year_times(
+        amounts: &[ElemDatedAmount],
+        first_date: NaiveDate,
+    ) -> (Vec<usize>, Vec<Decimal>) {
+        let month_date = |months: i32| {
+            CoreUtility::date_newi(
+                first_date,
+                first_date,
+                crate::FrequencyType::OneMonth,
+                months,
+                false,
+            )
+        };
+
+        let mut times: Vec<usize> = Vec::new();
+        let mut fractions: Vec<Decimal> = Vec::new();
+
+        for o in amounts.iter() {
+            let mut months = CoreUtility::date_diff(
+                first_date,
+                o.date(),
+                crate::FrequencyType::OneMonth,
+                1,
+                false,
+            )
+            .max(0);
+            while months > 0 && month_date(months) > o.date() {
+                months -= 1;
+            }
+            while month_date(months + 1) <= o.date() {
+                months += 1;
+            }
+
+            let period_date = month_date(months);
+            let odd_days = CoreUtility::date_to_serial(o.date())
+                .saturating_sub(CoreUtility::date_to_serial(period_date));
+            let days_in_year = 365 + CoreUtility::leap_year(period_date.year() as usize);
+
+            times.push((months / 12) as usize);
+            fractions.push(
+                Decimal::from(months % 12) / dec!(12.0)
+                    + Decimal::from(odd_days) / Decimal::from(days_in_year),
+            );
+        }
+
+        (times, fractions)
+    }
+
+    /// Returns the present value of amounts at a periodic rate.
+    /// Fractions of a unit period are discounted at simple interest
+    /// (Regulation Z) or at compound interest.
+    ///
+    /// # Arguments
+    ///
+    /// * `amounts` - Series of dated amounts.
+    /// * `times` - Full unit periods of each amount.
+    /// * `fractions` - Fraction of a unit period of each amount.
+    /// * `simple` - Discount fractions at simple interest.
+    /// * `rate` - Periodic rate.
+    ///
+    /// # Return
+    ///
+    /// * The present value, otherwise None if out of range.
+    fn present_value(
+        amounts: &[ElemDatedAmount],
+        times: &[usize],
+        fractions: &[Decimal],
+        simple: bool,
+        rate: Decimal,
+    ) -> Option<Decimal> {
+        let dec_zero = dec!(0.0);
+        let dec_one = dec!(1.0);
+        let mut present_value = dec_zero;
+
+        for (amount, (time, fraction)) in amounts.iter().zip(times.iter().zip(fractions.iter())) {
+            let fraction_factor = if simple {
+                dec_one + fraction * rate
+            } else {
+                let factor = (dec_one + rate).to_f64()?.powf(fraction.to_f64()?);
+                if !factor.is_finite() {
+                    return None;
+                }
+                Decimal::from_f64(factor)?
+            };
+
+            let factor = match CoreUtility::checked_decimal_pow(dec_one + rate, *time) {
+                None if rate > dec_zero => continue, // Discounted to nothing
+                None => return None,
+                Some(o) => match o.checked_mul(fraction_factor) {
+                    None if rate > dec_zero => continue,
+                    None => return None,
+                    Some(o2) => o2,
+                },
+            };
+
+            present_value = present_value.checked_add(amount.amount().checked_div(factor)?)?;
+        }
+
+        Some(present_value)
+    }
+
+    /// Find the periodic rate that equates the present value of the
+    /// advances with the present value of the payments (bisection).
+    ///
+    /// # Arguments
+    ///
+    /// * `amounts` - Series of dated amounts.
+    /// * `times` - Full unit periods of each amount.
+    /// * `fractions` - Fraction of a unit period of each amount.
+    /// * `simple` - Discount fractions at simple interest.
+    /// * `periods_in_year` - Number of unit periods in a year.
+    /// * `amount_financed` - Amount financed.
+    /// * `solver_settings` - Solver settings.
+    ///
+    /// # Return
+    ///
+    /// * The periodic rate and the iterations used or an error code.
+    fn solve(
+        amounts: &[ElemDatedAmount],
+        times: &[usize],
+        fractions: &[Decimal],
+        simple: bool,
+        periods_in_year: usize,
+        amount_financed: Decimal,
+        solver_settings: &ElemSolverSettings,
+    ) -> Result<(Decimal, usize), ElemError> {
+        let dec_zero = dec!(0.0);
+        let dec_two = dec!(2.0);
+        let to_periodic = |o: Decimal| o / dec!(100.0) / Decimal::from(periods_in_year.max(1));
+        let present_value =
+            |rate: Decimal| CalcCompliance::present_value(amounts, times, fractions, simple, rate);
+
+        let tolerance = CoreUtility::parse_decimal(crate::DEFAULT_TOLERANCE_CALCULATE_IRR);
+        let max_iterations = solver_settings
+            .max_iterations()
+            .unwrap_or(crate::MAXIMUM_ITERATIONS_CALCULATE_IRR);
+        let mut low = solver_settings
+            .lower_bound()
+            .map(to_periodic)
+            .unwrap_or(dec_zero);
+        let mut high = solver_settings
+            .upper_bound()
+            .map(to_periodic)
+            .unwrap_or_else(|| CoreUtility::parse_decimal(crate::MAX_CALC_IRR));
+
+        let (mut low_value, high_value) = match (present_value(low), present_value(high)) {
+            (Some(o), Some(o2)) => (o, o2),
+            _ => return Err(ElemError::new(crate::ErrorType::CalcInterest)),
+        };
+
+        if solver_settings.converged(low_value, amount_financed) {
+            return Ok((low, 0));
+        }
+
+        if high <= low || (low_value < dec_zero) == (high_value < dec_zero) {
+            return Err(ElemError::new(crate::ErrorType::CalcInterest));
+        }
+
+        let mut iterations: usize = 0;
+        while iterations < max_iterations && high - low > tolerance {
+            iterations += 1;
+
+            let mid = (low + high) / dec_two;
+            let mid_value = match present_value(mid) {
+                None => return Err(ElemError::new(crate::ErrorType::CalcInterest)),
+                Some(o) => o,
+            };
+            if solver_settings.converged(mid_value, amount_financed) {
+                return Ok((mid, iterations));
+            }
+
+            if (mid_value < dec_zero) == (low_value < dec_zero) {
+                low = mid;
+                low_value = mid_value;
+            } else {
+                high = mid;
+            }
+        }
+
+        Ok(((low + high) / dec_two, iterations))
+    }
+}
@@ -7,25 +7,27 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use chrono::NaiveDate;
 use rust_decimal::prelude::*;
-use std::cell::RefCell;
-use std::cell::{Ref, RefMut};
 use std::collections::HashMap;
-use std::rc::Rc;
+use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
 
 use super::{
-    CalcExpression, CalcManager, CalcUtility, ElemCashflow, ElemCashflowStats, ElemPreferences,
+    CalcCompliance, CalcExpression, CalcManager, CalcUtility, ElemCashflow, ElemCashflowStats,
+    ElemPreferences,
 };
 use crate::core::{
-    CoreManager, CoreUtility, ElemBalanceResult, ElemColumn, ElemExtension, ElemSymbol,
-    ListAmortization, ListColumn, ListDescriptor, ListEvent, ListParameter, ListStatisticHelper,
-    ListSummary,
+    CoreFinancial, CoreIrr, CoreManager, CoreUtility, ElemAprResult, ElemBalanceResult, ElemColumn,
+    ElemDatedAmount, ElemDefaultScenario, ElemError, ElemExprLimits, ElemExprTrace, ElemExtension,
+    ElemInterestChange, ElemIrrResult, ElemPaymentStep, ElemPrepayment, ElemPrincipalChange,
+    ElemSolverSettings, ElemSymbol, ListAmortization, ListColumn, ListDescriptor, ListEvent,
+    ListParameter, ListStatisticHelper, ListSummary,
 };
 use crate::ListTrait;
 
 pub struct CalcEngine {
     /// Calculator manager element.
-    calc_manager: Rc<RefCell<CalcManager>>,
+    calc_manager: Arc<RwLock<CalcManager>>,
 }
 
 /// The main default implementation of the AmFn engine component.
@@ -53,12 +55,10 @@ impl CalcEngine {
 
     pub fn new() -> CalcEngine {
         let calc_engine = CalcEngine {
-            calc_manager: Rc::new(RefCell::new(CalcManager::new(CoreManager::new()))),
+            calc_manager: Arc::new(RwLock::new(CalcManager::new(CoreManager::new()))),
         };
 
-        calc_engine
-            .calc_mgr_mut()
-            .init_calc_manager(calc_engine.calc_manager());
+        calc_engine.calc_mgr_mut().init_calc_manager();
 
         calc_engine
     }
@@ -69,7 +69,7 @@ impl CalcEngine {
     ///
     /// * See description.
 
-    pub fn calc_manager(&self) -> &Rc<RefCell<CalcManager>> {
+    pub fn calc_manager(&self) -> &Arc<RwLock<CalcManager>> {
         &self.calc_manager
     }
 
@@ -79,8 +79,8 @@ impl CalcEngine {
     ///
     /// * See description.
 
-    pub fn calc_mgr(&self) -> Ref<CalcManager> {
-        self.calc_manager.borrow()
+    pub fn calc_mgr(&self) -> RwLockReadGuard<'_, CalcManager> {
+        self.calc_manager.read().unwrap()
     }
 
     /// Returns the mutable calculation manager.
@@ -89,8 +89,8 @@ impl CalcEngine {
     ///
     /// * See description.
 
-    pub fn calc_mgr_mut(&self) -> RefMut<CalcManager> {
-        self.calc_manager.borrow_mut()
+    pub fn calc_mgr_mut(&self) -> RwLockWriteGuard<'_, CalcManager> {
+        self.calc_manager.write().unwrap()
     }
 
     /// Initialize the engine.
@@ -100,42 +100,24 @@ impl CalcEngine {
     /// * `locale_str_param` - Initial locale string to select.
 
     pub fn init_engine(&self, locale_str_param: &str) {
+        let mut calc_mgr = self.calc_mgr_mut();
+
         if !locale_str_param.is_empty() {
-            self.calc_mgr()
-                .list_locale()
-                .select_user_locale(locale_str_param);
-            let decimal_digits = self.calc_mgr().list_locale().decimal_digits(false);
-            self.calc_mgr()
-                .preferences()
-                .set_decimal_digits(decimal_digits);
+            calc_mgr.list_locale().select_user_locale(locale_str_param);
+            let decimal_digits = calc_mgr.list_locale().decimal_digits(false);
+            calc_mgr.preferences().set_decimal_digits(decimal_digits);
         }
 
-        let orig_index = self.calc_mgr().list_cashflow().index();
-
-        self.evaluate_user_descriptors();
-        self.calc_mgr().list_template_group().evaluate_descriptors();
-
-        let mut index = 0;
-
-        loop {
-            if !self.calc_mgr().list_cashflow().get_element(index) {
-                break;
-            }
-
-            if !self.calc_mgr().list_cashflow().cashflow_valid() {
-                self.evaluate_cashflow_descriptors();
-                self.evaluate_cashflow_event_type_all();
+        let orig_index = calc_mgr.list_cashflow().index();
 
-                match self.balance_cashflow() {
-                    Err(_e) => {}
-                    Ok(_o) => {}
-                }
-            }
+        self.evaluate_user_descriptors_ext(&calc_mgr);
+        calc_mgr
+            .list_template_group()
+            .evaluate_descriptors(&calc_mgr);
 
-            index += 1;
-        }
+        self.balance_invalid_cashflows(&mut calc_mgr);
 
-        self.calc_mgr().list_cashflow().get_element(orig_index);
+        calc_mgr.select_cashflow(orig_index);
     }
 
     /// Initialize a cashflow.
@@ -149,27 +131,54 @@ impl CalcEngine {
     /// * True if successful, otherwise false.
 
     pub fn init_cashflow(&self, index: u32) -> bool {
-        if !self.calc_mgr().list_cashflow().get_element(index as usize) {
+        let mut calc_mgr = self.calc_mgr_mut();
+
+        if !calc_mgr.select_cashflow(index as usize) {
             return false;
         }
 
-        if !self.calc_mgr().list_cashflow().cashflow_valid() {
-            self.evaluate_cashflow_descriptors();
-            self.evaluate_cashflow_event_type_all();
+        if !calc_mgr.list_cashflow().cashflow_valid() {
+            self.evaluate_cashflow_descriptors_ext(&calc_mgr);
+            self.evaluate_cashflow_event_type_all_ext(&mut calc_mgr);
 
-            match self.balance_cashflow() {
-                Err(_e) => {
-                    return false;
-                }
-                Ok(_o) => {}
+            if self.balance_cashflow_ext(&mut calc_mgr).is_err() {
+                return false;
             }
         }
 
         true
     }
 
-    /// Copy the current preferences locales, exchange rates, template groups,
-    /// and optionally cashflows and return a new AmFn engine.
+    /// Balance every cashflow that is not valid. The selected
+    /// cashflow is left undefined.
+    ///
+    /// # Arguments
+    ///
+    /// * `calc_mgr` - Calculation manager.
+    fn balance_invalid_cashflows(&self, calc_mgr: &mut CalcManager) {
+        let mut index: usize = 0;
+
+        while calc_mgr.select_cashflow(index) {
+            if !calc_mgr.list_cashflow().cashflow_valid() {
+                self.evaluate_cashflow_descriptors_ext(calc_mgr);
+                self.evaluate_cashflow_event_type_all_ext(calc_mgr);
+
+                match self.balance_cashflow_ext(calc_mgr) {
+                    Err(_e) => {}
+                    Ok(_o) => {}
+                }
+            }
+
+            index += 1;
+        }
+    }
+
+    /// Copy the current preferences locales, exchange rates, calendars,
+    /// template groups, and optionally cashflows and return a new AmFn engine.
+    /// The engine is Send and Sync, so a loaded engine may be shared
+    /// (e.g., within an Arc) across threads and copied by each thread
+    /// that needs to balance its own cashflows. The copy only reads
+    /// the shared engine and has its own cashflow selection.
     ///
     /// # Arguments
     ///
@@ -181,64 +190,33 @@ impl CalcEngine {
 
     pub fn copy(&self, cashflow: bool) -> CalcEngine {
         let calc_engine = CalcEngine::new();
+        let calc_mgr = self.calc_mgr();
+        let mut new_calc_mgr = calc_engine.calc_mgr_mut();
 
-        let preferences = self
-            .calc_manager()
-            .borrow()
-            .preferences()
-            .copy_with_calc_manager(calc_engine.calc_manager(), true);
-        calc_engine.calc_mgr_mut().set_preferences(preferences);
-
-        let list_locale = self.calc_manager().borrow().list_locale().copy();
-        calc_engine.calc_mgr_mut().set_list_locale(list_locale);
-
-        let list_exchange_rate = self
-            .calc_manager()
-            .borrow()
-            .list_exchange_rate()
-            .copy_with_calc_manager();
-        calc_engine
-            .calc_mgr_mut()
-            .set_list_exchange_rate(list_exchange_rate);
+        let updating_json = calc_mgr.updating_json();
 
-        let list_template_group = self
-            .calc_manager()
-            .borrow()
-            .list_template_group()
-            .copy_with_calc_manager(calc_engine.calc_manager());
-        calc_engine
-            .calc_mgr_mut()
-            .set_list_template_group(list_template_group);
+        new_calc_mgr.set_preferences(calc_mgr.preferences().copy(true));
+        new_calc_mgr.set_list_locale(calc_mgr.list_locale().copy());
+        new_calc_mgr.set_list_exchange_rate(calc_mgr.list_exchange_rate().copy_with_calc_manager());
+        new_calc_mgr.set_list_calendar(calc_mgr.list_calendar().copy());
+        new_calc_mgr.set_list_rate_index(calc_mgr.list_rate_index().copy());
+        new_calc_mgr.set_list_template_group(calc_mgr.list_template_group().copy(updating_json));
 
         if cashflow {
-            let list_cashflow = self
-                .calc_manager()
-                .borrow()
+            let list_cashflow = calc_mgr
                 .list_cashflow()
-                .copy_with_calc_manager(calc_engine.calc_manager());
-            calc_engine.calc_mgr_mut().set_list_cashflow(list_cashflow);
-
-            let mut index: usize = 0;
-
-            loop {
-                if !self.calc_mgr().list_cashflow().get_element(index) {
-                    break;
-                }
-
-                if !self.calc_mgr().list_cashflow().cashflow_valid() {
-                    self.evaluate_cashflow_descriptors();
-                    self.evaluate_cashflow_event_type_all();
+                .copy_with_calc_manager(&new_calc_mgr);
+            let orig_index = list_cashflow.index();
+            new_calc_mgr.set_list_cashflow(list_cashflow);
 
-                    match self.balance_cashflow() {
-                        Err(_e) => {}
-                        Ok(_o) => {}
-                    }
-                }
+            drop(calc_mgr);
 
-                index += 1;
-            }
+            self.balance_invalid_cashflows(&mut new_calc_mgr);
+            new_calc_mgr.select_cashflow(orig_index);
         }
 
+        drop(new_calc_mgr);
+
         calc_engine
     }
 
@@ -247,6 +225,7 @@ impl CalcEngine {
     ///
     /// # Arguments
     ///
+    /// * `calc_mgr` - Calculation manager.
     /// * `date_param` - Base starting date for the new event(s).
     /// * `end_date_param` - Base ending date for the new event(s).
     /// * `new_date_param` - Next date for the new event(s) (i.e.,
@@ -259,25 +238,21 @@ impl CalcEngine {
 
     fn copy_template_events(
         &self,
-        date_param: usize,
-        end_date_param: usize,
-        new_date_param: usize,
+        calc_mgr: &mut CalcManager,
+        date_param: NaiveDate,
+        end_date_param: NaiveDate,
+        new_date_param: NaiveDate,
         frequency_param: crate::FrequencyType,
-    ) -> Result<ListEvent, crate::ErrorType> {
-        let new_list_event: ListEvent = match self.calc_mgr().copy_template_events(
-            &self.calc_manager,
+    ) -> Result<ListEvent, ElemError> {
+        let new_list_event: ListEvent = calc_mgr.copy_template_events(
             date_param,
             end_date_param,
             new_date_param,
             frequency_param,
-        ) {
-            Err(e) => return Err(e),
-            Ok(o) => o,
-        };
+        )?;
 
-        let mut calc_mgr = self.calc_mgr_mut();
         let list_event: &mut ListEvent = match calc_mgr.list_cashflow_mut().list_event_mut() {
-            None => return Err(crate::ErrorType::Index),
+            None => return Err(ElemError::new(crate::ErrorType::Index)),
             Some(o) => o,
         };
 
@@ -344,34 +319,53 @@ impl CalcEngine {
     ///
     /// * The results from this method or an error code.
 
-    pub fn balance_cashflow(&self) -> Result<ElemBalanceResult, crate::ErrorType> {
-        let combine_principal = self.calc_mgr().combine_principal(true);
+    pub fn balance_cashflow(&self) -> Result<ElemBalanceResult, ElemError> {
+        self.balance_cashflow_ext(&mut self.calc_mgr_mut())
+    }
+
+    /// Performs primary calculations on the selected cashflow
+    /// (see balance_cashflow).
+    ///
+    /// # Arguments
+    ///
+    /// * `calc_mgr` - Calculation manager.
+    ///
+    /// # Return
+    ///
+    /// * The results from this method or an error code.
+    fn balance_cashflow_ext(
+        &self,
+        calc_mgr: &mut CalcManager,
+    ) -> Result<ElemBalanceResult, ElemError> {
+        let combine_principal = calc_mgr.combine_principal(true);
 
         let mut list_am: ListAmortization;
         let mut statistic_helper: ListStatisticHelper;
         let mut elem_balance_result = ElemBalanceResult::new();
 
         {
-            let calc_mgr = self.calc_mgr();
             let list_cashflow = calc_mgr.list_cashflow();
 
             let list_event: &ListEvent = match list_cashflow.list_event() {
-                None => return Err(crate::ErrorType::Index),
+                None => return Err(ElemError::new(crate::ErrorType::Index)),
                 Some(o) => o,
             };
 
             match list_cashflow.list_statistic_helper() {
                 None => {
-                    return Err(crate::ErrorType::Index);
+                    return Err(ElemError::new(crate::ErrorType::Index));
                 }
                 Some(o) => {
                     statistic_helper = o.copy();
                 }
             }
 
-            let result = list_cashflow.calculate().expand_cashflow(list_event, false);
+            let result = list_cashflow
+                .calculate()
+                .expand_cashflow(calc_mgr, list_event, false);
             match result {
-                Err(e) => {
+                Err(mut e) => {
+                    e.set_cashflow_name(list_cashflow.name());
                     return Err(e);
                 }
                 Ok(o) => {
@@ -380,13 +374,15 @@ impl CalcEngine {
             }
 
             let result = list_cashflow.calculate().normalize_cashflow(
+                calc_mgr,
                 &list_am,
                 &mut statistic_helper,
                 combine_principal,
             );
 
             match result {
-                Err(e) => {
+                Err(mut e) => {
+                    e.set_cashflow_name(list_cashflow.name());
                     return Err(e);
                 }
                 Ok(o) => {
@@ -394,6 +390,7 @@ impl CalcEngine {
                 }
             }
             let mut result = list_cashflow.calculate().balance_cashflow(
+                calc_mgr,
                 &mut list_am,
                 &mut statistic_helper,
                 &elem_balance_result,
@@ -402,7 +399,8 @@ impl CalcEngine {
                 false,
             );
             match result {
-                Err(e) => {
+                Err(mut e) => {
+                    e.set_cashflow_name(list_cashflow.name());
                     return Err(e);
                 }
                 Ok(o) => {
@@ -413,6 +411,7 @@ impl CalcEngine {
             if elem_balance_result.rule_of_78_seen() {
                 // Perform rule of 78 interest allocation
                 result = list_cashflow.calculate().balance_cashflow(
+                    calc_mgr,
                     &mut list_am,
                     &mut statistic_helper,
                     &elem_balance_result,
@@ -421,7 +420,8 @@ impl CalcEngine {
                     false,
                 );
                 match result {
-                    Err(e) => {
+                    Err(mut e) => {
+                        e.set_cashflow_name(list_cashflow.name());
                         return Err(e);
                     }
                     Ok(o) => {
@@ -434,7 +434,6 @@ impl CalcEngine {
         let balance_result = elem_balance_result.copy();
 
         {
-            let mut calc_mgr = self.calc_mgr_mut();
             let list_cashflow = calc_mgr.list_cashflow_mut();
 
             list_cashflow.set_list_amortization(list_am);
@@ -442,7 +441,7 @@ impl CalcEngine {
             list_cashflow.set_elem_balance_result(elem_balance_result);
 
             let index: usize = match list_cashflow.list_amortization() {
-                None => return Err(crate::ErrorType::Index),
+                None => return Err(ElemError::new(crate::ErrorType::Index)),
                 Some(o) => {
                     if list_cashflow.last_amortization_index() != usize::MAX
                         && !o.get_element(list_cashflow.last_amortization_index())
@@ -466,13 +465,41 @@ impl CalcEngine {
     /// is the smallest amount greater than or equal to the given
     /// parameter value.
     ///
+    /// The solver settings of the cashflow and user
+    /// preferences are used.
+    ///
     /// # Return
     ///
     /// * The results from this method or an error code.
 
-    pub fn calculate_yield(&self) -> Result<ElemBalanceResult, crate::ErrorType> {
-        let updating_json = self.calc_mgr().updating_json();
-        let target_value = self.calc_mgr().target();
+    pub fn calculate_yield(&self) -> Result<ElemBalanceResult, ElemError> {
+        self.calculate_yield_with_settings(&ElemSolverSettings::new())
+    }
+
+    /// Calculates the value for an overall yield (i.e., APR).
+    /// Calculates an overall yield value that will satisfy the
+    /// condition that the remaining balance of the cashflow
+    /// is the smallest amount greater than or equal to the given
+    /// parameter value.
+    ///
+    /// Settings not set in the solver settings parameter are
+    /// resolved from the cashflow and user preferences.
+    ///
+    /// # Arguments
+    ///
+    /// * `solver_settings_param` - Solver settings.
+    ///
+    /// # Return
+    ///
+    /// * The results from this method or an error code.
+    pub fn calculate_yield_with_settings(
+        &self,
+        solver_settings_param: &ElemSolverSettings,
+    ) -> Result<ElemBalanceResult, ElemError> {
+        let mut calc_mgr = self.calc_mgr_mut();
+        let updating_json = calc_mgr.updating_json();
+        let target_value = calc_mgr.target();
+        let solver_settings = solver_settings_param.resolve(&calc_mgr.solver_settings());
 
         let mut list_am: ListAmortization;
         let mut list_statistic_helper: ListStatisticHelper;
@@ -480,12 +507,11 @@ impl CalcEngine {
         let elem_balance_result: ElemBalanceResult;
 
         {
-            let calc_mgr = self.calc_mgr();
             let list_cashflow = calc_mgr.list_cashflow();
 
             match list_cashflow.list_amortization() {
                 None => {
-                    return Err(crate::ErrorType::Index);
+                    return Err(ElemError::new(crate::ErrorType::Index));
                 }
                 Some(o) => {
                     list_am = o.copy(updating_json);
@@ -494,7 +520,7 @@ impl CalcEngine {
 
             match list_cashflow.list_statistic_helper() {
                 None => {
-                    return Err(crate::ErrorType::Index);
+                    return Err(ElemError::new(crate::ErrorType::Index));
                 }
                 Some(o) => {
                     list_statistic_helper = o.copy();
@@ -502,17 +528,20 @@ impl CalcEngine {
             }
 
             let result = match list_cashflow.list_event() {
-                None => return Err(crate::ErrorType::Index),
+                None => return Err(ElemError::new(crate::ErrorType::Index)),
                 Some(o) => list_cashflow.calculate().calculate_yield(
+                    &calc_mgr,
                     o,
                     &mut list_am,
                     &mut list_statistic_helper,
                     target_value,
+                    &solver_settings,
                 ),
             };
 
             match result {
-                Err(e) => {
+                Err(mut e) => {
+                    e.set_cashflow_name(list_cashflow.name());
                     return Err(e);
                 }
                 Ok(o) => {
@@ -524,7 +553,6 @@ impl CalcEngine {
         let balance_result = elem_balance_result.copy();
 
         {
-            let mut calc_mgr = self.calc_mgr_mut();
             let list_cashflow = calc_mgr.list_cashflow_mut();
 
             list_cashflow.set_list_amortization(list_am);
@@ -535,6 +563,189 @@ impl CalcEngine {
         Ok(balance_result)
     }
 
+    /// Calculates the internal rate of return of the selected
+    /// cashflow from its amortization list (see
+    /// CoreIrr::amortization_amounts). The cashflow must be balanced.
+    ///
+    /// # Arguments
+    ///
+    /// * `frequency_param` - Frequency of the periodic rate.
+    /// * `days_in_year_param` - Number of days in the year.
+    /// * `tolerance_param` - Rate tolerance (e.g., 0.0000000001).
+    ///
+    /// # Return
+    ///
+    /// * The results from this method or an error code.
+    pub fn calculate_irr(
+        &self,
+        frequency_param: crate::FrequencyType,
+        days_in_year_param: usize,
+        tolerance_param: Decimal,
+    ) -> Result<ElemIrrResult, ElemError> {
+        let calc_mgr = self.calc_mgr();
+        let amounts = self.amortization_amounts(&calc_mgr)?;
+
+        match CoreIrr::irr(
+            &amounts,
+            frequency_param,
+            days_in_year_param,
+            tolerance_param,
+        ) {
+            Err(mut e) => {
+                e.set_cashflow_name(calc_mgr.list_cashflow().name());
+                Err(e)
+            }
+            Ok(o) => Ok(o),
+        }
+    }
+
+    /// Calculates the XIRR-style yield (an effective annual rate
+    /// using actual days) of the selected cashflow from its
+    /// amortization list. The cashflow must be balanced.
+    ///
+    /// # Arguments
+    ///
+    /// * `days_in_year_param` - Number of days in the year (e.g., 365).
+    /// * `tolerance_param` - Rate tolerance (e.g., 0.0000000001).
+    ///
+    /// # Return
+    ///
+    /// * The results from this method or an error code.
+    pub fn calculate_xirr(
+        &self,
+        days_in_year_param: usize,
+        tolerance_param: Decimal,
+    ) -> Result<ElemIrrResult, ElemError> {
+        let calc_mgr = self.calc_mgr();
+        let amounts = self.amortization_amounts(&calc_mgr)?;
+
+        match CoreIrr::xirr(&amounts, days_in_year_param, tolerance_param) {
+            Err(mut e) => {
+                e.set_cashflow_name(calc_mgr.list_cashflow().name());
+                Err(e)
+            }
+            Ok(o) => Ok(o),
+        }
+    }
+
+    /// Calculates the annual percentage rate of the selected
+    /// cashflow under US Regulation Z (Appendix J) or the EU
+    /// Consumer Credit Directive. Descriptors of the "Fee" group
+    /// are finance charges (see CalcCompliance). The cashflow
+    /// must be balanced.
+    ///
+    /// # Arguments
+    ///
+    /// * `apr_type_param` - Regulation to calculate under.
+    ///
+    /// # Return
+    ///
+    /// * The results from this method or an error code.
+    pub fn calculate_apr(
+        &self,
+        apr_type_param: crate::AprType,
+    ) -> Result<ElemAprResult, ElemError> {
+        self.calculate_apr_with_settings(apr_type_param, &ElemSolverSettings::new())
+    }
+
+    /// Calculates the annual percentage rate of the selected
+    /// cashflow (see calculate_apr).
+    ///
+    /// Settings not set in the solver settings parameter are
+    /// resolved from the cashflow and user preferences.
+    ///
+    /// # Arguments
+    ///
+    /// * `apr_type_param` - Regulation to calculate under.
+    /// * `solver_settings_param` - Solver settings.
+    ///
+    /// # Return
+    ///
+    /// * The results from this method or an error code.
+    pub fn calculate_apr_with_settings(
+        &self,
+        apr_type_param: crate::AprType,
+        solver_settings_param: &ElemSolverSettings,
+    ) -> Result<ElemAprResult, ElemError> {
+        let calc_mgr = self.calc_mgr();
+        let solver_settings = solver_settings_param.resolve(&calc_mgr.solver_settings());
+        let list_cashflow = calc_mgr.list_cashflow();
+
+        let list_event = match list_cashflow.list_event() {
+            None => return Err(ElemError::new(crate::ErrorType::Index)),
+            Some(o) => o,
+        };
+
+        let list_am = match list_cashflow.list_amortization() {
+            None => return Err(ElemError::new(crate::ErrorType::Index)),
+            Some(o) => o,
+        };
+
+        let list_descriptor_cashflow = match list_cashflow.preferences() {
+            None => None,
+            Some(o) => Option::from(o.list_descriptor()),
+        };
+
+        match CalcCompliance::calculate_apr(
+            apr_type_param,
+            list_event,
+            list_am,
+            list_descriptor_cashflow,
+            calc_mgr.decimal_digits(true),
+            &solver_settings,
+        ) {
+            Err(mut e) => {
+                e.set_cashflow_name(list_cashflow.name());
+                Err(e)
+            }
+            Ok(o) => Ok(o),
+        }
+    }
+
+    /// Returns the series of dated amounts of the selected
+    /// cashflow's amortization list.
+    ///
+    /// # Arguments
+    ///
+    /// * `calc_mgr` - Calculation manager.
+    ///
+    /// # Return
+    ///
+    /// * The dated amounts or an error code.
+    fn amortization_amounts(
+        &self,
+        calc_mgr: &CalcManager,
+    ) -> Result<Vec<ElemDatedAmount>, ElemError> {
+        let list_cashflow = calc_mgr.list_cashflow();
+
+        match list_cashflow.list_amortization() {
+            None => {
+                let mut error = ElemError::new(crate::ErrorType::Index);
+                error.set_cashflow_name(list_cashflow.name());
+                Err(error)
+            }
+            Some(o) => Ok(CoreIrr::amortization_amounts(o)),
+        }
+    }
+
+    /// Calculates the value for an event.
+    /// Calculates either an interest amount or a principal amount
+    /// (depending upon the selected event type) that will satisfy
+    /// the condition that the remaining balance of the cashflow
+    /// is the smallest amount greater than or equal to the given
+    /// parameter value.
+    ///
+    /// The solver settings of the cashflow and user
+    /// preferences are used.
+    ///
+    /// # Return
+    ///
+    /// * The results from this method or an error code.
+
+    pub fn calculate_value(&self) -> Result<ElemBalanceResult, ElemError> {
+        self.calculate_value_with_settings(&ElemSolverSettings::new())
+    }
+
     /// Calculates the value for an event.
     /// Calculates either an interest amount or a principal amount
     /// (depending upon the selected event type) that will satisfy
@@ -542,25 +753,53 @@ impl CalcEngine {
     /// is the smallest amount greater than or equal to the given
     /// parameter value.
     ///
+    /// Settings not set in the solver settings parameter are
+    /// resolved from the cashflow and user preferences.
+    ///
+    /// # Arguments
+    ///
+    /// * `solver_settings_param` - Solver settings.
+    ///
     /// # Return
     ///
     /// * The results from this method or an error code.
+    pub fn calculate_value_with_settings(
+        &self,
+        solver_settings_param: &ElemSolverSettings,
+    ) -> Result<ElemBalanceResult, ElemError> {
+        self.calculate_value_ext(&mut self.calc_mgr_mut(), solver_settings_param)
+    }
 
-    pub fn calculate_value(&self) -> Result<ElemBalanceResult, crate::ErrorType> {
-        let updating_json = self.calc_mgr().updating_json();
-        let target_value = self.calc_mgr().target();
+    /// Calculates the value for the selected event of the
+    /// selected cashflow (see calculate_value_with_settings).
+    ///
+    /// # Arguments
+    ///
+    /// * `calc_mgr` - Calculation manager.
+    /// * `solver_settings_param` - Solver settings.
+    ///
+    /// # Return
+    ///
+    /// * The results from this method or an error code.
+    fn calculate_value_ext(
+        &self,
+        calc_mgr: &mut CalcManager,
+        solver_settings_param: &ElemSolverSettings,
+    ) -> Result<ElemBalanceResult, ElemError> {
+        let updating_json = calc_mgr.updating_json();
+        let target_value = calc_mgr.target();
+        let solver_settings = solver_settings_param.resolve(&calc_mgr.solver_settings());
 
         let mut list_am: ListAmortization;
         let mut list_statistic_helper: ListStatisticHelper;
         let elem_balance_result: ElemBalanceResult;
 
         {
-            let calc_mgr = self.calc_mgr();
             let list_cashflow = calc_mgr.list_cashflow();
 
             match list_cashflow.list_amortization() {
                 None => {
-                    return Err(crate::ErrorType::Index);
+                    return Err(ElemError::new(crate::ErrorType::Index));
                 }
                 Some(o) => {
                     list_am = o.copy(updating_json);
@@ -569,7 +808,7 @@ impl CalcEngine {
 
             match list_cashflow.list_statistic_helper() {
                 None => {
-                    return Err(crate::ErrorType::Index);
+                    return Err(ElemError::new(crate::ErrorType::Index));
                 }
                 Some(o) => {
                     list_statistic_helper = o.copy();
@@ -579,23 +818,27 @@ impl CalcEngine {
             let result;
             match list_cashflow.list_event() {
                 None => {
-                    return Err(crate::ErrorType::Index);
+                    return Err(ElemError::new(crate::ErrorType::Index));
                 }
                 Some(o) => match o.elem_type() {
                     crate::ExtensionType::PrincipalChange => {
                         result = list_cashflow.calculate().calculate_principal(
+                            calc_mgr,
                             o,
                             &mut list_am,
                             &mut list_statistic_helper,
                             target_value,
+                            &solver_settings,
                         );
                     }
                     crate::ExtensionType::InterestChange => {
                         result = list_cashflow.calculate().calculate_interest(
+                            calc_mgr,
                             o,
                             &mut list_am,
                             &mut list_statistic_helper,
                             target_value,
+                            &solver_settings,
                         );
                     }
                     _ => {
@@ -605,7 +848,8 @@ impl CalcEngine {
             }
 
             match result {
-                Err(e) => {
+                Err(mut e) => {
+                    e.set_cashflow_name(list_cashflow.name());
                     return Err(e);
                 }
                 Ok(o) => {
@@ -617,7 +861,6 @@ impl CalcEngine {
         let balance_result = elem_balance_result.copy();
 
         {
-            let mut calc_mgr = self.calc_mgr_mut();
             let list_cashflow = calc_mgr.list_cashflow_mut();
 
             list_cashflow.set_cashflow_valid(false);
@@ -635,25 +878,52 @@ impl CalcEngine {
     /// is the smallest amount greater than or equal to the given
     /// parameter value.
     ///
+    /// The solver settings of the cashflow and user
+    /// preferences are used.
+    ///
     /// # Return
     ///
     /// * The results from this method or an error code.
 
-    pub fn calculate_periods(&self) -> Result<ElemBalanceResult, crate::ErrorType> {
-        let updating_json = self.calc_mgr().updating_json();
-        let target_value = self.calc_mgr().target();
+    pub fn calculate_periods(&self) -> Result<ElemBalanceResult, ElemError> {
+        self.calculate_periods_with_settings(&ElemSolverSettings::new())
+    }
+
+    /// Calculates the periods for an event.
+    /// Calculates the number of periods that will satisfy the
+    /// condition that the remaining balance of the cashflow
+    /// is the smallest amount greater than or equal to the given
+    /// parameter value.
+    ///
+    /// Settings not set in the solver settings parameter are
+    /// resolved from the cashflow and user preferences.
+    ///
+    /// # Arguments
+    ///
+    /// * `solver_settings_param` - Solver settings.
+    ///
+    /// # Return
+    ///
+    /// * The results from this method or an error code.
+    pub fn calculate_periods_with_settings(
+        &self,
+        solver_settings_param: &ElemSolverSettings,
+    ) -> Result<ElemBalanceResult, ElemError> {
+        let mut calc_mgr = self.calc_mgr_mut();
+        let updating_json = calc_mgr.updating_json();
+        let target_value = calc_mgr.target();
+        let solver_settings = solver_settings_param.resolve(&calc_mgr.solver_settings());
 
         let mut list_am: ListAmortization;
         let mut list_statistic_helper: ListStatisticHelper;
         let elem_balance_result: ElemBalanceResult;
 
         {
-            let calc_mgr = self.calc_mgr();
             let list_cashflow = calc_mgr.list_cashflow();
 
             match list_cashflow.list_amortization() {
                 None => {
-                    return Err(crate::ErrorType::Index);
+                    return Err(ElemError::new(crate::ErrorType::Index));
                 }
                 Some(o) => {
                     list_am = o.copy(updating_json);
@@ -662,7 +932,7 @@ impl CalcEngine {
 
             match list_cashflow.list_statistic_helper() {
                 None => {
-                    return Err(crate::ErrorType::Index);
+                    return Err(ElemError::new(crate::ErrorType::Index));
                 }
                 Some(o) => {
                     list_statistic_helper = o.copy();
@@ -670,17 +940,20 @@ impl CalcEngine {
             }
 
             let result = match list_cashflow.list_event() {
-                None => return Err(crate::ErrorType::Index),
+                None => return Err(ElemError::new(crate::ErrorType::Index)),
                 Some(o) => list_cashflow.calculate().calculate_periods(
+                    &calc_mgr,
                     o,
                     &mut list_am,
                     &mut list_statistic_helper,
                     target_value,
+                    &solver_settings,
                 ),
             };
 
             match result {
-                Err(e) => {
+                Err(mut e) => {
+                    e.set_cashflow_name(list_cashflow.name());
                     return Err(e);
                 }
                 Ok(o) => {
@@ -692,7 +965,6 @@ impl CalcEngine {
         let balance_result = elem_balance_result.copy();
 
         {
-            let mut calc_mgr = self.calc_mgr_mut();
             let list_cashflow = calc_mgr.list_cashflow_mut();
 
             list_cashflow.set_cashflow_valid(false);
@@ -724,36 +996,32 @@ impl CalcEngine {
         name2_param: &str,
         new_name_param: &str,
         new_group_param: &str,
-    ) -> Result<ElemBalanceResult, crate::ErrorType> {
+    ) -> Result<ElemBalanceResult, ElemError> {
+        let mut calc_mgr = self.calc_mgr_mut();
+
         let mut new_name = String::from(new_name_param);
         if new_name.is_empty() {
-            new_name = String::from(self.calc_mgr().list_locale().get_resource(crate::USER_NEW));
+            new_name = String::from(calc_mgr.list_locale().get_resource(crate::USER_NEW));
         }
 
         let mut new_group = String::from(new_group_param);
         let new_list_event: ListEvent;
 
-        self.calc_mgr().set_updating_json(false);
+        calc_mgr.set_updating_json(false);
 
         let mut elem_preferences_opt: Option<ElemPreferences> = None;
         if !new_group.is_empty()
-            && self
-                .calc_mgr()
+            && calc_mgr
                 .list_template_group()
                 .get_element_by_group(new_group.as_str(), true)
         {
-            elem_preferences_opt = Option::from(
-                self.calc_mgr()
-                    .list_template_group()
-                    .preferences()
-                    .copy(true),
-            );
+            elem_preferences_opt =
+                Option::from(calc_mgr.list_template_group().preferences().copy(true));
         }
 
         let elem_cashflow: ElemCashflow;
 
         {
-            let calc_mgr = self.calc_mgr();
             let list_cashflow = calc_mgr.list_cashflow();
 
             let orig_index = list_cashflow.index();
@@ -766,7 +1034,7 @@ impl CalcEngine {
             };
 
             if !list_cashflow.get_element_by_name(name2_param, true) {
-                return Err(crate::ErrorType::CfName);
+                return Err(ElemError::new(crate::ErrorType::CfName));
             }
 
             let list_am2: &ListAmortization = match list_cashflow.list_amortization() {
@@ -780,7 +1048,7 @@ impl CalcEngine {
 
             let result = list_cashflow
                 .calculate()
-                .combine_cashflow(list_am, list_am2);
+                .combine_cashflow(&calc_mgr, list_am, list_am2);
 
             let new_list_am: ListAmortization = match result {
                 Err(e) => return Err(e),
@@ -802,6 +1070,7 @@ impl CalcEngine {
             };
 
             let result = list_cashflow.calculate().transform_cashflow(
+                &calc_mgr,
                 &new_list_am_output,
                 false,
                 false,
@@ -820,7 +1089,7 @@ impl CalcEngine {
 
             match list_cashflow.preferences() {
                 None => {
-                    return Err(crate::ErrorType::Index);
+                    return Err(ElemError::new(crate::ErrorType::Index));
                 }
                 Some(o) => {
                     if new_group.is_empty() && !o.group().is_empty() {
@@ -856,13 +1125,14 @@ impl CalcEngine {
             }
 
             match list_cashflow.add_cashflow_prep(
+                &calc_mgr,
                 new_name.as_str(),
                 Option::from(new_list_event),
                 elem_preferences_opt,
                 new_group.as_str(),
             ) {
                 Err(_e) => {
-                    return Err(crate::ErrorType::Cashflow);
+                    return Err(ElemError::new(crate::ErrorType::Cashflow));
                 }
                 Ok(o) => {
                     elem_cashflow = o;
@@ -871,34 +1141,24 @@ impl CalcEngine {
         }
 
         {
-            let mut calc_mgr = self.calc_mgr_mut();
             let list_cashflow = calc_mgr.list_cashflow_mut();
 
             list_cashflow.add_cashflow(elem_cashflow);
         }
 
-        {
-            let calc_mgr = self.calc_mgr();
-            let list_cashflow = calc_mgr.list_cashflow();
-
-            list_cashflow.get_element_by_name(new_name.as_str(), true);
-        }
+        calc_mgr.select_cashflow_by_name(new_name.as_str());
 
-        self.evaluate_cashflow_descriptors();
-        self.evaluate_cashflow_event_type_all();
+        self.evaluate_cashflow_descriptors_ext(&calc_mgr);
+        self.evaluate_cashflow_event_type_all_ext(&mut calc_mgr);
 
-        let elem_balance_result: ElemBalanceResult = match self.balance_cashflow() {
-            Err(e) => return Err(e),
-            Ok(o) => o,
-        };
+        let elem_balance_result: ElemBalanceResult = self.balance_cashflow_ext(&mut calc_mgr)?;
 
         {
-            let mut calc_mgr = self.calc_mgr_mut();
             let list_cashflow = calc_mgr.list_cashflow_mut();
 
             match list_cashflow.preferences_mut() {
                 None => {
-                    return Err(crate::ErrorType::Index);
+                    return Err(ElemError::new(crate::ErrorType::Index));
                 }
                 Some(o) => {
                     if !new_group.is_empty() {
@@ -909,7 +1169,7 @@ impl CalcEngine {
 
             match list_cashflow.list_event() {
                 None => {
-                    return Err(crate::ErrorType::Index);
+                    return Err(ElemError::new(crate::ErrorType::Index));
                 }
                 Some(o) => {
                     o.set_index(0);
@@ -917,7 +1177,7 @@ impl CalcEngine {
             }
         }
 
-        self.calc_mgr().set_updating_json(false);
+        calc_mgr.set_updating_json(false);
         Ok(elem_balance_result)
     }
 
@@ -944,36 +1204,32 @@ impl CalcEngine {
         new_name_param: &str,
         new_group_param: &str,
         interest_event_action: crate::MergeType,
-    ) -> Result<ElemBalanceResult, crate::ErrorType> {
+    ) -> Result<ElemBalanceResult, ElemError> {
+        let mut calc_mgr = self.calc_mgr_mut();
+
         let mut new_name: String = String::from(new_name_param);
         if new_name.is_empty() {
-            new_name = String::from(self.calc_mgr().list_locale().get_resource(crate::USER_NEW));
+            new_name = String::from(calc_mgr.list_locale().get_resource(crate::USER_NEW));
         }
 
         let mut new_group = String::from(new_group_param);
         let new_list_event: ListEvent;
 
-        self.calc_mgr().set_updating_json(true);
+        calc_mgr.set_updating_json(true);
 
         let mut elem_preferences_opt: Option<ElemPreferences> = None;
         if !new_group.is_empty()
-            && self
-                .calc_mgr()
+            && calc_mgr
                 .list_template_group()
                 .get_element_by_group(new_group.as_str(), true)
         {
-            elem_preferences_opt = Option::from(
-                self.calc_mgr()
-                    .list_template_group()
-                    .preferences()
-                    .copy(true),
-            );
+            elem_preferences_opt =
+                Option::from(calc_mgr.list_template_group().preferences().copy(true));
         }
 
         let elem_cashflow: ElemCashflow;
 
         {
-            let calc_mgr = self.calc_mgr();
             let list_cashflow = calc_mgr.list_cashflow();
 
             let orig_index = list_cashflow.index();
@@ -986,7 +1242,7 @@ impl CalcEngine {
             };
 
             if !list_cashflow.get_element_by_name(name2_param, true) {
-                return Err(crate::ErrorType::CfName);
+                return Err(ElemError::new(crate::ErrorType::CfName));
             }
 
             let list_event2: &ListEvent = match list_cashflow.list_event() {
@@ -999,6 +1255,7 @@ impl CalcEngine {
             list_cashflow.get_element(orig_index);
 
             let result = list_cashflow.calculate().merge_cashflow(
+                &calc_mgr,
                 list_event,
                 list_event2,
                 interest_event_action,
@@ -1014,7 +1271,7 @@ impl CalcEngine {
 
             match list_cashflow.preferences() {
                 None => {
-                    return Err(crate::ErrorType::Index);
+                    return Err(ElemError::new(crate::ErrorType::Index));
                 }
                 Some(o) => {
                     if new_group.is_empty() && !o.group().is_empty() {
@@ -1061,13 +1318,14 @@ impl CalcEngine {
             }
 
             match list_cashflow.add_cashflow_prep(
+                &calc_mgr,
                 new_name.as_str(),
                 Option::from(new_list_event),
                 elem_preferences_opt,
                 new_group.as_str(),
             ) {
                 Err(_e) => {
-                    return Err(crate::ErrorType::Cashflow);
+                    return Err(ElemError::new(crate::ErrorType::Cashflow));
                 }
                 Ok(o) => {
                     elem_cashflow = o;
@@ -1076,34 +1334,24 @@ impl CalcEngine {
         }
 
         {
-            let mut calc_mgr = self.calc_mgr_mut();
             let list_cashflow = calc_mgr.list_cashflow_mut();
 
             list_cashflow.add_cashflow(elem_cashflow);
         }
 
-        {
-            let calc_mgr = self.calc_mgr();
-            let list_cashflow = calc_mgr.list_cashflow();
-
-            list_cashflow.get_element_by_name(new_name.as_str(), true);
-        }
+        calc_mgr.select_cashflow_by_name(new_name.as_str());
 
-        self.evaluate_cashflow_descriptors();
-        self.evaluate_cashflow_event_type_all();
+        self.evaluate_cashflow_descriptors_ext(&calc_mgr);
+        self.evaluate_cashflow_event_type_all_ext(&mut calc_mgr);
 
-        let elem_balance_result: ElemBalanceResult = match self.balance_cashflow() {
-            Err(e) => return Err(e),
-            Ok(o) => o,
-        };
+        let elem_balance_result: ElemBalanceResult = self.balance_cashflow_ext(&mut calc_mgr)?;
 
         {
-            let mut calc_mgr = self.calc_mgr_mut();
             let list_cashflow = calc_mgr.list_cashflow_mut();
 
             match list_cashflow.preferences_mut() {
                 None => {
-                    return Err(crate::ErrorType::Index);
+                    return Err(ElemError::new(crate::ErrorType::Index));
                 }
                 Some(o) => {
                     if !new_group.is_empty() {
@@ -1114,7 +1362,7 @@ impl CalcEngine {
 
             match list_cashflow.list_event() {
                 None => {
-                    return Err(crate::ErrorType::Index);
+                    return Err(ElemError::new(crate::ErrorType::Index));
                 }
                 Some(o) => {
                     o.set_index(0);
@@ -1122,7 +1370,7 @@ impl CalcEngine {
             }
         }
 
-        self.calc_mgr().set_updating_json(false);
+        calc_mgr.set_updating_json(false);
 
         Ok(elem_balance_result)
     }
@@ -1142,27 +1390,29 @@ impl CalcEngine {
     ///
     /// * The results from this method or an error code.
 
-    pub fn split_cashflow(&self, all_events: bool) -> Result<ElemBalanceResult, crate::ErrorType> {
+    pub fn split_cashflow(&self, all_events: bool) -> Result<ElemBalanceResult, ElemError> {
+        let mut calc_mgr = self.calc_mgr_mut();
+
         let mut list_event: ListEvent;
 
-        self.calc_mgr().set_updating_json(true);
+        calc_mgr.set_updating_json(true);
 
         {
-            let calc_mgr = self.calc_mgr();
             let list_cashflow = calc_mgr.list_cashflow();
 
             match list_cashflow.list_event() {
                 None => {
-                    return Err(crate::ErrorType::Index);
+                    return Err(ElemError::new(crate::ErrorType::Index));
                 }
                 Some(o) => {
                     list_event = o.copy(true);
                 }
             }
 
-            let result = list_cashflow
-                .calculate()
-                .split_cashflow(&mut list_event, all_events);
+            let result =
+                list_cashflow
+                    .calculate()
+                    .split_cashflow(&calc_mgr, &mut list_event, all_events);
 
             match result {
                 Err(e) => {
@@ -1172,19 +1422,15 @@ impl CalcEngine {
             }
         }
 
-        let elem_balance_result: ElemBalanceResult = match self.balance_cashflow() {
-            Err(e) => return Err(e),
-            Ok(o) => o,
-        };
+        let elem_balance_result: ElemBalanceResult = self.balance_cashflow_ext(&mut calc_mgr)?;
 
         {
-            let mut calc_mgr = self.calc_mgr_mut();
             let list_cashflow = calc_mgr.list_cashflow_mut();
 
             list_cashflow.set_list_event(list_event);
         }
 
-        self.calc_mgr().set_updating_json(false);
+        calc_mgr.set_updating_json(false);
 
         Ok(elem_balance_result)
     }
@@ -1216,44 +1462,40 @@ impl CalcEngine {
         new_group_param: &str,
         after_pv: bool,
         omit_interest_events: bool,
-    ) -> Result<ElemBalanceResult, crate::ErrorType> {
+    ) -> Result<ElemBalanceResult, ElemError> {
+        let mut calc_mgr = self.calc_mgr_mut();
+
         let mut new_name: String = String::from(new_name_param);
         if new_name.is_empty() {
-            new_name = String::from(self.calc_mgr().list_locale().get_resource(crate::USER_NEW));
+            new_name = String::from(calc_mgr.list_locale().get_resource(crate::USER_NEW));
         }
 
         let mut new_group: String = String::from(new_group_param);
         let new_list_event: ListEvent;
 
-        self.calc_mgr().set_updating_json(true);
+        calc_mgr.set_updating_json(true);
 
         let mut elem_preferences_opt: Option<ElemPreferences> = None;
         if !new_group.is_empty()
-            && self
-                .calc_mgr()
+            && calc_mgr
                 .list_template_group()
                 .get_element_by_group(new_group.as_str(), true)
         {
-            elem_preferences_opt = Option::from(
-                self.calc_mgr()
-                    .list_template_group()
-                    .preferences()
-                    .copy(true),
-            );
+            elem_preferences_opt =
+                Option::from(calc_mgr.list_template_group().preferences().copy(true));
         }
 
         let elem_cashflow: ElemCashflow;
 
         {
-            let calc_mgr = self.calc_mgr();
             let list_cashflow = calc_mgr.list_cashflow();
 
             if !list_cashflow.cashflow_valid() {
-                return Err(crate::ErrorType::Cashflow);
+                return Err(ElemError::new(crate::ErrorType::Cashflow));
             }
 
             let list_am: &ListAmortization = match list_cashflow.list_amortization() {
-                None => return Err(crate::ErrorType::Index),
+                None => return Err(ElemError::new(crate::ErrorType::Index)),
                 Some(o) => o,
             };
 
@@ -1266,6 +1508,7 @@ impl CalcEngine {
             };
 
             match list_cashflow.calculate().transform_cashflow(
+                &calc_mgr,
                 &new_list_am_output,
                 after_pv,
                 omit_interest_events,
@@ -1281,7 +1524,7 @@ impl CalcEngine {
             }
 
             let preferences: &ElemPreferences = match list_cashflow.preferences() {
-                None => return Err(crate::ErrorType::Index),
+                None => return Err(ElemError::new(crate::ErrorType::Index)),
                 Some(o) => o,
             };
 
@@ -1341,13 +1584,14 @@ impl CalcEngine {
             }
 
             match list_cashflow.add_cashflow_prep(
+                &calc_mgr,
                 new_name.as_str(),
                 Option::from(new_list_event),
                 elem_preferences_opt,
                 new_group.as_str(),
             ) {
                 Err(_e) => {
-                    return Err(crate::ErrorType::Cashflow);
+                    return Err(ElemError::new(crate::ErrorType::Cashflow));
                 }
                 Ok(o) => {
                     elem_cashflow = o;
@@ -1356,34 +1600,24 @@ impl CalcEngine {
         }
 
         {
-            let mut calc_mgr = self.calc_mgr_mut();
             let list_cashflow = calc_mgr.list_cashflow_mut();
 
             list_cashflow.add_cashflow(elem_cashflow);
         }
 
-        {
-            let calc_mgr = self.calc_mgr();
-            let list_cashflow = calc_mgr.list_cashflow();
-
-            list_cashflow.get_element_by_name(new_name.as_str(), true);
-        }
+        calc_mgr.select_cashflow_by_name(new_name.as_str());
 
-        self.evaluate_cashflow_descriptors();
-        self.evaluate_cashflow_event_type_all();
+        self.evaluate_cashflow_descriptors_ext(&calc_mgr);
+        self.evaluate_cashflow_event_type_all_ext(&mut calc_mgr);
 
-        let elem_balance_result: ElemBalanceResult = match self.balance_cashflow() {
-            Err(e) => return Err(e),
-            Ok(o) => o,
-        };
+        let elem_balance_result: ElemBalanceResult = self.balance_cashflow_ext(&mut calc_mgr)?;
 
         {
-            let mut calc_mgr = self.calc_mgr_mut();
             let list_cashflow = calc_mgr.list_cashflow_mut();
 
             match list_cashflow.preferences_mut() {
                 None => {
-                    return Err(crate::ErrorType::Index);
+                    return Err(ElemError::new(crate::ErrorType::Index));
                 }
                 Some(o) => {
                     if !new_group.is_empty() {
@@ -1394,7 +1628,7 @@ impl CalcEngine {
 
             match list_cashflow.list_event() {
                 None => {
-                    return Err(crate::ErrorType::Index);
+                    return Err(ElemError::new(crate::ErrorType::Index));
                 }
                 Some(o) => {
                     o.set_index(0);
@@ -1402,7 +1636,7 @@ impl CalcEngine {
             }
         }
 
-        self.calc_mgr().set_updating_json(false);
+        calc_mgr.set_updating_json(false);
 
         Ok(elem_balance_result)
     }
@@ -1433,8 +1667,8 @@ impl CalcEngine {
         include_details: bool,
         compress_descriptor: bool,
         omit_statistic_events: bool,
-    ) -> Result<ListAmortization, crate::ErrorType> {
-        self.calc_mgr().list_cashflow().create_cashflow_output(
+    ) -> Result<ListAmortization, ElemError> {
+        self.calc_mgr_mut().list_cashflow().create_cashflow_output(
             include_rollups,
             include_details,
             compress_descriptor,
@@ -1461,25 +1695,26 @@ impl CalcEngine {
         group_param: &str,
         event_param: &str,
         cf_index: usize,
-    ) -> Result<ListEvent, crate::ErrorType> {
-        let mut event_date: usize = CoreUtility::date_now();
-        let mut end_date: usize = event_date;
-        let mut new_date: usize = event_date;
+    ) -> Result<ListEvent, ElemError> {
+        let mut calc_mgr = self.calc_mgr_mut();
+
+        let mut event_date = CoreUtility::date_now();
+        let mut end_date = event_date;
+        let mut new_date = event_date;
         let mut frequency = crate::FrequencyType::OneMonth;
 
         {
-            let calc_mgr = self.calc_mgr();
             let list_template_group = calc_mgr.list_template_group();
             let list_cashflow = calc_mgr.list_cashflow();
 
             if !list_template_group.get_element_by_group(group_param, true) {
-                return Err(crate::ErrorType::Index);
+                return Err(ElemError::new(crate::ErrorType::Index));
             }
 
             let list_template_event = list_template_group.list_template_event();
 
             if !list_template_event.get_element_by_name(event_param, true) {
-                return Err(crate::ErrorType::Index);
+                return Err(ElemError::new(crate::ErrorType::Index));
             }
 
             if list_template_event.list_event().get_element(0) {
@@ -1487,7 +1722,7 @@ impl CalcEngine {
             }
 
             if !list_cashflow.get_element(cf_index) {
-                return Err(crate::ErrorType::Index);
+                return Err(ElemError::new(crate::ErrorType::Index));
             }
 
             match list_cashflow.list_event() {
@@ -1523,14 +1758,11 @@ impl CalcEngine {
         }
 
         let list_event: ListEvent =
-            match self.copy_template_events(event_date, end_date, new_date, frequency) {
-                Err(e) => return Err(e),
-                Ok(o) => o,
-            };
+            self.copy_template_events(&mut calc_mgr, event_date, end_date, new_date, frequency)?;
 
-        self.evaluate_cashflow_event_type_all();
+        self.evaluate_cashflow_event_type_all_ext(&mut calc_mgr);
 
-        match self.balance_cashflow() {
+        match self.balance_cashflow_ext(&mut calc_mgr) {
             Err(e) => {
                 return Err(e);
             }
@@ -1557,22 +1789,22 @@ impl CalcEngine {
         group_param: &str,
         new_name_param: &str,
         new_group_param: &str,
-    ) -> Result<ElemBalanceResult, crate::ErrorType> {
+    ) -> Result<ElemBalanceResult, ElemError> {
+        let mut calc_mgr = self.calc_mgr_mut();
+
         let elem_preferences_opt: Option<ElemPreferences>;
         let group: String;
 
         let elem_cashflow: ElemCashflow;
 
         {
-            let calc_mgr = self.calc_mgr();
             let list_cashflow = calc_mgr.list_cashflow();
 
-            if !self
-                .calc_mgr()
+            if !calc_mgr
                 .list_template_group()
                 .get_element_by_group(group_param, true)
             {
-                return Err(crate::ErrorType::Index);
+                return Err(ElemError::new(crate::ErrorType::Index));
             }
 
             group = String::from(calc_mgr.list_template_group().group());
@@ -1581,13 +1813,14 @@ impl CalcEngine {
                 Option::from(calc_mgr.list_template_group().preferences().copy(true));
 
             match list_cashflow.add_cashflow_prep(
+                &calc_mgr,
                 new_name_param,
                 None,
                 elem_preferences_opt,
                 group.as_str(),
             ) {
                 Err(_e) => {
-                    return Err(crate::ErrorType::Cashflow);
+                    return Err(ElemError::new(crate::ErrorType::Cashflow));
                 }
                 Ok(o) => {
                     elem_cashflow = o;
@@ -1596,28 +1829,18 @@ impl CalcEngine {
         }
 
         {
-            let mut calc_mgr = self.calc_mgr_mut();
             let list_cashflow = calc_mgr.list_cashflow_mut();
 
             list_cashflow.add_cashflow(elem_cashflow);
         }
 
-        {
-            let calc_mgr = self.calc_mgr();
-            let list_cashflow = calc_mgr.list_cashflow();
-
-            list_cashflow.get_element_by_name(new_name_param, true);
-        }
+        calc_mgr.select_cashflow_by_name(new_name_param);
 
-        self.evaluate_cashflow_descriptors();
+        self.evaluate_cashflow_descriptors_ext(&calc_mgr);
 
-        let elem_balance_result: ElemBalanceResult = match self.balance_cashflow() {
-            Err(e) => return Err(e),
-            Ok(o) => o,
-        };
+        let elem_balance_result: ElemBalanceResult = self.balance_cashflow_ext(&mut calc_mgr)?;
 
         {
-            let mut calc_mgr = self.calc_mgr_mut();
             let list_cashflow = calc_mgr.list_cashflow_mut();
 
             if !new_group_param.is_empty() {
@@ -1640,6 +1863,573 @@ impl CalcEngine {
         Ok(elem_balance_result)
     }
 
+    /// Creates a new cashflow with a graduated payment schedule and
+    /// solves for the initial payment that amortizes the principal
+    /// to zero. The payment increases by a fixed percentage every
+    /// step periods for the number of steps and the final payment
+    /// continues for the remaining term.
+    ///
+    /// # Arguments
+    ///
+    /// * `name_param` - The name of the new cashflow.
+    /// * `principal` - The principal of the loan.
+    /// * `int_rate` - The nominal annual interest rate expressed as a percentage.
+    /// * `start_date` - The date of the loan (the first payment is one period later).
+    /// * `frequency` - The payment and compounding frequency.
+    /// * `periods` - The total number of payments.
+    /// * `step_periods` - The number of payments between increases.
+    /// * `step_count` - The number of increases.
+    /// * `step_change` - The payment increase expressed as a percentage.
+    ///
+    /// # Return
+    ///
+    /// * The results of calculating the initial payment if
+    ///   successful, otherwise an error code.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_graduated_payments(
+        &self,
+        name_param: &str,
+        principal: Decimal,
+        int_rate: Decimal,
+        start_date: NaiveDate,
+        frequency: crate::FrequencyType,
+        periods: usize,
+        step_periods: usize,
+        step_count: usize,
+        step_change: Decimal,
+    ) -> Result<ElemBalanceResult, ElemError> {
+        if (step_count > 0 && step_periods == 0) || step_periods * step_count >= periods {
+            return Err(ElemError::new(crate::ErrorType::CalcPeriods));
+        }
+
+        let mut steps: Vec<ElemPaymentStep> = Vec::new();
+        for _index in 0..step_count {
+            steps.push(ElemPaymentStep::new(step_periods, step_change));
+        }
+        steps.push(ElemPaymentStep::new(
+            periods - step_periods * step_count,
+            step_change,
+        ));
+
+        self.create_stepped_payments(
+            name_param, principal, int_rate, start_date, frequency, &steps,
+        )
+    }
+
+    /// Creates a new cashflow with a stepped payment schedule and
+    /// solves for the initial payment that amortizes the principal
+    /// to zero. The first step is the solved payment and the value
+    /// expression of each following step is linked to the last
+    /// payment of the previous step. The solved payment is rounded
+    /// to the currency decimal digits and the rounding residual is
+    /// settled in the final payment.
+    ///
+    /// # Arguments
+    ///
+    /// * `name_param` - The name of the new cashflow.
+    /// * `principal` - The principal of the loan.
+    /// * `int_rate` - The nominal annual interest rate expressed as a percentage.
+    /// * `start_date` - The date of the loan (the first payment is one period later).
+    /// * `frequency` - The payment and compounding frequency.
+    /// * `steps` - The payment steps in date order.
+    ///
+    /// # Return
+    ///
+    /// * The results of calculating the initial payment if
+    ///   successful, otherwise an error code.
+    pub fn create_stepped_payments(
+        &self,
+        name_param: &str,
+        principal: Decimal,
+        int_rate: Decimal,
+        start_date: NaiveDate,
+        frequency: crate::FrequencyType,
+        steps: &[ElemPaymentStep],
+    ) -> Result<ElemBalanceResult, ElemError> {
+        if steps.is_empty() || steps.iter().any(|o| o.periods() == 0) {
+            return Err(ElemError::new(crate::ErrorType::CalcPeriods));
+        }
+
+        let mut calc_mgr = self.calc_mgr_mut();
+        let payment_date = CoreUtility::date_new(start_date, start_date, frequency, 1, false);
+        let mut list_event = CalcEngine::loan_events(principal, int_rate, start_date, frequency);
+
+        let mut event_date = payment_date;
+        let mut final_date = payment_date;
+        for (index, step) in steps.iter().enumerate() {
+            let value_expr = if index == 0 {
+                String::from("")
+            } else {
+                // The first payment of the step changes the last payment of the previous step
+                format!(
+                    "round(am(\"PrinPrev\", \"Value\") * if(intSequence = 0, {}, 1.0))",
+                    dec!(1.0) + step.change() / dec!(100.0)
+                )
+            };
+
+            // The final payment is a separate event that settles the rounding residual
+            let mut periods = step.periods();
+            if index == steps.len() - 1 && periods > 1 {
+                periods -= 1;
+            }
+
+            CalcEngine::add_payment_event(
+                &mut list_event,
+                event_date,
+                dec!(0.0),
+                value_expr.as_str(),
+                periods,
+                frequency,
+            );
+
+            final_date =
+                CoreUtility::date_new(payment_date, event_date, frequency, periods - 1, false);
+            event_date = CoreUtility::date_new(payment_date, event_date, frequency, periods, false);
+
+            if periods < step.periods() {
+                CalcEngine::add_payment_event(
+                    &mut list_event,
+                    event_date,
+                    dec!(0.0),
+                    "round(am(\"PrinPrev\", \"Value\"))",
+                    1,
+                    frequency,
+                );
+                final_date = event_date;
+            }
+        }
+
+        let elem_balance_result =
+            self.solve_generated_cashflow(&mut calc_mgr, name_param, list_event, payment_date)?;
+
+        self.settle_generated_cashflow(
+            &mut calc_mgr,
+            &elem_balance_result,
+            payment_date,
+            final_date,
+        )
+    }
+
+    /// Round the solved payment of a generated cashflow to the
+    /// currency decimal digits and settle the rounding residual
+    /// in the final payment so that the cashflow amortizes to zero.
+    ///
+    /// # Arguments
+    ///
+    /// * `calc_mgr` - Calculation manager.
+    /// * `elem_balance_result` - The results of solving the payment.
+    /// * `solve_date` - The date of the solved payment event.
+    /// * `final_date` - The date of the final payment event.
+    ///
+    /// # Return
+    ///
+    /// * The results of balancing the cashflow if successful,
+    ///   otherwise an error code.
+    fn settle_generated_cashflow(
+        &self,
+        calc_mgr: &mut CalcManager,
+        elem_balance_result: &ElemBalanceResult,
+        solve_date: NaiveDate,
+        final_date: NaiveDate,
+    ) -> Result<ElemBalanceResult, ElemError> {
+        let decimal_digits = calc_mgr.decimal_digits(false);
+        let payment = CoreUtility::util_round(elem_balance_result.result_decimal(), decimal_digits);
+
+        {
+            match calc_mgr.list_cashflow().list_event() {
+                None => return Err(ElemError::new(crate::ErrorType::Index)),
+                Some(o) => {
+                    if !o.get_element_by_date(solve_date, crate::DEFAULT_SORT_PRINCIPAL_CHANGE) {
+                        return Err(ElemError::new(crate::ErrorType::Index));
+                    }
+                    o.set_value(payment);
+                }
+            }
+        }
+
+        let residual = match self.balance_cashflow_ext(calc_mgr) {
+            Err(e) => return Err(e),
+            Ok(o) => {
+                o.balance()
+                    + if o.balance() < dec!(0.0) {
+                        -o.acc_balance()
+                    } else {
+                        o.acc_balance()
+                    }
+            }
+        };
+
+        let mut final_payment = dec!(0.0);
+        {
+            match calc_mgr.list_cashflow().list_amortization() {
+                None => return Err(ElemError::new(crate::ErrorType::Index)),
+                Some(o) => {
+                    let mut index: usize = 0;
+                    while o.get_element(index) {
+                        if o.elem_type() == crate::ExtensionType::PrincipalChange
+                            && o.event_date() == final_date
+                        {
+                            final_payment = o.value();
+                        }
+                        index += 1;
+                    }
+                }
+            }
+        }
+
+        final_payment = CoreUtility::util_round(final_payment + residual, decimal_digits);
+
+        {
+            match calc_mgr.list_cashflow_mut().list_event_mut() {
+                None => return Err(ElemError::new(crate::ErrorType::Index)),
+                Some(o) => {
+                    if !o.get_element_by_date(final_date, crate::DEFAULT_SORT_PRINCIPAL_CHANGE) {
+                        return Err(ElemError::new(crate::ErrorType::Index));
+                    }
+                    o.set_value_expr("");
+                    o.set_value_expr_balance(false);
+                    o.set_value(final_payment);
+                }
+            }
+        }
+
+        let elem_balance_result_new = self.balance_cashflow_ext(calc_mgr)?;
+
+        let balance = CoreUtility::util_round(
+            elem_balance_result_new.balance()
+                + if elem_balance_result_new.balance() < dec!(0.0) {
+                    -elem_balance_result_new.acc_balance()
+                } else {
+                    elem_balance_result_new.acc_balance()
+                },
+            decimal_digits,
+        );
+        if balance != dec!(0.0) {
+            return Err(ElemError::new(crate::ErrorType::CalcPrincipal));
+        }
+
+        elem_balance_result_new.set_result_decimal(payment);
+        elem_balance_result_new.set_iterations(elem_balance_result.iterations());
+
+        Ok(elem_balance_result_new)
+    }
+
+    /// Creates a new cashflow with an interest-only, amortizing,
+    /// balloon or bullet loan structure and solves for the balloon
+    /// payment at maturity. Interest-only payments are followed by
+    /// level payments that amortize the principal over the
+    /// amortization term (which may exceed the loan term) and the
+    /// balance remaining at maturity is paid by the balloon payment.
+    /// If the amortization term is zero, no amortizing payments are
+    /// made (i.e., a bullet loan if there is no interest-only period).
+    /// The interest-only and amortizing payments must end before
+    /// maturity and the amortization term cannot be shorter than the
+    /// periods remaining after the interest-only period.
+    ///
+    /// # Arguments
+    ///
+    /// * `name_param` - The name of the new cashflow.
+    /// * `principal` - The principal of the loan.
+    /// * `int_rate` - The nominal annual interest rate expressed as a percentage.
+    /// * `start_date` - The date of the loan (the first payment is one period later).
+    /// * `frequency` - The payment and compounding frequency.
+    /// * `io_periods` - The number of interest-only payments.
+    /// * `amortization_periods` - The amortization term in periods (or zero).
+    /// * `maturity_periods` - The loan term in periods (the balloon is the last payment).
+    ///
+    /// # Return
+    ///
+    /// * The results of calculating the balloon payment if
+    ///   successful, otherwise an error code.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_loan_structure(
+        &self,
+        name_param: &str,
+        principal: Decimal,
+        int_rate: Decimal,
+        start_date: NaiveDate,
+        frequency: crate::FrequencyType,
+        io_periods: usize,
+        amortization_periods: usize,
+        maturity_periods: usize,
+    ) -> Result<ElemBalanceResult, ElemError> {
+        // The last payment is the balloon, so the interest-only and
+        // amortizing payments must end before maturity
+        if io_periods >= maturity_periods
+            || (amortization_periods > 0
+                && (io_periods + 1 == maturity_periods
+                    || amortization_periods < maturity_periods - io_periods))
+        {
+            return Err(ElemError::new(crate::ErrorType::CalcPeriods));
+        }
+
+        let mut calc_mgr = self.calc_mgr_mut();
+        let payment_date = CoreUtility::date_new(start_date, start_date, frequency, 1, false);
+        let mut list_event = CalcEngine::loan_events(principal, int_rate, start_date, frequency);
+
+        let mut event_date = payment_date;
+        let periods = maturity_periods - io_periods - 1;
+
+        if io_periods > 0 {
+            CalcEngine::add_payment_event(
+                &mut list_event,
+                event_date,
+                dec!(0.0),
+                "round(decInterest)",
+                io_periods,
+                frequency,
+            );
+            event_date =
+                CoreUtility::date_new(payment_date, event_date, frequency, io_periods, false);
+        }
+
+        if amortization_periods > 0 {
+            let periodic_rate = CoreUtility::rate_nar_to_pr(
+                int_rate / dec!(100.0),
+                frequency,
+                crate::DEFAULT_DAYS_IN_YEAR,
+            );
+            let payment = match CoreFinancial::pmt(
+                periodic_rate,
+                dec!(amortization_periods),
+                -principal,
+                dec!(0.0),
+                false,
+            ) {
+                Err(e) => return Err(e),
+                Ok(o) => CoreUtility::util_round(o, calc_mgr.decimal_digits(false)),
+            };
+
+            CalcEngine::add_payment_event(
+                &mut list_event,
+                event_date,
+                payment,
+                "",
+                periods,
+                frequency,
+            );
+        }
+
+        if periods > 0 {
+            event_date = CoreUtility::date_new(payment_date, event_date, frequency, periods, false);
+        }
+
+        CalcEngine::add_payment_event(&mut list_event, event_date, dec!(0.0), "", 1, frequency);
+
+        let elem_balance_result =
+            self.solve_generated_cashflow(&mut calc_mgr, name_param, list_event, event_date)?;
+
+        let balance = CoreUtility::util_round(
+            elem_balance_result.balance()
+                + if elem_balance_result.balance() < dec!(0.0) {
+                    -elem_balance_result.acc_balance()
+                } else {
+                    elem_balance_result.acc_balance()
+                },
+            calc_mgr.decimal_digits(false),
+        );
+        if balance != dec!(0.0) {
+            return Err(ElemError::new(crate::ErrorType::CalcPrincipal));
+        }
+
+        Ok(elem_balance_result)
+    }
+
+    /// Creates and returns a new event list containing the
+    /// principal and interest change events of a generated loan.
+    ///
+    /// # Arguments
+    ///
+    /// * `principal` - The principal of the loan.
+    /// * `int_rate` - The nominal annual interest rate expressed as a percentage.
+    /// * `start_date` - The date of the loan.
+    /// * `frequency` - The compounding frequency.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn loan_events(
+        principal: Decimal,
+        int_rate: Decimal,
+        start_date: NaiveDate,
+        frequency: crate::FrequencyType,
+    ) -> ListEvent {
+        let mut list_event = ListEvent::new(true);
+
+        list_event.add_event(
+            start_date,
+            "",
+            crate::DEFAULT_SORT_PRINCIPAL_CHANGE,
+            principal,
+            "",
+            false,
+            1,
+            "",
+            0,
+            0,
+            1,
+            frequency,
+            ElemExtension::new_principal_change(ElemPrincipalChange::new(
+                crate::PrincipalType::Increase,
+                false,
+                false,
+                false,
+                false,
+                false,
+            )),
+            None,
+            None,
+            "",
+            "",
+        );
+
+        list_event.add_event(
+            start_date,
+            "",
+            crate::DEFAULT_SORT_INTEREST_CHANGE,
+            int_rate,
+            "",
+            false,
+            1,
+            "",
+            0,
+            0,
+            1,
+            frequency,
+            ElemExtension::new_interest_change(ElemInterestChange::new(
+                crate::MethodType::Actuarial,
+                crate::DayCountType::Periodic,
+                crate::DEFAULT_DAYS_IN_YEAR,
+                crate::FrequencyType::None,
+                crate::FrequencyType::None,
+                crate::RoundType::None,
+                dec!(0.0),
+            )),
+            None,
+            None,
+            "",
+            "",
+        );
+
+        list_event
+    }
+
+    /// Add a series of payments to the event list of a generated loan.
+    /// A value expression is evaluated when the cashflow is balanced.
+    ///
+    /// # Arguments
+    ///
+    /// * `list_event` - The event list of the generated loan.
+    /// * `event_date` - The date of the first payment.
+    /// * `value` - The payment.
+    /// * `value_expr` - The payment value expression (or empty).
+    /// * `periods` - The number of payments.
+    /// * `frequency` - The payment frequency.
+    fn add_payment_event(
+        list_event: &mut ListEvent,
+        event_date: NaiveDate,
+        value: Decimal,
+        value_expr: &str,
+        periods: usize,
+        frequency: crate::FrequencyType,
+    ) {
+        list_event.add_event(
+            event_date,
+            "",
+            crate::DEFAULT_SORT_PRINCIPAL_CHANGE,
+            value,
+            value_expr,
+            !value_expr.is_empty(),
+            periods,
+            "",
+            0,
+            0,
+            1,
+            frequency,
+            ElemExtension::new_principal_change(ElemPrincipalChange::new(
+                crate::PrincipalType::Decrease,
+                false,
+                false,
+                true,
+                false,
+                false,
+            )),
+            None,
+            None,
+            "",
+            "",
+        );
+    }
+
+    /// Add the event list of a generated loan as a new cashflow,
+    /// calculate the value of the payment event on the solve date
+    /// and balance the cashflow with the calculated value.
+    ///
+    /// # Arguments
+    ///
+    /// * `calc_mgr` - Calculation manager.
+    /// * `name_param` - The name of the new cashflow.
+    /// * `list_event` - The event list of the generated loan.
+    /// * `solve_date` - The date of the payment event to calculate.
+    ///
+    /// # Return
+    ///
+    /// * The results of the calculation if successful, otherwise an error code.
+    fn solve_generated_cashflow(
+        &self,
+        calc_mgr: &mut CalcManager,
+        name_param: &str,
+        list_event: ListEvent,
+        solve_date: NaiveDate,
+    ) -> Result<ElemBalanceResult, ElemError> {
+        let elem_cashflow: ElemCashflow = match calc_mgr.list_cashflow().add_cashflow_prep(
+            calc_mgr,
+            name_param,
+            Option::from(list_event),
+            None,
+            "",
+        ) {
+            Err(_e) => {
+                return Err(ElemError::new(crate::ErrorType::Cashflow));
+            }
+            Ok(o) => o,
+        };
+
+        calc_mgr.list_cashflow_mut().add_cashflow(elem_cashflow);
+
+        if !calc_mgr.select_cashflow_by_name(name_param) {
+            return Err(ElemError::new(crate::ErrorType::Cashflow));
+        }
+
+        match self.balance_cashflow_ext(calc_mgr) {
+            Err(e) => return Err(e),
+            Ok(_o) => {}
+        }
+
+        {
+            match calc_mgr.list_cashflow().list_event() {
+                None => return Err(ElemError::new(crate::ErrorType::Index)),
+                Some(o) => {
+                    if !o.get_element_by_date(solve_date, crate::DEFAULT_SORT_PRINCIPAL_CHANGE) {
+                        return Err(ElemError::new(crate::ErrorType::Index));
+                    }
+                }
+            }
+        }
+
+        let elem_balance_result = self.calculate_value_ext(calc_mgr, &ElemSolverSettings::new())?;
+
+        // Balance the cashflow with the calculated value
+        match self.balance_cashflow_ext(calc_mgr) {
+            Err(e) => Err(e),
+            Ok(o) => {
+                o.set_result_decimal(elem_balance_result.result_decimal());
+                o.set_iterations(elem_balance_result.iterations());
+                Ok(o)
+            }
+        }
+    }
+
     /// Creates and returns the statistics for the
     /// currently selected cashflow.
     ///
@@ -1648,7 +2438,7 @@ impl CalcEngine {
     /// * See description.
 
     pub fn create_cashflow_stats(&self) -> ElemCashflowStats {
-        self.calc_mgr().list_cashflow().create_cashflow_stats()
+        self.calc_mgr_mut().list_cashflow().create_cashflow_stats()
     }
 
     /// Evaluate all of the descriptors in the user preferences.
@@ -1656,21 +2446,28 @@ impl CalcEngine {
     /// execute the expression using the list of parameters.
 
     pub fn evaluate_user_descriptors(&self) {
-        let calc_mgr = self.calc_mgr();
+        self.evaluate_user_descriptors_ext(&self.calc_mgr_mut());
+    }
 
-        let calc_expression = CalcExpression::new(
-            self.calc_manager(),
+    /// Evaluate all of the descriptors in the user preferences
+    /// (see evaluate_user_descriptors).
+    ///
+    /// # Arguments
+    ///
+    /// * `calc_mgr` - Calculation manager.
+    fn evaluate_user_descriptors_ext(&self, calc_mgr: &CalcManager) {
+        let mut calc_expression = CalcExpression::new(
+            calc_mgr,
             calc_mgr.fiscal_year_start(false),
             calc_mgr.decimal_digits(false),
         );
-        let expression = RefCell::new(calc_expression);
 
         let list_parameter = calc_mgr.preferences().list_parameter();
         let list_descriptor = calc_mgr.preferences().list_descriptor();
 
         CalcUtility::evaluate_descriptors(
-            self.calc_manager(),
-            &expression,
+            calc_mgr,
+            &mut calc_expression,
             list_parameter,
             list_descriptor,
         );
@@ -1681,10 +2478,18 @@ impl CalcEngine {
     /// execute the expression using the list of parameters.
 
     pub fn evaluate_cashflow_descriptors(&self) {
-        let calc_mgr = self.calc_mgr();
+        self.evaluate_cashflow_descriptors_ext(&self.calc_mgr_mut());
+    }
 
-        let calc_expression = CalcExpression::new(
-            self.calc_manager(),
+    /// Evaluate all of the descriptors in the selected cashflow
+    /// (see evaluate_cashflow_descriptors).
+    ///
+    /// # Arguments
+    ///
+    /// * `calc_mgr` - Calculation manager.
+    fn evaluate_cashflow_descriptors_ext(&self, calc_mgr: &CalcManager) {
+        let mut calc_expression = CalcExpression::new(
+            calc_mgr,
             calc_mgr.fiscal_year_start(false),
             calc_mgr.decimal_digits(false),
         );
@@ -1695,12 +2500,11 @@ impl CalcEngine {
             }
             Some(o) => o,
         };
-
-        let expression = RefCell::new(calc_expression);
+        calc_expression.init_list_function(Option::from(preferences.list_function()));
 
         CalcUtility::evaluate_descriptors(
-            self.calc_manager(),
-            &expression,
+            calc_mgr,
+            &mut calc_expression,
             preferences.list_parameter(),
             preferences.list_descriptor(),
         );
@@ -1709,11 +2513,20 @@ impl CalcEngine {
     /// Evaluate the event type expression for all events in the selected cashflow.
 
     pub fn evaluate_cashflow_event_type_all(&self) {
-        let mut list_result_symbol: Vec<Result<ElemSymbol, crate::ErrorType>> = Vec::new();
+        self.evaluate_cashflow_event_type_all_ext(&mut self.calc_mgr_mut());
+    }
+
+    /// Evaluate the event type expression for all events in the
+    /// selected cashflow (see evaluate_cashflow_event_type_all).
+    ///
+    /// # Arguments
+    ///
+    /// * `calc_mgr` - Calculation manager.
+    fn evaluate_cashflow_event_type_all_ext(&self, calc_mgr: &mut CalcManager) {
+        let mut list_result_symbol: Vec<Result<ElemSymbol, ElemError>> = Vec::new();
         let list_event: &ListEvent;
 
         {
-            let calc_mgr = self.calc_mgr();
             match calc_mgr.list_cashflow().list_event() {
                 None => {
                     panic!("Cashflow list index not set");
@@ -1768,28 +2581,28 @@ impl CalcEngine {
                         match list_event.elem_type() {
                             crate::ExtensionType::CurrentValue => {
                                 event_type_expr = String::from(
-                                    self.calc_mgr()
+                                    calc_mgr
                                         .list_locale()
                                         .get_resource(crate::USER_EVENT_TYPE_CURRENT_VALUE),
                                 );
                             }
                             crate::ExtensionType::StatisticValue => {
                                 event_type_expr = String::from(
-                                    self.calc_mgr()
+                                    calc_mgr
                                         .list_locale()
                                         .get_resource(crate::USER_EVENT_TYPE_STATISTIC_VALUE),
                                 );
                             }
                             crate::ExtensionType::InterestChange => {
                                 event_type_expr = String::from(
-                                    self.calc_mgr()
+                                    calc_mgr
                                         .list_locale()
                                         .get_resource(crate::USER_EVENT_TYPE_INTEREST_CHANGE),
                                 );
                             }
                             _ => {
                                 event_type_expr = String::from(
-                                    self.calc_mgr()
+                                    calc_mgr
                                         .list_locale()
                                         .get_resource(crate::USER_EVENT_TYPE_PRINCIPAL_CHANGE),
                                 );
@@ -1799,7 +2612,7 @@ impl CalcEngine {
                 }
 
                 let mut core_expression = CalcExpression::new(
-                    self.calc_manager(),
+                    calc_mgr,
                     calc_mgr.fiscal_year_start(true),
                     calc_mgr.decimal_digits(true),
                 );
@@ -1807,7 +2620,7 @@ impl CalcEngine {
                 let preferences_cashflow = calc_mgr.list_cashflow().preferences();
 
                 let list_parameter = CalcUtility::create_event_type_list_parameter(
-                    self.calc_manager(),
+                    calc_mgr,
                     list_event.elem_type(),
                     list_event.elem_extension(),
                 );
@@ -1816,6 +2629,7 @@ impl CalcEngine {
                         panic!("Missing cashflow preferences");
                     }
                     Some(o) => {
+                        core_expression.init_list_function(Option::from(o.list_function()));
                         core_expression.init_expression(
                             Option::from(o.list_descriptor()),
                             None,
@@ -1833,10 +2647,9 @@ impl CalcEngine {
             }
         }
 
-        let mut errs: HashMap<usize, crate::ErrorType> = HashMap::new();
+        let mut errs: HashMap<usize, ElemError> = HashMap::new();
 
         {
-            let mut calc_mgr = self.calc_mgr_mut();
             match calc_mgr.list_cashflow_mut().list_event_mut() {
                 None => {
                     panic!("Cashflow list index not set");
@@ -1853,12 +2666,12 @@ impl CalcEngine {
                             None => {}
                             Some(o) => match o {
                                 Err(e) => {
-                                    errs.insert(index, *e);
+                                    errs.insert(index, e.clone());
                                 }
                                 Ok(o2) => match o2.sym_type() {
                                     crate::TokenType::Integer => {
                                         list_event.set_event_type(
-                                            format!("{}", o2.sym_integer()).as_str(),
+                                            format!("{}", o2.sym_integeri()).as_str(),
                                         );
                                     }
                                     crate::TokenType::Decimal => {
@@ -1882,12 +2695,11 @@ impl CalcEngine {
         let mut errors: HashMap<usize, String> = HashMap::new();
 
         for (index, err) in errs.iter_mut() {
-            let error_string = self.calc_mgr_mut().get_error_string(*err);
+            let error_string = calc_mgr.get_error_string(err.kind());
             errors.insert(*index, error_string);
         }
 
         {
-            let mut calc_mgr = self.calc_mgr_mut();
             match calc_mgr.list_cashflow_mut().list_event_mut() {
                 None => {
                     panic!("Cashflow list index not set");
@@ -1914,11 +2726,42 @@ impl CalcEngine {
         }
     }
 
+    /// Check the expression without evaluating it (e.g., before
+    /// saving a template group). The type of every sub-expression
+    /// is inferred and the symbols and descriptors referenced are
+    /// resolved. Errors include the position within the expression.
+    ///
+    /// # Arguments
+    ///
+    /// * `list_descriptor` - Event descriptors in scope (or None).
+    /// * `list_parameter` - List of parameters in scope (or None).
+    /// * `expression_str` - The expression to check.
+    /// * `cashflow` - Search the cashflow preferences.
+    ///
+    /// # Return
+    ///
+    /// * Type of the expression result (Unknown if only known when
+    ///   evaluated) if successful, otherwise error code.
+    pub fn check_expression(
+        &self,
+        list_descriptor: Option<&ListDescriptor>,
+        list_parameter: Option<&ListParameter>,
+        expression_str: &str,
+        cashflow: bool,
+    ) -> Result<crate::TokenType, ElemError> {
+        CalcUtility::check_expression(
+            &self.calc_mgr(),
+            list_descriptor,
+            list_parameter,
+            expression_str,
+            cashflow,
+        )
+    }
+
     /// Evaluate the expression.
     ///
     /// # Arguments
     ///
-    /// * `calc_manager_param` - Calculation manager.
     /// * `list_parameter` - List of parameters used with evaluation.
     /// * `expression_str` - The expression to evaluate.
     /// * `cashflow` - Search the cashflow preferences.
@@ -1934,14 +2777,83 @@ impl CalcEngine {
         expression_str: &str,
         cashflow: bool,
     ) -> ElemSymbol {
-        CalcUtility::evaluate_expression(
-            self.calc_manager(),
+        CalcUtility::evaluate_expression(&self.calc_mgr(), list_parameter, expression_str, cashflow)
+    }
+
+    /// Evaluate the expression and record an evaluation trace.
+    /// The trace is a tree of the executed nodes (constants, symbol
+    /// lookups, operators and function calls with their arguments)
+    /// with the result of each node, and shows, for example, which
+    /// branch of the if function was taken and why.
+    ///
+    /// # Arguments
+    ///
+    /// * `list_parameter` - List of parameters used with evaluation.
+    /// * `expression_str` - The expression to evaluate.
+    /// * `cashflow` - Search the cashflow preferences.
+    ///
+    /// # Return
+    ///
+    /// * Resulting symbol if successful, otherwise an error
+    ///   message in the symbol, and the evaluation trace
+    ///   (None if the expression could not be compiled).
+    pub fn evaluate_expression_trace(
+        &self,
+        list_parameter: Option<&ListParameter>,
+        expression_str: &str,
+        cashflow: bool,
+    ) -> (ElemSymbol, Option<ElemExprTrace>) {
+        CalcUtility::evaluate_expression_trace(
+            &self.calc_mgr(),
             list_parameter,
             expression_str,
             cashflow,
         )
     }
 
+    /// Register a native Rust function that can be called from
+    /// expressions like a built-in function. The function receives
+    /// the evaluated arguments. Native functions are searched after
+    /// the user-defined functions of the preferences and remain
+    /// registered when the engine is cleared.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The function name.
+    /// * `native_function` - The native function.
+    pub fn register_function<F>(&self, name: &str, native_function: F)
+    where
+        F: Fn(&[ElemSymbol]) -> Result<ElemSymbol, ElemError> + Send + Sync + 'static,
+    {
+        self.calc_mgr()
+            .register_function(name, Arc::new(native_function));
+    }
+
+    /// Set the limits enforced when evaluating expressions (e.g.,
+    /// expressions authored by untrusted users). Expressions that
+    /// exceed a limit fail with the Limit error. The limits remain
+    /// set when the engine is cleared.
+    ///
+    /// # Arguments
+    ///
+    /// * `expr_limits` - The expression limits.
+    pub fn set_expr_limits(&self, expr_limits: ElemExprLimits) {
+        self.calc_mgr_mut().set_expr_limits(expr_limits);
+    }
+
+    /// Remove a native function registered with register_function.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The function name.
+    ///
+    /// # Return
+    ///
+    /// * True if the function was registered, otherwise false.
+    pub fn unregister_function(&self, name: &str) -> bool {
+        self.calc_mgr().unregister_function(name)
+    }
+
     /// Format a date and return the internal format.
     ///
     /// # Arguments
@@ -2018,13 +2930,13 @@ impl CalcEngine {
     ///
     /// # Arguments
     ///
-    /// * `val` - The usize date value to format.
+    /// * `val` - The date value to format.
     ///
     /// # Return
     ///
     /// * See description.
 
-    pub fn format_date_out(&self, val: usize) -> String {
+    pub fn format_date_out(&self, val: NaiveDate) -> String {
         let calc_mgr = self.calc_mgr();
 
         let fs = calc_mgr.list_locale().format_date_out(val);
@@ -2146,7 +3058,7 @@ impl CalcEngine {
     /// * See description.
 
     pub fn am_value(&self, elem_column: &ElemColumn, list_am_opt: &ListAmortization) -> String {
-        CalcUtility::get_am_value(self.calc_manager(), elem_column, list_am_opt)
+        CalcUtility::get_am_value(&self.calc_mgr_mut(), elem_column, list_am_opt)
     }
 
     /// Get the appropriate event list value as a string.
@@ -2160,7 +3072,7 @@ impl CalcEngine {
     /// * See description.
 
     pub fn event_value(&self, elem_column: &ElemColumn) -> String {
-        CalcUtility::get_event_value(self.calc_manager(), elem_column)
+        CalcUtility::get_event_value(&self.calc_mgr_mut(), elem_column)
     }
 
     /// Create and return a column list object.
@@ -2174,7 +3086,7 @@ impl CalcEngine {
     /// * See description.
 
     pub fn parse_columns(&self, event_type: crate::TableType) -> ListColumn {
-        CalcUtility::parse_columns(self.calc_manager(), event_type, true)
+        CalcUtility::parse_columns(&self.calc_mgr(), event_type, true)
     }
 
     /// Create and return a summary list object.
@@ -2184,7 +3096,7 @@ impl CalcEngine {
     /// * See description.
 
     pub fn parse_summary(&self) -> ListSummary {
-        CalcUtility::parse_summary(self.calc_manager())
+        CalcUtility::parse_summary(&self.calc_mgr_mut())
     }
 
     /// Set the appropriate event list value and
@@ -2211,7 +3123,7 @@ impl CalcEngine {
         value_param: &str,
     ) -> String {
         CalcUtility::set_event_value(
-            self.calc_manager(),
+            &mut self.calc_mgr_mut(),
             col_name_index,
             col_type,
             col_code,
@@ -2232,7 +3144,7 @@ impl CalcEngine {
     /// * True if successful, otherwise false.
 
     pub fn set_extension_values(&self, index: usize, ext_param: &ElemExtension) -> bool {
-        CalcUtility::set_extension_values(self.calc_manager(), index, ext_param)
+        CalcUtility::set_extension_values(&mut self.calc_mgr_mut(), index, ext_param)
     }
 
     /// Set the appropriate event list parameter values.
@@ -2247,6 +3159,63 @@ impl CalcEngine {
     /// * True if successful, otherwise false.
 
     pub fn set_parameter_values(&self, index_param: usize, parameters: Vec<String>) -> bool {
-        CalcUtility::set_parameter_values(self.calc_manager(), index_param, parameters)
+        CalcUtility::set_parameter_values(&mut self.calc_mgr_mut(), index_param, parameters)
+    }
+
+    /// Set the prepayment model applied when the selected cashflow
+    /// is balanced. Each payment is scaled to the surviving balance
+    /// and the balance following the payment is reduced by the
+    /// prepayment rate for the period. Scheduled principal, prepaid
+    /// principal and surviving balance are recorded in the amortization
+    /// list and totaled in the balance result.
+    ///
+    /// # Arguments
+    ///
+    /// * `prepayment` - Prepayment model (None for no prepayments).
+    ///
+    /// # Return
+    ///
+    /// * True if successful, otherwise false.
+    pub fn set_prepayment(&self, prepayment: Option<ElemPrepayment>) -> bool {
+        let mut calc_mgr = self.calc_mgr_mut();
+        let list_cashflow = calc_mgr.list_cashflow_mut();
+
+        if !list_cashflow.set_cashflow_valid(false) {
+            return false;
+        }
+
+        list_cashflow.calculate().set_prepayment(prepayment);
+
+        true
+    }
+
+    /// Set the default scenario applied when the selected cashflow
+    /// is balanced. Each period the default rate is applied to the
+    /// performing balance and the defaulted principal enters the
+    /// delinquency pipeline. Defaulted principal is liquidated after
+    /// the recovery lag and split into the recovery and the loss.
+    /// The values are recorded in the amortization list and totaled
+    /// in the balance result.
+    ///
+    /// # Arguments
+    ///
+    /// * `default_scenario` - Default scenario (None for no defaults).
+    ///
+    /// # Return
+    ///
+    /// * True if successful, otherwise false.
+    pub fn set_default_scenario(&self, default_scenario: Option<ElemDefaultScenario>) -> bool {
+        let mut calc_mgr = self.calc_mgr_mut();
+        let list_cashflow = calc_mgr.list_cashflow_mut();
+
+        if !list_cashflow.set_cashflow_valid(false) {
+            return false;
+        }
+
+        list_cashflow
+            .calculate()
+            .set_default_scenario(default_scenario);
+
+        true
     }
 }
@@ -7,26 +7,30 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use chrono::{Datelike, NaiveDate};
 use rust_decimal::prelude::*;
-use std::cell::{Ref, RefCell, RefMut};
-use std::collections::VecDeque;
-use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
 
 use super::{CalcManager, CalcScan};
 use crate::core::{
-    CoreUtility, ElemBalanceResult, ElemSymbol, ListAmortization, ListDescriptor, ListParameter,
-    MapSymbol,
+    CoreFinancial, CoreUtility, ElemAmortization, ElemBalanceResult, ElemDatedAmount, ElemError,
+    ElemExprLimits, ElemExprNode, ElemExprTrace, ElemFunction, ElemSymbol, ListAmortization,
+    ListDescriptor, ListFunction, ListParameter, MapSymbol,
 };
-use crate::ListTrait;
+use crate::{ExtensionTrait, ListTrait};
 
-pub struct CalcExpression {
-    /// Calculator manager element.
-    calc_manager: Rc<RefCell<CalcManager>>,
+pub struct CalcExpression<'a> {
+    /// Calculator manager element (borrowed for the lifetime of the
+    /// expression while the caller holds the manager guard).
+    calc_manager: &'a CalcManager,
 
     /// Common symbol table.
-    symbol_table: Rc<RefCell<MapSymbol>>,
+    symbol_table: Arc<RwLock<MapSymbol>>,
     /// Token scanner.
-    scanner: RefCell<CalcScan>,
+    scanner: RwLock<CalcScan>,
+    /// The expression text.
+    expression: String,
 
     /// User descriptor list.
     list_descriptor_user: ListDescriptor,
@@ -35,6 +39,25 @@ pub struct CalcExpression {
     /// Event descriptor list.
     list_descriptor_event: Option<ListDescriptor>,
 
+    /// User-defined function list of the cashflow or template group.
+    list_function_cashflow: Option<ListFunction>,
+    /// Parameter frames of the user-defined function calls being
+    /// evaluated (the function body and the bound parameters).
+    frames: RwLock<Vec<(String, MapSymbol)>>,
+
+    /// Limits of the expression set by the host.
+    expr_limits: ElemExprLimits,
+    /// Number of nodes evaluated (including user-defined functions).
+    steps: AtomicUsize,
+    /// Current nesting depth of the nodes being evaluated.
+    depth: AtomicUsize,
+
+    /// Record an evaluation trace.
+    trace: bool,
+    /// Traces of the nodes being evaluated (the first element holds the
+    /// trace of the last evaluation).
+    trace_stack: RwLock<Vec<ElemExprTrace>>,
+
     /// Start of fiscal year in MMDD format.
     fiscal_year_start: usize,
     /// Number of significant decimal digits.
@@ -43,7 +66,7 @@ pub struct CalcExpression {
 
 /// The AmFn expression mechanism implementation.
 
-impl CalcExpression {
+impl<'a> CalcExpression<'a> {
     /// Create and return a new AmFn expression.
     ///
     /// # Arguments
@@ -57,117 +80,160 @@ impl CalcExpression {
     /// * See description.
 
     pub fn new(
-        calc_manager_param: &Rc<RefCell<CalcManager>>,
+        calc_manager_param: &'a CalcManager,
         fiscal_year_start_param: usize,
         decimal_digits_param: usize,
-    ) -> CalcExpression {
-        let updating_json = calc_manager_param.borrow().updating_json();
+    ) -> CalcExpression<'a> {
+        let updating_json = calc_manager_param.updating_json();
         let tlist_descriptor_user = calc_manager_param
-            .borrow()
             .preferences()
             .list_descriptor()
             .copy(false, updating_json);
+        let texpr_limits = calc_manager_param.expr_limits();
 
         CalcExpression {
-            calc_manager: Rc::clone(calc_manager_param),
-            symbol_table: Rc::new(RefCell::new(MapSymbol::new())),
-            scanner: RefCell::new(CalcScan::new("")),
+            calc_manager: calc_manager_param,
+            symbol_table: Arc::new(RwLock::new(MapSymbol::new())),
+            scanner: RwLock::new(CalcScan::new("")),
+            expression: String::from(""),
             list_descriptor_user: tlist_descriptor_user,
             list_descriptor_cashflow: None,
             list_descriptor_event: None,
+            list_function_cashflow: None,
+            frames: RwLock::new(Vec::new()),
+            expr_limits: texpr_limits,
+            steps: AtomicUsize::new(0),
+            depth: AtomicUsize::new(0),
+            trace: false,
+            trace_stack: RwLock::new(Vec::new()),
             fiscal_year_start: fiscal_year_start_param,
             decimal_digits: decimal_digits_param,
         }
     }
 
-    /// Create and return a new AmFn expression for an internal sub-expression.
+    /// Get the calculation manager.
     ///
-    /// # Arguments
+    /// # Return
     ///
-    /// * `calc_manager_param` - Calculation manager.
-    /// * `symbol_param` - Symbol table reference from base instance.
-    /// * `fiscal_year_start_param` - Start of fiscal year in MMDD format.
-    /// * `decimal_digits_param` - Number of significant decimal digits.
-    /// * `expr_str` - The sub-expression to execute.
+    /// * See description.
+
+    fn calc_mgr(&self) -> &'a CalcManager {
+        self.calc_manager
+    }
+
+    /// Get the scanner.
     ///
     /// # Return
     ///
     /// * See description.
 
-    fn new_with_symbol_table(
-        calc_manager_param: &Rc<RefCell<CalcManager>>,
-        symbol_param: &Rc<RefCell<MapSymbol>>,
-        fiscal_year_start_param: usize,
-        decimal_digits_param: usize,
-        expr_str: &str,
-    ) -> CalcExpression {
-        let updating_json = calc_manager_param.borrow().updating_json();
-        let tlist_descriptor_user = calc_manager_param
-            .borrow()
-            .preferences()
-            .list_descriptor()
-            .copy(false, updating_json);
-
-        CalcExpression {
-            calc_manager: Rc::clone(calc_manager_param),
-            symbol_table: Rc::clone(symbol_param),
-            scanner: RefCell::new(CalcScan::new(expr_str)),
-            list_descriptor_user: tlist_descriptor_user,
-            list_descriptor_cashflow: None,
-            list_descriptor_event: None,
-            fiscal_year_start: fiscal_year_start_param,
-            decimal_digits: decimal_digits_param,
-        }
+    fn scanner(&self) -> RwLockReadGuard<'_, CalcScan> {
+        self.scanner.read().unwrap()
     }
 
-    /// Get the calculation manager.
+    /// Get the mutable scanner.
     ///
     /// # Return
     ///
     /// * See description.
 
-    fn calc_mgr(&self) -> Ref<CalcManager> {
-        self.calc_manager.borrow()
+    fn scanner_mut(&self) -> RwLockWriteGuard<'_, CalcScan> {
+        self.scanner.write().unwrap()
     }
 
-    /// Get the scanner.
+    /// Get the symbol table.
     ///
     /// # Return
     ///
     /// * See description.
 
-    fn scanner(&self) -> Ref<CalcScan> {
-        self.scanner.borrow()
+    fn symbol_table(&self) -> RwLockReadGuard<'_, MapSymbol> {
+        self.symbol_table.read().unwrap()
     }
 
-    /// Get the mutable scanner.
+    /// Get the mutable symbol table.
     ///
     /// # Return
     ///
     /// * See description.
 
-    fn scanner_mut(&self) -> RefMut<CalcScan> {
-        self.scanner.borrow_mut()
+    fn symbol_table_mut(&self) -> RwLockWriteGuard<'_, MapSymbol> {
+        self.symbol_table.write().unwrap()
     }
 
-    /// Get the symbol table.
+    /// Get the parameter frames of the user-defined function calls.
     ///
     /// # Return
     ///
     /// * See description.
+    fn frames(&self) -> RwLockReadGuard<'_, Vec<(String, MapSymbol)>> {
+        self.frames.read().unwrap()
+    }
 
-    fn symbol_table(&self) -> Ref<MapSymbol> {
-        self.symbol_table.borrow()
+    /// Get the mutable parameter frames of the user-defined function calls.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn frames_mut(&self) -> RwLockWriteGuard<'_, Vec<(String, MapSymbol)>> {
+        self.frames.write().unwrap()
     }
 
-    /// Get the mutable symbol table.
+    /// Get the value of a symbol. The parameter frames of the
+    /// user-defined function calls are searched (innermost first)
+    /// before the symbol table.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Symbol name.
+    ///
+    /// # Return
+    ///
+    /// * The symbol if found, otherwise None.
+    fn get_symbol(&self, name: &str) -> Option<ElemSymbol> {
+        for (_expression, frame) in self.frames().iter().rev() {
+            if let Some(o) = frame.get_symbol(name) {
+                return Some(o.copy());
+            }
+        }
+
+        self.symbol_table().get_symbol(name).map(|o| o.copy())
+    }
+
+    /// Add a symbol to the innermost parameter frame or, outside
+    /// of a user-defined function call, to the symbol table.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Symbol name.
+    /// * `elem_symbol` - Symbol value.
+    fn add_symbol(&self, name: &str, elem_symbol: ElemSymbol) {
+        match self.frames_mut().last_mut() {
+            None => self.symbol_table_mut().add_symbol(name, elem_symbol),
+            Some(o) => o.1.add_symbol(name, elem_symbol),
+        }
+    }
+
+    /// Get the text of the expression being evaluated (i.e., the body
+    /// of the innermost user-defined function call if any).
     ///
     /// # Return
     ///
     /// * See description.
+    fn expression_text(&self) -> String {
+        match self.frames().last() {
+            None => self.expression.clone(),
+            Some(o) => o.0.clone(),
+        }
+    }
 
-    fn symbol_table_mut(&self) -> RefMut<MapSymbol> {
-        self.symbol_table.borrow_mut()
+    /// Get the mutable evaluation trace stack.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn trace_stack_mut(&self) -> RwLockWriteGuard<'_, Vec<ElemExprTrace>> {
+        self.trace_stack.write().unwrap()
     }
 
     /// Clear the symbol table.
@@ -209,6 +275,18 @@ impl CalcExpression {
 
         self.init_list_parameter(list_parameter_param);
         self.scanner_mut().init_scan(expression);
+        self.expression = String::from(expression);
+    }
+
+    /// Initialize the user-defined functions of the cashflow or
+    /// template group. These functions are searched before the
+    /// user-defined functions of the user preferences.
+    ///
+    /// # Arguments
+    ///
+    /// * `list_function_param` - The user-defined functions (or None).
+    pub fn init_list_function(&mut self, list_function_param: Option<&ListFunction>) {
+        self.list_function_cashflow = list_function_param.map(|o| o.copy());
     }
 
     /// Initialize the symbol table with passed parameters.
@@ -311,9 +389,10 @@ impl CalcExpression {
     }
 
     /// Executes the currently initialized expression.
-    /// The expression is first parsed into postfix notation.
-    /// Then the resulting symbols are executed on a stack with
-    /// the final result being returned from this method.
+    /// The expression is compiled once and the compiled expression
+    /// is cached so that later evaluations of the same expression
+    /// text skip the parse. If an error occurs, the expression text
+    /// and the position of the failing token are recorded in the error.
     ///
     /// # Arguments
     ///
@@ -323,942 +402,709 @@ impl CalcExpression {
     /// # Return
     ///
     /// * Result symbol if successful, otherwise error code.
-
     pub fn evaluate(
         &self,
         list_am_opt: Option<&ListAmortization>,
         elem_balance_result_opt: Option<&ElemBalanceResult>,
-    ) -> Result<ElemSymbol, crate::ErrorType> {
-        let mut expression = VecDeque::new();
-        let mut stack = Vec::new();
-        let mut elem_last_symbol: Option<ElemSymbol>;
-        let mut text: String;
-        let mut ch1: char;
-        let mut level: i32 = 0;
-        let mut operator_needed: bool;
+    ) -> Result<ElemSymbol, ElemError> {
+        self.steps.store(0, Ordering::Relaxed);
+        self.depth.store(0, Ordering::Relaxed);
+
+        if self.trace {
+            let mut trace_stack = self.trace_stack_mut();
+            trace_stack.clear();
+            trace_stack.push(ElemExprTrace::new(
+                crate::NodeType::Sequence,
+                self.expression.as_str(),
+                0,
+            ));
+        }
 
-        loop {
-            expression.clear();
-            stack.clear();
-            operator_needed = false;
+        let elem_expr_node = self.compile()?;
 
-            while self.scanner_mut().scan_token() != crate::TokenType::Unknown {
-                let scan_type = self.scanner().get_type();
+        self.evaluate_node(&elem_expr_node, list_am_opt, elem_balance_result_opt)
+    }
 
-                if level == 0
-                    && scan_type == crate::TokenType::Punctuation
-                    && self.scanner().get_token().starts_with(',')
-                {
-                    break;
+    /// Set whether an evaluation trace is recorded. When set, each
+    /// node of the compiled expression that is executed (constants,
+    /// symbol lookups, operators, function calls with their arguments
+    /// and the bodies of user-defined functions) is recorded with its
+    /// result in the order executed. Branches that are not taken
+    /// (e.g., of the if function) do not appear in the trace.
+    ///
+    /// # Arguments
+    ///
+    /// * `trace_param` - See description.
+    pub fn set_trace(&mut self, trace_param: bool) {
+        self.trace = trace_param;
+        self.trace_stack_mut().clear();
+    }
+
+    /// Get the evaluation trace of the last evaluation.
+    ///
+    /// # Return
+    ///
+    /// * The trace of the expression if recorded, otherwise None.
+    pub fn trace(&self) -> Option<ElemExprTrace> {
+        match self.trace_stack.read().unwrap().first() {
+            None => None,
+            Some(o) => o.nodes().first().cloned(),
+        }
+    }
+
+    /// Returns the text of a node for the evaluation trace.
+    ///
+    /// # Arguments
+    ///
+    /// * `elem_expr_node` - The node.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn trace_text(&self, elem_expr_node: &ElemExprNode) -> String {
+        match elem_expr_node.node_type() {
+            crate::NodeType::Constant => {
+                let value = elem_expr_node.value();
+                match value.sym_type() {
+                    crate::TokenType::Integer => format!("{}", value.sym_integeri()),
+                    crate::TokenType::Decimal => format!("{}", value.sym_decimal()),
+                    _ => format!("\"{}\"", value.sym_string()),
                 }
+            }
+            crate::NodeType::Operator => {
+                let (text, _) = self.operator_text(elem_expr_node.operator_type());
+                text
+            }
+            crate::NodeType::Sequence => String::from(","),
+            _ => String::from(elem_expr_node.name()),
+        }
+    }
+
+    /// Returns the text and precedence of an operator.
+    ///
+    /// # Arguments
+    ///
+    /// * `operator_type` - The operator type.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn operator_text(&self, operator_type: crate::OperatorType) -> (String, usize) {
+        let elem_key = match self
+            .calc_mgr()
+            .core_manager()
+            .operators()
+            .get_key_by_value(operator_type as usize)
+        {
+            None => return (format!("{:?}", operator_type), 0),
+            Some(o) => o,
+        };
+
+        if operator_type == crate::OperatorType::UnaryMinus {
+            return (String::from("-"), elem_key.value_ext());
+        }
+
+        (String::from(elem_key.key()), elem_key.value_ext())
+    }
+
+    /// Compiles the currently initialized expression into an
+    /// expression tree. Compiled expressions are cached by the
+    /// calculation manager and shared by all expression instances.
+    ///
+    /// # Return
+    ///
+    /// * Compiled expression if successful, otherwise error code.
+    pub fn compile(&self) -> Result<Arc<ElemExprNode>, ElemError> {
+        self.compile_text(self.expression.as_str())
+    }
+
+    /// Compiles an expression text into an expression tree.
+    ///
+    /// # Arguments
+    ///
+    /// * `expression` - The expression text.
+    ///
+    /// # Return
+    ///
+    /// * Compiled expression if successful, otherwise error code.
+    fn compile_text(&self, expression: &str) -> Result<Arc<ElemExprNode>, ElemError> {
+        match self.calc_mgr().compiled_expression(expression) {
+            None => {}
+            Some(o) => return Ok(o),
+        }
+
+        self.scanner_mut().init_scan(expression);
+
+        let elem_expr_node = match self.compile_sequence() {
+            Err(mut e) => {
+                e.set_expression(expression, self.scanner().get_position());
+                return Err(e);
+            }
+            Ok(o) => Arc::new(o),
+        };
+
+        self.calc_mgr()
+            .add_compiled_expression(expression, Arc::clone(&elem_expr_node));
+
+        Ok(elem_expr_node)
+    }
+
+    /// Compiles a sequence of comma separated expressions.
+    ///
+    /// # Return
+    ///
+    /// * Compiled expression if successful, otherwise error code.
+    fn compile_sequence(&self) -> Result<ElemExprNode, ElemError> {
+        let mut nodes: Vec<ElemExprNode> = Vec::new();
+
+        loop {
+            match self.compile_expr('\x00', 0) {
+                Err(e) => return Err(e),
+                Ok(o) => nodes.push(o),
+            }
+
+            if self.scanner().get_type() == crate::TokenType::Unknown {
+                break;
+            }
+        }
+
+        if nodes.len() == 1 {
+            return match nodes.pop() {
+                None => Err(ElemError::new(crate::ErrorType::Incomplete)),
+                Some(o) => Ok(o),
+            };
+        }
+
+        Ok(ElemExprNode::new_sequence(nodes, 0))
+    }
+
+    /// Compiles a single expression from the scanner using operator
+    /// precedence. Compiling stops at the end of the expression, at a
+    /// comma or at the closing character (outside of any parentheses).
+    ///
+    /// # Arguments
+    ///
+    /// * `close` - The closing character (')' for function arguments,
+    ///   ']' for symbol indexes or '\x00' for none).
+    /// * `depth` - Nesting depth of the expression within function
+    ///   arguments and symbol indexes.
+    ///
+    /// # Return
+    ///
+    /// * Compiled expression if successful, otherwise error code.
+    fn compile_expr(&self, close: char, depth: usize) -> Result<ElemExprNode, ElemError> {
+        let mut operands: Vec<ElemExprNode> = Vec::new();
+        let mut operators: Vec<(crate::OperatorType, usize, usize)> = Vec::new(); // Type, precedence, position
+        let mut level: usize = 0;
+        let mut operator_needed: bool = false;
 
-                text = String::from(self.scanner().get_token());
+        loop {
+            let scan_type = self.scanner_mut().scan_token();
+            let position = self.scanner().get_position();
+            let mut text = String::from(self.scanner().get_token());
+            let ch1: char = text.chars().next().unwrap_or('\x00');
 
-                ch1 = '\x00';
-                if !text.is_empty() {
-                    ch1 = text.chars().next().unwrap_or(' ');
+            if scan_type == crate::TokenType::Unknown {
+                if level > 0 || close == ')' {
+                    return Err(ElemError::new(crate::ErrorType::RightParen));
                 }
-                match scan_type {
-                    crate::TokenType::Punctuation => {
-                        match ch1 {
-                            '(' => {
-                                level += 1;
-                                stack.push(ElemSymbol::new_with_token_type(
-                                    crate::TokenType::LeftParen,
-                                ));
-                            }
-                            ')' => {
-                                level -= 1;
-                                if level < 0 {
-                                    return Err(crate::ErrorType::LeftParen);
-                                }
-                                loop {
-                                    let elem_symbol: ElemSymbol = match stack.pop() {
-                                        None => break,
-                                        Some(o) => o,
-                                    };
+                if close == ']' {
+                    return Err(ElemError::new(crate::ErrorType::RightBracket));
+                }
+                break;
+            }
+
+            if level == 0
+                && scan_type == crate::TokenType::Punctuation
+                && (ch1 == ',' || ch1 == close)
+            {
+                break;
+            }
 
-                                    if elem_symbol.sym_type() == crate::TokenType::LeftParen {
-                                        break;
-                                    }
+            let is_operator = match scan_type {
+                crate::TokenType::Punctuation => ch1 != '(' && ch1 != ')',
+                crate::TokenType::Alpha => self
+                    .calc_mgr()
+                    .core_manager()
+                    .operators()
+                    .get_key(text.as_str())
+                    .is_some(),
+                _ => false,
+            };
 
-                                    expression.push_back(elem_symbol);
-                                }
-                            }
-                            _ => {
-                                if (ch1 == '<' || ch1 == '>')
-                                    && self.scanner_mut().scan_token() != crate::TokenType::Unknown
-                                {
-                                    let ch2 =
-                                        self.scanner().get_token().chars().next().unwrap_or(' ');
-                                    if ch2 == '=' || (ch1 == '<' && ch2 == '>') {
-                                        text += self.scanner().get_token();
-                                    } else {
-                                        self.scanner_mut().rescan();
-                                    }
-                                }
-                                if !self
-                                    .calc_mgr()
-                                    .core_manager()
-                                    .operators()
-                                    .get_element_by_key(text.as_str())
-                                {
-                                    return Err(crate::ErrorType::InvalidOperator);
-                                }
-                                if operator_needed
-                                    && CoreUtility::get_operator(
-                                        self.calc_mgr().core_manager().operators().value(),
-                                    ) == crate::OperatorType::UnaryNot
-                                {
-                                    return Err(crate::ErrorType::InvalidOperator);
-                                }
-                                if !operator_needed {
-                                    let op_type = CoreUtility::get_operator(
-                                        self.calc_mgr().core_manager().operators().value(),
-                                    );
-                                    match op_type {
-                                        crate::OperatorType::Minus => {
-                                            if !self
-                                                .calc_mgr()
-                                                .core_manager()
-                                                .operators()
-                                                .get_element_by_key("~")
-                                            {
-                                                // Unary minus
-                                                return Err(crate::ErrorType::InvalidOperator);
-                                            }
-                                        }
-                                        crate::OperatorType::UnaryNot => {}
-                                        _ => {
-                                            return Err(crate::ErrorType::Operand);
-                                        }
-                                    }
-                                }
-                                while !stack.is_empty() {
-                                    let elem = &stack[stack.len() - 1];
-                                    if elem.sym_type() == crate::TokenType::LeftParen
-                                        || (elem.sym_type() == crate::TokenType::Operator
-                                            && self
-                                                .calc_mgr()
-                                                .core_manager()
-                                                .operators()
-                                                .get_value_ext_by_index(elem.sym_integer())
-                                                < self
-                                                    .calc_mgr()
-                                                    .core_manager()
-                                                    .operators()
-                                                    .value_ext())
-                                    {
-                                        break;
-                                    }
-                                    match stack.pop() {
-                                        None => {}
-                                        Some(o) => {
-                                            expression.push_back(o);
-                                        }
-                                    }
-                                }
-                                let mut elem =
-                                    ElemSymbol::new_with_token_type(crate::TokenType::Operator);
-                                elem.set_operator(
-                                    self.calc_mgr().core_manager().operators().index(),
-                                );
-                                stack.push(elem);
-                                operator_needed = false;
-                            }
-                        }
+            if is_operator {
+                if (ch1 == '<' || ch1 == '>')
+                    && self.scanner_mut().scan_token() != crate::TokenType::Unknown
+                {
+                    let ch2 = self.scanner().get_token().chars().next().unwrap_or(' ');
+                    if ch2 == '=' || (ch1 == '<' && ch2 == '>') {
+                        text += self.scanner().get_token();
+                    } else {
+                        self.scanner_mut().rescan();
                     }
-                    crate::TokenType::Integer => {
-                        if operator_needed {
-                            return Err(crate::ErrorType::Operator);
-                        }
-                        let mut elem_symbol = ElemSymbol::new();
-                        elem_symbol.set_integeri(CoreUtility::parse_integeri(text.as_str()));
-                        expression.push_back(elem_symbol);
-                        operator_needed = true;
+                }
+
+                let list_operator = self.calc_mgr().core_manager().operators();
+                let elem_key = match list_operator.get_key(text.as_str()) {
+                    None => return Err(ElemError::new(crate::ErrorType::InvalidOperator)),
+                    Some(o) => o,
+                };
+                let mut operator_type = CoreUtility::get_operator(elem_key.value());
+                let mut precedence = elem_key.value_ext();
+
+                if operator_needed {
+                    if operator_type == crate::OperatorType::UnaryNot {
+                        return Err(ElemError::new(crate::ErrorType::InvalidOperator));
                     }
-                    crate::TokenType::Decimal => {
-                        if operator_needed {
-                            return Err(crate::ErrorType::Operator);
+                    while let Some(o) = operators.last() {
+                        if o.0 == crate::OperatorType::None || o.1 < precedence {
+                            break;
                         }
-                        let mut elem_symbol = ElemSymbol::new();
-                        elem_symbol.set_decimal(CoreUtility::parse_decimal(text.as_str()));
-                        expression.push_back(elem_symbol);
-                        operator_needed = true;
-                    }
-                    crate::TokenType::String => {
-                        if operator_needed {
-                            return Err(crate::ErrorType::Operator);
+                        if let Some(o) = operators.pop() {
+                            self.compile_reduce(&mut operands, o.0, o.2)?
                         }
-                        let mut elem_symbol = ElemSymbol::new();
-                        elem_symbol.set_string(text.as_str());
-                        expression.push_back(elem_symbol);
-                        operator_needed = true;
                     }
-                    crate::TokenType::Alpha => {
-                        if self
-                            .calc_mgr()
-                            .core_manager()
-                            .operators()
-                            .get_element_by_key(text.as_str())
-                        {
-                            while !stack.is_empty() {
-                                let elem_symbol = &stack[stack.len() - 1];
-                                if elem_symbol.sym_type() == crate::TokenType::LeftParen
-                                    || (elem_symbol.sym_type() == crate::TokenType::Operator
-                                        && self
-                                            .calc_mgr()
-                                            .core_manager()
-                                            .operators()
-                                            .get_value_ext_by_index(elem_symbol.sym_integer())
-                                            < self
-                                                .calc_mgr()
-                                                .core_manager()
-                                                .operators()
-                                                .value_ext())
-                                {
-                                    break;
-                                }
-                                match stack.pop() {
-                                    None => {}
-                                    Some(o) => {
-                                        expression.push_back(o);
-                                    }
-                                }
-                            }
-                            let mut elem_symbol =
-                                ElemSymbol::new_with_token_type(crate::TokenType::Operator);
-                            elem_symbol
-                                .set_operator(self.calc_mgr().core_manager().operators().index());
-                            stack.push(elem_symbol);
-                            operator_needed = false;
-                        } else {
-                            if operator_needed {
-                                return Err(crate::ErrorType::Operator);
-                            }
-                            let token = self.scanner_mut().scan_token();
-                            if token == crate::TokenType::Punctuation
-                                && self.scanner().get_token().starts_with('(')
-                            {
-                                let result = self.function(
-                                    text.as_str(),
-                                    list_am_opt,
-                                    elem_balance_result_opt,
-                                );
-                                match result {
-                                    Err(e) => {
-                                        return Err(e);
-                                    }
-                                    Ok(o) => {
-                                        expression.push_back(o);
-                                    }
-                                }
-                            } else {
-                                if !(self.scanner().get_type() == crate::TokenType::Punctuation
-                                    && self.scanner().get_token().starts_with('['))
-                                {
-                                    self.scanner_mut().rescan();
-                                } else {
-                                    self.scanner_mut().scan_token();
-                                    let result =
-                                        self.get_expr_integer(list_am_opt, elem_balance_result_opt);
-                                    match result {
-                                        Err(e) => {
-                                            return Err(e);
-                                        }
-                                        Ok(o) => {
-                                            if !(self.scanner().get_type()
-                                                == crate::TokenType::Punctuation
-                                                && self.scanner().get_token().starts_with(']'))
-                                            {
-                                                return Err(crate::ErrorType::RightBracket);
-                                            }
-                                            text += format!("[{}]", o.sym_integer()).as_str();
-                                        }
-                                    }
-                                }
-                                let sym_table = self.symbol_table();
-                                let sym_opt = sym_table.get_symbol(text.as_str());
-                                if sym_opt.is_none() {
-                                    return Err(crate::ErrorType::InvalidSymbol);
-                                }
-                                match sym_opt {
-                                    None => {}
-                                    Some(o) => {
-                                        let mut sym2 = ElemSymbol::new();
-                                        match o.sym_type() {
-                                            crate::TokenType::Integer => {
-                                                sym2.set_integer(o.sym_integer());
-                                            }
-                                            crate::TokenType::Decimal => {
-                                                sym2.set_decimal(o.sym_decimal());
-                                            }
-                                            crate::TokenType::String => {
-                                                sym2.set_string(o.sym_string());
-                                            }
-                                            _ => {}
-                                        }
-                                        expression.push_back(sym2);
-                                    }
+                } else {
+                    match operator_type {
+                        crate::OperatorType::Minus => {
+                            let elem_key = match list_operator.get_key("~") {
+                                // Unary minus
+                                None => {
+                                    return Err(ElemError::new(crate::ErrorType::InvalidOperator))
                                 }
-                            }
-                            operator_needed = true;
+                                Some(o) => o,
+                            };
+                            operator_type = crate::OperatorType::UnaryMinus;
+                            precedence = elem_key.value_ext();
+                        }
+                        crate::OperatorType::UnaryNot => {}
+                        _ => {
+                            return Err(ElemError::new(crate::ErrorType::Operand));
                         }
-                    }
-                    _ => {
-                        return Err(crate::ErrorType::InvalidToken);
                     }
                 }
-            }
-
-            if level > 0 {
-                return Err(crate::ErrorType::RightParen);
-            }
 
-            loop {
-                match stack.pop() {
-                    None => {
-                        break;
-                    }
-                    Some(o) => {
-                        expression.push_back(o);
-                    }
-                }
+                operators.push((operator_type, precedence, position));
+                operator_needed = false;
+                continue;
             }
 
-            let mut elem_symbol1_opt: Option<ElemSymbol>;
-            let mut elem_symbol2_opt: Option<ElemSymbol>;
-            for elem_symbol in expression.iter_mut() {
-                match elem_symbol.sym_type() {
-                    crate::TokenType::Operator => {
-                        if !self
-                            .calc_mgr()
-                            .core_manager()
-                            .operators()
-                            .get_element(elem_symbol.sym_integer())
-                        {
-                            return Err(crate::ErrorType::InvalidOperator);
+            match scan_type {
+                crate::TokenType::Punctuation => {
+                    if ch1 == '(' {
+                        if operator_needed {
+                            return Err(ElemError::new(crate::ErrorType::Operator));
                         }
-                        if stack.is_empty() {
-                            return Err(crate::ErrorType::MissingOperand);
+                        level += 1;
+                        operators.push((crate::OperatorType::None, 0, position));
+                    } else {
+                        if level == 0 {
+                            return Err(ElemError::new(crate::ErrorType::LeftParen));
                         }
-                        elem_symbol2_opt = stack.pop();
-                        let sym_val = CoreUtility::get_operator(elem_symbol.sym_integer());
-                        if sym_val == crate::OperatorType::UnaryMinus
-                            || sym_val == crate::OperatorType::UnaryNot
-                        {
-                            match elem_symbol2_opt.as_mut() {
-                                None => {
-                                    return Err(crate::ErrorType::MissingOperand);
-                                }
-                                Some(o) => {
-                                    elem_symbol1_opt = Option::from(o.copy());
-                                }
-                            }
-                        } else {
-                            if stack.is_empty() {
-                                return Err(crate::ErrorType::MissingOperand);
-                            }
-                            elem_symbol1_opt = stack.pop();
-                            let elem_symbol1: &mut ElemSymbol = match elem_symbol1_opt.as_mut() {
-                                None => return Err(crate::ErrorType::MissingOperand),
-                                Some(o) => o,
-                            };
-                            let elem_symbol2: &mut ElemSymbol = match elem_symbol2_opt.as_mut() {
-                                None => return Err(crate::ErrorType::MissingOperand),
+                        level -= 1;
+                        loop {
+                            let operator = match operators.pop() {
+                                None => break,
                                 Some(o) => o,
                             };
-                            if elem_symbol1.sym_type() == crate::TokenType::Integer
-                                && elem_symbol2.sym_type() == crate::TokenType::Decimal
-                            {
-                                elem_symbol1.set_decimal(dec!(elem_symbol1.sym_integer()));
-                            } else if elem_symbol1.sym_type() == crate::TokenType::Decimal
-                                && elem_symbol2.sym_type() == crate::TokenType::Integer
-                            {
-                                elem_symbol2.set_decimal(dec!(elem_symbol2.sym_integer()));
-                            }
-                        }
-                        let elem_symbol1: &mut ElemSymbol = match elem_symbol1_opt.as_mut() {
-                            None => return Err(crate::ErrorType::MissingOperand),
-                            Some(o) => o,
-                        };
-                        let elem_symbol2: &mut ElemSymbol = match elem_symbol2_opt.as_mut() {
-                            None => return Err(crate::ErrorType::MissingOperand),
-                            Some(o) => o,
-                        };
-                        match CoreUtility::get_operator(
-                            self.calc_mgr().core_manager().operators().value(),
-                        ) {
-                            crate::OperatorType::And => {
-                                if elem_symbol1.sym_type() != crate::TokenType::Integer
-                                    || elem_symbol2.sym_type() != crate::TokenType::Integer
-                                {
-                                    return Err(crate::ErrorType::InvalidOperand);
-                                }
-                                if elem_symbol1.sym_integer() != 0
-                                    && elem_symbol2.sym_integer() != 0
-                                {
-                                    elem_symbol1.set_integer(1);
-                                } else {
-                                    elem_symbol1.set_integer(0);
-                                }
-                            }
-                            crate::OperatorType::Or => {
-                                if elem_symbol1.sym_type() != crate::TokenType::Integer
-                                    || elem_symbol2.sym_type() != crate::TokenType::Integer
-                                {
-                                    return Err(crate::ErrorType::InvalidOperand);
-                                }
-                                if elem_symbol1.sym_integer() != 0
-                                    || elem_symbol2.sym_integer() != 0
-                                {
-                                    elem_symbol1.set_integer(1);
-                                } else {
-                                    elem_symbol1.set_integer(0);
-                                }
-                            }
-                            crate::OperatorType::Greater => {
-                                if elem_symbol1.sym_type() == crate::TokenType::String
-                                    || elem_symbol2.sym_type() == crate::TokenType::String
-                                {
-                                    return Err(crate::ErrorType::InvalidOperand);
-                                }
-                                if elem_symbol1.sym_type() == crate::TokenType::Integer {
-                                    if elem_symbol1.sym_integer() > elem_symbol2.sym_integer() {
-                                        elem_symbol1.set_integer(1);
-                                    } else {
-                                        elem_symbol1.set_integer(0);
-                                    }
-                                } else if elem_symbol1.sym_decimal() > elem_symbol2.sym_decimal() {
-                                    elem_symbol1.set_integer(1);
-                                } else {
-                                    elem_symbol1.set_integer(0);
-                                }
-                            }
-                            crate::OperatorType::Less => {
-                                if elem_symbol1.sym_type() == crate::TokenType::String
-                                    || elem_symbol2.sym_type() == crate::TokenType::String
-                                {
-                                    return Err(crate::ErrorType::InvalidOperand);
-                                }
-                                if elem_symbol1.sym_type() == crate::TokenType::Integer {
-                                    if elem_symbol1.sym_integer() < elem_symbol2.sym_integer() {
-                                        elem_symbol1.set_integer(1);
-                                    } else {
-                                        elem_symbol1.set_integer(0);
-                                    }
-                                } else if elem_symbol1.sym_decimal() < elem_symbol2.sym_decimal() {
-                                    elem_symbol1.set_integer(1);
-                                } else {
-                                    elem_symbol1.set_integer(0);
-                                }
-                            }
-                            crate::OperatorType::GreaterEqual => {
-                                if elem_symbol1.sym_type() == crate::TokenType::String
-                                    || elem_symbol2.sym_type() == crate::TokenType::String
-                                {
-                                    return Err(crate::ErrorType::InvalidOperand);
-                                }
-                                if elem_symbol1.sym_type() == crate::TokenType::Integer {
-                                    if elem_symbol1.sym_integer() >= elem_symbol2.sym_integer() {
-                                        elem_symbol1.set_integer(1);
-                                    } else {
-                                        elem_symbol1.set_integer(0);
-                                    }
-                                } else if elem_symbol1.sym_decimal() >= elem_symbol2.sym_decimal() {
-                                    elem_symbol1.set_integer(1);
-                                } else {
-                                    elem_symbol1.set_integer(0);
-                                }
-                            }
-                            crate::OperatorType::LessEqual => {
-                                if elem_symbol1.sym_type() == crate::TokenType::String
-                                    || elem_symbol2.sym_type() == crate::TokenType::String
-                                {
-                                    return Err(crate::ErrorType::InvalidOperand);
-                                }
-                                if elem_symbol1.sym_type() == crate::TokenType::Integer {
-                                    if elem_symbol1.sym_integer() <= elem_symbol2.sym_integer() {
-                                        elem_symbol1.set_integer(1);
-                                    } else {
-                                        elem_symbol1.set_integer(0);
-                                    }
-                                } else if elem_symbol1.sym_decimal() <= elem_symbol2.sym_decimal() {
-                                    elem_symbol1.set_integer(1);
-                                } else {
-                                    elem_symbol1.set_integer(0);
-                                }
-                            }
-                            crate::OperatorType::Equal => {
-                                if elem_symbol1.sym_type() != elem_symbol2.sym_type() {
-                                    return Err(crate::ErrorType::InvalidOperand);
-                                }
-                                if elem_symbol1.sym_type() == crate::TokenType::Integer {
-                                    if elem_symbol1.sym_integer() == elem_symbol2.sym_integer() {
-                                        elem_symbol1.set_integer(1);
-                                    } else {
-                                        elem_symbol1.set_integer(0);
-                                    }
-                                } else if elem_symbol1.sym_type() == crate::TokenType::Decimal {
-                                    if elem_symbol1.sym_decimal() == elem_symbol2.sym_decimal() {
-                                        elem_symbol1.set_integer(1);
-                                    } else {
-                                        elem_symbol1.set_integer(0);
-                                    }
-                                } else if elem_symbol1.sym_string() == elem_symbol2.sym_string() {
-                                    elem_symbol1.set_integer(1);
-                                } else {
-                                    elem_symbol1.set_integer(0);
-                                }
-                            }
-                            crate::OperatorType::NotEqual => {
-                                if elem_symbol1.sym_type() != elem_symbol2.sym_type() {
-                                    return Err(crate::ErrorType::InvalidOperand);
-                                }
-                                if elem_symbol1.sym_type() == crate::TokenType::Integer {
-                                    if elem_symbol1.sym_integer() != elem_symbol2.sym_integer() {
-                                        elem_symbol1.set_integer(1);
-                                    } else {
-                                        elem_symbol1.set_integer(0);
-                                    }
-                                } else if elem_symbol1.sym_type() == crate::TokenType::Decimal {
-                                    if elem_symbol1.sym_decimal() != elem_symbol2.sym_decimal() {
-                                        elem_symbol1.set_integer(1);
-                                    } else {
-                                        elem_symbol1.set_integer(0);
-                                    }
-                                } else if elem_symbol1.sym_string() == elem_symbol2.sym_string() {
-                                    elem_symbol1.set_integer(1);
-                                } else {
-                                    elem_symbol1.set_integer(0);
-                                }
-                            }
-                            crate::OperatorType::Plus => {
-                                if elem_symbol1.sym_type() != elem_symbol2.sym_type() {
-                                    return Err(crate::ErrorType::InvalidOperand);
-                                }
-                                if elem_symbol1.sym_type() == crate::TokenType::Integer {
-                                    elem_symbol1.set_integer(
-                                        elem_symbol1.sym_integer() + elem_symbol2.sym_integer(),
-                                    );
-                                } else if elem_symbol1.sym_type() == crate::TokenType::Decimal {
-                                    elem_symbol1.set_decimal(
-                                        elem_symbol1.sym_decimal() + elem_symbol2.sym_decimal(),
-                                    );
-                                } else {
-                                    elem_symbol1.set_string(
-                                        format!(
-                                            "{}{}",
-                                            elem_symbol1.sym_string(),
-                                            elem_symbol2.sym_string()
-                                        )
-                                        .as_str(),
-                                    );
-                                }
-                            }
-                            crate::OperatorType::Minus => {
-                                if elem_symbol1.sym_type() == crate::TokenType::String
-                                    || elem_symbol2.sym_type() == crate::TokenType::String
-                                {
-                                    return Err(crate::ErrorType::InvalidOperand);
-                                }
-                                if elem_symbol1.sym_type() == crate::TokenType::Integer {
-                                    elem_symbol1.set_integer(
-                                        elem_symbol1.sym_integer() - elem_symbol2.sym_integer(),
-                                    );
-                                } else {
-                                    elem_symbol1.set_decimal(
-                                        elem_symbol1.sym_decimal() - elem_symbol2.sym_decimal(),
-                                    );
-                                }
-                            }
-                            crate::OperatorType::Times => {
-                                if elem_symbol1.sym_type() == crate::TokenType::String
-                                    || elem_symbol2.sym_type() == crate::TokenType::String
-                                {
-                                    return Err(crate::ErrorType::InvalidOperand);
-                                }
-                                if elem_symbol1.sym_type() == crate::TokenType::Integer {
-                                    elem_symbol1.set_integer(
-                                        elem_symbol1.sym_integer() * elem_symbol2.sym_integer(),
-                                    );
-                                } else {
-                                    elem_symbol1.set_decimal(
-                                        elem_symbol1.sym_decimal() * elem_symbol2.sym_decimal(),
-                                    );
-                                }
-                            }
-                            crate::OperatorType::Divide => {
-                                if elem_symbol1.sym_type() == crate::TokenType::String
-                                    || elem_symbol2.sym_type() == crate::TokenType::String
-                                {
-                                    return Err(crate::ErrorType::InvalidOperand);
-                                }
-                                if elem_symbol1.sym_type() == crate::TokenType::Integer {
-                                    if elem_symbol2.sym_integer() == 0 {
-                                        elem_symbol1.set_integer(0); // Do not error on divide by zero
-                                    } else {
-                                        elem_symbol1.set_integer(
-                                            elem_symbol1.sym_integer() / elem_symbol2.sym_integer(),
-                                        );
-                                    }
-                                } else if elem_symbol2.sym_decimal() == dec!(0.0) {
-                                    elem_symbol1.set_decimal(dec!(0.0)); // Do not error on divide by zero
-                                } else {
-                                    elem_symbol1.set_decimal(
-                                        elem_symbol1.sym_decimal() / elem_symbol2.sym_decimal(),
-                                    );
-                                }
-                            }
-                            crate::OperatorType::Modulus => {
-                                if elem_symbol1.sym_type() == crate::TokenType::String
-                                    || elem_symbol2.sym_type() == crate::TokenType::String
-                                {
-                                    return Err(crate::ErrorType::InvalidOperand);
-                                }
-                                if elem_symbol1.sym_type() == crate::TokenType::Integer {
-                                    if elem_symbol2.sym_integer() == 0 {
-                                        elem_symbol1.set_integer(0); // Do not error on divide by zero
-                                    } else {
-                                        elem_symbol1.set_integer(
-                                            elem_symbol1.sym_integer() % elem_symbol2.sym_integer(),
-                                        );
-                                    }
-                                } else if elem_symbol2.sym_decimal() == dec!(0.0) {
-                                    elem_symbol1.set_decimal(dec!(0.0)); // Do not error on divide by zero
-                                } else {
-                                    elem_symbol1.set_decimal(
-                                        elem_symbol1.sym_decimal() % elem_symbol2.sym_decimal(),
-                                    );
-                                }
-                            }
-                            crate::OperatorType::Exponent => {
-                                if elem_symbol1.sym_type() == crate::TokenType::String
-                                    || elem_symbol2.sym_type() == crate::TokenType::String
-                                {
-                                    return Err(crate::ErrorType::InvalidOperand);
-                                }
-                                if elem_symbol1.sym_type() == crate::TokenType::Integer {
-                                    elem_symbol1.set_integer(
-                                        elem_symbol1
-                                            .sym_integer()
-                                            .pow(elem_symbol2.sym_integer() as u32),
-                                    );
-                                } else {
-                                    match elem_symbol2.sym_decimal().to_usize() {
-                                        None => {
-                                            return Err(crate::ErrorType::InvalidOperand);
-                                        }
-                                        Some(o) => {
-                                            elem_symbol1.set_decimal(CoreUtility::decimal_pow(
-                                                elem_symbol1.sym_decimal(),
-                                                o,
-                                            ));
-                                        }
-                                    }
-                                }
-                            }
-                            crate::OperatorType::UnaryMinus => {
-                                if elem_symbol2.sym_type() == crate::TokenType::String {
-                                    return Err(crate::ErrorType::InvalidOperand);
-                                }
-                                if elem_symbol2.sym_type() == crate::TokenType::Integer {
-                                    elem_symbol1.set_integeri(-elem_symbol2.sym_integeri());
-                                } else {
-                                    elem_symbol1.set_decimal(-elem_symbol2.sym_decimal());
-                                }
-                            }
-                            crate::OperatorType::UnaryNot => {
-                                if elem_symbol2.sym_type() != crate::TokenType::Integer {
-                                    return Err(crate::ErrorType::InvalidOperand);
-                                }
-                                if elem_symbol2.sym_integer() == 0 {
-                                    elem_symbol1.set_integer(1);
-                                } else {
-                                    elem_symbol1.set_integer(0);
-                                }
+                            if operator.0 == crate::OperatorType::None {
+                                break;
                             }
-                            _ => {}
+                            self.compile_reduce(&mut operands, operator.0, operator.2)?
                         }
-                        stack.push(elem_symbol1.copy());
                     }
-                    crate::TokenType::Integer
-                    | crate::TokenType::Decimal
-                    | crate::TokenType::String => {
-                        stack.push(elem_symbol.copy());
+                }
+                crate::TokenType::Integer => {
+                    if operator_needed {
+                        return Err(ElemError::new(crate::ErrorType::Operator));
                     }
-                    _ => {}
+                    let mut elem_symbol = ElemSymbol::new();
+                    elem_symbol.set_integeri(CoreUtility::parse_integeri(text.as_str()));
+                    operands.push(ElemExprNode::new_constant(elem_symbol, position));
+                    operator_needed = true;
+                }
+                crate::TokenType::Decimal => {
+                    if operator_needed {
+                        return Err(ElemError::new(crate::ErrorType::Operator));
+                    }
+                    let mut elem_symbol = ElemSymbol::new();
+                    elem_symbol.set_decimal(CoreUtility::parse_decimal(text.as_str()));
+                    operands.push(ElemExprNode::new_constant(elem_symbol, position));
+                    operator_needed = true;
+                }
+                crate::TokenType::String => {
+                    if operator_needed {
+                        return Err(ElemError::new(crate::ErrorType::Operator));
+                    }
+                    let mut elem_symbol = ElemSymbol::new();
+                    elem_symbol.set_string(text.as_str());
+                    operands.push(ElemExprNode::new_constant(elem_symbol, position));
+                    operator_needed = true;
+                }
+                crate::TokenType::Alpha => {
+                    if operator_needed {
+                        return Err(ElemError::new(crate::ErrorType::Operator));
+                    }
+                    let token = self.scanner_mut().scan_token();
+                    if token == crate::TokenType::Punctuation
+                        && (self.scanner().get_token().starts_with('(')
+                            || self.scanner().get_token().starts_with('['))
+                        && self.expr_limits.exceeds_depth(depth + 1)
+                    {
+                        return Err(ElemError::new(crate::ErrorType::Limit));
+                    }
+                    if token == crate::TokenType::Punctuation
+                        && self.scanner().get_token().starts_with('(')
+                    {
+                        match self.compile_function(text.as_str(), position, depth + 1) {
+                            Err(e) => return Err(e),
+                            Ok(o) => operands.push(o),
+                        }
+                    } else if token == crate::TokenType::Punctuation
+                        && self.scanner().get_token().starts_with('[')
+                    {
+                        let index = self.compile_expr(']', depth + 1)?;
+                        if !(self.scanner().get_type() == crate::TokenType::Punctuation
+                            && self.scanner().get_token().starts_with(']'))
+                        {
+                            return Err(ElemError::new(crate::ErrorType::RightBracket));
+                        }
+                        operands.push(ElemExprNode::new_symbol(
+                            text.as_str(),
+                            Option::from(index),
+                            position,
+                        ));
+                    } else {
+                        self.scanner_mut().rescan();
+                        operands.push(ElemExprNode::new_symbol(text.as_str(), None, position));
+                    }
+                    operator_needed = true;
+                }
+                _ => {
+                    return Err(ElemError::new(crate::ErrorType::InvalidToken));
                 }
             }
+        }
 
-            if stack.len() != 1 {
-                return Err(crate::ErrorType::Incomplete);
-            }
-
-            elem_last_symbol = stack.pop();
+        while let Some(o) = operators.pop() {
+            self.compile_reduce(&mut operands, o.0, o.2)?
+        }
 
-            if self.scanner().get_type() == crate::TokenType::Unknown {
-                break;
-            }
+        if operands.len() != 1 {
+            return Err(ElemError::new(crate::ErrorType::Incomplete));
         }
-        if elem_last_symbol.is_none() {
-            return Err(crate::ErrorType::Incomplete);
+
+        match operands.pop() {
+            None => Err(ElemError::new(crate::ErrorType::Incomplete)),
+            Some(o) => Ok(o),
         }
-        let elem: &ElemSymbol = match elem_last_symbol.as_ref() {
-            None => return Err(crate::ErrorType::Incomplete),
-            Some(o) => o,
+    }
+
+    /// Replaces the top operand(s) on the operand stack with
+    /// a new operator node. The height of the new node is
+    /// checked against the depth limit so that long operator
+    /// chains cannot build a tree too deep to evaluate.
+    ///
+    /// # Arguments
+    ///
+    /// * `operands` - The operand stack.
+    /// * `operator_type` - The operator type.
+    /// * `position` - Position of the operator within the expression text.
+    ///
+    /// # Return
+    ///
+    /// * Ok if successful, otherwise error code.
+    fn compile_reduce(
+        &self,
+        operands: &mut Vec<ElemExprNode>,
+        operator_type: crate::OperatorType,
+        position: usize,
+    ) -> Result<(), ElemError> {
+        let count: usize = if operator_type == crate::OperatorType::UnaryMinus
+            || operator_type == crate::OperatorType::UnaryNot
+        {
+            1
+        } else {
+            2
         };
 
-        let mut elem_result_symbol = ElemSymbol::new();
+        if operands.len() < count {
+            return Err(ElemError::new(crate::ErrorType::MissingOperand));
+        }
 
-        match elem.sym_type() {
-            crate::TokenType::Integer => {
-                elem_result_symbol.set_integer(elem.sym_integer());
+        let nodes = operands.split_off(operands.len() - count);
+        let node = ElemExprNode::new_operator(operator_type, nodes, position);
+        if self.expr_limits.exceeds_depth(node.height()) {
+            return Err(ElemError::new(crate::ErrorType::Limit));
+        }
+        operands.push(node);
+
+        Ok(())
+    }
+
+    /// Compiles the arguments of a function. The scanner is
+    /// positioned on the opening parenthesis of the function.
+    /// Built-in function names are not case sensitive (i.e.,
+    /// "If" and "IF" are compiled as the "if" function).
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the function.
+    /// * `position` - Position of the function within the expression text.
+    /// * `depth` - Nesting depth of the function arguments.
+    ///
+    /// # Return
+    ///
+    /// * Compiled function if successful, otherwise error code.
+    fn compile_function(
+        &self,
+        name: &str,
+        position: usize,
+        depth: usize,
+    ) -> Result<ElemExprNode, ElemError> {
+        let function_type: crate::FunctionType;
+        let mut name = String::from(name);
+        let list_function = self.calc_mgr().core_manager().functions();
+        if let Some(o) = list_function.get_key(name.as_str()) {
+            function_type = CoreUtility::get_function(o.value());
+        } else if let Some(o) = list_function.get_key(name.to_lowercase().as_str()) {
+            function_type = CoreUtility::get_function(o.value());
+            name = name.to_lowercase();
+        } else {
+            function_type = crate::FunctionType::None; // User-defined or native function
+        }
+
+        let mut nodes: Vec<ElemExprNode> = Vec::new();
+        let token = self.scanner_mut().scan_token();
+        if !(token == crate::TokenType::Punctuation && self.scanner().get_token().starts_with(')'))
+        {
+            self.scanner_mut().rescan();
+            loop {
+                match self.compile_expr(')', depth) {
+                    Err(e) => return Err(e),
+                    Ok(o) => nodes.push(o),
+                }
+                if self.scanner().get_token().starts_with(')') {
+                    break;
+                }
             }
-            crate::TokenType::Decimal => {
-                elem_result_symbol.set_decimal(elem.sym_decimal());
+        }
+
+        if function_type != crate::FunctionType::None {
+            let (min_args, max_args) = CalcExpression::function_arguments(function_type);
+            if nodes.len() < min_args {
+                return Err(ElemError::new(crate::ErrorType::Incomplete));
             }
-            crate::TokenType::String => {
-                elem_result_symbol.set_string(elem.sym_string());
+            if nodes.len() > max_args {
+                return Err(ElemError::new(crate::ErrorType::RightParen));
             }
-            _ => {}
         }
 
-        Ok(elem_result_symbol)
+        let node = ElemExprNode::new_function(name.as_str(), function_type, nodes, position);
+        if self.expr_limits.exceeds_depth(node.height()) {
+            return Err(ElemError::new(crate::ErrorType::Limit));
+        }
+
+        Ok(node)
     }
 
-    /// Parses an unknown type of symbol from the expression.
-    /// This method will first look for a sub-expression and,
-    /// if found, instanciate this element recursively to
-    /// return the result of the sub-expression. However,
-    /// if a sub-expression is not found, this method will parse
-    /// the symbol, lookup it's value in the symbol table, and
-    /// return the symbol's value as the result.
+    /// Returns the minimum and maximum number of arguments of a function.
     ///
     /// # Arguments
     ///
+    /// * `function_type` - The function type.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn function_arguments(function_type: crate::FunctionType) -> (usize, usize) {
+        match function_type {
+            crate::FunctionType::DateNow => (0, 0),
+            crate::FunctionType::Am
+            | crate::FunctionType::Default
+            | crate::FunctionType::Max
+            | crate::FunctionType::Min
+            | crate::FunctionType::Set => (2, 2),
+            crate::FunctionType::If
+            | crate::FunctionType::Mid
+            | crate::FunctionType::Parse
+            | crate::FunctionType::Replace => (3, 3),
+            crate::FunctionType::DateFiscal | crate::FunctionType::Descriptor => (4, 4),
+            crate::FunctionType::DateDiff | crate::FunctionType::DateNew => (5, 5),
+            crate::FunctionType::Round => (1, 3),
+            crate::FunctionType::RoundFraction => (2, 3),
+            crate::FunctionType::Effect | crate::FunctionType::Nominal => (2, 2),
+            crate::FunctionType::Fv
+            | crate::FunctionType::Nper
+            | crate::FunctionType::Pmt
+            | crate::FunctionType::Pv => (3, 5),
+            crate::FunctionType::Ipmt | crate::FunctionType::Ppmt => (4, 6),
+            crate::FunctionType::Rate => (3, 6),
+            crate::FunctionType::Npv => (2, usize::MAX),
+            crate::FunctionType::Xnpv => (3, 3),
+            crate::FunctionType::AmList => (1, 4),
+            crate::FunctionType::Filter => (3, 3),
+            crate::FunctionType::List => (0, usize::MAX),
+            crate::FunctionType::DateAddMonths => (2, 3),
+            crate::FunctionType::DateNextBusinessDay => (1, 2),
+            _ => (1, 1),
+        }
+    }
+
+    /// Executes a compiled expression against the symbol table.
+    /// If an error occurs, the expression text and the position
+    /// of the failing node are recorded in the error.
+    ///
+    /// # Arguments
+    ///
+    /// * `elem_expr_node` - The compiled expression.
     /// * `list_am_opt` - Amortization list.
     /// * `elem_balance_result_opt` - Balance results.
-    /// * `symbol_name` - If true return the symbol name itself,
-    ///     otherwise lookup the symbol name in the symbol table and
-    ///     return it's value.
     ///
     /// # Return
     ///
     /// * Result symbol if successful, otherwise error code.
-
-    pub fn get_expr_symbol(
+    pub fn evaluate_node(
         &self,
+        elem_expr_node: &ElemExprNode,
         list_am_opt: Option<&ListAmortization>,
         elem_balance_result_opt: Option<&ElemBalanceResult>,
-        symbol_name: bool,
-    ) -> Result<ElemSymbol, crate::ErrorType> {
-        let mut elem_result_symbol = ElemSymbol::new();
-        if self.scanner_mut().scan_subexpression() {
-            let mut elem_symbol_opt = None;
-            let token: String;
+    ) -> Result<ElemSymbol, ElemError> {
+        let steps = self.steps.fetch_add(1, Ordering::Relaxed) + 1;
+        let depth = self.depth.fetch_add(1, Ordering::Relaxed) + 1;
+        if self.expr_limits.exceeds_steps(steps) || self.expr_limits.exceeds_depth(depth) {
+            self.depth.fetch_sub(1, Ordering::Relaxed);
+            let mut e = ElemError::new(crate::ErrorType::Limit);
+            e.set_expression(self.expression_text().as_str(), elem_expr_node.position());
+            return Err(e);
+        }
 
-            {
-                let mut sym_table = self.symbol_table_mut();
-                token = String::from(self.scanner().get_token());
-                match sym_table.get_symbol_mut(token.as_str()) {
-                    None => {}
-                    Some(o) => {
-                        elem_symbol_opt = Option::from(o.copy());
-                    }
-                }
-            }
-            if elem_symbol_opt.is_none() {
-                let calc_expression = CalcExpression::new_with_symbol_table(
-                    &self.calc_manager,
-                    &self.symbol_table,
-                    self.fiscal_year_start,
-                    self.decimal_digits,
-                    token.as_str(),
-                );
+        if self.trace {
+            let elem_expr_trace = ElemExprTrace::new(
+                elem_expr_node.node_type(),
+                self.trace_text(elem_expr_node).as_str(),
+                elem_expr_node.position(),
+            );
+            self.trace_stack_mut().push(elem_expr_trace);
+        }
 
-                let result = calc_expression.evaluate(list_am_opt, elem_balance_result_opt);
-                match result {
-                    Err(e) => {
-                        return Err(e);
-                    }
-                    Ok(o) => {
-                        elem_symbol_opt = Option::from(o);
+        let result = match elem_expr_node.node_type() {
+            crate::NodeType::Constant => Ok(elem_expr_node.value().copy()),
+            crate::NodeType::Symbol => {
+                self.evaluate_symbol(elem_expr_node, list_am_opt, elem_balance_result_opt)
+            }
+            crate::NodeType::Operator => {
+                self.evaluate_operator(elem_expr_node, list_am_opt, elem_balance_result_opt)
+            }
+            crate::NodeType::Function => {
+                self.function(elem_expr_node, list_am_opt, elem_balance_result_opt)
+            }
+            crate::NodeType::Sequence => {
+                let mut result = Err(ElemError::new(crate::ErrorType::Incomplete));
+                for node in elem_expr_node.nodes().iter() {
+                    result = self.evaluate_node(node, list_am_opt, elem_balance_result_opt);
+                    if result.is_err() {
+                        break;
                     }
                 }
+                result
             }
+        };
 
-            let elem_symbol: &mut ElemSymbol = match elem_symbol_opt.as_mut() {
-                None => return Err(crate::ErrorType::Incomplete),
-                Some(o) => o,
-            };
+        self.depth.fetch_sub(1, Ordering::Relaxed);
 
-            match elem_symbol.sym_type() {
-                crate::TokenType::Integer => {
-                    elem_result_symbol.set_integer(elem_symbol.sym_integer());
-                }
-                crate::TokenType::Decimal => {
-                    elem_result_symbol.set_decimal(elem_symbol.sym_decimal());
+        let result = match result {
+            Err(e) => Err(e),
+            Ok(o) => self.check_limits(o),
+        };
+
+        if self.trace {
+            let mut trace_stack = self.trace_stack_mut();
+            if let Some(mut o) = trace_stack.pop() {
+                match result.as_ref() {
+                    Err(e) => o.set_error(e.kind()),
+                    Ok(o2) => o.set_result(o2.clone()),
                 }
-                crate::TokenType::String => {
-                    elem_result_symbol.set_string(elem_symbol.sym_string());
+                if let Some(o2) = trace_stack.last_mut() {
+                    o2.add_node(o);
                 }
-                _ => {}
             }
-
-            return Ok(elem_result_symbol);
         }
-        let scan_type = self.scanner().get_type();
 
-        let mut signed: bool = false;
-        if scan_type == crate::TokenType::Punctuation && self.scanner().get_token().starts_with('-')
-        {
-            self.scanner_mut().scan_token();
-            signed = true;
+        match result {
+            Err(mut e) => {
+                e.set_expression(self.expression_text().as_str(), elem_expr_node.position());
+                Err(e)
+            }
+            Ok(o) => Ok(o),
         }
+    }
 
-        let mut text: String;
-        {
-            let scanner = self.scanner();
-            text = String::from(scanner.get_token());
-        }
+    /// Checks a value against the string length and numeric
+    /// magnitude limits of the expression.
+    ///
+    /// # Arguments
+    ///
+    /// * `elem_symbol` - The value.
+    ///
+    /// # Return
+    ///
+    /// * The value if within the limits, otherwise error code.
+    fn check_limits(&self, elem_symbol: ElemSymbol) -> Result<ElemSymbol, ElemError> {
+        let exceeds = match elem_symbol.sym_type() {
+            crate::TokenType::Integer => self
+                .expr_limits
+                .exceeds_magnitude(Decimal::from(elem_symbol.sym_integeri())),
+            crate::TokenType::Decimal => self
+                .expr_limits
+                .exceeds_magnitude(elem_symbol.sym_decimal()),
+            crate::TokenType::String => self
+                .expr_limits
+                .exceeds_string_length(elem_symbol.sym_string().chars().count()),
+            _ => false,
+        };
 
-        match scan_type {
-            crate::TokenType::Integer => {
-                let mut result = CoreUtility::parse_integeri(text.as_str());
-                if signed {
-                    result = -result;
-                }
-                elem_result_symbol.set_integeri(result);
-            }
-            crate::TokenType::Decimal => {
-                let mut dresult = CoreUtility::parse_decimal(text.as_str());
-                if signed {
-                    dresult = -dresult;
-                }
-                elem_result_symbol.set_decimal(dresult);
-            }
-            crate::TokenType::String => {
-                elem_result_symbol.set_string(text.as_str());
-            }
-            crate::TokenType::Alpha => {
-                let token = self.scanner_mut().scan_token();
-                if token == crate::TokenType::Punctuation
-                    && self.scanner().get_token().starts_with('(')
-                {
-                    let result = self.function(text.as_str(), list_am_opt, elem_balance_result_opt);
-                    match result {
-                        Err(e) => {
-                            return Err(e);
-                        }
-                        Ok(o) => {
-                            elem_result_symbol = o;
-                        }
-                    }
-                } else {
-                    if !(self.scanner().get_type() == crate::TokenType::Punctuation
-                        && self.scanner().get_token().starts_with('['))
-                    {
-                        self.scanner_mut().rescan();
-                    } else {
-                        self.scanner_mut().scan_token();
-                        let result = self.get_expr_integer(list_am_opt, elem_balance_result_opt);
-                        match result {
-                            Err(e) => {
-                                return Err(e);
-                            }
-                            Ok(o) => {
-                                elem_result_symbol = o;
-                            }
-                        }
-                        if !(self.scanner().get_type() == crate::TokenType::Punctuation
-                            && self.scanner().get_token().starts_with(']'))
-                        {
-                            return Err(crate::ErrorType::RightBracket);
-                        }
-                        text = format!("{}[{}]", text, elem_result_symbol.sym_integer());
-                    }
-                    if symbol_name {
-                        elem_result_symbol.set_string(text.as_str());
-                    } else {
-                        let sym_table = self.symbol_table();
-                        let elem_symbol_opt = sym_table.get_symbol(text.as_str());
-                        if elem_symbol_opt.is_none() {
-                            return Err(crate::ErrorType::InvalidSymbol);
-                        }
-                        match elem_symbol_opt.as_ref() {
-                            None => {
-                                return Err(crate::ErrorType::Incomplete);
-                            }
-                            Some(o) => match o.sym_type() {
-                                crate::TokenType::Integer => {
-                                    elem_result_symbol.set_integer(o.sym_integer());
-                                }
-                                crate::TokenType::Decimal => {
-                                    elem_result_symbol.set_decimal(o.sym_decimal());
-                                }
-                                crate::TokenType::String => {
-                                    elem_result_symbol.set_string(o.sym_string());
-                                }
-                                _ => {}
-                            },
-                        }
-                    }
-                }
-                if signed {
-                    match elem_result_symbol.sym_type() {
-                        crate::TokenType::Integer => {
-                            elem_result_symbol.set_integeri(-elem_result_symbol.sym_integeri());
-                        }
-                        crate::TokenType::Decimal => {
-                            elem_result_symbol.set_decimal(-elem_result_symbol.sym_decimal());
-                        }
-                        _ => {}
-                    }
-                }
-            }
-            _ => {
-                return Err(crate::ErrorType::InvalidToken);
-            }
+        if exceeds {
+            return Err(ElemError::new(crate::ErrorType::Limit));
         }
 
-        Ok(elem_result_symbol)
+        Ok(elem_symbol)
     }
 
-    /// Parses an integer symbol from the expression.
+    /// Returns the name of a symbol node. The index
+    /// of an indexed symbol (i.e., name[index]) is
+    /// evaluated and included in the name.
     ///
     /// # Arguments
     ///
+    /// * `elem_expr_node` - The symbol node.
     /// * `list_am_opt` - Amortization list.
     /// * `elem_balance_result_opt` - Balance results.
     ///
     /// # Return
     ///
-    /// * Result symbol if successful, otherwise error code.
-
-    pub fn get_expr_integer(
+    /// * Symbol name if successful, otherwise error code.
+    fn symbol_name(
         &self,
+        elem_expr_node: &ElemExprNode,
         list_am_opt: Option<&ListAmortization>,
         elem_balance_result_opt: Option<&ElemBalanceResult>,
-    ) -> Result<ElemSymbol, crate::ErrorType> {
-        let result = self.get_expr_symbol(list_am_opt, elem_balance_result_opt, false);
-
-        match result.as_ref() {
-            Err(e) => Err(*e),
-            Ok(o) => {
-                if o.sym_type() != crate::TokenType::Integer {
-                    return Err(crate::ErrorType::Integer);
-                }
-
-                result
-            }
+    ) -> Result<String, ElemError> {
+        match elem_expr_node.nodes().first() {
+            None => Ok(String::from(elem_expr_node.name())),
+            Some(o) => match self.get_expr_integer(o, list_am_opt, elem_balance_result_opt) {
+                Err(e) => Err(e),
+                Ok(o) => Ok(format!("{}[{}]", elem_expr_node.name(), o.sym_integer())),
+            },
         }
     }
 
-    /// Parses a Decimal symbol from the expression.
+    /// Executes a symbol node by looking up
+    /// the symbol's value in the symbol table.
     ///
     /// # Arguments
     ///
+    /// * `elem_expr_node` - The symbol node.
     /// * `list_am_opt` - Amortization list.
     /// * `elem_balance_result_opt` - Balance results.
     ///
@@ -1266,28 +1112,601 @@ impl CalcExpression {
     ///
     /// * Result symbol if successful, otherwise error code.
 
-    pub fn get_expr_decimal(
+    fn evaluate_symbol(
         &self,
+        elem_expr_node: &ElemExprNode,
         list_am_opt: Option<&ListAmortization>,
         elem_balance_result_opt: Option<&ElemBalanceResult>,
-    ) -> Result<ElemSymbol, crate::ErrorType> {
-        let result = self.get_expr_symbol(list_am_opt, elem_balance_result_opt, false);
+    ) -> Result<ElemSymbol, ElemError> {
+        let name = self.symbol_name(elem_expr_node, list_am_opt, elem_balance_result_opt)?;
 
-        match result.as_ref() {
-            Err(e) => Err(*e),
-            Ok(o) => {
+        if self.trace {
+            if let Some(o) = self.trace_stack_mut().last_mut() {
+                o.set_text(name.as_str()); // Include the index
+            }
+        }
+
+        match self.get_symbol(name.as_str()) {
+            None => Err(ElemError::new(crate::ErrorType::InvalidSymbol)),
+            Some(o) => Ok(o),
+        }
+    }
+
+    /// Executes an operator node. Arithmetic that overflows the
+    /// integer or Decimal range fails with the Limit error.
+    ///
+    /// # Arguments
+    ///
+    /// * `elem_expr_node` - The operator node.
+    /// * `list_am_opt` - Amortization list.
+    /// * `elem_balance_result_opt` - Balance results.
+    ///
+    /// # Return
+    ///
+    /// * Result symbol if successful, otherwise error code.
+    fn evaluate_operator(
+        &self,
+        elem_expr_node: &ElemExprNode,
+        list_am_opt: Option<&ListAmortization>,
+        elem_balance_result_opt: Option<&ElemBalanceResult>,
+    ) -> Result<ElemSymbol, ElemError> {
+        let nodes = elem_expr_node.nodes();
+        let mut elem_symbol1: ElemSymbol;
+        let mut elem_symbol2: ElemSymbol;
+
+        if nodes.len() == 1 {
+            elem_symbol2 = self.evaluate_node(&nodes[0], list_am_opt, elem_balance_result_opt)?;
+            elem_symbol1 = elem_symbol2.copy();
+        } else if nodes.len() == 2 {
+            elem_symbol1 = self.evaluate_node(&nodes[0], list_am_opt, elem_balance_result_opt)?;
+            elem_symbol2 = self.evaluate_node(&nodes[1], list_am_opt, elem_balance_result_opt)?;
+            if elem_symbol1.sym_type() == crate::TokenType::Integer
+                && elem_symbol2.sym_type() == crate::TokenType::Decimal
+            {
+                elem_symbol1.set_decimal(Decimal::from(elem_symbol1.sym_integeri()));
+            } else if elem_symbol1.sym_type() == crate::TokenType::Decimal
+                && elem_symbol2.sym_type() == crate::TokenType::Integer
+            {
+                elem_symbol2.set_decimal(Decimal::from(elem_symbol2.sym_integeri()));
+            }
+        } else {
+            return Err(ElemError::new(crate::ErrorType::MissingOperand));
+        }
+
+        if elem_symbol1.sym_type() == crate::TokenType::List
+            || elem_symbol2.sym_type() == crate::TokenType::List
+        {
+            return Err(ElemError::new(crate::ErrorType::InvalidOperand));
+        }
+
+        match elem_expr_node.operator_type() {
+            crate::OperatorType::And => {
+                if elem_symbol1.sym_type() != crate::TokenType::Integer
+                    || elem_symbol2.sym_type() != crate::TokenType::Integer
+                {
+                    return Err(ElemError::new(crate::ErrorType::InvalidOperand));
+                }
+                if elem_symbol1.sym_integer() != 0 && elem_symbol2.sym_integer() != 0 {
+                    elem_symbol1.set_integer(1);
+                } else {
+                    elem_symbol1.set_integer(0);
+                }
+            }
+            crate::OperatorType::Or => {
+                if elem_symbol1.sym_type() != crate::TokenType::Integer
+                    || elem_symbol2.sym_type() != crate::TokenType::Integer
+                {
+                    return Err(ElemError::new(crate::ErrorType::InvalidOperand));
+                }
+                if elem_symbol1.sym_integer() != 0 || elem_symbol2.sym_integer() != 0 {
+                    elem_symbol1.set_integer(1);
+                } else {
+                    elem_symbol1.set_integer(0);
+                }
+            }
+            crate::OperatorType::Greater => {
+                if elem_symbol1.sym_type() == crate::TokenType::String
+                    || elem_symbol2.sym_type() == crate::TokenType::String
+                {
+                    return Err(ElemError::new(crate::ErrorType::InvalidOperand));
+                }
+                if elem_symbol1.sym_type() == crate::TokenType::Integer {
+                    if elem_symbol1.sym_integeri() > elem_symbol2.sym_integeri() {
+                        elem_symbol1.set_integer(1);
+                    } else {
+                        elem_symbol1.set_integer(0);
+                    }
+                } else if elem_symbol1.sym_decimal() > elem_symbol2.sym_decimal() {
+                    elem_symbol1.set_integer(1);
+                } else {
+                    elem_symbol1.set_integer(0);
+                }
+            }
+            crate::OperatorType::Less => {
+                if elem_symbol1.sym_type() == crate::TokenType::String
+                    || elem_symbol2.sym_type() == crate::TokenType::String
+                {
+                    return Err(ElemError::new(crate::ErrorType::InvalidOperand));
+                }
+                if elem_symbol1.sym_type() == crate::TokenType::Integer {
+                    if elem_symbol1.sym_integeri() < elem_symbol2.sym_integeri() {
+                        elem_symbol1.set_integer(1);
+                    } else {
+                        elem_symbol1.set_integer(0);
+                    }
+                } else if elem_symbol1.sym_decimal() < elem_symbol2.sym_decimal() {
+                    elem_symbol1.set_integer(1);
+                } else {
+                    elem_symbol1.set_integer(0);
+                }
+            }
+            crate::OperatorType::GreaterEqual => {
+                if elem_symbol1.sym_type() == crate::TokenType::String
+                    || elem_symbol2.sym_type() == crate::TokenType::String
+                {
+                    return Err(ElemError::new(crate::ErrorType::InvalidOperand));
+                }
+                if elem_symbol1.sym_type() == crate::TokenType::Integer {
+                    if elem_symbol1.sym_integeri() >= elem_symbol2.sym_integeri() {
+                        elem_symbol1.set_integer(1);
+                    } else {
+                        elem_symbol1.set_integer(0);
+                    }
+                } else if elem_symbol1.sym_decimal() >= elem_symbol2.sym_decimal() {
+                    elem_symbol1.set_integer(1);
+                } else {
+                    elem_symbol1.set_integer(0);
+                }
+            }
+            crate::OperatorType::LessEqual => {
+                if elem_symbol1.sym_type() == crate::TokenType::String
+                    || elem_symbol2.sym_type() == crate::TokenType::String
+                {
+                    return Err(ElemError::new(crate::ErrorType::InvalidOperand));
+                }
+                if elem_symbol1.sym_type() == crate::TokenType::Integer {
+                    if elem_symbol1.sym_integeri() <= elem_symbol2.sym_integeri() {
+                        elem_symbol1.set_integer(1);
+                    } else {
+                        elem_symbol1.set_integer(0);
+                    }
+                } else if elem_symbol1.sym_decimal() <= elem_symbol2.sym_decimal() {
+                    elem_symbol1.set_integer(1);
+                } else {
+                    elem_symbol1.set_integer(0);
+                }
+            }
+            crate::OperatorType::Equal => {
+                if elem_symbol1.sym_type() != elem_symbol2.sym_type() {
+                    return Err(ElemError::new(crate::ErrorType::InvalidOperand));
+                }
+                if elem_symbol1.sym_type() == crate::TokenType::Integer {
+                    if elem_symbol1.sym_integeri() == elem_symbol2.sym_integeri() {
+                        elem_symbol1.set_integer(1);
+                    } else {
+                        elem_symbol1.set_integer(0);
+                    }
+                } else if elem_symbol1.sym_type() == crate::TokenType::Decimal {
+                    if elem_symbol1.sym_decimal() == elem_symbol2.sym_decimal() {
+                        elem_symbol1.set_integer(1);
+                    } else {
+                        elem_symbol1.set_integer(0);
+                    }
+                } else if elem_symbol1.sym_string() == elem_symbol2.sym_string() {
+                    elem_symbol1.set_integer(1);
+                } else {
+                    elem_symbol1.set_integer(0);
+                }
+            }
+            crate::OperatorType::NotEqual => {
+                if elem_symbol1.sym_type() != elem_symbol2.sym_type() {
+                    return Err(ElemError::new(crate::ErrorType::InvalidOperand));
+                }
+                if elem_symbol1.sym_type() == crate::TokenType::Integer {
+                    if elem_symbol1.sym_integeri() != elem_symbol2.sym_integeri() {
+                        elem_symbol1.set_integer(1);
+                    } else {
+                        elem_symbol1.set_integer(0);
+                    }
+                } else if elem_symbol1.sym_type() == crate::TokenType::Decimal {
+                    if elem_symbol1.sym_decimal() != elem_symbol2.sym_decimal() {
+                        elem_symbol1.set_integer(1);
+                    } else {
+                        elem_symbol1.set_integer(0);
+                    }
+                } else if elem_symbol1.sym_string() == elem_symbol2.sym_string() {
+                    elem_symbol1.set_integer(1);
+                } else {
+                    elem_symbol1.set_integer(0);
+                }
+            }
+            crate::OperatorType::Plus => {
+                if elem_symbol1.sym_type() != elem_symbol2.sym_type() {
+                    return Err(ElemError::new(crate::ErrorType::InvalidOperand));
+                }
+                if elem_symbol1.sym_type() == crate::TokenType::Integer {
+                    match elem_symbol1
+                        .sym_integeri()
+                        .checked_add(elem_symbol2.sym_integeri())
+                    {
+                        None => return Err(ElemError::new(crate::ErrorType::Limit)),
+                        Some(o) => elem_symbol1.set_integeri(o),
+                    }
+                } else if elem_symbol1.sym_type() == crate::TokenType::Decimal {
+                    match elem_symbol1
+                        .sym_decimal()
+                        .checked_add(elem_symbol2.sym_decimal())
+                    {
+                        None => return Err(ElemError::new(crate::ErrorType::Limit)),
+                        Some(o) => elem_symbol1.set_decimal(o),
+                    }
+                } else {
+                    elem_symbol1.set_string(
+                        format!("{}{}", elem_symbol1.sym_string(), elem_symbol2.sym_string())
+                            .as_str(),
+                    );
+                }
+            }
+            crate::OperatorType::Minus => {
+                if elem_symbol1.sym_type() == crate::TokenType::String
+                    || elem_symbol2.sym_type() == crate::TokenType::String
+                {
+                    return Err(ElemError::new(crate::ErrorType::InvalidOperand));
+                }
+                if elem_symbol1.sym_type() == crate::TokenType::Integer {
+                    match elem_symbol1
+                        .sym_integeri()
+                        .checked_sub(elem_symbol2.sym_integeri())
+                    {
+                        None => return Err(ElemError::new(crate::ErrorType::Limit)),
+                        Some(o) => elem_symbol1.set_integeri(o),
+                    }
+                } else {
+                    match elem_symbol1
+                        .sym_decimal()
+                        .checked_sub(elem_symbol2.sym_decimal())
+                    {
+                        None => return Err(ElemError::new(crate::ErrorType::Limit)),
+                        Some(o) => elem_symbol1.set_decimal(o),
+                    }
+                }
+            }
+            crate::OperatorType::Times => {
+                if elem_symbol1.sym_type() == crate::TokenType::String
+                    || elem_symbol2.sym_type() == crate::TokenType::String
+                {
+                    return Err(ElemError::new(crate::ErrorType::InvalidOperand));
+                }
+                if elem_symbol1.sym_type() == crate::TokenType::Integer {
+                    match elem_symbol1
+                        .sym_integeri()
+                        .checked_mul(elem_symbol2.sym_integeri())
+                    {
+                        None => return Err(ElemError::new(crate::ErrorType::Limit)),
+                        Some(o) => elem_symbol1.set_integeri(o),
+                    }
+                } else {
+                    match elem_symbol1
+                        .sym_decimal()
+                        .checked_mul(elem_symbol2.sym_decimal())
+                    {
+                        None => return Err(ElemError::new(crate::ErrorType::Limit)),
+                        Some(o) => elem_symbol1.set_decimal(o),
+                    }
+                }
+            }
+            crate::OperatorType::Divide => {
+                if elem_symbol1.sym_type() == crate::TokenType::String
+                    || elem_symbol2.sym_type() == crate::TokenType::String
+                {
+                    return Err(ElemError::new(crate::ErrorType::InvalidOperand));
+                }
+                if elem_symbol1.sym_type() == crate::TokenType::Integer {
+                    if elem_symbol2.sym_integeri() == 0 {
+                        elem_symbol1.set_integer(0); // Do not error on divide by zero
+                    } else {
+                        match elem_symbol1
+                            .sym_integeri()
+                            .checked_div(elem_symbol2.sym_integeri())
+                        {
+                            None => return Err(ElemError::new(crate::ErrorType::Limit)),
+                            Some(o) => elem_symbol1.set_integeri(o),
+                        }
+                    }
+                } else if elem_symbol2.sym_decimal() == dec!(0.0) {
+                    elem_symbol1.set_decimal(dec!(0.0)); // Do not error on divide by zero
+                } else {
+                    match elem_symbol1
+                        .sym_decimal()
+                        .checked_div(elem_symbol2.sym_decimal())
+                    {
+                        None => return Err(ElemError::new(crate::ErrorType::Limit)),
+                        Some(o) => elem_symbol1.set_decimal(o),
+                    }
+                }
+            }
+            crate::OperatorType::Modulus => {
+                if elem_symbol1.sym_type() == crate::TokenType::String
+                    || elem_symbol2.sym_type() == crate::TokenType::String
+                {
+                    return Err(ElemError::new(crate::ErrorType::InvalidOperand));
+                }
+                if elem_symbol1.sym_type() == crate::TokenType::Integer {
+                    if elem_symbol2.sym_integeri() == 0 {
+                        elem_symbol1.set_integer(0); // Do not error on divide by zero
+                    } else {
+                        match elem_symbol1
+                            .sym_integeri()
+                            .checked_rem(elem_symbol2.sym_integeri())
+                        {
+                            None => return Err(ElemError::new(crate::ErrorType::Limit)),
+                            Some(o) => elem_symbol1.set_integeri(o),
+                        }
+                    }
+                } else if elem_symbol2.sym_decimal() == dec!(0.0) {
+                    elem_symbol1.set_decimal(dec!(0.0)); // Do not error on divide by zero
+                } else {
+                    match elem_symbol1
+                        .sym_decimal()
+                        .checked_rem(elem_symbol2.sym_decimal())
+                    {
+                        None => return Err(ElemError::new(crate::ErrorType::Limit)),
+                        Some(o) => elem_symbol1.set_decimal(o),
+                    }
+                }
+            }
+            crate::OperatorType::Exponent => {
+                if elem_symbol1.sym_type() == crate::TokenType::String
+                    || elem_symbol2.sym_type() == crate::TokenType::String
+                {
+                    return Err(ElemError::new(crate::ErrorType::InvalidOperand));
+                }
+                if elem_symbol1.sym_type() == crate::TokenType::Integer {
+                    if elem_symbol2.sym_integeri() < 0 {
+                        return Err(ElemError::new(crate::ErrorType::InvalidOperand));
+                    }
+                    match elem_symbol1
+                        .sym_integeri()
+                        .checked_pow(elem_symbol2.sym_integeri() as u32)
+                    {
+                        None => return Err(ElemError::new(crate::ErrorType::Limit)),
+                        Some(o) => elem_symbol1.set_integeri(o),
+                    }
+                } else {
+                    match elem_symbol2.sym_decimal().to_usize() {
+                        None => {
+                            return Err(ElemError::new(crate::ErrorType::InvalidOperand));
+                        }
+                        Some(o) => {
+                            match CoreUtility::checked_decimal_pow(elem_symbol1.sym_decimal(), o) {
+                                None => return Err(ElemError::new(crate::ErrorType::Limit)),
+                                Some(o) => elem_symbol1.set_decimal(o),
+                            }
+                        }
+                    }
+                }
+            }
+            crate::OperatorType::UnaryMinus => {
+                if elem_symbol2.sym_type() == crate::TokenType::String {
+                    return Err(ElemError::new(crate::ErrorType::InvalidOperand));
+                }
+                if elem_symbol2.sym_type() == crate::TokenType::Integer {
+                    match elem_symbol2.sym_integeri().checked_neg() {
+                        None => return Err(ElemError::new(crate::ErrorType::Limit)),
+                        Some(o) => elem_symbol1.set_integeri(o),
+                    }
+                } else {
+                    elem_symbol1.set_decimal(-elem_symbol2.sym_decimal());
+                }
+            }
+            crate::OperatorType::UnaryNot => {
+                if elem_symbol2.sym_type() != crate::TokenType::Integer {
+                    return Err(ElemError::new(crate::ErrorType::InvalidOperand));
+                }
+                if elem_symbol2.sym_integeri() == 0 {
+                    elem_symbol1.set_integer(1);
+                } else {
+                    elem_symbol1.set_integer(0);
+                }
+            }
+            _ => {}
+        }
+
+        Ok(elem_symbol1)
+    }
+
+    /// Executes an argument node returning an unknown type of symbol.
+    ///
+    /// # Arguments
+    ///
+    /// * `elem_expr_node` - The argument node.
+    /// * `list_am_opt` - Amortization list.
+    /// * `elem_balance_result_opt` - Balance results.
+    /// * `symbol_name` - If true and the argument is a symbol, return the
+    ///   symbol name itself, otherwise lookup the symbol name in the
+    ///   symbol table and return it's value.
+    ///
+    /// # Return
+    ///
+    /// * Result symbol if successful, otherwise error code.
+    pub fn get_expr_symbol(
+        &self,
+        elem_expr_node: &ElemExprNode,
+        list_am_opt: Option<&ListAmortization>,
+        elem_balance_result_opt: Option<&ElemBalanceResult>,
+        symbol_name: bool,
+    ) -> Result<ElemSymbol, ElemError> {
+        if symbol_name && elem_expr_node.node_type() == crate::NodeType::Symbol {
+            let mut elem_result_symbol = ElemSymbol::new();
+            match self.symbol_name(elem_expr_node, list_am_opt, elem_balance_result_opt) {
+                Err(e) => return Err(e),
+                Ok(o) => elem_result_symbol.set_string(o.as_str()),
+            }
+            return Ok(elem_result_symbol);
+        }
+
+        self.evaluate_node(elem_expr_node, list_am_opt, elem_balance_result_opt)
+    }
+
+    /// Executes an argument node returning an integer symbol.
+    ///
+    /// # Arguments
+    ///
+    /// * `elem_expr_node` - The argument node.
+    /// * `list_am_opt` - Amortization list.
+    /// * `elem_balance_result_opt` - Balance results.
+    ///
+    /// # Return
+    ///
+    /// * Result symbol if successful, otherwise error code.
+
+    pub fn get_expr_integer(
+        &self,
+        elem_expr_node: &ElemExprNode,
+        list_am_opt: Option<&ListAmortization>,
+        elem_balance_result_opt: Option<&ElemBalanceResult>,
+    ) -> Result<ElemSymbol, ElemError> {
+        let result =
+            self.get_expr_symbol(elem_expr_node, list_am_opt, elem_balance_result_opt, false);
+
+        match result.as_ref() {
+            Err(e) => Err(e.clone()),
+            Ok(o) => {
+                if o.sym_type() != crate::TokenType::Integer {
+                    return Err(ElemError::new(crate::ErrorType::Integer));
+                }
+
+                result
+            }
+        }
+    }
+
+    /// Executes an argument node returning a date
+    /// (an integer in YYYYMMDD form).
+    ///
+    /// # Arguments
+    ///
+    /// * `elem_expr_node` - The argument node.
+    /// * `list_am_opt` - Amortization list.
+    /// * `elem_balance_result_opt` - Balance results.
+    ///
+    /// # Return
+    ///
+    /// * Date if successful, otherwise error code.
+    pub fn get_expr_date(
+        &self,
+        elem_expr_node: &ElemExprNode,
+        list_am_opt: Option<&ListAmortization>,
+        elem_balance_result_opt: Option<&ElemBalanceResult>,
+    ) -> Result<NaiveDate, ElemError> {
+        let elem_symbol =
+            self.get_expr_integer(elem_expr_node, list_am_opt, elem_balance_result_opt)?;
+
+        match CoreUtility::integer_to_date(elem_symbol.sym_integer()) {
+            None => Err(ElemError::new(crate::ErrorType::Date)),
+            Some(o) => Ok(o),
+        }
+    }
+
+    /// Executes an argument node returning a Decimal symbol.
+    ///
+    /// # Arguments
+    ///
+    /// * `elem_expr_node` - The argument node.
+    /// * `list_am_opt` - Amortization list.
+    /// * `elem_balance_result_opt` - Balance results.
+    ///
+    /// # Return
+    ///
+    /// * Result symbol if successful, otherwise error code.
+
+    pub fn get_expr_decimal(
+        &self,
+        elem_expr_node: &ElemExprNode,
+        list_am_opt: Option<&ListAmortization>,
+        elem_balance_result_opt: Option<&ElemBalanceResult>,
+    ) -> Result<ElemSymbol, ElemError> {
+        let result =
+            self.get_expr_symbol(elem_expr_node, list_am_opt, elem_balance_result_opt, false);
+
+        match result.as_ref() {
+            Err(e) => Err(e.clone()),
+            Ok(o) => {
                 if o.sym_type() != crate::TokenType::Decimal {
-                    return Err(crate::ErrorType::Decimal);
+                    return Err(ElemError::new(crate::ErrorType::Decimal));
                 }
                 result
             }
         }
     }
 
-    /// Parses a string symbol from the expression.
+    /// Executes an argument node returning a number (an integer
+    /// or a Decimal symbol) as a Decimal.
     ///
     /// # Arguments
     ///
+    /// * `elem_expr_node` - The argument node.
+    /// * `list_am_opt` - Amortization list.
+    /// * `elem_balance_result_opt` - Balance results.
+    ///
+    /// # Return
+    ///
+    /// * Decimal value if successful, otherwise error code.
+    pub fn get_expr_number(
+        &self,
+        elem_expr_node: &ElemExprNode,
+        list_am_opt: Option<&ListAmortization>,
+        elem_balance_result_opt: Option<&ElemBalanceResult>,
+    ) -> Result<Decimal, ElemError> {
+        let elem_symbol =
+            self.get_expr_symbol(elem_expr_node, list_am_opt, elem_balance_result_opt, false)?;
+
+        match elem_symbol.sym_type() {
+            crate::TokenType::Integer => Ok(Decimal::from(elem_symbol.sym_integeri())),
+            crate::TokenType::Decimal => Ok(elem_symbol.sym_decimal()),
+            _ => Err(ElemError::new(crate::ErrorType::Decimal)),
+        }
+    }
+
+    /// Executes the argument nodes of a financial function returning
+    /// their values as Decimals. Omitted optional arguments are zero.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - Function arguments.
+    /// * `count` - Total number of arguments (including optional arguments).
+    /// * `list_am_opt` - Amortization list.
+    /// * `elem_balance_result_opt` - Balance results.
+    ///
+    /// # Return
+    ///
+    /// * Argument values if successful, otherwise error code.
+    fn get_expr_numbers(
+        &self,
+        args: &[ElemExprNode],
+        count: usize,
+        list_am_opt: Option<&ListAmortization>,
+        elem_balance_result_opt: Option<&ElemBalanceResult>,
+    ) -> Result<Vec<Decimal>, ElemError> {
+        let mut values: Vec<Decimal> = Vec::new();
+
+        for arg in args.iter() {
+            match self.get_expr_number(arg, list_am_opt, elem_balance_result_opt) {
+                Err(e) => return Err(e),
+                Ok(o) => values.push(o),
+            }
+        }
+
+        values.resize(count.max(values.len()), dec!(0.0));
+
+        Ok(values)
+    }
+
+    /// Executes an argument node returning a string symbol.
+    ///
+    /// # Arguments
+    ///
+    /// * `elem_expr_node` - The argument node.
     /// * `list_am_opt` - Amortization list.
     /// * `elem_balance_result_opt` - Balance results.
     ///
@@ -1297,142 +1716,1010 @@ impl CalcExpression {
 
     pub fn get_expr_string(
         &self,
+        elem_expr_node: &ElemExprNode,
         list_am_opt: Option<&ListAmortization>,
         elem_balance_result_opt: Option<&ElemBalanceResult>,
-    ) -> Result<ElemSymbol, crate::ErrorType> {
-        let result = self.get_expr_symbol(list_am_opt, elem_balance_result_opt, false);
+    ) -> Result<ElemSymbol, ElemError> {
+        let result =
+            self.get_expr_symbol(elem_expr_node, list_am_opt, elem_balance_result_opt, false);
 
         match result.as_ref() {
-            Err(e) => Err(*e),
+            Err(e) => Err(e.clone()),
             Ok(o) => {
                 if o.sym_type() != crate::TokenType::String {
-                    return Err(crate::ErrorType::String);
+                    return Err(ElemError::new(crate::ErrorType::String));
+                }
+                result
+            }
+        }
+    }
+
+    /// Executes an argument node returning a list symbol.
+    ///
+    /// # Arguments
+    ///
+    /// * `elem_expr_node` - The argument node.
+    /// * `list_am_opt` - Amortization list.
+    /// * `elem_balance_result_opt` - Balance results.
+    ///
+    /// # Return
+    ///
+    /// * Result symbol if successful, otherwise error code.
+    pub fn get_expr_list(
+        &self,
+        elem_expr_node: &ElemExprNode,
+        list_am_opt: Option<&ListAmortization>,
+        elem_balance_result_opt: Option<&ElemBalanceResult>,
+    ) -> Result<ElemSymbol, ElemError> {
+        let result =
+            self.get_expr_symbol(elem_expr_node, list_am_opt, elem_balance_result_opt, false);
+
+        match result.as_ref() {
+            Err(e) => Err(e.clone()),
+            Ok(o) => {
+                if o.sym_type() != crate::TokenType::List {
+                    return Err(ElemError::new(crate::ErrorType::List));
+                }
+                result
+            }
+        }
+    }
+
+    /// Executes a function node. Based upon the function type,
+    /// the appropriate method is called to execute the
+    /// specific function and return the resulting symbol.
+    ///
+    /// # Arguments
+    ///
+    /// * `elem_expr_node` - The function node.
+    /// * `list_am_opt` - Amortization list.
+    /// * `elem_balance_result_opt` - Balance results.
+    ///
+    /// # Return
+    ///
+    /// * Result symbol if successful, otherwise error code.
+
+    pub fn function(
+        &self,
+        elem_expr_node: &ElemExprNode,
+        list_am_opt: Option<&ListAmortization>,
+        elem_balance_result_opt: Option<&ElemBalanceResult>,
+    ) -> Result<ElemSymbol, ElemError> {
+        let args = elem_expr_node.nodes();
+
+        match elem_expr_node.function_type() {
+            crate::FunctionType::Abs => {
+                self.function_abs(args, list_am_opt, elem_balance_result_opt)
+            }
+            crate::FunctionType::Am => self.function_am(args, list_am_opt, elem_balance_result_opt),
+            crate::FunctionType::AmList => {
+                self.function_am_list(args, list_am_opt, elem_balance_result_opt)
+            }
+            crate::FunctionType::Avg => {
+                self.function_avg(args, list_am_opt, elem_balance_result_opt)
+            }
+            crate::FunctionType::Cashflow => {
+                self.function_cashflow(args, list_am_opt, elem_balance_result_opt)
+            }
+            crate::FunctionType::Count => {
+                self.function_count(args, list_am_opt, elem_balance_result_opt)
+            }
+            crate::FunctionType::DateAddMonths => {
+                self.function_date_add_months(args, list_am_opt, elem_balance_result_opt)
+            }
+            crate::FunctionType::DateDay => {
+                self.function_date_day(args, list_am_opt, elem_balance_result_opt)
+            }
+            crate::FunctionType::DateDayOfYear => {
+                self.function_date_day_of_year(args, list_am_opt, elem_balance_result_opt)
+            }
+            crate::FunctionType::DateDiff => {
+                self.function_date_diff(args, list_am_opt, elem_balance_result_opt)
+            }
+            crate::FunctionType::DateEndOfMonth => {
+                self.function_date_end_of_month(args, list_am_opt, elem_balance_result_opt)
+            }
+            crate::FunctionType::DateFiscal => {
+                self.function_date_fiscal(args, list_am_opt, elem_balance_result_opt)
+            }
+            crate::FunctionType::DateIsLeapYear => {
+                self.function_date_is_leap_year(args, list_am_opt, elem_balance_result_opt)
+            }
+            crate::FunctionType::DateMonth => {
+                self.function_date_month(args, list_am_opt, elem_balance_result_opt)
+            }
+            crate::FunctionType::DateNew => {
+                self.function_date_new(args, list_am_opt, elem_balance_result_opt)
+            }
+            crate::FunctionType::DateNextBusinessDay => {
+                self.function_date_next_business_day(args, list_am_opt, elem_balance_result_opt)
+            }
+            crate::FunctionType::DateNow => self.function_date_now(),
+            crate::FunctionType::DateWeekday => {
+                self.function_date_weekday(args, list_am_opt, elem_balance_result_opt)
+            }
+            crate::FunctionType::DateYear => {
+                self.function_date_year(args, list_am_opt, elem_balance_result_opt)
+            }
+            crate::FunctionType::Default => {
+                self.function_default(args, list_am_opt, elem_balance_result_opt)
+            }
+            crate::FunctionType::Descriptor => {
+                self.function_descriptor(args, list_am_opt, elem_balance_result_opt)
+            }
+            crate::FunctionType::Decimal => {
+                self.function_decimal(args, list_am_opt, elem_balance_result_opt)
+            }
+            crate::FunctionType::Effect => {
+                self.function_effect(args, list_am_opt, elem_balance_result_opt)
+            }
+            crate::FunctionType::Filter => {
+                self.function_filter(args, list_am_opt, elem_balance_result_opt)
+            }
+            crate::FunctionType::First => {
+                self.function_first(args, list_am_opt, elem_balance_result_opt)
+            }
+            crate::FunctionType::Format => {
+                self.function_format(args, list_am_opt, elem_balance_result_opt)
+            }
+            crate::FunctionType::FormatCurrency => {
+                self.function_format_currency(args, list_am_opt, elem_balance_result_opt)
+            }
+            crate::FunctionType::FormatDate => {
+                self.function_format_date(args, list_am_opt, elem_balance_result_opt)
+            }
+            crate::FunctionType::FormatNumber => {
+                self.function_format_number(args, list_am_opt, elem_balance_result_opt)
+            }
+            crate::FunctionType::Fv => self.function_fv(args, list_am_opt, elem_balance_result_opt),
+            crate::FunctionType::If => self.function_if(args, list_am_opt, elem_balance_result_opt),
+            crate::FunctionType::Integer => {
+                self.function_integer(args, list_am_opt, elem_balance_result_opt)
+            }
+            crate::FunctionType::Ipmt => {
+                self.function_ipmt(args, list_am_opt, elem_balance_result_opt)
+            }
+            crate::FunctionType::Last => {
+                self.function_last(args, list_am_opt, elem_balance_result_opt)
+            }
+            crate::FunctionType::Len => {
+                self.function_len(args, list_am_opt, elem_balance_result_opt)
+            }
+            crate::FunctionType::List => {
+                self.function_list(args, list_am_opt, elem_balance_result_opt)
+            }
+            crate::FunctionType::Lowercase => {
+                self.function_lowercase(args, list_am_opt, elem_balance_result_opt)
+            }
+            crate::FunctionType::Max => {
+                self.function_max(args, list_am_opt, elem_balance_result_opt)
+            }
+            crate::FunctionType::Mid => {
+                self.function_mid(args, list_am_opt, elem_balance_result_opt)
+            }
+            crate::FunctionType::Min => {
+                self.function_min(args, list_am_opt, elem_balance_result_opt)
+            }
+            crate::FunctionType::Nominal => {
+                self.function_nominal(args, list_am_opt, elem_balance_result_opt)
+            }
+            crate::FunctionType::Nper => {
+                self.function_nper(args, list_am_opt, elem_balance_result_opt)
+            }
+            crate::FunctionType::Npv => {
+                self.function_npv(args, list_am_opt, elem_balance_result_opt)
+            }
+            crate::FunctionType::Parse => {
+                self.function_parse(args, list_am_opt, elem_balance_result_opt)
+            }
+            crate::FunctionType::Pmt => {
+                self.function_pmt(args, list_am_opt, elem_balance_result_opt)
+            }
+            crate::FunctionType::Ppmt => {
+                self.function_ppmt(args, list_am_opt, elem_balance_result_opt)
+            }
+            crate::FunctionType::Pr => self.function_pr(args, list_am_opt, elem_balance_result_opt),
+            crate::FunctionType::Pv => self.function_pv(args, list_am_opt, elem_balance_result_opt),
+            crate::FunctionType::Rate => {
+                self.function_rate(args, list_am_opt, elem_balance_result_opt)
+            }
+            crate::FunctionType::Replace => {
+                self.function_replace(args, list_am_opt, elem_balance_result_opt)
+            }
+            crate::FunctionType::Round => {
+                self.function_round(args, list_am_opt, elem_balance_result_opt)
+            }
+            crate::FunctionType::RoundFraction => {
+                self.function_round_fraction(args, list_am_opt, elem_balance_result_opt)
+            }
+            crate::FunctionType::Set => {
+                self.function_set(args, list_am_opt, elem_balance_result_opt)
+            }
+            crate::FunctionType::Sum => {
+                self.function_sum(args, list_am_opt, elem_balance_result_opt)
+            }
+            crate::FunctionType::Trim => {
+                self.function_trim(args, list_am_opt, elem_balance_result_opt)
+            }
+            crate::FunctionType::Type => {
+                self.function_type(args, list_am_opt, elem_balance_result_opt)
+            }
+            crate::FunctionType::Uppercase => {
+                self.function_uppercase(args, list_am_opt, elem_balance_result_opt)
+            }
+            crate::FunctionType::Xnpv => {
+                self.function_xnpv(args, list_am_opt, elem_balance_result_opt)
+            }
+            _ => self.function_user(
+                elem_expr_node.name(),
+                args,
+                list_am_opt,
+                elem_balance_result_opt,
+            ),
+        }
+    }
+
+    /// Find and return a user-defined function by name. The functions
+    /// of the cashflow (or template group) take precedence over the
+    /// functions of the user preferences.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Function name.
+    ///
+    /// # Return
+    ///
+    /// * The user-defined function, otherwise None.
+    fn user_function(&self, name: &str) -> Option<&ElemFunction> {
+        match self.list_function_cashflow.as_ref() {
+            None => {}
+            Some(o) => match o.get_function(name) {
+                None => {}
+                Some(o2) => return Some(o2),
+            },
+        }
+
+        self.calc_mgr()
+            .preferences()
+            .list_function()
+            .get_function(name)
+    }
+
+    /// Executes a user-defined function or a native function
+    /// registered by the host. User-defined functions of the
+    /// cashflow (or template group) are searched first, followed
+    /// by those of the user preferences and finally the native
+    /// functions. The body of a user-defined function is executed
+    /// with a parameter frame that binds the arguments to the
+    /// parameter names. The caller's symbols remain visible within
+    /// the body, but symbols set within the body are added to the
+    /// frame and are not visible to the caller.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Function name.
+    /// * `args` - Function arguments.
+    /// * `list_am_opt` - Amortization list.
+    /// * `elem_balance_result_opt` - Balance results.
+    ///
+    /// # Return
+    ///
+    /// * Result symbol if successful, otherwise error code.
+    fn function_user(
+        &self,
+        name: &str,
+        args: &[ElemExprNode],
+        list_am_opt: Option<&ListAmortization>,
+        elem_balance_result_opt: Option<&ElemBalanceResult>,
+    ) -> Result<ElemSymbol, ElemError> {
+        let mut values: Vec<ElemSymbol> = Vec::new();
+        for arg in args.iter() {
+            match self.evaluate_node(arg, list_am_opt, elem_balance_result_opt) {
+                Err(e) => return Err(e),
+                Ok(o) => values.push(o),
+            }
+        }
+
+        let elem_function = match self.user_function(name) {
+            None => {
+                let native_function = match self.calc_mgr().native_function(name) {
+                    None => return Err(ElemError::new(crate::ErrorType::Function)),
+                    Some(o) => o,
+                };
+                return native_function(&values);
+            }
+            Some(o) => o,
+        };
+
+        let parameters = elem_function.parameters();
+        if values.len() < parameters.len() {
+            return Err(ElemError::new(crate::ErrorType::Incomplete));
+        }
+        if values.len() > parameters.len() {
+            return Err(ElemError::new(crate::ErrorType::RightParen));
+        }
+
+        if self.frames().len() >= crate::MAXIMUM_FUNCTION_DEPTH {
+            return Err(ElemError::new(crate::ErrorType::Recursion));
+        }
+
+        let elem_expr_node = self.compile_text(elem_function.expression())?;
+
+        let mut frame = MapSymbol::new();
+        for (param, value) in parameters.iter().zip(values) {
+            frame.add_symbol(param.as_str(), value);
+        }
+
+        self.frames_mut()
+            .push((String::from(elem_function.expression()), frame));
+
+        let result = self.evaluate_node(&elem_expr_node, list_am_opt, elem_balance_result_opt);
+
+        self.frames_mut().pop();
+
+        result
+    }
+
+    /// Checks the currently initialized expression without executing it.
+    /// The type of every sub-expression is inferred and each symbol is
+    /// resolved against the symbol table (the expression parameters), the
+    /// symbols supplied by the engine during calculation and the symbols
+    /// assigned within the expression itself. Descriptor references with
+    /// constant arguments are resolved against the descriptor lists.
+    /// If an error occurs, the expression text and the position of the
+    /// failing sub-expression are recorded in the error.
+    ///
+    /// # Return
+    ///
+    /// * The type of the expression result if successful (Unknown if the
+    ///   type can only be determined when executed), otherwise error code.
+    pub fn check(&self) -> Result<crate::TokenType, ElemError> {
+        let elem_expr_node = self.compile()?;
+
+        let mut scope = self.symbol_table().copy();
+
+        for name in [
+            "decAccBalance",
+            "decBalance",
+            "decInterest",
+            "decSLInterest",
+            "decValue",
+            "decYield",
+            "intDate",
+            "intEndDate",
+            "intEOM",
+            "intIntervals",
+            "intNewDate",
+            "intPeriods",
+            "intSequence",
+            "strFrequency",
+        ]
+        .iter()
+        {
+            if scope.get_symbol(name).is_some() {
+                continue;
+            }
+            let sym_type = if name.starts_with("dec") {
+                crate::TokenType::Decimal
+            } else if name.starts_with("int") {
+                crate::TokenType::Integer
+            } else {
+                crate::TokenType::String
+            };
+            scope.add_symbol(name, ElemSymbol::new_with_token_type(sym_type));
+        }
+
+        self.check_node(&elem_expr_node, &mut scope)
+    }
+
+    /// Checks a compiled expression and infers its type.
+    ///
+    /// # Arguments
+    ///
+    /// * `elem_expr_node` - The compiled expression.
+    /// * `scope` - The symbols in scope.
+    ///
+    /// # Return
+    ///
+    /// * The inferred type if successful, otherwise error code.
+    fn check_node(
+        &self,
+        elem_expr_node: &ElemExprNode,
+        scope: &mut MapSymbol,
+    ) -> Result<crate::TokenType, ElemError> {
+        let result = match elem_expr_node.node_type() {
+            crate::NodeType::Constant => Ok(elem_expr_node.value().sym_type()),
+            crate::NodeType::Symbol => self.check_symbol(elem_expr_node, scope),
+            crate::NodeType::Operator => self.check_operator(elem_expr_node, scope),
+            crate::NodeType::Function => self.check_function(elem_expr_node, scope),
+            crate::NodeType::Sequence => {
+                let mut result = Ok(crate::TokenType::Unknown);
+                for node in elem_expr_node.nodes().iter() {
+                    result = self.check_node(node, scope);
+                    if result.is_err() {
+                        break;
+                    }
+                }
+                result
+            }
+        };
+
+        match result {
+            Err(mut e) => {
+                e.set_expression(self.expression.as_str(), elem_expr_node.position());
+                Err(e)
+            }
+            Ok(o) => Ok(o),
+        }
+    }
+
+    /// Checks an argument node against an expected type.
+    ///
+    /// # Arguments
+    ///
+    /// * `elem_expr_node` - The argument node.
+    /// * `scope` - The symbols in scope.
+    /// * `expected` - The expected type (Unknown for any type).
+    ///
+    /// # Return
+    ///
+    /// * The inferred type if successful, otherwise error code.
+    fn check_arg(
+        &self,
+        elem_expr_node: &ElemExprNode,
+        scope: &mut MapSymbol,
+        expected: crate::TokenType,
+    ) -> Result<crate::TokenType, ElemError> {
+        let sym_type = self.check_node(elem_expr_node, scope)?;
+
+        let error_type = match expected {
+            crate::TokenType::Integer => crate::ErrorType::Integer,
+            crate::TokenType::Decimal => crate::ErrorType::Decimal,
+            crate::TokenType::String => crate::ErrorType::String,
+            crate::TokenType::List => crate::ErrorType::List,
+            _ => return Ok(sym_type),
+        };
+
+        if sym_type == crate::TokenType::Unknown
+            || sym_type == expected
+            || (expected == crate::TokenType::Decimal && sym_type == crate::TokenType::Integer)
+        {
+            return Ok(sym_type);
+        }
+
+        let mut e = ElemError::new(error_type);
+        e.set_expression(self.expression.as_str(), elem_expr_node.position());
+        Err(e)
+    }
+
+    /// Checks a symbol node and returns the name of the symbol.
+    /// The name of an indexed symbol includes a constant index
+    /// (i.e., name[1]) or is empty if the index is not constant.
+    ///
+    /// # Arguments
+    ///
+    /// * `elem_expr_node` - The symbol node.
+    /// * `scope` - The symbols in scope.
+    ///
+    /// # Return
+    ///
+    /// * Symbol name if successful, otherwise error code.
+    fn check_symbol_name(
+        &self,
+        elem_expr_node: &ElemExprNode,
+        scope: &mut MapSymbol,
+    ) -> Result<String, ElemError> {
+        let index = match elem_expr_node.nodes().first() {
+            None => return Ok(String::from(elem_expr_node.name())),
+            Some(o) => o,
+        };
+
+        self.check_arg(index, scope, crate::TokenType::Integer)?;
+
+        if index.node_type() == crate::NodeType::Constant {
+            return Ok(format!(
+                "{}[{}]",
+                elem_expr_node.name(),
+                index.value().sym_integer()
+            ));
+        }
+
+        Ok(String::from(""))
+    }
+
+    /// Checks a symbol node by resolving the symbol in scope.
+    ///
+    /// # Arguments
+    ///
+    /// * `elem_expr_node` - The symbol node.
+    /// * `scope` - The symbols in scope.
+    ///
+    /// # Return
+    ///
+    /// * The type of the symbol if successful, otherwise error code.
+    fn check_symbol(
+        &self,
+        elem_expr_node: &ElemExprNode,
+        scope: &mut MapSymbol,
+    ) -> Result<crate::TokenType, ElemError> {
+        let name = self.check_symbol_name(elem_expr_node, scope)?;
+
+        let elem_symbol_opt = if name.is_empty() {
+            scope.get_indexed_symbol(elem_expr_node.name())
+        } else {
+            match scope.get_symbol(name.as_str()) {
+                None => {
+                    let index_name = format!("{}[]", elem_expr_node.name());
+                    scope.get_symbol(index_name.as_str())
                 }
-                result
+                Some(o) => Some(o),
             }
+        };
+
+        match elem_symbol_opt {
+            None => Err(ElemError::new(crate::ErrorType::InvalidSymbol)),
+            Some(o) => Ok(o.sym_type()),
         }
     }
 
-    /// Parses and executes a function from the expression.
-    /// Based upon a given function name, the appropriate method
-    /// is called to parse the parameters, execute the specific
-    /// function and return the resulting symbol.
+    /// Checks an operator node.
     ///
     /// # Arguments
     ///
-    /// * `name` - The name of the function.
-    /// * `list_am_opt` - Amortization list.
-    /// * `elem_balance_result_opt` - Balance results.
+    /// * `elem_expr_node` - The operator node.
+    /// * `scope` - The symbols in scope.
     ///
     /// # Return
     ///
-    /// * Result symbol if successful, otherwise error code.
-
-    pub fn function(
+    /// * The inferred type if successful, otherwise error code.
+    fn check_operator(
         &self,
-        name: &str,
-        list_am_opt: Option<&ListAmortization>,
-        elem_balance_result_opt: Option<&ElemBalanceResult>,
-    ) -> Result<ElemSymbol, crate::ErrorType> {
-        if !self
-            .calc_mgr()
-            .core_manager()
-            .functions()
-            .get_element_by_key(name)
+        elem_expr_node: &ElemExprNode,
+        scope: &mut MapSymbol,
+    ) -> Result<crate::TokenType, ElemError> {
+        let mut sym_types: Vec<crate::TokenType> = Vec::new();
+        for node in elem_expr_node.nodes().iter() {
+            match self.check_node(node, scope) {
+                Err(e) => return Err(e),
+                Ok(o) => sym_types.push(o),
+            }
+        }
+
+        let (mut type1, mut type2) = match sym_types.len() {
+            1 => (sym_types[0], sym_types[0]),
+            2 => (sym_types[0], sym_types[1]),
+            _ => return Err(ElemError::new(crate::ErrorType::MissingOperand)),
+        };
+
+        if (type1 == crate::TokenType::Integer && type2 == crate::TokenType::Decimal)
+            || (type1 == crate::TokenType::Decimal && type2 == crate::TokenType::Integer)
         {
-            return Err(crate::ErrorType::Function);
+            type1 = crate::TokenType::Decimal;
+            type2 = crate::TokenType::Decimal;
         }
 
-        self.scanner_mut().scan_token();
+        if type1 == crate::TokenType::List || type2 == crate::TokenType::List {
+            return Err(ElemError::new(crate::ErrorType::InvalidOperand));
+        }
+
+        let known = type1 != crate::TokenType::Unknown && type2 != crate::TokenType::Unknown;
+        let string = type1 == crate::TokenType::String || type2 == crate::TokenType::String;
 
-        let function_type =
-            CoreUtility::get_function(self.calc_mgr().core_manager().functions().value());
+        match elem_expr_node.operator_type() {
+            crate::OperatorType::And | crate::OperatorType::Or | crate::OperatorType::UnaryNot => {
+                if (type1 != crate::TokenType::Unknown && type1 != crate::TokenType::Integer)
+                    || (type2 != crate::TokenType::Unknown && type2 != crate::TokenType::Integer)
+                {
+                    return Err(ElemError::new(crate::ErrorType::InvalidOperand));
+                }
+                Ok(crate::TokenType::Integer)
+            }
+            crate::OperatorType::Greater
+            | crate::OperatorType::Less
+            | crate::OperatorType::GreaterEqual
+            | crate::OperatorType::LessEqual => {
+                if string {
+                    return Err(ElemError::new(crate::ErrorType::InvalidOperand));
+                }
+                Ok(crate::TokenType::Integer)
+            }
+            crate::OperatorType::Equal | crate::OperatorType::NotEqual => {
+                if known && type1 != type2 {
+                    return Err(ElemError::new(crate::ErrorType::InvalidOperand));
+                }
+                Ok(crate::TokenType::Integer)
+            }
+            crate::OperatorType::Plus => {
+                if known && type1 != type2 {
+                    return Err(ElemError::new(crate::ErrorType::InvalidOperand));
+                }
+                Ok(if known {
+                    type1
+                } else {
+                    crate::TokenType::Unknown
+                })
+            }
+            _ => {
+                if string {
+                    return Err(ElemError::new(crate::ErrorType::InvalidOperand));
+                }
+                if type1 == crate::TokenType::Decimal || type2 == crate::TokenType::Decimal {
+                    return Ok(crate::TokenType::Decimal);
+                }
+                Ok(if known {
+                    type1
+                } else {
+                    crate::TokenType::Unknown
+                })
+            }
+        }
+    }
 
-        let mut result: Result<ElemSymbol, crate::ErrorType> = match function_type {
-            crate::FunctionType::Abs => self.function_abs(list_am_opt, elem_balance_result_opt),
-            crate::FunctionType::Am => self.function_am(list_am_opt, elem_balance_result_opt),
+    /// Checks a function node.
+    ///
+    /// # Arguments
+    ///
+    /// * `elem_expr_node` - The function node.
+    /// * `scope` - The symbols in scope.
+    ///
+    /// # Return
+    ///
+    /// * The inferred type if successful, otherwise error code.
+    fn check_function(
+        &self,
+        elem_expr_node: &ElemExprNode,
+        scope: &mut MapSymbol,
+    ) -> Result<crate::TokenType, ElemError> {
+        let args = elem_expr_node.nodes();
+        let mut expected: Vec<crate::TokenType> = Vec::new();
+        let result_type: crate::TokenType = match elem_expr_node.function_type() {
+            crate::FunctionType::Abs => {
+                let sym_type = self.check_arg(&args[0], scope, crate::TokenType::Unknown)?;
+                if sym_type == crate::TokenType::String {
+                    let mut e = ElemError::new(crate::ErrorType::Integer);
+                    e.set_expression(self.expression.as_str(), args[0].position());
+                    return Err(e);
+                }
+                return Ok(sym_type);
+            }
+            crate::FunctionType::Am => {
+                if args[1].node_type() == crate::NodeType::Constant {
+                    let col_name = args[1].value().sym_string();
+                    if self
+                        .calc_mgr()
+                        .core_manager()
+                        .map_col_names()
+                        .get_key(col_name)
+                        .is_none()
+                        && col_name != "StrBal"
+                        && col_name != "EAR"
+                        && col_name != "PR"
+                        && col_name != "DR"
+                    {
+                        let mut e = ElemError::new(crate::ErrorType::Element);
+                        e.set_expression(self.expression.as_str(), args[1].position());
+                        return Err(e);
+                    }
+                }
+                expected.push(crate::TokenType::String);
+                expected.push(crate::TokenType::String);
+                crate::TokenType::Unknown
+            }
             crate::FunctionType::Cashflow => {
-                self.function_cashflow(list_am_opt, elem_balance_result_opt)
+                expected.push(crate::TokenType::String);
+                crate::TokenType::Unknown
             }
-            crate::FunctionType::DateDiff => {
-                self.function_date_diff(list_am_opt, elem_balance_result_opt)
+            crate::FunctionType::DateDiff | crate::FunctionType::DateNew => {
+                expected.push(crate::TokenType::Integer);
+                expected.push(crate::TokenType::Integer);
+                expected.push(crate::TokenType::String);
+                expected.push(crate::TokenType::Integer);
+                expected.push(crate::TokenType::Integer);
+                crate::TokenType::Integer
             }
             crate::FunctionType::DateFiscal => {
-                self.function_date_fiscal(list_am_opt, elem_balance_result_opt)
+                expected.push(crate::TokenType::Integer);
+                expected.push(crate::TokenType::String);
+                expected.push(crate::TokenType::Integer);
+                expected.push(crate::TokenType::Integer);
+                crate::TokenType::Integer
             }
-            crate::FunctionType::DateNew => {
-                self.function_date_new(list_am_opt, elem_balance_result_opt)
+            crate::FunctionType::DateNow => crate::TokenType::Integer,
+            crate::FunctionType::DateAddMonths => {
+                expected.resize(args.len(), crate::TokenType::Integer);
+                crate::TokenType::Integer
             }
-            crate::FunctionType::DateNow => self.function_date_now(),
-            crate::FunctionType::Default => {
-                self.function_default(list_am_opt, elem_balance_result_opt)
+            crate::FunctionType::DateDay
+            | crate::FunctionType::DateDayOfYear
+            | crate::FunctionType::DateEndOfMonth
+            | crate::FunctionType::DateIsLeapYear
+            | crate::FunctionType::DateMonth
+            | crate::FunctionType::DateWeekday
+            | crate::FunctionType::DateYear => {
+                expected.push(crate::TokenType::Integer);
+                crate::TokenType::Integer
             }
-            crate::FunctionType::Descriptor => {
-                self.function_descriptor(list_am_opt, elem_balance_result_opt)
+            crate::FunctionType::DateNextBusinessDay => {
+                expected.push(crate::TokenType::Integer);
+                expected.push(crate::TokenType::String);
+                crate::TokenType::Integer
             }
             crate::FunctionType::Decimal => {
-                self.function_decimal(list_am_opt, elem_balance_result_opt)
+                expected.push(crate::TokenType::Unknown);
+                crate::TokenType::Decimal
             }
-            crate::FunctionType::Format => {
-                self.function_format(list_am_opt, elem_balance_result_opt)
+            crate::FunctionType::Default => {
+                let name = self.check_name_arg(&args[0], scope)?;
+                let sym_type = self.check_arg(&args[1], scope, crate::TokenType::Unknown)?;
+                return Ok(match scope.get_symbol(name.as_str()) {
+                    None => sym_type,
+                    Some(o) => o.sym_type(),
+                });
             }
-            crate::FunctionType::FormatCurrency => {
-                self.function_format_currency(list_am_opt, elem_balance_result_opt)
+            crate::FunctionType::Descriptor => {
+                for arg in args.iter() {
+                    self.check_arg(arg, scope, crate::TokenType::String)?;
+                }
+                if args
+                    .iter()
+                    .all(|o| o.node_type() == crate::NodeType::Constant)
+                {
+                    let mut value = CoreUtility::get_descriptor_value(
+                        Option::from(&self.list_descriptor_user),
+                        self.list_descriptor_cashflow.as_ref(),
+                        self.list_descriptor_event.as_ref(),
+                        args[0].value().sym_string(),
+                        args[1].value().sym_string(),
+                        args[2].value().sym_string(),
+                        args[3].value().sym_string(),
+                    );
+                    if value.is_empty() {
+                        value = CoreUtility::get_descriptor_value(
+                            Option::from(&self.list_descriptor_user),
+                            self.list_descriptor_cashflow.as_ref(),
+                            self.list_descriptor_event.as_ref(),
+                            args[0].value().sym_string(),
+                            args[1].value().sym_string(),
+                            "",
+                            "",
+                        );
+                    }
+                    if value.is_empty() {
+                        return Err(ElemError::new(crate::ErrorType::InvalidSymbol));
+                    }
+                }
+                return Ok(crate::TokenType::String);
+            }
+            crate::FunctionType::Format
+            | crate::FunctionType::FormatCurrency
+            | crate::FunctionType::FormatNumber => {
+                expected.push(crate::TokenType::Unknown);
+                crate::TokenType::String
             }
             crate::FunctionType::FormatDate => {
-                self.function_format_date(list_am_opt, elem_balance_result_opt)
+                expected.push(crate::TokenType::Integer);
+                crate::TokenType::String
             }
-            crate::FunctionType::FormatNumber => {
-                self.function_format_number(list_am_opt, elem_balance_result_opt)
+            crate::FunctionType::If => {
+                self.check_arg(&args[0], scope, crate::TokenType::Integer)?;
+                let type1 = self.check_arg(&args[1], scope, crate::TokenType::Unknown)?;
+                let type2 = self.check_arg(&args[2], scope, crate::TokenType::Unknown)?;
+                return Ok(if type1 == type2 {
+                    type1
+                } else {
+                    crate::TokenType::Unknown
+                });
             }
-            crate::FunctionType::If => self.function_if(list_am_opt, elem_balance_result_opt),
             crate::FunctionType::Integer => {
-                self.function_integer(list_am_opt, elem_balance_result_opt)
+                expected.push(crate::TokenType::Unknown);
+                crate::TokenType::Integer
             }
-            crate::FunctionType::Len => self.function_len(list_am_opt, elem_balance_result_opt),
-            crate::FunctionType::Lowercase => {
-                self.function_lowercase(list_am_opt, elem_balance_result_opt)
+            crate::FunctionType::Len => {
+                expected.push(crate::TokenType::String);
+                crate::TokenType::Integer
+            }
+            crate::FunctionType::Lowercase
+            | crate::FunctionType::Trim
+            | crate::FunctionType::Uppercase => {
+                expected.push(crate::TokenType::String);
+                crate::TokenType::String
+            }
+            crate::FunctionType::Max | crate::FunctionType::Min => {
+                expected.push(crate::TokenType::Decimal);
+                expected.push(crate::TokenType::Decimal);
+                crate::TokenType::Decimal
+            }
+            crate::FunctionType::Mid => {
+                expected.push(crate::TokenType::String);
+                expected.push(crate::TokenType::Integer);
+                expected.push(crate::TokenType::Integer);
+                crate::TokenType::String
+            }
+            crate::FunctionType::Parse => {
+                for arg in args.iter().take(2) {
+                    self.check_arg(arg, scope, crate::TokenType::String)?;
+                }
+                if args[2].node_type() != crate::NodeType::Symbol || !args[2].nodes().is_empty() {
+                    let mut e = ElemError::new(crate::ErrorType::Alpha);
+                    e.set_expression(self.expression.as_str(), args[2].position());
+                    return Err(e);
+                }
+                let name = format!("{}[]", args[2].name());
+                scope.add_symbol(
+                    name.as_str(),
+                    ElemSymbol::new_with_token_type(crate::TokenType::String),
+                );
+                return Ok(crate::TokenType::Integer);
+            }
+            crate::FunctionType::Pr => {
+                expected.push(crate::TokenType::Decimal);
+                crate::TokenType::Decimal
+            }
+            crate::FunctionType::Effect
+            | crate::FunctionType::Fv
+            | crate::FunctionType::Ipmt
+            | crate::FunctionType::Nominal
+            | crate::FunctionType::Nper
+            | crate::FunctionType::Npv
+            | crate::FunctionType::Pmt
+            | crate::FunctionType::Ppmt
+            | crate::FunctionType::Pv
+            | crate::FunctionType::Rate => {
+                expected.resize(args.len(), crate::TokenType::Decimal);
+                crate::TokenType::Decimal
+            }
+            crate::FunctionType::AmList => {
+                expected.push(crate::TokenType::String);
+                expected.push(crate::TokenType::Integer);
+                expected.push(crate::TokenType::Integer);
+                expected.push(crate::TokenType::String);
+                crate::TokenType::List
+            }
+            crate::FunctionType::Avg | crate::FunctionType::Sum => {
+                expected.push(crate::TokenType::List);
+                crate::TokenType::Decimal
+            }
+            crate::FunctionType::Count => {
+                expected.push(crate::TokenType::List);
+                crate::TokenType::Integer
+            }
+            crate::FunctionType::First | crate::FunctionType::Last => {
+                expected.push(crate::TokenType::List);
+                crate::TokenType::Unknown
+            }
+            crate::FunctionType::Filter => {
+                self.check_arg(&args[0], scope, crate::TokenType::List)?;
+                if args[1].node_type() != crate::NodeType::Symbol || !args[1].nodes().is_empty() {
+                    let mut e = ElemError::new(crate::ErrorType::Alpha);
+                    e.set_expression(self.expression.as_str(), args[1].position());
+                    return Err(e);
+                }
+                scope.add_symbol(
+                    args[1].name(),
+                    ElemSymbol::new_with_token_type(crate::TokenType::Unknown),
+                );
+                self.check_arg(&args[2], scope, crate::TokenType::Integer)?;
+                return Ok(crate::TokenType::List);
+            }
+            crate::FunctionType::List => {
+                expected.resize(args.len(), crate::TokenType::Unknown);
+                crate::TokenType::List
+            }
+            crate::FunctionType::Xnpv => {
+                expected.push(crate::TokenType::Decimal);
+                expected.push(crate::TokenType::List);
+                expected.push(crate::TokenType::List);
+                crate::TokenType::Decimal
             }
-            crate::FunctionType::Max => self.function_max(list_am_opt, elem_balance_result_opt),
-            crate::FunctionType::Mid => self.function_mid(list_am_opt, elem_balance_result_opt),
-            crate::FunctionType::Min => self.function_min(list_am_opt, elem_balance_result_opt),
-            crate::FunctionType::Parse => self.function_parse(list_am_opt, elem_balance_result_opt),
-            crate::FunctionType::Pr => self.function_pr(list_am_opt, elem_balance_result_opt),
             crate::FunctionType::Replace => {
-                self.function_replace(list_am_opt, elem_balance_result_opt)
+                expected.push(crate::TokenType::String);
+                expected.push(crate::TokenType::String);
+                expected.push(crate::TokenType::String);
+                crate::TokenType::String
             }
-            crate::FunctionType::Round => self.function_round(list_am_opt, elem_balance_result_opt),
-            crate::FunctionType::RoundFraction => {
-                self.function_round_fraction(list_am_opt, elem_balance_result_opt)
+            crate::FunctionType::Round | crate::FunctionType::RoundFraction => {
+                expected.push(crate::TokenType::Decimal);
+                if args.len() > 1 {
+                    expected.push(
+                        if elem_expr_node.function_type() == crate::FunctionType::Round {
+                            crate::TokenType::Integer
+                        } else {
+                            crate::TokenType::Decimal
+                        },
+                    );
+                }
+                crate::TokenType::Decimal // The rounding argument is not checked
             }
-            crate::FunctionType::Set => self.function_set(list_am_opt, elem_balance_result_opt),
-            crate::FunctionType::Trim => self.function_trim(list_am_opt, elem_balance_result_opt),
-            crate::FunctionType::Type => self.function_type(list_am_opt, elem_balance_result_opt),
-            crate::FunctionType::Uppercase => {
-                self.function_uppercase(list_am_opt, elem_balance_result_opt)
+            crate::FunctionType::Set => {
+                if args[0].node_type() != crate::NodeType::Symbol {
+                    let mut e = ElemError::new(crate::ErrorType::Alpha);
+                    e.set_expression(self.expression.as_str(), args[0].position());
+                    return Err(e);
+                }
+                let mut name = self.check_symbol_name(&args[0], scope)?;
+                if name.is_empty() {
+                    name = format!("{}[]", args[0].name());
+                }
+                let sym_type = if args[1].node_type() == crate::NodeType::Symbol {
+                    crate::TokenType::String // Symbol name
+                } else {
+                    self.check_arg(&args[1], scope, crate::TokenType::Unknown)?
+                };
+                scope.add_symbol(name.as_str(), ElemSymbol::new_with_token_type(sym_type));
+                return Ok(sym_type);
             }
-            _ => return Err(crate::ErrorType::Function),
-        };
-
-        match result.as_ref() {
-            Err(_e) => {}
-            Ok(_o) => {
-                let token = self.scanner_mut().scan_token();
-                if !(token == crate::TokenType::Punctuation
-                    && self.scanner().get_token().starts_with(')'))
-                {
-                    result = Err(crate::ErrorType::RightParen);
+            crate::FunctionType::Type => {
+                self.check_name_arg(&args[0], scope)?;
+                return Ok(crate::TokenType::Integer);
+            }
+            _ => {
+                match self.user_function(elem_expr_node.name()) {
+                    None => {
+                        if self
+                            .calc_mgr()
+                            .native_function(elem_expr_node.name())
+                            .is_none()
+                        {
+                            return Err(ElemError::new(crate::ErrorType::Function));
+                        }
+                    }
+                    Some(o) => {
+                        if args.len() < o.parameters().len() {
+                            return Err(ElemError::new(crate::ErrorType::Incomplete));
+                        }
+                        if args.len() > o.parameters().len() {
+                            return Err(ElemError::new(crate::ErrorType::RightParen));
+                        }
+                    }
+                }
+                for arg in args.iter() {
+                    self.check_arg(arg, scope, crate::TokenType::Unknown)?;
                 }
+                return Ok(crate::TokenType::Unknown);
             }
+        };
+
+        for (arg, sym_type) in args.iter().zip(expected.iter()) {
+            self.check_arg(arg, scope, *sym_type)?;
         }
 
-        result
+        Ok(result_type)
+    }
+
+    /// Checks an argument that names a symbol (either
+    /// a symbol or an expression resulting in a string).
+    ///
+    /// # Arguments
+    ///
+    /// * `elem_expr_node` - The argument node.
+    /// * `scope` - The symbols in scope.
+    ///
+    /// # Return
+    ///
+    /// * Symbol name if known (otherwise empty) if successful, otherwise error code.
+    fn check_name_arg(
+        &self,
+        elem_expr_node: &ElemExprNode,
+        scope: &mut MapSymbol,
+    ) -> Result<String, ElemError> {
+        if elem_expr_node.node_type() == crate::NodeType::Symbol {
+            return self.check_symbol_name(elem_expr_node, scope);
+        }
+
+        self.check_arg(elem_expr_node, scope, crate::TokenType::String)?;
+
+        if elem_expr_node.node_type() == crate::NodeType::Constant {
+            return Ok(String::from(elem_expr_node.value().sym_string()));
+        }
+
+        Ok(String::from(""))
     }
 
     /// Parses and executes the absolute value function.
     ///
     /// # Arguments
     ///
+    /// * `args` - Function arguments.
     /// * `list_am_opt` - Amortization list.
     /// * `elem_balance_result_opt` - Balance results.
     ///
@@ -1442,10 +2729,11 @@ impl CalcExpression {
 
     pub fn function_abs(
         &self,
+        args: &[ElemExprNode],
         list_am_opt: Option<&ListAmortization>,
         elem_balance_result_opt: Option<&ElemBalanceResult>,
-    ) -> Result<ElemSymbol, crate::ErrorType> {
-        let result = self.get_expr_symbol(list_am_opt, elem_balance_result_opt, false);
+    ) -> Result<ElemSymbol, ElemError> {
+        let result = self.get_expr_symbol(&args[0], list_am_opt, elem_balance_result_opt, false);
         let mut elem_result_symbol = ElemSymbol::new();
 
         match result {
@@ -1459,7 +2747,7 @@ impl CalcExpression {
                         elem_result_symbol.set_decimal(o.sym_decimal().abs());
                     }
                     _ => {
-                        return Err(crate::ErrorType::Integer);
+                        return Err(ElemError::new(crate::ErrorType::Integer));
                     }
                 }
 
@@ -1472,6 +2760,7 @@ impl CalcExpression {
     ///
     /// # Arguments
     ///
+    /// * `args` - Function arguments.
     /// * `list_am_opt` - Amortization list.
     /// * `elem_balance_result_opt` - Balance results.
     ///
@@ -1481,43 +2770,23 @@ impl CalcExpression {
 
     pub fn function_am(
         &self,
+        args: &[ElemExprNode],
         list_am_opt: Option<&ListAmortization>,
         elem_balance_result_opt: Option<&ElemBalanceResult>,
-    ) -> Result<ElemSymbol, crate::ErrorType> {
-        let location: String = match self.get_expr_string(list_am_opt, elem_balance_result_opt) {
-            Err(e) => return Err(e),
-            Ok(o) => String::from(o.sym_string()),
-        };
-
-        let token = self.scanner_mut().scan_token();
-        if token == crate::TokenType::Punctuation && self.scanner().get_token().starts_with(',') {
-            self.scanner_mut().scan_token();
-        }
+    ) -> Result<ElemSymbol, ElemError> {
+        let location: String =
+            match self.get_expr_string(&args[0], list_am_opt, elem_balance_result_opt) {
+                Err(e) => return Err(e),
+                Ok(o) => String::from(o.sym_string()),
+            };
 
-        let col_name: String = match self.get_expr_string(list_am_opt, elem_balance_result_opt) {
-            Err(e) => return Err(e),
-            Ok(o) => String::from(o.sym_string()),
-        };
+        let col_name: String =
+            match self.get_expr_string(&args[1], list_am_opt, elem_balance_result_opt) {
+                Err(e) => return Err(e),
+                Ok(o) => String::from(o.sym_string()),
+            };
 
-        let index: usize;
-        if self
-            .calc_mgr()
-            .core_manager()
-            .map_col_names()
-            .get_element_by_key(col_name.as_str())
-        {
-            index = self.calc_mgr().core_manager().map_col_names().value();
-        } else if col_name == "StrBal" {
-            index = crate::COL_LABEL_STRBAL;
-        } else if col_name == "EAR" {
-            index = crate::COL_LABEL_EAR;
-        } else if col_name == "PR" {
-            index = crate::COL_LABEL_PR;
-        } else if col_name == "DR" {
-            index = crate::COL_LABEL_DR;
-        } else {
-            return Err(crate::ErrorType::Element);
-        }
+        let index = self.am_column_index(col_name.as_str())?;
 
         let mut elem_result_symbol = ElemSymbol::new();
 
@@ -1536,7 +2805,8 @@ impl CalcExpression {
                 | crate::ColumnType::ValueToInterest
                 | crate::ColumnType::ValueToPrincipal
                 | crate::ColumnType::AccruedBalance
-                | crate::ColumnType::Balance => {
+                | crate::ColumnType::Balance
+                | crate::ColumnType::DeferredInterest => {
                     elem_result_symbol.set_decimal(dec!(0.0));
                     return Ok(elem_result_symbol);
                 }
@@ -1551,131 +2821,130 @@ impl CalcExpression {
                     return Ok(elem_result_symbol);
                 }
                 _ => {
-                    return Err(crate::ErrorType::Element);
+                    return Err(ElemError::new(crate::ErrorType::Element));
                 }
             }
         }
 
         let list_am: &ListAmortization = match list_am_opt {
-            None => return Err(crate::ErrorType::Index),
+            None => return Err(ElemError::new(crate::ErrorType::Index)),
             Some(o) => o,
         };
 
         let elem_balance_result: &ElemBalanceResult = match elem_balance_result_opt {
-            None => return Err(crate::ErrorType::Index),
+            None => return Err(ElemError::new(crate::ErrorType::Index)),
             Some(o) => o,
         };
 
         let orig_index = list_am.index();
         let mut location_index = usize::MAX;
-        let mut interest_days_in_year: usize = 360;
 
         if location == "Current" {
             location_index = orig_index;
         } else if location.starts_with("FirstStart=") {
             let text = CoreUtility::crop_letters(location.as_str(), 11);
             for index in 0..list_am.count() {
-                if !list_am.get_element(index) {
-                    break;
-                }
-                if list_am.event_type().starts_with(text) {
+                let elem_am = match list_am.get_elem_amortization(index) {
+                    None => break,
+                    Some(o) => o,
+                };
+                if elem_am.event_type().starts_with(text) {
                     location_index = index;
                     break;
                 }
             }
-            list_am.get_element(orig_index);
         } else if location.starts_with("FirstMid=") {
             let text = CoreUtility::crop_letters(location.as_str(), 9);
             for index in 0..list_am.count() {
-                if !list_am.get_element(index) {
-                    break;
-                }
-                if list_am.event_type().contains(text) {
+                let elem_am = match list_am.get_elem_amortization(index) {
+                    None => break,
+                    Some(o) => o,
+                };
+                if elem_am.event_type().contains(text) {
                     location_index = index;
                     break;
                 }
             }
-            list_am.get_element(orig_index);
         } else if location.starts_with("LastStart=") {
             let text = CoreUtility::crop_letters(location.as_str(), 10);
             for index in (0..list_am.count()).rev() {
-                if !list_am.get_element(index) {
-                    break;
-                }
-                if list_am.event_type().starts_with(text) {
+                let elem_am = match list_am.get_elem_amortization(index) {
+                    None => break,
+                    Some(o) => o,
+                };
+                if elem_am.event_type().starts_with(text) {
                     location_index = index;
                     break;
                 }
             }
-            list_am.get_element(orig_index);
         } else if location.starts_with("LastMid=") {
             let text = CoreUtility::crop_letters(location.as_str(), 8);
             for index in (0..list_am.count()).rev() {
-                if !list_am.get_element(index) {
-                    break;
-                }
-                if list_am.event_type().contains(text) {
+                let elem_am = match list_am.get_elem_amortization(index) {
+                    None => break,
+                    Some(o) => o,
+                };
+                if elem_am.event_type().contains(text) {
                     location_index = index;
                     break;
                 }
             }
-            list_am.get_element(orig_index);
         } else if location == "PrinPrev" {
             let mut index: i32 = (orig_index as i32) - 1;
             while index >= 0 {
-                if !list_am.get_element(index as usize) {
-                    break;
-                }
-                if list_am.elem_type() == crate::ExtensionType::PrincipalChange {
+                let elem_am = match list_am.get_elem_amortization(index as usize) {
+                    None => break,
+                    Some(o) => o,
+                };
+                if elem_am.elem_type() == crate::ExtensionType::PrincipalChange {
                     location_index = index as usize;
                     break;
                 }
                 index -= 1;
             }
-            list_am.get_element(orig_index);
         } else if location == "PrinPrevStat" {
             let mut index: i32 = (orig_index as i32) - 1;
             while index >= 0 {
-                if !list_am.get_element(index as usize) {
-                    break;
-                }
-                if list_am.elem_type() == crate::ExtensionType::PrincipalChange
-                    && list_am.elem_extension().pc_balance_statistics()
+                let elem_am = match list_am.get_elem_amortization(index as usize) {
+                    None => break,
+                    Some(o) => o,
+                };
+                if elem_am.elem_type() == crate::ExtensionType::PrincipalChange
+                    && elem_am.elem_extension().pc_balance_statistics()
                 {
                     location_index = index as usize;
                     break;
                 }
                 index -= 1;
             }
-            list_am.get_element(orig_index);
         } else if location == "PrinNext" {
             let mut index = orig_index + 1;
             while index < list_am.count() {
-                if !list_am.get_element(index) {
-                    break;
-                }
-                if list_am.elem_type() == crate::ExtensionType::PrincipalChange {
+                let elem_am = match list_am.get_elem_amortization(index) {
+                    None => break,
+                    Some(o) => o,
+                };
+                if elem_am.elem_type() == crate::ExtensionType::PrincipalChange {
                     location_index = index;
                     break;
                 }
                 index += 1;
             }
-            list_am.get_element(orig_index);
         } else if location == "PrinNextStat" {
             let mut index = orig_index + 1;
             while index < list_am.count() {
-                if !list_am.get_element(index) {
-                    break;
-                }
-                if list_am.elem_type() == crate::ExtensionType::PrincipalChange
-                    && list_am.elem_extension().pc_balance_statistics()
+                let elem_am = match list_am.get_elem_amortization(index) {
+                    None => break,
+                    Some(o) => o,
+                };
+                if elem_am.elem_type() == crate::ExtensionType::PrincipalChange
+                    && elem_am.elem_extension().pc_balance_statistics()
                 {
                     location_index = index;
                     break;
                 }
                 index += 1;
             }
-            list_am.get_element(orig_index);
         } else if location == "PrinFirst" {
             location_index = elem_balance_result.prin_first_index();
         } else if location == "PrinFirstStat" {
@@ -1693,10 +2962,11 @@ impl CalcExpression {
         } else if location == "IntPrev" {
             let mut index: i32 = (orig_index as i32) - 1;
             while index >= 0 {
-                if !list_am.get_element(index as usize) {
-                    break;
-                }
-                if list_am.elem_type() == crate::ExtensionType::InterestChange {
+                let elem_am = match list_am.get_elem_amortization(index as usize) {
+                    None => break,
+                    Some(o) => o,
+                };
+                if elem_am.elem_type() == crate::ExtensionType::InterestChange {
                     location_index = index as usize;
                     break;
                 }
@@ -1705,14 +2975,14 @@ impl CalcExpression {
             if location_index == usize::MAX {
                 location_index = orig_index;
             }
-            list_am.get_element(orig_index);
         } else if location == "IntNext" {
             let mut index = orig_index + 1;
             while index < list_am.count() {
-                if !list_am.get_element(index) {
-                    break;
-                }
-                if list_am.elem_type() == crate::ExtensionType::InterestChange {
+                let elem_am = match list_am.get_elem_amortization(index) {
+                    None => break,
+                    Some(o) => o,
+                };
+                if elem_am.elem_type() == crate::ExtensionType::InterestChange {
                     location_index = index;
                     break;
                 }
@@ -1721,22 +2991,25 @@ impl CalcExpression {
             if location_index >= list_am.count() {
                 location_index = orig_index;
             }
-            list_am.get_element(orig_index);
         } else if location == "IntFirst" {
             location_index = elem_balance_result.int_first_index();
         } else if location == "IntLast" {
             location_index = elem_balance_result.int_last_index();
         } else {
-            return Err(crate::ErrorType::Element);
+            return Err(ElemError::new(crate::ErrorType::Element));
         }
 
-        list_am.get_element(location_index);
+        // A location that is not found falls back to the selected element
+        let elem_am_opt = match list_am.get_elem_amortization(location_index) {
+            None => list_am.get_elem_amortization(orig_index),
+            Some(o) => Some(o),
+        };
 
         match CoreUtility::get_col_name(index) {
             crate::ColumnType::EventType
             | crate::ColumnType::Frequency
             | crate::ColumnType::StrBal => {
-                if list_am.index() == usize::MAX {
+                if elem_am_opt.is_none() {
                     elem_result_symbol.set_string("");
                     return Ok(elem_result_symbol);
                 }
@@ -1748,82 +3021,155 @@ impl CalcExpression {
             | crate::ColumnType::ValueToInterest
             | crate::ColumnType::ValueToPrincipal
             | crate::ColumnType::AccruedBalance
-            | crate::ColumnType::Balance => {
-                if list_am.index() == usize::MAX {
+            | crate::ColumnType::Balance
+            | crate::ColumnType::DeferredInterest => {
+                if elem_am_opt.is_none() {
                     elem_result_symbol.set_decimal(dec!(0.0));
                     return Ok(elem_result_symbol);
                 }
             }
             crate::ColumnType::Date | crate::ColumnType::Intervals | crate::ColumnType::EndDate => {
-                if list_am.index() == usize::MAX {
+                if elem_am_opt.is_none() {
                     elem_result_symbol.set_integer(0);
                     return Ok(elem_result_symbol);
                 }
             }
             crate::ColumnType::Ear | crate::ColumnType::Pr | crate::ColumnType::Dr => {
-                if list_am.index() == usize::MAX
-                    || list_am.elem_type() != crate::ExtensionType::InterestChange
+                if elem_am_opt.map(|o| o.elem_type()) != Some(crate::ExtensionType::InterestChange)
                 {
                     elem_result_symbol.set_decimal(dec!(0.0));
                     return Ok(elem_result_symbol);
                 }
-                interest_days_in_year = list_am.elem_extension().ic_days_in_year();
             }
             _ => {
-                return Err(crate::ErrorType::Element);
+                return Err(ElemError::new(crate::ErrorType::Element));
+            }
+        }
+
+        let elem_am = match elem_am_opt {
+            None => return Err(ElemError::new(crate::ErrorType::Index)),
+            Some(o) => o,
+        };
+
+        self.am_column_value(elem_am, index, elem_balance_result)
+    }
+
+    /// Returns the column index of an amortization list column name.
+    ///
+    /// # Arguments
+    ///
+    /// * `col_name` - Column name.
+    ///
+    /// # Return
+    ///
+    /// * Column index if successful, otherwise error code.
+    fn am_column_index(&self, col_name: &str) -> Result<usize, ElemError> {
+        if let Some(o) = self
+            .calc_mgr()
+            .core_manager()
+            .map_col_names()
+            .get_key(col_name)
+        {
+            return Ok(o.value());
+        }
+
+        match col_name {
+            "StrBal" => Ok(crate::COL_LABEL_STRBAL),
+            "EAR" => Ok(crate::COL_LABEL_EAR),
+            "PR" => Ok(crate::COL_LABEL_PR),
+            "DR" => Ok(crate::COL_LABEL_DR),
+            _ => Err(ElemError::new(crate::ErrorType::Element)),
+        }
+    }
+
+    /// Returns the column value of an amortization element.
+    /// The rate columns (EAR, PR and DR) are zero unless the
+    /// element is an interest change.
+    ///
+    /// # Arguments
+    ///
+    /// * `elem_am` - Amortization element.
+    /// * `index` - Column index.
+    /// * `elem_balance_result` - Balance results.
+    ///
+    /// # Return
+    ///
+    /// * Column value if successful, otherwise error code.
+    fn am_column_value(
+        &self,
+        elem_am: &ElemAmortization,
+        index: usize,
+        elem_balance_result: &ElemBalanceResult,
+    ) -> Result<ElemSymbol, ElemError> {
+        let mut elem_result_symbol = ElemSymbol::new();
+        let mut interest_days_in_year: usize = 360;
+
+        match CoreUtility::get_col_name(index) {
+            crate::ColumnType::Ear | crate::ColumnType::Pr | crate::ColumnType::Dr => {
+                if elem_am.elem_type() != crate::ExtensionType::InterestChange {
+                    elem_result_symbol.set_decimal(dec!(0.0));
+                    return Ok(elem_result_symbol);
+                }
+                interest_days_in_year = elem_am.elem_extension().ic_days_in_year();
             }
+            _ => {}
         }
 
         match CoreUtility::get_col_name(index) {
             crate::ColumnType::EventType => {
-                elem_result_symbol.set_string(list_am.event_type());
+                elem_result_symbol.set_string(elem_am.event_type());
             }
             crate::ColumnType::Date => {
-                elem_result_symbol.set_integer(list_am.event_date());
+                elem_result_symbol.set_integer(CoreUtility::date_to_integer(elem_am.event_date()));
             }
             crate::ColumnType::Value => {
-                elem_result_symbol.set_decimal(list_am.value());
+                elem_result_symbol.set_decimal(elem_am.value());
             }
             crate::ColumnType::Frequency => {
-                let freq = CoreUtility::get_frequency_mnemonic(list_am.frequency());
+                let freq = CoreUtility::get_frequency_mnemonic(elem_am.frequency());
                 elem_result_symbol.set_string(freq.as_str());
             }
             crate::ColumnType::Intervals => {
-                elem_result_symbol.set_integer(list_am.intervals());
+                elem_result_symbol.set_integer(elem_am.intervals());
             }
             crate::ColumnType::EndDate => {
-                elem_result_symbol.set_integer(CoreUtility::date_new(
-                    list_am.orig_date(),
-                    list_am.event_date(),
-                    list_am.frequency(),
-                    list_am.intervals(),
-                    list_am.elem_extension().extension_eom(),
+                elem_result_symbol.set_integer(CoreUtility::date_to_integer(
+                    CoreUtility::date_new(
+                        elem_am.orig_date(),
+                        elem_am.event_date(),
+                        elem_am.frequency(),
+                        elem_am.intervals(),
+                        elem_am.elem_extension().extension_eom(),
+                    ),
                 ));
             }
             crate::ColumnType::Interest => {
-                elem_result_symbol.set_decimal(list_am.interest());
+                elem_result_symbol.set_decimal(elem_am.interest());
             }
             crate::ColumnType::SlInterest => {
-                elem_result_symbol.set_decimal(list_am.sl_interest());
+                elem_result_symbol.set_decimal(elem_am.sl_interest());
             }
             crate::ColumnType::IntOnInterest => {
-                elem_result_symbol.set_decimal(list_am.interest() - list_am.sl_interest());
+                elem_result_symbol.set_decimal(elem_am.interest() - elem_am.sl_interest());
             }
             crate::ColumnType::ValueToInterest => {
-                elem_result_symbol.set_decimal(list_am.value_to_interest());
+                elem_result_symbol.set_decimal(elem_am.value_to_interest());
             }
             crate::ColumnType::ValueToPrincipal => {
-                elem_result_symbol.set_decimal(list_am.value_to_principal());
+                elem_result_symbol.set_decimal(elem_am.value_to_principal());
             }
             crate::ColumnType::AccruedBalance => {
-                elem_result_symbol.set_decimal(list_am.acc_balance());
+                elem_result_symbol.set_decimal(elem_am.acc_balance());
             }
             crate::ColumnType::Balance => {
-                elem_result_symbol.set_decimal(list_am.balance());
+                elem_result_symbol.set_decimal(elem_am.balance());
+            }
+            crate::ColumnType::DeferredInterest => {
+                elem_result_symbol.set_decimal(elem_am.deferred_interest());
             }
             crate::ColumnType::StrBal => {
                 // StrBal
-                let balance = CoreUtility::util_round(list_am.balance(), self.decimal_digits);
+                let balance = CoreUtility::util_round(elem_am.balance(), self.decimal_digits);
                 if elem_balance_result.polarity() < 0 {
                     if balance > dec!(0.0) {
                         elem_result_symbol.set_string(
@@ -1855,8 +3201,8 @@ impl CalcExpression {
             crate::ColumnType::Ear => {
                 elem_result_symbol.set_decimal(
                     CoreUtility::rate_nar_to_ear(
-                        list_am.value() / dec!(100.0),
-                        list_am.frequency(),
+                        elem_am.value() / dec!(100.0),
+                        elem_am.frequency(),
                         interest_days_in_year,
                     ) * dec!(100.0),
                 );
@@ -1864,24 +3210,121 @@ impl CalcExpression {
             crate::ColumnType::Pr => {
                 elem_result_symbol.set_decimal(
                     CoreUtility::rate_nar_to_pr(
-                        list_am.value() / dec!(100.0),
-                        list_am.frequency(),
+                        elem_am.value() / dec!(100.0),
+                        elem_am.frequency(),
+                        interest_days_in_year,
+                    ) * dec!(100.0),
+                );
+            }
+            crate::ColumnType::Dr => {
+                elem_result_symbol.set_decimal(
+                    CoreUtility::rate_nar_to_dr(
+                        elem_am.value() / dec!(100.0),
                         interest_days_in_year,
                     ) * dec!(100.0),
                 );
             }
-            crate::ColumnType::Dr => {
-                elem_result_symbol.set_decimal(
-                    CoreUtility::rate_nar_to_dr(
-                        list_am.value() / dec!(100.0),
-                        interest_days_in_year,
-                    ) * dec!(100.0),
-                );
+            _ => {
+                return Err(ElemError::new(crate::ErrorType::Element));
+            }
+        }
+
+        Ok(elem_result_symbol)
+    }
+
+    /// Parses and executes the amortization list column function.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - Function arguments.
+    /// * `list_am_opt` - Amortization list.
+    /// * `elem_balance_result_opt` - Balance results.
+    ///
+    /// # Return
+    ///
+    /// * Result symbol if successful, otherwise error code.
+    pub fn function_am_list(
+        &self,
+        args: &[ElemExprNode],
+        list_am_opt: Option<&ListAmortization>,
+        elem_balance_result_opt: Option<&ElemBalanceResult>,
+    ) -> Result<ElemSymbol, ElemError> {
+        let col_name: String =
+            match self.get_expr_string(&args[0], list_am_opt, elem_balance_result_opt) {
+                Err(e) => return Err(e),
+                Ok(o) => String::from(o.sym_string()),
+            };
+
+        let index = self.am_column_index(col_name.as_str())?;
+
+        let mut dates: Vec<usize> = vec![0, 0];
+        for (arg, date) in args.iter().skip(1).take(2).zip(dates.iter_mut()) {
+            match self.get_expr_integer(arg, list_am_opt, elem_balance_result_opt) {
+                Err(e) => return Err(e),
+                Ok(o) => *date = o.sym_integer(),
+            }
+        }
+
+        let mut event_type = String::from("");
+        if args.len() > 3 {
+            match self.get_expr_string(&args[3], list_am_opt, elem_balance_result_opt) {
+                Err(e) => return Err(e),
+                Ok(o) => event_type = String::from(o.sym_string()),
             }
-            _ => {}
         }
 
-        list_am.get_element(orig_index);
+        let mut list_value: Vec<ElemSymbol> = Vec::new();
+
+        if let (Some(list_am), Some(elem_balance_result)) = (list_am_opt, elem_balance_result_opt) {
+            for index_am in 0..list_am.count() {
+                let elem_am = match list_am.get_elem_amortization(index_am) {
+                    None => break,
+                    Some(o) => o,
+                };
+                let date = CoreUtility::date_to_integer(elem_am.event_date());
+                if (dates[0] > 0 && date < dates[0])
+                    || (dates[1] > 0 && date > dates[1])
+                    || !elem_am.event_type().starts_with(event_type.as_str())
+                {
+                    continue;
+                }
+                list_value.push(self.am_column_value(elem_am, index, elem_balance_result)?);
+            }
+        }
+
+        let mut elem_result_symbol = ElemSymbol::new();
+        elem_result_symbol.set_list(list_value.as_slice());
+        Ok(elem_result_symbol)
+    }
+
+    /// Parses and executes the average of list function.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - Function arguments.
+    /// * `list_am_opt` - Amortization list.
+    /// * `elem_balance_result_opt` - Balance results.
+    ///
+    /// # Return
+    ///
+    /// * Result symbol if successful, otherwise error code.
+    pub fn function_avg(
+        &self,
+        args: &[ElemExprNode],
+        list_am_opt: Option<&ListAmortization>,
+        elem_balance_result_opt: Option<&ElemBalanceResult>,
+    ) -> Result<ElemSymbol, ElemError> {
+        let elem_symbol = self.get_expr_list(&args[0], list_am_opt, elem_balance_result_opt)?;
+
+        let sum = CalcExpression::list_sum(elem_symbol.sym_list())?;
+
+        let count = elem_symbol.sym_list().len();
+        if count == 0 {
+            return Err(ElemError::new(crate::ErrorType::DivideByZero));
+        }
+
+        let mut elem_result_symbol = ElemSymbol::new();
+        elem_result_symbol.set_decimal(sum / Decimal::from(count));
         Ok(elem_result_symbol)
     }
 
@@ -1889,6 +3332,7 @@ impl CalcExpression {
     ///
     /// # Arguments
     ///
+    /// * `args` - Function arguments.
     /// * `list_am_opt` - Amortization list.
     /// * `elem_balance_result_opt` - Balance results.
     ///
@@ -1898,13 +3342,15 @@ impl CalcExpression {
 
     pub fn function_cashflow(
         &self,
+        args: &[ElemExprNode],
         list_am_opt: Option<&ListAmortization>,
         elem_balance_result_opt: Option<&ElemBalanceResult>,
-    ) -> Result<ElemSymbol, crate::ErrorType> {
-        let text: String = match self.get_expr_string(list_am_opt, elem_balance_result_opt) {
-            Err(e) => return Err(e),
-            Ok(o) => String::from(o.sym_string()),
-        };
+    ) -> Result<ElemSymbol, ElemError> {
+        let text: String =
+            match self.get_expr_string(&args[0], list_am_opt, elem_balance_result_opt) {
+                Err(e) => return Err(e),
+                Ok(o) => String::from(o.sym_string()),
+            };
 
         let calc_mgr = self.calc_mgr();
         let cashflow = calc_mgr.list_cashflow();
@@ -2055,7 +3501,7 @@ impl CalcExpression {
                     elem_result_symbol.set_integer(0);
                 }
                 Some(o) => {
-                    elem_result_symbol.set_integer(o.balance_date());
+                    elem_result_symbol.set_integer(CoreUtility::date_to_integer(o.balance_date()));
                 }
             },
             "StrBal" => match elem_balance_result_opt {
@@ -2166,61 +3612,206 @@ impl CalcExpression {
                 }
             },
             _ => {
-                return Err(crate::ErrorType::Element);
+                return Err(ElemError::new(crate::ErrorType::Element));
             }
         }
         Ok(elem_result_symbol)
     }
 
-    /// Parses and executes the date difference function.
+    /// Parses and executes the count of list function.
     ///
     /// # Arguments
     ///
+    /// * `args` - Function arguments.
     /// * `list_am_opt` - Amortization list.
     /// * `elem_balance_result_opt` - Balance results.
     ///
     /// # Return
     ///
     /// * Result symbol if successful, otherwise error code.
+    pub fn function_count(
+        &self,
+        args: &[ElemExprNode],
+        list_am_opt: Option<&ListAmortization>,
+        elem_balance_result_opt: Option<&ElemBalanceResult>,
+    ) -> Result<ElemSymbol, ElemError> {
+        let elem_symbol = self.get_expr_list(&args[0], list_am_opt, elem_balance_result_opt)?;
 
-    pub fn function_date_diff(
+        let mut elem_result_symbol = ElemSymbol::new();
+        elem_result_symbol.set_integer(elem_symbol.sym_list().len());
+        Ok(elem_result_symbol)
+    }
+
+    /// Parses and executes the add months to date function.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - Function arguments.
+    /// * `list_am_opt` - Amortization list.
+    /// * `elem_balance_result_opt` - Balance results.
+    ///
+    /// # Return
+    ///
+    /// * Result symbol if successful, otherwise error code.
+    pub fn function_date_add_months(
         &self,
+        args: &[ElemExprNode],
         list_am_opt: Option<&ListAmortization>,
         elem_balance_result_opt: Option<&ElemBalanceResult>,
-    ) -> Result<ElemSymbol, crate::ErrorType> {
-        let date1: usize = match self.get_expr_integer(list_am_opt, elem_balance_result_opt) {
-            Err(e) => return Err(e),
-            Ok(o) => o.sym_integer(),
-        };
+    ) -> Result<ElemSymbol, ElemError> {
+        let date: NaiveDate = self.get_expr_date(&args[0], list_am_opt, elem_balance_result_opt)?;
 
-        let token = self.scanner_mut().scan_token();
-        if token == crate::TokenType::Punctuation && self.scanner().get_token().starts_with(',') {
-            self.scanner_mut().scan_token();
+        let months: i32 =
+            match self.get_expr_integer(&args[1], list_am_opt, elem_balance_result_opt) {
+                Err(e) => return Err(e),
+                Ok(o) => o.sym_integeri(),
+            };
+
+        let mut eom = false;
+        if args.len() > 2 {
+            match self.get_expr_integer(&args[2], list_am_opt, elem_balance_result_opt) {
+                Err(e) => return Err(e),
+                Ok(o) => eom = o.sym_integer() != 0,
+            }
         }
 
-        let date2: usize = match self.get_expr_integer(list_am_opt, elem_balance_result_opt) {
-            Err(e) => return Err(e),
-            Ok(o) => o.sym_integer(),
+        let new_date = if eom {
+            // Dates at the end of the month remain at the end of the month
+            CoreUtility::date_newi(date, date, crate::FrequencyType::OneMonth, months, true)
+        } else {
+            let month = date.year() * 12 + date.month0() as i32 + months;
+            let year = month.div_euclid(12) as usize;
+            let month = month.rem_euclid(12) as usize + 1;
+            // The day is limited to the number of days in the new month
+            let day = (date.day() as usize).min(CoreUtility::days_in_month(year, month));
+            match CoreUtility::date_from_ymd(year, month, day) {
+                None => return Err(ElemError::new(crate::ErrorType::Date)),
+                Some(o) => o,
+            }
         };
 
-        let token = self.scanner_mut().scan_token();
-        if token == crate::TokenType::Punctuation && self.scanner().get_token().starts_with(',') {
-            self.scanner_mut().scan_token();
+        if !CoreUtility::date_in_range(new_date) {
+            return Err(ElemError::new(crate::ErrorType::Date));
         }
 
+        let mut elem_result_symbol = ElemSymbol::new();
+        elem_result_symbol.set_integer(CoreUtility::date_to_integer(new_date));
+        Ok(elem_result_symbol)
+    }
+
+    /// Parses and executes the day of date function.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - Function arguments.
+    /// * `list_am_opt` - Amortization list.
+    /// * `elem_balance_result_opt` - Balance results.
+    ///
+    /// # Return
+    ///
+    /// * Result symbol if successful, otherwise error code.
+    pub fn function_date_day(
+        &self,
+        args: &[ElemExprNode],
+        list_am_opt: Option<&ListAmortization>,
+        elem_balance_result_opt: Option<&ElemBalanceResult>,
+    ) -> Result<ElemSymbol, ElemError> {
+        let date: NaiveDate = self.get_expr_date(&args[0], list_am_opt, elem_balance_result_opt)?;
+
+        let mut elem_result_symbol = ElemSymbol::new();
+        elem_result_symbol.set_integer(date.day() as usize);
+        Ok(elem_result_symbol)
+    }
+
+    /// Parses and executes the day of year of date function.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - Function arguments.
+    /// * `list_am_opt` - Amortization list.
+    /// * `elem_balance_result_opt` - Balance results.
+    ///
+    /// # Return
+    ///
+    /// * Result symbol if successful, otherwise error code.
+    pub fn function_date_day_of_year(
+        &self,
+        args: &[ElemExprNode],
+        list_am_opt: Option<&ListAmortization>,
+        elem_balance_result_opt: Option<&ElemBalanceResult>,
+    ) -> Result<ElemSymbol, ElemError> {
+        let date: NaiveDate = self.get_expr_date(&args[0], list_am_opt, elem_balance_result_opt)?;
+
+        let mut elem_result_symbol = ElemSymbol::new();
+        elem_result_symbol.set_integer(date.ordinal() as usize);
+        Ok(elem_result_symbol)
+    }
+
+    /// Parses and executes the end of month date function.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - Function arguments.
+    /// * `list_am_opt` - Amortization list.
+    /// * `elem_balance_result_opt` - Balance results.
+    ///
+    /// # Return
+    ///
+    /// * Result symbol if successful, otherwise error code.
+    pub fn function_date_end_of_month(
+        &self,
+        args: &[ElemExprNode],
+        list_am_opt: Option<&ListAmortization>,
+        elem_balance_result_opt: Option<&ElemBalanceResult>,
+    ) -> Result<ElemSymbol, ElemError> {
+        let date: NaiveDate = self.get_expr_date(&args[0], list_am_opt, elem_balance_result_opt)?;
+
+        let new_date = match CoreUtility::date_from_ymd(
+            date.year() as usize,
+            date.month() as usize,
+            CoreUtility::days_in_month(date.year() as usize, date.month() as usize),
+        ) {
+            None => return Err(ElemError::new(crate::ErrorType::Date)),
+            Some(o) => o,
+        };
+
+        let mut elem_result_symbol = ElemSymbol::new();
+        elem_result_symbol.set_integer(CoreUtility::date_to_integer(new_date));
+        Ok(elem_result_symbol)
+    }
+
+    /// Parses and executes the date difference function.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - Function arguments.
+    /// * `list_am_opt` - Amortization list.
+    /// * `elem_balance_result_opt` - Balance results.
+    ///
+    /// # Return
+    ///
+    /// * Result symbol if successful, otherwise error code.
+
+    pub fn function_date_diff(
+        &self,
+        args: &[ElemExprNode],
+        list_am_opt: Option<&ListAmortization>,
+        elem_balance_result_opt: Option<&ElemBalanceResult>,
+    ) -> Result<ElemSymbol, ElemError> {
+        let date1: NaiveDate =
+            self.get_expr_date(&args[0], list_am_opt, elem_balance_result_opt)?;
+
+        let date2: NaiveDate =
+            self.get_expr_date(&args[1], list_am_opt, elem_balance_result_opt)?;
+
         let frequency: crate::FrequencyType =
-            match self.get_expr_string(list_am_opt, elem_balance_result_opt) {
+            match self.get_expr_string(&args[2], list_am_opt, elem_balance_result_opt) {
                 Err(e) => return Err(e),
                 Ok(o) => CoreUtility::get_frequency(o.sym_string()),
             };
 
-        let token = self.scanner_mut().scan_token();
-        if token == crate::TokenType::Punctuation && self.scanner().get_token().starts_with(',') {
-            self.scanner_mut().scan_token();
-        }
-
         let mut intervals: usize;
-        let result = self.get_expr_integer(list_am_opt, elem_balance_result_opt);
+        let result = self.get_expr_integer(&args[3], list_am_opt, elem_balance_result_opt);
         match result {
             Err(e) => {
                 return Err(e);
@@ -2232,11 +3823,8 @@ impl CalcExpression {
                 }
             }
         }
-        let token = self.scanner_mut().scan_token();
-        if token == crate::TokenType::Punctuation && self.scanner().get_token().starts_with(',') {
-            self.scanner_mut().scan_token();
-        }
-        let eom: bool = match self.get_expr_integer(list_am_opt, elem_balance_result_opt) {
+        let eom: bool = match self.get_expr_integer(&args[4], list_am_opt, elem_balance_result_opt)
+        {
             Err(e) => return Err(e),
             Ok(o) => o.sym_integer() != 0,
         };
@@ -2254,6 +3842,7 @@ impl CalcExpression {
     ///
     /// # Arguments
     ///
+    /// * `args` - Function arguments.
     /// * `list_am_opt` - Amortization list.
     /// * `elem_balance_result_opt` - Balance results.
     ///
@@ -2263,31 +3852,21 @@ impl CalcExpression {
 
     pub fn function_date_fiscal(
         &self,
+        args: &[ElemExprNode],
         list_am_opt: Option<&ListAmortization>,
         elem_balance_result_opt: Option<&ElemBalanceResult>,
-    ) -> Result<ElemSymbol, crate::ErrorType> {
-        let orig_date: usize = match self.get_expr_integer(list_am_opt, elem_balance_result_opt) {
-            Err(e) => return Err(e),
-            Ok(o) => o.sym_integer(),
-        };
-
-        let token = self.scanner_mut().scan_token();
-        if token == crate::TokenType::Punctuation && self.scanner().get_token().starts_with(',') {
-            self.scanner_mut().scan_token();
-        }
+    ) -> Result<ElemSymbol, ElemError> {
+        let orig_date: NaiveDate =
+            self.get_expr_date(&args[0], list_am_opt, elem_balance_result_opt)?;
 
         let frequency: crate::FrequencyType =
-            match self.get_expr_string(list_am_opt, elem_balance_result_opt) {
+            match self.get_expr_string(&args[1], list_am_opt, elem_balance_result_opt) {
                 Err(e) => return Err(e),
                 Ok(o) => CoreUtility::get_frequency(o.sym_string()),
             };
 
-        let token = self.scanner_mut().scan_token();
-        if token == crate::TokenType::Punctuation && self.scanner().get_token().starts_with(',') {
-            self.scanner_mut().scan_token();
-        }
         let mut intervals: usize;
-        let result = self.get_expr_integer(list_am_opt, elem_balance_result_opt);
+        let result = self.get_expr_integer(&args[2], list_am_opt, elem_balance_result_opt);
         match result {
             Err(e) => {
                 return Err(e);
@@ -2299,22 +3878,23 @@ impl CalcExpression {
                 }
             }
         }
-        let token = self.scanner_mut().scan_token();
-        if token == crate::TokenType::Punctuation && self.scanner().get_token().starts_with(',') {
-            self.scanner_mut().scan_token();
-        }
-        let adjust: bool = match self.get_expr_integer(list_am_opt, elem_balance_result_opt) {
-            Err(e) => return Err(e),
-            Ok(o) => o.sym_integer() != 0,
-        };
+        let adjust: bool =
+            match self.get_expr_integer(&args[3], list_am_opt, elem_balance_result_opt) {
+                Err(e) => return Err(e),
+                Ok(o) => o.sym_integer() != 0,
+            };
 
-        let mut year = orig_date / 10000;
-        if crate::SERIAL_BASE_YEAR * 10000 + (orig_date % 10000)
-            < crate::SERIAL_BASE_YEAR * 10000 + self.fiscal_year_start
+        let month = self.fiscal_year_start / 100;
+        let day = self.fiscal_year_start % 100;
+        let mut year = orig_date.year() as usize;
+        if (orig_date.month() as usize) * 100 + (orig_date.day() as usize) < self.fiscal_year_start
         {
             year -= 1;
         }
-        let mut date = year * 10000 + self.fiscal_year_start;
+        let mut date = match CoreUtility::date_from_ymd(year, month, day) {
+            None => return Err(ElemError::new(crate::ErrorType::Date)),
+            Some(o) => o,
+        };
         let mut prev_date = date;
         while date <= orig_date {
             prev_date = date;
@@ -2332,8 +3912,56 @@ impl CalcExpression {
         }
 
         let mut elem_result_symbol = ElemSymbol::new();
-        elem_result_symbol.set_integer(prev_date);
+        elem_result_symbol.set_integer(CoreUtility::date_to_integer(prev_date));
+
+        Ok(elem_result_symbol)
+    }
+
+    /// Parses and executes the leap year of date function.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - Function arguments.
+    /// * `list_am_opt` - Amortization list.
+    /// * `elem_balance_result_opt` - Balance results.
+    ///
+    /// # Return
+    ///
+    /// * Result symbol if successful, otherwise error code.
+    pub fn function_date_is_leap_year(
+        &self,
+        args: &[ElemExprNode],
+        list_am_opt: Option<&ListAmortization>,
+        elem_balance_result_opt: Option<&ElemBalanceResult>,
+    ) -> Result<ElemSymbol, ElemError> {
+        let date: NaiveDate = self.get_expr_date(&args[0], list_am_opt, elem_balance_result_opt)?;
+
+        let mut elem_result_symbol = ElemSymbol::new();
+        elem_result_symbol.set_integer(CoreUtility::leap_year(date.year() as usize));
+        Ok(elem_result_symbol)
+    }
+
+    /// Parses and executes the month of date function.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - Function arguments.
+    /// * `list_am_opt` - Amortization list.
+    /// * `elem_balance_result_opt` - Balance results.
+    ///
+    /// # Return
+    ///
+    /// * Result symbol if successful, otherwise error code.
+    pub fn function_date_month(
+        &self,
+        args: &[ElemExprNode],
+        list_am_opt: Option<&ListAmortization>,
+        elem_balance_result_opt: Option<&ElemBalanceResult>,
+    ) -> Result<ElemSymbol, ElemError> {
+        let date: NaiveDate = self.get_expr_date(&args[0], list_am_opt, elem_balance_result_opt)?;
 
+        let mut elem_result_symbol = ElemSymbol::new();
+        elem_result_symbol.set_integer(date.month() as usize);
         Ok(elem_result_symbol)
     }
 
@@ -2341,6 +3969,7 @@ impl CalcExpression {
     ///
     /// # Arguments
     ///
+    /// * `args` - Function arguments.
     /// * `list_am_opt` - Amortization list.
     /// * `elem_balance_result_opt` - Balance results.
     ///
@@ -2350,22 +3979,16 @@ impl CalcExpression {
 
     pub fn function_date_new(
         &self,
+        args: &[ElemExprNode],
         list_am_opt: Option<&ListAmortization>,
         elem_balance_result_opt: Option<&ElemBalanceResult>,
-    ) -> Result<ElemSymbol, crate::ErrorType> {
-        let mut date: usize = match self.get_expr_integer(list_am_opt, elem_balance_result_opt) {
-            Err(e) => return Err(e),
-            Ok(o) => o.sym_integer(),
-        };
+    ) -> Result<ElemSymbol, ElemError> {
+        let mut date: NaiveDate =
+            self.get_expr_date(&args[0], list_am_opt, elem_balance_result_opt)?;
 
         let orig_date = date;
-        let token = self.scanner_mut().scan_token();
-        if token == crate::TokenType::Punctuation && self.scanner().get_token().starts_with(',') {
-            self.scanner_mut().scan_token();
-        }
-
         let mut periods: usize;
-        match self.get_expr_integer(list_am_opt, elem_balance_result_opt) {
+        match self.get_expr_integer(&args[1], list_am_opt, elem_balance_result_opt) {
             Err(e) => {
                 return Err(e);
             }
@@ -2377,24 +4000,14 @@ impl CalcExpression {
             }
         }
 
-        let token = self.scanner_mut().scan_token();
-        if token == crate::TokenType::Punctuation && self.scanner().get_token().starts_with(',') {
-            self.scanner_mut().scan_token();
-        }
-
         let frequency: crate::FrequencyType =
-            match self.get_expr_string(list_am_opt, elem_balance_result_opt) {
+            match self.get_expr_string(&args[2], list_am_opt, elem_balance_result_opt) {
                 Err(e) => return Err(e),
                 Ok(o) => CoreUtility::get_frequency(o.sym_string()),
             };
 
-        let token = self.scanner_mut().scan_token();
-        if token == crate::TokenType::Punctuation && self.scanner().get_token().starts_with(',') {
-            self.scanner_mut().scan_token();
-        }
-
         let mut intervals: usize;
-        let result = self.get_expr_integer(list_am_opt, elem_balance_result_opt);
+        let result = self.get_expr_integer(&args[3], list_am_opt, elem_balance_result_opt);
         match result {
             Err(e) => {
                 return Err(e);
@@ -2407,36 +4020,118 @@ impl CalcExpression {
             }
         }
 
-        let token = self.scanner_mut().scan_token();
-        if token == crate::TokenType::Punctuation && self.scanner().get_token().starts_with(',') {
-            self.scanner_mut().scan_token();
-        }
+        let eom: bool = match self.get_expr_integer(&args[4], list_am_opt, elem_balance_result_opt)
+        {
+            Err(e) => return Err(e),
+            Ok(o) => o.sym_integer() != 0,
+        };
+
+        while periods > 0 {
+            date = CoreUtility::date_new(orig_date, date, frequency, intervals, eom);
+            periods -= 1;
+        }
+
+        let mut elem_result_symbol = ElemSymbol::new();
+        elem_result_symbol.set_integer(CoreUtility::date_to_integer(date));
+        Ok(elem_result_symbol)
+    }
+
+    /// Parses and executes the next business day function.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - Function arguments.
+    /// * `list_am_opt` - Amortization list.
+    /// * `elem_balance_result_opt` - Balance results.
+    ///
+    /// # Return
+    ///
+    /// * Result symbol if successful, otherwise error code.
+    pub fn function_date_next_business_day(
+        &self,
+        args: &[ElemExprNode],
+        list_am_opt: Option<&ListAmortization>,
+        elem_balance_result_opt: Option<&ElemBalanceResult>,
+    ) -> Result<ElemSymbol, ElemError> {
+        let date: NaiveDate = self.get_expr_date(&args[0], list_am_opt, elem_balance_result_opt)?;
+
+        let mut name = String::from("");
+        if args.len() > 1 {
+            match self.get_expr_string(&args[1], list_am_opt, elem_balance_result_opt) {
+                Err(e) => return Err(e),
+                Ok(o) => name = String::from(o.sym_string()),
+            }
+        }
+
+        let calendar = match self.calc_mgr().list_calendar().resolve(name.as_str()) {
+            None => return Err(ElemError::new(crate::ErrorType::Calendar)),
+            Some(o) => o,
+        };
+
+        let mut elem_result_symbol = ElemSymbol::new();
+        elem_result_symbol.set_integer(CoreUtility::date_to_integer(
+            calendar.adjust_date(date, crate::BusinessDayType::Following),
+        ));
+        Ok(elem_result_symbol)
+    }
+
+    /// Parses and executes the current date function.
+    ///
+    /// # Return
+    ///
+    /// * Result symbol if successful, otherwise error code.
 
-        let eom: bool = match self.get_expr_integer(list_am_opt, elem_balance_result_opt) {
-            Err(e) => return Err(e),
-            Ok(o) => o.sym_integer() != 0,
-        };
+    pub fn function_date_now(&self) -> Result<ElemSymbol, ElemError> {
+        let mut elem_result_symbol = ElemSymbol::new();
+        elem_result_symbol.set_integer(CoreUtility::date_to_integer(CoreUtility::date_now()));
+        Ok(elem_result_symbol)
+    }
 
-        while periods > 0 {
-            date = CoreUtility::date_new(orig_date, date, frequency, intervals, eom);
-            periods -= 1;
-        }
+    /// Parses and executes the day of week of date function.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - Function arguments.
+    /// * `list_am_opt` - Amortization list.
+    /// * `elem_balance_result_opt` - Balance results.
+    ///
+    /// # Return
+    ///
+    /// * Result symbol if successful, otherwise error code.
+    pub fn function_date_weekday(
+        &self,
+        args: &[ElemExprNode],
+        list_am_opt: Option<&ListAmortization>,
+        elem_balance_result_opt: Option<&ElemBalanceResult>,
+    ) -> Result<ElemSymbol, ElemError> {
+        let date: NaiveDate = self.get_expr_date(&args[0], list_am_opt, elem_balance_result_opt)?;
 
         let mut elem_result_symbol = ElemSymbol::new();
-        elem_result_symbol.set_integer(date);
+        elem_result_symbol.set_integer(CoreUtility::day_of_week(date));
         Ok(elem_result_symbol)
     }
 
-    /// Parses and executes the current date function.
+    /// Parses and executes the year of date function.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - Function arguments.
+    /// * `list_am_opt` - Amortization list.
+    /// * `elem_balance_result_opt` - Balance results.
     ///
     /// # Return
     ///
     /// * Result symbol if successful, otherwise error code.
+    pub fn function_date_year(
+        &self,
+        args: &[ElemExprNode],
+        list_am_opt: Option<&ListAmortization>,
+        elem_balance_result_opt: Option<&ElemBalanceResult>,
+    ) -> Result<ElemSymbol, ElemError> {
+        let date: NaiveDate = self.get_expr_date(&args[0], list_am_opt, elem_balance_result_opt)?;
 
-    pub fn function_date_now(&self) -> Result<ElemSymbol, crate::ErrorType> {
         let mut elem_result_symbol = ElemSymbol::new();
-        elem_result_symbol.set_integer(CoreUtility::date_now());
-        self.scanner_mut().rescan();
+        elem_result_symbol.set_integer(date.year() as usize);
         Ok(elem_result_symbol)
     }
 
@@ -2444,6 +4139,7 @@ impl CalcExpression {
     ///
     /// # Arguments
     ///
+    /// * `args` - Function arguments.
     /// * `list_am_opt` - Amortization list.
     /// * `elem_balance_result_opt` - Balance results.
     ///
@@ -2453,14 +4149,12 @@ impl CalcExpression {
 
     pub fn function_decimal(
         &self,
+        args: &[ElemExprNode],
         list_am_opt: Option<&ListAmortization>,
         elem_balance_result_opt: Option<&ElemBalanceResult>,
-    ) -> Result<ElemSymbol, crate::ErrorType> {
+    ) -> Result<ElemSymbol, ElemError> {
         let mut elem_result_symbol: ElemSymbol =
-            match self.get_expr_symbol(list_am_opt, elem_balance_result_opt, false) {
-                Err(e) => return Err(e),
-                Ok(o) => o,
-            };
+            self.get_expr_symbol(&args[0], list_am_opt, elem_balance_result_opt, false)?;
 
         match elem_result_symbol.sym_type() {
             crate::TokenType::Integer => {
@@ -2479,6 +4173,7 @@ impl CalcExpression {
     ///
     /// # Arguments
     ///
+    /// * `args` - Function arguments.
     /// * `list_am_opt` - Amortization list.
     /// * `elem_balance_result_opt` - Balance results.
     ///
@@ -2488,51 +4183,42 @@ impl CalcExpression {
 
     pub fn function_default(
         &self,
+        args: &[ElemExprNode],
         list_am_opt: Option<&ListAmortization>,
         elem_balance_result_opt: Option<&ElemBalanceResult>,
-    ) -> Result<ElemSymbol, crate::ErrorType> {
+    ) -> Result<ElemSymbol, ElemError> {
         let mut elem_result_symbol: ElemSymbol =
-            match self.get_expr_symbol(list_am_opt, elem_balance_result_opt, true) {
-                Err(e) => return Err(e),
-                Ok(o) => o,
-            };
+            self.get_expr_symbol(&args[0], list_am_opt, elem_balance_result_opt, true)?;
 
         if elem_result_symbol.sym_type() != crate::TokenType::String {
-            return Err(crate::ErrorType::String);
-        }
-        let token = self.scanner_mut().scan_token();
-        if token == crate::TokenType::Punctuation && self.scanner().get_token().starts_with(',') {
-            self.scanner_mut().scan_token();
-        }
-        let sym_table = self.symbol_table();
-        let mut elem_symbol_opt = sym_table.get_symbol(elem_result_symbol.sym_string());
-        if elem_symbol_opt.is_none() {
-            return self.get_expr_symbol(list_am_opt, elem_balance_result_opt, false);
+            return Err(ElemError::new(crate::ErrorType::String));
         }
-
-        match elem_symbol_opt.as_mut() {
-            None => {
-                return Err(crate::ErrorType::Incomplete);
-            }
-            Some(o) => match o.sym_type() {
-                crate::TokenType::Integer => {
-                    elem_result_symbol.set_integer(o.sym_integer());
-                }
-                crate::TokenType::Decimal => {
-                    elem_result_symbol.set_decimal(o.sym_decimal());
-                }
-                _ => {
-                    elem_result_symbol.set_string(o.sym_string());
+        match self.get_symbol(elem_result_symbol.sym_string()) {
+            None => {}
+            Some(o) => {
+                match o.sym_type() {
+                    crate::TokenType::Integer => {
+                        elem_result_symbol.set_integer(o.sym_integer());
+                    }
+                    crate::TokenType::Decimal => {
+                        elem_result_symbol.set_decimal(o.sym_decimal());
+                    }
+                    _ => {
+                        elem_result_symbol.set_string(o.sym_string());
+                    }
                 }
-            },
+                return Ok(elem_result_symbol);
+            }
         }
-        Ok(elem_result_symbol)
+
+        self.get_expr_symbol(&args[1], list_am_opt, elem_balance_result_opt, false)
     }
 
     /// Parses and executes the descriptor function.
     ///
     /// # Arguments
     ///
+    /// * `args` - Function arguments.
     /// * `list_am_opt` - Amortization list.
     /// * `elem_balance_result_opt` - Balance results.
     ///
@@ -2542,43 +4228,33 @@ impl CalcExpression {
 
     pub fn function_descriptor(
         &self,
+        args: &[ElemExprNode],
         list_am_opt: Option<&ListAmortization>,
         elem_balance_result_opt: Option<&ElemBalanceResult>,
-    ) -> Result<ElemSymbol, crate::ErrorType> {
-        let group: String = match self.get_expr_string(list_am_opt, elem_balance_result_opt) {
-            Err(e) => return Err(e),
-            Ok(o) => String::from(o.sym_string()),
-        };
-
-        let token = self.scanner_mut().scan_token();
-        if token == crate::TokenType::Punctuation && self.scanner().get_token().starts_with(',') {
-            self.scanner_mut().scan_token();
-        }
-
-        let name: String = match self.get_expr_string(list_am_opt, elem_balance_result_opt) {
-            Err(e) => return Err(e),
-            Ok(o) => String::from(o.sym_string()),
-        };
-
-        let token = self.scanner_mut().scan_token();
-        if token == crate::TokenType::Punctuation && self.scanner().get_token().starts_with(',') {
-            self.scanner_mut().scan_token();
-        }
+    ) -> Result<ElemSymbol, ElemError> {
+        let group: String =
+            match self.get_expr_string(&args[0], list_am_opt, elem_balance_result_opt) {
+                Err(e) => return Err(e),
+                Ok(o) => String::from(o.sym_string()),
+            };
 
-        let elem_type: String = match self.get_expr_string(list_am_opt, elem_balance_result_opt) {
-            Err(e) => return Err(e),
-            Ok(o) => String::from(o.sym_string()),
-        };
+        let name: String =
+            match self.get_expr_string(&args[1], list_am_opt, elem_balance_result_opt) {
+                Err(e) => return Err(e),
+                Ok(o) => String::from(o.sym_string()),
+            };
 
-        let token = self.scanner_mut().scan_token();
-        if token == crate::TokenType::Punctuation && self.scanner().get_token().starts_with(',') {
-            self.scanner_mut().scan_token();
-        }
+        let elem_type: String =
+            match self.get_expr_string(&args[2], list_am_opt, elem_balance_result_opt) {
+                Err(e) => return Err(e),
+                Ok(o) => String::from(o.sym_string()),
+            };
 
-        let code: String = match self.get_expr_string(list_am_opt, elem_balance_result_opt) {
-            Err(e) => return Err(e),
-            Ok(o) => String::from(o.sym_string()),
-        };
+        let code: String =
+            match self.get_expr_string(&args[3], list_am_opt, elem_balance_result_opt) {
+                Err(e) => return Err(e),
+                Ok(o) => String::from(o.sym_string()),
+            };
 
         let mut value = CoreUtility::get_descriptor_value(
             Option::from(&self.list_descriptor_user),
@@ -2608,10 +4284,113 @@ impl CalcExpression {
         Ok(elem_result_symbol)
     }
 
+    /// Parses and executes the effective annual rate (EFFECT) function.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - Function arguments.
+    /// * `list_am_opt` - Amortization list.
+    /// * `elem_balance_result_opt` - Balance results.
+    ///
+    /// # Return
+    ///
+    /// * Result symbol if successful, otherwise error code.
+    pub fn function_effect(
+        &self,
+        args: &[ElemExprNode],
+        list_am_opt: Option<&ListAmortization>,
+        elem_balance_result_opt: Option<&ElemBalanceResult>,
+    ) -> Result<ElemSymbol, ElemError> {
+        let values = self.get_expr_numbers(args, 2, list_am_opt, elem_balance_result_opt)?;
+
+        let periods = match values[1].to_usize() {
+            None => return Err(ElemError::new(crate::ErrorType::Integer)),
+            Some(o) => o,
+        };
+
+        let mut elem_result_symbol = ElemSymbol::new();
+        match CoreFinancial::effect(values[0], periods) {
+            Err(e) => return Err(e),
+            Ok(o) => elem_result_symbol.set_decimal(o),
+        }
+
+        Ok(elem_result_symbol)
+    }
+
+    /// Parses and executes the filter list function.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - Function arguments.
+    /// * `list_am_opt` - Amortization list.
+    /// * `elem_balance_result_opt` - Balance results.
+    ///
+    /// # Return
+    ///
+    /// * Result symbol if successful, otherwise error code.
+    pub fn function_filter(
+        &self,
+        args: &[ElemExprNode],
+        list_am_opt: Option<&ListAmortization>,
+        elem_balance_result_opt: Option<&ElemBalanceResult>,
+    ) -> Result<ElemSymbol, ElemError> {
+        let elem_symbol = self.get_expr_list(&args[0], list_am_opt, elem_balance_result_opt)?;
+
+        if args[1].node_type() != crate::NodeType::Symbol || !args[1].nodes().is_empty() {
+            return Err(ElemError::new(crate::ErrorType::Alpha));
+        }
+
+        let name = args[1].name();
+        let mut list_value: Vec<ElemSymbol> = Vec::new();
+
+        for elem in elem_symbol.sym_list().iter() {
+            self.add_symbol(name, elem.copy());
+
+            match self.get_expr_integer(&args[2], list_am_opt, elem_balance_result_opt) {
+                Err(e) => return Err(e),
+                Ok(o) => {
+                    if o.sym_integer() != 0 {
+                        list_value.push(elem.copy());
+                    }
+                }
+            }
+        }
+
+        let mut elem_result_symbol = ElemSymbol::new();
+        elem_result_symbol.set_list(list_value.as_slice());
+        Ok(elem_result_symbol)
+    }
+
+    /// Parses and executes the first of list function.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - Function arguments.
+    /// * `list_am_opt` - Amortization list.
+    /// * `elem_balance_result_opt` - Balance results.
+    ///
+    /// # Return
+    ///
+    /// * Result symbol if successful, otherwise error code.
+    pub fn function_first(
+        &self,
+        args: &[ElemExprNode],
+        list_am_opt: Option<&ListAmortization>,
+        elem_balance_result_opt: Option<&ElemBalanceResult>,
+    ) -> Result<ElemSymbol, ElemError> {
+        let elem_symbol = self.get_expr_list(&args[0], list_am_opt, elem_balance_result_opt)?;
+
+        match elem_symbol.sym_list().first() {
+            None => Err(ElemError::new(crate::ErrorType::Index)),
+            Some(o) => Ok(o.copy()),
+        }
+    }
+
     /// Parses and executes the format function.
     ///
     /// # Arguments
     ///
+    /// * `args` - Function arguments.
     /// * `list_am_opt` - Amortization list.
     /// * `elem_balance_result_opt` - Balance results.
     ///
@@ -2621,19 +4400,17 @@ impl CalcExpression {
 
     pub fn function_format(
         &self,
+        args: &[ElemExprNode],
         list_am_opt: Option<&ListAmortization>,
         elem_balance_result_opt: Option<&ElemBalanceResult>,
-    ) -> Result<ElemSymbol, crate::ErrorType> {
+    ) -> Result<ElemSymbol, ElemError> {
         let mut elem_result_symbol: ElemSymbol =
-            match self.get_expr_symbol(list_am_opt, elem_balance_result_opt, false) {
-                Err(e) => return Err(e),
-                Ok(o) => o,
-            };
+            self.get_expr_symbol(&args[0], list_am_opt, elem_balance_result_opt, false)?;
 
         match elem_result_symbol.sym_type() {
             crate::TokenType::Integer => {
                 elem_result_symbol
-                    .set_string(format!("{}", elem_result_symbol.sym_integer()).as_str());
+                    .set_string(format!("{}", elem_result_symbol.sym_integeri()).as_str());
             }
             crate::TokenType::Decimal => {
                 elem_result_symbol.set_string(
@@ -2657,6 +4434,7 @@ impl CalcExpression {
     ///
     /// # Arguments
     ///
+    /// * `args` - Function arguments.
     /// * `list_am_opt` - Amortization list.
     /// * `elem_balance_result_opt` - Balance results.
     ///
@@ -2666,14 +4444,12 @@ impl CalcExpression {
 
     pub fn function_format_currency(
         &self,
+        args: &[ElemExprNode],
         list_am_opt: Option<&ListAmortization>,
         elem_balance_result_opt: Option<&ElemBalanceResult>,
-    ) -> Result<ElemSymbol, crate::ErrorType> {
+    ) -> Result<ElemSymbol, ElemError> {
         let mut elem_result_symbol: ElemSymbol =
-            match self.get_expr_symbol(list_am_opt, elem_balance_result_opt, false) {
-                Err(e) => return Err(e),
-                Ok(o) => o,
-            };
+            self.get_expr_symbol(&args[0], list_am_opt, elem_balance_result_opt, false)?;
 
         match elem_result_symbol.sym_type() {
             crate::TokenType::Integer => {
@@ -2704,6 +4480,7 @@ impl CalcExpression {
     ///
     /// # Arguments
     ///
+    /// * `args` - Function arguments.
     /// * `list_am_opt` - Amortization list.
     /// * `elem_balance_result_opt` - Balance results.
     ///
@@ -2713,21 +4490,14 @@ impl CalcExpression {
 
     pub fn function_format_date(
         &self,
+        args: &[ElemExprNode],
         list_am_opt: Option<&ListAmortization>,
         elem_balance_result_opt: Option<&ElemBalanceResult>,
-    ) -> Result<ElemSymbol, crate::ErrorType> {
-        let mut elem_result_symbol: ElemSymbol =
-            match self.get_expr_integer(list_am_opt, elem_balance_result_opt) {
-                Err(e) => return Err(e),
-                Ok(o) => o,
-            };
+    ) -> Result<ElemSymbol, ElemError> {
+        let date: NaiveDate = self.get_expr_date(&args[0], list_am_opt, elem_balance_result_opt)?;
 
-        elem_result_symbol.set_string(
-            self.calc_mgr()
-                .list_locale()
-                .format_date_out(elem_result_symbol.sym_integer())
-                .as_str(),
-        );
+        let mut elem_result_symbol = ElemSymbol::new();
+        elem_result_symbol.set_string(self.calc_mgr().list_locale().format_date_out(date).as_str());
         Ok(elem_result_symbol)
     }
 
@@ -2735,6 +4505,7 @@ impl CalcExpression {
     ///
     /// # Arguments
     ///
+    /// * `args` - Function arguments.
     /// * `list_am_opt` - Amortization list.
     /// * `elem_balance_result_opt` - Balance results.
     ///
@@ -2744,14 +4515,12 @@ impl CalcExpression {
 
     pub fn function_format_number(
         &self,
+        args: &[ElemExprNode],
         list_am_opt: Option<&ListAmortization>,
         elem_balance_result_opt: Option<&ElemBalanceResult>,
-    ) -> Result<ElemSymbol, crate::ErrorType> {
+    ) -> Result<ElemSymbol, ElemError> {
         let mut elem_result_symbol: ElemSymbol =
-            match self.get_expr_symbol(list_am_opt, elem_balance_result_opt, false) {
-                Err(e) => return Err(e),
-                Ok(o) => o,
-            };
+            self.get_expr_symbol(&args[0], list_am_opt, elem_balance_result_opt, false)?;
 
         match elem_result_symbol.sym_type() {
             crate::TokenType::Integer => {
@@ -2775,10 +4544,47 @@ impl CalcExpression {
         Ok(elem_result_symbol)
     }
 
+    /// Parses and executes the future value (FV) function.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - Function arguments.
+    /// * `list_am_opt` - Amortization list.
+    /// * `elem_balance_result_opt` - Balance results.
+    ///
+    /// # Return
+    ///
+    /// * Result symbol if successful, otherwise error code.
+    pub fn function_fv(
+        &self,
+        args: &[ElemExprNode],
+        list_am_opt: Option<&ListAmortization>,
+        elem_balance_result_opt: Option<&ElemBalanceResult>,
+    ) -> Result<ElemSymbol, ElemError> {
+        let values = self.get_expr_numbers(args, 5, list_am_opt, elem_balance_result_opt)?;
+
+        let mut elem_result_symbol = ElemSymbol::new();
+        match CoreFinancial::fv(
+            values[0],
+            values[1],
+            values[2],
+            values[3],
+            values[4] != dec!(0.0),
+        ) {
+            Err(e) => return Err(e),
+            Ok(o) => {
+                elem_result_symbol.set_decimal(CoreUtility::util_round(o, self.decimal_digits))
+            }
+        }
+
+        Ok(elem_result_symbol)
+    }
+
     /// Parses and executes the if function.
     ///
     /// # Arguments
     ///
+    /// * `args` - Function arguments.
     /// * `list_am_opt` - Amortization list.
     /// * `elem_balance_result_opt` - Balance results.
     ///
@@ -2788,97 +4594,126 @@ impl CalcExpression {
 
     pub fn function_if(
         &self,
+        args: &[ElemExprNode],
         list_am_opt: Option<&ListAmortization>,
         elem_balance_result_opt: Option<&ElemBalanceResult>,
-    ) -> Result<ElemSymbol, crate::ErrorType> {
+    ) -> Result<ElemSymbol, ElemError> {
         let elem_symbol: ElemSymbol =
-            match self.get_expr_integer(list_am_opt, elem_balance_result_opt) {
-                Err(e) => return Err(e),
-                Ok(o) => o,
-            };
+            self.get_expr_integer(&args[0], list_am_opt, elem_balance_result_opt)?;
 
-        let token = self.scanner_mut().scan_token();
-        if token == crate::TokenType::Punctuation && self.scanner().get_token().starts_with(',') {
-            self.scanner_mut().scan_token();
+        if elem_symbol.sym_integer() == 0 {
+            return self.get_expr_symbol(&args[2], list_am_opt, elem_balance_result_opt, false);
         }
 
-        let mut elem_result_symbol: ElemSymbol =
-            match self.get_expr_symbol(list_am_opt, elem_balance_result_opt, false) {
-                Err(e) => return Err(e),
-                Ok(o) => o,
-            };
+        self.get_expr_symbol(&args[1], list_am_opt, elem_balance_result_opt, false)
+    }
 
-        let token = self.scanner_mut().scan_token();
-        if token == crate::TokenType::Punctuation && self.scanner().get_token().starts_with(',') {
-            self.scanner_mut().scan_token();
-        }
+    /// Parses and executes the integer function.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - Function arguments.
+    /// * `list_am_opt` - Amortization list.
+    /// * `elem_balance_result_opt` - Balance results.
+    ///
+    /// # Return
+    ///
+    /// * Result symbol if successful, otherwise error code.
 
-        if elem_symbol.sym_integer() == 0 {
-            let result = self.get_expr_symbol(list_am_opt, elem_balance_result_opt, false);
-            match result {
-                Err(e) => {
-                    return Err(e);
+    pub fn function_integer(
+        &self,
+        args: &[ElemExprNode],
+        list_am_opt: Option<&ListAmortization>,
+        elem_balance_result_opt: Option<&ElemBalanceResult>,
+    ) -> Result<ElemSymbol, ElemError> {
+        let mut elem_result_symbol: ElemSymbol =
+            self.get_expr_symbol(&args[0], list_am_opt, elem_balance_result_opt, false)?;
+
+        match elem_result_symbol.sym_type() {
+            crate::TokenType::Decimal => match elem_result_symbol.sym_decimal().to_i32() {
+                None => {
+                    elem_result_symbol.set_integer(0);
                 }
-                Ok(o) => {
-                    elem_result_symbol = o;
+                Some(o) => {
+                    elem_result_symbol.set_integeri(o);
                 }
+            },
+            crate::TokenType::String => {
+                elem_result_symbol
+                    .set_integeri(CoreUtility::parse_integeri(elem_result_symbol.sym_string()));
             }
-        } else {
-            let result = self.get_expr_symbol(list_am_opt, elem_balance_result_opt, false); // Scan past the symbol
-            match result {
-                Err(e) => {
-                    return Err(e);
-                }
-                Ok(_o) => {}
+            _ => {}
+        }
+        Ok(elem_result_symbol)
+    }
+
+    /// Parses and executes the interest payment (IPMT) function.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - Function arguments.
+    /// * `list_am_opt` - Amortization list.
+    /// * `elem_balance_result_opt` - Balance results.
+    ///
+    /// # Return
+    ///
+    /// * Result symbol if successful, otherwise error code.
+    pub fn function_ipmt(
+        &self,
+        args: &[ElemExprNode],
+        list_am_opt: Option<&ListAmortization>,
+        elem_balance_result_opt: Option<&ElemBalanceResult>,
+    ) -> Result<ElemSymbol, ElemError> {
+        let values = self.get_expr_numbers(args, 6, list_am_opt, elem_balance_result_opt)?;
+
+        let mut elem_result_symbol = ElemSymbol::new();
+        match CoreFinancial::ipmt(
+            values[0],
+            values[1],
+            values[2],
+            values[3],
+            values[4],
+            values[5] != dec!(0.0),
+        ) {
+            Err(e) => return Err(e),
+            Ok(o) => {
+                elem_result_symbol.set_decimal(CoreUtility::util_round(o, self.decimal_digits))
             }
         }
+
         Ok(elem_result_symbol)
     }
 
-    /// Parses and executes the integer function.
+    /// Parses and executes the last of list function.
     ///
     /// # Arguments
     ///
+    /// * `args` - Function arguments.
     /// * `list_am_opt` - Amortization list.
     /// * `elem_balance_result_opt` - Balance results.
     ///
     /// # Return
     ///
     /// * Result symbol if successful, otherwise error code.
-
-    pub fn function_integer(
+    pub fn function_last(
         &self,
+        args: &[ElemExprNode],
         list_am_opt: Option<&ListAmortization>,
         elem_balance_result_opt: Option<&ElemBalanceResult>,
-    ) -> Result<ElemSymbol, crate::ErrorType> {
-        let mut elem_result_symbol: ElemSymbol =
-            match self.get_expr_symbol(list_am_opt, elem_balance_result_opt, false) {
-                Err(e) => return Err(e),
-                Ok(o) => o,
-            };
+    ) -> Result<ElemSymbol, ElemError> {
+        let elem_symbol = self.get_expr_list(&args[0], list_am_opt, elem_balance_result_opt)?;
 
-        match elem_result_symbol.sym_type() {
-            crate::TokenType::Decimal => match elem_result_symbol.sym_decimal().to_i32() {
-                None => {
-                    elem_result_symbol.set_integer(0);
-                }
-                Some(o) => {
-                    elem_result_symbol.set_integeri(o);
-                }
-            },
-            crate::TokenType::String => {
-                elem_result_symbol
-                    .set_integeri(CoreUtility::parse_integeri(elem_result_symbol.sym_string()));
-            }
-            _ => {}
+        match elem_symbol.sym_list().last() {
+            None => Err(ElemError::new(crate::ErrorType::Index)),
+            Some(o) => Ok(o.copy()),
         }
-        Ok(elem_result_symbol)
     }
 
     /// Parses and executes the length function.
     ///
     /// # Arguments
     ///
+    /// * `args` - Function arguments.
     /// * `list_am_opt` - Amortization list.
     /// * `elem_balance_result_opt` - Balance results.
     ///
@@ -2888,23 +4723,53 @@ impl CalcExpression {
 
     pub fn function_len(
         &self,
+        args: &[ElemExprNode],
         list_am_opt: Option<&ListAmortization>,
         elem_balance_result_opt: Option<&ElemBalanceResult>,
-    ) -> Result<ElemSymbol, crate::ErrorType> {
+    ) -> Result<ElemSymbol, ElemError> {
         let mut elem_result_symbol: ElemSymbol =
-            match self.get_expr_string(list_am_opt, elem_balance_result_opt) {
-                Err(e) => return Err(e),
-                Ok(o) => o,
-            };
+            self.get_expr_string(&args[0], list_am_opt, elem_balance_result_opt)?;
 
         elem_result_symbol.set_integer(elem_result_symbol.sym_string().len());
         Ok(elem_result_symbol)
     }
 
+    /// Parses and executes the new list function.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - Function arguments.
+    /// * `list_am_opt` - Amortization list.
+    /// * `elem_balance_result_opt` - Balance results.
+    ///
+    /// # Return
+    ///
+    /// * Result symbol if successful, otherwise error code.
+    pub fn function_list(
+        &self,
+        args: &[ElemExprNode],
+        list_am_opt: Option<&ListAmortization>,
+        elem_balance_result_opt: Option<&ElemBalanceResult>,
+    ) -> Result<ElemSymbol, ElemError> {
+        let mut list_value: Vec<ElemSymbol> = Vec::new();
+
+        for arg in args.iter() {
+            match self.get_expr_symbol(arg, list_am_opt, elem_balance_result_opt, false) {
+                Err(e) => return Err(e),
+                Ok(o) => list_value.push(o),
+            }
+        }
+
+        let mut elem_result_symbol = ElemSymbol::new();
+        elem_result_symbol.set_list(list_value.as_slice());
+        Ok(elem_result_symbol)
+    }
+
     /// Parses and executes the lowercase function.
     ///
     /// # Arguments
     ///
+    /// * `args` - Function arguments.
     /// * `list_am_opt` - Amortization list.
     /// * `elem_balance_result_opt` - Balance results.
     ///
@@ -2914,14 +4779,12 @@ impl CalcExpression {
 
     pub fn function_lowercase(
         &self,
+        args: &[ElemExprNode],
         list_am_opt: Option<&ListAmortization>,
         elem_balance_result_opt: Option<&ElemBalanceResult>,
-    ) -> Result<ElemSymbol, crate::ErrorType> {
+    ) -> Result<ElemSymbol, ElemError> {
         let mut elem_result_symbol: ElemSymbol =
-            match self.get_expr_string(list_am_opt, elem_balance_result_opt) {
-                Err(e) => return Err(e),
-                Ok(o) => o,
-            };
+            self.get_expr_string(&args[0], list_am_opt, elem_balance_result_opt)?;
 
         elem_result_symbol.set_string(elem_result_symbol.sym_string().to_lowercase().as_str());
         Ok(elem_result_symbol)
@@ -2931,6 +4794,7 @@ impl CalcExpression {
     ///
     /// # Arguments
     ///
+    /// * `args` - Function arguments.
     /// * `list_am_opt` - Amortization list.
     /// * `elem_balance_result_opt` - Balance results.
     ///
@@ -2940,29 +4804,22 @@ impl CalcExpression {
 
     pub fn function_max(
         &self,
+        args: &[ElemExprNode],
         list_am_opt: Option<&ListAmortization>,
         elem_balance_result_opt: Option<&ElemBalanceResult>,
-    ) -> Result<ElemSymbol, crate::ErrorType> {
+    ) -> Result<ElemSymbol, ElemError> {
         let mut elem_result_symbol: ElemSymbol =
-            match self.get_expr_symbol(list_am_opt, elem_balance_result_opt, false) {
-                Err(e) => return Err(e),
-                Ok(o) => o,
-            };
+            self.get_expr_symbol(&args[0], list_am_opt, elem_balance_result_opt, false)?;
 
         let result1: Decimal = match elem_result_symbol.sym_type() {
             crate::TokenType::Integer => {
                 dec!(elem_result_symbol.sym_integer())
             }
             crate::TokenType::Decimal => elem_result_symbol.sym_decimal(),
-            _ => return Err(crate::ErrorType::Decimal),
+            _ => return Err(ElemError::new(crate::ErrorType::Decimal)),
         };
 
-        let token = self.scanner_mut().scan_token();
-        if token == crate::TokenType::Punctuation && self.scanner().get_token().starts_with(',') {
-            self.scanner_mut().scan_token();
-        }
-
-        let result = self.get_expr_symbol(list_am_opt, elem_balance_result_opt, false);
+        let result = self.get_expr_symbol(&args[1], list_am_opt, elem_balance_result_opt, false);
         match result {
             Err(e) => {
                 return Err(e);
@@ -2977,7 +4834,7 @@ impl CalcExpression {
                 dec!(elem_result_symbol.sym_integer())
             }
             crate::TokenType::Decimal => elem_result_symbol.sym_decimal(),
-            _ => return Err(crate::ErrorType::Decimal),
+            _ => return Err(ElemError::new(crate::ErrorType::Decimal)),
         };
 
         elem_result_symbol.set_decimal(if result1 >= result2 { result1 } else { result2 });
@@ -2988,6 +4845,7 @@ impl CalcExpression {
     ///
     /// # Arguments
     ///
+    /// * `args` - Function arguments.
     /// * `list_am_opt` - Amortization list.
     /// * `elem_balance_result_opt` - Balance results.
     ///
@@ -2997,22 +4855,15 @@ impl CalcExpression {
 
     pub fn function_mid(
         &self,
+        args: &[ElemExprNode],
         list_am_opt: Option<&ListAmortization>,
         elem_balance_result_opt: Option<&ElemBalanceResult>,
-    ) -> Result<ElemSymbol, crate::ErrorType> {
+    ) -> Result<ElemSymbol, ElemError> {
         let mut elem_result_symbol: ElemSymbol =
-            match self.get_expr_string(list_am_opt, elem_balance_result_opt) {
-                Err(e) => return Err(e),
-                Ok(o) => o,
-            };
+            self.get_expr_string(&args[0], list_am_opt, elem_balance_result_opt)?;
 
         let text = String::from(elem_result_symbol.sym_string());
-        let token = self.scanner_mut().scan_token();
-        if token == crate::TokenType::Punctuation && self.scanner().get_token().starts_with(',') {
-            self.scanner_mut().scan_token();
-        }
-
-        let result = self.get_expr_integer(list_am_opt, elem_balance_result_opt);
+        let result = self.get_expr_integer(&args[1], list_am_opt, elem_balance_result_opt);
         match result {
             Err(e) => {
                 return Err(e);
@@ -3030,12 +4881,7 @@ impl CalcExpression {
         if start == usize::MAX || start >= text.len() {
             start = 0;
         }
-        let token = self.scanner_mut().scan_token();
-        if token == crate::TokenType::Punctuation && self.scanner().get_token().starts_with(',') {
-            self.scanner_mut().scan_token();
-        }
-
-        let result = self.get_expr_integer(list_am_opt, elem_balance_result_opt);
+        let result = self.get_expr_integer(&args[2], list_am_opt, elem_balance_result_opt);
         match result {
             Err(e) => {
                 return Err(e);
@@ -3059,6 +4905,7 @@ impl CalcExpression {
     ///
     /// # Arguments
     ///
+    /// * `args` - Function arguments.
     /// * `list_am_opt` - Amortization list.
     /// * `elem_balance_result_opt` - Balance results.
     ///
@@ -3068,29 +4915,22 @@ impl CalcExpression {
 
     pub fn function_min(
         &self,
+        args: &[ElemExprNode],
         list_am_opt: Option<&ListAmortization>,
         elem_balance_result_opt: Option<&ElemBalanceResult>,
-    ) -> Result<ElemSymbol, crate::ErrorType> {
+    ) -> Result<ElemSymbol, ElemError> {
         let mut elem_result_symbol: ElemSymbol =
-            match self.get_expr_symbol(list_am_opt, elem_balance_result_opt, false) {
-                Err(e) => return Err(e),
-                Ok(o) => o,
-            };
+            self.get_expr_symbol(&args[0], list_am_opt, elem_balance_result_opt, false)?;
 
         let result1: Decimal = match elem_result_symbol.sym_type() {
             crate::TokenType::Integer => {
                 dec!(elem_result_symbol.sym_integer())
             }
             crate::TokenType::Decimal => elem_result_symbol.sym_decimal(),
-            _ => return Err(crate::ErrorType::Decimal),
+            _ => return Err(ElemError::new(crate::ErrorType::Decimal)),
         };
 
-        let token = self.scanner_mut().scan_token();
-        if token == crate::TokenType::Punctuation && self.scanner().get_token().starts_with(',') {
-            self.scanner_mut().scan_token();
-        }
-
-        match self.get_expr_symbol(list_am_opt, elem_balance_result_opt, false) {
+        match self.get_expr_symbol(&args[1], list_am_opt, elem_balance_result_opt, false) {
             Err(e) => {
                 return Err(e);
             }
@@ -3104,64 +4944,145 @@ impl CalcExpression {
                 dec!(elem_result_symbol.sym_integer())
             }
             crate::TokenType::Decimal => elem_result_symbol.sym_decimal(),
-            _ => return Err(crate::ErrorType::Decimal),
+            _ => return Err(ElemError::new(crate::ErrorType::Decimal)),
         };
 
         elem_result_symbol.set_decimal(if result1 <= result2 { result1 } else { result2 });
         Ok(elem_result_symbol)
     }
 
-    /// Parses and executes the parse function.
+    /// Parses and executes the nominal annual rate (NOMINAL) function.
     ///
     /// # Arguments
     ///
+    /// * `args` - Function arguments.
     /// * `list_am_opt` - Amortization list.
     /// * `elem_balance_result_opt` - Balance results.
     ///
     /// # Return
     ///
     /// * Result symbol if successful, otherwise error code.
-
-    pub fn function_parse(
+    pub fn function_nominal(
         &self,
+        args: &[ElemExprNode],
         list_am_opt: Option<&ListAmortization>,
         elem_balance_result_opt: Option<&ElemBalanceResult>,
-    ) -> Result<ElemSymbol, crate::ErrorType> {
-        let mut elem_result_symbol: ElemSymbol =
-            match self.get_expr_string(list_am_opt, elem_balance_result_opt) {
-                Err(e) => return Err(e),
-                Ok(o) => o,
-            };
+    ) -> Result<ElemSymbol, ElemError> {
+        let values = self.get_expr_numbers(args, 2, list_am_opt, elem_balance_result_opt)?;
 
-        let text = String::from(elem_result_symbol.sym_string());
-        let token = self.scanner_mut().scan_token();
-        if token == crate::TokenType::Punctuation && self.scanner().get_token().starts_with(',') {
-            self.scanner_mut().scan_token();
-        }
+        let periods = match values[1].to_usize() {
+            None => return Err(ElemError::new(crate::ErrorType::Integer)),
+            Some(o) => o,
+        };
 
-        match self.get_expr_string(list_am_opt, elem_balance_result_opt) {
+        let mut elem_result_symbol = ElemSymbol::new();
+        match CoreFinancial::nominal(values[0], periods) {
             Err(e) => return Err(e),
-            Ok(o) => o,
-        };
+            Ok(o) => elem_result_symbol.set_decimal(o),
+        }
 
-        let delims = String::from(elem_result_symbol.sym_string());
-        let token = self.scanner_mut().scan_token();
-        if token == crate::TokenType::Punctuation && self.scanner().get_token().starts_with(',') {
-            self.scanner_mut().scan_token();
+        Ok(elem_result_symbol)
+    }
+
+    /// Parses and executes the number of periods (NPER) function.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - Function arguments.
+    /// * `list_am_opt` - Amortization list.
+    /// * `elem_balance_result_opt` - Balance results.
+    ///
+    /// # Return
+    ///
+    /// * Result symbol if successful, otherwise error code.
+    pub fn function_nper(
+        &self,
+        args: &[ElemExprNode],
+        list_am_opt: Option<&ListAmortization>,
+        elem_balance_result_opt: Option<&ElemBalanceResult>,
+    ) -> Result<ElemSymbol, ElemError> {
+        let values = self.get_expr_numbers(args, 5, list_am_opt, elem_balance_result_opt)?;
+
+        let mut elem_result_symbol = ElemSymbol::new();
+        match CoreFinancial::nper(
+            values[0],
+            values[1],
+            values[2],
+            values[3],
+            values[4] != dec!(0.0),
+        ) {
+            Err(e) => return Err(e),
+            Ok(o) => elem_result_symbol.set_decimal(o),
         }
 
-        if self.scanner().get_type() != crate::TokenType::Alpha {
-            return Err(crate::ErrorType::Alpha);
+        Ok(elem_result_symbol)
+    }
+
+    /// Parses and executes the net present value (NPV) function.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - Function arguments.
+    /// * `list_am_opt` - Amortization list.
+    /// * `elem_balance_result_opt` - Balance results.
+    ///
+    /// # Return
+    ///
+    /// * Result symbol if successful, otherwise error code.
+    pub fn function_npv(
+        &self,
+        args: &[ElemExprNode],
+        list_am_opt: Option<&ListAmortization>,
+        elem_balance_result_opt: Option<&ElemBalanceResult>,
+    ) -> Result<ElemSymbol, ElemError> {
+        let values = self.get_expr_numbers(args, 0, list_am_opt, elem_balance_result_opt)?;
+
+        let mut elem_result_symbol = ElemSymbol::new();
+        match CoreFinancial::npv(values[0], &values[1..]) {
+            Err(e) => return Err(e),
+            Ok(o) => {
+                elem_result_symbol.set_decimal(CoreUtility::util_round(o, self.decimal_digits))
+            }
         }
 
-        let tokens: Vec<&str>;
-        let name: String;
-        {
-            let scanner = self.scanner();
-            name = String::from(scanner.get_token());
-            tokens = text.split(delims.as_str()).collect();
+        Ok(elem_result_symbol)
+    }
+
+    /// Parses and executes the parse function.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - Function arguments.
+    /// * `list_am_opt` - Amortization list.
+    /// * `elem_balance_result_opt` - Balance results.
+    ///
+    /// # Return
+    ///
+    /// * Result symbol if successful, otherwise error code.
+
+    pub fn function_parse(
+        &self,
+        args: &[ElemExprNode],
+        list_am_opt: Option<&ListAmortization>,
+        elem_balance_result_opt: Option<&ElemBalanceResult>,
+    ) -> Result<ElemSymbol, ElemError> {
+        let mut elem_result_symbol: ElemSymbol =
+            self.get_expr_string(&args[0], list_am_opt, elem_balance_result_opt)?;
+
+        let text = String::from(elem_result_symbol.sym_string());
+        let delims: String =
+            match self.get_expr_string(&args[1], list_am_opt, elem_balance_result_opt) {
+                Err(e) => return Err(e),
+                Ok(o) => String::from(o.sym_string()),
+            };
+
+        if args[2].node_type() != crate::NodeType::Symbol || !args[2].nodes().is_empty() {
+            return Err(ElemError::new(crate::ErrorType::Alpha));
         }
 
+        let name = args[2].name();
+        let tokens: Vec<&str> = text.split(delims.as_str()).collect();
+
         let token_count = tokens.len();
         elem_result_symbol.set_integer(token_count);
         for (index, token) in tokens.iter().enumerate() {
@@ -3169,55 +5090,199 @@ impl CalcExpression {
             elem_symbol.set_string(token);
 
             let text_fmt = format!("{}[{}]", name, index);
-            self.symbol_table_mut()
-                .add_symbol(text_fmt.as_str(), elem_symbol);
+            self.add_symbol(text_fmt.as_str(), elem_symbol);
+        }
+        Ok(elem_result_symbol)
+    }
+
+    /// Parses and executes the payment (PMT) function.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - Function arguments.
+    /// * `list_am_opt` - Amortization list.
+    /// * `elem_balance_result_opt` - Balance results.
+    ///
+    /// # Return
+    ///
+    /// * Result symbol if successful, otherwise error code.
+    pub fn function_pmt(
+        &self,
+        args: &[ElemExprNode],
+        list_am_opt: Option<&ListAmortization>,
+        elem_balance_result_opt: Option<&ElemBalanceResult>,
+    ) -> Result<ElemSymbol, ElemError> {
+        let values = self.get_expr_numbers(args, 5, list_am_opt, elem_balance_result_opt)?;
+
+        let mut elem_result_symbol = ElemSymbol::new();
+        match CoreFinancial::pmt(
+            values[0],
+            values[1],
+            values[2],
+            values[3],
+            values[4] != dec!(0.0),
+        ) {
+            Err(e) => return Err(e),
+            Ok(o) => {
+                elem_result_symbol.set_decimal(CoreUtility::util_round(o, self.decimal_digits))
+            }
+        }
+
+        Ok(elem_result_symbol)
+    }
+
+    /// Parses and executes the principal payment (PPMT) function.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - Function arguments.
+    /// * `list_am_opt` - Amortization list.
+    /// * `elem_balance_result_opt` - Balance results.
+    ///
+    /// # Return
+    ///
+    /// * Result symbol if successful, otherwise error code.
+    pub fn function_ppmt(
+        &self,
+        args: &[ElemExprNode],
+        list_am_opt: Option<&ListAmortization>,
+        elem_balance_result_opt: Option<&ElemBalanceResult>,
+    ) -> Result<ElemSymbol, ElemError> {
+        let values = self.get_expr_numbers(args, 6, list_am_opt, elem_balance_result_opt)?;
+
+        let mut elem_result_symbol = ElemSymbol::new();
+        match CoreFinancial::ppmt(
+            values[0],
+            values[1],
+            values[2],
+            values[3],
+            values[4],
+            values[5] != dec!(0.0),
+        ) {
+            Err(e) => return Err(e),
+            Ok(o) => {
+                elem_result_symbol.set_decimal(CoreUtility::util_round(o, self.decimal_digits))
+            }
+        }
+
+        Ok(elem_result_symbol)
+    }
+
+    /// Parses and executes the pr function.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - Function arguments.
+    /// * `list_am_opt` - Amortization list.
+    /// * `elem_balance_result_opt` - Balance results.
+    ///
+    /// # Return
+    ///
+    /// * Result symbol if successful, otherwise error code.
+
+    pub fn function_pr(
+        &self,
+        args: &[ElemExprNode],
+        list_am_opt: Option<&ListAmortization>,
+        elem_balance_result_opt: Option<&ElemBalanceResult>,
+    ) -> Result<ElemSymbol, ElemError> {
+        let mut elem_result_symbol: ElemSymbol =
+            self.get_expr_symbol(&args[0], list_am_opt, elem_balance_result_opt, false)?;
+
+        let dresult: Decimal = match elem_result_symbol.sym_type() {
+            crate::TokenType::Integer => {
+                dec!(elem_result_symbol.sym_integer())
+            }
+            crate::TokenType::Decimal => elem_result_symbol.sym_decimal(),
+            _ => return Err(ElemError::new(crate::ErrorType::Decimal)),
+        };
+
+        let mut dpr: Decimal = dec!(0.0);
+        match list_am_opt {
+            None => {}
+            Some(o) => {
+                dpr = CoreUtility::rate_nar_to_pr(
+                    dresult / dec!(100.0),
+                    o.frequency(),
+                    crate::DEFAULT_DAYS_IN_YEAR,
+                ) * dec!(100.0);
+            }
+        }
+
+        elem_result_symbol.set_decimal(dpr);
+        Ok(elem_result_symbol)
+    }
+
+    /// Parses and executes the present value (PV) function.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - Function arguments.
+    /// * `list_am_opt` - Amortization list.
+    /// * `elem_balance_result_opt` - Balance results.
+    ///
+    /// # Return
+    ///
+    /// * Result symbol if successful, otherwise error code.
+    pub fn function_pv(
+        &self,
+        args: &[ElemExprNode],
+        list_am_opt: Option<&ListAmortization>,
+        elem_balance_result_opt: Option<&ElemBalanceResult>,
+    ) -> Result<ElemSymbol, ElemError> {
+        let values = self.get_expr_numbers(args, 5, list_am_opt, elem_balance_result_opt)?;
+
+        let mut elem_result_symbol = ElemSymbol::new();
+        match CoreFinancial::pv(
+            values[0],
+            values[1],
+            values[2],
+            values[3],
+            values[4] != dec!(0.0),
+        ) {
+            Err(e) => return Err(e),
+            Ok(o) => {
+                elem_result_symbol.set_decimal(CoreUtility::util_round(o, self.decimal_digits))
+            }
         }
+
         Ok(elem_result_symbol)
     }
 
-    /// Parses and executes the pr function.
+    /// Parses and executes the periodic rate (RATE) function.
     ///
     /// # Arguments
     ///
+    /// * `args` - Function arguments.
     /// * `list_am_opt` - Amortization list.
     /// * `elem_balance_result_opt` - Balance results.
     ///
     /// # Return
     ///
     /// * Result symbol if successful, otherwise error code.
-
-    pub fn function_pr(
+    pub fn function_rate(
         &self,
+        args: &[ElemExprNode],
         list_am_opt: Option<&ListAmortization>,
         elem_balance_result_opt: Option<&ElemBalanceResult>,
-    ) -> Result<ElemSymbol, crate::ErrorType> {
-        let mut elem_result_symbol: ElemSymbol =
-            match self.get_expr_symbol(list_am_opt, elem_balance_result_opt, false) {
-                Err(e) => return Err(e),
-                Ok(o) => o,
-            };
+    ) -> Result<ElemSymbol, ElemError> {
+        let values = self.get_expr_numbers(args, 6, list_am_opt, elem_balance_result_opt)?;
 
-        let dresult: Decimal = match elem_result_symbol.sym_type() {
-            crate::TokenType::Integer => {
-                dec!(elem_result_symbol.sym_integer())
-            }
-            crate::TokenType::Decimal => elem_result_symbol.sym_decimal(),
-            _ => return Err(crate::ErrorType::Decimal),
-        };
+        let guess = if args.len() > 5 { values[5] } else { dec!(0.1) };
 
-        let mut dpr: Decimal = dec!(0.0);
-        match list_am_opt {
-            None => {}
-            Some(o) => {
-                dpr = CoreUtility::rate_nar_to_pr(
-                    dresult / dec!(100.0),
-                    o.frequency(),
-                    crate::DEFAULT_DAYS_IN_YEAR,
-                ) * dec!(100.0);
-            }
+        let mut elem_result_symbol = ElemSymbol::new();
+        match CoreFinancial::rate(
+            values[0],
+            values[1],
+            values[2],
+            values[3],
+            values[4] != dec!(0.0),
+            guess,
+        ) {
+            Err(e) => return Err(e),
+            Ok(o) => elem_result_symbol.set_decimal(o),
         }
 
-        elem_result_symbol.set_decimal(dpr);
         Ok(elem_result_symbol)
     }
 
@@ -3225,6 +5290,7 @@ impl CalcExpression {
     ///
     /// # Arguments
     ///
+    /// * `args` - Function arguments.
     /// * `list_am_opt` - Amortization list.
     /// * `elem_balance_result_opt` - Balance results.
     ///
@@ -3234,34 +5300,35 @@ impl CalcExpression {
 
     pub fn function_replace(
         &self,
+        args: &[ElemExprNode],
         list_am_opt: Option<&ListAmortization>,
         elem_balance_result_opt: Option<&ElemBalanceResult>,
-    ) -> Result<ElemSymbol, crate::ErrorType> {
-        let text: String = match self.get_expr_string(list_am_opt, elem_balance_result_opt) {
-            Err(e) => return Err(e),
-            Ok(o) => String::from(o.sym_string()),
-        };
+    ) -> Result<ElemSymbol, ElemError> {
+        let text: String =
+            match self.get_expr_string(&args[0], list_am_opt, elem_balance_result_opt) {
+                Err(e) => return Err(e),
+                Ok(o) => String::from(o.sym_string()),
+            };
 
-        let token = self.scanner_mut().scan_token();
-        if token == crate::TokenType::Punctuation && self.scanner().get_token().starts_with(',') {
-            self.scanner_mut().scan_token();
-        }
+        let cfrom: String =
+            match self.get_expr_string(&args[1], list_am_opt, elem_balance_result_opt) {
+                Err(e) => return Err(e),
+                Ok(o) => String::from(o.sym_string()),
+            };
 
-        let cfrom: String = match self.get_expr_string(list_am_opt, elem_balance_result_opt) {
+        let cto: String = match self.get_expr_string(&args[2], list_am_opt, elem_balance_result_opt)
+        {
             Err(e) => return Err(e),
             Ok(o) => String::from(o.sym_string()),
         };
 
-        let token = self.scanner_mut().scan_token();
-        if token == crate::TokenType::Punctuation && self.scanner().get_token().starts_with(',') {
-            self.scanner_mut().scan_token();
+        let count = text.matches(cfrom.as_str()).count();
+        let length = (text.chars().count() + count * cto.chars().count())
+            .saturating_sub(count * cfrom.chars().count());
+        if self.expr_limits.exceeds_string_length(length) {
+            return Err(ElemError::new(crate::ErrorType::Limit));
         }
 
-        let cto: String = match self.get_expr_string(list_am_opt, elem_balance_result_opt) {
-            Err(e) => return Err(e),
-            Ok(o) => String::from(o.sym_string()),
-        };
-
         let rs = text.replace(cfrom.as_str(), cto.as_str());
 
         let mut elem_result_symbol = ElemSymbol::new();
@@ -3273,6 +5340,7 @@ impl CalcExpression {
     ///
     /// # Arguments
     ///
+    /// * `args` - Function arguments.
     /// * `list_am_opt` - Amortization list.
     /// * `elem_balance_result_opt` - Balance results.
     ///
@@ -3282,61 +5350,33 @@ impl CalcExpression {
 
     pub fn function_round(
         &self,
+        args: &[ElemExprNode],
         list_am_opt: Option<&ListAmortization>,
         elem_balance_result_opt: Option<&ElemBalanceResult>,
-    ) -> Result<ElemSymbol, crate::ErrorType> {
+    ) -> Result<ElemSymbol, ElemError> {
         let mut elem_result_symbol: ElemSymbol =
-            match self.get_expr_symbol(list_am_opt, elem_balance_result_opt, false) {
-                Err(e) => return Err(e),
-                Ok(o) => o,
-            };
+            self.get_expr_symbol(&args[0], list_am_opt, elem_balance_result_opt, false)?;
 
         let dvalue: Decimal = match elem_result_symbol.sym_type() {
             crate::TokenType::Integer => {
                 dec!(elem_result_symbol.sym_integer())
             }
             crate::TokenType::Decimal => elem_result_symbol.sym_decimal(),
-            _ => return Err(crate::ErrorType::Decimal),
+            _ => return Err(ElemError::new(crate::ErrorType::Decimal)),
         };
 
         let mut digits: usize = self.decimal_digits;
         let mut round_ctrl = crate::RoundType::Bankers;
 
-        let token = self.scanner_mut().scan_token();
-        if token == crate::TokenType::Punctuation && self.scanner().get_token().starts_with(',') {
-            self.scanner_mut().scan_token();
-            let result = self.get_expr_integer(list_am_opt, elem_balance_result_opt);
-            match result {
-                Err(e) => {
-                    return Err(e);
-                }
-                Ok(o) => {
-                    elem_result_symbol = o;
-                }
+        if let Some(o) = args.get(1) {
+            match self.get_expr_integer(o, list_am_opt, elem_balance_result_opt) {
+                Err(e) => return Err(e),
+                Ok(o) => digits = o.sym_integer(),
             }
+        }
 
-            digits = elem_result_symbol.sym_integer();
-
-            let token = self.scanner_mut().scan_token();
-            if token == crate::TokenType::Punctuation && self.scanner().get_token().starts_with(',')
-            {
-                self.scanner_mut().scan_token();
-                round_ctrl = if self.scanner().get_token() == "bias-up" {
-                    crate::RoundType::BiasUp
-                } else if self.scanner().get_token() == "bias-down" {
-                    crate::RoundType::BiasDown
-                } else if self.scanner().get_token() == "up" {
-                    crate::RoundType::Up
-                } else if self.scanner().get_token() == "truncate" {
-                    crate::RoundType::Truncate
-                } else {
-                    crate::RoundType::Bankers
-                };
-            } else {
-                self.scanner_mut().rescan();
-            }
-        } else {
-            self.scanner_mut().rescan();
+        if let Some(o) = args.get(2) {
+            round_ctrl = CalcExpression::round_type(o);
         }
 
         elem_result_symbol.set_decimal(CoreUtility::round(dvalue, digits, round_ctrl));
@@ -3347,6 +5387,7 @@ impl CalcExpression {
     ///
     /// # Arguments
     ///
+    /// * `args` - Function arguments.
     /// * `list_am_opt` - Amortization list.
     /// * `elem_balance_result_opt` - Balance results.
     ///
@@ -3356,29 +5397,22 @@ impl CalcExpression {
 
     pub fn function_round_fraction(
         &self,
+        args: &[ElemExprNode],
         list_am_opt: Option<&ListAmortization>,
         elem_balance_result_opt: Option<&ElemBalanceResult>,
-    ) -> Result<ElemSymbol, crate::ErrorType> {
+    ) -> Result<ElemSymbol, ElemError> {
         let mut elem_result_symbol: ElemSymbol =
-            match self.get_expr_symbol(list_am_opt, elem_balance_result_opt, false) {
-                Err(e) => return Err(e),
-                Ok(o) => o,
-            };
+            self.get_expr_symbol(&args[0], list_am_opt, elem_balance_result_opt, false)?;
 
         let dvalue: Decimal = match elem_result_symbol.sym_type() {
             crate::TokenType::Integer => {
                 dec!(elem_result_symbol.sym_integer())
             }
             crate::TokenType::Decimal => elem_result_symbol.sym_decimal(),
-            _ => return Err(crate::ErrorType::Decimal),
+            _ => return Err(ElemError::new(crate::ErrorType::Decimal)),
         };
 
-        let token = self.scanner_mut().scan_token();
-        if token == crate::TokenType::Punctuation && self.scanner().get_token().starts_with(',') {
-            self.scanner_mut().scan_token();
-        }
-
-        let result = self.get_expr_symbol(list_am_opt, elem_balance_result_opt, false);
+        let result = self.get_expr_symbol(&args[1], list_am_opt, elem_balance_result_opt, false);
         match result {
             Err(e) => {
                 return Err(e);
@@ -3393,36 +5427,57 @@ impl CalcExpression {
                 dec!(elem_result_symbol.sym_integer())
             }
             crate::TokenType::Decimal => elem_result_symbol.sym_decimal(),
-            _ => return Err(crate::ErrorType::Decimal),
+            _ => return Err(ElemError::new(crate::ErrorType::Decimal)),
         };
 
         let mut round_ctrl = crate::RoundType::Bankers;
-        let token = self.scanner_mut().scan_token();
-        if token == crate::TokenType::Punctuation && self.scanner().get_token().starts_with(',') {
-            self.scanner_mut().scan_token();
-            round_ctrl = if self.scanner().get_token() == "bias-up" {
-                crate::RoundType::BiasUp
-            } else if self.scanner().get_token() == "bias-down" {
-                crate::RoundType::BiasDown
-            } else if self.scanner().get_token() == "up" {
-                crate::RoundType::Up
-            } else if self.scanner().get_token() == "truncate" {
-                crate::RoundType::Truncate
-            } else {
-                crate::RoundType::Bankers
-            };
-        } else {
-            self.scanner_mut().rescan();
+        if let Some(o) = args.get(2) {
+            round_ctrl = CalcExpression::round_type(o);
         }
 
         elem_result_symbol.set_decimal(CoreUtility::round_fraction(dvalue, dfraction, round_ctrl));
         Ok(elem_result_symbol)
     }
 
+    /// Returns the rounding type named by a rounding argument
+    /// (i.e., up, truncate, bias-up or bias-down).
+    ///
+    /// # Arguments
+    ///
+    /// * `elem_expr_node` - The rounding argument.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn round_type(elem_expr_node: &ElemExprNode) -> crate::RoundType {
+        let name: String = match elem_expr_node.node_type() {
+            crate::NodeType::Constant => String::from(elem_expr_node.value().sym_string()),
+            crate::NodeType::Symbol => String::from(elem_expr_node.name()),
+            crate::NodeType::Operator => {
+                let nodes = elem_expr_node.nodes();
+                if elem_expr_node.operator_type() != crate::OperatorType::Minus || nodes.len() != 2
+                {
+                    return crate::RoundType::Bankers;
+                }
+                format!("{}-{}", nodes[0].name(), nodes[1].name())
+            }
+            _ => return crate::RoundType::Bankers,
+        };
+
+        match name.as_str() {
+            "bias-up" => crate::RoundType::BiasUp,
+            "bias-down" => crate::RoundType::BiasDown,
+            "up" => crate::RoundType::Up,
+            "truncate" => crate::RoundType::Truncate,
+            _ => crate::RoundType::Bankers,
+        }
+    }
+
     /// Parses and executes the set function.
     ///
     /// # Arguments
     ///
+    /// * `args` - Function arguments.
     /// * `list_am_opt` - Amortization list.
     /// * `elem_balance_result_opt` - Balance results.
     ///
@@ -3432,47 +5487,24 @@ impl CalcExpression {
 
     pub fn function_set(
         &self,
+        args: &[ElemExprNode],
         list_am_opt: Option<&ListAmortization>,
         elem_balance_result_opt: Option<&ElemBalanceResult>,
-    ) -> Result<ElemSymbol, crate::ErrorType> {
-        if self.scanner().get_type() != crate::TokenType::Alpha {
-            return Err(crate::ErrorType::Alpha);
-        }
-        let mut text = String::from(self.scanner().get_token());
-
-        let token = self.scanner_mut().scan_token();
-        if !(token == crate::TokenType::Punctuation && self.scanner().get_token().starts_with('['))
-        {
-            self.scanner_mut().rescan();
-        } else {
-            self.scanner_mut().scan_token();
-            let result = self.get_expr_integer(list_am_opt, elem_balance_result_opt);
-            match result {
-                Err(e) => {
-                    return Err(e);
-                }
-                Ok(o) => {
-                    if !(self.scanner().get_type() == crate::TokenType::Punctuation
-                        && self.scanner().get_token().starts_with(']'))
-                    {
-                        return Err(crate::ErrorType::RightBracket);
-                    }
-                    text = format!("{}[{}]", text, o.sym_integer());
-                }
-            }
-        }
-        let token = self.scanner_mut().scan_token();
-        if token == crate::TokenType::Punctuation && self.scanner().get_token().starts_with(',') {
-            self.scanner_mut().scan_token();
+    ) -> Result<ElemSymbol, ElemError> {
+        if args[0].node_type() != crate::NodeType::Symbol {
+            return Err(ElemError::new(crate::ErrorType::Alpha));
         }
+        let text = self.symbol_name(&args[0], list_am_opt, elem_balance_result_opt)?;
 
         let elem_result_symbol: ElemSymbol =
-            match self.get_expr_symbol(list_am_opt, elem_balance_result_opt, true) {
-                Err(e) => return Err(e),
-                Ok(o) => o,
-            };
+            self.get_expr_symbol(&args[1], list_am_opt, elem_balance_result_opt, true)?;
 
-        let mut sym_table = self.symbol_table_mut();
+        let mut frames = self.frames_mut();
+        let mut symbol_table = self.symbol_table_mut();
+        let sym_table = match frames.last_mut() {
+            None => &mut *symbol_table,
+            Some(o) => &mut o.1,
+        };
         let mut elem_symbol_opt = sym_table.get_symbol_mut(text.as_str());
         let mut symbol_new = ElemSymbol::new();
         let mut new_symbol: bool = false;
@@ -3484,7 +5516,7 @@ impl CalcExpression {
 
         match elem_symbol_opt.as_mut() {
             None => {
-                return Err(crate::ErrorType::Incomplete);
+                return Err(ElemError::new(crate::ErrorType::Incomplete));
             }
             Some(o) => match elem_result_symbol.sym_type() {
                 crate::TokenType::Integer => {
@@ -3499,6 +5531,10 @@ impl CalcExpression {
                     o.set_string(elem_result_symbol.sym_string());
                     symbol_new.set_string(elem_result_symbol.sym_string());
                 }
+                crate::TokenType::List => {
+                    o.set_list(elem_result_symbol.sym_list());
+                    symbol_new.set_list(elem_result_symbol.sym_list());
+                }
                 _ => {}
             },
         }
@@ -3509,10 +5545,66 @@ impl CalcExpression {
         Ok(elem_result_symbol)
     }
 
+    /// Parses and executes the sum of list function.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - Function arguments.
+    /// * `list_am_opt` - Amortization list.
+    /// * `elem_balance_result_opt` - Balance results.
+    ///
+    /// # Return
+    ///
+    /// * Result symbol if successful, otherwise error code.
+    pub fn function_sum(
+        &self,
+        args: &[ElemExprNode],
+        list_am_opt: Option<&ListAmortization>,
+        elem_balance_result_opt: Option<&ElemBalanceResult>,
+    ) -> Result<ElemSymbol, ElemError> {
+        let elem_symbol = self.get_expr_list(&args[0], list_am_opt, elem_balance_result_opt)?;
+
+        let mut elem_result_symbol = ElemSymbol::new();
+        match CalcExpression::list_sum(elem_symbol.sym_list()) {
+            Err(e) => return Err(e),
+            Ok(o) => elem_result_symbol.set_decimal(o),
+        }
+
+        Ok(elem_result_symbol)
+    }
+
+    /// Returns the sum of the numeric elements of a list.
+    ///
+    /// # Arguments
+    ///
+    /// * `list_value` - List elements.
+    ///
+    /// # Return
+    ///
+    /// * Sum if successful, otherwise error code.
+    fn list_sum(list_value: &[ElemSymbol]) -> Result<Decimal, ElemError> {
+        let mut sum = dec!(0.0);
+
+        for elem in list_value.iter() {
+            let value = match elem.sym_type() {
+                crate::TokenType::Integer => Decimal::from(elem.sym_integeri()),
+                crate::TokenType::Decimal => elem.sym_decimal(),
+                _ => return Err(ElemError::new(crate::ErrorType::Decimal)),
+            };
+            sum = match sum.checked_add(value) {
+                None => return Err(ElemError::new(crate::ErrorType::Limit)),
+                Some(o) => o,
+            };
+        }
+
+        Ok(sum)
+    }
+
     /// Parses and executes the trim function.
     ///
     /// # Arguments
     ///
+    /// * `args` - Function arguments.
     /// * `list_am_opt` - Amortization list.
     /// * `elem_balance_result_opt` - Balance results.
     ///
@@ -3522,14 +5614,12 @@ impl CalcExpression {
 
     pub fn function_trim(
         &self,
+        args: &[ElemExprNode],
         list_am_opt: Option<&ListAmortization>,
         elem_balance_result_opt: Option<&ElemBalanceResult>,
-    ) -> Result<ElemSymbol, crate::ErrorType> {
+    ) -> Result<ElemSymbol, ElemError> {
         let mut elem_result_symbol: ElemSymbol =
-            match self.get_expr_string(list_am_opt, elem_balance_result_opt) {
-                Err(e) => return Err(e),
-                Ok(o) => o,
-            };
+            self.get_expr_string(&args[0], list_am_opt, elem_balance_result_opt)?;
 
         let s = String::from(elem_result_symbol.sym_string().trim());
         elem_result_symbol.set_string(s.as_str());
@@ -3540,6 +5630,7 @@ impl CalcExpression {
     ///
     /// # Arguments
     ///
+    /// * `args` - Function arguments.
     /// * `list_am_opt` - Amortization list.
     /// * `elem_balance_result_opt` - Balance results.
     ///
@@ -3549,22 +5640,18 @@ impl CalcExpression {
 
     pub fn function_type(
         &self,
+        args: &[ElemExprNode],
         list_am_opt: Option<&ListAmortization>,
         elem_balance_result_opt: Option<&ElemBalanceResult>,
-    ) -> Result<ElemSymbol, crate::ErrorType> {
+    ) -> Result<ElemSymbol, ElemError> {
         let mut elem_result_symbol: ElemSymbol =
-            match self.get_expr_symbol(list_am_opt, elem_balance_result_opt, true) {
-                Err(e) => return Err(e),
-                Ok(o) => o,
-            };
+            self.get_expr_symbol(&args[0], list_am_opt, elem_balance_result_opt, true)?;
 
         if elem_result_symbol.sym_type() != crate::TokenType::String {
-            return Err(crate::ErrorType::String);
+            return Err(ElemError::new(crate::ErrorType::String));
         }
 
-        let sym_table = self.symbol_table();
-        let elem_symbol = sym_table.get_symbol(elem_result_symbol.sym_string());
-        match elem_symbol {
+        match self.get_symbol(elem_result_symbol.sym_string()) {
             None => {
                 elem_result_symbol.set_integer(crate::TokenType::Unknown as usize);
             }
@@ -3579,6 +5666,7 @@ impl CalcExpression {
     ///
     /// # Arguments
     ///
+    /// * `args` - Function arguments.
     /// * `list_am_opt` - Amortization list.
     /// * `elem_balance_result_opt` - Balance results.
     ///
@@ -3588,19 +5676,71 @@ impl CalcExpression {
 
     pub fn function_uppercase(
         &self,
+        args: &[ElemExprNode],
         list_am_opt: Option<&ListAmortization>,
         elem_balance_result_opt: Option<&ElemBalanceResult>,
-    ) -> Result<ElemSymbol, crate::ErrorType> {
+    ) -> Result<ElemSymbol, ElemError> {
         let mut elem_result_symbol: ElemSymbol =
-            match self.get_expr_string(list_am_opt, elem_balance_result_opt) {
-                Err(e) => return Err(e),
-                Ok(o) => o,
-            };
+            self.get_expr_string(&args[0], list_am_opt, elem_balance_result_opt)?;
 
         elem_result_symbol.set_string(elem_result_symbol.sym_string().to_uppercase().as_str());
         Ok(elem_result_symbol)
     }
 
+    /// Parses and executes the dated net present value (XNPV) function.
+    /// The values and their dates are lists of equal length.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - Function arguments.
+    /// * `list_am_opt` - Amortization list.
+    /// * `elem_balance_result_opt` - Balance results.
+    ///
+    /// # Return
+    ///
+    /// * Result symbol if successful, otherwise error code.
+    pub fn function_xnpv(
+        &self,
+        args: &[ElemExprNode],
+        list_am_opt: Option<&ListAmortization>,
+        elem_balance_result_opt: Option<&ElemBalanceResult>,
+    ) -> Result<ElemSymbol, ElemError> {
+        let rate = self.get_expr_number(&args[0], list_am_opt, elem_balance_result_opt)?;
+        let values = self.get_expr_list(&args[1], list_am_opt, elem_balance_result_opt)?;
+        let dates = self.get_expr_list(&args[2], list_am_opt, elem_balance_result_opt)?;
+
+        if values.sym_list().len() != dates.sym_list().len() {
+            return Err(ElemError::new(crate::ErrorType::List));
+        }
+
+        let mut amounts: Vec<ElemDatedAmount> = Vec::new();
+        for (value, date) in values.sym_list().iter().zip(dates.sym_list().iter()) {
+            let amount = match value.sym_type() {
+                crate::TokenType::Integer => Decimal::from(value.sym_integeri()),
+                crate::TokenType::Decimal => value.sym_decimal(),
+                _ => return Err(ElemError::new(crate::ErrorType::Decimal)),
+            };
+            if date.sym_type() != crate::TokenType::Integer {
+                return Err(ElemError::new(crate::ErrorType::Integer));
+            }
+            let date = match CoreUtility::integer_to_date(date.sym_integer()) {
+                None => return Err(ElemError::new(crate::ErrorType::Date)),
+                Some(o) => o,
+            };
+            amounts.push(ElemDatedAmount::new(date, amount));
+        }
+
+        let mut elem_result_symbol = ElemSymbol::new();
+        match CoreFinancial::xnpv(rate, &amounts) {
+            Err(e) => return Err(e),
+            Ok(o) => {
+                elem_result_symbol.set_decimal(CoreUtility::util_round(o, self.decimal_digits))
+            }
+        }
+
+        Ok(elem_result_symbol)
+    }
+
     /// Normalize the currently initialized expression.
     ///
     /// # Arguments
@@ -3668,7 +5808,8 @@ impl CalcExpression {
                                 .calc_mgr()
                                 .core_manager()
                                 .operators()
-                                .get_element_by_key(text.as_str())
+                                .get_key(text.as_str())
+                                .is_some()
                             {
                                 if !buf.is_empty() {
                                     buf.push(' ');
@@ -3693,7 +5834,8 @@ impl CalcExpression {
                             .calc_mgr()
                             .core_manager()
                             .operators()
-                            .get_element_by_key(text.as_str())
+                            .get_key(text.as_str())
+                            .is_some()
                         {
                             if !buf.is_empty() {
                                 buf.push(' ');
@@ -3725,4 +5867,265 @@ impl CalcExpression {
         }
         buf
     }
+
+    /// Format the currently initialized expression. The expression
+    /// is reprinted from its compiled expression tree with canonical
+    /// spacing, lowercase built-in function names and only the
+    /// parentheses required by operator precedence. Expressions of a
+    /// sequence are placed on separate lines and the arguments of an
+    /// if function that contains another if function are indented on
+    /// separate lines. The formatted expression is compiled and
+    /// compared with the original expression tree to guarantee that
+    /// both are equivalent.
+    ///
+    /// # Return
+    ///
+    /// * The formatted expression string if successful, otherwise error code.
+    pub fn format_expression(&self) -> Result<String, ElemError> {
+        let elem_expr_node = self.compile()?;
+
+        let text = self.format_node(&elem_expr_node, 0);
+
+        let elem_expr_node2 = self.compile_text(text.as_str())?;
+
+        if !elem_expr_node.equal(&elem_expr_node2) {
+            let mut e = ElemError::new(crate::ErrorType::Format);
+            e.set_expression(text.as_str(), 0);
+            return Err(e);
+        }
+
+        Ok(text)
+    }
+
+    /// Format a node of a compiled expression.
+    ///
+    /// # Arguments
+    ///
+    /// * `elem_expr_node` - The node.
+    /// * `level` - Indentation level of the line containing the node.
+    ///
+    /// # Return
+    ///
+    /// * The formatted node string.
+    fn format_node(&self, elem_expr_node: &ElemExprNode, level: usize) -> String {
+        match elem_expr_node.node_type() {
+            crate::NodeType::Constant => self.trace_text(elem_expr_node),
+            crate::NodeType::Symbol => match elem_expr_node.nodes().first() {
+                None => String::from(elem_expr_node.name()),
+                Some(o) => format!("{}[{}]", elem_expr_node.name(), self.format_node(o, level)),
+            },
+            crate::NodeType::Operator => {
+                let (text, precedence) = self.operator_text(elem_expr_node.operator_type());
+                let nodes = elem_expr_node.nodes();
+
+                if nodes.len() == 1 {
+                    let operand = self.format_operand(&nodes[0], level, usize::MAX);
+                    return if elem_expr_node.operator_type() == crate::OperatorType::UnaryNot {
+                        format!("{} {}", text, operand)
+                    } else {
+                        format!("{}{}", text, operand)
+                    };
+                }
+
+                // Binary operators are left associative
+                format!(
+                    "{} {} {}",
+                    self.format_operand(&nodes[0], level, precedence),
+                    text,
+                    self.format_operand(&nodes[1], level, precedence + 1)
+                )
+            }
+            crate::NodeType::Function => {
+                let args = elem_expr_node.nodes();
+
+                if elem_expr_node.function_type() == crate::FunctionType::If
+                    && args.iter().any(CalcExpression::contains_if)
+                {
+                    let indent = "    ".repeat(level + 1);
+                    let args: Vec<String> = args
+                        .iter()
+                        .map(|o| self.format_node(o, level + 1))
+                        .collect();
+                    return format!(
+                        "{}({})",
+                        elem_expr_node.name(),
+                        args.join(format!(",\n{}", indent).as_str())
+                    );
+                }
+
+                let args: Vec<String> = args.iter().map(|o| self.format_node(o, level)).collect();
+                format!("{}({})", elem_expr_node.name(), args.join(", "))
+            }
+            crate::NodeType::Sequence => {
+                let nodes: Vec<String> = elem_expr_node
+                    .nodes()
+                    .iter()
+                    .map(|o| self.format_node(o, level))
+                    .collect();
+                nodes.join(",\n")
+            }
+        }
+    }
+
+    /// Format an operand of an operator node, enclosing the operand in
+    /// parentheses if it is an operator with a lower precedence.
+    ///
+    /// # Arguments
+    ///
+    /// * `elem_expr_node` - The operand node.
+    /// * `level` - Indentation level of the line containing the node.
+    /// * `precedence` - Minimum precedence of the operand without parentheses
+    ///   (usize::MAX if the operand must be enclosed if it is an operator).
+    ///
+    /// # Return
+    ///
+    /// * The formatted operand string.
+    fn format_operand(
+        &self,
+        elem_expr_node: &ElemExprNode,
+        level: usize,
+        precedence: usize,
+    ) -> String {
+        let text = self.format_node(elem_expr_node, level);
+
+        if elem_expr_node.node_type() != crate::NodeType::Operator {
+            return text;
+        }
+
+        let (_, operand_precedence) = self.operator_text(elem_expr_node.operator_type());
+        if elem_expr_node.nodes().len() == 1 && precedence != usize::MAX {
+            return text; // Unary operators have the highest precedence
+        }
+
+        if operand_precedence < precedence {
+            format!("({})", text)
+        } else {
+            text
+        }
+    }
+
+    /// Tests if a node is or contains an if function.
+    ///
+    /// # Arguments
+    ///
+    /// * `elem_expr_node` - The node.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn contains_if(elem_expr_node: &ElemExprNode) -> bool {
+        (elem_expr_node.node_type() == crate::NodeType::Function
+            && elem_expr_node.function_type() == crate::FunctionType::If)
+            || elem_expr_node
+                .nodes()
+                .iter()
+                .any(CalcExpression::contains_if)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::CalcEngine;
+
+    /// Evaluate an expression with the engine.
+    fn evaluate(calc_engine: &CalcEngine, expression: &str) -> Result<ElemSymbol, ElemError> {
+        let calc_mgr = calc_engine.calc_mgr();
+        let mut calc_expression = CalcExpression::new(&calc_mgr, 101, 2);
+        calc_expression.init_expression(None, None, None, expression);
+        calc_expression.evaluate(None, None)
+    }
+
+    #[test]
+    fn negative_integer_arithmetic() {
+        let calc_engine = CalcEngine::new();
+
+        let result = evaluate(&calc_engine, "-3 + 5").unwrap();
+        assert_eq!(result.sym_type(), crate::TokenType::Integer);
+        assert_eq!(result.sym_integeri(), 2);
+
+        let result = evaluate(&calc_engine, "1 - -1").unwrap();
+        assert_eq!(result.sym_integeri(), 2);
+
+        let result = evaluate(&calc_engine, "-(2 + 3)").unwrap();
+        assert_eq!(result.sym_integeri(), -5);
+
+        let result = evaluate(&calc_engine, "2 - 5").unwrap();
+        assert_eq!(result.sym_integeri(), -3);
+    }
+
+    #[test]
+    fn negative_integer_comparison() {
+        let calc_engine = CalcEngine::new();
+
+        assert_eq!(evaluate(&calc_engine, "-1 < 1").unwrap().sym_integeri(), 1);
+        assert_eq!(evaluate(&calc_engine, "-1 > 1").unwrap().sym_integeri(), 0);
+        assert_eq!(
+            evaluate(&calc_engine, "-1 + 0.5").unwrap().sym_decimal(),
+            dec!(-0.5)
+        );
+    }
+
+    #[test]
+    fn integer_overflow_is_error() {
+        let calc_engine = CalcEngine::new();
+
+        for expression in [
+            "2147483647 + 1",
+            "-2147483647 - 2",
+            "65536 * 65536",
+            "2 exp 31",
+        ] {
+            match evaluate(&calc_engine, expression) {
+                Err(e) => assert_eq!(e.kind(), crate::ErrorType::Limit),
+                Ok(o) => panic!("{} = {}", expression, o.sym_integeri()),
+            }
+        }
+    }
+
+    #[test]
+    fn decimal_overflow_is_error() {
+        let calc_engine = CalcEngine::new();
+
+        for expression in [
+            "79228162514264337593543950335.0 + 1.0",
+            "-79228162514264337593543950335.0 - 1.0",
+            "79228162514264337593543950335.0 * 2.0",
+            "10.0 exp 29",
+            "sum(list(79228162514264337593543950335.0, 1.0))",
+        ] {
+            match evaluate(&calc_engine, expression) {
+                Err(e) => assert_eq!(e.kind(), crate::ErrorType::Limit),
+                Ok(o) => panic!("{} = {}", expression, o.sym_decimal()),
+            }
+        }
+
+        let result = evaluate(&calc_engine, "1.5 exp 2").unwrap();
+        assert_eq!(result.sym_decimal(), dec!(2.25));
+    }
+
+    #[test]
+    fn limits_are_errors() {
+        let calc_engine = CalcEngine::new();
+        let mut expr_limits = ElemExprLimits::new();
+        expr_limits.set_max_depth(Option::from(8));
+        expr_limits.set_max_magnitude(Option::from(dec!(1000.0)));
+        calc_engine.set_expr_limits(expr_limits);
+
+        let expression = format!("{}1{}", "(1 + ".repeat(20), ")".repeat(20));
+        match evaluate(&calc_engine, expression.as_str()) {
+            Err(e) => assert_eq!(e.kind(), crate::ErrorType::Limit),
+            Ok(o) => panic!("{} = {}", expression, o.sym_integeri()),
+        }
+
+        match evaluate(&calc_engine, "999 + 2") {
+            Err(e) => assert_eq!(e.kind(), crate::ErrorType::Limit),
+            Ok(o) => panic!("999 + 2 = {}", o.sym_integeri()),
+        }
+
+        assert_eq!(
+            evaluate(&calc_engine, "0 - 1 + 1").unwrap().sym_integeri(),
+            0
+        );
+    }
 }
@@ -7,26 +7,28 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-use std::cell::{Ref, RefCell, RefMut};
 use std::collections::HashMap;
-use std::rc::Rc;
+use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
 
+use chrono::NaiveDate;
 use json::JsonValue;
 use regex::Regex;
 use rust_decimal::prelude::*;
 
 use super::{
-    CalcManager, ElemLocaleFormat, ElemPreferences, ListCashflow, ListExchangeRate, ListLocale,
-    ListTemplateEvent, ListTemplateGroup,
+    CalcJsonValidate, CalcManager, ElemLocaleFormat, ElemPreferences, ElemSchemaViolation,
+    ListCalendar, ListCashflow, ListExchangeRate, ListLocale, ListRateIndex, ListTemplateEvent,
+    ListTemplateGroup,
 };
 use crate::core::{
-    CoreUtility, ElemCurrentValue, ElemExtension, ElemInterestChange, ElemPrincipalChange,
-    ElemStatisticValue, ListDescriptor, ListEvent, ListParameter,
+    CoreUtility, ElemCalendar, ElemCurrentValue, ElemError, ElemExtension, ElemHoliday,
+    ElemInterestChange, ElemPrincipalChange, ElemRateIndex, ElemStatisticValue, ListDescriptor,
+    ListEvent, ListFunction, ListParameter,
 };
 
 pub struct CalcJsonDeserialize {
     /// Calculator manager element.
-    calc_manager: Rc<RefCell<CalcManager>>,
+    calc_manager: Arc<RwLock<CalcManager>>,
 }
 
 /// The deserialize json implementation of the AmFn engine.
@@ -42,9 +44,9 @@ impl CalcJsonDeserialize {
     ///
     /// * See description.
 
-    pub fn new(calc_manager_param: &Rc<RefCell<CalcManager>>) -> CalcJsonDeserialize {
+    pub fn new(calc_manager_param: &Arc<RwLock<CalcManager>>) -> CalcJsonDeserialize {
         CalcJsonDeserialize {
-            calc_manager: Rc::clone(calc_manager_param),
+            calc_manager: Arc::clone(calc_manager_param),
         }
     }
 
@@ -54,8 +56,8 @@ impl CalcJsonDeserialize {
     ///
     /// * See description.
 
-    fn calc_mgr(&self) -> Ref<CalcManager> {
-        self.calc_manager.borrow()
+    fn calc_mgr(&self) -> RwLockReadGuard<'_, CalcManager> {
+        self.calc_manager.read().unwrap()
     }
 
     /// Returns the mutable calculation manager.
@@ -64,11 +66,34 @@ impl CalcJsonDeserialize {
     ///
     /// * See description.
 
-    fn calc_mgr_mut(&self) -> RefMut<CalcManager> {
-        self.calc_manager.borrow_mut()
+    fn calc_mgr_mut(&self) -> RwLockWriteGuard<'_, CalcManager> {
+        self.calc_manager.write().unwrap()
     }
 
-    /// Deserialize and ingest serialized Json.
+    /// Validate serialized Json against amfn.schema.json without
+    /// ingesting it.
+    ///
+    /// # Arguments
+    ///
+    /// * `input_param` - Input containing serialized Json.
+    ///
+    /// # Return
+    ///
+    /// * ERROR_NONE if valid, otherwise every schema violation.
+    pub fn validate_only(&self, input_param: &str) -> Result<(), Vec<ElemSchemaViolation>> {
+        let violations = CalcJsonValidate::shared().validate(input_param);
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations)
+        }
+    }
+
+    /// Deserialize and ingest serialized Json. The input is first
+    /// validated against amfn.schema.json; the error of an invalid
+    /// input holds the pointer and message of the first violation
+    /// (use validate_only to obtain every violation).
     ///
     /// # Arguments
     ///
@@ -78,26 +103,35 @@ impl CalcJsonDeserialize {
     ///
     /// * ERROR_NONE if successful, otherwise error code.
 
-    pub fn deserialize(&self, input_param: String) -> Result<(), crate::ErrorType> {
+    pub fn deserialize(&self, input_param: String) -> Result<(), ElemError> {
         let data: JsonValue = match json::parse(input_param.as_str()) {
             Err(e) => {
                 println!("Json error: {:?}", e);
-                return Err(crate::ErrorType::Json);
+                return Err(ElemError::new(crate::ErrorType::Json));
             }
             Ok(o) => o,
         };
 
-        self.calc_mgr().set_updating_json(true);
+        if let Some(o) = CalcJsonValidate::shared().validate_value(&data).first() {
+            let mut error = ElemError::new(crate::ErrorType::Json);
+            error.set_pointer(o.pointer());
+            error.set_message(o.message());
+            return Err(error);
+        }
+
+        let mut calc_mgr = self.calc_mgr_mut();
+
+        calc_mgr.set_updating_json(true);
 
         if !data["preferences"].is_null() {
             let result = self.deserialize_preferences(&data["preferences"]);
             match result {
                 Err(e) => {
-                    self.calc_mgr().set_updating_json(false);
+                    calc_mgr.set_updating_json(false);
                     return Err(e);
                 }
                 Ok(o) => {
-                    self.calc_mgr_mut().set_preferences(o);
+                    calc_mgr.set_preferences(o);
                 }
             }
         }
@@ -106,11 +140,11 @@ impl CalcJsonDeserialize {
             let result = self.deserialize_locales(&data["locales"]);
             match result {
                 Err(e) => {
-                    self.calc_mgr().set_updating_json(false);
+                    calc_mgr.set_updating_json(false);
                     return Err(e);
                 }
                 Ok(o) => {
-                    self.calc_mgr_mut().list_locale_mut().append_locales(o);
+                    calc_mgr.list_locale_mut().append_locales(o);
                 }
             }
         }
@@ -119,52 +153,286 @@ impl CalcJsonDeserialize {
             let result = self.deserialize_exchange_rates(&data["exchange-rates"]);
             match result {
                 Err(e) => {
-                    self.calc_mgr().set_updating_json(false);
+                    calc_mgr.set_updating_json(false);
+                    return Err(e);
+                }
+                Ok(o) => {
+                    calc_mgr.set_list_exchange_rate(o);
+                }
+            }
+        }
+
+        if !data["calendars"].is_null() {
+            let result = self.deserialize_calendars(&calc_mgr, &data["calendars"]);
+            match result {
+                Err(e) => {
+                    calc_mgr.set_updating_json(false);
                     return Err(e);
                 }
                 Ok(o) => {
-                    self.calc_mgr_mut().set_list_exchange_rate(o);
+                    calc_mgr.list_calendar_mut().append_calendars(o);
+                }
+            }
+        }
+
+        if !data["rate-indexes"].is_null() {
+            let result = self.deserialize_rate_indexes(&data["rate-indexes"]);
+            match result {
+                Err(e) => {
+                    calc_mgr.set_updating_json(false);
+                    return Err(e);
+                }
+                Ok(o) => {
+                    calc_mgr.list_rate_index_mut().append_rate_indexes(o);
                 }
             }
         }
 
         if !data["template-groups"].is_null() {
-            let result = self.deserialize_template_groups(&data["template-groups"]);
+            let result = self.deserialize_template_groups(&calc_mgr, &data["template-groups"]);
             match result {
                 Err(e) => {
-                    self.calc_mgr().set_updating_json(false);
+                    calc_mgr.set_updating_json(false);
                     return Err(e);
                 }
                 Ok(o) => {
-                    self.calc_mgr_mut()
-                        .list_template_group_mut()
-                        .append_template_groups(o);
+                    calc_mgr.list_template_group_mut().append_template_groups(o);
                 }
             }
         }
 
         if !data["cashflows"].is_null() {
-            let result = self.deserialize_cashflows(&data["cashflows"]);
+            let result = self.deserialize_cashflows(&calc_mgr, &data["cashflows"]);
             match result {
                 Err(e) => {
-                    self.calc_mgr().set_updating_json(false);
+                    calc_mgr.set_updating_json(false);
                     return Err(e);
                 }
                 Ok(o) => {
-                    self.calc_mgr_mut().list_cashflow_mut().append_cashflows(o);
+                    calc_mgr.list_cashflow_mut().append_cashflows(o);
                 }
             }
         }
 
-        self.calc_mgr().set_updating_json(false);
+        calc_mgr.set_updating_json(false);
 
         Ok(())
     }
 
+    /// Deserialize and ingest Json calendars.
+    ///
+    /// # Arguments
+    ///
+    /// * `calc_mgr` - Calculation manager.
+    /// * `cals` - Json value for calendars.
+    ///
+    /// # Return
+    ///
+    /// * ERROR_NONE if successful, otherwise error code.
+    fn deserialize_calendars(
+        &self,
+        calc_mgr: &CalcManager,
+        cals: &JsonValue,
+    ) -> Result<ListCalendar, ElemError> {
+        let mut calendars = ListCalendar::new();
+        let mut index: usize = 0;
+
+        loop {
+            let cal = &cals[index];
+            if cal.is_null() {
+                break;
+            }
+
+            let calendar = match self.deserialize_calendar(calc_mgr, cal, &calendars) {
+                Err(mut e) => {
+                    e.set_pointer(format!("/calendars/{}", index).as_str());
+                    return Err(e);
+                }
+                Ok(o) => o,
+            };
+
+            calendars.add_calendar(calendar);
+
+            index += 1;
+        }
+
+        Ok(calendars)
+    }
+
+    /// Deserialize a Json calendar. Joint calendars are
+    /// resolved from the calendars deserialized so far and
+    /// then from the calendars already loaded.
+    ///
+    /// # Arguments
+    ///
+    /// * `calc_mgr` - Calculation manager.
+    /// * `cal` - Json value for the calendar.
+    /// * `calendars` - Calendars deserialized so far.
+    ///
+    /// # Return
+    ///
+    /// * The calendar if successful, otherwise error code.
+    fn deserialize_calendar(
+        &self,
+        calc_mgr: &CalcManager,
+        cal: &JsonValue,
+        calendars: &ListCalendar,
+    ) -> Result<ElemCalendar, ElemError> {
+        let name: &str = match cal["name"].as_str() {
+            None => return Err(ElemError::new(crate::ErrorType::Json)),
+            Some(o) => o,
+        };
+
+        let mut calendar: ElemCalendar;
+        if !cal["joint"].is_null() {
+            let mut joint_names: Vec<&str> = Vec::new();
+            for member in cal["joint"].members() {
+                match member.as_str() {
+                    None => return Err(ElemError::new(crate::ErrorType::Json)),
+                    Some(o) => joint_names.push(o),
+                }
+            }
+
+            let joint_name = joint_names.join("+");
+            calendar = match calendars.resolve(joint_name.as_str()) {
+                None => match calc_mgr.list_calendar().resolve(joint_name.as_str()) {
+                    None => return Err(ElemError::new(crate::ErrorType::Calendar)),
+                    Some(o) => o,
+                },
+                Some(o) => o,
+            };
+            calendar.set_name(name);
+        } else if !cal["file"].is_null() {
+            calendar = match cal["file"].as_str() {
+                None => return Err(ElemError::new(crate::ErrorType::Json)),
+                Some(o) => self.deserialize_calendar_file_ext(calc_mgr, name, o)?,
+            };
+        } else {
+            calendar = ElemCalendar::new(name);
+        }
+
+        if !cal["weekend"].is_null() {
+            let mut weekend_days: Vec<usize> = Vec::new();
+            for member in cal["weekend"].members() {
+                match member.as_usize() {
+                    None => return Err(ElemError::new(crate::ErrorType::Json)),
+                    Some(o) => weekend_days.push(o),
+                }
+            }
+            calendar.set_weekend_days(weekend_days.as_slice());
+        }
+
+        for hol in cal["holidays"].members() {
+            let holiday_name = hol["name"].as_str().unwrap_or("");
+            let month = hol["month"].as_usize().unwrap_or(0);
+            let weekday = hol["weekday"].as_usize().unwrap_or(0);
+
+            let holiday = match hol["type"].as_str().unwrap_or("fixed") {
+                "nth-weekday" => ElemHoliday::new_nth_weekday(
+                    holiday_name,
+                    month,
+                    hol["week"].as_usize().unwrap_or(1),
+                    weekday,
+                ),
+                "last-weekday" => ElemHoliday::new_last_weekday(holiday_name, month, weekday),
+                "easter" => {
+                    ElemHoliday::new_easter(holiday_name, hol["offset"].as_i32().unwrap_or(0))
+                }
+                "date" => match hol["date"].as_str() {
+                    None => return Err(ElemError::new(crate::ErrorType::Json)),
+                    Some(o) => match self.get_date(o) {
+                        Err(e) => return Err(e),
+                        Ok(o) => ElemHoliday::new_date(holiday_name, o),
+                    },
+                },
+                _ => ElemHoliday::new_fixed(
+                    holiday_name,
+                    month,
+                    hol["day"].as_usize().unwrap_or(0),
+                    hol["observed"].as_bool().unwrap_or(false),
+                ),
+            };
+
+            calendar.add_holiday(holiday);
+        }
+
+        Ok(calendar)
+    }
+
+    /// Deserialize a calendar from a local iCalendar (.ics) or Json file.
+    /// A Json file contains either a single calendar or an object
+    /// with a "calendars" array (the first calendar is used).
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the calendar.
+    /// * `path` - Path of the file.
+    ///
+    /// # Return
+    ///
+    /// * The calendar if successful, otherwise error code.
+    pub fn deserialize_calendar_file(
+        &self,
+        name: &str,
+        path: &str,
+    ) -> Result<ElemCalendar, ElemError> {
+        self.deserialize_calendar_file_ext(&self.calc_mgr(), name, path)
+    }
+
+    /// Deserialize a calendar from a local iCalendar (.ics) or Json file.
+    /// A Json file contains either a single calendar or an object
+    /// with a "calendars" array (the first calendar is used).
+    ///
+    /// # Arguments
+    ///
+    /// * `calc_mgr` - Calculation manager.
+    /// * `name` - Name of the calendar.
+    /// * `path` - Path of the file.
+    ///
+    /// # Return
+    ///
+    /// * The calendar if successful, otherwise error code.
+    fn deserialize_calendar_file_ext(
+        &self,
+        calc_mgr: &CalcManager,
+        name: &str,
+        path: &str,
+    ) -> Result<ElemCalendar, ElemError> {
+        let text = match std::fs::read_to_string(path) {
+            Err(_e) => return Err(ElemError::new(crate::ErrorType::Calendar)),
+            Ok(o) => o,
+        };
+
+        if path.to_lowercase().ends_with(".ics") || text.trim_start().starts_with("BEGIN:VCALENDAR")
+        {
+            return ElemCalendar::new_from_ics(name, text.as_str());
+        }
+
+        let data: JsonValue = match json::parse(text.as_str()) {
+            Err(_e) => return Err(ElemError::new(crate::ErrorType::Json)),
+            Ok(o) => o,
+        };
+
+        let mut cal = if data["calendars"].is_null() {
+            data
+        } else {
+            data["calendars"][0].clone()
+        };
+
+        if cal.is_null() || !cal["file"].is_null() {
+            return Err(ElemError::new(crate::ErrorType::Calendar));
+        }
+
+        cal["name"] = JsonValue::from(name);
+
+        self.deserialize_calendar(calc_mgr, &cal, &ListCalendar::new())
+    }
+
     /// Deserialize and ingest Json cashflows.
     ///
     /// # Arguments
     ///
+    /// * `calc_mgr` - Calculation manager.
     /// * `cfs` - Json value for cashflows.
     /// * `cashflows` - List of cashflows.
     ///
@@ -172,8 +440,12 @@ impl CalcJsonDeserialize {
     ///
     /// * ERROR_NONE if successful, otherwise error code.
 
-    fn deserialize_cashflows(&self, cfs: &JsonValue) -> Result<ListCashflow, crate::ErrorType> {
-        let mut cashflows = ListCashflow::new(&self.calc_manager);
+    fn deserialize_cashflows(
+        &self,
+        calc_mgr: &CalcManager,
+        cfs: &JsonValue,
+    ) -> Result<ListCashflow, ElemError> {
+        let mut cashflows = ListCashflow::new();
         let mut index: usize = 0;
 
         loop {
@@ -183,15 +455,20 @@ impl CalcJsonDeserialize {
             }
 
             let name: &str = match cf["name"].as_str() {
-                None => return Err(crate::ErrorType::Json),
+                None => return Err(ElemError::new(crate::ErrorType::Json)),
                 Some(o) => o,
             };
 
-            let group = String::from(self.calc_mgr().preferences().group());
-            let preferences = self.calc_mgr().preferences().copy(true);
+            let group = String::from(calc_mgr.preferences().group());
+            let preferences = calc_mgr.preferences().copy(true);
 
-            match cashflows.add_cashflow_prep(name, None, Option::from(preferences), group.as_str())
-            {
+            match cashflows.add_cashflow_prep(
+                calc_mgr,
+                name,
+                None,
+                Option::from(preferences),
+                group.as_str(),
+            ) {
                 Err(_e) => {
                     panic!("Add cashflow failed");
                 }
@@ -207,7 +484,8 @@ impl CalcJsonDeserialize {
                     Some(o) => {
                         let result = self.deserialize_preferences_with_prefs(&cf["preferences"], o);
                         match result {
-                            Err(e) => {
+                            Err(mut e) => {
+                                e.set_cashflow_name(name);
                                 return Err(e);
                             }
                             Ok(_o) => {}
@@ -222,9 +500,10 @@ impl CalcJsonDeserialize {
                 match cashflows.list_event_mut() {
                     None => {}
                     Some(o) => {
-                        let result = self.deserialize_event_list(&cf["event-list"], o);
+                        let result = self.deserialize_event_list(calc_mgr, &cf["event-list"], o);
                         match result {
-                            Err(e) => {
+                            Err(mut e) => {
+                                e.set_cashflow_name(name);
                                 return Err(e);
                             }
                             Ok(_o) => {}
@@ -253,7 +532,7 @@ impl CalcJsonDeserialize {
         &self,
         cv: &JsonValue,
         current_value: &mut ElemCurrentValue,
-    ) -> Result<(), crate::ErrorType> {
+    ) -> Result<(), ElemError> {
         match cv["eom"].as_bool() {
             None => {}
             Some(o) => {
@@ -275,6 +554,20 @@ impl CalcJsonDeserialize {
             }
         }
 
+        match cv["business-day"].as_str() {
+            None => {}
+            Some(o) => {
+                current_value.set_business_day(CoreUtility::get_business_day(o));
+            }
+        }
+
+        match cv["calendar"].as_str() {
+            None => {}
+            Some(o) => {
+                current_value.set_calendar(o);
+            }
+        }
+
         Ok(())
     }
 
@@ -293,7 +586,7 @@ impl CalcJsonDeserialize {
         &self,
         descs: &JsonValue,
         descriptors: &mut ListDescriptor,
-    ) -> Result<(), crate::ErrorType> {
+    ) -> Result<(), ElemError> {
         let mut index: usize = 0;
 
         descriptors.set_sort_on_add(false);
@@ -307,27 +600,27 @@ impl CalcJsonDeserialize {
             let propagate: bool = desc["propagate"].as_bool().unwrap_or(false);
 
             let descriptor_code: &str = match desc["descriptor-code"].as_str() {
-                None => return Err(crate::ErrorType::Json),
+                None => return Err(ElemError::new(crate::ErrorType::Json)),
                 Some(o) => o,
             };
 
             let descriptor_type: &str = match desc["descriptor-type"].as_str() {
-                None => return Err(crate::ErrorType::Json),
+                None => return Err(ElemError::new(crate::ErrorType::Json)),
                 Some(o) => o,
             };
 
             let group: &str = match desc["group"].as_str() {
-                None => return Err(crate::ErrorType::Json),
+                None => return Err(ElemError::new(crate::ErrorType::Json)),
                 Some(o) => o,
             };
 
             let name: &str = match desc["name"].as_str() {
-                None => return Err(crate::ErrorType::Json),
+                None => return Err(ElemError::new(crate::ErrorType::Json)),
                 Some(o) => o,
             };
 
             let value: &str = match desc["value"].as_str() {
-                None => return Err(crate::ErrorType::Json),
+                None => return Err(ElemError::new(crate::ErrorType::Json)),
                 Some(o) => o,
             };
 
@@ -335,7 +628,7 @@ impl CalcJsonDeserialize {
             if !desc["expression"].is_null() {
                 match desc["expression"].as_str() {
                     None => {
-                        return Err(crate::ErrorType::Json);
+                        return Err(ElemError::new(crate::ErrorType::Json));
                     }
                     Some(o) => {
                         expr = o;
@@ -372,6 +665,7 @@ impl CalcJsonDeserialize {
     ///
     /// # Arguments
     ///
+    /// * `calc_mgr` - Calculation manager.
     /// * `evs` - Json value for events.
     /// * `events` - List of events.
     ///
@@ -381,9 +675,10 @@ impl CalcJsonDeserialize {
 
     fn deserialize_event_list(
         &self,
+        calc_mgr: &CalcManager,
         evs: &JsonValue,
         events: &mut ListEvent,
-    ) -> Result<(), crate::ErrorType> {
+    ) -> Result<(), ElemError> {
         let mut index: usize = 0;
 
         events.set_sort_on_add(false);
@@ -394,174 +689,191 @@ impl CalcJsonDeserialize {
                 break;
             }
 
-            let event_date: usize = match ev["event-date"]["date"].as_str() {
-                None => return Err(crate::ErrorType::Json),
-                Some(o) => self.get_date(o),
-            };
-
-            let mut event_date_expr = "";
-            if !ev["event-date"]["expression"].is_null() {
-                match ev["event-date"]["expression"].as_str() {
-                    None => {
-                        return Err(crate::ErrorType::Json);
-                    }
-                    Some(o) => {
-                        event_date_expr = o;
-                    }
-                }
+            if let Err(mut e) = self.deserialize_event(calc_mgr, ev, events) {
+                e.set_event_index(index);
+                return Err(e);
             }
 
-            let event_value: Decimal;
-            match ev["event-value"]["value"].as_str() {
+            index += 1;
+        }
+
+        events.set_sort_on_add(true); // Sorts list
+        Ok(())
+    }
+
+    /// Deserialize and ingest a Json event.
+    ///
+    /// # Arguments
+    ///
+    /// * `calc_mgr` - Calculation manager.
+    /// * `ev` - Json value for the event.
+    /// * `events` - List of events.
+    ///
+    /// # Return
+    ///
+    /// * ERROR_NONE if successful, otherwise error code.
+    fn deserialize_event(
+        &self,
+        calc_mgr: &CalcManager,
+        ev: &JsonValue,
+        events: &mut ListEvent,
+    ) -> Result<(), ElemError> {
+        let event_date: NaiveDate = match ev["event-date"]["date"].as_str() {
+            None => return Err(ElemError::new(crate::ErrorType::Json)),
+            Some(o) => self.get_date(o)?,
+        };
+
+        let mut event_date_expr = "";
+        if !ev["event-date"]["expression"].is_null() {
+            match ev["event-date"]["expression"].as_str() {
                 None => {
-                    return Err(crate::ErrorType::Json);
+                    return Err(ElemError::new(crate::ErrorType::Json));
+                }
+                Some(o) => {
+                    event_date_expr = o;
                 }
-                Some(o) => match o.parse::<Decimal>() {
-                    Err(_e) => {
-                        return Err(crate::ErrorType::Json);
-                    }
-                    Ok(o2) => {
-                        event_value = o2;
-                    }
-                },
             }
+        }
 
-            let mut event_value_expr = "";
-            if !ev["event-value"]["expression"].is_null() {
-                match ev["event-value"]["expression"].as_str() {
-                    None => {
-                        return Err(crate::ErrorType::Json);
-                    }
-                    Some(o) => {
-                        event_value_expr = o;
-                    }
-                }
+        let event_value: Decimal;
+        match ev["event-value"]["value"].as_str() {
+            None => {
+                return Err(ElemError::new(crate::ErrorType::Json));
             }
+            Some(o) => match o.parse::<Decimal>() {
+                Err(_e) => {
+                    return Err(ElemError::new(crate::ErrorType::Json));
+                }
+                Ok(o2) => {
+                    event_value = o2;
+                }
+            },
+        }
 
-            let mut event_value_expr_balance = false;
-            if !ev["event-value"]["expr-balance"].is_null() {
-                match ev["event-value"]["expr-balance"].as_bool() {
-                    None => {
-                        return Err(crate::ErrorType::Json);
-                    }
-                    Some(o) => {
-                        event_value_expr_balance = o;
-                    }
+        let mut event_value_expr = "";
+        if !ev["event-value"]["expression"].is_null() {
+            match ev["event-value"]["expression"].as_str() {
+                None => {
+                    return Err(ElemError::new(crate::ErrorType::Json));
+                }
+                Some(o) => {
+                    event_value_expr = o;
                 }
             }
+        }
 
-            let periods: usize = match ev["event-periods"]["periods"].as_usize() {
-                None => return Err(crate::ErrorType::Json),
-                Some(o) => o,
-            };
-
-            let mut periods_expr = "";
-            if !ev["event-periods"]["expression"].is_null() {
-                match ev["event-periods"]["expression"].as_str() {
-                    None => {
-                        return Err(crate::ErrorType::Json);
-                    }
-                    Some(o) => {
-                        periods_expr = o;
-                    }
+        let mut event_value_expr_balance = false;
+        if !ev["event-value"]["expr-balance"].is_null() {
+            match ev["event-value"]["expr-balance"].as_bool() {
+                None => {
+                    return Err(ElemError::new(crate::ErrorType::Json));
+                }
+                Some(o) => {
+                    event_value_expr_balance = o;
                 }
             }
+        }
+
+        let periods: usize = match ev["event-periods"]["periods"].as_usize() {
+            None => return Err(ElemError::new(crate::ErrorType::Json)),
+            Some(o) => o,
+        };
 
-            let skip_len: usize;
-            let skip_mask: u128;
-            match ev["skip-mask"].as_str() {
+        let mut periods_expr = "";
+        if !ev["event-periods"]["expression"].is_null() {
+            match ev["event-periods"]["expression"].as_str() {
                 None => {
-                    skip_len = 0;
-                    skip_mask = 0;
+                    return Err(ElemError::new(crate::ErrorType::Json));
                 }
                 Some(o) => {
-                    let (tskip_len, tskip_mask) = self.get_skip_mask(o);
-                    skip_len = tskip_len;
-                    skip_mask = tskip_mask;
+                    periods_expr = o;
                 }
             }
+        }
 
-            let sort_order: usize = match ev["sort-order"].as_usize() {
-                None => return Err(crate::ErrorType::Json),
-                Some(o) => o,
-            };
+        let skip_len: usize;
+        let skip_mask: u128;
+        match ev["skip-mask"].as_str() {
+            None => {
+                skip_len = 0;
+                skip_mask = 0;
+            }
+            Some(o) => {
+                let (tskip_len, tskip_mask) = self.get_skip_mask(o);
+                skip_len = tskip_len;
+                skip_mask = tskip_mask;
+            }
+        }
 
-            let intervals: usize = match ev["intervals"].as_usize() {
-                None => return Err(crate::ErrorType::Json),
-                Some(o) => o,
-            };
+        let sort_order: usize = match ev["sort-order"].as_usize() {
+            None => return Err(ElemError::new(crate::ErrorType::Json)),
+            Some(o) => o,
+        };
 
-            let frequency: crate::FrequencyType = match ev["frequency"].as_str() {
-                None => return Err(crate::ErrorType::Json),
-                Some(o) => CoreUtility::get_frequency(o),
-            };
+        let intervals: usize = match ev["intervals"].as_usize() {
+            None => return Err(ElemError::new(crate::ErrorType::Json)),
+            Some(o) => o,
+        };
 
-            if ev["extension"].is_null() {
-                return Err(crate::ErrorType::Json);
-            }
+        let frequency: crate::FrequencyType = match ev["frequency"].as_str() {
+            None => return Err(ElemError::new(crate::ErrorType::Json)),
+            Some(o) => CoreUtility::get_frequency(o),
+        };
 
-            let extension: ElemExtension = match self.deserialize_extension(&ev["extension"]) {
-                Err(e) => return Err(e),
-                Ok(o) => o,
-            };
+        if ev["extension"].is_null() {
+            return Err(ElemError::new(crate::ErrorType::Json));
+        }
 
-            let mut params = ListParameter::new();
-            if !ev["parameter-list"].is_null() {
-                let result = self.deserialize_parameter_list(&ev["parameter-list"], &mut params);
-                match result {
-                    Err(e) => {
-                        return Err(e);
-                    }
-                    Ok(_o) => {}
-                }
-            }
+        let extension: ElemExtension =
+            self.deserialize_extension_ext(calc_mgr, &ev["extension"])?;
 
-            let mut descs = ListDescriptor::new();
-            if ev["descriptor-list"].is_null() {
-                return Err(crate::ErrorType::Json);
-            }
-            let result = self.deserialize_descriptor_list(&ev["descriptor-list"], &mut descs);
+        let mut params = ListParameter::new();
+        if !ev["parameter-list"].is_null() {
+            let result = self.deserialize_parameter_list(&ev["parameter-list"], &mut params);
             match result {
                 Err(e) => {
                     return Err(e);
                 }
                 Ok(_o) => {}
             }
+        }
 
-            let event_name: &str = match ev["event-name"].as_str() {
-                None => "",
-                Some(o) => o,
-            };
-
-            let next_name: &str = match ev["event-next-name"].as_str() {
-                None => "",
-                Some(o) => o,
-            };
-
-            events.add_event(
-                event_date,
-                event_date_expr,
-                sort_order,
-                event_value,
-                event_value_expr,
-                event_value_expr_balance,
-                periods,
-                periods_expr,
-                skip_len,
-                skip_mask,
-                intervals,
-                frequency,
-                extension,
-                Option::from(params),
-                Option::from(descs),
-                event_name,
-                next_name,
-            );
-
-            index += 1;
+        let mut descs = ListDescriptor::new();
+        if ev["descriptor-list"].is_null() {
+            return Err(ElemError::new(crate::ErrorType::Json));
+        }
+        let result = self.deserialize_descriptor_list(&ev["descriptor-list"], &mut descs);
+        match result {
+            Err(e) => {
+                return Err(e);
+            }
+            Ok(_o) => {}
         }
 
-        events.set_sort_on_add(true); // Sorts list
+        let event_name: &str = ev["event-name"].as_str().unwrap_or_default();
+
+        let next_name: &str = ev["event-next-name"].as_str().unwrap_or_default();
+
+        events.add_event(
+            event_date,
+            event_date_expr,
+            sort_order,
+            event_value,
+            event_value_expr,
+            event_value_expr_balance,
+            periods,
+            periods_expr,
+            skip_len,
+            skip_mask,
+            intervals,
+            frequency,
+            extension,
+            Option::from(params),
+            Option::from(descs),
+            event_name,
+            next_name,
+        );
+
         Ok(())
     }
 
@@ -579,7 +891,7 @@ impl CalcJsonDeserialize {
     fn deserialize_exchange_rates(
         &self,
         exch_rates: &JsonValue,
-    ) -> Result<ListExchangeRate, crate::ErrorType> {
+    ) -> Result<ListExchangeRate, ElemError> {
         let mut exchange_rates = ListExchangeRate::new();
         let mut index: usize = 0;
         exchange_rates.set_sort_on_add(false);
@@ -591,23 +903,23 @@ impl CalcJsonDeserialize {
             }
 
             let from_str: &str = match exch_rate["from"].as_str() {
-                None => return Err(crate::ErrorType::Json),
+                None => return Err(ElemError::new(crate::ErrorType::Json)),
                 Some(o) => o,
             };
 
             let to_str: &str = match exch_rate["to"].as_str() {
-                None => return Err(crate::ErrorType::Json),
+                None => return Err(ElemError::new(crate::ErrorType::Json)),
                 Some(o) => o,
             };
 
             let value: Decimal;
             match exch_rate["value"].as_str() {
                 None => {
-                    return Err(crate::ErrorType::Json);
+                    return Err(ElemError::new(crate::ErrorType::Json));
                 }
                 Some(o) => match o.parse::<Decimal>() {
                     Err(_e) => {
-                        return Err(crate::ErrorType::Json);
+                        return Err(ElemError::new(crate::ErrorType::Json));
                     }
                     Ok(o2) => {
                         value = o2;
@@ -633,11 +945,25 @@ impl CalcJsonDeserialize {
     /// # Return
     ///
     /// * ERROR_NONE if successful, otherwise error code.
+    pub fn deserialize_extension(&self, ext: &JsonValue) -> Result<ElemExtension, ElemError> {
+        self.deserialize_extension_ext(&self.calc_mgr(), ext)
+    }
 
-    pub fn deserialize_extension(
+    /// Deserialize and ingest Json extension.
+    ///
+    /// # Arguments
+    ///
+    /// * `calc_mgr` - Calculation manager.
+    /// * `ext` - Json value for extension.
+    ///
+    /// # Return
+    ///
+    /// * ERROR_NONE if successful, otherwise error code.
+    fn deserialize_extension_ext(
         &self,
+        calc_mgr: &CalcManager,
         ext: &JsonValue,
-    ) -> Result<ElemExtension, crate::ErrorType> {
+    ) -> Result<ElemExtension, ElemError> {
         let event_type: crate::ExtensionType;
         if !ext["current-value"].is_null() {
             event_type = crate::ExtensionType::CurrentValue;
@@ -674,7 +1000,8 @@ impl CalcJsonDeserialize {
                     dec!(0.0),
                 );
 
-                let result = self.deserialize_interest_change(&ext["interest-change"], &mut ic);
+                let result =
+                    self.deserialize_interest_change(calc_mgr, &ext["interest-change"], &mut ic);
                 match result {
                     Err(e) => {
                         return Err(e);
@@ -735,7 +1062,7 @@ impl CalcJsonDeserialize {
     pub fn deserialize_extension_from_str(
         &self,
         ext_param: &str,
-    ) -> Result<ElemExtension, crate::ErrorType> {
+    ) -> Result<ElemExtension, ElemError> {
         let ext = if ext_param.starts_with('{') {
             String::from(ext_param)
         } else {
@@ -745,18 +1072,70 @@ impl CalcJsonDeserialize {
         let data: JsonValue = match json::parse(ext.as_str()) {
             Err(e) => {
                 println!("Json error: {:?}", e);
-                return Err(crate::ErrorType::Json);
+                return Err(ElemError::new(crate::ErrorType::Json));
             }
             Ok(o) => o,
         };
 
-        self.deserialize_extension(&data)
+        self.deserialize_extension_ext(&self.calc_mgr(), &data)
+    }
+
+    /// Deserialize and ingest Json user-defined functions.
+    ///
+    /// # Arguments
+    ///
+    /// * `funcs` - Json value for functions.
+    /// * `functions` - List of functions.
+    ///
+    /// # Return
+    ///
+    /// * ERROR_NONE if successful, otherwise error code.
+    fn deserialize_function_list(
+        &self,
+        funcs: &JsonValue,
+        functions: &mut ListFunction,
+    ) -> Result<(), ElemError> {
+        let mut index: usize = 0;
+
+        loop {
+            let func = &funcs[index];
+            if func.is_null() {
+                break;
+            }
+
+            let name: &str = match func["name"].as_str() {
+                None => return Err(ElemError::new(crate::ErrorType::Json)),
+                Some(o) => o,
+            };
+
+            let description: &str = func["description"].as_str().unwrap_or_default();
+
+            let mut parameters: Vec<String> = Vec::new();
+            for param in func["parameters"].members() {
+                match param.as_str() {
+                    None => return Err(ElemError::new(crate::ErrorType::Json)),
+                    Some(o) => parameters.push(String::from(o)),
+                }
+            }
+
+            let expression: &str = match func["expression"].as_str() {
+                None => return Err(ElemError::new(crate::ErrorType::Json)),
+                Some(o) => o,
+            };
+
+            functions.add_function(name, description, &parameters, expression);
+
+            index += 1;
+        }
+
+        Ok(())
     }
 
     /// Deserialize and ingest Json interest change.
     ///
     /// # Arguments
     ///
+    /// * `calc_mgr` - Calculation manager.
     /// * `ic` - Json value for interest change.
     /// * `interest_change` - Element interest change.
     ///
@@ -766,16 +1145,16 @@ impl CalcJsonDeserialize {
 
     fn deserialize_interest_change(
         &self,
+        calc_mgr: &CalcManager,
         ic: &JsonValue,
         interest_change: &mut ElemInterestChange,
-    ) -> Result<(), crate::ErrorType> {
-        let calc_mgr = self.calc_mgr();
+    ) -> Result<(), ElemError> {
         let decimal_digits = calc_mgr.decimal_digits(false);
 
         match ic["round-balance"].as_str() {
             None => {}
             Some(o) => {
-                let round_balance: crate::RoundType = match o {
+                let round_balance: crate::RoundType = match o.to_lowercase().as_str() {
                     "bankers" => crate::RoundType::Bankers,
                     "bias-up" => crate::RoundType::BiasUp,
                     "bias-down" => crate::RoundType::BiasDown,
@@ -793,19 +1172,7 @@ impl CalcJsonDeserialize {
         match ic["day-count-basis"].as_str() {
             None => {}
             Some(o) => {
-                let day_count_basis: crate::DayCountType = match o {
-                    "rule-of-78" => crate::DayCountType::RuleOf78,
-                    "actual" => crate::DayCountType::Actual,
-                    "actual-actual-isma" => crate::DayCountType::ActualActualISMA,
-                    "actual-actual-afb" => crate::DayCountType::ActualActualAFB,
-                    "actual-365L" => crate::DayCountType::Actual365L,
-                    "30" => crate::DayCountType::Dc30,
-                    "30E" => crate::DayCountType::Dc30E,
-                    "30EP" => crate::DayCountType::Dc30EP,
-                    _ => crate::DayCountType::Periodic,
-                };
-
-                interest_change.set_day_count_basis(day_count_basis);
+                interest_change.set_day_count_basis(CoreUtility::get_day_count_basis(o));
             }
         }
         match ic["days-in-year"].as_usize() {
@@ -831,7 +1198,7 @@ impl CalcJsonDeserialize {
         match ic["interest-method"].as_str() {
             None => {}
             Some(o) => {
-                let interest_method: crate::MethodType = match o {
+                let interest_method: crate::MethodType = match o.to_lowercase().as_str() {
                     "simple-interest" => crate::MethodType::SimpleInterest,
                     _ => crate::MethodType::Actuarial,
                 };
@@ -849,9 +1216,131 @@ impl CalcJsonDeserialize {
             }
         }
 
+        match ic["index"].as_str() {
+            None => {}
+            Some(o) => {
+                interest_change.set_index(o);
+            }
+        }
+
+        match ic["calendar"].as_str() {
+            None => {}
+            Some(o) => {
+                interest_change.set_calendar(o);
+            }
+        }
+
+        match ic["margin"].as_str() {
+            None => {}
+            Some(o) => {
+                interest_change.set_margin(CoreUtility::parse_decimal(o));
+            }
+        }
+
+        match ic["reset-frequency"].as_str() {
+            None => {}
+            Some(o) => {
+                interest_change.set_reset_frequency(CoreUtility::get_frequency(o));
+            }
+        }
+
+        match ic["lookback-days"].as_usize() {
+            None => {}
+            Some(o) => {
+                interest_change.set_lookback_days(o);
+            }
+        }
+
+        if let Some(o) = ic["periodic-cap"].as_str() {
+            interest_change.set_periodic_cap(Option::from(CoreUtility::parse_decimal(o)));
+        }
+
+        if let Some(o) = ic["periodic-floor"].as_str() {
+            interest_change.set_periodic_floor(Option::from(CoreUtility::parse_decimal(o)));
+        }
+
+        if let Some(o) = ic["lifetime-cap"].as_str() {
+            interest_change.set_lifetime_cap(Option::from(CoreUtility::parse_decimal(o)));
+        }
+
+        if let Some(o) = ic["lifetime-floor"].as_str() {
+            interest_change.set_lifetime_floor(Option::from(CoreUtility::parse_decimal(o)));
+        }
+
         Ok(())
     }
 
+    /// Deserialize and ingest Json rate indexes.
+    ///
+    /// # Arguments
+    ///
+    /// * `rate_indexes` - Json value for rate indexes.
+    ///
+    /// # Return
+    ///
+    /// * List of rate indexes if successful, otherwise error code.
+    fn deserialize_rate_indexes(
+        &self,
+        rate_indexes: &JsonValue,
+    ) -> Result<ListRateIndex, ElemError> {
+        let mut list_rate_index = ListRateIndex::new();
+        let mut index: usize = 0;
+
+        loop {
+            let rate_index = &rate_indexes[index];
+            if rate_index.is_null() {
+                break;
+            }
+
+            let name: &str = match rate_index["name"].as_str() {
+                None => {
+                    let mut error = ElemError::new(crate::ErrorType::Json);
+                    error.set_pointer(format!("/rate-indexes/{}", index).as_str());
+                    return Err(error);
+                }
+                Some(o) => o,
+            };
+
+            let mut elem_rate_index = ElemRateIndex::new(name);
+
+            for (index2, val) in rate_index["values"].members().enumerate() {
+                let date = match val["date"].as_str() {
+                    None => Err(ElemError::new(crate::ErrorType::Json)),
+                    Some(o) => self.get_date(o),
+                };
+
+                let date = match date {
+                    Err(mut e) => {
+                        e.set_pointer(
+                            format!("/rate-indexes/{}/values/{}", index, index2).as_str(),
+                        );
+                        return Err(e);
+                    }
+                    Ok(o) => o,
+                };
+
+                let rate = match val["rate"].as_str() {
+                    None => {
+                        let mut error = ElemError::new(crate::ErrorType::Json);
+                        error.set_pointer(
+                            format!("/rate-indexes/{}/values/{}", index, index2).as_str(),
+                        );
+                        return Err(error);
+                    }
+                    Some(o) => CoreUtility::parse_decimal(o),
+                };
+
+                elem_rate_index.add_value(date, rate);
+            }
+
+            list_rate_index.add_rate_index(elem_rate_index);
+
+            index += 1;
+        }
+
+        Ok(list_rate_index)
+    }
+
     /// Deserialize and ingest Json locales.
     ///
     /// # Arguments
@@ -862,7 +1351,7 @@ impl CalcJsonDeserialize {
     ///
     /// * List of locales if successful, otherwise error code.
 
-    fn deserialize_locales(&self, locales: &JsonValue) -> Result<ListLocale, crate::ErrorType> {
+    fn deserialize_locales(&self, locales: &JsonValue) -> Result<ListLocale, ElemError> {
         let mut list_locale = ListLocale::new();
         let mut index: usize = 0;
 
@@ -873,12 +1362,12 @@ impl CalcJsonDeserialize {
             }
 
             let locale_str: &str = match locale["locale-str"].as_str() {
-                None => return Err(crate::ErrorType::Json),
+                None => return Err(ElemError::new(crate::ErrorType::Json)),
                 Some(o) => o,
             };
 
             let currency_code: &str = match locale["currency-code"].as_str() {
-                None => return Err(crate::ErrorType::Json),
+                None => return Err(ElemError::new(crate::ErrorType::Json)),
                 Some(o) => o,
             };
 
@@ -891,7 +1380,7 @@ impl CalcJsonDeserialize {
             }
 
             if locale["format-in"].is_null() {
-                return Err(crate::ErrorType::Json);
+                return Err(ElemError::new(crate::ErrorType::Json));
             }
             let format_in: ElemLocaleFormat =
                 match self.deserialize_locale_format(&locale["format-in"]) {
@@ -900,7 +1389,7 @@ impl CalcJsonDeserialize {
                 };
 
             if locale["format-out"].is_null() {
-                return Err(crate::ErrorType::Json);
+                return Err(ElemError::new(crate::ErrorType::Json));
             }
             let format_out: ElemLocaleFormat =
                 match self.deserialize_locale_format(&locale["format-out"]) {
@@ -917,12 +1406,12 @@ impl CalcJsonDeserialize {
                     break;
                 }
                 let key: &str = match resource["key"].as_str() {
-                    None => return Err(crate::ErrorType::Json),
+                    None => return Err(ElemError::new(crate::ErrorType::Json)),
                     Some(o) => o,
                 };
 
                 let text: &str = match resource["text"].as_str() {
-                    None => return Err(crate::ErrorType::Json),
+                    None => return Err(ElemError::new(crate::ErrorType::Json)),
                     Some(o) => o,
                 };
 
@@ -958,44 +1447,44 @@ impl CalcJsonDeserialize {
     fn deserialize_locale_format(
         &self,
         locale_format: &JsonValue,
-    ) -> Result<ElemLocaleFormat, crate::ErrorType> {
+    ) -> Result<ElemLocaleFormat, ElemError> {
         let date_regex: &str = match locale_format["date-regex"].as_str() {
-            None => return Err(crate::ErrorType::Json),
+            None => return Err(ElemError::new(crate::ErrorType::Json)),
             Some(o) => o,
         };
 
         let date_replace: &str = match locale_format["date-replace"].as_str() {
-            None => return Err(crate::ErrorType::Json),
+            None => return Err(ElemError::new(crate::ErrorType::Json)),
             Some(o) => o,
         };
 
         let integer_regex: &str = match locale_format["integer-regex"].as_str() {
-            None => return Err(crate::ErrorType::Json),
+            None => return Err(ElemError::new(crate::ErrorType::Json)),
             Some(o) => o,
         };
 
         let integer_replace: &str = match locale_format["integer-replace"].as_str() {
-            None => return Err(crate::ErrorType::Json),
+            None => return Err(ElemError::new(crate::ErrorType::Json)),
             Some(o) => o,
         };
 
         let decimal_regex: &str = match locale_format["decimal-regex"].as_str() {
-            None => return Err(crate::ErrorType::Json),
+            None => return Err(ElemError::new(crate::ErrorType::Json)),
             Some(o) => o,
         };
 
         let decimal_replace: &str = match locale_format["decimal-replace"].as_str() {
-            None => return Err(crate::ErrorType::Json),
+            None => return Err(ElemError::new(crate::ErrorType::Json)),
             Some(o) => o,
         };
 
         let currency_regex: &str = match locale_format["currency-regex"].as_str() {
-            None => return Err(crate::ErrorType::Json),
+            None => return Err(ElemError::new(crate::ErrorType::Json)),
             Some(o) => o,
         };
 
         let currency_replace: &str = match locale_format["currency-replace"].as_str() {
-            None => return Err(crate::ErrorType::Json),
+            None => return Err(ElemError::new(crate::ErrorType::Json)),
             Some(o) => o,
         };
 
@@ -1026,7 +1515,7 @@ impl CalcJsonDeserialize {
         &self,
         params: &JsonValue,
         parameters: &mut ListParameter,
-    ) -> Result<(), crate::ErrorType> {
+    ) -> Result<(), ElemError> {
         let mut index: usize = 0;
 
         loop {
@@ -1036,7 +1525,7 @@ impl CalcJsonDeserialize {
             }
 
             let name: &str = match param["name"].as_str() {
-                None => return Err(crate::ErrorType::Json),
+                None => return Err(ElemError::new(crate::ErrorType::Json)),
                 Some(o) => o,
             };
 
@@ -1051,12 +1540,12 @@ impl CalcJsonDeserialize {
             };
 
             let param_type: &str = match param["parameter-type"].as_str() {
-                None => return Err(crate::ErrorType::Json),
+                None => return Err(ElemError::new(crate::ErrorType::Json)),
                 Some(o) => o,
             };
 
             let value: &str = match param["value"].as_str() {
-                None => return Err(crate::ErrorType::Json),
+                None => return Err(ElemError::new(crate::ErrorType::Json)),
                 Some(o) => o,
             };
 
@@ -1091,12 +1580,8 @@ impl CalcJsonDeserialize {
     ///
     /// * ERROR_NONE if successful, otherwise error code.
 
-    fn deserialize_preferences(
-        &self,
-        prefs: &JsonValue,
-    ) -> Result<ElemPreferences, crate::ErrorType> {
+    fn deserialize_preferences(&self, prefs: &JsonValue) -> Result<ElemPreferences, ElemError> {
         let mut preferences = ElemPreferences::new(
-            &self.calc_manager,
             "",
             "",
             "",
@@ -1134,7 +1619,7 @@ impl CalcJsonDeserialize {
         &self,
         prefs: &JsonValue,
         preferences: &mut ElemPreferences,
-    ) -> Result<(), crate::ErrorType> {
+    ) -> Result<(), ElemError> {
         match prefs["combine-principal"].as_i32() {
             None => {}
             Some(o) => {
@@ -1183,6 +1668,19 @@ impl CalcJsonDeserialize {
             }
         }
 
+        if !prefs["function-list"].is_null() {
+            let result = self.deserialize_function_list(
+                &prefs["function-list"],
+                preferences.list_function_mut(),
+            );
+            match result {
+                Err(e) => {
+                    return Err(e);
+                }
+                Ok(_o) => {}
+            }
+        }
+
         match prefs["group"].as_str() {
             None => {}
             Some(o) => {
@@ -1242,7 +1740,7 @@ impl CalcJsonDeserialize {
         &self,
         pc: &JsonValue,
         prin_change: &mut ElemPrincipalChange,
-    ) -> Result<(), crate::ErrorType> {
+    ) -> Result<(), ElemError> {
         match pc["auxiliary"].as_bool() {
             None => {}
             Some(o) => {
@@ -1284,6 +1782,28 @@ impl CalcJsonDeserialize {
                 prin_change.set_type(CoreUtility::get_principal_type(o));
             }
         }
+
+        match pc["business-day"].as_str() {
+            None => {}
+            Some(o) => {
+                prin_change.set_business_day(CoreUtility::get_business_day(o));
+            }
+        }
+
+        match pc["calendar"].as_str() {
+            None => {}
+            Some(o) => {
+                prin_change.set_calendar(o);
+            }
+        }
+
+        if let Some(o) = pc["payment-cap"].as_str() {
+            prin_change.set_payment_cap(Option::from(CoreUtility::parse_decimal(o)));
+        }
+
+        if let Some(o) = pc["neg-am-cap"].as_str() {
+            prin_change.set_neg_am_cap(Option::from(CoreUtility::parse_decimal(o)));
+        }
         Ok(())
     }
 
@@ -1302,7 +1822,7 @@ impl CalcJsonDeserialize {
         &self,
         sv: &JsonValue,
         stat_value: &mut ElemStatisticValue,
-    ) -> Result<(), crate::ErrorType> {
+    ) -> Result<(), ElemError> {
         match sv["eom"].as_bool() {
             None => {}
             Some(o) => {
@@ -1331,6 +1851,7 @@ impl CalcJsonDeserialize {
     ///
     /// # Arguments
     ///
+    /// * `calc_mgr` - Calculation manager.
     /// * `templ_events` - Json value for template events.
     /// * `template_events` - List of template events.
     ///
@@ -1340,9 +1861,10 @@ impl CalcJsonDeserialize {
 
     fn deserialize_template_events(
         &self,
+        calc_mgr: &CalcManager,
         templ_events: &JsonValue,
         template_events: &mut ListTemplateEvent,
-    ) -> Result<(), crate::ErrorType> {
+    ) -> Result<(), ElemError> {
         let mut index: usize = 0;
 
         template_events.set_sort_on_add(false);
@@ -1354,19 +1876,20 @@ impl CalcJsonDeserialize {
             }
 
             let name: &str = match templ_event["name"].as_str() {
-                None => return Err(crate::ErrorType::Json),
+                None => return Err(ElemError::new(crate::ErrorType::Json)),
                 Some(o) => o,
             };
 
             let initial: bool = templ_event["initial"].as_bool().unwrap_or(false);
 
             if templ_event["event-list"].is_null() {
-                return Err(crate::ErrorType::Json);
+                return Err(ElemError::new(crate::ErrorType::Json));
             }
 
             let mut list_event = ListEvent::new(false);
 
-            let result = self.deserialize_event_list(&templ_event["event-list"], &mut list_event);
+            let result =
+                self.deserialize_event_list(calc_mgr, &templ_event["event-list"], &mut list_event);
             match result {
                 Err(_e) => {
                     panic!("Deserialize event list failed");
@@ -1387,6 +1910,7 @@ impl CalcJsonDeserialize {
     ///
     /// # Arguments
     ///
+    /// * `calc_mgr` - Calculation manager.
     /// * `templ_groups` - Json value for template groups.
     /// * `template_groups` - List of template groups.
     ///
@@ -1396,9 +1920,10 @@ impl CalcJsonDeserialize {
 
     fn deserialize_template_groups(
         &self,
+        calc_mgr: &CalcManager,
         templ_groups: &JsonValue,
-    ) -> Result<ListTemplateGroup, crate::ErrorType> {
-        let mut template_groups = ListTemplateGroup::new(&self.calc_manager);
+    ) -> Result<ListTemplateGroup, ElemError> {
+        let mut template_groups = ListTemplateGroup::new();
         let mut index: usize = 0;
 
         template_groups.set_sort_on_add(false);
@@ -1410,11 +1935,11 @@ impl CalcJsonDeserialize {
             }
 
             let group: &str = match templ_group["group"].as_str() {
-                None => return Err(crate::ErrorType::Json),
+                None => return Err(ElemError::new(crate::ErrorType::Json)),
                 Some(o) => o,
             };
 
-            match template_groups.add_template_group(group) {
+            match template_groups.add_template_group(calc_mgr, group) {
                 Err(_e) => {
                     panic!("Add template group failed");
                 }
@@ -1435,10 +1960,11 @@ impl CalcJsonDeserialize {
             }
 
             if templ_group["template-events"].is_null() {
-                return Err(crate::ErrorType::Json);
+                return Err(ElemError::new(crate::ErrorType::Json));
             }
 
             let result = self.deserialize_template_events(
+                calc_mgr,
                 &templ_group["template-events"],
                 template_groups.list_template_event_mut(),
             );
@@ -1456,7 +1982,7 @@ impl CalcJsonDeserialize {
         Ok(template_groups)
     }
 
-    /// Parse and return a numeric date.
+    /// Parse and return a date.
     ///
     /// # Arguments
     ///
@@ -1464,21 +1990,23 @@ impl CalcJsonDeserialize {
     ///
     /// # Return
     ///
-    /// * See description.
-
-    fn get_date(&self, event_date: &str) -> usize {
+    /// * The date if valid, otherwise error code.
+    fn get_date(&self, event_date: &str) -> Result<NaiveDate, ElemError> {
         match Regex::new(r"(\d{4})-(\d{2})-(\d{2})") {
             Err(_e) => {
                 panic!("Invalid regular expression");
             }
             Ok(o) => match o.captures(event_date) {
-                None => 0,
+                None => Err(ElemError::new(crate::ErrorType::Date)),
                 Some(dt) => {
                     let year = CoreUtility::parse_integer(&dt[1]);
                     let month = CoreUtility::parse_integer(&dt[2]);
                     let day = CoreUtility::parse_integer(&dt[3]);
 
-                    year * 10000 + month * 100 + day
+                    match CoreUtility::integer_to_date(year * 10000 + month * 100 + day) {
+                        None => Err(ElemError::new(crate::ErrorType::Date)),
+                        Some(o) => Ok(o),
+                    }
                 }
             },
         }
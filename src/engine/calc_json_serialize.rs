@@ -7,28 +7,28 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-use std::cell::{Cell, Ref, RefCell};
-use std::rc::Rc;
-
+use chrono::NaiveDate;
 use rust_decimal::prelude::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
 
 use super::{
-    CalcManager, ElemPreferences, ListCashflow, ListExchangeRate, ListTemplateEvent,
-    ListTemplateGroup,
+    CalcManager, ElemPreferences, ListCalendar, ListCashflow, ListExchangeRate, ListRateIndex,
+    ListTemplateEvent, ListTemplateGroup,
 };
 use crate::core::{
     CoreUtility, ElemBalanceResult, ElemCurrentValue, ElemExtension, ElemInterestChange,
     ElemPrincipalChange, ElemStatisticValue, ExtensionValue, ListAmortization, ListDescriptor,
-    ListEvent, ListParameter,
+    ListEvent, ListFunction, ListParameter,
 };
 use crate::ListTrait;
 
 pub struct CalcJsonSerialize {
     /// Calculator manager element.
-    calc_manager: Rc<RefCell<CalcManager>>,
+    calc_manager: Arc<RwLock<CalcManager>>,
 
     /// Active Json Element depth
-    depth: Cell<usize>,
+    depth: AtomicUsize,
 }
 
 /// The serilaize json implementation of the AmFn engine.
@@ -44,10 +44,10 @@ impl CalcJsonSerialize {
     ///
     /// * See description.
 
-    pub fn new(calc_manager_param: &Rc<RefCell<CalcManager>>) -> CalcJsonSerialize {
+    pub fn new(calc_manager_param: &Arc<RwLock<CalcManager>>) -> CalcJsonSerialize {
         CalcJsonSerialize {
-            calc_manager: Rc::clone(calc_manager_param),
-            depth: Cell::new(0),
+            calc_manager: Arc::clone(calc_manager_param),
+            depth: AtomicUsize::new(0),
         }
     }
 
@@ -57,8 +57,17 @@ impl CalcJsonSerialize {
     ///
     /// * See description.
 
-    fn calc_mgr(&self) -> Ref<CalcManager> {
-        self.calc_manager.borrow()
+    fn calc_mgr(&self) -> RwLockReadGuard<'_, CalcManager> {
+        self.calc_manager.read().unwrap()
+    }
+
+    /// Returns the mutable calculation manager.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn calc_mgr_mut(&self) -> RwLockWriteGuard<'_, CalcManager> {
+        self.calc_manager.write().unwrap()
     }
 
     /// Serialize elements into Json.
@@ -74,13 +83,18 @@ impl CalcJsonSerialize {
     pub fn serialize(&self, options: usize) -> String {
         let mut buf = String::from("");
 
-        self.depth.set(0);
+        // Walking the cashflows moves the list cursor.
+        let calc_mgr = self.calc_mgr_mut();
+
+        self.depth.store(0, Ordering::Relaxed);
 
         buf.push('{');
         buf.push_str(crate::LINE_ENDING);
         self.increment_depth();
 
         let mut add_comma = options & crate::JSON_SERIALIZE_EXCHANGE_RATES != 0
+            || options & crate::JSON_SERIALIZE_CALENDARS != 0
+            || options & crate::JSON_SERIALIZE_RATE_INDEXES != 0
             || options & crate::JSON_SERIALIZE_PREFERENCES != 0
             || options & crate::JSON_SERIALIZE_TEMPLATES != 0;
 
@@ -93,32 +107,51 @@ impl CalcJsonSerialize {
             || is_am_list
         {
             self.serialize_cashflows(
-                self.calc_mgr().list_cashflow(),
+                &calc_mgr,
+                calc_mgr.list_cashflow(),
                 &mut buf,
                 options,
                 add_comma,
             );
         }
 
-        add_comma = options & crate::JSON_SERIALIZE_PREFERENCES != 0
+        add_comma = options & crate::JSON_SERIALIZE_CALENDARS != 0
+            || options & crate::JSON_SERIALIZE_RATE_INDEXES != 0
+            || options & crate::JSON_SERIALIZE_PREFERENCES != 0
             || options & crate::JSON_SERIALIZE_TEMPLATES != 0;
 
         if options & crate::JSON_SERIALIZE_EXCHANGE_RATES != 0 {
-            self.serialize_exchange_rates(
-                self.calc_mgr().list_exchange_rate(),
-                &mut buf,
-                add_comma,
-            );
+            self.serialize_exchange_rates(calc_mgr.list_exchange_rate(), &mut buf, add_comma);
+        }
+
+        add_comma = options & crate::JSON_SERIALIZE_RATE_INDEXES != 0
+            || options & crate::JSON_SERIALIZE_PREFERENCES != 0
+            || options & crate::JSON_SERIALIZE_TEMPLATES != 0;
+
+        if options & crate::JSON_SERIALIZE_CALENDARS != 0 {
+            self.serialize_calendars(calc_mgr.list_calendar(), &mut buf, add_comma);
+        }
+
+        add_comma = options & crate::JSON_SERIALIZE_PREFERENCES != 0
+            || options & crate::JSON_SERIALIZE_TEMPLATES != 0;
+
+        if options & crate::JSON_SERIALIZE_RATE_INDEXES != 0 {
+            self.serialize_rate_indexes(calc_mgr.list_rate_index(), &mut buf, add_comma);
         }
 
         add_comma = options & crate::JSON_SERIALIZE_TEMPLATES != 0;
 
         if options & crate::JSON_SERIALIZE_PREFERENCES != 0 {
-            self.serialize_preferences(self.calc_mgr().preferences(), &mut buf, add_comma);
+            self.serialize_preferences(calc_mgr.preferences(), &mut buf, add_comma);
         }
 
         if options & crate::JSON_SERIALIZE_TEMPLATES != 0 {
-            self.serialize_template_groups(self.calc_mgr().list_template_group(), &mut buf, false);
+            self.serialize_template_groups(
+                &calc_mgr,
+                calc_mgr.list_template_group(),
+                &mut buf,
+                false,
+            );
         }
 
         self.decrement_depth();
@@ -133,6 +166,7 @@ impl CalcJsonSerialize {
     ///
     /// # Arguments
     ///
+    /// * `calc_mgr` - Calculation manager.
     /// * `include_rollups` - Include rollup elements.
     /// * `include_details` - Include detail elements (if include_rollups is true).
     /// * `buf` - Buffer to append serialization.
@@ -140,12 +174,12 @@ impl CalcJsonSerialize {
 
     fn serialize_am_list(
         &self,
+        calc_mgr: &CalcManager,
         include_rollups: bool,
         include_details: bool,
         buf: &mut String,
         add_comma: bool,
     ) {
-        let calc_mgr = self.calc_mgr();
         let decimal_digits = calc_mgr.decimal_digits(true);
         let list_locale = calc_mgr.list_locale();
 
@@ -193,6 +227,17 @@ impl CalcJsonSerialize {
                 buf.push_str(list_locale.format_date_out(list_am.event_date()).as_str());
                 buf.push_str("\",");
                 buf.push_str(crate::LINE_ENDING);
+                if list_am.scheduled_date() != list_am.event_date() {
+                    buf.push_str(self.indent().as_str());
+                    buf.push_str("\"scheduled-date\": \"");
+                    buf.push_str(
+                        list_locale
+                            .format_date_out(list_am.scheduled_date())
+                            .as_str(),
+                    );
+                    buf.push_str("\",");
+                    buf.push_str(crate::LINE_ENDING);
+                }
                 buf.push_str(self.indent().as_str());
                 buf.push_str("\"frequency\": \"");
                 buf.push_str(CoreUtility::get_frequency_mnemonic(list_am.frequency()).as_str());
@@ -226,6 +271,13 @@ impl CalcJsonSerialize {
 
                 buf.push_str("\",");
                 buf.push_str(crate::LINE_ENDING);
+                if let Some(o) = list_am.index_value() {
+                    buf.push_str(self.indent().as_str());
+                    buf.push_str("\"index-value\": \"");
+                    buf.push_str(list_locale.format_decimal_out(o).as_str());
+                    buf.push_str("\",");
+                    buf.push_str(crate::LINE_ENDING);
+                }
                 buf.push_str(self.indent().as_str());
                 buf.push_str("\"value-to-interest\": \"");
                 buf.push_str(
@@ -244,6 +296,90 @@ impl CalcJsonSerialize {
                 );
                 buf.push_str("\",");
                 buf.push_str(crate::LINE_ENDING);
+                if list_am.deferred_interest() != dec!(0.0) {
+                    buf.push_str(self.indent().as_str());
+                    buf.push_str("\"deferred-interest\": \"");
+                    buf.push_str(
+                        list_locale
+                            .format_currency_out(list_am.deferred_interest(), decimal_digits)
+                            .as_str(),
+                    );
+                    buf.push_str("\",");
+                    buf.push_str(crate::LINE_ENDING);
+                }
+                if list_am.scheduled_principal() != dec!(0.0)
+                    || list_am.prepaid_principal() != dec!(0.0)
+                {
+                    buf.push_str(self.indent().as_str());
+                    buf.push_str("\"scheduled-principal\": \"");
+                    buf.push_str(
+                        list_locale
+                            .format_currency_out(list_am.scheduled_principal(), decimal_digits)
+                            .as_str(),
+                    );
+                    buf.push_str("\",");
+                    buf.push_str(crate::LINE_ENDING);
+                    buf.push_str(self.indent().as_str());
+                    buf.push_str("\"prepaid-principal\": \"");
+                    buf.push_str(
+                        list_locale
+                            .format_currency_out(list_am.prepaid_principal(), decimal_digits)
+                            .as_str(),
+                    );
+                    buf.push_str("\",");
+                    buf.push_str(crate::LINE_ENDING);
+                    buf.push_str(self.indent().as_str());
+                    buf.push_str("\"surviving-balance\": \"");
+                    buf.push_str(
+                        list_locale
+                            .format_currency_out(list_am.surviving_balance(), decimal_digits)
+                            .as_str(),
+                    );
+                    buf.push_str("\",");
+                    buf.push_str(crate::LINE_ENDING);
+                }
+                if list_am.defaulted_principal() != dec!(0.0)
+                    || list_am.recovery() != dec!(0.0)
+                    || list_am.loss() != dec!(0.0)
+                    || list_am.delinquent_balance() != dec!(0.0)
+                {
+                    buf.push_str(self.indent().as_str());
+                    buf.push_str("\"defaulted-principal\": \"");
+                    buf.push_str(
+                        list_locale
+                            .format_currency_out(list_am.defaulted_principal(), decimal_digits)
+                            .as_str(),
+                    );
+                    buf.push_str("\",");
+                    buf.push_str(crate::LINE_ENDING);
+                    buf.push_str(self.indent().as_str());
+                    buf.push_str("\"recovery\": \"");
+                    buf.push_str(
+                        list_locale
+                            .format_currency_out(list_am.recovery(), decimal_digits)
+                            .as_str(),
+                    );
+                    buf.push_str("\",");
+                    buf.push_str(crate::LINE_ENDING);
+                    buf.push_str(self.indent().as_str());
+                    buf.push_str("\"loss\": \"");
+                    buf.push_str(
+                        list_locale
+                            .format_currency_out(list_am.loss(), decimal_digits)
+                            .as_str(),
+                    );
+                    buf.push_str("\",");
+                    buf.push_str(crate::LINE_ENDING);
+                    buf.push_str(self.indent().as_str());
+                    buf.push_str("\"delinquent-balance\": \"");
+                    buf.push_str(
+                        list_locale
+                            .format_currency_out(list_am.delinquent_balance(), decimal_digits)
+                            .as_str(),
+                    );
+                    buf.push_str("\",");
+                    buf.push_str(crate::LINE_ENDING);
+                }
                 buf.push_str(self.indent().as_str());
                 buf.push_str("\"principal-decrease\": \"");
                 buf.push_str(
@@ -309,7 +445,8 @@ impl CalcJsonSerialize {
                 buf.push_str(crate::LINE_ENDING);
 
                 buf.push_str(
-                    self.serialize_extension(
+                    self.serialize_extension_ext(
+                        calc_mgr,
                         list_am.elem_extension(),
                         list_am.value(),
                         list_am.frequency(),
@@ -351,17 +488,18 @@ impl CalcJsonSerialize {
     ///
     /// # Arguments
     ///
+    /// * `calc_mgr` - Calculation manager.
     /// * `balance_result` - Balance result to serialize.
     /// * `buf` - Buffer to append serialization.
     /// * `add_comma` - Append comma on last line of output.
 
     fn serialize_balance_result(
         &self,
+        calc_mgr: &CalcManager,
         balance_result: &ElemBalanceResult,
         buf: &mut String,
         add_comma: bool,
     ) {
-        let calc_mgr = self.calc_mgr();
         let decimal_digits = calc_mgr.decimal_digits(true);
         let list_locale = calc_mgr.list_locale();
 
@@ -497,6 +635,82 @@ impl CalcJsonSerialize {
         buf.push_str("\",");
         buf.push_str(crate::LINE_ENDING);
 
+        if balance_result.scheduled_principal_total() != dec!(0.0)
+            || balance_result.prepaid_principal_total() != dec!(0.0)
+        {
+            buf.push_str(self.indent().as_str());
+            buf.push_str("\"scheduled-principal-total\": \"");
+            buf.push_str(
+                list_locale
+                    .format_currency_out(balance_result.scheduled_principal_total(), decimal_digits)
+                    .as_str(),
+            );
+            buf.push_str("\",");
+            buf.push_str(crate::LINE_ENDING);
+
+            buf.push_str(self.indent().as_str());
+            buf.push_str("\"prepaid-principal-total\": \"");
+            buf.push_str(
+                list_locale
+                    .format_currency_out(balance_result.prepaid_principal_total(), decimal_digits)
+                    .as_str(),
+            );
+            buf.push_str("\",");
+            buf.push_str(crate::LINE_ENDING);
+
+            buf.push_str(self.indent().as_str());
+            buf.push_str("\"surviving-balance\": \"");
+            buf.push_str(
+                list_locale
+                    .format_currency_out(balance_result.surviving_balance(), decimal_digits)
+                    .as_str(),
+            );
+            buf.push_str("\",");
+            buf.push_str(crate::LINE_ENDING);
+        }
+
+        if balance_result.defaulted_principal_total() != dec!(0.0) {
+            buf.push_str(self.indent().as_str());
+            buf.push_str("\"defaulted-principal-total\": \"");
+            buf.push_str(
+                list_locale
+                    .format_currency_out(balance_result.defaulted_principal_total(), decimal_digits)
+                    .as_str(),
+            );
+            buf.push_str("\",");
+            buf.push_str(crate::LINE_ENDING);
+
+            buf.push_str(self.indent().as_str());
+            buf.push_str("\"recovery-total\": \"");
+            buf.push_str(
+                list_locale
+                    .format_currency_out(balance_result.recovery_total(), decimal_digits)
+                    .as_str(),
+            );
+            buf.push_str("\",");
+            buf.push_str(crate::LINE_ENDING);
+
+            buf.push_str(self.indent().as_str());
+            buf.push_str("\"loss-total\": \"");
+            buf.push_str(
+                list_locale
+                    .format_currency_out(balance_result.loss_total(), decimal_digits)
+                    .as_str(),
+            );
+            buf.push_str("\",");
+            buf.push_str(crate::LINE_ENDING);
+
+            buf.push_str(self.indent().as_str());
+            buf.push_str("\"delinquent-balance\": \"");
+            buf.push_str(
+                list_locale
+                    .format_currency_out(balance_result.delinquent_balance(), decimal_digits)
+                    .as_str(),
+            );
+            buf.push_str("\",");
+            buf.push_str(crate::LINE_ENDING);
+        }
+
         buf.push_str(self.indent().as_str());
         buf.push_str("\"principal-changes-present\": ");
         buf.push_str(
@@ -546,10 +760,149 @@ impl CalcJsonSerialize {
         buf.push_str(crate::LINE_ENDING);
     }
 
+    /// Serialize list of calendars.
+    ///
+    /// # Arguments
+    ///
+    /// * `calendars` - List of calendars to serialize.
+    /// * `buf` - Buffer to append serialization.
+    /// * `add_comma` - Append comma on last line of output.
+    fn serialize_calendars(&self, calendars: &ListCalendar, buf: &mut String, add_comma: bool) {
+        buf.push_str(self.indent().as_str());
+        buf.push_str("\"calendars\": [");
+        buf.push_str(crate::LINE_ENDING);
+        self.increment_depth();
+
+        let orig_index = calendars.index();
+        let mut index: usize = 0;
+        while calendars.get_element(index) {
+            let calendar = calendars.calendar();
+
+            buf.push_str(self.indent().as_str());
+            buf.push('{');
+            buf.push_str(crate::LINE_ENDING);
+            self.increment_depth();
+
+            buf.push_str(self.indent().as_str());
+            buf.push_str("\"name\": \"");
+            buf.push_str(calendar.name());
+            buf.push_str("\",");
+            buf.push_str(crate::LINE_ENDING);
+
+            let weekend_days: Vec<String> = calendar
+                .weekend_days()
+                .iter()
+                .map(|o| o.to_string())
+                .collect();
+            buf.push_str(self.indent().as_str());
+            buf.push_str("\"weekend\": [");
+            buf.push_str(weekend_days.join(", ").as_str());
+            buf.push_str("],");
+            buf.push_str(crate::LINE_ENDING);
+
+            buf.push_str(self.indent().as_str());
+            buf.push_str("\"holidays\": [");
+            buf.push_str(crate::LINE_ENDING);
+            self.increment_depth();
+
+            let holidays = calendar.holidays();
+            for (holiday_index, holiday) in holidays.iter().enumerate() {
+                buf.push_str(self.indent().as_str());
+                buf.push_str("{ \"name\": \"");
+                buf.push_str(holiday.name());
+                buf.push_str("\", ");
+
+                match holiday.holiday_type() {
+                    crate::HolidayType::Fixed => {
+                        buf.push_str(
+                            format!(
+                                "\"type\": \"fixed\", \"month\": {}, \"day\": {}, \"observed\": {}",
+                                holiday.month(),
+                                holiday.day(),
+                                self.get_bool_str(holiday.observed())
+                            )
+                            .as_str(),
+                        );
+                    }
+                    crate::HolidayType::NthWeekday => {
+                        buf.push_str(
+                            format!(
+                                "\"type\": \"nth-weekday\", \"month\": {}, \"week\": {}, \"weekday\": {}",
+                                holiday.month(),
+                                holiday.week(),
+                                holiday.weekday()
+                            )
+                            .as_str(),
+                        );
+                    }
+                    crate::HolidayType::LastWeekday => {
+                        buf.push_str(
+                            format!(
+                                "\"type\": \"last-weekday\", \"month\": {}, \"weekday\": {}",
+                                holiday.month(),
+                                holiday.weekday()
+                            )
+                            .as_str(),
+                        );
+                    }
+                    crate::HolidayType::Easter => {
+                        buf.push_str(
+                            format!("\"type\": \"easter\", \"offset\": {}", holiday.offset())
+                                .as_str(),
+                        );
+                    }
+                    crate::HolidayType::Date => {
+                        if let Some(o) = holiday.date() {
+                            buf.push_str(
+                                format!(
+                                    "\"type\": \"date\", \"date\": \"{}\"",
+                                    self.get_date_str(o)
+                                )
+                                .as_str(),
+                            );
+                        }
+                    }
+                }
+
+                buf.push_str(" }");
+                if holiday_index + 1 < holidays.len() {
+                    buf.push(',');
+                }
+                buf.push_str(crate::LINE_ENDING);
+            }
+
+            self.decrement_depth();
+            buf.push_str(self.indent().as_str());
+            buf.push(']');
+            buf.push_str(crate::LINE_ENDING);
+
+            index += 1;
+
+            self.decrement_depth();
+            buf.push_str(self.indent().as_str());
+            buf.push('}');
+            if index < calendars.count() {
+                buf.push(',');
+            }
+            buf.push_str(crate::LINE_ENDING);
+        }
+
+        calendars.get_element(orig_index);
+
+        self.decrement_depth();
+        buf.push_str(self.indent().as_str());
+        buf.push(']');
+        if add_comma {
+            buf.push(',');
+        }
+        buf.push_str(crate::LINE_ENDING);
+    }
+
     /// Serialize list of cashflows.
     ///
     /// # Arguments
     ///
+    /// * `calc_mgr` - Calculation manager.
     /// * `cashflows` - List of cashflows to serialize.
     /// * `buf` - Buffer to append serialization.
     /// * `options` - Serialization options.
@@ -557,6 +910,7 @@ impl CalcJsonSerialize {
 
     fn serialize_cashflows(
         &self,
+        calc_mgr: &CalcManager,
         cashflows: &ListCashflow,
         buf: &mut String,
         options: usize,
@@ -610,17 +964,17 @@ impl CalcJsonSerialize {
                     match cashflows.list_event() {
                         None => {}
                         Some(o) => {
-                            self.serialize_event_list(o, buf, is_am_list);
+                            self.serialize_event_list(calc_mgr, o, buf, is_am_list);
                         }
                     }
                 }
 
                 if is_am_list {
-                    self.serialize_am_list(is_am_rollups, is_am_details, buf, true);
+                    self.serialize_am_list(calc_mgr, is_am_rollups, is_am_details, buf, true);
                     match cashflows.elem_balance_result() {
                         None => {}
                         Some(o) => {
-                            self.serialize_balance_result(o, buf, false);
+                            self.serialize_balance_result(calc_mgr, o, buf, false);
                         }
                     }
                 }
@@ -678,6 +1032,24 @@ impl CalcJsonSerialize {
         buf.push(',');
         buf.push_str(crate::LINE_ENDING);
 
+        if current_value.business_day() != crate::BusinessDayType::Unadjusted {
+            buf.push_str(self.indent().as_str());
+            buf.push_str("\"business-day\": \"");
+            buf.push_str(
+                CoreUtility::get_business_day_mnemonic(current_value.business_day()).as_str(),
+            );
+            buf.push_str("\",");
+            buf.push_str(crate::LINE_ENDING);
+        }
+
+        if !current_value.calendar().is_empty() {
+            buf.push_str(self.indent().as_str());
+            buf.push_str("\"calendar\": \"");
+            buf.push_str(current_value.calendar());
+            buf.push_str("\",");
+            buf.push_str(crate::LINE_ENDING);
+        }
+
         buf.push_str(self.indent().as_str());
         buf.push_str("\"eom\": ");
         buf.push_str(self.get_bool_str(current_value.eom()));
@@ -790,11 +1162,18 @@ impl CalcJsonSerialize {
     ///
     /// # Arguments
     ///
+    /// * `calc_mgr` - Calculation manager.
     /// * `list_event` - List of event elements to serialize.
     /// * `buf` - Buffer to append serialization.
     /// * `add_comma` - Append comma on last line of output.
 
-    fn serialize_event_list(&self, list_event: &ListEvent, buf: &mut String, add_comma: bool) {
+    fn serialize_event_list(
+        &self,
+        calc_mgr: &CalcManager,
+        list_event: &ListEvent,
+        buf: &mut String,
+        add_comma: bool,
+    ) {
         buf.push_str(self.indent().as_str());
         buf.push_str("\"event-list\": [");
         buf.push_str(crate::LINE_ENDING);
@@ -946,7 +1325,8 @@ impl CalcJsonSerialize {
                 buf.push_str(crate::LINE_ENDING);
 
                 buf.push_str(
-                    self.serialize_extension(
+                    self.serialize_extension_ext(
+                        calc_mgr,
                         list_event.elem_extension(),
                         dec!(0.0),
                         list_event.frequency(),
@@ -1071,7 +1451,6 @@ impl CalcJsonSerialize {
     /// * `buf` - Buffer to append serialization.
     /// * `add_comma` - Append comma on last line of output.
     /// * `all_data` - Serialize all data properties.
-
     pub fn serialize_extension(
         &self,
         ext: &ElemExtension,
@@ -1079,6 +1458,27 @@ impl CalcJsonSerialize {
         frequency: crate::FrequencyType,
         add_comma: bool,
         all_data: bool,
+    ) -> String {
+        self.serialize_extension_ext(&self.calc_mgr(), ext, value, frequency, add_comma, all_data)
+    }
+
+    /// Serialize extension.
+    ///
+    /// # Arguments
+    ///
+    /// * `calc_mgr` - Calculation manager.
+    /// * `ext` - Extension to serialize.
+    /// * `buf` - Buffer to append serialization.
+    /// * `add_comma` - Append comma on last line of output.
+    /// * `all_data` - Serialize all data properties.
+    fn serialize_extension_ext(
+        &self,
+        calc_mgr: &CalcManager,
+        ext: &ElemExtension,
+        value: Decimal,
+        frequency: crate::FrequencyType,
+        add_comma: bool,
+        all_data: bool,
     ) -> String {
         let mut buf = String::from("");
         buf.push_str(self.indent().as_str());
@@ -1091,7 +1491,7 @@ impl CalcJsonSerialize {
                 self.serialize_current_value(o, &mut buf);
             }
             ExtensionValue::InterestChange(o) => {
-                self.serialize_interest_change(o, &mut buf, value, frequency, all_data);
+                self.serialize_interest_change(calc_mgr, o, &mut buf, value, frequency, all_data);
             }
             ExtensionValue::PrincipalChange(o) => {
                 self.serialize_principal_change(o, &mut buf);
@@ -1112,10 +1512,91 @@ impl CalcJsonSerialize {
         buf
     }
 
+    /// Serialize user-defined function list.
+    ///
+    /// # Arguments
+    ///
+    /// * `list_function` - List of functions to serialize.
+    /// * `buf` - Buffer to append serialization.
+    /// * `add_comma` - Append comma on last line of output.
+    fn serialize_function_list(
+        &self,
+        list_function: &ListFunction,
+        buf: &mut String,
+        add_comma: bool,
+    ) {
+        buf.push_str(self.indent().as_str());
+        buf.push_str("\"function-list\": [");
+        buf.push_str(crate::LINE_ENDING);
+        self.increment_depth();
+
+        let mut index: usize = 0;
+        if list_function.get_element(index) {
+            let mut deserialize_list = true;
+
+            while deserialize_list {
+                buf.push_str(self.indent().as_str());
+                buf.push('{');
+                buf.push_str(crate::LINE_ENDING);
+                self.increment_depth();
+                buf.push_str(self.indent().as_str());
+                buf.push_str("\"name\": \"");
+                buf.push_str(list_function.name());
+                buf.push_str("\",");
+                buf.push_str(crate::LINE_ENDING);
+
+                if !list_function.description().is_empty() {
+                    buf.push_str(self.indent().as_str());
+                    buf.push_str("\"description\": \"");
+                    buf.push_str(self.escape_string(list_function.description()).as_str());
+                    buf.push_str("\",");
+                    buf.push_str(crate::LINE_ENDING);
+                }
+
+                buf.push_str(self.indent().as_str());
+                buf.push_str("\"parameters\": [");
+                for (index2, param) in list_function.parameters().iter().enumerate() {
+                    if index2 > 0 {
+                        buf.push_str(", ");
+                    }
+                    buf.push('"');
+                    buf.push_str(param.as_str());
+                    buf.push('"');
+                }
+                buf.push_str("],");
+                buf.push_str(crate::LINE_ENDING);
+                buf.push_str(self.indent().as_str());
+                buf.push_str("\"expression\": \"");
+                buf.push_str(self.escape_string(list_function.expression()).as_str());
+                buf.push('"');
+                buf.push_str(crate::LINE_ENDING);
+                index += 1;
+                deserialize_list = list_function.get_element(index);
+
+                self.decrement_depth();
+                buf.push_str(self.indent().as_str());
+                buf.push('}');
+                if deserialize_list {
+                    buf.push(',');
+                }
+                buf.push_str(crate::LINE_ENDING);
+            }
+        }
+
+        self.decrement_depth();
+        buf.push_str(self.indent().as_str());
+        buf.push(']');
+        if add_comma {
+            buf.push(',');
+        }
+        buf.push_str(crate::LINE_ENDING);
+    }
+
     /// Serialize interest change element.
     ///
     /// # Arguments
     ///
+    /// * `calc_mgr` - Calculation manager.
     /// * `interest_change` - Interest change element.
     /// * `buf` - Buffer to append serialization.
     /// * `nar` - Nominal annual rate.
@@ -1124,13 +1605,13 @@ impl CalcJsonSerialize {
 
     fn serialize_interest_change(
         &self,
+        calc_mgr: &CalcManager,
         interest_change: &ElemInterestChange,
         buf: &mut String,
         nar: Decimal,
         frequency: crate::FrequencyType,
         all_data: bool,
     ) {
-        let calc_mgr = self.calc_mgr();
         let decimal_digits = calc_mgr.decimal_digits(false);
         let list_locale = calc_mgr.list_locale();
 
@@ -1258,6 +1739,61 @@ impl CalcJsonSerialize {
             buf.push_str("\",");
             buf.push_str(crate::LINE_ENDING);
         }
+
+        if !interest_change.index().is_empty() {
+            buf.push_str(self.indent().as_str());
+            buf.push_str("\"index\": \"");
+            buf.push_str(interest_change.index());
+            buf.push_str("\",");
+            buf.push_str(crate::LINE_ENDING);
+            buf.push_str(self.indent().as_str());
+            buf.push_str("\"margin\": \"");
+            buf.push_str(interest_change.margin().to_string().as_str());
+            buf.push_str("\",");
+            buf.push_str(crate::LINE_ENDING);
+
+            if all_data || interest_change.reset_frequency() != crate::FrequencyType::None {
+                buf.push_str(self.indent().as_str());
+                buf.push_str("\"reset-frequency\": \"");
+                buf.push_str(
+                    CoreUtility::get_frequency_mnemonic(interest_change.reset_frequency()).as_str(),
+                );
+                buf.push_str("\",");
+                buf.push_str(crate::LINE_ENDING);
+            }
+
+            if all_data || interest_change.lookback_days() > 0 {
+                buf.push_str(self.indent().as_str());
+                buf.push_str("\"lookback-days\": ");
+                buf.push_str(interest_change.lookback_days().to_string().as_str());
+                buf.push(',');
+                buf.push_str(crate::LINE_ENDING);
+            }
+
+            let limits = [
+                ("periodic-cap", interest_change.periodic_cap()),
+                ("periodic-floor", interest_change.periodic_floor()),
+                ("lifetime-cap", interest_change.lifetime_cap()),
+                ("lifetime-floor", interest_change.lifetime_floor()),
+            ];
+
+            for (name, limit) in limits.iter() {
+                if let Some(o) = limit {
+                    buf.push_str(self.indent().as_str());
+                    buf.push_str(format!("\"{}\": \"{}\",", name, o).as_str());
+                    buf.push_str(crate::LINE_ENDING);
+                }
+            }
+        }
+
+        if !interest_change.calendar().is_empty() {
+            buf.push_str(self.indent().as_str());
+            buf.push_str("\"calendar\": \"");
+            buf.push_str(interest_change.calendar());
+            buf.push_str("\",");
+            buf.push_str(crate::LINE_ENDING);
+        }
+
         buf.push_str(self.indent().as_str());
         buf.push_str("\"days-in-year\": ");
         buf.push_str(interest_change.days_in_year().to_string().as_str());
@@ -1432,6 +1968,10 @@ impl CalcJsonSerialize {
 
         self.serialize_parameter_list(preferences.list_parameter(), buf, true);
 
+        if preferences.list_function().count() > 0 {
+            self.serialize_function_list(preferences.list_function(), buf, true);
+        }
+
         self.serialize_descriptor_list(preferences.list_descriptor(), buf, false);
 
         self.decrement_depth();
@@ -1485,6 +2025,36 @@ impl CalcJsonSerialize {
         buf.push(',');
         buf.push_str(crate::LINE_ENDING);
 
+        if prin_change.business_day() != crate::BusinessDayType::Unadjusted {
+            buf.push_str(self.indent().as_str());
+            buf.push_str("\"business-day\": \"");
+            buf.push_str(
+                CoreUtility::get_business_day_mnemonic(prin_change.business_day()).as_str(),
+            );
+            buf.push_str("\",");
+            buf.push_str(crate::LINE_ENDING);
+        }
+
+        if !prin_change.calendar().is_empty() {
+            buf.push_str(self.indent().as_str());
+            buf.push_str("\"calendar\": \"");
+            buf.push_str(prin_change.calendar());
+            buf.push_str("\",");
+            buf.push_str(crate::LINE_ENDING);
+        }
+
+        if let Some(o) = prin_change.payment_cap() {
+            buf.push_str(self.indent().as_str());
+            buf.push_str(format!("\"payment-cap\": \"{}\",", o).as_str());
+            buf.push_str(crate::LINE_ENDING);
+        }
+
+        if let Some(o) = prin_change.neg_am_cap() {
+            buf.push_str(self.indent().as_str());
+            buf.push_str(format!("\"neg-am-cap\": \"{}\",", o).as_str());
+            buf.push_str(crate::LINE_ENDING);
+        }
+
         buf.push_str(self.indent().as_str());
         buf.push_str("\"eom\": ");
         buf.push_str(self.get_bool_str(prin_change.eom()));
@@ -1496,6 +2066,89 @@ impl CalcJsonSerialize {
         buf.push_str(crate::LINE_ENDING);
     }
 
+    /// Serialize list of rate indexes.
+    ///
+    /// # Arguments
+    ///
+    /// * `rate_indexes` - List of rate indexes to serialize.
+    /// * `buf` - Buffer to append serialization.
+    /// * `add_comma` - Append comma on last line of output.
+    fn serialize_rate_indexes(
+        &self,
+        rate_indexes: &ListRateIndex,
+        buf: &mut String,
+        add_comma: bool,
+    ) {
+        buf.push_str(self.indent().as_str());
+        buf.push_str("\"rate-indexes\": [");
+        buf.push_str(crate::LINE_ENDING);
+        self.increment_depth();
+
+        let orig_index = rate_indexes.index();
+        let mut index: usize = 0;
+        while rate_indexes.get_element(index) {
+            let rate_index = rate_indexes.rate_index();
+
+            buf.push_str(self.indent().as_str());
+            buf.push('{');
+            buf.push_str(crate::LINE_ENDING);
+            self.increment_depth();
+
+            buf.push_str(self.indent().as_str());
+            buf.push_str("\"name\": \"");
+            buf.push_str(rate_index.name());
+            buf.push_str("\",");
+            buf.push_str(crate::LINE_ENDING);
+
+            buf.push_str(self.indent().as_str());
+            buf.push_str("\"values\": [");
+            buf.push_str(crate::LINE_ENDING);
+            self.increment_depth();
+
+            let values = rate_index.values();
+            for (value_index, value) in values.iter().enumerate() {
+                buf.push_str(self.indent().as_str());
+                buf.push_str(
+                    format!(
+                        "{{ \"date\": \"{}\", \"rate\": \"{}\" }}",
+                        self.get_date_str(value.date()),
+                        value.amount()
+                    )
+                    .as_str(),
+                );
+                if value_index + 1 < values.len() {
+                    buf.push(',');
+                }
+                buf.push_str(crate::LINE_ENDING);
+            }
+
+            self.decrement_depth();
+            buf.push_str(self.indent().as_str());
+            buf.push(']');
+            buf.push_str(crate::LINE_ENDING);
+
+            index += 1;
+
+            self.decrement_depth();
+            buf.push_str(self.indent().as_str());
+            buf.push('}');
+            if index < rate_indexes.count() {
+                buf.push(',');
+            }
+            buf.push_str(crate::LINE_ENDING);
+        }
+
+        rate_indexes.get_element(orig_index);
+
+        self.decrement_depth();
+        buf.push_str(self.indent().as_str());
+        buf.push(']');
+        if add_comma {
+            buf.push(',');
+        }
+        buf.push_str(crate::LINE_ENDING);
+    }
+
     /// Serialize statistic value element.
     ///
     /// # Arguments
@@ -1536,12 +2189,14 @@ impl CalcJsonSerialize {
     ///
     /// # Arguments
     ///
+    /// * `calc_mgr` - Calculation manager.
     /// * `template_events` - List of template events to serialize.
     /// * `buf` - Buffer to append serialization.
     /// * `add_comma` - Append comma on last line of output.
 
     fn serialize_template_events(
         &self,
+        calc_mgr: &CalcManager,
         template_events: &ListTemplateEvent,
         buf: &mut String,
         add_comma: bool,
@@ -1561,7 +2216,7 @@ impl CalcJsonSerialize {
                 buf.push_str(crate::LINE_ENDING);
                 self.increment_depth();
 
-                self.serialize_event_list(template_events.list_event(), buf, true);
+                self.serialize_event_list(calc_mgr, template_events.list_event(), buf, true);
                 buf.push_str(self.indent().as_str());
                 buf.push_str("\"name\": \"");
                 buf.push_str(template_events.name());
@@ -1592,12 +2247,14 @@ impl CalcJsonSerialize {
     ///
     /// # Arguments
     ///
+    /// * `calc_mgr` - Calculation manager.
     /// * `template_groups` - List of template groups to serialize.
     /// * `buf` - Buffer to append serialization.
     /// * `add_comma` - Append comma on last line of output.
 
     fn serialize_template_groups(
         &self,
+        calc_mgr: &CalcManager,
         template_groups: &ListTemplateGroup,
         buf: &mut String,
         add_comma: bool,
@@ -1624,7 +2281,12 @@ impl CalcJsonSerialize {
 
                 self.serialize_preferences(template_groups.preferences(), buf, true);
 
-                self.serialize_template_events(template_groups.list_template_event(), buf, false);
+                self.serialize_template_events(
+                    calc_mgr,
+                    template_groups.list_template_event(),
+                    buf,
+                    false,
+                );
                 index += 1;
                 deserialize_list = template_groups.get_element(index);
 
@@ -1663,11 +2325,12 @@ impl CalcJsonSerialize {
     /// Decrement the indentation depth.
 
     fn decrement_depth(&self) {
-        if self.depth.get() == 0 {
+        if self.depth.load(Ordering::Relaxed) == 0 {
             return;
         }
 
-        self.depth.set(self.depth.get() - 1);
+        self.depth
+            .store(self.depth.load(Ordering::Relaxed) - 1, Ordering::Relaxed);
     }
 
     /// Serialize a boolean value.
@@ -1698,13 +2361,8 @@ impl CalcJsonSerialize {
     ///
     /// * See description.
 
-    fn get_date_str(&self, event_date: usize) -> String {
-        return format!(
-            "{:04}-{:02}-{:02}",
-            event_date / 10000,
-            event_date / 100 % 100,
-            event_date % 100
-        );
+    fn get_date_str(&self, event_date: NaiveDate) -> String {
+        event_date.format("%Y-%m-%d").to_string()
     }
 
     /// Serialize a polarity value.
@@ -1728,7 +2386,8 @@ impl CalcJsonSerialize {
     /// Increment the indentation depth.
 
     fn increment_depth(&self) {
-        self.depth.set(self.depth.get() + 1);
+        self.depth
+            .store(self.depth.load(Ordering::Relaxed) + 1, Ordering::Relaxed);
     }
 
     /// Return a string containing spaces relative to the indentation depth.
@@ -1738,6 +2397,6 @@ impl CalcJsonSerialize {
     /// * See description.
 
     fn indent(&self) -> String {
-        " ".repeat(self.depth.get() * crate::TAB_SPACES)
+        " ".repeat(self.depth.load(Ordering::Relaxed) * crate::TAB_SPACES)
     }
 }
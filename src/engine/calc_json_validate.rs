@@ -0,0 +1,424 @@
+//! The validate json element of the AmFn engine.
+// Copyright (c) 2021 ShiftLeft Software
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use json::JsonValue;
+use regex::Regex;
+
+use super::ElemSchemaViolation;
+
+/// The AmFn Json schema shipped with the engine.
+const AMFN_SCHEMA: &str = include_str!("../../amfn.schema.json");
+
+/// The validation element shared by every deserialization.
+static SHARED_VALIDATE: OnceLock<CalcJsonValidate> = OnceLock::new();
+
+pub struct CalcJsonValidate {
+    /// Parsed AmFn Json schema.
+    schema: JsonValue,
+    /// Compiled regular expressions of the schema patterns.
+    patterns: HashMap<String, Regex>,
+}
+
+/// The validate json default implementation.
+impl Default for CalcJsonValidate {
+    /// Create and return a new validation element.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn default() -> Self {
+        CalcJsonValidate::new()
+    }
+}
+
+/// The validate json implementation of the AmFn engine.
+/// Validates documents against the subset of Json schema
+/// (draft-04) used by amfn.schema.json: $ref, type, enum,
+/// pattern, oneOf, properties, required, additionalProperties
+/// and items. String enum values are compared ignoring case
+/// (as the deserializer does).
+impl CalcJsonValidate {
+    /// Create and return a new validation element.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn new() -> CalcJsonValidate {
+        let schema = match json::parse(AMFN_SCHEMA) {
+            Err(e) => {
+                panic!("Invalid AmFn schema: {:?}", e);
+            }
+            Ok(o) => o,
+        };
+
+        let mut patterns: HashMap<String, Regex> = HashMap::new();
+        for (_key, definition) in schema["definitions"].entries() {
+            if let Some(o) = definition["pattern"].as_str() {
+                match Regex::new(o) {
+                    Err(e) => {
+                        panic!("Invalid AmFn schema pattern: {:?}", e);
+                    }
+                    Ok(o2) => {
+                        patterns.insert(String::from(o), o2);
+                    }
+                }
+            }
+        }
+
+        CalcJsonValidate { schema, patterns }
+    }
+
+    /// Returns the validation element shared by every caller.
+    /// The schema is parsed and its patterns are compiled once.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn shared() -> &'static CalcJsonValidate {
+        SHARED_VALIDATE.get_or_init(CalcJsonValidate::new)
+    }
+
+    /// Validate serialized Json against the AmFn schema.
+    ///
+    /// # Arguments
+    ///
+    /// * `input_param` - Input containing serialized Json.
+    ///
+    /// # Return
+    ///
+    /// * All schema violations (empty if the input is valid).
+    pub fn validate(&self, input_param: &str) -> Vec<ElemSchemaViolation> {
+        match json::parse(input_param) {
+            Err(e) => vec![ElemSchemaViolation::new("", e.to_string().as_str())],
+            Ok(o) => self.validate_value(&o),
+        }
+    }
+
+    /// Validate a parsed Json document against the AmFn schema.
+    ///
+    /// # Arguments
+    ///
+    /// * `data_param` - Parsed Json document.
+    ///
+    /// # Return
+    ///
+    /// * All schema violations (empty if the document is valid).
+    pub fn validate_value(&self, data_param: &JsonValue) -> Vec<ElemSchemaViolation> {
+        let mut violations: Vec<ElemSchemaViolation> = Vec::new();
+
+        self.validate_schema(&self.schema, data_param, "", &mut violations);
+
+        violations
+    }
+
+    /// Resolve a local schema reference (i.e., "#/definitions/name").
+    ///
+    /// # Arguments
+    ///
+    /// * `reference_param` - Schema reference.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn resolve(&self, reference_param: &str) -> &JsonValue {
+        let mut schema = &self.schema;
+
+        for token in reference_param.trim_start_matches('#').split('/') {
+            if !token.is_empty() {
+                schema = &schema[token];
+            }
+        }
+
+        schema
+    }
+
+    /// Returns the description of a schema used in messages.
+    ///
+    /// # Arguments
+    ///
+    /// * `schema_param` - Schema (or schema reference).
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn schema_name(schema_param: &JsonValue) -> String {
+        match schema_param["$ref"].as_str() {
+            None => String::from("schema"),
+            Some(o) => String::from(o.rsplit('/').next().unwrap_or(o)),
+        }
+    }
+
+    /// Returns the Json type name of a value.
+    ///
+    /// # Arguments
+    ///
+    /// * `value_param` - Json value.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn type_name(value_param: &JsonValue) -> &'static str {
+        if value_param.is_object() {
+            "object"
+        } else if value_param.is_array() {
+            "array"
+        } else if value_param.is_string() {
+            "string"
+        } else if value_param.is_number() {
+            "number"
+        } else if value_param.is_boolean() {
+            "boolean"
+        } else {
+            "null"
+        }
+    }
+
+    /// Returns true if a value matches an enum member. String
+    /// values are compared ignoring case.
+    ///
+    /// # Arguments
+    ///
+    /// * `member_param` - Enum member.
+    /// * `value_param` - Json value.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn enum_matches(member_param: &JsonValue, value_param: &JsonValue) -> bool {
+        match (member_param.as_str(), value_param.as_str()) {
+            (Some(o), Some(o2)) => o.eq_ignore_ascii_case(o2),
+            _ => member_param == value_param,
+        }
+    }
+
+    /// Escape a property name as a JSON pointer token.
+    ///
+    /// # Arguments
+    ///
+    /// * `name_param` - Property name.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn pointer_token(name_param: &str) -> String {
+        name_param.replace('~', "~0").replace('/', "~1")
+    }
+
+    /// Validate a value against a schema, collecting violations.
+    ///
+    /// # Arguments
+    ///
+    /// * `schema_param` - Schema to validate against.
+    /// * `value_param` - Json value to validate.
+    /// * `pointer_param` - JSON pointer of the value.
+    /// * `violations_param` - Collected violations.
+    fn validate_schema(
+        &self,
+        schema_param: &JsonValue,
+        value_param: &JsonValue,
+        pointer_param: &str,
+        violations_param: &mut Vec<ElemSchemaViolation>,
+    ) {
+        if let Some(o) = schema_param["$ref"].as_str() {
+            self.validate_schema(
+                self.resolve(o),
+                value_param,
+                pointer_param,
+                violations_param,
+            );
+            return;
+        }
+
+        if let Some(o) = schema_param["type"].as_str() {
+            let type_name = CalcJsonValidate::type_name(value_param);
+            if o != type_name {
+                violations_param.push(ElemSchemaViolation::new(
+                    pointer_param,
+                    format!("expected {} but found {}", o, type_name).as_str(),
+                ));
+                return;
+            }
+        }
+
+        if schema_param["enum"].is_array()
+            && !schema_param["enum"]
+                .members()
+                .any(|o| CalcJsonValidate::enum_matches(o, value_param))
+        {
+            let allowed: Vec<String> = schema_param["enum"].members().map(|o| o.dump()).collect();
+            violations_param.push(ElemSchemaViolation::new(
+                pointer_param,
+                format!(
+                    "{} is not one of {}",
+                    value_param.dump(),
+                    allowed.join(", ")
+                )
+                .as_str(),
+            ));
+        }
+
+        if let (Some(o), Some(o2)) = (schema_param["pattern"].as_str(), value_param.as_str()) {
+            if let Some(o3) = self.patterns.get(o) {
+                if !o3.is_match(o2) {
+                    violations_param.push(ElemSchemaViolation::new(
+                        pointer_param,
+                        format!("\"{}\" does not match the pattern \"{}\"", o2, o).as_str(),
+                    ));
+                }
+            }
+        }
+
+        if schema_param["oneOf"].is_array() {
+            self.validate_one_of(
+                &schema_param["oneOf"],
+                value_param,
+                pointer_param,
+                violations_param,
+            );
+        }
+
+        if value_param.is_object() {
+            self.validate_object(schema_param, value_param, pointer_param, violations_param);
+        }
+
+        if value_param.is_array() && !schema_param["items"].is_null() {
+            for (index, item) in value_param.members().enumerate() {
+                self.validate_schema(
+                    &schema_param["items"],
+                    item,
+                    format!("{}/{}", pointer_param, index).as_str(),
+                    violations_param,
+                );
+            }
+        }
+    }
+
+    /// Validate an object against the properties, required and
+    /// additionalProperties rules of a schema.
+    ///
+    /// # Arguments
+    ///
+    /// * `schema_param` - Schema to validate against.
+    /// * `value_param` - Json object to validate.
+    /// * `pointer_param` - JSON pointer of the object.
+    /// * `violations_param` - Collected violations.
+    fn validate_object(
+        &self,
+        schema_param: &JsonValue,
+        value_param: &JsonValue,
+        pointer_param: &str,
+        violations_param: &mut Vec<ElemSchemaViolation>,
+    ) {
+        for required in schema_param["required"].members() {
+            if let Some(o) = required.as_str() {
+                if !value_param.has_key(o) {
+                    violations_param.push(ElemSchemaViolation::new(
+                        pointer_param,
+                        format!("missing required property \"{}\"", o).as_str(),
+                    ));
+                }
+            }
+        }
+
+        let additional = schema_param["additionalProperties"] != false;
+
+        for (key, value) in value_param.entries() {
+            let pointer = format!("{}/{}", pointer_param, CalcJsonValidate::pointer_token(key));
+
+            if schema_param["properties"].has_key(key) {
+                self.validate_schema(
+                    &schema_param["properties"][key],
+                    value,
+                    pointer.as_str(),
+                    violations_param,
+                );
+            } else if !additional {
+                violations_param.push(ElemSchemaViolation::new(
+                    pointer.as_str(),
+                    format!("unexpected property \"{}\"", key).as_str(),
+                ));
+            }
+        }
+    }
+
+    /// Validate a value against exactly one of several schemas.
+    /// When no schema matches, the violations of the closest schema
+    /// (the one whose first violation is nested the deepest) are
+    /// reported; if none is closer than the others, a single
+    /// violation listing the allowed shapes is reported.
+    ///
+    /// # Arguments
+    ///
+    /// * `schemas_param` - Array of alternative schemas.
+    /// * `value_param` - Json value to validate.
+    /// * `pointer_param` - JSON pointer of the value.
+    /// * `violations_param` - Collected violations.
+    fn validate_one_of(
+        &self,
+        schemas_param: &JsonValue,
+        value_param: &JsonValue,
+        pointer_param: &str,
+        violations_param: &mut Vec<ElemSchemaViolation>,
+    ) {
+        let mut matches: usize = 0;
+        let mut closest: Vec<ElemSchemaViolation> = Vec::new();
+        let mut closest_depth: usize = 0;
+        let mut closest_count: usize = 0;
+
+        for schema in schemas_param.members() {
+            let mut violations: Vec<ElemSchemaViolation> = Vec::new();
+            self.validate_schema(schema, value_param, pointer_param, &mut violations);
+
+            if violations.is_empty() {
+                matches += 1;
+                continue;
+            }
+
+            let depth = violations
+                .iter()
+                .map(|o| o.pointer().matches('/').count())
+                .min()
+                .unwrap_or(0);
+
+            if depth > closest_depth || closest_count == 0 {
+                closest_depth = depth;
+                closest_count = 1;
+                closest = violations;
+            } else if depth == closest_depth {
+                closest_count += 1;
+            }
+        }
+
+        if matches == 1 {
+            return;
+        }
+
+        let names: Vec<String> = schemas_param
+            .members()
+            .map(CalcJsonValidate::schema_name)
+            .collect();
+
+        if matches > 1 {
+            violations_param.push(ElemSchemaViolation::new(
+                pointer_param,
+                format!("matches more than one of {}", names.join(", ")).as_str(),
+            ));
+        } else if closest_count == 1 && closest_depth > pointer_param.matches('/').count() {
+            violations_param.append(&mut closest);
+        } else {
+            violations_param.push(ElemSchemaViolation::new(
+                pointer_param,
+                format!("expected exactly one of {}", names.join(", ")).as_str(),
+            ));
+        }
+    }
+}
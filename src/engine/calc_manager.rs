@@ -7,17 +7,26 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-use std::cell::{Cell, RefCell};
-use std::rc::Rc;
-
+use chrono::NaiveDate;
 use rust_decimal::prelude::*;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
 
 use super::{
-    CalcExpression, ElemPreferences, ListCashflow, ListExchangeRate, ListLocale, ListTemplateGroup,
+    CalcExpression, ElemPreferences, ListCalendar, ListCashflow, ListExchangeRate, ListLocale,
+    ListRateIndex, ListTemplateGroup,
+};
+use crate::core::{
+    CoreManager, CoreUtility, ElemError, ElemExprLimits, ElemExprNode, ElemSolverSettings,
+    ElemSymbol, ListDescriptor, ListEvent, MapExpression,
 };
-use crate::core::{CoreManager, CoreUtility, ElemSymbol, ListDescriptor, ListEvent};
 use crate::ListTrait;
 
+/// A native function registered by the host and called from expressions.
+/// The function receives the evaluated arguments and returns the result.
+pub type NativeFunction = Arc<dyn Fn(&[ElemSymbol]) -> Result<ElemSymbol, ElemError> + Send + Sync>;
+
 pub struct CalcManager {
     /// Core manager element.
     core_manager: CoreManager,
@@ -34,8 +43,23 @@ pub struct CalcManager {
     /// List of exchange rates.
     list_exchange_rate: ListExchangeRate,
 
+    /// List of business day calendars.
+    list_calendar: ListCalendar,
+
+    /// List of rate indexes.
+    list_rate_index: ListRateIndex,
+
+    /// Compiled expression cache.
+    map_expression: RwLock<MapExpression>,
+
+    /// Native functions registered by the host.
+    map_native_function: RwLock<HashMap<String, NativeFunction>>,
+
+    /// Limits of expressions set by the host.
+    expr_limits: ElemExprLimits,
+
     /// Currently updating while loading a JSON source.
-    updating_json: Cell<bool>,
+    updating_json: AtomicBool,
 }
 
 /// The manager implementation of the AmFn engine component.
@@ -59,19 +83,19 @@ impl CalcManager {
             list_cashflow: None,
             list_template_group: None,
             list_exchange_rate: ListExchangeRate::new(),
-            updating_json: Cell::new(false),
+            list_calendar: ListCalendar::new(),
+            list_rate_index: ListRateIndex::new(),
+            map_expression: RwLock::new(MapExpression::new()),
+            map_native_function: RwLock::new(HashMap::new()),
+            expr_limits: ElemExprLimits::new(),
+            updating_json: AtomicBool::new(false),
         }
     }
 
     /// Initialize the calc manager element.
-    ///
-    /// # Arguments
-    ///
-    /// * `calc_manager` - Calculation manager.
 
-    pub fn init_calc_manager(&mut self, calc_manager: &Rc<RefCell<CalcManager>>) {
+    pub fn init_calc_manager(&mut self) {
         self.elem_preferences = Option::from(ElemPreferences::new(
-            calc_manager,
             "",
             "",
             "",
@@ -88,9 +112,9 @@ impl CalcManager {
             false,
         ));
 
-        self.list_cashflow = Option::from(ListCashflow::new(calc_manager));
+        self.list_cashflow = Option::from(ListCashflow::new());
 
-        self.list_template_group = Option::from(ListTemplateGroup::new(calc_manager));
+        self.list_template_group = Option::from(ListTemplateGroup::new());
     }
 
     /// Get the core manager.
@@ -103,6 +127,99 @@ impl CalcManager {
         &self.core_manager
     }
 
+    /// Find and return a compiled expression from the
+    /// compiled expression cache.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The expression text.
+    ///
+    /// # Return
+    ///
+    /// * The compiled expression, otherwise None.
+    pub fn compiled_expression(&self, text: &str) -> Option<Arc<ElemExprNode>> {
+        self.map_expression.read().unwrap().get_expression(text)
+    }
+
+    /// Add a compiled expression to the compiled expression cache.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The expression text.
+    /// * `elem_expr_node` - The compiled expression.
+    pub fn add_compiled_expression(&self, text: &str, elem_expr_node: Arc<ElemExprNode>) {
+        self.map_expression
+            .write()
+            .unwrap()
+            .add_expression(text, elem_expr_node);
+    }
+
+    /// Find and return a native function registered by the host.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The function name.
+    ///
+    /// # Return
+    ///
+    /// * The native function, otherwise None.
+    pub fn native_function(&self, name: &str) -> Option<NativeFunction> {
+        self.map_native_function
+            .read()
+            .unwrap()
+            .get(name)
+            .map(Arc::clone)
+    }
+
+    /// Register a native function that can be called from expressions.
+    /// A previously registered function with the same name is replaced.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The function name.
+    /// * `native_function` - The native function.
+    pub fn register_function(&self, name: &str, native_function: NativeFunction) {
+        self.map_native_function
+            .write()
+            .unwrap()
+            .insert(String::from(name), native_function);
+    }
+
+    /// Remove a native function registered by the host.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The function name.
+    ///
+    /// # Return
+    ///
+    /// * True if the function was registered, otherwise false.
+    pub fn unregister_function(&self, name: &str) -> bool {
+        self.map_native_function
+            .write()
+            .unwrap()
+            .remove(name)
+            .is_some()
+    }
+
+    /// Get the limits of expressions.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn expr_limits(&self) -> ElemExprLimits {
+        self.expr_limits
+    }
+
+    /// Set the limits of expressions.
+    ///
+    /// # Arguments
+    ///
+    /// * `expr_limits_param` - See description.
+    pub fn set_expr_limits(&mut self, expr_limits_param: ElemExprLimits) {
+        self.expr_limits = expr_limits_param;
+    }
+
     /// Clear all engine elements and lists.
 
     pub fn clear(&mut self) {
@@ -110,6 +227,9 @@ impl CalcManager {
         self.list_template_group_mut().clear();
         self.list_cashflow_mut().clear();
         self.list_exchange_rate_mut().clear();
+        self.list_calendar_mut().clear();
+        self.list_rate_index_mut().clear();
+        self.map_expression.write().unwrap().clear();
     }
 
     /// Copies the event list from the currently selected template event into
@@ -117,7 +237,6 @@ impl CalcManager {
     ///
     /// # Arguments
     ///
-    /// * `calc_manager` - Calculation manager.
     /// * `date_param` - Base starting date for the new event(s).
     /// * `end_date_param` - Base ending date for the new event(s).
     /// * `new_date_param` - Next date for the new event(s) (i.e.,
@@ -130,12 +249,11 @@ impl CalcManager {
 
     pub fn copy_template_events(
         &self,
-        calc_manager: &Rc<RefCell<CalcManager>>,
-        date_param: usize,
-        end_date_param: usize,
-        new_date_param: usize,
+        date_param: NaiveDate,
+        end_date_param: NaiveDate,
+        new_date_param: NaiveDate,
         frequency_param: crate::FrequencyType,
-    ) -> Result<ListEvent, crate::ErrorType> {
+    ) -> Result<ListEvent, ElemError> {
         let updating_json = self.updating_json();
         let fiscal_year_start = self.fiscal_year_start(false);
         let decimal_digits = self.decimal_digits(false);
@@ -144,7 +262,7 @@ impl CalcManager {
             .list_template_event()
             .list_event();
         if list_template_event_list_event.count() == 0 {
-            return Err(crate::ErrorType::Element);
+            return Err(ElemError::new(crate::ErrorType::Element));
         }
 
         let mut new_list_event = ListEvent::new(true);
@@ -177,17 +295,23 @@ impl CalcManager {
 
             if !new_list_event.date_expr().is_empty() {
                 let mut calc_expression =
-                    CalcExpression::new(calc_manager, fiscal_year_start, decimal_digits);
+                    CalcExpression::new(self, fiscal_year_start, decimal_digits);
 
+                calc_expression.init_list_function(Option::from(
+                    self.list_template_group().preferences().list_function(),
+                ));
                 calc_expression.init_expression(
                     None,
                     None,
                     list_template_event_list_event.list_parameter(),
                     new_list_event.date_expr(),
                 );
-                calc_expression.set_symbol_integer("intDate", event_date);
-                calc_expression.set_symbol_integer("intEndDate", end_date);
-                calc_expression.set_symbol_integer("intNewDate", new_date);
+                calc_expression
+                    .set_symbol_integer("intDate", CoreUtility::date_to_integer(event_date));
+                calc_expression
+                    .set_symbol_integer("intEndDate", CoreUtility::date_to_integer(end_date));
+                calc_expression
+                    .set_symbol_integer("intNewDate", CoreUtility::date_to_integer(new_date));
                 calc_expression.set_symbol_integer("intIntervals", intervals);
                 calc_expression.set_symbol_string(
                     "strFrequency",
@@ -203,28 +327,34 @@ impl CalcManager {
                     Ok(o) => o,
                 };
 
+                let mut date_val: usize = CoreUtility::date_to_integer(new_date);
                 match elem_result_symbol.sym_type() {
                     crate::TokenType::Integer => {
-                        new_date = elem_result_symbol.sym_integer();
+                        date_val = elem_result_symbol.sym_integer();
                     }
                     crate::TokenType::Decimal => {
                         match elem_result_symbol.sym_decimal().to_usize() {
                             None => {
-                                new_date = 0;
+                                date_val = 0;
                             }
                             Some(o) => {
-                                new_date = o;
+                                date_val = o;
                             }
                         }
                     }
                     crate::TokenType::String => {
-                        new_date = CoreUtility::parse_integer(elem_result_symbol.sym_string());
+                        date_val = CoreUtility::parse_integer(elem_result_symbol.sym_string());
                     }
                     _ => {}
                 }
-                if new_date == 0 {
-                    list_template_event_list_event.get_element(orig_index);
-                    return Err(crate::ErrorType::Date);
+                match CoreUtility::integer_to_date(date_val) {
+                    None => {
+                        list_template_event_list_event.get_element(orig_index);
+                        return Err(ElemError::new(crate::ErrorType::Date));
+                    }
+                    Some(o) => {
+                        new_date = o;
+                    }
                 }
             }
             new_list_event.set_date_result(new_date);
@@ -452,6 +582,23 @@ impl CalcManager {
         }
     }
 
+    /// Get the resolved solver settings.
+    /// The cashflow and user preferences are
+    /// searched in that order for each setting.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn solver_settings(&self) -> ElemSolverSettings {
+        let cashflow_preferences = self.list_cashflow().preferences();
+        match cashflow_preferences {
+            None => *self.preferences().solver_settings(),
+            Some(o) => o
+                .solver_settings()
+                .resolve(self.preferences().solver_settings()),
+        }
+    }
+
     /// Searches the various descriptor lists, from lowest
     /// to highest, and returns the constant value for a
     /// group, name, type, and code.
@@ -669,6 +816,42 @@ impl CalcManager {
         &mut self.list_exchange_rate
     }
 
+    /// Get the list of business day calendars.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn list_calendar(&self) -> &ListCalendar {
+        &self.list_calendar
+    }
+
+    /// Get the mutable list of business day calendars.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn list_calendar_mut(&mut self) -> &mut ListCalendar {
+        &mut self.list_calendar
+    }
+
+    /// Get the list of rate indexes.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn list_rate_index(&self) -> &ListRateIndex {
+        &self.list_rate_index
+    }
+
+    /// Get the mutable list of rate indexes.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn list_rate_index_mut(&mut self) -> &mut ListRateIndex {
+        &mut self.list_rate_index
+    }
+
     /// Get the error text corresponding to an error value.
     ///
     /// # Arguments
@@ -680,18 +863,10 @@ impl CalcManager {
     /// * See description.
     pub fn get_error_string(&self, error: crate::ErrorType) -> String {
         let error_index = error as usize;
-        if !self
-            .core_manager
-            .map_error()
-            .get_element_by_value(error_index)
-        {
-            return format!("{}{}", crate::ERROR_PREFIX, error_index);
+        match self.core_manager.map_error().get_key_by_value(error_index) {
+            None => format!("{}{}", crate::ERROR_PREFIX, error_index),
+            Some(o) => String::from(self.list_locale.get_resource(o.key())),
         }
-
-        let key = self.core_manager.map_error().key();
-        let fs = String::from(self.list_locale.get_resource(key));
-
-        fs
     }
 
     /// Get the resolved locale.
@@ -725,6 +900,53 @@ impl CalcManager {
         String::from(self.list_locale.get_locale_str(false))
     }
 
+    /// Select a cashflow and its locale.
+    ///
+    /// # Arguments
+    ///
+    /// * `index_param` - Index of the cashflow to select.
+    ///
+    /// # Return
+    ///
+    /// * True if successful, otherwise false.
+    pub fn select_cashflow(&self, index_param: usize) -> bool {
+        if !self.list_cashflow().get_element(index_param) {
+            return false;
+        }
+
+        self.select_cashflow_locale();
+        true
+    }
+
+    /// Select a cashflow by name and its locale.
+    ///
+    /// # Arguments
+    ///
+    /// * `name_param` - Name of the cashflow to select.
+    ///
+    /// # Return
+    ///
+    /// * True if successful, otherwise false.
+    pub fn select_cashflow_by_name(&self, name_param: &str) -> bool {
+        if !self.list_cashflow().get_element_by_name(name_param, true) {
+            return false;
+        }
+
+        self.select_cashflow_locale();
+        true
+    }
+
+    /// Select the locale of the selected cashflow as the
+    /// cashflow locale (does nothing if no cashflow is selected).
+    pub fn select_cashflow_locale(&self) {
+        if self.list_cashflow().index() >= self.list_cashflow().count() {
+            return;
+        }
+
+        self.list_locale
+            .select_cashflow_locale(self.locale(true).as_str());
+    }
+
     /// Get the statistic events from the compressed
     /// event list.
     /// The cashflow and user preferences are
@@ -764,7 +986,7 @@ impl CalcManager {
     /// * See description.
 
     pub fn updating_json(&self) -> bool {
-        self.updating_json.get()
+        self.updating_json.load(Ordering::Relaxed)
     }
 
     /// Set the list locale.
@@ -817,6 +1039,24 @@ impl CalcManager {
         self.list_exchange_rate = list_exchange_rate;
     }
 
+    /// Set the list of business day calendars.
+    ///
+    /// # Arguments
+    ///
+    /// * `list_calendar` - See description.
+    pub fn set_list_calendar(&mut self, list_calendar: ListCalendar) {
+        self.list_calendar = list_calendar;
+    }
+
+    /// Set the list of rate indexes.
+    ///
+    /// # Arguments
+    ///
+    /// * `list_rate_index` - See description.
+    pub fn set_list_rate_index(&mut self, list_rate_index: ListRateIndex) {
+        self.list_rate_index = list_rate_index;
+    }
+
     /// Set the updating json.
     ///
     /// # Arguments
@@ -824,7 +1064,7 @@ impl CalcManager {
     /// * `value_param` - See description.
 
     pub fn set_updating_json(&self, value_param: bool) {
-        self.updating_json.set(value_param);
+        self.updating_json.store(value_param, Ordering::Relaxed);
     }
 
     /// Get the column name resource key.
@@ -848,8 +1088,8 @@ impl CalcManager {
     ///
     /// # Arguments
     ///
-    /// * `date1` - First date in YYYYMMDD format.
-    /// * `date2` - Second date in YYYYMMDD format.
+    /// * `date1` - First date.
+    /// * `date2` - Second date.
     /// * `frequency` - Date frequency.
     /// * `intervals` - Number of intervals of frequency.
     /// * `eom` - Adjust successive dates to end of month.
@@ -859,8 +1099,8 @@ impl CalcManager {
     /// * Number of intervals (positive or negative).
 
     pub fn util_date_diff(
-        date1: usize,
-        date2: usize,
+        date1: NaiveDate,
+        date2: NaiveDate,
         frequency: crate::FrequencyType,
         intervals: usize,
         eom: bool,
@@ -881,7 +1121,7 @@ impl CalcManager {
     ///
     /// # Arguments
     ///
-    /// * `event_date` - Date in YYYYMMDD format.
+    /// * `event_date` - The date.
     /// * `periods` - Number of periods.
     /// * `frequency` - Date frequency.
     /// * `intervals` - Number of intervals of frequency.
@@ -889,15 +1129,14 @@ impl CalcManager {
     ///
     /// # Return
     ///
-    /// * New date in YYYYMMDD format.
-
+    /// * New date.
     pub fn util_date_newi(
-        event_date: usize,
+        event_date: NaiveDate,
         mut periods: usize,
         frequency: crate::FrequencyType,
         mut intervals: i32,
         eom: bool,
-    ) -> usize {
+    ) -> NaiveDate {
         let mut new_date = event_date;
         let orig_date = event_date;
         periods = if periods > 0 { periods } else { 1 };
@@ -913,7 +1152,7 @@ impl CalcManager {
     ///
     /// # Arguments
     ///
-    /// * `event_date` - Date in YYYYMMDD format.
+    /// * `event_date` - The date.
     /// * `periods` - Number of periods.
     /// * `frequency` - Date frequency.
     /// * `intervals` - Number of intervals of frequency.
@@ -921,25 +1160,23 @@ impl CalcManager {
     ///
     /// # Return
     ///
-    /// * New date in YYYYMMDD format.
-
+    /// * New date.
     pub fn util_date_new(
-        event_date: usize,
+        event_date: NaiveDate,
         periods: usize,
         frequency: crate::FrequencyType,
         intervals: usize,
         eom: bool,
-    ) -> usize {
+    ) -> NaiveDate {
         CalcManager::util_date_newi(event_date, periods, frequency, intervals as i32, eom)
     }
 
-    /// Returns the current date in YYYYMMDD format.
+    /// Returns the current date.
     ///
     /// # Return
     ///
-    /// * Current date in YYYYMMDD format.
-
-    pub fn util_date_now() -> usize {
+    /// * See description.
+    pub fn util_date_now() -> NaiveDate {
         CoreUtility::date_now()
     }
 
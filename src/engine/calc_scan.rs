@@ -69,6 +69,24 @@ impl CalcScan {
         self.token.as_str()
     }
 
+    /// Get the text of the expression being scanned.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn get_expression(&self) -> String {
+        self.expr.iter().collect()
+    }
+
+    /// Get the position (starting from 0) of the last token scanned.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn get_position(&self) -> usize {
+        self.last_index
+    }
+
     /// Initialize the instance variables with a new expression.
     ///
     /// # Arguments
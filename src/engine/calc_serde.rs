@@ -0,0 +1,2458 @@
+//! The serde support of the AmFn engine.
+// Copyright (c) 2021 ShiftLeft Software
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use chrono::NaiveDate;
+use rust_decimal::prelude::*;
+use std::marker::PhantomData;
+use std::sync::{Arc, RwLock};
+
+use serde::de::{self, DeserializeSeed, Deserializer};
+use serde::ser::Serializer;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    CalcManager, ElemExchangeRate, ElemPreferences, ElemTemplateGroup, ListCashflow,
+    ListTemplateEvent,
+};
+use crate::core::{
+    CoreUtility, ElemAmortization, ElemBalanceResult, ElemCurrentValue, ElemError, ElemEvent,
+    ElemExtension, ElemInterestChange, ElemPrincipalChange, ElemStatisticValue, ExtensionValue,
+    ListAmortization, ListDescriptor, ListEvent, ListFunction, ListParameter,
+};
+use crate::{ExtensionTrait, ListTrait};
+
+/// Seed used to deserialize the elements that require a
+/// calculation manager (i.e., ElemPreferences, ElemTemplateGroup
+/// and ListCashflow).
+pub struct CalcSeed<'a, T> {
+    /// Calculator manager element.
+    calc_manager: &'a Arc<RwLock<CalcManager>>,
+    /// Type of the element created by the seed.
+    elem_type: PhantomData<T>,
+}
+
+/// The calculation seed implementation.
+impl<'a, T> CalcSeed<'a, T> {
+    /// Create and return a new calculation seed.
+    ///
+    /// # Arguments
+    ///
+    /// * `calc_manager_param` - Calculation manager.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn new(calc_manager_param: &'a Arc<RwLock<CalcManager>>) -> CalcSeed<'a, T> {
+        CalcSeed {
+            calc_manager: calc_manager_param,
+            elem_type: PhantomData,
+        }
+    }
+
+    /// Set the updating Json flag of the calculation manager.
+    ///
+    /// # Arguments
+    ///
+    /// * `updating_json_param` - See description.
+    fn set_updating_json(&self, updating_json_param: bool) {
+        self.calc_manager
+            .read()
+            .unwrap()
+            .set_updating_json(updating_json_param);
+    }
+}
+
+/// Serde shape of a parameter.
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct ParameterData {
+    name: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    label: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    description: String,
+    parameter_type: String,
+    value: String,
+}
+
+/// Serde shape of a user-defined function.
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct FunctionData {
+    name: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    description: String,
+    #[serde(default)]
+    parameters: Vec<String>,
+    expression: String,
+}
+
+/// Serde shape of a descriptor.
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct DescriptorData {
+    group: String,
+    name: String,
+    descriptor_type: String,
+    descriptor_code: String,
+    value: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    expression: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    event_index: Option<usize>,
+    #[serde(default)]
+    propagate: bool,
+}
+
+/// Serde shape of an event date.
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct EventDateData {
+    date: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    expression: String,
+}
+
+/// Serde shape of an event value.
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct EventValueData {
+    value: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    expression: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    expr_balance: Option<bool>,
+}
+
+/// Serde shape of event periods.
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct EventPeriodsData {
+    periods: usize,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    expression: String,
+}
+
+/// Serde shape of a current value.
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct CurrentValueData {
+    #[serde(default)]
+    passive: bool,
+    #[serde(default)]
+    present: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    business_day: Option<String>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    calendar: String,
+    #[serde(default)]
+    eom: bool,
+}
+
+/// Serde shape of an interest change.
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct InterestChangeData {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    interest_method: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    day_count_basis: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    effective_frequency: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    interest_frequency: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    round_balance: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    round_decimal_digits: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    days_in_year: Option<usize>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    index: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    margin: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reset_frequency: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    lookback_days: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    periodic_cap: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    periodic_floor: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    lifetime_cap: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    lifetime_floor: Option<String>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    calendar: String,
+}
+
+/// Serde shape of a principal change.
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct PrincipalChangeData {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    principal_type: Option<String>,
+    #[serde(default)]
+    auxiliary: bool,
+    #[serde(default)]
+    passive: bool,
+    #[serde(default)]
+    principal_first: bool,
+    #[serde(default)]
+    statistics: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    business_day: Option<String>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    calendar: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    payment_cap: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    neg_am_cap: Option<String>,
+    #[serde(default)]
+    eom: bool,
+}
+
+/// Serde shape of a statistic value.
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct StatisticValueData {
+    #[serde(default)]
+    name: String,
+    #[serde(default, rename = "final")]
+    is_final: bool,
+    #[serde(default)]
+    eom: bool,
+}
+
+/// Serde shape of an extension (exactly one of the extension types).
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+enum ExtensionData {
+    CurrentValue(CurrentValueData),
+    InterestChange(Box<InterestChangeData>),
+    PrincipalChange(PrincipalChangeData),
+    StatisticValue(StatisticValueData),
+}
+
+/// Serde shape of an event.
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct EventData {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    event_name: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    event_type: String,
+    event_date: EventDateData,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    end_date: Option<String>,
+    frequency: String,
+    intervals: usize,
+    event_periods: EventPeriodsData,
+    event_value: EventValueData,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    skip_mask: String,
+    sort_order: usize,
+    extension: ExtensionData,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    parameter_list: Vec<ParameterData>,
+    #[serde(default)]
+    descriptor_list: Vec<DescriptorData>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    event_next_name: String,
+}
+
+/// Serde shape of an amortization element.
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct AmData {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    event_type: String,
+    date: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    scheduled_date: Option<String>,
+    frequency: String,
+    intervals: usize,
+    periods: usize,
+    value: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    index_value: Option<String>,
+    value_to_interest: String,
+    value_to_principal: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    deferred_interest: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    scheduled_principal: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    prepaid_principal: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    surviving_balance: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    defaulted_principal: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    recovery: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    loss: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    delinquent_balance: Option<String>,
+    principal_decrease: String,
+    principal_increase: String,
+    interest: String,
+    sl_interest: String,
+    accrued_balance: String,
+    balance: String,
+    sort_order: usize,
+    extension: ExtensionData,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parameter_list: Option<Vec<ParameterData>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    descriptor_list: Option<Vec<DescriptorData>>,
+}
+
+/// Serde shape of a balance result.
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct BalanceResultData {
+    #[serde(default)]
+    accrued_balance_seen: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    polarity: Option<String>,
+    #[serde(default)]
+    rule_of_78_seen: bool,
+    auxiliary_active_decrease: String,
+    auxiliary_active_increase: String,
+    auxiliary_passive_decrease: String,
+    auxiliary_passive_increase: String,
+    final_accrued_balance: String,
+    final_balance: String,
+    final_balance_date: String,
+    interest_present: String,
+    interest_total: String,
+    sl_interest_present: String,
+    sl_interest_total: String,
+    principal_changes_present: usize,
+    principal_changes_total: usize,
+    principal_total_decrease: String,
+    principal_total_increase: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    scheduled_principal_total: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    prepaid_principal_total: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    surviving_balance: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    defaulted_principal_total: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    recovery_total: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    loss_total: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    delinquent_balance: Option<String>,
+}
+
+/// Serde shape of an exchange rate.
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct ExchangeRateData {
+    from: String,
+    to: String,
+    value: String,
+}
+
+/// Serde shape of preferences.
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct PreferencesData {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    group: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    locale: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cross_rate_code: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    default_encoding: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    decimal_digits: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fiscal_year_start: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    target: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    combine_principal: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    compress_descriptor: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    omit_statistic_events: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parameter_list: Option<Vec<ParameterData>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    function_list: Option<Vec<FunctionData>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    descriptor_list: Option<Vec<DescriptorData>>,
+}
+
+/// Serde shape of a template event.
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct TemplateEventData {
+    name: String,
+    #[serde(default)]
+    initial: bool,
+    event_list: Vec<EventData>,
+}
+
+/// Serde shape of a template group.
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct TemplateGroupData {
+    group: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    preferences: Option<PreferencesData>,
+    template_events: Vec<TemplateEventData>,
+}
+
+/// Serde shape of a cashflow.
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct CashflowData {
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    preferences: Option<PreferencesData>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    event_list: Option<Vec<EventData>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    am_list: Option<Vec<AmData>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    balance_result: Option<BalanceResultData>,
+}
+
+/// Return a date string in YYYY-MM-DD format.
+///
+/// # Arguments
+///
+/// * `date_param` - The date.
+///
+/// # Return
+///
+/// * See description.
+fn date_str(date_param: NaiveDate) -> String {
+    date_param.format("%Y-%m-%d").to_string()
+}
+
+/// Format a business day convention, omitting the unadjusted default.
+///
+/// # Arguments
+///
+/// * `business_day_param` - Business day convention.
+///
+/// # Return
+///
+/// * See description.
+fn business_day_str(business_day_param: crate::BusinessDayType) -> Option<String> {
+    if business_day_param == crate::BusinessDayType::Unadjusted {
+        return None;
+    }
+
+    Option::from(CoreUtility::get_business_day_mnemonic(business_day_param))
+}
+
+/// Parse a date string in YYYY-MM-DD format.
+///
+/// # Arguments
+///
+/// * `date_param` - Date string.
+///
+/// # Return
+///
+/// * Date if successful, otherwise error code.
+fn parse_date(date_param: &str) -> Result<NaiveDate, ElemError> {
+    match CoreUtility::parse_date(date_param) {
+        None => Err(ElemError::new(crate::ErrorType::Json)),
+        Some(o) => Ok(o),
+    }
+}
+
+/// Parse a decimal string.
+///
+/// # Arguments
+///
+/// * `decimal_param` - Decimal string.
+///
+/// # Return
+///
+/// * Decimal value if successful, otherwise error code.
+fn parse_decimal(decimal_param: &str) -> Result<Decimal, ElemError> {
+    match decimal_param.parse::<Decimal>() {
+        Err(_e) => Err(ElemError::new(crate::ErrorType::Json)),
+        Ok(o) => Ok(o),
+    }
+}
+
+/// Parse a round balance mnemonic.
+///
+/// # Arguments
+///
+/// * `round_balance_param` - Round balance mnemonic.
+///
+/// # Return
+///
+/// * See description.
+fn parse_round_balance(round_balance_param: &str) -> crate::RoundType {
+    match round_balance_param {
+        "bankers" => crate::RoundType::Bankers,
+        "bias-up" => crate::RoundType::BiasUp,
+        "bias-down" => crate::RoundType::BiasDown,
+        "up" => crate::RoundType::Up,
+        "truncate" => crate::RoundType::Truncate,
+        "yes" => crate::RoundType::Bankers,
+        _ => crate::RoundType::None,
+    }
+}
+
+/// The parameter serde implementation.
+impl ParameterData {
+    /// Create the serde shapes of a parameter list.
+    ///
+    /// # Arguments
+    ///
+    /// * `list_parameter` - List of parameters.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn list(list_parameter: &ListParameter) -> Vec<ParameterData> {
+        let mut params: Vec<ParameterData> = Vec::new();
+        let orig_index = list_parameter.index();
+
+        let mut index: usize = 0;
+        while list_parameter.get_element(index) {
+            let value = match list_parameter.param_type() {
+                crate::TokenType::Integer => list_parameter.param_integeri().to_string(),
+                crate::TokenType::Decimal => list_parameter.param_decimal().to_string(),
+                _ => String::from(list_parameter.param_string()),
+            };
+
+            params.push(ParameterData {
+                name: String::from(list_parameter.name()),
+                label: String::from(list_parameter.label()),
+                description: String::from(list_parameter.description()),
+                parameter_type: CoreUtility::get_param_type(list_parameter.param_type()),
+                value,
+            });
+
+            index += 1;
+        }
+
+        list_parameter.get_element(orig_index);
+
+        params
+    }
+
+    /// Add the serde shapes of parameters to a parameter list.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - Serde shapes of the parameters.
+    /// * `list_parameter` - List of parameters.
+    fn add_list(params: Vec<ParameterData>, list_parameter: &mut ListParameter) {
+        for param in params {
+            list_parameter.add_parameter(
+                param.name.as_str(),
+                param.label.as_str(),
+                param.description.as_str(),
+                true,
+            );
+
+            match param.parameter_type.as_str() {
+                "integer" => {
+                    list_parameter.set_integeri(CoreUtility::parse_integeri(param.value.as_str()));
+                }
+                "decimal" => {
+                    list_parameter.set_decimal(CoreUtility::parse_decimal(param.value.as_str()));
+                }
+                _ => {
+                    list_parameter.set_string(param.value.as_str());
+                }
+            }
+        }
+    }
+}
+
+/// The user-defined function serde implementation.
+impl FunctionData {
+    /// Create the serde shapes of a function list.
+    ///
+    /// # Arguments
+    ///
+    /// * `list_function` - List of functions.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn list(list_function: &ListFunction) -> Vec<FunctionData> {
+        let mut funcs: Vec<FunctionData> = Vec::new();
+        let orig_index = list_function.index();
+
+        let mut index: usize = 0;
+        while list_function.get_element(index) {
+            funcs.push(FunctionData {
+                name: String::from(list_function.name()),
+                description: String::from(list_function.description()),
+                parameters: list_function.parameters().to_vec(),
+                expression: String::from(list_function.expression()),
+            });
+
+            index += 1;
+        }
+
+        list_function.get_element(orig_index);
+
+        funcs
+    }
+
+    /// Add the serde shapes of functions to a function list.
+    ///
+    /// # Arguments
+    ///
+    /// * `funcs` - Serde shapes of the functions.
+    /// * `list_function` - List of functions.
+    fn add_list(funcs: Vec<FunctionData>, list_function: &mut ListFunction) {
+        for func in funcs {
+            list_function.add_function(
+                func.name.as_str(),
+                func.description.as_str(),
+                &func.parameters,
+                func.expression.as_str(),
+            );
+        }
+    }
+}
+
+/// The descriptor serde implementation.
+impl DescriptorData {
+    /// Create the serde shapes of a descriptor list.
+    ///
+    /// # Arguments
+    ///
+    /// * `list_descriptor` - List of descriptors.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn list(list_descriptor: &ListDescriptor) -> Vec<DescriptorData> {
+        let mut descs: Vec<DescriptorData> = Vec::new();
+        let orig_index = list_descriptor.index();
+
+        let mut index: usize = 0;
+        while list_descriptor.get_element(index) {
+            let event_index = list_descriptor.list_event_index();
+
+            descs.push(DescriptorData {
+                group: String::from(list_descriptor.group()),
+                name: String::from(list_descriptor.name()),
+                descriptor_type: String::from(list_descriptor.desc_type()),
+                descriptor_code: String::from(list_descriptor.code()),
+                value: list_descriptor.value(),
+                expression: list_descriptor.value_expr(),
+                event_index: if event_index == usize::MAX {
+                    None
+                } else {
+                    Option::from(event_index)
+                },
+                propagate: list_descriptor.propagate(),
+            });
+
+            index += 1;
+        }
+
+        list_descriptor.get_element(orig_index);
+
+        descs
+    }
+
+    /// Add the serde shapes of descriptors to a descriptor list.
+    ///
+    /// # Arguments
+    ///
+    /// * `descs` - Serde shapes of the descriptors.
+    /// * `list_descriptor` - List of descriptors.
+    fn add_list(descs: Vec<DescriptorData>, list_descriptor: &mut ListDescriptor) {
+        list_descriptor.set_sort_on_add(false);
+
+        for desc in descs {
+            list_descriptor.add_descriptor(
+                desc.group.as_str(),
+                desc.name.as_str(),
+                desc.descriptor_type.as_str(),
+                desc.descriptor_code.as_str(),
+                desc.value,
+                desc.expression.as_str(),
+                desc.propagate,
+                true,
+            );
+
+            if let Some(o) = desc.event_index {
+                list_descriptor.set_list_event_index(o);
+            }
+        }
+
+        list_descriptor.set_sort_on_add(true); // Sorts list
+    }
+}
+
+/// The extension serde implementation.
+impl ExtensionData {
+    /// Create the serde shape of a current value.
+    ///
+    /// # Arguments
+    ///
+    /// * `current_value` - Current value element.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn current_value(current_value: &ElemCurrentValue) -> CurrentValueData {
+        CurrentValueData {
+            passive: current_value.passive(),
+            present: current_value.present(),
+            business_day: business_day_str(current_value.business_day()),
+            calendar: String::from(current_value.calendar()),
+            eom: current_value.eom(),
+        }
+    }
+
+    /// Create the serde shape of an interest change.
+    ///
+    /// # Arguments
+    ///
+    /// * `interest_change` - Interest change element.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn interest_change(interest_change: &ElemInterestChange) -> InterestChangeData {
+        let effective_frequency = interest_change.effective_frequency();
+        let interest_frequency = interest_change.interest_frequency();
+        let round_balance = interest_change.round_balance();
+        let reset_frequency = interest_change.reset_frequency();
+        let is_index = !interest_change.index().is_empty();
+
+        InterestChangeData {
+            interest_method: Option::from(CoreUtility::get_interest_method_mnemonic(
+                interest_change.method(),
+            )),
+            day_count_basis: Option::from(CoreUtility::get_day_count_basis_mnemonic(
+                interest_change.day_count_basis(),
+            )),
+            effective_frequency: if effective_frequency == crate::FrequencyType::None {
+                None
+            } else {
+                Option::from(CoreUtility::get_frequency_mnemonic(effective_frequency))
+            },
+            interest_frequency: if interest_frequency == crate::FrequencyType::None {
+                None
+            } else {
+                Option::from(CoreUtility::get_frequency_mnemonic(interest_frequency))
+            },
+            round_balance: if round_balance == crate::RoundType::None {
+                None
+            } else {
+                Option::from(CoreUtility::get_round_balance(round_balance))
+            },
+            round_decimal_digits: Option::from(interest_change.round_decimal_digits().to_string()),
+            days_in_year: Option::from(interest_change.days_in_year()),
+            index: String::from(interest_change.index()),
+            margin: if is_index {
+                Option::from(interest_change.margin().to_string())
+            } else {
+                None
+            },
+            reset_frequency: if reset_frequency == crate::FrequencyType::None {
+                None
+            } else {
+                Option::from(CoreUtility::get_frequency_mnemonic(reset_frequency))
+            },
+            lookback_days: if interest_change.lookback_days() == 0 {
+                None
+            } else {
+                Option::from(interest_change.lookback_days())
+            },
+            periodic_cap: interest_change.periodic_cap().map(|o| o.to_string()),
+            periodic_floor: interest_change.periodic_floor().map(|o| o.to_string()),
+            lifetime_cap: interest_change.lifetime_cap().map(|o| o.to_string()),
+            lifetime_floor: interest_change.lifetime_floor().map(|o| o.to_string()),
+            calendar: String::from(interest_change.calendar()),
+        }
+    }
+
+    /// Create the serde shape of a principal change.
+    ///
+    /// # Arguments
+    ///
+    /// * `principal_change` - Principal change element.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn principal_change(principal_change: &ElemPrincipalChange) -> PrincipalChangeData {
+        PrincipalChangeData {
+            principal_type: Option::from(CoreUtility::get_principal_type_mnemonic(
+                principal_change.pc_type(),
+            )),
+            auxiliary: principal_change.auxiliary(),
+            passive: principal_change.aux_passive(),
+            principal_first: principal_change.principal_first(),
+            statistics: principal_change.balance_statistics(),
+            business_day: business_day_str(principal_change.business_day()),
+            calendar: String::from(principal_change.calendar()),
+            payment_cap: principal_change.payment_cap().map(|o| o.to_string()),
+            neg_am_cap: principal_change.neg_am_cap().map(|o| o.to_string()),
+            eom: principal_change.eom(),
+        }
+    }
+
+    /// Create the serde shape of a statistic value.
+    ///
+    /// # Arguments
+    ///
+    /// * `statistic_value` - Statistic value element.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn statistic_value(statistic_value: &ElemStatisticValue) -> StatisticValueData {
+        StatisticValueData {
+            name: String::from(statistic_value.name()),
+            is_final: statistic_value.is_final(),
+            eom: statistic_value.eom(),
+        }
+    }
+
+    /// Create the serde shape of an extension.
+    ///
+    /// # Arguments
+    ///
+    /// * `elem_extension` - Extension element.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn new(elem_extension: &ElemExtension) -> ExtensionData {
+        match elem_extension.extension_value() {
+            ExtensionValue::CurrentValue(o) => {
+                ExtensionData::CurrentValue(ExtensionData::current_value(o))
+            }
+            ExtensionValue::InterestChange(o) => {
+                ExtensionData::InterestChange(Box::new(ExtensionData::interest_change(o)))
+            }
+            ExtensionValue::PrincipalChange(o) => {
+                ExtensionData::PrincipalChange(ExtensionData::principal_change(o))
+            }
+            ExtensionValue::StatisticValue(o) => {
+                ExtensionData::StatisticValue(ExtensionData::statistic_value(o))
+            }
+        }
+    }
+
+    /// Create a current value element from its serde shape.
+    ///
+    /// # Arguments
+    ///
+    /// * `cv` - Serde shape of the current value.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn elem_current_value(cv: CurrentValueData) -> ElemCurrentValue {
+        let mut current_value = ElemCurrentValue::new(cv.eom, cv.passive, cv.present);
+
+        if let Some(o) = cv.business_day {
+            current_value.set_business_day(CoreUtility::get_business_day(o.as_str()));
+        }
+        current_value.set_calendar(cv.calendar.as_str());
+
+        current_value
+    }
+
+    /// Create an interest change element from its serde shape.
+    ///
+    /// # Arguments
+    ///
+    /// * `ic` - Serde shape of the interest change.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn elem_interest_change(ic: InterestChangeData) -> ElemInterestChange {
+        let mut interest_change = ElemInterestChange::new(
+            crate::MethodType::Actuarial,
+            crate::DayCountType::Periodic,
+            crate::DEFAULT_DAYS_IN_YEAR,
+            crate::FrequencyType::None,
+            crate::FrequencyType::None,
+            crate::RoundType::None,
+            dec!(crate::DEFAULT_DECIMAL_DIGITS),
+        );
+
+        if let Some(o) = ic.interest_method {
+            interest_change.set_method(CoreUtility::get_interest_method(o.as_str()));
+        }
+
+        if let Some(o) = ic.day_count_basis {
+            interest_change.set_day_count_basis(CoreUtility::get_day_count_basis(o.as_str()));
+        }
+
+        if let Some(o) = ic.days_in_year {
+            interest_change.set_days_in_year(o);
+        }
+
+        if let Some(o) = ic.effective_frequency {
+            interest_change.set_effective_frequency(CoreUtility::get_frequency(o.as_str()));
+        }
+
+        if let Some(o) = ic.interest_frequency {
+            interest_change.set_interest_frequency(CoreUtility::get_frequency(o.as_str()));
+        }
+
+        if let Some(o) = ic.round_balance {
+            interest_change.set_round_balance(parse_round_balance(o.as_str()));
+        }
+
+        if let Some(o) = ic.round_decimal_digits {
+            interest_change.set_round_decimal_digits(CoreUtility::parse_decimal(o.as_str()));
+        }
+
+        interest_change.set_index(ic.index.as_str());
+        interest_change.set_calendar(ic.calendar.as_str());
+
+        if let Some(o) = ic.margin {
+            interest_change.set_margin(CoreUtility::parse_decimal(o.as_str()));
+        }
+
+        if let Some(o) = ic.reset_frequency {
+            interest_change.set_reset_frequency(CoreUtility::get_frequency(o.as_str()));
+        }
+
+        if let Some(o) = ic.lookback_days {
+            interest_change.set_lookback_days(o);
+        }
+
+        interest_change.set_periodic_cap(
+            ic.periodic_cap
+                .map(|o| CoreUtility::parse_decimal(o.as_str())),
+        );
+        interest_change.set_periodic_floor(
+            ic.periodic_floor
+                .map(|o| CoreUtility::parse_decimal(o.as_str())),
+        );
+        interest_change.set_lifetime_cap(
+            ic.lifetime_cap
+                .map(|o| CoreUtility::parse_decimal(o.as_str())),
+        );
+        interest_change.set_lifetime_floor(
+            ic.lifetime_floor
+                .map(|o| CoreUtility::parse_decimal(o.as_str())),
+        );
+
+        interest_change
+    }
+
+    /// Create a principal change element from its serde shape.
+    ///
+    /// # Arguments
+    ///
+    /// * `pc` - Serde shape of the principal change.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn elem_principal_change(pc: PrincipalChangeData) -> ElemPrincipalChange {
+        let principal_type = match pc.principal_type {
+            None => crate::PrincipalType::Increase,
+            Some(o) => CoreUtility::get_principal_type(o.as_str()),
+        };
+
+        let mut principal_change = ElemPrincipalChange::new(
+            principal_type,
+            pc.eom,
+            pc.principal_first,
+            pc.statistics,
+            pc.auxiliary,
+            pc.passive,
+        );
+
+        if let Some(o) = pc.business_day {
+            principal_change.set_business_day(CoreUtility::get_business_day(o.as_str()));
+        }
+        principal_change.set_calendar(pc.calendar.as_str());
+        principal_change.set_payment_cap(
+            pc.payment_cap
+                .map(|o| CoreUtility::parse_decimal(o.as_str())),
+        );
+        principal_change.set_neg_am_cap(
+            pc.neg_am_cap
+                .map(|o| CoreUtility::parse_decimal(o.as_str())),
+        );
+
+        principal_change
+    }
+
+    /// Create a statistic value element from its serde shape.
+    ///
+    /// # Arguments
+    ///
+    /// * `sv` - Serde shape of the statistic value.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn elem_statistic_value(sv: StatisticValueData) -> ElemStatisticValue {
+        ElemStatisticValue::new(sv.name.as_str(), sv.eom, sv.is_final)
+    }
+
+    /// Create an extension element from this serde shape.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn elem_extension(self) -> ElemExtension {
+        match self {
+            ExtensionData::CurrentValue(o) => {
+                ElemExtension::new_current_value(ExtensionData::elem_current_value(o))
+            }
+            ExtensionData::InterestChange(o) => {
+                ElemExtension::new_interest_change(ExtensionData::elem_interest_change(*o))
+            }
+            ExtensionData::PrincipalChange(o) => {
+                ElemExtension::new_principal_change(ExtensionData::elem_principal_change(o))
+            }
+            ExtensionData::StatisticValue(o) => {
+                ElemExtension::new_statistic_value(ExtensionData::elem_statistic_value(o))
+            }
+        }
+    }
+}
+
+/// The event serde implementation.
+impl EventData {
+    /// Create the serde shape of an event.
+    ///
+    /// # Arguments
+    ///
+    /// * `elem_event` - Event element.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn new(elem_event: &ElemEvent) -> EventData {
+        let event_date = elem_event.event_date();
+        let periods = elem_event.periods();
+        let mut end_date = event_date;
+        if periods > 1 {
+            end_date = CoreUtility::date_new(
+                event_date,
+                event_date,
+                elem_event.frequency(),
+                elem_event.intervals() * (periods - 1),
+                elem_event.elem_extension().extension_eom(),
+            );
+        }
+
+        EventData {
+            event_name: String::from(elem_event.event_name()),
+            event_type: String::from(elem_event.event_type()),
+            event_date: EventDateData {
+                date: date_str(event_date),
+                expression: String::from(elem_event.date_expr()),
+            },
+            end_date: Option::from(date_str(end_date)),
+            frequency: CoreUtility::get_frequency_mnemonic(elem_event.frequency()),
+            intervals: elem_event.intervals(),
+            event_periods: EventPeriodsData {
+                periods,
+                expression: String::from(elem_event.periods_expr()),
+            },
+            event_value: EventValueData {
+                value: elem_event.value().to_string(),
+                expression: String::from(elem_event.value_expr()),
+                expr_balance: if elem_event.value_expr().is_empty() {
+                    None
+                } else {
+                    Option::from(elem_event.value_expr_balance())
+                },
+            },
+            skip_mask: CoreUtility::skip_mask_to_string(
+                elem_event.skip_mask_len(),
+                elem_event.skip_mask(),
+            ),
+            sort_order: elem_event.sort_order(),
+            extension: ExtensionData::new(elem_event.elem_extension()),
+            parameter_list: match elem_event.list_parameter() {
+                None => Vec::new(),
+                Some(o) => ParameterData::list(o),
+            },
+            descriptor_list: match elem_event.list_descriptor() {
+                None => Vec::new(),
+                Some(o) => DescriptorData::list(o),
+            },
+            event_next_name: String::from(elem_event.next_name()),
+        }
+    }
+
+    /// Create the serde shapes of an event list.
+    ///
+    /// # Arguments
+    ///
+    /// * `list_event` - List of events.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn list(list_event: &ListEvent) -> Vec<EventData> {
+        let mut events: Vec<EventData> = Vec::new();
+        let orig_index = list_event.index();
+
+        let mut index: usize = 0;
+        while list_event.get_element(index) {
+            events.push(EventData::new(list_event.elem_event()));
+            index += 1;
+        }
+
+        list_event.get_element(orig_index);
+
+        events
+    }
+
+    /// Create an event element from this serde shape.
+    ///
+    /// # Return
+    ///
+    /// * Event element if successful, otherwise error code.
+    fn elem_event(self) -> Result<ElemEvent, ElemError> {
+        let event_date = parse_date(self.event_date.date.as_str())?;
+
+        let value = parse_decimal(self.event_value.value.as_str())?;
+
+        let (skip_mask_len, skip_mask) = CoreUtility::string_to_skip_mask(self.skip_mask.as_str());
+
+        let mut list_parameter = ListParameter::new();
+        ParameterData::add_list(self.parameter_list, &mut list_parameter);
+
+        let mut list_descriptor = ListDescriptor::new();
+        DescriptorData::add_list(self.descriptor_list, &mut list_descriptor);
+
+        Ok(ElemEvent::new(
+            self.event_type.as_str(),
+            event_date,
+            self.event_date.expression.as_str(),
+            self.sort_order,
+            value,
+            self.event_value.expression.as_str(),
+            self.event_value.expr_balance.unwrap_or(false),
+            self.event_periods.periods,
+            self.event_periods.expression.as_str(),
+            skip_mask_len,
+            skip_mask,
+            self.intervals,
+            CoreUtility::get_frequency(self.frequency.as_str()),
+            self.extension.elem_extension(),
+            Option::from(list_parameter),
+            Option::from(list_descriptor),
+            self.event_name.as_str(),
+            self.event_next_name.as_str(),
+        ))
+    }
+
+    /// Add the serde shapes of events to an event list.
+    ///
+    /// # Arguments
+    ///
+    /// * `events` - Serde shapes of the events.
+    /// * `list_event` - List of events.
+    ///
+    /// # Return
+    ///
+    /// * ERROR_NONE if successful, otherwise error code.
+    fn add_list(events: Vec<EventData>, list_event: &mut ListEvent) -> Result<(), ElemError> {
+        list_event.set_sort_on_add(false);
+
+        for (index, event) in events.into_iter().enumerate() {
+            let elem_event = match event.elem_event() {
+                Err(mut e) => {
+                    e.set_event_index(index);
+                    return Err(e);
+                }
+                Ok(o) => o,
+            };
+
+            list_event.add_event(
+                elem_event.event_date(),
+                elem_event.date_expr(),
+                elem_event.sort_order(),
+                elem_event.value(),
+                elem_event.value_expr(),
+                elem_event.value_expr_balance(),
+                elem_event.periods(),
+                elem_event.periods_expr(),
+                elem_event.skip_mask_len(),
+                elem_event.skip_mask(),
+                elem_event.intervals(),
+                elem_event.frequency(),
+                elem_event.elem_extension().copy(),
+                elem_event.list_parameter().map(|o| o.copy(true)),
+                elem_event.list_descriptor().map(|o| o.copy(true, true)),
+                elem_event.event_name(),
+                elem_event.next_name(),
+            );
+        }
+
+        list_event.set_sort_on_add(true); // Sorts list
+
+        Ok(())
+    }
+}
+
+/// The amortization serde implementation.
+impl AmData {
+    /// Create the serde shape of an amortization element.
+    ///
+    /// # Arguments
+    ///
+    /// * `elem_am` - Amortization element.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn new(elem_am: &ElemAmortization) -> AmData {
+        AmData {
+            event_type: String::from(elem_am.event_type()),
+            date: date_str(elem_am.event_date()),
+            scheduled_date: if elem_am.scheduled_date() == elem_am.event_date() {
+                None
+            } else {
+                Option::from(date_str(elem_am.scheduled_date()))
+            },
+            frequency: CoreUtility::get_frequency_mnemonic(elem_am.frequency()),
+            intervals: elem_am.intervals(),
+            periods: elem_am.periods(),
+            value: elem_am.value().to_string(),
+            index_value: elem_am.index_value().map(|o| o.to_string()),
+            value_to_interest: elem_am.value_to_interest().to_string(),
+            value_to_principal: elem_am.value_to_principal().to_string(),
+            deferred_interest: if elem_am.deferred_interest() == dec!(0.0) {
+                None
+            } else {
+                Option::from(elem_am.deferred_interest().to_string())
+            },
+            scheduled_principal: if elem_am.scheduled_principal() == dec!(0.0)
+                && elem_am.prepaid_principal() == dec!(0.0)
+            {
+                None
+            } else {
+                Option::from(elem_am.scheduled_principal().to_string())
+            },
+            prepaid_principal: if elem_am.scheduled_principal() == dec!(0.0)
+                && elem_am.prepaid_principal() == dec!(0.0)
+            {
+                None
+            } else {
+                Option::from(elem_am.prepaid_principal().to_string())
+            },
+            surviving_balance: if elem_am.scheduled_principal() == dec!(0.0)
+                && elem_am.prepaid_principal() == dec!(0.0)
+            {
+                None
+            } else {
+                Option::from(elem_am.surviving_balance().to_string())
+            },
+            defaulted_principal: if elem_am.defaulted_principal() == dec!(0.0)
+                && elem_am.recovery() == dec!(0.0)
+                && elem_am.loss() == dec!(0.0)
+                && elem_am.delinquent_balance() == dec!(0.0)
+            {
+                None
+            } else {
+                Option::from(elem_am.defaulted_principal().to_string())
+            },
+            recovery: if elem_am.defaulted_principal() == dec!(0.0)
+                && elem_am.recovery() == dec!(0.0)
+                && elem_am.loss() == dec!(0.0)
+                && elem_am.delinquent_balance() == dec!(0.0)
+            {
+                None
+            } else {
+                Option::from(elem_am.recovery().to_string())
+            },
+            loss: if elem_am.defaulted_principal() == dec!(0.0)
+                && elem_am.recovery() == dec!(0.0)
+                && elem_am.loss() == dec!(0.0)
+                && elem_am.delinquent_balance() == dec!(0.0)
+            {
+                None
+            } else {
+                Option::from(elem_am.loss().to_string())
+            },
+            delinquent_balance: if elem_am.defaulted_principal() == dec!(0.0)
+                && elem_am.recovery() == dec!(0.0)
+                && elem_am.loss() == dec!(0.0)
+                && elem_am.delinquent_balance() == dec!(0.0)
+            {
+                None
+            } else {
+                Option::from(elem_am.delinquent_balance().to_string())
+            },
+            principal_decrease: elem_am.principal_decrease().to_string(),
+            principal_increase: elem_am.principal_increase().to_string(),
+            interest: elem_am.interest().to_string(),
+            sl_interest: elem_am.sl_interest().to_string(),
+            accrued_balance: elem_am.acc_balance().to_string(),
+            balance: elem_am.balance().to_string(),
+            sort_order: elem_am.sort_order(),
+            extension: ExtensionData::new(elem_am.elem_extension()),
+            parameter_list: elem_am.list_parameter().map(ParameterData::list),
+            descriptor_list: elem_am.list_descriptor().map(DescriptorData::list),
+        }
+    }
+
+    /// Create the serde shapes of an amortization list.
+    ///
+    /// # Arguments
+    ///
+    /// * `list_am` - List of amortization elements.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn list(list_am: &ListAmortization) -> Vec<AmData> {
+        let mut ams: Vec<AmData> = Vec::new();
+        let orig_index = list_am.index();
+
+        let mut index: usize = 0;
+        while list_am.get_element(index) {
+            ams.push(AmData::new(list_am.elem_amortization()));
+            index += 1;
+        }
+
+        list_am.get_element(orig_index);
+
+        ams
+    }
+
+    /// Create an amortization element from this serde shape.
+    ///
+    /// # Return
+    ///
+    /// * Amortization element if successful, otherwise error code.
+    fn elem_amortization(self) -> Result<ElemAmortization, ElemError> {
+        let event_date = parse_date(self.date.as_str())?;
+
+        let scheduled_date = match self.scheduled_date {
+            None => event_date,
+            Some(o) => parse_date(o.as_str())?,
+        };
+
+        let index_value = match self.index_value {
+            None => None,
+            Some(o) => match parse_decimal(o.as_str()) {
+                Err(e) => return Err(e),
+                Ok(o) => Option::from(o),
+            },
+        };
+
+        let deferred_interest = match self.deferred_interest {
+            None => dec!(0.0),
+            Some(o) => parse_decimal(o.as_str())?,
+        };
+
+        let scheduled_principal = match self.scheduled_principal {
+            None => dec!(0.0),
+            Some(o) => parse_decimal(o.as_str())?,
+        };
+
+        let prepaid_principal = match self.prepaid_principal {
+            None => dec!(0.0),
+            Some(o) => parse_decimal(o.as_str())?,
+        };
+
+        let surviving_balance = match self.surviving_balance {
+            None => dec!(0.0),
+            Some(o) => parse_decimal(o.as_str())?,
+        };
+
+        let defaulted_principal = match self.defaulted_principal {
+            None => dec!(0.0),
+            Some(o) => parse_decimal(o.as_str())?,
+        };
+
+        let recovery = match self.recovery {
+            None => dec!(0.0),
+            Some(o) => parse_decimal(o.as_str())?,
+        };
+
+        let loss = match self.loss {
+            None => dec!(0.0),
+            Some(o) => parse_decimal(o.as_str())?,
+        };
+
+        let delinquent_balance = match self.delinquent_balance {
+            None => dec!(0.0),
+            Some(o) => parse_decimal(o.as_str())?,
+        };
+
+        let mut decimals: Vec<Decimal> = Vec::new();
+        for text in [
+            &self.value,
+            &self.principal_decrease,
+            &self.principal_increase,
+            &self.interest,
+            &self.sl_interest,
+            &self.value_to_interest,
+            &self.value_to_principal,
+            &self.accrued_balance,
+            &self.balance,
+        ]
+        .iter()
+        {
+            match parse_decimal(text.as_str()) {
+                Err(e) => return Err(e),
+                Ok(o) => decimals.push(o),
+            }
+        }
+
+        let list_parameter = self.parameter_list.map(|o| {
+            let mut list_parameter = ListParameter::new();
+            ParameterData::add_list(o, &mut list_parameter);
+            list_parameter
+        });
+
+        let list_descriptor = self.descriptor_list.map(|o| {
+            let mut list_descriptor = ListDescriptor::new();
+            DescriptorData::add_list(o, &mut list_descriptor);
+            list_descriptor
+        });
+
+        let mut elem_am = ElemAmortization::new(
+            self.event_type.as_str(),
+            event_date,
+            event_date,
+            self.sort_order,
+            decimals[0],
+            decimals[0],
+            "",
+            self.periods,
+            self.intervals,
+            CoreUtility::get_frequency(self.frequency.as_str()),
+            decimals[1],
+            decimals[2],
+            decimals[3],
+            decimals[4],
+            decimals[5],
+            decimals[6],
+            decimals[7],
+            decimals[8],
+            usize::MAX,
+            0,
+            0,
+            self.extension.elem_extension(),
+            list_parameter,
+            list_descriptor,
+        );
+        elem_am.set_scheduled_date(scheduled_date);
+        elem_am.set_index_value(index_value);
+        elem_am.set_deferred_interest(deferred_interest);
+        elem_am.set_scheduled_principal(scheduled_principal);
+        elem_am.set_prepaid_principal(prepaid_principal);
+        elem_am.set_surviving_balance(surviving_balance);
+        elem_am.set_defaulted_principal(defaulted_principal);
+        elem_am.set_recovery(recovery);
+        elem_am.set_loss(loss);
+        elem_am.set_delinquent_balance(delinquent_balance);
+
+        Ok(elem_am)
+    }
+}
+
+/// The balance result serde implementation.
+impl BalanceResultData {
+    /// Create the serde shape of a balance result.
+    ///
+    /// # Arguments
+    ///
+    /// * `balance_result` - Balance result element.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn new(balance_result: &ElemBalanceResult) -> BalanceResultData {
+        BalanceResultData {
+            accrued_balance_seen: balance_result.acc_balance_seen(),
+            polarity: Option::from(String::from(if balance_result.polarity() < 0 {
+                "negative"
+            } else {
+                "positive"
+            })),
+            rule_of_78_seen: balance_result.rule_of_78_seen(),
+            auxiliary_active_decrease: balance_result.aux_active_decrease().to_string(),
+            auxiliary_active_increase: balance_result.aux_active_increase().to_string(),
+            auxiliary_passive_decrease: balance_result.aux_passive_decrease().to_string(),
+            auxiliary_passive_increase: balance_result.aux_passive_increase().to_string(),
+            final_accrued_balance: balance_result.acc_balance().to_string(),
+            final_balance: balance_result.balance().to_string(),
+            final_balance_date: date_str(balance_result.balance_date()),
+            interest_present: balance_result.interest_present().to_string(),
+            interest_total: balance_result.interest_total().to_string(),
+            sl_interest_present: balance_result.sl_interest_present().to_string(),
+            sl_interest_total: balance_result.sl_interest_total().to_string(),
+            principal_changes_present: balance_result.prin_present(),
+            principal_changes_total: balance_result.prin_total(),
+            principal_total_decrease: balance_result.prin_decrease().to_string(),
+            principal_total_increase: balance_result.prin_increase().to_string(),
+            scheduled_principal_total: if balance_result.scheduled_principal_total() == dec!(0.0)
+                && balance_result.prepaid_principal_total() == dec!(0.0)
+            {
+                None
+            } else {
+                Option::from(balance_result.scheduled_principal_total().to_string())
+            },
+            prepaid_principal_total: if balance_result.scheduled_principal_total() == dec!(0.0)
+                && balance_result.prepaid_principal_total() == dec!(0.0)
+            {
+                None
+            } else {
+                Option::from(balance_result.prepaid_principal_total().to_string())
+            },
+            surviving_balance: if balance_result.scheduled_principal_total() == dec!(0.0)
+                && balance_result.prepaid_principal_total() == dec!(0.0)
+            {
+                None
+            } else {
+                Option::from(balance_result.surviving_balance().to_string())
+            },
+            defaulted_principal_total: if balance_result.defaulted_principal_total() == dec!(0.0) {
+                None
+            } else {
+                Option::from(balance_result.defaulted_principal_total().to_string())
+            },
+            recovery_total: if balance_result.defaulted_principal_total() == dec!(0.0) {
+                None
+            } else {
+                Option::from(balance_result.recovery_total().to_string())
+            },
+            loss_total: if balance_result.defaulted_principal_total() == dec!(0.0) {
+                None
+            } else {
+                Option::from(balance_result.loss_total().to_string())
+            },
+            delinquent_balance: if balance_result.defaulted_principal_total() == dec!(0.0) {
+                None
+            } else {
+                Option::from(balance_result.delinquent_balance().to_string())
+            },
+        }
+    }
+
+    /// Create a balance result element from this serde shape.
+    ///
+    /// # Return
+    ///
+    /// * Balance result element if successful, otherwise error code.
+    fn elem_balance_result(self) -> Result<ElemBalanceResult, ElemError> {
+        let mut balance_result = ElemBalanceResult::new();
+
+        let balance_date = parse_date(self.final_balance_date.as_str())?;
+
+        let mut decimals: Vec<Decimal> = Vec::new();
+        for text in [
+            &self.auxiliary_active_decrease,
+            &self.auxiliary_active_increase,
+            &self.auxiliary_passive_decrease,
+            &self.auxiliary_passive_increase,
+            &self.final_accrued_balance,
+            &self.final_balance,
+            &self.interest_present,
+            &self.interest_total,
+            &self.sl_interest_present,
+            &self.sl_interest_total,
+            &self.principal_total_decrease,
+            &self.principal_total_increase,
+        ]
+        .iter()
+        {
+            match parse_decimal(text.as_str()) {
+                Err(e) => return Err(e),
+                Ok(o) => decimals.push(o),
+            }
+        }
+
+        balance_result.set_acc_balance_seen(self.accrued_balance_seen);
+        balance_result.set_polarity(match self.polarity.as_deref() {
+            Some("negative") => -1,
+            _ => 1,
+        });
+        balance_result.set_rule_of_78_seen(self.rule_of_78_seen);
+        balance_result.incr_aux_active_decrease(decimals[0]);
+        balance_result.incr_aux_active_increase(decimals[1]);
+        balance_result.incr_aux_passive_decrease(decimals[2]);
+        balance_result.incr_aux_passive_increase(decimals[3]);
+        balance_result.set_acc_balance(decimals[4]);
+        balance_result.set_balance(decimals[5]);
+        balance_result.set_balance_date(balance_date);
+        balance_result.incr_interest_present(decimals[6]);
+        balance_result.incr_interest_total(decimals[7]);
+        balance_result.incr_sl_interest_present(decimals[8]);
+        balance_result.incr_sl_interest_total(decimals[9]);
+        balance_result.incr_prin_present(self.principal_changes_present);
+        balance_result.incr_prin_total(self.principal_changes_total);
+        balance_result.incr_prin_decrease(decimals[10]);
+        balance_result.incr_prin_increase(decimals[11]);
+        if let Some(o) = self.scheduled_principal_total {
+            match parse_decimal(o.as_str()) {
+                Err(e) => return Err(e),
+                Ok(o) => balance_result.incr_scheduled_principal_total(o),
+            }
+        }
+        if let Some(o) = self.prepaid_principal_total {
+            match parse_decimal(o.as_str()) {
+                Err(e) => return Err(e),
+                Ok(o) => balance_result.incr_prepaid_principal_total(o),
+            }
+        }
+        if let Some(o) = self.surviving_balance {
+            match parse_decimal(o.as_str()) {
+                Err(e) => return Err(e),
+                Ok(o) => balance_result.set_surviving_balance(o),
+            }
+        }
+        if let Some(o) = self.defaulted_principal_total {
+            match parse_decimal(o.as_str()) {
+                Err(e) => return Err(e),
+                Ok(o) => balance_result.incr_defaulted_principal_total(o),
+            }
+        }
+        if let Some(o) = self.recovery_total {
+            match parse_decimal(o.as_str()) {
+                Err(e) => return Err(e),
+                Ok(o) => balance_result.incr_recovery_total(o),
+            }
+        }
+        if let Some(o) = self.loss_total {
+            match parse_decimal(o.as_str()) {
+                Err(e) => return Err(e),
+                Ok(o) => balance_result.incr_loss_total(o),
+            }
+        }
+        if let Some(o) = self.delinquent_balance {
+            match parse_decimal(o.as_str()) {
+                Err(e) => return Err(e),
+                Ok(o) => balance_result.set_delinquent_balance(o),
+            }
+        }
+
+        Ok(balance_result)
+    }
+}
+
+/// The preferences serde implementation.
+impl PreferencesData {
+    /// Create the serde shape of preferences.
+    ///
+    /// # Arguments
+    ///
+    /// * `preferences` - Preferences element.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn new(preferences: &ElemPreferences) -> PreferencesData {
+        PreferencesData {
+            group: Option::from(String::from(preferences.group())),
+            locale: Option::from(String::from(preferences.locale_str())),
+            cross_rate_code: if preferences.cross_rate_code().is_empty() {
+                None
+            } else {
+                Option::from(String::from(preferences.cross_rate_code()))
+            },
+            default_encoding: if preferences.default_encoding().is_empty() {
+                None
+            } else {
+                Option::from(String::from(preferences.default_encoding()))
+            },
+            decimal_digits: Option::from(preferences.decimal_digits()),
+            fiscal_year_start: Option::from(preferences.fiscal_year_start()),
+            target: if preferences.target() == dec!(0.0) {
+                None
+            } else {
+                Option::from(preferences.target().to_string())
+            },
+            combine_principal: Option::from(preferences.combine_principal()),
+            compress_descriptor: Option::from(preferences.compress_descriptor()),
+            omit_statistic_events: Option::from(preferences.statistic_events()),
+            parameter_list: Option::from(ParameterData::list(preferences.list_parameter())),
+            function_list: if preferences.list_function().count() == 0 {
+                None
+            } else {
+                Option::from(FunctionData::list(preferences.list_function()))
+            },
+            descriptor_list: Option::from(DescriptorData::list(preferences.list_descriptor())),
+        }
+    }
+
+    /// Apply this serde shape to a preferences element.
+    ///
+    /// # Arguments
+    ///
+    /// * `preferences` - Preferences element.
+    ///
+    /// # Return
+    ///
+    /// * ERROR_NONE if successful, otherwise error code.
+    fn apply(self, preferences: &mut ElemPreferences) -> Result<(), ElemError> {
+        if let Some(o) = self.combine_principal {
+            preferences.set_combine_principal(o);
+        }
+
+        if let Some(o) = self.compress_descriptor {
+            preferences.set_compress_descriptor(o);
+        }
+
+        if let Some(o) = self.decimal_digits {
+            preferences.set_decimal_digits(o);
+        }
+
+        if let Some(o) = self.cross_rate_code {
+            preferences.set_cross_rate_code(o.as_str());
+        }
+
+        if let Some(o) = self.default_encoding {
+            preferences.set_default_encoding(o.as_str());
+        }
+
+        if let Some(o) = self.descriptor_list {
+            DescriptorData::add_list(o, preferences.list_descriptor_mut());
+        }
+
+        if let Some(o) = self.fiscal_year_start {
+            preferences.set_fiscal_year_start(o);
+        }
+
+        if let Some(o) = self.function_list {
+            FunctionData::add_list(o, preferences.list_function_mut());
+        }
+
+        if let Some(o) = self.group {
+            preferences.set_group(o.as_str());
+        }
+
+        if let Some(o) = self.locale {
+            preferences.set_locale_str(o.as_str());
+        }
+
+        if let Some(o) = self.omit_statistic_events {
+            preferences.set_statistic_events(o);
+        }
+
+        if let Some(o) = self.parameter_list {
+            ParameterData::add_list(o, preferences.list_parameter_mut());
+        }
+
+        if let Some(o) = self.target {
+            match parse_decimal(o.as_str()) {
+                Err(e) => return Err(e),
+                Ok(o2) => preferences.set_target(o2),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The template event serde implementation.
+impl TemplateEventData {
+    /// Create the serde shapes of a template event list.
+    ///
+    /// # Arguments
+    ///
+    /// * `list_template_event` - List of template events.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn list(list_template_event: &ListTemplateEvent) -> Vec<TemplateEventData> {
+        let mut template_events: Vec<TemplateEventData> = Vec::new();
+        let orig_index = list_template_event.index();
+
+        let mut index: usize = 0;
+        while list_template_event.get_element(index) {
+            template_events.push(TemplateEventData {
+                name: String::from(list_template_event.name()),
+                initial: list_template_event.initial_event(),
+                event_list: EventData::list(list_template_event.list_event()),
+            });
+            index += 1;
+        }
+
+        list_template_event.get_element(orig_index);
+
+        template_events
+    }
+
+    /// Add the serde shapes of template events to a template event list.
+    ///
+    /// # Arguments
+    ///
+    /// * `template_events` - Serde shapes of the template events.
+    /// * `list_template_event` - List of template events.
+    ///
+    /// # Return
+    ///
+    /// * ERROR_NONE if successful, otherwise error code.
+    fn add_list(
+        template_events: Vec<TemplateEventData>,
+        list_template_event: &mut ListTemplateEvent,
+    ) -> Result<(), ElemError> {
+        list_template_event.set_sort_on_add(false);
+
+        for template_event in template_events {
+            let mut list_event = ListEvent::new(false);
+
+            EventData::add_list(template_event.event_list, &mut list_event)?;
+
+            list_template_event.add_template_event(
+                template_event.name.as_str(),
+                template_event.initial,
+                Option::from(list_event),
+            );
+        }
+
+        list_template_event.set_sort_on_add(true); // Sorts list
+
+        Ok(())
+    }
+}
+
+/// The cashflow serde implementation.
+impl CashflowData {
+    /// Create the serde shapes of a cashflow list.
+    ///
+    /// # Arguments
+    ///
+    /// * `list_cashflow` - List of cashflows.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn list(list_cashflow: &ListCashflow) -> Vec<CashflowData> {
+        let mut cashflows: Vec<CashflowData> = Vec::new();
+        let orig_index = list_cashflow.index();
+
+        let mut index: usize = 0;
+        while list_cashflow.get_element(index) {
+            let mut am_list = None;
+            let mut balance_result = None;
+
+            if list_cashflow.cashflow_valid() {
+                am_list = list_cashflow.list_amortization().map(AmData::list);
+                balance_result = list_cashflow
+                    .elem_balance_result()
+                    .map(BalanceResultData::new);
+            }
+
+            cashflows.push(CashflowData {
+                name: String::from(list_cashflow.name()),
+                preferences: list_cashflow.preferences().map(PreferencesData::new),
+                event_list: list_cashflow.list_event().map(EventData::list),
+                am_list,
+                balance_result,
+            });
+
+            index += 1;
+        }
+
+        list_cashflow.get_element(orig_index);
+
+        cashflows
+    }
+}
+
+/// The event element serialize implementation.
+impl Serialize for ElemEvent {
+    /// Serialize the event element.
+    ///
+    /// # Arguments
+    ///
+    /// * `serializer` - Serde serializer.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        EventData::new(self).serialize(serializer)
+    }
+}
+
+/// The event element deserialize implementation.
+impl<'de> Deserialize<'de> for ElemEvent {
+    /// Deserialize an event element.
+    ///
+    /// # Arguments
+    ///
+    /// * `deserializer` - Serde deserializer.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<ElemEvent, D::Error> {
+        match EventData::deserialize(deserializer) {
+            Err(e) => Err(e),
+            Ok(o) => o.elem_event().map_err(de::Error::custom),
+        }
+    }
+}
+
+/// The extension element serialize implementation.
+impl Serialize for ElemExtension {
+    /// Serialize the extension element.
+    ///
+    /// # Arguments
+    ///
+    /// * `serializer` - Serde serializer.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        ExtensionData::new(self).serialize(serializer)
+    }
+}
+
+/// The extension element deserialize implementation.
+impl<'de> Deserialize<'de> for ElemExtension {
+    /// Deserialize an extension element.
+    ///
+    /// # Arguments
+    ///
+    /// * `deserializer` - Serde deserializer.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<ElemExtension, D::Error> {
+        match ExtensionData::deserialize(deserializer) {
+            Err(e) => Err(e),
+            Ok(o) => Ok(o.elem_extension()),
+        }
+    }
+}
+
+/// The current value element serialize implementation.
+impl Serialize for ElemCurrentValue {
+    /// Serialize the current value element.
+    ///
+    /// # Arguments
+    ///
+    /// * `serializer` - Serde serializer.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        ExtensionData::current_value(self).serialize(serializer)
+    }
+}
+
+/// The current value element deserialize implementation.
+impl<'de> Deserialize<'de> for ElemCurrentValue {
+    /// Deserialize a current value element.
+    ///
+    /// # Arguments
+    ///
+    /// * `deserializer` - Serde deserializer.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<ElemCurrentValue, D::Error> {
+        match CurrentValueData::deserialize(deserializer) {
+            Err(e) => Err(e),
+            Ok(o) => Ok(ExtensionData::elem_current_value(o)),
+        }
+    }
+}
+
+/// The interest change element serialize implementation.
+impl Serialize for ElemInterestChange {
+    /// Serialize the interest change element.
+    ///
+    /// # Arguments
+    ///
+    /// * `serializer` - Serde serializer.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        ExtensionData::interest_change(self).serialize(serializer)
+    }
+}
+
+/// The interest change element deserialize implementation.
+impl<'de> Deserialize<'de> for ElemInterestChange {
+    /// Deserialize an interest change element.
+    ///
+    /// # Arguments
+    ///
+    /// * `deserializer` - Serde deserializer.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<ElemInterestChange, D::Error> {
+        match InterestChangeData::deserialize(deserializer) {
+            Err(e) => Err(e),
+            Ok(o) => Ok(ExtensionData::elem_interest_change(o)),
+        }
+    }
+}
+
+/// The principal change element serialize implementation.
+impl Serialize for ElemPrincipalChange {
+    /// Serialize the principal change element.
+    ///
+    /// # Arguments
+    ///
+    /// * `serializer` - Serde serializer.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        ExtensionData::principal_change(self).serialize(serializer)
+    }
+}
+
+/// The principal change element deserialize implementation.
+impl<'de> Deserialize<'de> for ElemPrincipalChange {
+    /// Deserialize a principal change element.
+    ///
+    /// # Arguments
+    ///
+    /// * `deserializer` - Serde deserializer.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<ElemPrincipalChange, D::Error> {
+        match PrincipalChangeData::deserialize(deserializer) {
+            Err(e) => Err(e),
+            Ok(o) => Ok(ExtensionData::elem_principal_change(o)),
+        }
+    }
+}
+
+/// The statistic value element serialize implementation.
+impl Serialize for ElemStatisticValue {
+    /// Serialize the statistic value element.
+    ///
+    /// # Arguments
+    ///
+    /// * `serializer` - Serde serializer.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        ExtensionData::statistic_value(self).serialize(serializer)
+    }
+}
+
+/// The statistic value element deserialize implementation.
+impl<'de> Deserialize<'de> for ElemStatisticValue {
+    /// Deserialize a statistic value element.
+    ///
+    /// # Arguments
+    ///
+    /// * `deserializer` - Serde deserializer.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<ElemStatisticValue, D::Error> {
+        match StatisticValueData::deserialize(deserializer) {
+            Err(e) => Err(e),
+            Ok(o) => Ok(ExtensionData::elem_statistic_value(o)),
+        }
+    }
+}
+
+/// The amortization element serialize implementation.
+impl Serialize for ElemAmortization {
+    /// Serialize the amortization element.
+    ///
+    /// # Arguments
+    ///
+    /// * `serializer` - Serde serializer.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        AmData::new(self).serialize(serializer)
+    }
+}
+
+/// The amortization element deserialize implementation.
+impl<'de> Deserialize<'de> for ElemAmortization {
+    /// Deserialize an amortization element.
+    ///
+    /// # Arguments
+    ///
+    /// * `deserializer` - Serde deserializer.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<ElemAmortization, D::Error> {
+        match AmData::deserialize(deserializer) {
+            Err(e) => Err(e),
+            Ok(o) => o.elem_amortization().map_err(de::Error::custom),
+        }
+    }
+}
+
+/// The balance result element serialize implementation.
+impl Serialize for ElemBalanceResult {
+    /// Serialize the balance result element.
+    ///
+    /// # Arguments
+    ///
+    /// * `serializer` - Serde serializer.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        BalanceResultData::new(self).serialize(serializer)
+    }
+}
+
+/// The balance result element deserialize implementation.
+impl<'de> Deserialize<'de> for ElemBalanceResult {
+    /// Deserialize a balance result element.
+    ///
+    /// # Arguments
+    ///
+    /// * `deserializer` - Serde deserializer.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<ElemBalanceResult, D::Error> {
+        match BalanceResultData::deserialize(deserializer) {
+            Err(e) => Err(e),
+            Ok(o) => o.elem_balance_result().map_err(de::Error::custom),
+        }
+    }
+}
+
+/// The exchange rate element serialize implementation.
+impl Serialize for ElemExchangeRate {
+    /// Serialize the exchange rate element.
+    ///
+    /// # Arguments
+    ///
+    /// * `serializer` - Serde serializer.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        ExchangeRateData {
+            from: String::from(self.from_code()),
+            to: String::from(self.to_code()),
+            value: self.exchange_rate().to_string(),
+        }
+        .serialize(serializer)
+    }
+}
+
+/// The exchange rate element deserialize implementation.
+impl<'de> Deserialize<'de> for ElemExchangeRate {
+    /// Deserialize an exchange rate element.
+    ///
+    /// # Arguments
+    ///
+    /// * `deserializer` - Serde deserializer.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<ElemExchangeRate, D::Error> {
+        let data = ExchangeRateData::deserialize(deserializer)?;
+
+        match parse_decimal(data.value.as_str()) {
+            Err(e) => Err(de::Error::custom(e)),
+            Ok(o) => Ok(ElemExchangeRate::new(
+                data.from.as_str(),
+                data.to.as_str(),
+                o,
+            )),
+        }
+    }
+}
+
+/// The preferences element serialize implementation.
+impl Serialize for ElemPreferences {
+    /// Serialize the preferences element.
+    ///
+    /// # Arguments
+    ///
+    /// * `serializer` - Serde serializer.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        PreferencesData::new(self).serialize(serializer)
+    }
+}
+
+/// The preferences element deserialize implementation.
+impl<'de, 'a> DeserializeSeed<'de> for CalcSeed<'a, ElemPreferences> {
+    type Value = ElemPreferences;
+
+    /// Deserialize a preferences element.
+    ///
+    /// # Arguments
+    ///
+    /// * `deserializer` - Serde deserializer.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn deserialize<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<ElemPreferences, D::Error> {
+        let data = PreferencesData::deserialize(deserializer)?;
+
+        let mut preferences = ElemPreferences::new(
+            "",
+            "",
+            "",
+            "",
+            0,
+            crate::DEFAULT_DECIMAL_DIGITS,
+            dec!(0.0),
+            -1,
+            -1,
+            -1,
+            None,
+            None,
+            false,
+            true,
+        );
+
+        match data.apply(&mut preferences) {
+            Err(e) => Err(de::Error::custom(e)),
+            Ok(_o) => Ok(preferences),
+        }
+    }
+}
+
+/// The template group element serialize implementation.
+impl Serialize for ElemTemplateGroup {
+    /// Serialize the template group element.
+    ///
+    /// # Arguments
+    ///
+    /// * `serializer` - Serde serializer.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        TemplateGroupData {
+            group: String::from(self.group()),
+            preferences: Option::from(PreferencesData::new(self.preferences())),
+            template_events: TemplateEventData::list(self.list_template_event()),
+        }
+        .serialize(serializer)
+    }
+}
+
+/// The template group element deserialize implementation.
+impl<'de, 'a> DeserializeSeed<'de> for CalcSeed<'a, ElemTemplateGroup> {
+    type Value = ElemTemplateGroup;
+
+    /// Deserialize a template group element. The group starts with
+    /// a copy of the user preferences.
+    ///
+    /// # Arguments
+    ///
+    /// * `deserializer` - Serde deserializer.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn deserialize<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<ElemTemplateGroup, D::Error> {
+        let data = TemplateGroupData::deserialize(deserializer)?;
+
+        self.set_updating_json(true);
+
+        let preferences = self.calc_manager.read().unwrap().preferences().copy(true);
+        let mut template_group = ElemTemplateGroup::new(data.group.as_str(), preferences);
+
+        let mut result = match data.preferences {
+            None => Ok(()),
+            Some(o) => o.apply(template_group.preferences_mut()),
+        };
+
+        if result.is_ok() {
+            result = TemplateEventData::add_list(
+                data.template_events,
+                template_group.list_template_event_mut(),
+            );
+        }
+
+        self.set_updating_json(false);
+
+        match result {
+            Err(e) => Err(de::Error::custom(e)),
+            Ok(_o) => Ok(template_group),
+        }
+    }
+}
+
+/// The cashflow list serialize implementation.
+impl Serialize for ListCashflow {
+    /// Serialize the list of cashflows. The amortization list and
+    /// balance results are included for balanced cashflows.
+    ///
+    /// # Arguments
+    ///
+    /// * `serializer` - Serde serializer.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        CashflowData::list(self).serialize(serializer)
+    }
+}
+
+/// The cashflow list deserialize implementation.
+impl<'de, 'a> DeserializeSeed<'de> for CalcSeed<'a, ListCashflow> {
+    type Value = ListCashflow;
+
+    /// Deserialize a list of cashflows. Each cashflow starts with a
+    /// copy of the user preferences. Amortization lists and balance
+    /// results are ignored since they are recalculated when the
+    /// cashflow is balanced.
+    ///
+    /// # Arguments
+    ///
+    /// * `deserializer` - Serde deserializer.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<ListCashflow, D::Error> {
+        let data = Vec::<CashflowData>::deserialize(deserializer)?;
+
+        self.set_updating_json(true);
+        let result = self.list_cashflow(data);
+        self.set_updating_json(false);
+
+        result.map_err(de::Error::custom)
+    }
+}
+
+/// The cashflow list seed implementation.
+impl<'a> CalcSeed<'a, ListCashflow> {
+    /// Create a list of cashflows from their serde shapes.
+    ///
+    /// # Arguments
+    ///
+    /// * `cashflows` - Serde shapes of the cashflows.
+    ///
+    /// # Return
+    ///
+    /// * List of cashflows if successful, otherwise error code.
+    fn list_cashflow(&self, cashflows: Vec<CashflowData>) -> Result<ListCashflow, ElemError> {
+        let calc_mgr = self.calc_manager.read().unwrap();
+        let mut list_cashflow = ListCashflow::new();
+
+        for cashflow in cashflows {
+            let name = cashflow.name.as_str();
+            let group = String::from(calc_mgr.preferences().group());
+            let preferences = calc_mgr.preferences().copy(true);
+
+            match list_cashflow.add_cashflow_prep(
+                &calc_mgr,
+                name,
+                None,
+                Option::from(preferences),
+                group.as_str(),
+            ) {
+                Err(mut e) => {
+                    e.set_cashflow_name(name);
+                    return Err(e);
+                }
+                Ok(o) => {
+                    list_cashflow.add_cashflow(o);
+                    list_cashflow.get_element_by_name(name, true);
+                }
+            }
+
+            if let Some(o) = cashflow.preferences {
+                if let Some(o2) = list_cashflow.preferences_mut() {
+                    if let Err(mut e) = o.apply(o2) {
+                        e.set_cashflow_name(name);
+                        return Err(e);
+                    }
+                }
+
+                list_cashflow.update_preferences();
+            }
+
+            if let Some(o) = cashflow.event_list {
+                if let Some(o2) = list_cashflow.list_event_mut() {
+                    if let Err(mut e) = EventData::add_list(o, o2) {
+                        e.set_cashflow_name(name);
+                        return Err(e);
+                    }
+                }
+            }
+        }
+
+        Ok(list_cashflow)
+    }
+}
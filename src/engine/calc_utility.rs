@@ -8,13 +8,12 @@
 // except according to those terms.
 
 use rust_decimal::prelude::*;
-use std::cell::{Ref, RefCell};
-use std::rc::Rc;
 
 use super::{CalcExpression, CalcManager};
 use crate::core::{
-    CoreUtility, ElemBalanceResult, ElemColumn, ElemExtension, ElemSymbol, ListAmortization,
-    ListColumn, ListDescriptor, ListEvent, ListParameter, ListStatisticHelper, ListSummary,
+    CoreUtility, ElemBalanceResult, ElemColumn, ElemError, ElemExprTrace, ElemExtension,
+    ElemSymbol, ListAmortization, ListColumn, ListDescriptor, ListEvent, ListFunction,
+    ListParameter, ListStatisticHelper, ListSummary,
 };
 use crate::ListTrait;
 
@@ -31,7 +30,7 @@ impl CalcUtility {
     ///
     /// # Arguments
     ///
-    /// * `calc_mgr` - Calculation manager reference.
+    /// * `calc_mgr` - Calculation manager.
     /// * `cashflow_currency_code` - Cashflow currency code.
     /// * `event_currency_code` - Event currency code.
     /// * `value` - The value to convert.
@@ -41,7 +40,7 @@ impl CalcUtility {
     /// * See description.
 
     pub fn convert_currency_event(
-        calc_mgr: &Ref<CalcManager>,
+        calc_mgr: &CalcManager,
         cashflow_currency_code: &str,
         event_currency_code: &str,
         value: Decimal,
@@ -62,7 +61,7 @@ impl CalcUtility {
     ///
     /// # Arguments
     ///
-    /// * `calc_manager` - Calculation manager.
+    /// * `calc_mgr` - Calculation manager.
     /// * `elem_type` - Type of the event.
     /// * `elem_extension` - A ElemPrincipalChange, ElemCurrentValue,
     ///     ElemInterestChange, or ElemStatisticValue element.
@@ -72,12 +71,12 @@ impl CalcUtility {
     /// * See description.
 
     pub fn create_event_type_list_parameter(
-        calc_manager: &Rc<RefCell<CalcManager>>,
+        calc_mgr: &CalcManager,
         elem_type: crate::ExtensionType,
         elem_extension: &ElemExtension,
     ) -> ListParameter {
         let mut list_parameter = ListParameter::new();
-        let updating_json = calc_manager.borrow().updating_json();
+        let updating_json = calc_mgr.updating_json();
         match elem_type {
             crate::ExtensionType::CurrentValue => {
                 list_parameter.add_parameter("intEOM", "", "", updating_json);
@@ -166,19 +165,17 @@ impl CalcUtility {
     ///
     /// # Arguments
     ///
-    /// * `calc_manager` - Calculation manager.
+    /// * `calc_mgr` - Calculation manager.
     /// * `expression` - CalcExpression element.
     /// * `list_parameter` - List of parameters used with evaluation.
     /// * `list_descriptor` - List of descriptors to evaluate.
 
     pub fn evaluate_descriptors(
-        calc_manager: &Rc<RefCell<CalcManager>>,
-        expression: &RefCell<CalcExpression>,
+        calc_mgr: &CalcManager,
+        expression: &mut CalcExpression,
         list_parameter: &ListParameter,
         list_descriptor: &ListDescriptor,
     ) {
-        let calc_mgr = calc_manager.borrow();
-
         let orig_index = list_descriptor.index();
         let mut index: usize = 0;
 
@@ -205,19 +202,19 @@ impl CalcUtility {
                     list_descriptor_cashflow = Option::from(o.list_descriptor());
                 }
             }
-            expression.borrow_mut().init_expression(
+            expression.init_expression(
                 list_descriptor_cashflow,
                 None,
                 Option::from(list_parameter),
                 list_descriptor.value_expr().as_str(),
             );
 
-            let elem_result_symbol: ElemSymbol = match expression.borrow().evaluate(
+            let elem_result_symbol: ElemSymbol = match expression.evaluate(
                 calc_mgr.list_cashflow().list_amortization(),
                 calc_mgr.list_cashflow().elem_balance_result(),
             ) {
                 Err(e) => {
-                    let error_string = calc_mgr.get_error_string(e);
+                    let error_string = calc_mgr.get_error_string(e.kind());
                     list_descriptor
                         .set_value(format!("{}{}", crate::ERROR_PREFIX, error_string).as_str());
                     calc_mgr.list_locale().select_event_locale("");
@@ -229,7 +226,7 @@ impl CalcUtility {
 
             let value: String = match elem_result_symbol.sym_type() {
                 crate::TokenType::Integer => {
-                    format!("{}", elem_result_symbol.sym_integer())
+                    format!("{}", elem_result_symbol.sym_integeri())
                 }
                 crate::TokenType::Decimal => {
                     format!("{}", elem_result_symbol.sym_decimal())
@@ -246,11 +243,62 @@ impl CalcUtility {
         list_descriptor.set_index(orig_index);
     }
 
+    /// Check the expression without evaluating it. The type of
+    /// every sub-expression is inferred and the symbols and
+    /// descriptors referenced are resolved.
+    ///
+    /// # Arguments
+    ///
+    /// * `calc_mgr` - Calculation manager.
+    /// * `list_descriptor_event` - Event descriptors in scope (or None).
+    /// * `list_parameter` - List of parameters in scope (or None).
+    /// * `expression_str` - The expression to check.
+    /// * `cashflow` - Search the cashflow preferences.
+    ///
+    /// # Return
+    ///
+    /// * Type of the expression result (Unknown if only known when
+    ///   evaluated) if successful, otherwise error code.
+    pub fn check_expression(
+        calc_mgr: &CalcManager,
+        list_descriptor_event: Option<&ListDescriptor>,
+        list_parameter: Option<&ListParameter>,
+        expression_str: &str,
+        cashflow: bool,
+    ) -> Result<crate::TokenType, ElemError> {
+        let mut list_descriptor_cashflow: Option<&ListDescriptor> = None;
+        let mut list_function_cashflow: Option<&ListFunction> = None;
+
+        match calc_mgr.list_cashflow().preferences() {
+            None => {}
+            Some(o) => {
+                list_descriptor_cashflow = Option::from(o.list_descriptor());
+                list_function_cashflow = Option::from(o.list_function());
+            }
+        }
+
+        let mut expression = CalcExpression::new(
+            calc_mgr,
+            calc_mgr.fiscal_year_start(cashflow),
+            calc_mgr.decimal_digits(cashflow),
+        );
+
+        expression.init_list_function(list_function_cashflow);
+        expression.init_expression(
+            list_descriptor_cashflow,
+            list_descriptor_event,
+            list_parameter,
+            expression_str,
+        );
+
+        expression.check()
+    }
+
     /// Evaluate the expression.
     ///
     /// # Arguments
     ///
-    /// * `calc_manager` - Calculation manager.
+    /// * `calc_mgr` - Calculation manager.
     /// * `list_parameter` - List of parameters used with evaluation.
     /// * `expression_str` - The expression to evaluate.
     /// * `cashflow` - Search the cashflow preferences.
@@ -261,27 +309,90 @@ impl CalcUtility {
     ///     message in the symbol.
 
     pub fn evaluate_expression(
-        calc_manager: &Rc<RefCell<CalcManager>>,
+        calc_mgr: &CalcManager,
         list_parameter: Option<&ListParameter>,
         expression_str: &str,
         cashflow: bool,
     ) -> ElemSymbol {
-        let calc_mgr = calc_manager.borrow();
+        let (elem_result_symbol, _) = CalcUtility::evaluate_expression_ext(
+            calc_mgr,
+            list_parameter,
+            expression_str,
+            cashflow,
+            false,
+        );
+
+        elem_result_symbol
+    }
+
+    /// Evaluate the expression and record an evaluation trace.
+    ///
+    /// # Arguments
+    ///
+    /// * `calc_mgr` - Calculation manager.
+    /// * `list_parameter` - List of parameters used with evaluation.
+    /// * `expression_str` - The expression to evaluate.
+    /// * `cashflow` - Search the cashflow preferences.
+    ///
+    /// # Return
+    ///
+    /// * Resulting symbol if successful, otherwise an error
+    ///   message in the symbol, and the evaluation trace
+    ///   (None if the expression could not be compiled).
+    pub fn evaluate_expression_trace(
+        calc_mgr: &CalcManager,
+        list_parameter: Option<&ListParameter>,
+        expression_str: &str,
+        cashflow: bool,
+    ) -> (ElemSymbol, Option<ElemExprTrace>) {
+        CalcUtility::evaluate_expression_ext(
+            calc_mgr,
+            list_parameter,
+            expression_str,
+            cashflow,
+            true,
+        )
+    }
+
+    /// Evaluate the expression and optionally record an evaluation trace.
+    ///
+    /// # Arguments
+    ///
+    /// * `calc_mgr` - Calculation manager.
+    /// * `list_parameter` - List of parameters used with evaluation.
+    /// * `expression_str` - The expression to evaluate.
+    /// * `cashflow` - Search the cashflow preferences.
+    /// * `trace` - Record an evaluation trace.
+    ///
+    /// # Return
+    ///
+    /// * Resulting symbol and evaluation trace.
+    fn evaluate_expression_ext(
+        calc_mgr: &CalcManager,
+        list_parameter: Option<&ListParameter>,
+        expression_str: &str,
+        cashflow: bool,
+        trace: bool,
+    ) -> (ElemSymbol, Option<ElemExprTrace>) {
         let mut list_descriptor_cashflow: Option<&ListDescriptor> = None;
+        let mut list_function_cashflow: Option<&ListFunction> = None;
 
         match calc_mgr.list_cashflow().preferences() {
             None => {}
             Some(o) => {
                 list_descriptor_cashflow = Option::from(o.list_descriptor());
+                list_function_cashflow = Option::from(o.list_function());
             }
         }
 
         let mut expression = CalcExpression::new(
-            calc_manager,
+            calc_mgr,
             calc_mgr.fiscal_year_start(cashflow),
             calc_mgr.decimal_digits(cashflow),
         );
 
+        expression.set_trace(trace);
+        expression.init_list_function(list_function_cashflow);
         expression.init_expression(
             list_descriptor_cashflow,
             None,
@@ -297,7 +408,7 @@ impl CalcUtility {
         match result {
             Err(e) => {
                 elem_result_symbol = ElemSymbol::new();
-                let error_string = calc_mgr.get_error_string(e);
+                let error_string = calc_mgr.get_error_string(e.kind());
                 elem_result_symbol
                     .set_string(format!("{}{}", crate::ERROR_PREFIX, error_string).as_str());
             }
@@ -306,25 +417,21 @@ impl CalcUtility {
             }
         }
 
-        elem_result_symbol
+        (elem_result_symbol, expression.trace())
     }
 
     /// Get the appropriate event list value as a string.
     ///
     /// # Arguments
     ///
-    /// * `calc_manager` - Calculation manager.
+    /// * `calc_mgr` - Calculation manager.
     /// * `elem_column` - Column element.
     ///
     /// # Return
     ///
     /// * See description.
 
-    pub fn get_event_value(
-        calc_manager: &Rc<RefCell<CalcManager>>,
-        elem_column: &ElemColumn,
-    ) -> String {
-        let calc_mgr = calc_manager.borrow();
+    pub fn get_event_value(calc_mgr: &CalcManager, elem_column: &ElemColumn) -> String {
         let decimal_digits = calc_mgr.decimal_digits(true);
         let list_locale = calc_mgr.list_locale();
         let list_cashflow = calc_mgr.list_cashflow();
@@ -360,7 +467,9 @@ impl CalcUtility {
                                 crate::FormatType::Date => match o.value().parse::<usize>() {
                                     Err(_e) => {}
                                     Ok(o2) => {
-                                        result = list_locale.format_date_out(o2);
+                                        if let Some(o3) = CoreUtility::integer_to_date(o2) {
+                                            result = list_locale.format_date_out(o3);
+                                        }
                                     }
                                 },
                                 crate::FormatType::Integer => match o.value().parse::<i32>() {
@@ -440,14 +549,13 @@ impl CalcUtility {
                 result = format!("{}", list_event.intervals());
             }
             crate::ColumnType::Frequency => {
-                calc_manager
-                    .borrow()
+                if let Some(o) = calc_mgr
                     .core_manager()
                     .map_frequency()
-                    .get_element_by_value(list_event.frequency() as usize);
-                result = String::from(
-                    list_locale.get_resource(calc_mgr.core_manager().map_frequency().key()),
-                );
+                    .get_key_by_value(list_event.frequency() as usize)
+                {
+                    result = String::from(list_locale.get_resource(o.key()));
+                }
             }
             crate::ColumnType::EndDate => {
                 if list_event.periods() > 1 {
@@ -502,7 +610,7 @@ impl CalcUtility {
     ///
     /// # Arguments
     ///
-    /// * `calc_manager` - Calculation manager.
+    /// * `calc_mgr` - Calculation manager.
     /// * `elem_column` - Column element.
     /// * `list_am_opt` - Amortization list.
     ///
@@ -511,11 +619,10 @@ impl CalcUtility {
     /// * See description.
 
     pub fn get_am_value(
-        calc_manager: &Rc<RefCell<CalcManager>>,
+        calc_mgr: &CalcManager,
         elem_column: &ElemColumn,
         list_am: &ListAmortization,
     ) -> String {
-        let calc_mgr = calc_manager.borrow();
         let decimal_digits = calc_mgr.decimal_digits(true);
         let list_locale = calc_mgr.list_locale();
         let cashflow_currency_code = String::from(list_locale.cashflow_currency_code());
@@ -556,7 +663,9 @@ impl CalcUtility {
                                 crate::FormatType::Date => match o.value().parse::<usize>() {
                                     Err(_e) => {}
                                     Ok(o2) => {
-                                        result = list_locale.format_date_out(o2);
+                                        if let Some(o3) = CoreUtility::integer_to_date(o2) {
+                                            result = list_locale.format_date_out(o3);
+                                        }
                                     }
                                 },
                                 crate::FormatType::Integer => match o.value().parse::<i32>() {
@@ -576,7 +685,7 @@ impl CalcUtility {
                                     Ok(o2) => {
                                         result = list_locale.format_currency_out(
                                             CalcUtility::convert_currency_event(
-                                                &calc_mgr,
+                                                calc_mgr,
                                                 cashflow_currency_code.as_str(),
                                                 event_currency_code.as_str(),
                                                 o2,
@@ -612,7 +721,7 @@ impl CalcUtility {
                 crate::ExtensionType::PrincipalChange => {
                     result = list_locale.format_currency_out(
                         CalcUtility::convert_currency_event(
-                            &calc_mgr,
+                            calc_mgr,
                             cashflow_currency_code.as_str(),
                             event_currency_code.as_str(),
                             list_am.value(),
@@ -628,44 +737,39 @@ impl CalcUtility {
             crate::ColumnType::Periods => {
                 result = format!("{}", list_am.periods());
             }
-            crate::ColumnType::Decrease => {
-                if list_am.principal_decrease() > dec!(0.0) {
-                    result = list_locale.format_currency_out(
-                        CalcUtility::convert_currency_event(
-                            &calc_mgr,
-                            cashflow_currency_code.as_str(),
-                            event_currency_code.as_str(),
-                            list_am.principal_decrease(),
-                        ),
-                        decimal_digits,
-                    );
-                }
+            crate::ColumnType::Decrease if list_am.principal_decrease() > dec!(0.0) => {
+                result = list_locale.format_currency_out(
+                    CalcUtility::convert_currency_event(
+                        calc_mgr,
+                        cashflow_currency_code.as_str(),
+                        event_currency_code.as_str(),
+                        list_am.principal_decrease(),
+                    ),
+                    decimal_digits,
+                );
             }
-            crate::ColumnType::Increase => {
-                if list_am.principal_increase() > dec!(0.0) {
-                    result = list_locale.format_currency_out(
-                        CalcUtility::convert_currency_event(
-                            &calc_mgr,
-                            cashflow_currency_code.as_str(),
-                            event_currency_code.as_str(),
-                            list_am.principal_increase(),
-                        ),
-                        decimal_digits,
-                    );
-                }
+            crate::ColumnType::Increase if list_am.principal_increase() > dec!(0.0) => {
+                result = list_locale.format_currency_out(
+                    CalcUtility::convert_currency_event(
+                        calc_mgr,
+                        cashflow_currency_code.as_str(),
+                        event_currency_code.as_str(),
+                        list_am.principal_increase(),
+                    ),
+                    decimal_digits,
+                );
             }
             crate::ColumnType::Intervals => {
                 result = format!("{}", list_am.intervals());
             }
             crate::ColumnType::Frequency => {
-                calc_manager
-                    .borrow()
+                if let Some(o) = calc_mgr
                     .core_manager()
                     .map_frequency()
-                    .get_element_by_value(list_am.frequency() as usize);
-                result = String::from(
-                    list_locale.get_resource(calc_mgr.core_manager().map_frequency().key()),
-                );
+                    .get_key_by_value(list_am.frequency() as usize)
+                {
+                    result = String::from(list_locale.get_resource(o.key()));
+                }
             }
             crate::ColumnType::ParameterList => match list_am.list_parameter() {
                 None => {
@@ -687,141 +791,145 @@ impl CalcUtility {
                     }
                 }
             },
-            crate::ColumnType::Interest => {
+            crate::ColumnType::Interest
                 if !(list_am.interest() == dec!(0.0)
-                    && list_am.elem_type() == crate::ExtensionType::StatisticValue)
-                {
-                    result = list_locale.format_currency_out(
-                        CalcUtility::convert_currency_event(
-                            &calc_mgr,
-                            cashflow_currency_code.as_str(),
-                            event_currency_code.as_str(),
-                            list_am.interest(),
-                        ),
-                        decimal_digits,
-                    );
-                }
+                    && list_am.elem_type() == crate::ExtensionType::StatisticValue) =>
+            {
+                result = list_locale.format_currency_out(
+                    CalcUtility::convert_currency_event(
+                        calc_mgr,
+                        cashflow_currency_code.as_str(),
+                        event_currency_code.as_str(),
+                        list_am.interest(),
+                    ),
+                    decimal_digits,
+                );
             }
-            crate::ColumnType::SlInterest => {
+            crate::ColumnType::SlInterest
                 if !(list_am.sl_interest() == dec!(0.0)
-                    && list_am.elem_type() == crate::ExtensionType::StatisticValue)
-                {
-                    result = list_locale.format_currency_out(
-                        CalcUtility::convert_currency_event(
-                            &calc_mgr,
-                            cashflow_currency_code.as_str(),
-                            event_currency_code.as_str(),
-                            list_am.sl_interest(),
-                        ),
-                        decimal_digits,
-                    );
-                }
+                    && list_am.elem_type() == crate::ExtensionType::StatisticValue) =>
+            {
+                result = list_locale.format_currency_out(
+                    CalcUtility::convert_currency_event(
+                        calc_mgr,
+                        cashflow_currency_code.as_str(),
+                        event_currency_code.as_str(),
+                        list_am.sl_interest(),
+                    ),
+                    decimal_digits,
+                );
             }
-            crate::ColumnType::IntOnInterest => {
+            crate::ColumnType::IntOnInterest
                 if !(list_am.interest() - list_am.sl_interest() == dec!(0.0)
-                    && list_am.elem_type() == crate::ExtensionType::StatisticValue)
-                {
-                    result = list_locale.format_currency_out(
-                        CalcUtility::convert_currency_event(
-                            &calc_mgr,
-                            cashflow_currency_code.as_str(),
-                            event_currency_code.as_str(),
-                            list_am.interest() - list_am.sl_interest(),
-                        ),
-                        decimal_digits,
-                    );
-                }
+                    && list_am.elem_type() == crate::ExtensionType::StatisticValue) =>
+            {
+                result = list_locale.format_currency_out(
+                    CalcUtility::convert_currency_event(
+                        calc_mgr,
+                        cashflow_currency_code.as_str(),
+                        event_currency_code.as_str(),
+                        list_am.interest() - list_am.sl_interest(),
+                    ),
+                    decimal_digits,
+                );
             }
-            crate::ColumnType::ValueToInterest => {
+            crate::ColumnType::ValueToInterest
                 if !(list_am.value_to_interest() == dec!(0.0)
-                    && list_am.elem_type() == crate::ExtensionType::StatisticValue)
-                {
-                    result = list_locale.format_currency_out(
-                        CalcUtility::convert_currency_event(
-                            &calc_mgr,
-                            cashflow_currency_code.as_str(),
-                            event_currency_code.as_str(),
-                            list_am.value_to_interest(),
-                        ),
-                        decimal_digits,
-                    );
-                }
+                    && list_am.elem_type() == crate::ExtensionType::StatisticValue) =>
+            {
+                result = list_locale.format_currency_out(
+                    CalcUtility::convert_currency_event(
+                        calc_mgr,
+                        cashflow_currency_code.as_str(),
+                        event_currency_code.as_str(),
+                        list_am.value_to_interest(),
+                    ),
+                    decimal_digits,
+                );
             }
-            crate::ColumnType::ValueToPrincipal => {
+            crate::ColumnType::ValueToPrincipal
                 if !(list_am.value_to_principal() == dec!(0.0)
-                    && list_am.elem_type() == crate::ExtensionType::StatisticValue)
-                {
-                    result = list_locale.format_currency_out(
-                        CalcUtility::convert_currency_event(
-                            &calc_mgr,
-                            cashflow_currency_code.as_str(),
-                            event_currency_code.as_str(),
-                            list_am.value_to_principal(),
-                        ),
-                        decimal_digits,
-                    );
-                }
+                    && list_am.elem_type() == crate::ExtensionType::StatisticValue) =>
+            {
+                result = list_locale.format_currency_out(
+                    CalcUtility::convert_currency_event(
+                        calc_mgr,
+                        cashflow_currency_code.as_str(),
+                        event_currency_code.as_str(),
+                        list_am.value_to_principal(),
+                    ),
+                    decimal_digits,
+                );
             }
-            crate::ColumnType::AccruedBalance => {
+            crate::ColumnType::AccruedBalance
                 if !(list_am.acc_balance() == dec!(0.0)
-                    && list_am.elem_type() == crate::ExtensionType::StatisticValue)
-                {
-                    result = list_locale.format_currency_out(
-                        CalcUtility::convert_currency_event(
-                            &calc_mgr,
-                            cashflow_currency_code.as_str(),
-                            event_currency_code.as_str(),
-                            list_am.acc_balance(),
-                        ),
-                        decimal_digits,
-                    );
-                }
+                    && list_am.elem_type() == crate::ExtensionType::StatisticValue) =>
+            {
+                result = list_locale.format_currency_out(
+                    CalcUtility::convert_currency_event(
+                        calc_mgr,
+                        cashflow_currency_code.as_str(),
+                        event_currency_code.as_str(),
+                        list_am.acc_balance(),
+                    ),
+                    decimal_digits,
+                );
             }
-            crate::ColumnType::Balance => {
+            crate::ColumnType::DeferredInterest if list_am.deferred_interest() != dec!(0.0) => {
+                result = list_locale.format_currency_out(
+                    CalcUtility::convert_currency_event(
+                        calc_mgr,
+                        cashflow_currency_code.as_str(),
+                        event_currency_code.as_str(),
+                        list_am.deferred_interest(),
+                    ),
+                    decimal_digits,
+                );
+            }
+            crate::ColumnType::Balance
                 if !(list_am.balance() == dec!(0.0)
-                    && list_am.elem_type() == crate::ExtensionType::StatisticValue)
-                {
-                    let balance = CoreUtility::round(
-                        list_am.balance(),
-                        elem_column.decimal_digits(),
-                        crate::RoundType::Bankers,
-                    );
-                    if elem_balance_result.polarity() < 0 {
-                        if balance > dec!(0.0) {
-                            result = format!(
-                                "+{}",
-                                list_locale.format_currency_out(
-                                    CalcUtility::convert_currency_event(
-                                        &calc_mgr,
-                                        cashflow_currency_code.as_str(),
-                                        event_currency_code.as_str(),
-                                        balance
-                                    ),
-                                    decimal_digits
-                                )
-                            );
-                        } else {
-                            result = list_locale.format_currency_out(
+                    && list_am.elem_type() == crate::ExtensionType::StatisticValue) =>
+            {
+                let balance = CoreUtility::round(
+                    list_am.balance(),
+                    elem_column.decimal_digits(),
+                    crate::RoundType::Bankers,
+                );
+                if elem_balance_result.polarity() < 0 {
+                    if balance > dec!(0.0) {
+                        result = format!(
+                            "+{}",
+                            list_locale.format_currency_out(
                                 CalcUtility::convert_currency_event(
-                                    &calc_mgr,
+                                    calc_mgr,
                                     cashflow_currency_code.as_str(),
                                     event_currency_code.as_str(),
-                                    balance.abs(),
+                                    balance
                                 ),
-                                decimal_digits,
-                            );
-                        }
+                                decimal_digits
+                            )
+                        );
                     } else {
                         result = list_locale.format_currency_out(
                             CalcUtility::convert_currency_event(
-                                &calc_mgr,
+                                calc_mgr,
                                 cashflow_currency_code.as_str(),
                                 event_currency_code.as_str(),
-                                balance,
+                                balance.abs(),
                             ),
                             decimal_digits,
                         );
                     }
+                } else {
+                    result = list_locale.format_currency_out(
+                        CalcUtility::convert_currency_event(
+                            calc_mgr,
+                            cashflow_currency_code.as_str(),
+                            event_currency_code.as_str(),
+                            balance,
+                        ),
+                        decimal_digits,
+                    );
                 }
             }
             _ => {}
@@ -835,7 +943,7 @@ impl CalcUtility {
     ///
     /// # Arguments
     ///
-    /// * `calc_manager` - Calculation manager.
+    /// * `calc_mgr` - Calculation manager.
     /// * `expression` - The expression to normalize.
     /// * `new_line` - If true, format with a newline character between expressions.
     ///
@@ -844,25 +952,50 @@ impl CalcUtility {
     /// * Normalized expression.
 
     pub fn normalize_expression(
-        calc_manager: &Rc<RefCell<CalcManager>>,
+        calc_mgr: &CalcManager,
         expression: &str,
         new_line: bool,
     ) -> String {
         let mut calc_expression = CalcExpression::new(
-            calc_manager,
-            calc_manager.borrow().fiscal_year_start(false),
-            calc_manager.borrow().decimal_digits(false),
+            calc_mgr,
+            calc_mgr.fiscal_year_start(false),
+            calc_mgr.decimal_digits(false),
         );
 
         calc_expression.init_expression(None, None, None, expression);
         calc_expression.normalize_expression(new_line)
     }
 
+    /// Format an expression from its compiled expression tree
+    /// (see CalcExpression::format_expression).
+    ///
+    /// # Arguments
+    ///
+    /// * `calc_mgr` - Calculation manager.
+    /// * `expression` - The expression to format.
+    ///
+    /// # Return
+    ///
+    /// * Formatted expression if successful, otherwise error code.
+    pub fn format_expression(
+        calc_mgr: &CalcManager,
+        expression: &str,
+    ) -> Result<String, ElemError> {
+        let mut calc_expression = CalcExpression::new(
+            calc_mgr,
+            calc_mgr.fiscal_year_start(false),
+            calc_mgr.decimal_digits(false),
+        );
+
+        calc_expression.init_expression(None, None, None, expression);
+        calc_expression.format_expression()
+    }
+
     /// Create and return a column list object.
     ///
     /// # Arguments
     ///
-    /// * `calc_manager` - Calculation manager.
+    /// * `calc_mgr` - Calculation manager.
     /// * `elem_type` - The type of table.
     /// * `cashflow` - Search the cashflow preferences.
     ///
@@ -871,11 +1004,10 @@ impl CalcUtility {
     /// * See description.
 
     pub fn parse_columns(
-        calc_manager: &Rc<RefCell<CalcManager>>,
+        calc_mgr: &CalcManager,
         elem_type: crate::TableType,
         cashflow: bool,
     ) -> ListColumn {
-        let calc_mgr = calc_manager.borrow();
         let list_locale = calc_mgr.list_locale();
 
         let mut group = String::from(if !cashflow {
@@ -892,7 +1024,7 @@ impl CalcUtility {
             list_locale.user_locale().locale_str()
         };
 
-        let mut columns = calc_manager.borrow().descriptor_value(
+        let mut columns = calc_mgr.descriptor_value(
             group.as_str(),
             crate::NAME_COLUMNS,
             crate::TYPE_LOCALE,
@@ -902,7 +1034,7 @@ impl CalcUtility {
         );
 
         if columns.is_empty() {
-            columns = calc_manager.borrow().descriptor_value(
+            columns = calc_mgr.descriptor_value(
                 group.as_str(),
                 crate::NAME_COLUMNS,
                 crate::TYPE_CUSTOM,
@@ -940,19 +1072,14 @@ impl CalcUtility {
             let mut code = String::from("");
             let mut format = crate::FormatType::String;
             let mut col_name_index: usize = 0;
-            let mut decimal_digits = calc_manager.borrow().decimal_digits(cashflow);
+            let mut decimal_digits = calc_mgr.decimal_digits(cashflow);
             let mut column_editable: bool = false;
             let mut desc_text: String;
 
-            if calc_manager
-                .borrow()
-                .core_manager()
-                .map_col_names()
-                .get_element_by_key(col_name)
-            {
-                col_name_index = calc_manager.borrow().core_manager().map_col_names().value();
+            if let Some(o) = calc_mgr.core_manager().map_col_names().get_key(col_name) {
+                col_name_index = o.value();
 
-                col_header = calc_manager.borrow().descriptor_value(
+                col_header = calc_mgr.descriptor_value(
                     crate::GROUP_COLHEADER,
                     col_name,
                     crate::TYPE_LOCALE,
@@ -962,7 +1089,7 @@ impl CalcUtility {
                 );
 
                 if col_header.is_empty() {
-                    col_header = calc_manager.borrow().descriptor_value(
+                    col_header = calc_mgr.descriptor_value(
                         crate::GROUP_COLHEADER,
                         col_name,
                         crate::TYPE_CUSTOM,
@@ -982,15 +1109,11 @@ impl CalcUtility {
                     }
                 }
 
-                column_editable = (calc_manager
-                    .borrow()
-                    .core_manager()
-                    .map_col_names()
-                    .value_ext()
+                column_editable = (calc_mgr.core_manager().map_col_names().value_ext()
                     & crate::MAPCOLNAMES_EDITABLE)
                     != 0;
             } else {
-                desc_text = calc_manager.borrow().descriptor_value(
+                desc_text = calc_mgr.descriptor_value(
                     crate::GROUP_COLVALUE,
                     col_name,
                     crate::TYPE_LOCALE,
@@ -999,7 +1122,7 @@ impl CalcUtility {
                     false,
                 );
                 if desc_text.is_empty() {
-                    desc_text = calc_manager.borrow().descriptor_value(
+                    desc_text = calc_mgr.descriptor_value(
                         crate::GROUP_COLVALUE,
                         col_name,
                         crate::TYPE_CUSTOM,
@@ -1023,18 +1146,13 @@ impl CalcUtility {
                     code = String::from(tokens[1].trim());
                     decimal_digits = CoreUtility::parse_integer(tokens[2].trim());
 
-                    if !calc_manager
-                        .borrow()
-                        .core_manager()
-                        .map_col_names()
-                        .get_element_by_key(col_name)
+                    col_name_index = match calc_mgr.core_manager().map_col_names().get_key(col_name)
                     {
-                        continue;
-                    }
-
-                    col_name_index = calc_manager.borrow().core_manager().map_col_names().value();
+                        None => continue,
+                        Some(o) => o.value(),
+                    };
 
-                    col_header = calc_manager.borrow().descriptor_value(
+                    col_header = calc_mgr.descriptor_value(
                         crate::GROUP_COLHEADER,
                         col_name,
                         elem_type.as_str(),
@@ -1043,7 +1161,7 @@ impl CalcUtility {
                         false,
                     );
                     if col_header.is_empty() {
-                        col_header = calc_manager.borrow().descriptor_value(
+                        col_header = calc_mgr.descriptor_value(
                             crate::GROUP_COLHEADER,
                             col_name,
                             crate::TYPE_CUSTOM,
@@ -1072,7 +1190,7 @@ impl CalcUtility {
                     format = CoreUtility::get_format(CoreUtility::parse_integer(tokens[4].trim()));
                     decimal_digits = CoreUtility::parse_integer(tokens[5].trim());
 
-                    col_header = calc_manager.borrow().descriptor_value(
+                    col_header = calc_mgr.descriptor_value(
                         crate::GROUP_COLHEADER,
                         col_name,
                         elem_type.as_str(),
@@ -1081,7 +1199,7 @@ impl CalcUtility {
                         false,
                     );
                     if !col_header.is_empty() {
-                        col_header = calc_manager.borrow().descriptor_value(
+                        col_header = calc_mgr.descriptor_value(
                             crate::GROUP_COLHEADER,
                             col_name,
                             crate::TYPE_CUSTOM,
@@ -1125,7 +1243,8 @@ impl CalcUtility {
                 | crate::ColumnType::ValueToInterest
                 | crate::ColumnType::ValueToPrincipal
                 | crate::ColumnType::AccruedBalance
-                | crate::ColumnType::Balance => {
+                | crate::ColumnType::Balance
+                | crate::ColumnType::DeferredInterest => {
                     format = crate::FormatType::Currency;
                 }
                 crate::ColumnType::EndDate | crate::ColumnType::Date => {
@@ -1156,14 +1275,13 @@ impl CalcUtility {
     ///
     /// # Arguments
     ///
-    /// * `calc_manager` - Calculation manager.
+    /// * `calc_mgr` - Calculation manager.
     ///
     /// # Return
     ///
     /// * See description.
 
-    pub fn parse_summary(calc_manager: &Rc<RefCell<CalcManager>>) -> ListSummary {
-        let calc_mgr = calc_manager.borrow();
+    pub fn parse_summary(calc_mgr: &CalcManager) -> ListSummary {
         let list_locale = calc_mgr.list_locale();
         let list_cashflow = calc_mgr.list_cashflow();
         let preferences = list_cashflow.preferences();
@@ -1173,6 +1291,7 @@ impl CalcUtility {
 
         let list_parameter: &ListParameter;
         let list_descriptor: &ListDescriptor;
+        let list_function: &ListFunction;
         match preferences {
             None => {
                 return list_summary;
@@ -1180,14 +1299,16 @@ impl CalcUtility {
             Some(o) => {
                 list_parameter = o.list_parameter();
                 list_descriptor = o.list_descriptor();
+                list_function = o.list_function();
             }
         }
 
         let mut calc_expression = CalcExpression::new(
-            calc_manager,
+            calc_mgr,
             calc_mgr.fiscal_year_start(true),
             calc_mgr.decimal_digits(true),
         );
+        calc_expression.init_list_function(Option::from(list_function));
 
         let mut last_yield: Decimal = match list_cashflow.elem_balance_result() {
             None => {
@@ -1214,10 +1335,12 @@ impl CalcUtility {
                 };
 
             match list_cashflow.calculate().calculate_yield(
+                calc_mgr,
                 list_event,
                 &mut list_am,
                 &mut list_stat_helper,
                 dec!(0.0),
+                &calc_mgr.solver_settings(),
             ) {
                 Err(_e) => {
                     last_yield = dec!(0.0);
@@ -1317,7 +1440,7 @@ impl CalcUtility {
 
             match result {
                 Err(e) => {
-                    let error_string = calc_mgr.get_error_string(e);
+                    let error_string = calc_mgr.get_error_string(e.kind());
                     label_str = format!("{}{}", crate::ERROR_PREFIX, error_string);
                 }
                 Ok(o) => {
@@ -1356,7 +1479,7 @@ impl CalcUtility {
             );
             match result {
                 Err(e) => {
-                    let error_string = calc_mgr.get_error_string(e);
+                    let error_string = calc_mgr.get_error_string(e.kind());
                     result_str = format!("{}{}", crate::ERROR_PREFIX, error_string);
                 }
                 Ok(o) => {
@@ -1394,7 +1517,7 @@ impl CalcUtility {
     ///
     /// # Arguments
     ///
-    /// * `calc_manager` - Calculation manager.
+    /// * `calc_mgr` - Calculation manager.
     /// * `col_name_index` - Column name index.
     /// * `col_type` - Column type.
     /// * `col_code` - Column code.
@@ -1406,7 +1529,7 @@ impl CalcUtility {
     /// * See description.
 
     pub fn set_event_value(
-        calc_manager: &Rc<RefCell<CalcManager>>,
+        calc_mgr: &mut CalcManager,
         col_name_index: usize,
         col_type: &str,
         col_code: &str,
@@ -1416,7 +1539,6 @@ impl CalcUtility {
         let mut result = String::from("");
 
         {
-            let calc_mgr = calc_manager.borrow();
             let list_locale = calc_mgr.list_locale();
             let list_cashflow = calc_mgr.list_cashflow();
             let list_event_opt = list_cashflow.list_event();
@@ -1477,7 +1599,6 @@ impl CalcUtility {
         }
 
         {
-            let mut calc_mgr = calc_manager.borrow_mut();
             let list_cashflow = calc_mgr.list_cashflow_mut();
             let mut list_event_opt = list_cashflow.list_event_mut();
 
@@ -1490,7 +1611,9 @@ impl CalcUtility {
 
             match CoreUtility::get_col_name(col_name_index) {
                 crate::ColumnType::Date => {
-                    list_event.set_event_date(CoreUtility::parse_date(result.as_str()));
+                    if let Some(o) = CoreUtility::parse_date(result.as_str()) {
+                        list_event.set_event_date(o);
+                    }
                 }
                 crate::ColumnType::DateExpr => {
                     list_event.set_date_expr(result.as_str());
@@ -1546,10 +1669,9 @@ impl CalcUtility {
             false,
         );
 
-        result = CalcUtility::get_event_value(calc_manager, &column);
+        result = CalcUtility::get_event_value(calc_mgr, &column);
 
         {
-            let mut calc_mgr = calc_manager.borrow_mut();
             let list_cashflow = calc_mgr.list_cashflow_mut();
             let mut list_event_opt = list_cashflow.list_event_mut();
 
@@ -1562,7 +1684,6 @@ impl CalcUtility {
         }
 
         {
-            let calc_mgr = calc_manager.borrow();
             let list_locale = calc_mgr.list_locale();
             list_locale.select_event_locale("");
         }
@@ -1574,7 +1695,7 @@ impl CalcUtility {
     ///
     /// # Arguments
     ///
-    /// * `calc_manager` - Calculation manager.
+    /// * `calc_mgr` - Calculation manager.
     /// * `index` - Event row index.
     /// * `ext_param` - Extension values to set.
     ///
@@ -1583,11 +1704,10 @@ impl CalcUtility {
     /// * True if successful, otherwise false.
 
     pub fn set_extension_values(
-        calc_manager: &Rc<RefCell<CalcManager>>,
+        calc_mgr: &mut CalcManager,
         index: usize,
         ext_param: &ElemExtension,
     ) -> bool {
-        let mut calc_mgr = calc_manager.borrow_mut();
         let list_cashflow = calc_mgr.list_cashflow_mut();
         let mut list_event_opt = list_cashflow.list_event_mut();
 
@@ -1647,7 +1767,7 @@ impl CalcUtility {
     ///
     /// # Arguments
     ///
-    /// * `calc_manager` - Calculation manager.
+    /// * `calc_mgr` - Calculation manager.
     /// * `index_param` - Event row index.
     /// * `parameters` - Parameters to set.
     ///
@@ -1656,11 +1776,10 @@ impl CalcUtility {
     /// * True if successful, otherwise false.
 
     pub fn set_parameter_values(
-        calc_manager: &Rc<RefCell<CalcManager>>,
+        calc_mgr: &mut CalcManager,
         index_param: usize,
         parameters: Vec<String>,
     ) -> bool {
-        let mut calc_mgr = calc_manager.borrow_mut();
         let list_cashflow = calc_mgr.list_cashflow_mut();
 
         let list_event: &mut ListEvent = match list_cashflow.list_event_mut() {
@@ -7,19 +7,13 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-use std::cell::{Cell, Ref, RefCell};
-use std::rc::Rc;
-
 use rust_decimal::prelude::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
-use super::CalcManager;
-use crate::core::{ListDescriptor, ListParameter};
+use crate::core::{ElemSolverSettings, ListDescriptor, ListFunction, ListParameter};
 use crate::ListTrait;
 
 pub struct ElemPreferences {
-    /// Calculator manager element.
-    calc_manager: Rc<RefCell<CalcManager>>,
-
     /// ISO language code (ISO 639)_ISO country code (ISO 3166).
     locale_str: String,
     /// Cross rate international currency code (e.g., USD, GBP, JPY, AUD, EUR, other currency code).
@@ -29,9 +23,9 @@ pub struct ElemPreferences {
     /// Template group name.
     group: String,
     /// Start of fiscal year in MMDD format.
-    fiscal_year_start: Cell<usize>,
+    fiscal_year_start: AtomicUsize,
     /// Number of significant decimal digits.
-    decimal_digits: Cell<usize>,
+    decimal_digits: AtomicUsize,
     /// The target value for calculations (e.g., for loans this is usually 0)
     target: Decimal,
 
@@ -39,6 +33,8 @@ pub struct ElemPreferences {
     list_parameter: ListParameter,
     /// Descriptor list.
     list_descriptor: ListDescriptor,
+    /// User-defined function list.
+    list_function: ListFunction,
     /// Combine principal change events that are identical except their
     /// values and ListDescriptor objects (1=set, 0=reset, -1=not set).
     combine_principal: i32,
@@ -48,6 +44,8 @@ pub struct ElemPreferences {
     /// Eliminate statistic events from the amortization list
     /// (1=set, 0=reset, -1=not set).
     statistic_events: i32,
+    /// Settings of the interest, periods, principal and yield solvers.
+    solver_settings: ElemSolverSettings,
 }
 
 /// Preferences definition implementation.
@@ -57,7 +55,6 @@ impl ElemPreferences {
     ///
     /// # Arguments
     ///
-    /// * `calc_manager_param` - Calculator manager element.
     /// * `locale_str_param` - Locale string.
     /// * `cross_rate_code_param` - Cross rate code.
     /// * `default_encoding_param` - Default encoding.
@@ -80,7 +77,6 @@ impl ElemPreferences {
     #[allow(clippy::too_many_arguments)]
 
     pub fn new(
-        calc_manager_param: &Rc<RefCell<CalcManager>>,
         locale_str_param: &str,
         cross_rate_code_param: &str,
         default_encoding_param: &str,
@@ -107,19 +103,20 @@ impl ElemPreferences {
         };
 
         ElemPreferences {
-            calc_manager: Rc::clone(calc_manager_param),
             locale_str: String::from(locale_str_param),
             cross_rate_code: String::from(cross_rate_code_param),
             default_encoding: String::from(default_encoding_param),
             group: String::from(group_param),
-            fiscal_year_start: Cell::new(fiscal_year_start_param),
-            decimal_digits: Cell::new(decimal_digits_param),
+            fiscal_year_start: AtomicUsize::new(fiscal_year_start_param),
+            decimal_digits: AtomicUsize::new(decimal_digits_param),
             target: target_param,
             combine_principal: combine_principal_param,
             compress_descriptor: compress_descriptor_param,
             statistic_events: statistic_events_param,
             list_parameter: new_list_parameter,
             list_descriptor: new_list_descriptor,
+            list_function: ListFunction::new(),
+            solver_settings: ElemSolverSettings::new(),
         }
     }
 
@@ -130,24 +127,16 @@ impl ElemPreferences {
         self.cross_rate_code = String::from("");
         self.default_encoding = String::from("");
         self.group = String::from("");
-        self.fiscal_year_start.set(0);
-        self.decimal_digits.set(0);
+        self.fiscal_year_start.store(0, Ordering::Relaxed);
+        self.decimal_digits.store(0, Ordering::Relaxed);
         self.combine_principal = -1;
         self.compress_descriptor = -1;
         self.statistic_events = -1;
 
         self.list_parameter.clear();
         self.list_descriptor.clear();
-    }
-
-    /// Returns the calculation manager.
-    ///
-    /// # Return
-    ///
-    /// * See description.
-
-    fn calc_mgr(&self) -> Ref<CalcManager> {
-        self.calc_manager.borrow()
+        self.list_function.clear();
+        self.solver_settings.clear();
     }
 
     /// Copy this preferences element and return a new preferences element.
@@ -161,34 +150,13 @@ impl ElemPreferences {
     /// * See description.
 
     pub fn copy(&self, updating_json: bool) -> ElemPreferences {
-        self.copy_with_calc_manager(&self.calc_manager, updating_json)
-    }
-
-    /// Copy this preferences element and return a new preferences element.
-    ///
-    /// # Arguments
-    ///
-    /// * `calc_manager` - Calculator manager element.
-    /// * `elem_level_param` - Element level
-    /// * `updating_json` - Updating from Json.
-    ///
-    /// # Return
-    ///
-    /// * See description.
-
-    pub fn copy_with_calc_manager(
-        &self,
-        calc_manager: &Rc<RefCell<CalcManager>>,
-        updating_json: bool,
-    ) -> ElemPreferences {
-        ElemPreferences::new(
-            calc_manager,
+        let mut preferences = ElemPreferences::new(
             self.locale_str.as_str(),
             self.cross_rate_code.as_str(),
             self.default_encoding.as_str(),
             self.group.as_str(),
-            self.fiscal_year_start.get(),
-            self.decimal_digits.get(),
+            self.fiscal_year_start.load(Ordering::Relaxed),
+            self.decimal_digits.load(Ordering::Relaxed),
             self.target,
             self.combine_principal,
             self.compress_descriptor,
@@ -197,7 +165,12 @@ impl ElemPreferences {
             Option::from(&self.list_descriptor.copy(false, updating_json)),
             false,
             updating_json,
-        )
+        );
+
+        preferences.set_list_function(self.list_function.copy());
+        preferences.set_solver_settings(self.solver_settings);
+
+        preferences
     }
 
     /// Get the locale.
@@ -247,7 +220,7 @@ impl ElemPreferences {
     /// * See description.
 
     pub fn fiscal_year_start(&self) -> usize {
-        self.fiscal_year_start.get()
+        self.fiscal_year_start.load(Ordering::Relaxed)
     }
 
     /// Get the number of significant decimal digits.
@@ -257,7 +230,7 @@ impl ElemPreferences {
     /// * See description.
 
     pub fn decimal_digits(&self) -> usize {
-        self.decimal_digits.get()
+        self.decimal_digits.load(Ordering::Relaxed)
     }
 
     /// Get the target value.
@@ -343,6 +316,33 @@ impl ElemPreferences {
         &mut self.list_descriptor
     }
 
+    /// Get the user-defined function list.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn list_function(&self) -> &ListFunction {
+        &self.list_function
+    }
+
+    /// Get the mut user-defined function list.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn list_function_mut(&mut self) -> &mut ListFunction {
+        &mut self.list_function
+    }
+
+    /// Get the solver settings.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn solver_settings(&self) -> &ElemSolverSettings {
+        &self.solver_settings
+    }
+
     /// Set the locale name.
     ///
     /// # Arguments
@@ -400,7 +400,8 @@ impl ElemPreferences {
     /// * `fiscal_year_start_param` - See description.
 
     pub fn set_fiscal_year_start(&self, fiscal_year_start_param: usize) {
-        self.fiscal_year_start.set(fiscal_year_start_param);
+        self.fiscal_year_start
+            .store(fiscal_year_start_param, Ordering::Relaxed);
     }
 
     /// Set the number of significant decimal digits.
@@ -410,7 +411,8 @@ impl ElemPreferences {
     /// * `decimal_digits_param` - See description.
 
     pub fn set_decimal_digits(&self, decimal_digits_param: usize) {
-        self.decimal_digits.set(decimal_digits_param);
+        self.decimal_digits
+            .store(decimal_digits_param, Ordering::Relaxed);
     }
 
     /// Set the target value.
@@ -455,4 +457,22 @@ impl ElemPreferences {
     pub fn set_statistic_events(&mut self, statistic_events_param: i32) {
         self.statistic_events = statistic_events_param;
     }
+
+    /// Set the user-defined function list.
+    ///
+    /// # Arguments
+    ///
+    /// * `list_function_param` - See description.
+    pub fn set_list_function(&mut self, list_function_param: ListFunction) {
+        self.list_function = list_function_param;
+    }
+
+    /// Set the solver settings.
+    ///
+    /// # Arguments
+    ///
+    /// * `solver_settings_param` - See description.
+    pub fn set_solver_settings(&mut self, solver_settings_param: ElemSolverSettings) {
+        self.solver_settings = solver_settings_param;
+    }
 }
@@ -0,0 +1,72 @@
+//! The schema violation element definition.
+// Copyright (c) 2021 ShiftLeft Software
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::fmt;
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct ElemSchemaViolation {
+    /// JSON pointer (RFC 6901) of the offending value ("" for the document).
+    pointer: String,
+    /// Description of the violated schema rule.
+    message: String,
+}
+
+/// The schema violation element display implementation.
+impl fmt::Display for ElemSchemaViolation {
+    /// Format the violation as "#pointer: message".
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - Formatter.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "#{}: {}", self.pointer, self.message)
+    }
+}
+
+/// The schema violation element implementation.
+impl ElemSchemaViolation {
+    /// Create and return a new schema violation element.
+    ///
+    /// # Arguments
+    ///
+    /// * `pointer_param` - JSON pointer of the offending value.
+    /// * `message_param` - Description of the violated rule.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn new(pointer_param: &str, message_param: &str) -> ElemSchemaViolation {
+        ElemSchemaViolation {
+            pointer: String::from(pointer_param),
+            message: String::from(message_param),
+        }
+    }
+
+    /// Get the JSON pointer of the offending value.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn pointer(&self) -> &str {
+        self.pointer.as_str()
+    }
+
+    /// Get the description of the violated rule.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn message(&self) -> &str {
+        self.message.as_str()
+    }
+}
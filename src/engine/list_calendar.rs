@@ -0,0 +1,257 @@
+//! List of business day calendars.
+// Copyright (c) 2021 ShiftLeft Software
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::core::ElemCalendar;
+use crate::ListTrait;
+
+pub struct ListCalendar {
+    /// The list of calendars.
+    list_calendar: Vec<ElemCalendar>,
+
+    /// The index of the currently selected calendar element.
+    list_index: AtomicUsize,
+}
+
+/// List of calendars default implementation.
+impl Default for ListCalendar {
+    /// Create a new calendar list.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn default() -> Self {
+        ListCalendar::new()
+    }
+}
+
+/// List of calendars list implementation.
+impl ListTrait for ListCalendar {
+    /// Clear all calendars from the calendar list.
+    fn clear(&mut self) {
+        self.list_calendar.clear();
+        self.list_index.store(usize::MAX, Ordering::Relaxed);
+    }
+
+    /// Get the count of the calendar list.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn count(&self) -> usize {
+        self.list_calendar.len()
+    }
+
+    /// Get the index of the selected calendar (starting from 0).
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn index(&self) -> usize {
+        self.list_index.load(Ordering::Relaxed)
+    }
+
+    /// Select a calendar based upon an index value.
+    ///
+    /// # Arguments
+    ///
+    /// * `index_param` - The index value of the calendar to select (starting from 0).
+    ///
+    /// # Return
+    ///
+    /// * True if successful, otherwise false.
+    fn get_element(&self, index_param: usize) -> bool {
+        if index_param >= self.list_calendar.len() {
+            return false;
+        }
+
+        self.set_index(index_param);
+
+        true
+    }
+
+    /// Set the list index.
+    ///
+    /// # Arguments
+    ///
+    /// * `index_param` - See description.
+    fn set_index(&self, index_param: usize) -> bool {
+        if index_param >= self.list_calendar.len() {
+            return false;
+        }
+
+        self.list_index.store(index_param, Ordering::Relaxed);
+
+        true
+    }
+}
+
+/// List of calendars implementation.
+impl ListCalendar {
+    /// Create and return a new list of calendars.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn new() -> ListCalendar {
+        ListCalendar {
+            list_calendar: Vec::new(),
+            list_index: AtomicUsize::new(usize::MAX),
+        }
+    }
+
+    /// Add a new calendar into the calendar list.
+    /// A calendar with the same name is replaced.
+    ///
+    /// # Arguments
+    ///
+    /// * `calendar` - The calendar to add.
+    ///
+    /// # Return
+    ///
+    /// * True if successful, otherwise false.
+    pub fn add_calendar(&mut self, calendar: ElemCalendar) -> bool {
+        if self.get_element_by_name(calendar.name()) {
+            let index = self.list_index.load(Ordering::Relaxed);
+            self.list_calendar[index] = calendar;
+
+            return true;
+        }
+
+        self.list_calendar.push(calendar);
+        self.list_index
+            .store(self.list_calendar.len() - 1, Ordering::Relaxed);
+
+        true
+    }
+
+    /// Append calendars to the calendar list.
+    ///
+    /// # Arguments
+    ///
+    /// * `list_calendar` - The calendars to append.
+    pub fn append_calendars(&mut self, list_calendar: ListCalendar) {
+        for calendar in list_calendar.list_calendar {
+            self.add_calendar(calendar);
+        }
+    }
+
+    /// Copy all calendars from the calendar list
+    /// and return a new calendar list.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn copy(&self) -> ListCalendar {
+        ListCalendar {
+            list_calendar: self.list_calendar.clone(),
+            list_index: AtomicUsize::new(self.list_index.load(Ordering::Relaxed)),
+        }
+    }
+
+    /// Get the selected calendar.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn calendar(&self) -> &ElemCalendar {
+        match self
+            .list_calendar
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
+            None => {
+                panic!("Calendar list index not set");
+            }
+            Some(o) => o,
+        }
+    }
+
+    /// Get the name of the selected calendar.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn name(&self) -> &str {
+        self.calendar().name()
+    }
+
+    /// Select a calendar based upon its name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the calendar.
+    ///
+    /// # Return
+    ///
+    /// * True if successful, otherwise false.
+    pub fn get_element_by_name(&self, name: &str) -> bool {
+        for (index, elem) in self.list_calendar.iter().enumerate() {
+            if name == elem.name() {
+                self.set_index(index);
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Resolve a calendar name and return a copy of the calendar.
+    /// Names joined with "+" (e.g., "US+GB") resolve to a joint
+    /// calendar. An empty name resolves to a calendar with a
+    /// Saturday and Sunday weekend and no holidays.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the calendar.
+    ///
+    /// # Return
+    ///
+    /// * The calendar or None if a name is not found.
+    pub fn resolve(&self, name: &str) -> Option<ElemCalendar> {
+        if name.is_empty() {
+            return Option::from(ElemCalendar::new(""));
+        }
+
+        let mut calendars: Vec<&ElemCalendar> = Vec::new();
+        for part in name.split('+') {
+            match self.list_calendar.iter().find(|o| o.name() == part.trim()) {
+                None => return None,
+                Some(o) => calendars.push(o),
+            }
+        }
+
+        if calendars.len() == 1 {
+            return Option::from(calendars[0].clone());
+        }
+
+        Option::from(ElemCalendar::new_joint(name, calendars.as_slice()))
+    }
+
+    /// Remove the selected calendar from the calendar list.
+    ///
+    /// # Return
+    ///
+    /// * True if successful, otherwise false.
+    pub fn remove(&mut self) -> bool {
+        if self.list_index.load(Ordering::Relaxed) >= self.list_calendar.len() {
+            return false;
+        }
+
+        self.list_calendar
+            .remove(self.list_index.load(Ordering::Relaxed));
+        if self.list_index.load(Ordering::Relaxed) > 0 {
+            self.list_index.store(
+                self.list_index.load(Ordering::Relaxed) - 1,
+                Ordering::Relaxed,
+            );
+        }
+
+        true
+    }
+}
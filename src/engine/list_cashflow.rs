@@ -7,25 +7,21 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-use std::cell::{Cell, Ref, RefCell};
 use std::cmp::Ordering::Equal;
-use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 use rust_decimal::prelude::*;
 
 use super::{CalcCalculate, CalcManager, ElemCashflow, ElemCashflowStats, ElemPreferences};
-use crate::core::{ElemBalanceResult, ListAmortization, ListEvent, ListStatisticHelper};
+use crate::core::{ElemBalanceResult, ElemError, ListAmortization, ListEvent, ListStatisticHelper};
 use crate::ListTrait;
 
 pub struct ListCashflow {
-    /// Calculator manager element.
-    calc_manager: Rc<RefCell<CalcManager>>,
-
     /// The list of cashflows.
     list_cashflow: Vec<ElemCashflow>,
 
     /// The index of the currently selected cashflow element.
-    list_index: Cell<usize>,
+    list_index: AtomicUsize,
 }
 
 /// List of currently active cashflows list implementation.
@@ -35,7 +31,7 @@ impl ListTrait for ListCashflow {
 
     fn clear(&mut self) {
         self.list_cashflow.clear();
-        self.list_index.set(usize::MAX);
+        self.list_index.store(usize::MAX, Ordering::Relaxed);
     }
 
     /// Get the count of the cashflow list.
@@ -55,7 +51,7 @@ impl ListTrait for ListCashflow {
     /// * See description.
 
     fn index(&self) -> usize {
-        self.list_index.get()
+        self.list_index.load(Ordering::Relaxed)
     }
 
     /// Select a cashflow based upon an index value.
@@ -89,63 +85,40 @@ impl ListTrait for ListCashflow {
             return false;
         }
 
-        self.list_index.set(index_param);
-
-        if self.calc_mgr().list_cashflow().index() >= // From deserialize
-            self.calc_mgr().list_cashflow().count()
-        {
-            return true;
-        }
-
-        self.calc_mgr()
-            .list_locale()
-            .select_cashflow_locale(self.calc_mgr().locale(true).as_str());
+        self.list_index.store(index_param, Ordering::Relaxed);
 
         true
     }
 }
 
+/// List of currently active cashflows default implementation.
+impl Default for ListCashflow {
+    /// Create and return a new list cashflow.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn default() -> Self {
+        ListCashflow::new()
+    }
+}
+
 /// List of currently active cashflows implementation.
 
 impl ListCashflow {
     /// Create and return a new list cashflow.
     ///
-    /// # Arguments
-    ///
-    /// * `calc_manager_param` - Calculation manager.
-    ///
     /// # Return
     ///
     /// * See description.   
 
-    pub fn new(calc_manager_param: &Rc<RefCell<CalcManager>>) -> ListCashflow {
+    pub fn new() -> ListCashflow {
         ListCashflow {
-            calc_manager: Rc::clone(calc_manager_param),
             list_cashflow: Vec::new(),
-            list_index: Cell::new(usize::MAX),
+            list_index: AtomicUsize::new(usize::MAX),
         }
     }
 
-    /// Returns the calculation manager element.
-    ///
-    /// # Return
-    ///
-    /// * See description.
-
-    fn calc_manager(&self) -> &Rc<RefCell<CalcManager>> {
-        &self.calc_manager
-    }
-
-    /// Returns the calculation manager.
-    ///
-    /// # Return
-    ///
-    /// * See description.
-
-    fn calc_mgr(&self) -> Ref<CalcManager> {
-        self.calc_manager.borrow()
-    }
-
     /// Prepare to add a new cashflow into the cashflow list.
     /// If the name results in a duplicate entry, an
     /// incrementing number starting from 2 is appended to the
@@ -153,6 +126,7 @@ impl ListCashflow {
     ///
     /// # Arguments
     ///
+    /// * `calc_mgr` - Calculation manager.
     /// * `name_param` - The name of the cashflow.
     /// * `list_event_param` - A newly created event list.
     /// * `elem_preferences_param` - Original existing preferences
@@ -165,13 +139,14 @@ impl ListCashflow {
 
     pub fn add_cashflow_prep(
         &self,
+        calc_mgr: &CalcManager,
         name_param: &str,
         list_event_param: Option<ListEvent>,
         elem_preferences_param: Option<ElemPreferences>,
         group_param: &str,
-    ) -> Result<ElemCashflow, crate::ErrorType> {
+    ) -> Result<ElemCashflow, ElemError> {
         let name: String = String::from(name_param);
-        let updating_json = self.calc_mgr().updating_json();
+        let updating_json = calc_mgr.updating_json();
 
         if self.get_element_by_name(name_param, false) {
             // Check for duplicate name
@@ -190,9 +165,8 @@ impl ListCashflow {
 
         match elem_preferences_param.as_ref() {
             None => {
-                calculate = CalcCalculate::new(self.calc_manager(), None);
+                calculate = CalcCalculate::new(calc_mgr, None, None);
                 elem_preferences = Option::from(ElemPreferences::new(
-                    self.calc_manager(),
                     "",
                     "",
                     "",
@@ -216,11 +190,13 @@ impl ListCashflow {
                     group_param
                 });
 
-                calculate =
-                    CalcCalculate::new(self.calc_manager(), Option::from(o.list_descriptor()));
+                calculate = CalcCalculate::new(
+                    calc_mgr,
+                    Option::from(o.list_descriptor()),
+                    Option::from(o.list_function()),
+                );
 
                 elem_preferences = Option::from(ElemPreferences::new(
-                    self.calc_manager(),
                     "",
                     o.cross_rate_code(),
                     o.default_encoding(),
@@ -245,7 +221,7 @@ impl ListCashflow {
         }
 
         let elem_cashflow: ElemCashflow = match elem_preferences {
-            None => return Err(crate::ErrorType::Cashflow),
+            None => return Err(ElemError::new(crate::ErrorType::Cashflow)),
             Some(o) => ElemCashflow::new(name.as_str(), o, list_event_opt, calculate),
         };
 
@@ -283,66 +259,44 @@ impl ListCashflow {
     }
 
     /// Copy the list cashflow and return a new list cashflow.
+    /// The selected cashflow is also selected in the new list
+    /// cashflow. The selected cashflow of this list is not changed.
     ///
     /// # Arguments
     ///
-    /// * `calc_manager_param` - Calculation manager.
+    /// * `calc_mgr` - Calculation manager.
     ///
     /// # Return
     ///
     /// * See description.
 
-    pub fn copy_with_calc_manager(
-        &self,
-        calc_manager_param: &Rc<RefCell<CalcManager>>,
-    ) -> ListCashflow {
-        let mut list_cashflow = ListCashflow::new(calc_manager_param);
-
-        let mut index: usize = 0;
-        loop {
-            if !self.get_element(index) {
-                break;
-            }
-
-            let preferences: ElemPreferences;
-            let group: String;
-
-            match self.preferences() {
-                None => {
-                    break;
+    pub fn copy_with_calc_manager(&self, calc_mgr: &CalcManager) -> ListCashflow {
+        let mut list_cashflow = ListCashflow::new();
+
+        for elem_cashflow in self.list_cashflow.iter() {
+            let preferences = elem_cashflow.preferences();
+            let new_list_event = elem_cashflow.list_event().copy(true);
+
+            match list_cashflow.add_cashflow_prep(
+                calc_mgr,
+                elem_cashflow.name(),
+                Option::from(new_list_event),
+                Option::from(preferences.copy(true)),
+                preferences.group(),
+            ) {
+                Err(_e) => {
+                    panic!("Cannot create cashflow")
                 }
-                Some(o) => {
-                    preferences = o.copy(true);
-                    group = String::from(o.group());
+                Ok(o) => {
+                    list_cashflow.add_cashflow(o);
                 }
             }
+        }
 
-            match self.list_event() {
-                None => {
-                    break;
-                }
-                Some(o) => {
-                    let new_list_event = o.copy(true);
-
-                    match list_cashflow.add_cashflow_prep(
-                        self.name(),
-                        Option::from(new_list_event),
-                        Option::from(preferences),
-                        group.as_str(),
-                    ) {
-                        Err(_e) => {
-                            panic!("Cannot create cashflow")
-                        }
-                        Ok(o) => {
-                            list_cashflow.add_cashflow(o);
-                            list_cashflow.get_element_by_name(self.name(), true);
-                        }
-                    }
-                }
-            }
+        list_cashflow
+            .list_index
+            .store(self.list_index.load(Ordering::Relaxed), Ordering::Relaxed);
 
-            index += 1;
-        }
         list_cashflow
     }
 
@@ -429,22 +383,18 @@ impl ListCashflow {
         compress_descriptor: bool,
         omit_statistic_events: bool,
         updating_json: bool,
-    ) -> Result<ListAmortization, crate::ErrorType> {
-        match self.calc_mgr().list_cashflow().list_amortization() {
-            None => Err(crate::ErrorType::Cashflow),
+    ) -> Result<ListAmortization, ElemError> {
+        match self.list_amortization() {
+            None => Err(ElemError::new(crate::ErrorType::Cashflow)),
             Some(o) => {
-                let result = self
-                    .calc_mgr()
-                    .list_cashflow()
-                    .calculate()
-                    .create_cashflow_output(
-                        o,
-                        include_rollups,
-                        include_details,
-                        compress_descriptor,
-                        omit_statistic_events,
-                        updating_json,
-                    );
+                let result = self.calculate().create_cashflow_output(
+                    o,
+                    include_rollups,
+                    include_details,
+                    compress_descriptor,
+                    omit_statistic_events,
+                    updating_json,
+                );
 
                 match result {
                     Err(e) => Err(e),
@@ -461,7 +411,10 @@ impl ListCashflow {
     /// * See description.
 
     pub fn name(&self) -> &str {
-        match self.list_cashflow.get(self.list_index.get()) {
+        match self
+            .list_cashflow
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 panic!("Cashflow list index not set");
             }
@@ -476,7 +429,10 @@ impl ListCashflow {
     /// * See description.
 
     pub fn preferences(&self) -> Option<&ElemPreferences> {
-        match self.list_cashflow.get(self.list_index.get()) {
+        match self
+            .list_cashflow
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 panic!("Cashflow list index not set");
             }
@@ -491,7 +447,10 @@ impl ListCashflow {
     /// * See description.
 
     pub fn preferences_mut(&mut self) -> Option<&mut ElemPreferences> {
-        match self.list_cashflow.get_mut(self.list_index.get()) {
+        match self
+            .list_cashflow
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 panic!("Cashflow list index not set");
             }
@@ -506,7 +465,10 @@ impl ListCashflow {
     /// * See description.
 
     pub fn list_event(&self) -> Option<&ListEvent> {
-        match self.list_cashflow.get(self.list_index.get()) {
+        match self
+            .list_cashflow
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 panic!("Cashflow list index not set");
             }
@@ -521,7 +483,10 @@ impl ListCashflow {
     /// * See description.
 
     pub fn list_event_mut(&mut self) -> Option<&mut ListEvent> {
-        match self.list_cashflow.get_mut(self.list_index.get()) {
+        match self
+            .list_cashflow
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 panic!("Cashflow list index not set");
             }
@@ -536,7 +501,10 @@ impl ListCashflow {
     /// * See description.
 
     pub fn list_amortization(&self) -> Option<&ListAmortization> {
-        match self.list_cashflow.get(self.list_index.get()) {
+        match self
+            .list_cashflow
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 panic!("Cashflow list index not set");
             }
@@ -551,7 +519,10 @@ impl ListCashflow {
     /// * See description.
 
     pub fn calculate(&self) -> &CalcCalculate {
-        match self.list_cashflow.get(self.list_index.get()) {
+        match self
+            .list_cashflow
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 panic!("Cashflow list index not set");
             }
@@ -566,7 +537,10 @@ impl ListCashflow {
     /// * See description.
 
     pub fn list_statistic_helper(&self) -> Option<&ListStatisticHelper> {
-        match self.list_cashflow.get(self.list_index.get()) {
+        match self
+            .list_cashflow
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 panic!("Cashflow list index not set");
             }
@@ -581,7 +555,10 @@ impl ListCashflow {
     /// * See description.
 
     pub fn elem_balance_result(&self) -> Option<&ElemBalanceResult> {
-        match self.list_cashflow.get(self.list_index.get()) {
+        match self
+            .list_cashflow
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 panic!("Cashflow list index not set");
             }
@@ -596,7 +573,10 @@ impl ListCashflow {
     /// * See description.
 
     pub fn last_amortization_index(&self) -> usize {
-        match self.list_cashflow.get(self.list_index.get()) {
+        match self
+            .list_cashflow
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 panic!("Cashflow list index not set");
             }
@@ -611,7 +591,10 @@ impl ListCashflow {
     /// * See description.
 
     pub fn cashflow_valid(&self) -> bool {
-        match self.list_cashflow.get(self.list_index.get()) {
+        match self
+            .list_cashflow
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 panic!("Cashflow list index not set");
             }
@@ -626,7 +609,10 @@ impl ListCashflow {
     /// * See description.
 
     pub fn updated(&self) -> bool {
-        match self.list_cashflow.get(self.list_index.get()) {
+        match self
+            .list_cashflow
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 panic!("Cashflow list index not set");
             }
@@ -685,13 +671,17 @@ impl ListCashflow {
     /// * True if successful, otherwise false.
 
     pub fn remove(&mut self) -> bool {
-        if self.list_index.get() >= self.list_cashflow.len() {
+        if self.list_index.load(Ordering::Relaxed) >= self.list_cashflow.len() {
             return false;
         }
 
-        self.list_cashflow.remove(self.list_index.get());
-        if self.list_index.get() > 0 {
-            self.list_index.set(self.list_index.get() - 1);
+        self.list_cashflow
+            .remove(self.list_index.load(Ordering::Relaxed));
+        if self.list_index.load(Ordering::Relaxed) > 0 {
+            self.list_index.store(
+                self.list_index.load(Ordering::Relaxed) - 1,
+                Ordering::Relaxed,
+            );
         }
 
         true
@@ -700,7 +690,10 @@ impl ListCashflow {
     /// Reset the updated value.
 
     pub fn reset_updated(&mut self) {
-        match self.list_cashflow.get_mut(self.list_index.get()) {
+        match self
+            .list_cashflow
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
             None => {}
             Some(o) => {
                 o.set_updated(false);
@@ -715,7 +708,10 @@ impl ListCashflow {
     /// * `valid_param` - See description.
 
     pub fn set_cashflow_valid(&mut self, valid_param: bool) -> bool {
-        match self.list_cashflow.get_mut(self.list_index.get()) {
+        match self
+            .list_cashflow
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
             None => false,
             Some(o) => {
                 o.set_cashflow_valid(valid_param);
@@ -736,13 +732,16 @@ impl ListCashflow {
     /// * True if successful, otherwise false.
 
     pub fn set_name(&mut self, name_param: &str) -> bool {
-        if self.list_index.get() >= self.list_cashflow.len()
+        if self.list_index.load(Ordering::Relaxed) >= self.list_cashflow.len()
             || self.get_element_by_name(name_param, false)
         {
             return false;
         }
 
-        match self.list_cashflow.get_mut(self.list_index.get()) {
+        match self
+            .list_cashflow
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 return false;
             }
@@ -762,7 +761,7 @@ impl ListCashflow {
                 return false;
             }
             Some(o) => {
-                self.list_index.set(o);
+                self.list_index.store(o, Ordering::Relaxed);
             }
         }
 
@@ -776,7 +775,10 @@ impl ListCashflow {
     /// * `list_event_param` - See description.
 
     pub fn set_list_event(&mut self, list_event_param: ListEvent) -> bool {
-        match self.list_cashflow.get_mut(self.list_index.get()) {
+        match self
+            .list_cashflow
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
             None => false,
             Some(o) => {
                 o.set_list_event(list_event_param);
@@ -792,7 +794,10 @@ impl ListCashflow {
     /// * `list_am_param` - See description.
 
     pub fn set_list_amortization(&mut self, list_am_param: ListAmortization) -> bool {
-        match self.list_cashflow.get_mut(self.list_index.get()) {
+        match self
+            .list_cashflow
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
             None => false,
             Some(o) => {
                 o.set_list_amortization(list_am_param);
@@ -808,7 +813,10 @@ impl ListCashflow {
     /// * `statistic_helper_param` - See description.
 
     pub fn set_statistic_helper(&mut self, statistic_helper_param: ListStatisticHelper) -> bool {
-        match self.list_cashflow.get_mut(self.list_index.get()) {
+        match self
+            .list_cashflow
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
             None => false,
             Some(o) => {
                 o.set_list_statistic_helper(statistic_helper_param);
@@ -827,7 +835,10 @@ impl ListCashflow {
         &mut self,
         elem_balance_result_param: ElemBalanceResult,
     ) -> bool {
-        match self.list_cashflow.get_mut(self.list_index.get()) {
+        match self
+            .list_cashflow
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
             None => false,
             Some(o) => {
                 o.set_elem_balance_result(elem_balance_result_param);
@@ -843,7 +854,10 @@ impl ListCashflow {
     /// * `last_am_index_param` - See description.
 
     pub fn set_last_amortization_index(&mut self, last_am_index_param: usize) -> bool {
-        match self.list_cashflow.get_mut(self.list_index.get()) {
+        match self
+            .list_cashflow
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
             None => false,
             Some(o) => {
                 o.set_last_amortization_index(last_am_index_param);
@@ -859,7 +873,10 @@ impl ListCashflow {
     /// * `updated_param` - See description.
 
     pub fn set_updated_value(&mut self, updated_param: bool) -> bool {
-        match self.list_cashflow.get_mut(self.list_index.get()) {
+        match self
+            .list_cashflow
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
             None => false,
             Some(o) => {
                 o.set_updated(updated_param);
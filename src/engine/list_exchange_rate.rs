@@ -8,8 +8,8 @@
 // except according to those terms.
 
 use rust_decimal::prelude::*;
-use std::cell::Cell;
 use std::cmp::Ordering::Equal;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 use super::ElemExchangeRate;
 use crate::ListTrait;
@@ -19,7 +19,7 @@ pub struct ListExchangeRate {
     list_exchange_rate: Vec<ElemExchangeRate>,
 
     /// The index of the currently selected exchange rate element.
-    list_index: Cell<usize>,
+    list_index: AtomicUsize,
 
     /// If true sort when a exchange rate is added, otherwise do not sort (for bulk adds).
     sort_on_add: bool,
@@ -49,7 +49,7 @@ impl ListTrait for ListExchangeRate {
 
     fn clear(&mut self) {
         self.list_exchange_rate.clear();
-        self.list_index.set(usize::MAX);
+        self.list_index.store(usize::MAX, Ordering::Relaxed);
         self.sort_on_add = true;
         self.sort_updated = false;
     }
@@ -71,7 +71,7 @@ impl ListTrait for ListExchangeRate {
     /// * See description.
 
     fn index(&self) -> usize {
-        self.list_index.get()
+        self.list_index.load(Ordering::Relaxed)
     }
 
     /// Select a exchange rate based upon an index value.
@@ -105,7 +105,7 @@ impl ListTrait for ListExchangeRate {
             return false;
         }
 
-        self.list_index.set(index_param);
+        self.list_index.store(index_param, Ordering::Relaxed);
 
         true
     }
@@ -123,7 +123,7 @@ impl ListExchangeRate {
     pub fn new() -> ListExchangeRate {
         ListExchangeRate {
             list_exchange_rate: Vec::new(),
-            list_index: Cell::new(usize::MAX),
+            list_index: AtomicUsize::new(usize::MAX),
             sort_on_add: true,
             sort_updated: false,
         }
@@ -150,7 +150,10 @@ impl ListExchangeRate {
     ) -> bool {
         if self.get_element_by_name(from_code, to_code) {
             // Check for duplicate name
-            match self.list_exchange_rate.get_mut(self.list_index.get()) {
+            match self
+                .list_exchange_rate
+                .get_mut(self.list_index.load(Ordering::Relaxed))
+            {
                 None => {}
                 Some(o) => {
                     o.set_exchange_rate(exchange_rate);
@@ -175,7 +178,7 @@ impl ListExchangeRate {
         }) {
             None => {}
             Some(o) => {
-                self.list_index.set(o);
+                self.list_index.store(o, Ordering::Relaxed);
             }
         }
         if !self.sort_on_add {
@@ -194,20 +197,9 @@ impl ListExchangeRate {
 
     pub fn copy_with_calc_manager(&self) -> ListExchangeRate {
         let mut exch = ListExchangeRate::new();
-        let mut index: usize = 0;
 
-        loop {
-            if !self.get_element(index) {
-                break;
-            }
-
-            let from_code = self.from_code();
-            let to_code = self.to_code();
-            let exchange_rate = self.exchange_rate();
-
-            exch.add_exchange_rate(from_code, to_code, exchange_rate);
-
-            index += 1;
+        for elem in self.list_exchange_rate.iter() {
+            exch.add_exchange_rate(elem.from_code(), elem.to_code(), elem.exchange_rate());
         }
 
         exch
@@ -246,7 +238,10 @@ impl ListExchangeRate {
         to_code: &str,
         cross_rate_code: &str,
     ) -> Decimal {
-        match self.list_exchange_rate.get(self.list_index.get()) {
+        match self
+            .list_exchange_rate
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 return dec!(0.0);
             }
@@ -294,7 +289,10 @@ impl ListExchangeRate {
     /// * See description.
 
     pub fn from_code(&self) -> &str {
-        match self.list_exchange_rate.get(self.list_index.get()) {
+        match self
+            .list_exchange_rate
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 panic!("Exchange rate list index not set");
             }
@@ -309,7 +307,10 @@ impl ListExchangeRate {
     /// * See description.
 
     pub fn to_code(&self) -> &str {
-        match self.list_exchange_rate.get(self.list_index.get()) {
+        match self
+            .list_exchange_rate
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 panic!("Exchange rate list index not set");
             }
@@ -324,7 +325,10 @@ impl ListExchangeRate {
     /// * See description.
 
     pub fn exchange_rate(&self) -> Decimal {
-        match self.list_exchange_rate.get(self.list_index.get()) {
+        match self
+            .list_exchange_rate
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 panic!("Exchange rate list index not set");
             }
@@ -360,13 +364,17 @@ impl ListExchangeRate {
     /// * True if successful, otherwise false.
 
     pub fn remove(&mut self) -> bool {
-        if self.list_index.get() >= self.list_exchange_rate.len() {
+        if self.list_index.load(Ordering::Relaxed) >= self.list_exchange_rate.len() {
             return false;
         }
 
-        self.list_exchange_rate.remove(self.list_index.get());
-        if self.list_index.get() > 0 {
-            self.list_index.set(self.list_index.get() - 1);
+        self.list_exchange_rate
+            .remove(self.list_index.load(Ordering::Relaxed));
+        if self.list_index.load(Ordering::Relaxed) > 0 {
+            self.list_index.store(
+                self.list_index.load(Ordering::Relaxed) - 1,
+                Ordering::Relaxed,
+            );
         }
 
         true
@@ -387,7 +395,10 @@ impl ListExchangeRate {
         let to_code: String;
         let exchange_rate: Decimal;
 
-        match self.list_exchange_rate.get_mut(self.list_index.get()) {
+        match self
+            .list_exchange_rate
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 return false;
             }
@@ -410,7 +421,7 @@ impl ListExchangeRate {
         }) {
             None => {}
             Some(o) => {
-                self.list_index.set(o);
+                self.list_index.store(o, Ordering::Relaxed);
             }
         }
 
@@ -436,7 +447,10 @@ impl ListExchangeRate {
         let to_code: String;
         let exchange_rate: Decimal;
 
-        match self.list_exchange_rate.get_mut(self.list_index.get()) {
+        match self
+            .list_exchange_rate
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 return false;
             }
@@ -459,7 +473,7 @@ impl ListExchangeRate {
         }) {
             None => {}
             Some(o) => {
-                self.list_index.set(o);
+                self.list_index.store(o, Ordering::Relaxed);
             }
         }
 
@@ -481,7 +495,10 @@ impl ListExchangeRate {
     /// * True if successful, otherwise false.
 
     pub fn set_exchange_rate(&mut self, exchange_rate_param: Decimal) -> bool {
-        match self.list_exchange_rate.get_mut(self.list_index.get()) {
+        match self
+            .list_exchange_rate
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
             None => false,
             Some(o) => {
                 o.set_exchange_rate(exchange_rate_param);
@@ -509,7 +526,10 @@ impl ListExchangeRate {
         if self.sort_on_add && self.sort_updated {
             self.sort();
 
-            match self.list_exchange_rate.get(self.list_index.get()) {
+            match self
+                .list_exchange_rate
+                .get(self.list_index.load(Ordering::Relaxed))
+            {
                 None => {
                     return false;
                 }
@@ -521,7 +541,7 @@ impl ListExchangeRate {
                     }) {
                         None => {}
                         Some(o2) => {
-                            self.list_index.set(o2);
+                            self.list_index.store(o2, Ordering::Relaxed);
                         }
                     }
                 }
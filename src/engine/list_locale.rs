@@ -7,10 +7,11 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use chrono::NaiveDate;
 use regex::Regex;
 use rust_decimal::prelude::*;
-use std::cell::Cell;
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 use super::{ElemLocale, ElemLocaleFormat};
 use crate::core::CoreUtility;
@@ -20,16 +21,16 @@ pub struct ListLocale {
     list_locale: Vec<ElemLocale>,
 
     /// Currently selected locale element.
-    list_index: Cell<usize>,
+    list_index: AtomicUsize,
 
     /// Currently selected user locale element.
-    list_index_user: Cell<usize>,
+    list_index_user: AtomicUsize,
 
     /// Currently selected cashflow locale element.
-    list_index_cashflow: Cell<usize>,
+    list_index_cashflow: AtomicUsize,
 
     /// Currently selected event locale element.
-    list_index_event: Cell<usize>,
+    list_index_event: AtomicUsize,
 }
 
 /// List of currently active cashflows list implementation.
@@ -38,9 +39,10 @@ impl ListTrait for ListLocale {
     /// Clear all locales selects.
 
     fn clear(&mut self) {
-        self.list_index_user.set(usize::MAX);
-        self.list_index_cashflow.set(usize::MAX);
-        self.list_index_event.set(usize::MAX);
+        self.list_index_user.store(usize::MAX, Ordering::Relaxed);
+        self.list_index_cashflow
+            .store(usize::MAX, Ordering::Relaxed);
+        self.list_index_event.store(usize::MAX, Ordering::Relaxed);
 
         self.list_locale.clear();
     }
@@ -62,7 +64,7 @@ impl ListTrait for ListLocale {
     /// * See description.
 
     fn index(&self) -> usize {
-        self.list_index.get()
+        self.list_index.load(Ordering::Relaxed)
     }
 
     /// Select a locale based upon an index value.
@@ -96,7 +98,7 @@ impl ListTrait for ListLocale {
             return false;
         }
 
-        self.list_index.set(index_param);
+        self.list_index.store(index_param, Ordering::Relaxed);
 
         true
     }
@@ -127,10 +129,10 @@ impl ListLocale {
     pub fn new() -> ListLocale {
         ListLocale {
             list_locale: Vec::new(),
-            list_index: Cell::new(usize::MAX),
-            list_index_user: Cell::new(usize::MAX),
-            list_index_cashflow: Cell::new(usize::MAX),
-            list_index_event: Cell::new(usize::MAX),
+            list_index: AtomicUsize::new(usize::MAX),
+            list_index_user: AtomicUsize::new(usize::MAX),
+            list_index_cashflow: AtomicUsize::new(usize::MAX),
+            list_index_event: AtomicUsize::new(usize::MAX),
         }
     }
 
@@ -186,10 +188,8 @@ impl ListLocale {
     }
 
     /// Copy the locale list and return a new locale list.
-    ///
-    /// # Arguments
-    ///
-    /// * `event` - Check event level.
+    /// The selected user, cashflow and event locales are
+    /// also selected in the new locale list.
     ///
     /// # Return
     ///
@@ -209,6 +209,22 @@ impl ListLocale {
             );
         }
 
+        locales
+            .list_index
+            .store(self.list_index.load(Ordering::Relaxed), Ordering::Relaxed);
+        locales.list_index_user.store(
+            self.list_index_user.load(Ordering::Relaxed),
+            Ordering::Relaxed,
+        );
+        locales.list_index_cashflow.store(
+            self.list_index_cashflow.load(Ordering::Relaxed),
+            Ordering::Relaxed,
+        );
+        locales.list_index_event.store(
+            self.list_index_event.load(Ordering::Relaxed),
+            Ordering::Relaxed,
+        );
+
         locales
     }
 
@@ -333,7 +349,10 @@ impl ListLocale {
     /// * See description.
 
     pub fn user_locale(&self) -> &ElemLocale {
-        match self.list_locale.get(self.list_index_user.get()) {
+        match self
+            .list_locale
+            .get(self.list_index_user.load(Ordering::Relaxed))
+        {
             None => {
                 panic!("Locale list index not set");
             }
@@ -348,7 +367,10 @@ impl ListLocale {
     /// * See description.
 
     pub fn cashflow_locale(&self) -> &ElemLocale {
-        match self.list_locale.get(self.list_index_cashflow.get()) {
+        match self
+            .list_locale
+            .get(self.list_index_cashflow.load(Ordering::Relaxed))
+        {
             None => {
                 panic!("Locale list index not set");
             }
@@ -363,7 +385,10 @@ impl ListLocale {
     /// * See description.
 
     pub fn event_locale(&self) -> &ElemLocale {
-        match self.list_locale.get(self.list_index_event.get()) {
+        match self
+            .list_locale
+            .get(self.list_index_event.load(Ordering::Relaxed))
+        {
             None => {
                 panic!("Locale list index not set");
             }
@@ -414,8 +439,7 @@ impl ListLocale {
 
         let dd: Vec<_> = text.split('-').collect();
         if dd.len() != 3 {
-            let now = CoreUtility::date_now();
-            return format!("{:04}-{:02}-{:02}", now / 10000, now / 100 % 100, now % 100);
+            return CoreUtility::date_now().format("%Y-%m-%d").to_string();
         }
 
         let mut year = String::from(dd[0]);
@@ -430,8 +454,7 @@ impl ListLocale {
         }
 
         if year.len() != 4 {
-            let now = CoreUtility::date_now();
-            return format!("{:04}-{:02}-{:02}", now / 10000, now / 100 % 100, now % 100);
+            return CoreUtility::date_now().format("%Y-%m-%d").to_string();
         }
 
         format!(
@@ -512,14 +535,14 @@ impl ListLocale {
     ///
     /// # Arguments
     ///
-    /// * `val` - The usize date value to format.
+    /// * `val` - The date value to format.
     ///
     /// # Return
     ///
     /// * See description.
 
-    pub fn format_date_out(&self, val: usize) -> String {
-        let text = format!("{:04}-{:02}-{:02}", val / 10000, val / 100 % 100, val % 100);
+    pub fn format_date_out(&self, val: NaiveDate) -> String {
+        let text = val.format("%Y-%m-%d").to_string();
 
         match Regex::new(self.get_locale(true).format_out().date_regex()) {
             Err(_e) => text,
@@ -656,14 +679,14 @@ impl ListLocale {
     /// * See description.
 
     pub fn get_locale_index(&self, event: bool) -> usize {
-        if event && self.list_index_event.get() != usize::MAX {
-            return self.list_index_event.get();
+        if event && self.list_index_event.load(Ordering::Relaxed) != usize::MAX {
+            return self.list_index_event.load(Ordering::Relaxed);
         }
-        if self.list_index_cashflow.get() != usize::MAX {
-            return self.list_index_cashflow.get();
+        if self.list_index_cashflow.load(Ordering::Relaxed) != usize::MAX {
+            return self.list_index_cashflow.load(Ordering::Relaxed);
         }
 
-        self.list_index_user.get()
+        self.list_index_user.load(Ordering::Relaxed)
     }
 
     /// Get the most relevant locale.
@@ -677,10 +700,10 @@ impl ListLocale {
     /// * See description.
 
     pub fn get_locale(&self, event: bool) -> &ElemLocale {
-        if event && self.list_index_event.get() != usize::MAX {
+        if event && self.list_index_event.load(Ordering::Relaxed) != usize::MAX {
             return self.event_locale();
         }
-        if self.list_index_cashflow.get() != usize::MAX {
+        if self.list_index_cashflow.load(Ordering::Relaxed) != usize::MAX {
             return self.cashflow_locale();
         }
 
@@ -698,10 +721,10 @@ impl ListLocale {
     /// * See description.
 
     pub fn get_locale_str(&self, event: bool) -> &str {
-        if event && self.list_index_event.get() != usize::MAX {
+        if event && self.list_index_event.load(Ordering::Relaxed) != usize::MAX {
             return self.event_locale().locale_str();
         }
-        if self.list_index_cashflow.get() != usize::MAX {
+        if self.list_index_cashflow.load(Ordering::Relaxed) != usize::MAX {
             return self.cashflow_locale().locale_str();
         }
 
@@ -735,12 +758,12 @@ impl ListLocale {
     pub fn select_user_locale(&self, locale_str_param: &str) {
         for (index, loc) in self.list_locale.iter().enumerate() {
             if loc.locale_str() == locale_str_param {
-                self.list_index_user.set(index);
+                self.list_index_user.store(index, Ordering::Relaxed);
                 return;
             }
         }
 
-        self.list_index_user.set(usize::MAX);
+        self.list_index_user.store(usize::MAX, Ordering::Relaxed);
     }
 
     /// Select a cashflow locale parameter.
@@ -752,12 +775,13 @@ impl ListLocale {
     pub fn select_cashflow_locale(&self, locale_str_param: &str) {
         for (index, loc) in self.list_locale.iter().enumerate() {
             if loc.locale_str() == locale_str_param {
-                self.list_index_cashflow.set(index);
+                self.list_index_cashflow.store(index, Ordering::Relaxed);
                 return;
             }
         }
 
-        self.list_index_cashflow.set(usize::MAX);
+        self.list_index_cashflow
+            .store(usize::MAX, Ordering::Relaxed);
     }
 
     /// Select an event locale parameter.
@@ -769,12 +793,12 @@ impl ListLocale {
     pub fn select_event_locale(&self, locale_str_param: &str) {
         for (index, loc) in self.list_locale.iter().enumerate() {
             if loc.locale_str() == locale_str_param {
-                self.list_index_event.set(index);
+                self.list_index_event.store(index, Ordering::Relaxed);
                 return;
             }
         }
 
-        self.list_index_event.set(usize::MAX);
+        self.list_index_event.store(usize::MAX, Ordering::Relaxed);
     }
 
     /// Fill leading zeros for a number.
@@ -0,0 +1,239 @@
+//! List of rate indexes.
+// Copyright (c) 2021 ShiftLeft Software
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::core::ElemRateIndex;
+use crate::ListTrait;
+
+pub struct ListRateIndex {
+    /// The list of rate indexes.
+    list_rate_index: Vec<ElemRateIndex>,
+
+    /// The index of the currently selected rate index element.
+    list_index: AtomicUsize,
+}
+
+/// List of rate indexes default implementation.
+impl Default for ListRateIndex {
+    /// Create a new rate index list.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn default() -> Self {
+        ListRateIndex::new()
+    }
+}
+
+/// List of rate indexes list implementation.
+impl ListTrait for ListRateIndex {
+    /// Clear all rate indexes from the rate index list.
+    fn clear(&mut self) {
+        self.list_rate_index.clear();
+        self.list_index.store(usize::MAX, Ordering::Relaxed);
+    }
+
+    /// Get the count of the rate index list.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn count(&self) -> usize {
+        self.list_rate_index.len()
+    }
+
+    /// Get the index of the selected rate index (starting from 0).
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    fn index(&self) -> usize {
+        self.list_index.load(Ordering::Relaxed)
+    }
+
+    /// Select a rate index based upon an index value.
+    ///
+    /// # Arguments
+    ///
+    /// * `index_param` - The index value of the rate index to select (starting from 0).
+    ///
+    /// # Return
+    ///
+    /// * True if successful, otherwise false.
+    fn get_element(&self, index_param: usize) -> bool {
+        if index_param >= self.list_rate_index.len() {
+            return false;
+        }
+
+        self.set_index(index_param);
+
+        true
+    }
+
+    /// Set the list index.
+    ///
+    /// # Arguments
+    ///
+    /// * `index_param` - See description.
+    fn set_index(&self, index_param: usize) -> bool {
+        if index_param >= self.list_rate_index.len() {
+            return false;
+        }
+
+        self.list_index.store(index_param, Ordering::Relaxed);
+
+        true
+    }
+}
+
+/// List of rate indexes implementation.
+impl ListRateIndex {
+    /// Create and return a new list of rate indexes.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn new() -> ListRateIndex {
+        ListRateIndex {
+            list_rate_index: Vec::new(),
+            list_index: AtomicUsize::new(usize::MAX),
+        }
+    }
+
+    /// Add a new rate index into the rate index list.
+    /// A rate index with the same name is replaced.
+    ///
+    /// # Arguments
+    ///
+    /// * `rate_index` - The rate index to add.
+    ///
+    /// # Return
+    ///
+    /// * True if successful, otherwise false.
+    pub fn add_rate_index(&mut self, rate_index: ElemRateIndex) -> bool {
+        if self.get_element_by_name(rate_index.name()) {
+            let index = self.list_index.load(Ordering::Relaxed);
+            self.list_rate_index[index] = rate_index;
+
+            return true;
+        }
+
+        self.list_rate_index.push(rate_index);
+        self.list_index
+            .store(self.list_rate_index.len() - 1, Ordering::Relaxed);
+
+        true
+    }
+
+    /// Append rate indexes to the rate index list.
+    ///
+    /// # Arguments
+    ///
+    /// * `list_rate_index` - The rate indexes to append.
+    pub fn append_rate_indexes(&mut self, list_rate_index: ListRateIndex) {
+        for rate_index in list_rate_index.list_rate_index {
+            self.add_rate_index(rate_index);
+        }
+    }
+
+    /// Copy all rate indexes from the rate index list
+    /// and return a new rate index list.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn copy(&self) -> ListRateIndex {
+        ListRateIndex {
+            list_rate_index: self.list_rate_index.clone(),
+            list_index: AtomicUsize::new(self.list_index.load(Ordering::Relaxed)),
+        }
+    }
+
+    /// Get the selected rate index.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn rate_index(&self) -> &ElemRateIndex {
+        match self
+            .list_rate_index
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
+            None => {
+                panic!("Rate index list index not set");
+            }
+            Some(o) => o,
+        }
+    }
+
+    /// Get the name of the selected rate index.
+    ///
+    /// # Return
+    ///
+    /// * See description.
+    pub fn name(&self) -> &str {
+        self.rate_index().name()
+    }
+
+    /// Select a rate index based upon its name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the rate index.
+    ///
+    /// # Return
+    ///
+    /// * True if successful, otherwise false.
+    pub fn get_element_by_name(&self, name: &str) -> bool {
+        for (index, elem) in self.list_rate_index.iter().enumerate() {
+            if name == elem.name() {
+                self.set_index(index);
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Find and return a rate index by name without
+    /// changing the currently selected rate index.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the rate index.
+    ///
+    /// # Return
+    ///
+    /// * The rate index or None if the name is not found.
+    pub fn get_rate_index(&self, name: &str) -> Option<&ElemRateIndex> {
+        self.list_rate_index.iter().find(|o| o.name() == name)
+    }
+
+    /// Remove the selected rate index from the rate index list.
+    ///
+    /// # Return
+    ///
+    /// * True if successful, otherwise false.
+    pub fn remove(&mut self) -> bool {
+        if self.list_index.load(Ordering::Relaxed) >= self.list_rate_index.len() {
+            return false;
+        }
+
+        self.list_rate_index
+            .remove(self.list_index.load(Ordering::Relaxed));
+        if self.list_index.load(Ordering::Relaxed) > 0 {
+            self.list_index.store(
+                self.list_index.load(Ordering::Relaxed) - 1,
+                Ordering::Relaxed,
+            );
+        }
+
+        true
+    }
+}
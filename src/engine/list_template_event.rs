@@ -7,8 +7,8 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-use std::cell::Cell;
 use std::cmp::Ordering::Equal;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 use super::ElemTemplateEvent;
 use crate::core::ListEvent;
@@ -19,7 +19,7 @@ pub struct ListTemplateEvent {
     list_template_event: Vec<ElemTemplateEvent>,
 
     /// The index of the currently selected template event element.
-    list_index: Cell<usize>,
+    list_index: AtomicUsize,
 
     /// If true sort when an template event is added, otherwise do not sort (for bulk adds).
     sort_on_add: bool,
@@ -49,7 +49,7 @@ impl ListTrait for ListTemplateEvent {
 
     fn clear(&mut self) {
         self.list_template_event.clear();
-        self.list_index.set(usize::MAX);
+        self.list_index.store(usize::MAX, Ordering::Relaxed);
         self.sort_on_add = true;
         self.sort_updated = false;
     }
@@ -71,7 +71,7 @@ impl ListTrait for ListTemplateEvent {
     /// * See description.
 
     fn index(&self) -> usize {
-        self.list_index.get()
+        self.list_index.load(Ordering::Relaxed)
     }
 
     /// Select an template event based upon an index value.
@@ -105,7 +105,7 @@ impl ListTrait for ListTemplateEvent {
             return false;
         }
 
-        self.list_index.set(index_param);
+        self.list_index.store(index_param, Ordering::Relaxed);
 
         true
     }
@@ -123,7 +123,7 @@ impl ListTemplateEvent {
     pub fn new() -> ListTemplateEvent {
         ListTemplateEvent {
             list_template_event: Vec::new(),
-            list_index: Cell::new(usize::MAX),
+            list_index: AtomicUsize::new(usize::MAX),
             sort_on_add: true,
             sort_updated: false,
         }
@@ -235,7 +235,10 @@ impl ListTemplateEvent {
     /// * See description.
 
     pub fn name(&self) -> &str {
-        match self.list_template_event.get(self.list_index.get()) {
+        match self
+            .list_template_event
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 panic!("Template event list index not set");
             }
@@ -250,7 +253,10 @@ impl ListTemplateEvent {
     /// * See description.
 
     pub fn initial_event(&self) -> bool {
-        match self.list_template_event.get(self.list_index.get()) {
+        match self
+            .list_template_event
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 panic!("Template event list index not set");
             }
@@ -265,7 +271,10 @@ impl ListTemplateEvent {
     /// * See description.
 
     pub fn list_event(&self) -> &ListEvent {
-        match self.list_template_event.get(self.list_index.get()) {
+        match self
+            .list_template_event
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 panic!("Template event list index not set");
             }
@@ -280,7 +289,10 @@ impl ListTemplateEvent {
     /// * See description.
 
     pub fn list_event_mut(&mut self) -> &mut ListEvent {
-        match self.list_template_event.get_mut(self.list_index.get()) {
+        match self
+            .list_template_event
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 panic!("Template event list index not set");
             }
@@ -318,13 +330,17 @@ impl ListTemplateEvent {
     /// * True if successful, otherwise false.
 
     pub fn remove(&mut self) -> bool {
-        if self.list_index.get() >= self.list_template_event.len() {
+        if self.list_index.load(Ordering::Relaxed) >= self.list_template_event.len() {
             return false;
         }
 
-        self.list_template_event.remove(self.list_index.get());
-        if self.list_index.get() > 0 {
-            self.list_index.set(self.list_index.get() - 1);
+        self.list_template_event
+            .remove(self.list_index.load(Ordering::Relaxed));
+        if self.list_index.load(Ordering::Relaxed) > 0 {
+            self.list_index.store(
+                self.list_index.load(Ordering::Relaxed) - 1,
+                Ordering::Relaxed,
+            );
         }
 
         true
@@ -346,7 +362,10 @@ impl ListTemplateEvent {
             return false;
         }
 
-        match self.list_template_event.get_mut(self.list_index.get()) {
+        match self
+            .list_template_event
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 panic!("Template event list index not set");
             }
@@ -368,7 +387,7 @@ impl ListTemplateEvent {
                 return false;
             }
             Some(o) => {
-                self.list_index.set(o);
+                self.list_index.store(o, Ordering::Relaxed);
             }
         }
 
@@ -390,7 +409,10 @@ impl ListTemplateEvent {
     /// * True if successful, otherwise false.
 
     pub fn set_initial_event(&mut self, initial_event_param: bool) -> bool {
-        match self.list_template_event.get_mut(self.list_index.get()) {
+        match self
+            .list_template_event
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
             None => false,
             Some(o) => {
                 o.set_initial_event(initial_event_param);
@@ -418,7 +440,10 @@ impl ListTemplateEvent {
         if sort_on_add_param && self.sort_updated {
             self.sort();
 
-            match self.list_template_event.get(self.list_index.get()) {
+            match self
+                .list_template_event
+                .get(self.list_index.load(Ordering::Relaxed))
+            {
                 None => {
                     return false;
                 }
@@ -432,7 +457,7 @@ impl ListTemplateEvent {
                             return false;
                         }
                         Some(o2) => {
-                            self.list_index.set(o2);
+                            self.list_index.store(o2, Ordering::Relaxed);
                         }
                     }
                 }
@@ -7,32 +7,29 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-use std::cell::{Cell, Ref, RefCell};
 use std::cmp::Ordering::Equal;
-use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 
 use rust_decimal::prelude::*;
 
 use super::{
     CalcExpression, CalcManager, CalcUtility, ElemPreferences, ElemTemplateGroup, ListTemplateEvent,
 };
+use crate::core::ElemError;
 use crate::ListTrait;
 
 pub struct ListTemplateGroup {
-    /// Calculator manager element.
-    calc_manager: Rc<RefCell<CalcManager>>,
-
     /// The list of template groups.
     list_template_group: Vec<ElemTemplateGroup>,
 
     /// The index of the currently selected template group element.
-    list_index: Cell<usize>,
+    list_index: AtomicUsize,
 
     /// If true sort when an template event is added, otherwise do not sort (for bulk adds).
-    sort_on_add: Cell<bool>,
+    sort_on_add: AtomicBool,
 
     /// Updated while sort_on_add was false.
-    sort_updated: Cell<bool>,
+    sort_updated: AtomicBool,
 }
 
 /// List of template groups list implementation.
@@ -42,9 +39,9 @@ impl ListTrait for ListTemplateGroup {
 
     fn clear(&mut self) {
         self.list_template_group.clear();
-        self.list_index.set(usize::MAX);
-        self.sort_on_add.set(true);
-        self.sort_updated.set(false);
+        self.list_index.store(usize::MAX, Ordering::Relaxed);
+        self.sort_on_add.store(true, Ordering::Relaxed);
+        self.sort_updated.store(false, Ordering::Relaxed);
     }
 
     /// Get the count of the template group list.
@@ -64,7 +61,7 @@ impl ListTrait for ListTemplateGroup {
     /// * See description.
 
     fn index(&self) -> usize {
-        self.list_index.get()
+        self.list_index.load(Ordering::Relaxed)
     }
 
     /// Select a template group based upon an index value.
@@ -98,53 +95,39 @@ impl ListTrait for ListTemplateGroup {
             return false;
         }
 
-        self.list_index.set(index_param);
+        self.list_index.store(index_param, Ordering::Relaxed);
 
         true
     }
 }
 
-/// Implementation for the list of template groups.
-
-impl ListTemplateGroup {
+/// List of template groups default implementation.
+impl Default for ListTemplateGroup {
     /// Create and return a new list template group.
     ///
-    /// # Arguments
-    ///
-    /// * `calc_manager_param` - Calculation manager.
-    ///
     /// # Return
     ///
     /// * See description.
-
-    pub fn new(calc_manager_param: &Rc<RefCell<CalcManager>>) -> ListTemplateGroup {
-        ListTemplateGroup {
-            calc_manager: Rc::clone(calc_manager_param),
-            list_template_group: Vec::new(),
-            list_index: Cell::new(usize::MAX),
-            sort_on_add: Cell::new(true),
-            sort_updated: Cell::new(false),
-        }
-    }
-
-    /// Returns the calculation manager element.
-    ///
-    /// # Return
-    ///
-    /// * See description.
-
-    fn calc_manager(&self) -> &Rc<RefCell<CalcManager>> {
-        &self.calc_manager
+    fn default() -> Self {
+        ListTemplateGroup::new()
     }
+}
 
-    /// Returns the calculation manager.
+/// Implementation for the list of template groups.
+impl ListTemplateGroup {
+    /// Create and return a new list template group.
     ///
     /// # Return
     ///
     /// * See description.
 
-    fn calc_mgr(&self) -> Ref<CalcManager> {
-        self.calc_manager.borrow()
+    pub fn new() -> ListTemplateGroup {
+        ListTemplateGroup {
+            list_template_group: Vec::new(),
+            list_index: AtomicUsize::new(usize::MAX),
+            sort_on_add: AtomicBool::new(true),
+            sort_updated: AtomicBool::new(false),
+        }
     }
 
     /// Add a new template group into the template group list.
@@ -154,19 +137,21 @@ impl ListTemplateGroup {
     ///
     /// # Arguments
     ///
+    /// * `calc_mgr` - Calculation manager.
     /// * `group_param` - Group name of the template group.
     ///
     /// # Return
     ///
     /// * ERROR_NONE if successful, otherwise error code.
 
-    pub fn add_template_group(&mut self, group_param: &str) -> Result<(), crate::ErrorType> {
-        let prefs = self
-            .calc_mgr()
-            .preferences()
-            .copy(self.calc_mgr().updating_json());
+    pub fn add_template_group(
+        &mut self,
+        calc_mgr: &CalcManager,
+        group_param: &str,
+    ) -> Result<(), ElemError> {
+        let prefs = calc_mgr.preferences().copy(calc_mgr.updating_json());
 
-        match self.create_template_group(group_param, Option::from(prefs), true) {
+        match self.create_template_group(calc_mgr, group_param, Option::from(prefs), true) {
             Err(e) => {
                 return Err(e);
             }
@@ -180,7 +165,7 @@ impl ListTemplateGroup {
                 self.get_element_by_group(group_param, true);
 
                 if !self.sort_on_add() {
-                    self.sort_updated.set(true);
+                    self.sort_updated.store(true, Ordering::Relaxed);
                 }
             }
         }
@@ -214,6 +199,7 @@ impl ListTemplateGroup {
     ///
     /// # Arguments
     ///
+    /// * `calc_mgr` - Calculation manager.
     /// * `group_param` - Group name of the template group.
     /// * `elem_preferences_orig_opt` - Original preferences element to copy (or None).
     /// * `copy_propagate` - Copy only descriptors marked as "propagate"
@@ -225,13 +211,14 @@ impl ListTemplateGroup {
 
     pub fn create_template_group(
         &self,
+        calc_mgr: &CalcManager,
         group_param: &str,
         elem_preferences_orig_opt: Option<ElemPreferences>,
         copy_propagate: bool,
-    ) -> Result<ElemTemplateGroup, crate::ErrorType> {
+    ) -> Result<ElemTemplateGroup, ElemError> {
         let mut group = String::from(group_param);
         let mut update_element: bool = false;
-        let updating_json = self.calc_mgr().updating_json();
+        let updating_json = calc_mgr.updating_json();
 
         if self.get_element_by_group(group_param, false) {
             // Check for duplicate name
@@ -257,7 +244,6 @@ impl ListTemplateGroup {
         }
         let elem_preferences: ElemPreferences = match elem_preferences_orig_opt {
             None => ElemPreferences::new(
-                self.calc_manager(),
                 "",
                 "",
                 "",
@@ -274,7 +260,6 @@ impl ListTemplateGroup {
                 updating_json,
             ),
             Some(o) => ElemPreferences::new(
-                self.calc_manager(),
                 o.locale_str(),
                 o.cross_rate_code(),
                 o.default_encoding(),
@@ -294,7 +279,10 @@ impl ListTemplateGroup {
 
         let new_elem_template_group;
         if update_element {
-            match self.list_template_group.get(self.list_index.get()) {
+            match self
+                .list_template_group
+                .get(self.list_index.load(Ordering::Relaxed))
+            {
                 None => {
                     new_elem_template_group =
                         ElemTemplateGroup::new(group.as_str(), elem_preferences);
@@ -307,44 +295,36 @@ impl ListTemplateGroup {
             new_elem_template_group = ElemTemplateGroup::new(group.as_str(), elem_preferences);
         }
         if update_element {
-            self.sort_updated.set(true);
+            self.sort_updated.store(true, Ordering::Relaxed);
         }
         Ok(new_elem_template_group)
     }
 
     /// Performs a deep copy of the template group list and
-    /// returns a new template group list.
+    /// returns a new template group list. The selected template
+    /// group is also selected in the new template group list.
+    /// The selected template group of this list is not changed.
     ///
     /// # Arguments
     ///
-    /// * `calc_manager_param` - Calculation manager.
+    /// * `updating_json` - Updating from Json.
     ///
     /// # Return
     ///
     /// * See description.    
 
-    pub fn copy_with_calc_manager(
-        &self,
-        calc_manager_param: &Rc<RefCell<CalcManager>>,
-    ) -> ListTemplateGroup {
-        let mut list_template_group = ListTemplateGroup::new(calc_manager_param);
-        let mut index: usize = 0;
-
-        list_template_group.set_sort_on_add(false);
-
-        loop {
-            if !self.get_element(index) {
-                break;
-            }
-
-            let template_group = self.copy_selected();
-
-            list_template_group.list_template_group.push(template_group);
+    pub fn copy(&self, updating_json: bool) -> ListTemplateGroup {
+        let mut list_template_group = ListTemplateGroup::new();
 
-            index += 1;
+        for elem_template_group in self.list_template_group.iter() {
+            list_template_group
+                .list_template_group
+                .push(elem_template_group.copy(updating_json));
         }
 
-        list_template_group.set_sort_on_add(true);
+        list_template_group
+            .list_index
+            .store(self.list_index.load(Ordering::Relaxed), Ordering::Relaxed);
 
         list_template_group
     }
@@ -352,16 +332,20 @@ impl ListTemplateGroup {
     /// Performs a deep copy of the template group element and
     /// returns a new template group element.
     ///
+    /// # Arguments
+    ///
+    /// * `calc_mgr` - Calculation manager.
+    ///
     /// # Return
     ///
     /// * ERROR_NONE if successful, otherwise error code.
 
-    pub fn copy_selected(&self) -> ElemTemplateGroup {
-        let updating_json = self.calc_mgr().updating_json();
+    pub fn copy_selected(&self, calc_mgr: &CalcManager) -> ElemTemplateGroup {
+        let updating_json = calc_mgr.updating_json();
         let group = String::from(self.group());
         let prefs = Option::from(self.preferences().copy(updating_json));
 
-        let result = self.create_template_group(group.as_str(), prefs, false);
+        let result = self.create_template_group(calc_mgr, group.as_str(), prefs, false);
         match result {
             Err(_e) => ElemTemplateGroup::new(self.group(), self.preferences().copy(updating_json)),
             Ok(o) => o,
@@ -371,23 +355,29 @@ impl ListTemplateGroup {
     /// Evaluate all of the descriptors in the template group.
     /// For each descriptor that specifies an expression,
     /// execute the expression using the list of parameters.
-
-    pub fn evaluate_descriptors(&self) {
-        match self.list_template_group.get(self.list_index.get()) {
+    ///
+    /// # Arguments
+    ///
+    /// * `calc_mgr` - Calculation manager.
+    pub fn evaluate_descriptors(&self, calc_mgr: &CalcManager) {
+        match self
+            .list_template_group
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
             None => {}
             Some(o2) => {
-                let calc_expression = CalcExpression::new(
-                    self.calc_manager(),
-                    self.calc_mgr().fiscal_year_start(false),
-                    self.calc_mgr().decimal_digits(false),
+                let mut calc_expression = CalcExpression::new(
+                    calc_mgr,
+                    calc_mgr.fiscal_year_start(false),
+                    calc_mgr.decimal_digits(false),
                 );
 
                 let preferences = o2.preferences();
-                let expression = RefCell::new(calc_expression);
+                calc_expression.init_list_function(Option::from(preferences.list_function()));
 
                 CalcUtility::evaluate_descriptors(
-                    self.calc_manager(),
-                    &expression,
+                    calc_mgr,
+                    &mut calc_expression,
                     preferences.list_parameter(),
                     preferences.list_descriptor(),
                 );
@@ -402,7 +392,10 @@ impl ListTemplateGroup {
     /// * See description.
 
     pub fn group(&self) -> &str {
-        match self.list_template_group.get(self.list_index.get()) {
+        match self
+            .list_template_group
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
             None => "",
             Some(o) => o.group(),
         }
@@ -415,7 +408,10 @@ impl ListTemplateGroup {
     /// * See description.
 
     pub fn preferences(&self) -> &ElemPreferences {
-        match self.list_template_group.get(self.list_index.get()) {
+        match self
+            .list_template_group
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 panic!("Template group list index not set");
             }
@@ -430,7 +426,10 @@ impl ListTemplateGroup {
     /// * See description.
 
     pub fn preferences_mut(&mut self) -> &mut ElemPreferences {
-        match self.list_template_group.get_mut(self.list_index.get()) {
+        match self
+            .list_template_group
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 panic!("Template group list index not set");
             }
@@ -445,7 +444,10 @@ impl ListTemplateGroup {
     /// * See description.
 
     pub fn list_template_event(&self) -> &ListTemplateEvent {
-        match self.list_template_group.get(self.list_index.get()) {
+        match self
+            .list_template_group
+            .get(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 panic!("Template group list index not set");
             }
@@ -460,7 +462,10 @@ impl ListTemplateGroup {
     /// * See description.
 
     pub fn list_template_event_mut(&mut self) -> &mut ListTemplateEvent {
-        match self.list_template_group.get_mut(self.list_index.get()) {
+        match self
+            .list_template_group
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 panic!("Template group list index not set");
             }
@@ -475,7 +480,7 @@ impl ListTemplateGroup {
     /// * See description.
 
     pub fn sort_on_add(&mut self) -> bool {
-        self.sort_on_add.get()
+        self.sort_on_add.load(Ordering::Relaxed)
     }
 
     /// Select a template group based upon a group name.
@@ -508,13 +513,17 @@ impl ListTemplateGroup {
     /// * True if successful, otherwise false.
 
     pub fn remove(&mut self) -> bool {
-        if self.list_index.get() >= self.list_template_group.len() {
+        if self.list_index.load(Ordering::Relaxed) >= self.list_template_group.len() {
             return false;
         }
 
-        self.list_template_group.remove(self.list_index.get());
-        if self.list_index.get() > 0 {
-            self.list_index.set(self.list_index.get() - 1);
+        self.list_template_group
+            .remove(self.list_index.load(Ordering::Relaxed));
+        if self.list_index.load(Ordering::Relaxed) > 0 {
+            self.list_index.store(
+                self.list_index.load(Ordering::Relaxed) - 1,
+                Ordering::Relaxed,
+            );
         }
 
         true
@@ -531,13 +540,16 @@ impl ListTemplateGroup {
     /// * True if successful, otherwise false.
 
     pub fn set_group(&mut self, group_param: &str) -> bool {
-        if self.list_index.get() >= self.list_template_group.len()
+        if self.list_index.load(Ordering::Relaxed) >= self.list_template_group.len()
             || self.get_element_by_group(group_param, false)
         {
             return false;
         }
 
-        match self.list_template_group.get_mut(self.list_index.get()) {
+        match self
+            .list_template_group
+            .get_mut(self.list_index.load(Ordering::Relaxed))
+        {
             None => {
                 return false;
             }
@@ -545,7 +557,7 @@ impl ListTemplateGroup {
                 o.set_group(group_param);
             }
         }
-        if self.sort_on_add.get() {
+        if self.sort_on_add.load(Ordering::Relaxed) {
             self.sort();
         }
 
@@ -556,10 +568,10 @@ impl ListTemplateGroup {
         {
             None => false,
             Some(o) => {
-                self.list_index.set(o);
+                self.list_index.store(o, Ordering::Relaxed);
 
-                if !self.sort_on_add.get() {
-                    self.sort_updated.set(true);
+                if !self.sort_on_add.load(Ordering::Relaxed) {
+                    self.sort_updated.store(true, Ordering::Relaxed);
                 }
                 true
             }
@@ -578,14 +590,17 @@ impl ListTemplateGroup {
     /// * True if successful, otherwise false.
 
     pub fn set_sort_on_add(&mut self, sort_on_add_param: bool) -> bool {
-        if sort_on_add_param == self.sort_on_add.get() {
+        if sort_on_add_param == self.sort_on_add.load(Ordering::Relaxed) {
             return false;
         }
-        self.sort_on_add.set(sort_on_add_param);
-        if sort_on_add_param && self.sort_updated.get() {
+        self.sort_on_add.store(sort_on_add_param, Ordering::Relaxed);
+        if sort_on_add_param && self.sort_updated.load(Ordering::Relaxed) {
             self.sort();
 
-            match self.list_template_group.get(self.list_index.get()) {
+            match self
+                .list_template_group
+                .get(self.list_index.load(Ordering::Relaxed))
+            {
                 None => {
                     return false;
                 }
@@ -599,13 +614,13 @@ impl ListTemplateGroup {
                             return false;
                         }
                         Some(o2) => {
-                            self.list_index.set(o2);
+                            self.list_index.store(o2, Ordering::Relaxed);
                         }
                     }
                 }
             }
         }
-        self.sort_updated.set(false);
+        self.sort_updated.store(false, Ordering::Relaxed);
         true
     }
 
@@ -616,10 +631,11 @@ impl ListTemplateGroup {
     /// * `sort_updated_param` - If true sort updated otherwise false.
 
     pub fn set_sort_updated(&mut self, sort_updated_param: bool) -> bool {
-        if self.sort_updated.get() == sort_updated_param {
+        if self.sort_updated.load(Ordering::Relaxed) == sort_updated_param {
             return false;
         }
-        self.sort_updated.set(sort_updated_param);
+        self.sort_updated
+            .store(sort_updated_param, Ordering::Relaxed);
 
         true
     }
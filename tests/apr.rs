@@ -0,0 +1,155 @@
+// Copyright (c) 2021 ShiftLeft Software
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+mod common;
+
+use common::decimal;
+
+use amfnengine::core::ElemSolverSettings;
+use amfnengine::engine::CalcEngine;
+use amfnengine::AprType;
+
+/// Create a balanced engine with a cashflow of apr.json selected.
+///
+/// # Arguments
+///
+/// * `name` - Name of the cashflow.
+///
+/// # Return
+///
+/// * See description.
+fn loan(name: &str) -> CalcEngine {
+    let calc_engine = common::load_engine("apr.json");
+
+    assert!(calc_engine.calc_mgr().select_cashflow_by_name(name));
+    calc_engine.balance_cashflow().unwrap();
+
+    calc_engine
+}
+
+#[test]
+fn regulation_z_regular_schedule() {
+    // Appendix J (c)(5)(i): 5,000 repaid by 36 monthly payments
+    // of 166.07, advanced 1/10/78 with the first payment 2/10/78
+    let calc_engine = loan("RegZ-Regular");
+
+    let result = calc_engine.calculate_apr(AprType::RegulationZ).unwrap();
+
+    assert_eq!(result.apr().round_dp(2), decimal("12.00"));
+    assert_eq!(result.unit_period(), amfnengine::FrequencyType::OneMonth);
+    assert_eq!(result.amount_financed(), decimal("5000.00"));
+    assert_eq!(
+        result.finance_charge(),
+        result.total_of_payments() - result.amount_financed()
+    );
+}
+
+#[test]
+fn regulation_z_odd_first_period() {
+    // Appendix J (c)(5)(ii): 6,000 repaid by 36 monthly payments
+    // of 200, advanced 2/10/78 with the first payment 4/1/78
+    // (one unit period and 19/30 of a unit period)
+    let calc_engine = loan("RegZ-Odd");
+
+    let result = calc_engine.calculate_apr(AprType::RegulationZ).unwrap();
+
+    assert_eq!(result.apr().round_dp(2), decimal("11.82"));
+}
+
+#[test]
+fn consumer_credit_directive_whole_years() {
+    // 1,000 repaid by 1,100 one year later is 10% a year
+    let calc_engine = loan("CCD-Years");
+
+    let result = calc_engine
+        .calculate_apr(AprType::ConsumerCreditDirective)
+        .unwrap();
+
+    assert_eq!(result.apr().round_dp(2), decimal("10.00"));
+    assert_eq!(result.finance_charge(), decimal("100.00"));
+}
+
+#[test]
+fn consumer_credit_directive_equal_months() {
+    // Six equal months are half a year regardless of the days
+    // (Annex I): 1,000 repaid by 1,050 is 1.05 ^ 2 - 1 = 10.25%
+    let calc_engine = loan("CCD-Months");
+
+    let result = calc_engine
+        .calculate_apr(AprType::ConsumerCreditDirective)
+        .unwrap();
+
+    assert_eq!(result.apr().round_dp(2), decimal("10.25"));
+    assert_eq!(result.unit_period(), amfnengine::FrequencyType::OneYear);
+}
+
+#[test]
+fn consumer_credit_directive_odd_days() {
+    // 14 equal months and 14 days of 2023 (not a leap year) is
+    // 14 / 12 + 14 / 365 years: 1,000 repaid by 1,000 plus a fee
+    // of 121.71 (1,000 x 1.1 ^ 1.2050228) is 10% a year
+    let calc_engine = loan("CCD-Fee");
+
+    let result = calc_engine
+        .calculate_apr(AprType::ConsumerCreditDirective)
+        .unwrap();
+
+    assert_eq!(result.apr().round_dp(2), decimal("10.00"));
+    assert_eq!(result.finance_charge(), decimal("121.71"));
+}
+
+#[test]
+fn solver_settings() {
+    let calc_engine = loan("RegZ-Odd");
+    let result = calc_engine.calculate_apr(AprType::RegulationZ).unwrap();
+
+    assert_eq!(
+        calc_engine
+            .calculate_apr_with_settings(AprType::RegulationZ, &ElemSolverSettings::new())
+            .unwrap()
+            .apr(),
+        result.apr()
+    );
+
+    // Bounds are annual percentage rates
+    let mut solver_settings = ElemSolverSettings::new();
+    solver_settings.set_lower_bound(Option::from(decimal("11.0")));
+    solver_settings.set_upper_bound(Option::from(decimal("13.0")));
+    let result_bounds = calc_engine
+        .calculate_apr_with_settings(AprType::RegulationZ, &solver_settings)
+        .unwrap();
+
+    assert_eq!(result_bounds.apr().round_dp(4), result.apr().round_dp(4));
+    assert!(result_bounds.iterations() < result.iterations());
+
+    let mut solver_settings = ElemSolverSettings::new();
+    solver_settings.set_upper_bound(Option::from(decimal("10.0")));
+    match calc_engine.calculate_apr_with_settings(AprType::RegulationZ, &solver_settings) {
+        Err(e) => assert_eq!(e.kind(), amfnengine::ErrorType::CalcInterest),
+        Ok(o) => panic!("{}", o.apr()),
+    }
+
+    let mut solver_settings = ElemSolverSettings::new();
+    solver_settings.set_max_iterations(Option::from(4));
+    let result_capped = calc_engine
+        .calculate_apr_with_settings(AprType::RegulationZ, &solver_settings)
+        .unwrap();
+
+    assert_eq!(result_capped.iterations(), 4);
+    assert_ne!(result_capped.apr().round_dp(2), decimal("11.82"));
+
+    // The present value is within the tolerance before the rate
+    let mut solver_settings = ElemSolverSettings::new();
+    solver_settings.set_abs_tolerance(Option::from(decimal("10.0")));
+    let result_tolerance = calc_engine
+        .calculate_apr_with_settings(AprType::RegulationZ, &solver_settings)
+        .unwrap();
+
+    assert!(result_tolerance.iterations() < result.iterations());
+    assert_eq!(result_tolerance.apr().round_dp(0), decimal("12"));
+}
@@ -0,0 +1,105 @@
+// Copyright (c) 2021 ShiftLeft Software
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+mod common;
+
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use amfnengine::engine::{CalcEngine, CalcManager};
+use amfnengine::ListTrait;
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+#[test]
+fn engine_is_send_sync() {
+    assert_send_sync::<CalcEngine>();
+    assert_send_sync::<CalcManager>();
+}
+
+#[test]
+fn copy_keeps_selected_locale_and_cashflow() {
+    let calc_engine = common::load_engine("loan.json");
+
+    let calc_engine_copy = calc_engine.copy(true);
+
+    let calc_mgr = calc_engine_copy.calc_mgr();
+    assert_eq!(calc_mgr.list_locale().locale_str(false), "en-US");
+    assert_eq!(calc_mgr.list_locale().decimal_digits(false), 2);
+    assert_eq!(calc_mgr.list_cashflow().index(), 0);
+    assert_eq!(calc_mgr.list_cashflow().name(), "Loan");
+}
+
+#[test]
+fn copy_per_thread_balances() {
+    let calc_engine = Arc::new(common::load_engine("loan.json"));
+    let expected = calc_engine.balance_cashflow().unwrap().balance();
+
+    let handles: Vec<thread::JoinHandle<_>> = (0..8)
+        .map(|_| {
+            let calc_engine = Arc::clone(&calc_engine);
+            thread::spawn(move || {
+                let calc_engine_copy = calc_engine.copy(true);
+                let result = calc_engine_copy.balance_cashflow().unwrap();
+                calc_engine_copy.round_currency(result.balance())
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), calc_engine.round_currency(expected));
+    }
+}
+
+#[test]
+fn concurrent_evaluate_copy_and_balance() {
+    // Mixed operations on one shared engine must neither deadlock
+    // nor disturb each other's selections
+    let calc_engine = Arc::new(common::load_engine("loan.json"));
+    let expected = calc_engine.balance_cashflow().unwrap().balance();
+    let (sender, receiver) = mpsc::channel();
+
+    for thread_index in 0..6 {
+        let calc_engine = Arc::clone(&calc_engine);
+        let sender = sender.clone();
+        thread::spawn(move || {
+            for _ in 0..50 {
+                match thread_index % 3 {
+                    0 => {
+                        let result = calc_engine.evaluate_expression(
+                            None,
+                            "pmt(0.06 / 12, 360, 200000)",
+                            false,
+                        );
+                        assert_eq!(result.sym_decimal().round_dp(2).to_string(), "-1199.10");
+                    }
+                    1 => {
+                        let calc_engine_copy = calc_engine.copy(true);
+                        let result = calc_engine_copy.balance_cashflow().unwrap();
+                        assert_eq!(result.balance(), expected);
+                    }
+                    _ => {
+                        let result = calc_engine.balance_cashflow().unwrap();
+                        assert_eq!(result.balance(), expected);
+                    }
+                }
+            }
+            sender.send(thread_index).unwrap();
+        });
+    }
+    drop(sender);
+
+    for _ in 0..6 {
+        receiver
+            .recv_timeout(Duration::from_secs(60))
+            .expect("engine operations deadlocked or panicked");
+    }
+    assert_eq!(calc_engine.calc_mgr().list_cashflow().index(), 0);
+}
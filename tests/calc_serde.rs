@@ -0,0 +1,77 @@
+// Copyright (c) 2021 ShiftLeft Software
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![cfg(feature = "serde")]
+
+mod common;
+
+use serde::de::DeserializeSeed;
+
+use amfnengine::engine::{CalcEngine, CalcJsonDeserialize, CalcSeed, ListCashflow};
+
+/// Serialize the cashflows of an engine with serde.
+///
+/// # Arguments
+///
+/// * `calc_engine` - The engine.
+///
+/// # Return
+///
+/// * See description.
+fn serde_cashflows(calc_engine: &CalcEngine) -> serde_json::Value {
+    serde_json::to_value(calc_engine.calc_mgr().list_cashflow()).unwrap()
+}
+
+/// Serialize the cashflows of a document with serde, ingest the
+/// serde output as engine Json and serialize the cashflows again.
+///
+/// # Arguments
+///
+/// * `name` - File name of the document.
+fn round_trip(name: &str) {
+    let calc_engine = common::load_engine(name);
+    let cashflows = serde_cashflows(&calc_engine);
+
+    let mut data = json::parse(common::read_data(name).as_str()).unwrap();
+    data["cashflows"] = json::parse(cashflows.to_string().as_str()).unwrap();
+
+    let calc_engine_new = CalcEngine::new();
+    CalcJsonDeserialize::new(calc_engine_new.calc_manager())
+        .deserialize(data.dump())
+        .unwrap();
+    calc_engine_new.init_engine("en-US");
+    calc_engine_new.init_cashflow(0);
+
+    assert_eq!(serde_cashflows(&calc_engine_new), cashflows);
+}
+
+#[test]
+fn round_trip_loan() {
+    round_trip("loan.json");
+}
+
+#[test]
+fn round_trip_interest_change_calendar() {
+    round_trip("business_252.json");
+}
+
+#[test]
+fn seed_round_trip() {
+    // The cashflows are not balanced (no amortization lists or balance results)
+    let calc_engine = CalcEngine::new();
+    CalcJsonDeserialize::new(calc_engine.calc_manager())
+        .deserialize(common::read_data("business_252.json"))
+        .unwrap();
+    let cashflows = serde_cashflows(&calc_engine);
+
+    let list_cashflow = CalcSeed::<ListCashflow>::new(calc_engine.calc_manager())
+        .deserialize(&cashflows)
+        .unwrap();
+
+    assert_eq!(serde_json::to_value(&list_cashflow).unwrap(), cashflows);
+}
@@ -0,0 +1,331 @@
+// Copyright (c) 2021 ShiftLeft Software
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+mod common;
+
+use common::date;
+
+use chrono::NaiveDate;
+
+use amfnengine::core::{CoreUtility, ElemCalendar, ElemHoliday};
+use amfnengine::engine::{CalcEngine, CalcJsonDeserialize, CalcJsonSerialize};
+use amfnengine::{BusinessDayType, ErrorType, ExtensionType, ListTrait};
+
+/// Calendar with the US holidays exercised by the tests.
+///
+/// # Return
+///
+/// * See description.
+fn calendar_us() -> ElemCalendar {
+    let mut calendar = ElemCalendar::new("US");
+
+    calendar.add_holiday(ElemHoliday::new_fixed("Independence Day", 7, 4, true));
+    calendar.add_holiday(ElemHoliday::new_nth_weekday("Labor Day", 9, 1, 1));
+    calendar.add_holiday(ElemHoliday::new_fixed("Christmas Day", 12, 25, true));
+
+    calendar
+}
+
+#[test]
+fn following() {
+    let calendar = calendar_us();
+
+    // Business day is unchanged
+    assert_eq!(
+        calendar.adjust_date(date(2024, 7, 3), BusinessDayType::Following),
+        date(2024, 7, 3)
+    );
+    // Holiday
+    assert_eq!(
+        calendar.adjust_date(date(2024, 7, 4), BusinessDayType::Following),
+        date(2024, 7, 5)
+    );
+    // Saturday 31 August, Sunday and Labor Day roll into September
+    assert_eq!(
+        calendar.adjust_date(date(2024, 8, 31), BusinessDayType::Following),
+        date(2024, 9, 3)
+    );
+}
+
+#[test]
+fn modified_following() {
+    let calendar = calendar_us();
+
+    assert_eq!(
+        calendar.adjust_date(date(2024, 7, 4), BusinessDayType::ModifiedFollowing),
+        date(2024, 7, 5)
+    );
+    // Rolling forward would cross the month end
+    assert_eq!(
+        calendar.adjust_date(date(2024, 8, 31), BusinessDayType::ModifiedFollowing),
+        date(2024, 8, 30)
+    );
+    // Christmas 2021 (Saturday) is observed on Friday 24 December
+    assert_eq!(
+        calendar.adjust_date(date(2021, 12, 24), BusinessDayType::ModifiedFollowing),
+        date(2021, 12, 27)
+    );
+}
+
+#[test]
+fn preceding() {
+    let calendar = calendar_us();
+
+    assert_eq!(
+        calendar.adjust_date(date(2024, 7, 4), BusinessDayType::Preceding),
+        date(2024, 7, 3)
+    );
+    // Saturday 1 June rolls back into May
+    assert_eq!(
+        calendar.adjust_date(date(2024, 6, 1), BusinessDayType::Preceding),
+        date(2024, 5, 31)
+    );
+    // Labor Day rolls back over the weekend into August
+    assert_eq!(
+        calendar.adjust_date(date(2024, 9, 2), BusinessDayType::Preceding),
+        date(2024, 8, 30)
+    );
+}
+
+#[test]
+fn modified_preceding() {
+    let calendar = calendar_us();
+
+    assert_eq!(
+        calendar.adjust_date(date(2024, 6, 1), BusinessDayType::ModifiedPreceding),
+        date(2024, 6, 3)
+    );
+    assert_eq!(
+        calendar.adjust_date(date(2024, 9, 2), BusinessDayType::ModifiedPreceding),
+        date(2024, 9, 3)
+    );
+}
+
+#[test]
+fn weekend_days() {
+    let mut calendar = ElemCalendar::new("AE");
+
+    calendar.set_weekend_days(&[5, 6]);
+
+    // Friday 1 March 2024
+    assert_eq!(
+        calendar.adjust_date(date(2024, 3, 1), BusinessDayType::Following),
+        date(2024, 3, 3)
+    );
+    assert_eq!(
+        calendar.adjust_date(date(2024, 3, 2), BusinessDayType::Preceding),
+        date(2024, 2, 29)
+    );
+}
+
+#[test]
+fn adjustment_and_business_days_agree() {
+    let calendar = calendar_us();
+    let serial1 = CoreUtility::date_to_serial(date(2024, 1, 1));
+    let serial2 = CoreUtility::date_to_serial(date(2025, 1, 1));
+
+    let unadjusted = (serial1..serial2)
+        .map(CoreUtility::serial_to_date)
+        .filter(|o| calendar.adjust_date(*o, BusinessDayType::Following) == *o)
+        .count();
+
+    // 366 days less 104 weekend days and 3 weekday holidays
+    assert_eq!(unadjusted, 259);
+    assert_eq!(
+        CoreUtility::business_days(serial1, serial2, &calendar),
+        unadjusted
+    );
+}
+
+/// Returns the calendar of an engine resolved from a name.
+///
+/// # Arguments
+///
+/// * `calc_engine` - The engine.
+/// * `name` - Name of the calendar.
+///
+/// # Return
+///
+/// * See description.
+fn resolve(calc_engine: &CalcEngine, name: &str) -> ElemCalendar {
+    calc_engine
+        .calc_mgr()
+        .list_calendar()
+        .resolve(name)
+        .unwrap()
+}
+
+#[test]
+fn joint_calendar() {
+    let mut calendar_ae = ElemCalendar::new("AE");
+    calendar_ae.set_weekend_days(&[5, 6]);
+    calendar_ae.add_holiday(ElemHoliday::new_date("Bank Holiday", date(2024, 9, 4)));
+    let calendar_us = calendar_us();
+
+    let calendar = ElemCalendar::new_joint("US+AE", &[&calendar_us, &calendar_ae]);
+
+    assert_eq!(calendar.weekend_days(), vec![0, 5, 6]);
+    assert_eq!(calendar.holidays().len(), 4);
+    // Independence Day rolls over the joint Friday to Sunday weekend
+    assert_eq!(
+        calendar.adjust_date(date(2024, 7, 4), BusinessDayType::Following),
+        date(2024, 7, 8)
+    );
+    // Holiday of the second calendar only
+    assert_eq!(
+        calendar.adjust_date(date(2024, 9, 4), BusinessDayType::Following),
+        date(2024, 9, 5)
+    );
+    assert_eq!(
+        calendar_us.adjust_date(date(2024, 9, 4), BusinessDayType::Following),
+        date(2024, 9, 4)
+    );
+
+    let calc_engine = common::load_engine("calendar.json");
+    let joint = resolve(&calc_engine, "US+AE");
+    let named = resolve(&calc_engine, "US-AE");
+    for o in [date(2024, 7, 4), date(2024, 9, 4), date(2024, 12, 25)] {
+        assert_eq!(
+            named.adjust_date(o, BusinessDayType::Following),
+            joint.adjust_date(o, BusinessDayType::Following)
+        );
+    }
+}
+
+#[test]
+fn ics_import() {
+    let calc_engine = common::load_engine("calendar.json");
+    let calendar = resolve(&calc_engine, "FR");
+
+    let names: Vec<&str> = calendar.holidays().iter().map(|o| o.name()).collect();
+    assert_eq!(
+        names,
+        [
+            "Fête nationale",
+            "Assomption",
+            "Jour férié de mai",
+            "Action de grâce",
+            "Pont de l’Ascension"
+        ]
+    );
+
+    for (holiday, adjusted) in [
+        // Yearly fixed holidays (Monday 14 July 2025, Thursday 15 August 2024)
+        (date(2025, 7, 14), date(2025, 7, 15)),
+        (date(2024, 8, 15), date(2024, 8, 16)),
+        // Last Monday of May 2025
+        (date(2025, 5, 26), date(2025, 5, 27)),
+        // Fourth Thursday of November 2025
+        (date(2025, 11, 27), date(2025, 11, 28)),
+        // Single date holiday (Friday)
+        (date(2024, 5, 10), date(2024, 5, 13)),
+        (date(2025, 5, 9), date(2025, 5, 9)),
+    ] {
+        assert_eq!(
+            calendar.adjust_date(holiday, BusinessDayType::Following),
+            adjusted
+        );
+    }
+}
+
+#[test]
+fn ics_errors() {
+    for (rule, kind) in [
+        (
+            "FREQ=YEARLY;BYMONTH=11;BYDAY=4XX",
+            Some(ErrorType::Calendar),
+        ),
+        (
+            "FREQ=YEARLY;BYMONTH=11;BYDAY=ÜTH",
+            Some(ErrorType::Calendar),
+        ),
+        ("FREQ=YEARLY;BYMONTH=11;BYDAY=TH", None),
+        ("FREQ=YEARLY;BYMONTH=11;BYDAY=+4TH", None),
+    ] {
+        let text = format!(
+            "BEGIN:VCALENDAR\nBEGIN:VEVENT\nSUMMARY:Thanksgiving\n\
+             DTSTART;VALUE=DATE:20241128\nRRULE:{}\nEND:VEVENT\nEND:VCALENDAR\n",
+            rule
+        );
+        let result = ElemCalendar::new_from_ics("US", text.as_str());
+        assert_eq!(result.as_ref().err().map(|o| o.kind()), kind, "{}", rule);
+    }
+
+    // Non-ASCII characters within the start date
+    let text = "BEGIN:VEVENT\nSUMMARY:Fête\nDTSTART:２０２４0714\nEND:VEVENT\n";
+    match ElemCalendar::new_from_ics("FR", text) {
+        Err(e) => assert_eq!(e.kind(), ErrorType::Calendar),
+        Ok(_o) => panic!("invalid start date imported"),
+    }
+}
+
+#[test]
+fn json_round_trip() {
+    let calc_engine = common::load_engine("calendar.json");
+    let text = CalcJsonSerialize::new(calc_engine.calc_manager())
+        .serialize(amfnengine::JSON_SERIALIZE_CALENDARS);
+
+    let calc_engine_new = CalcEngine::new();
+    CalcJsonDeserialize::new(calc_engine_new.calc_manager())
+        .deserialize(text.clone())
+        .unwrap();
+
+    assert_eq!(
+        CalcJsonSerialize::new(calc_engine_new.calc_manager())
+            .serialize(amfnengine::JSON_SERIALIZE_CALENDARS),
+        text
+    );
+
+    let serial1 = CoreUtility::date_to_serial(date(2024, 1, 1));
+    let serial2 = CoreUtility::date_to_serial(date(2026, 1, 1));
+    for name in ["US", "AE", "US-AE", "FR"] {
+        let calendar = resolve(&calc_engine, name);
+        let calendar_new = resolve(&calc_engine_new, name);
+
+        assert_eq!(calendar_new.weekend_days(), calendar.weekend_days());
+        assert!((serial1..serial2)
+            .map(CoreUtility::serial_to_date)
+            .all(|o| {
+                calendar_new.adjust_date(o, BusinessDayType::ModifiedFollowing)
+                    == calendar.adjust_date(o, BusinessDayType::ModifiedFollowing)
+            }));
+    }
+}
+
+#[test]
+fn event_dates_adjusted() {
+    let calc_engine = common::load_engine("calendar.json");
+    assert!(calc_engine.calc_mgr().select_cashflow_by_name("Loan"));
+    calc_engine.balance_cashflow().unwrap();
+
+    let calc_mgr = calc_engine.calc_mgr();
+    let list_am = calc_mgr.list_cashflow().list_amortization().unwrap();
+    let mut dates: Vec<NaiveDate> = Vec::new();
+    let mut index: usize = 0;
+    while list_am.get_element(index) {
+        if list_am.elem_type() == ExtensionType::PrincipalChange {
+            dates.push(list_am.event_date());
+        }
+        index += 1;
+    }
+
+    assert_eq!(
+        dates,
+        [
+            // Independence Day (US calendar, following)
+            date(2024, 7, 5),
+            // Sunday (US+AE calendar, modified following)
+            date(2024, 8, 5),
+            // AE holiday
+            date(2024, 9, 5),
+            // Friday is an AE weekend day
+            date(2024, 10, 7),
+            date(2024, 11, 4),
+        ]
+    );
+}
@@ -0,0 +1,117 @@
+// Copyright (c) 2021 ShiftLeft Software
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+mod common;
+
+use common::decimal;
+
+use amfnengine::core::{ListDescriptor, ListParameter};
+use amfnengine::{ErrorType, TokenType};
+
+const DESCRIPTOR: &str = "descriptor(\"Fee\", \"Arrangement\", \"\", \"\")";
+
+#[test]
+fn infers_result_type() {
+    let calc_engine = common::load_engine("loan.json");
+
+    for (expression, expected) in [
+        ("1 + 2", TokenType::Integer),
+        ("round(1.5, 2)", TokenType::Decimal),
+        ("uppercase(\"a\")", TokenType::String),
+        ("if(1, 1, \"a\")", TokenType::Unknown),
+        ("decBalance * 2", TokenType::Decimal),
+        ("set(intX, 5), intX + 1", TokenType::Integer),
+        (
+            "datediff(20240101, 20240201, \"1-month\", 1, 0)",
+            TokenType::Integer,
+        ),
+    ] {
+        let result = calc_engine.check_expression(None, None, expression, false);
+
+        assert_eq!(result.unwrap(), expected, "{}", expression);
+    }
+}
+
+#[test]
+fn errors_report_kind_and_position() {
+    let calc_engine = common::load_engine("loan.json");
+
+    for (expression, kind, position) in [
+        ("missing + 1", ErrorType::InvalidSymbol, 0),
+        ("round(\"a\")", ErrorType::Decimal, 6),
+        ("1 + round(\"a\", 2)", ErrorType::Decimal, 10),
+        ("len(5)", ErrorType::String, 4),
+        ("datediff(1, 2)", ErrorType::Incomplete, 13),
+        (
+            "datediff(20240101, 20240201, 5, 1, 0)",
+            ErrorType::String,
+            29,
+        ),
+        ("nosuch(1)", ErrorType::Function, 0),
+        ("am(\"x\", \"Bogus\")", ErrorType::Element, 8),
+        ("parse(\"a\", \",\", 1)", ErrorType::Alpha, 16),
+        ("strFrequency + 1", ErrorType::InvalidOperand, 13),
+    ] {
+        let error = calc_engine
+            .check_expression(None, None, expression, false)
+            .unwrap_err();
+
+        assert_eq!(error.kind(), kind, "{}", expression);
+        assert_eq!(error.position(), position, "{}", expression);
+        assert_eq!(error.expression(), expression);
+    }
+}
+
+#[test]
+fn parameters_in_scope() {
+    let calc_engine = common::load_engine("loan.json");
+    let mut list_parameter = ListParameter::new();
+    assert!(list_parameter.add_parameter("param1", "", "", false));
+    assert!(list_parameter.set_decimal(decimal("1.5")));
+
+    let result = calc_engine.check_expression(None, Some(&list_parameter), "param1 * 2", false);
+    assert_eq!(result.unwrap(), TokenType::Decimal);
+
+    let error = calc_engine
+        .check_expression(None, None, "param1 * 2", false)
+        .unwrap_err();
+    assert_eq!(error.kind(), ErrorType::InvalidSymbol);
+}
+
+#[test]
+fn descriptors_in_scope() {
+    let calc_engine = common::load_engine("loan.json");
+    let mut list_descriptor = ListDescriptor::new();
+    assert!(list_descriptor.add_descriptor(
+        "Fee",
+        "Arrangement",
+        "",
+        "",
+        String::from("100"),
+        "",
+        false,
+        false
+    ));
+
+    let result = calc_engine.check_expression(Some(&list_descriptor), None, DESCRIPTOR, false);
+    assert_eq!(result.unwrap(), TokenType::String);
+
+    let error = calc_engine
+        .check_expression(None, None, DESCRIPTOR, false)
+        .unwrap_err();
+    assert_eq!(error.kind(), ErrorType::InvalidSymbol);
+
+    // Descriptor names known only when evaluated are not resolved
+    let result = calc_engine.check_expression(
+        None,
+        None,
+        "descriptor(\"Fee\", strFrequency, \"\", \"\")",
+        false,
+    );
+    assert_eq!(result.unwrap(), TokenType::String);
+}
@@ -0,0 +1,80 @@
+//! Helpers shared by the integration tests.
+// Copyright (c) 2021 ShiftLeft Software
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![allow(dead_code)]
+
+use chrono::NaiveDate;
+use rust_decimal::prelude::*;
+
+use amfnengine::engine::{CalcEngine, CalcJsonDeserialize};
+
+/// Read a Json document from the test data directory.
+///
+/// # Arguments
+///
+/// * `name` - File name of the document.
+///
+/// # Return
+///
+/// * See description.
+pub fn read_data(name: &str) -> String {
+    let path = format!("{}/tests/data/{}", env!("CARGO_MANIFEST_DIR"), name);
+
+    std::fs::read_to_string(path.as_str()).unwrap()
+}
+
+/// Create an engine, load a Json document from the test
+/// data directory and select the first cashflow.
+///
+/// # Arguments
+///
+/// * `name` - File name of the document.
+///
+/// # Return
+///
+/// * See description.
+pub fn load_engine(name: &str) -> CalcEngine {
+    let calc_engine = CalcEngine::new();
+
+    CalcJsonDeserialize::new(calc_engine.calc_manager())
+        .deserialize(read_data(name))
+        .unwrap();
+    calc_engine.init_engine("en-US");
+    calc_engine.init_cashflow(0);
+
+    calc_engine
+}
+
+/// Parse a decimal literal.
+///
+/// # Arguments
+///
+/// * `value` - Decimal string.
+///
+/// # Return
+///
+/// * See description.
+pub fn decimal(value: &str) -> Decimal {
+    Decimal::from_str(value).unwrap()
+}
+
+/// Create a date from its year, month and day.
+///
+/// # Arguments
+///
+/// * `year` - Year of the date.
+/// * `month` - Month of the date.
+/// * `day` - Day of the date.
+///
+/// # Return
+///
+/// * See description.
+pub fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
@@ -0,0 +1,235 @@
+// Copyright (c) 2021 ShiftLeft Software
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+mod common;
+
+use common::date;
+
+use amfnengine::core::CoreUtility;
+use amfnengine::engine::{CalcEngine, CalcJsonDeserialize};
+use amfnengine::{ErrorType, TokenType};
+
+#[test]
+fn date_from_ymd_rejects_invalid_dates() {
+    assert_eq!(
+        CoreUtility::date_from_ymd(2024, 2, 29),
+        Some(date(2024, 2, 29))
+    );
+    assert_eq!(
+        CoreUtility::date_from_ymd(2000, 2, 29),
+        Some(date(2000, 2, 29))
+    );
+    assert_eq!(CoreUtility::date_from_ymd(2023, 2, 29), None);
+    assert_eq!(CoreUtility::date_from_ymd(1900, 2, 29), None);
+    assert_eq!(CoreUtility::date_from_ymd(2024, 4, 31), None);
+    assert_eq!(CoreUtility::date_from_ymd(2024, 0, 1), None);
+    assert_eq!(CoreUtility::date_from_ymd(2024, 13, 1), None);
+    assert_eq!(CoreUtility::date_from_ymd(2024, 1, 0), None);
+    assert_eq!(CoreUtility::date_from_ymd(2024, 1, 32), None);
+    assert_eq!(CoreUtility::date_from_ymd(usize::MAX, 1, 1), None);
+}
+
+#[test]
+fn dates_before_serial_base_year_rejected() {
+    assert!(!CoreUtility::date_in_range(date(1899, 12, 31)));
+    assert!(CoreUtility::date_in_range(date(1900, 1, 1)));
+
+    assert_eq!(CoreUtility::integer_to_date(18991231), None);
+    assert_eq!(
+        CoreUtility::integer_to_date(19000101),
+        Some(date(1900, 1, 1))
+    );
+    assert_eq!(CoreUtility::integer_to_date(20231301), None);
+    assert_eq!(CoreUtility::parse_date("1899-12-31"), None);
+    assert_eq!(
+        CoreUtility::parse_date("1900-01-01"),
+        Some(date(1900, 1, 1))
+    );
+    assert_eq!(CoreUtility::parse_date("2023-02-29"), None);
+}
+
+#[test]
+fn serial_round_trip() {
+    assert_eq!(CoreUtility::date_to_serial(date(1900, 1, 1)), 1);
+
+    let mut serial = CoreUtility::date_to_serial(date(1900, 1, 1));
+    let mut current = date(1900, 1, 1);
+    while current < date(2101, 1, 1) {
+        assert_eq!(CoreUtility::date_to_serial(current), serial);
+        assert_eq!(CoreUtility::serial_to_date(serial), current);
+        current = current.succ_opt().unwrap();
+        serial += 1;
+    }
+}
+
+#[test]
+fn event_date_before_serial_base_year_rejected() {
+    let calc_engine = CalcEngine::new();
+    let input = common::read_data("loan.json").replacen("2024-01-01", "1899-12-31", 1);
+
+    let error = CalcJsonDeserialize::new(calc_engine.calc_manager())
+        .deserialize(input)
+        .unwrap_err();
+
+    assert_eq!(error.kind(), amfnengine::ErrorType::Date);
+}
+
+#[test]
+fn expression_dates() {
+    let calc_engine = common::load_engine("loan.json");
+    let evaluate = |expression: &str| calc_engine.evaluate_expression(None, expression, false);
+
+    // The day is limited to the end of the new month
+    assert_eq!(
+        evaluate("dateaddmonths(20240131, 1)").sym_integer(),
+        20240229
+    );
+    assert_eq!(
+        evaluate("dateaddmonths(20240131, 13)").sym_integer(),
+        20250228
+    );
+    assert_eq!(evaluate("dateendofmonth(19000215)").sym_integer(), 19000228);
+
+    for expression in [
+        "dateaddmonths(18991231, 1)",
+        "dateaddmonths(20230229, 1)",
+        "dateaddmonths(19000115, -1)",
+        "dateaddmonths(19000131, -1, 1)",
+        "dateendofmonth(20241301)",
+    ] {
+        assert!(
+            evaluate(expression)
+                .sym_string()
+                .starts_with(amfnengine::ERROR_PREFIX),
+            "{}",
+            expression
+        );
+    }
+}
+
+#[test]
+fn expression_date_parts() {
+    let calc_engine = common::load_engine("loan.json");
+    let evaluate = |expression: &str| calc_engine.evaluate_expression(None, expression, false);
+
+    for (expression, expected) in [
+        ("dateyear(20240315)", 2024),
+        ("datemonth(20240315)", 3),
+        ("dateday(20240315)", 15),
+        // Sunday is 0
+        ("dateweekday(20240107)", 0),
+        ("dateweekday(20240101)", 1),
+        ("dateweekday(20240106)", 6),
+        ("datedayofyear(20240101)", 1),
+        ("datedayofyear(20240301)", 61),
+        ("datedayofyear(20230301)", 60),
+        ("datedayofyear(20241231)", 366),
+        ("dateisleapyear(20240101)", 1),
+        ("dateisleapyear(20230101)", 0),
+        ("dateisleapyear(19000101)", 0),
+        ("dateisleapyear(20000101)", 1),
+        ("dateendofmonth(20240210)", 20240229),
+        ("dateendofmonth(20230210)", 20230228),
+        ("dateendofmonth(20241231)", 20241231),
+    ] {
+        let result = evaluate(expression);
+
+        assert_eq!(result.sym_type(), TokenType::Integer, "{}", expression);
+        assert_eq!(result.sym_integer(), expected, "{}", expression);
+    }
+}
+
+#[test]
+fn expression_add_months_end_of_month() {
+    let calc_engine = common::load_engine("loan.json");
+    let evaluate = |expression: &str| calc_engine.evaluate_expression(None, expression, false);
+
+    for (expression, expected) in [
+        ("dateaddmonths(20240430, 1)", 20240530),
+        ("dateaddmonths(20240331, -1)", 20240229),
+        ("dateaddmonths(20240315, 0)", 20240315),
+        ("dateaddmonths(20241115, 2)", 20250115),
+        // Dates at the end of the month remain at the end of the month
+        ("dateaddmonths(20240430, 1, 1)", 20240531),
+        ("dateaddmonths(20240229, 1, 1)", 20240331),
+        ("dateaddmonths(20240229, 12, 1)", 20250228),
+        ("dateaddmonths(20240315, 1, 1)", 20240415),
+        ("dateaddmonths(20240430, 1, 0)", 20240530),
+    ] {
+        assert_eq!(
+            evaluate(expression).sym_integer(),
+            expected,
+            "{}",
+            expression
+        );
+    }
+}
+
+#[test]
+fn expression_next_business_day() {
+    let calc_engine = common::load_engine("calendar.json");
+    let evaluate = |expression: &str| calc_engine.evaluate_expression(None, expression, false);
+
+    for (expression, expected) in [
+        // Independence Day (Thursday)
+        ("datenextbusinessday(20240704, \"US\")", 20240705),
+        ("datenextbusinessday(20240705, \"US\")", 20240705),
+        ("datenextbusinessday(20240706, \"US\")", 20240708),
+        // Thanksgiving
+        ("datenextbusinessday(20241128, \"US\")", 20241129),
+        // Without a calendar only weekends are skipped
+        ("datenextbusinessday(20240704)", 20240704),
+        ("datenextbusinessday(20240706)", 20240708),
+    ] {
+        assert_eq!(
+            evaluate(expression).sym_integer(),
+            expected,
+            "{}",
+            expression
+        );
+    }
+}
+
+#[test]
+fn expression_date_errors() {
+    let calc_engine = common::load_engine("calendar.json");
+
+    for expression in [
+        "dateday(20240230)",
+        "datemonth(20241301)",
+        "dateweekday(0)",
+        "datedayofyear(18991231)",
+        "dateisleapyear(20230229)",
+        "dateyear(\"2024\")",
+        "dateendofmonth(1.5)",
+        "dateaddmonths(20240131)",
+        "datenextbusinessday(20240704, \"XX\")",
+        "datenextbusinessday(20240704, 1)",
+    ] {
+        assert!(
+            calc_engine
+                .evaluate_expression(None, expression, false)
+                .sym_string()
+                .starts_with(amfnengine::ERROR_PREFIX),
+            "{}",
+            expression
+        );
+    }
+
+    for (expression, kind) in [
+        ("dateyear(\"2024\")", ErrorType::Integer),
+        ("datenextbusinessday(20240704, 1)", ErrorType::String),
+        ("dateaddmonths(20240131)", ErrorType::Incomplete),
+    ] {
+        let error = calc_engine
+            .check_expression(None, None, expression, false)
+            .unwrap_err();
+
+        assert_eq!(error.kind(), kind, "{}", expression);
+    }
+}
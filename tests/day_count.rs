@@ -0,0 +1,242 @@
+// Copyright (c) 2021 ShiftLeft Software
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+mod common;
+
+use common::decimal;
+
+use chrono::NaiveDate;
+use rust_decimal::prelude::*;
+
+use amfnengine::core::{CoreUtility, ElemCalendar, ElemHoliday};
+
+/// Brazilian calendar with the (non-fixed) Carnival holidays.
+///
+/// # Return
+///
+/// * See description.
+fn calendar_br() -> ElemCalendar {
+    let mut calendar = ElemCalendar::new("BR");
+
+    calendar.add_holiday(ElemHoliday::new_easter("Carnival Monday", -48));
+    calendar.add_holiday(ElemHoliday::new_easter("Carnival Tuesday", -47));
+
+    calendar
+}
+
+fn serial(year: i32, month: u32, day: u32) -> usize {
+    CoreUtility::date_to_serial(NaiveDate::from_ymd_opt(year, month, day).unwrap())
+}
+
+#[test]
+fn business_days_skip_weekend_and_carnival() {
+    let calendar = calendar_br();
+
+    // 2024-02-09 (Fri) to 2024-02-16: Fri 9, Wed 14 and Thu 15 (Carnival 12-13)
+    assert_eq!(
+        CoreUtility::business_days(serial(2024, 2, 9), serial(2024, 2, 16), &calendar),
+        3
+    );
+    assert_eq!(
+        CoreUtility::business_days(
+            serial(2024, 2, 9),
+            serial(2024, 2, 16),
+            &ElemCalendar::new("")
+        ),
+        5
+    );
+    assert_eq!(
+        CoreUtility::day_count_factor_with_calendar(
+            serial(2024, 2, 9),
+            serial(2024, 2, 16),
+            amfnengine::DayCountType::Business252,
+            365,
+            12,
+            &calendar
+        ),
+        Decimal::from(3) / Decimal::from(252)
+    );
+}
+
+#[test]
+fn business_252_daily_factor_matches_b3() {
+    // B3 publishes the daily DI factor rounded to 8 places:
+    // 13.65% a.a. -> 1.00050788
+    let factor = CoreUtility::rate_business_252(decimal("0.1365"), 1) + Decimal::one();
+
+    assert_eq!(factor.round_dp(8), decimal("1.00050788"));
+}
+
+#[test]
+fn business_252_compounds_in_decimal() {
+    assert_eq!(
+        CoreUtility::rate_business_252(decimal("0.10"), 252).round_dp(20),
+        decimal("0.10")
+    );
+    assert_eq!(
+        CoreUtility::rate_business_252(decimal("0.10"), 126).round_dp(20),
+        decimal("0.04880884817015154699")
+    );
+    assert_eq!(
+        CoreUtility::rate_business_252(decimal("0.1375"), 21).round_dp(20),
+        decimal("0.01079391108213219193")
+    );
+    assert_eq!(
+        CoreUtility::rate_business_252(decimal("0.10"), 0),
+        Decimal::zero()
+    );
+}
+
+#[test]
+fn business_252_uses_interest_change_calendar() {
+    let calc_engine = common::load_engine("business_252.json");
+
+    let result = calc_engine.balance_cashflow().unwrap();
+
+    // 1,000,000 x (1.10 ^ (3 / 252) - 1) over the Carnival week
+    assert_eq!(
+        calc_engine.round_currency(result.interest_total()),
+        decimal("1135.29")
+    );
+}
+
+/// Day count factor between two dates.
+///
+/// # Arguments
+///
+/// * `date1` - First date (year, month, day).
+/// * `date2` - Second date (year, month, day).
+/// * `day_count_basis` - Day count basis.
+///
+/// # Return
+///
+/// * See description.
+fn factor(
+    date1: (i32, u32, u32),
+    date2: (i32, u32, u32),
+    day_count_basis: amfnengine::DayCountType,
+) -> Decimal {
+    CoreUtility::day_count_factor(
+        serial(date1.0, date1.1, date1.2),
+        serial(date2.0, date2.1, date2.2),
+        day_count_basis,
+        365,
+        12,
+    )
+}
+
+#[test]
+fn actual_fixed_denominators() {
+    for (date1, date2, actual_360, actual_365f, actual_364) in [
+        // 182 days
+        (
+            (2024, 1, 15),
+            (2024, 7, 15),
+            "0.5055555556",
+            "0.4986301370",
+            "0.5",
+        ),
+        // 366 days over the leap year
+        (
+            (2024, 1, 1),
+            (2025, 1, 1),
+            "1.0166666667",
+            "1.0027397260",
+            "1.0054945055",
+        ),
+        // 28 days in February
+        (
+            (2023, 2, 1),
+            (2023, 3, 1),
+            "0.0777777778",
+            "0.0767123288",
+            "0.0769230769",
+        ),
+    ] {
+        for (day_count_basis, expected) in [
+            (amfnengine::DayCountType::Actual360, actual_360),
+            (amfnengine::DayCountType::Actual365F, actual_365f),
+            (amfnengine::DayCountType::Actual364, actual_364),
+        ] {
+            assert_eq!(
+                factor(date1, date2, day_count_basis).round_dp(10),
+                decimal(expected),
+                "{:?} {:?} {:?}",
+                date1,
+                date2,
+                day_count_basis
+            );
+        }
+    }
+}
+
+#[test]
+fn thirty_360_isda_and_30e_360_isda() {
+    for (date1, date2, days_isda, days_e_isda) in [
+        // 31st to 31st
+        ((2024, 1, 31), (2024, 3, 31), 60, 60),
+        ((2024, 1, 30), (2024, 3, 31), 60, 60),
+        // 31st end date is only adjusted by 30/360 ISDA
+        // if the start date is the 30th or 31st
+        ((2024, 1, 15), (2024, 3, 31), 76, 75),
+        ((2024, 3, 31), (2024, 4, 30), 30, 30),
+        // February end dates
+        ((2023, 2, 28), (2023, 8, 31), 180, 180),
+        ((2024, 2, 29), (2025, 2, 28), 360, 360),
+        ((2023, 2, 28), (2023, 8, 15), 165, 165),
+        ((2024, 1, 15), (2024, 2, 29), 44, 45),
+        ((2023, 2, 28), (2023, 3, 31), 30, 30),
+        // February 28 of a leap year is not the end of month
+        ((2024, 2, 28), (2024, 3, 31), 33, 32),
+        ((2024, 2, 28), (2024, 3, 30), 32, 32),
+    ] {
+        for (day_count_basis, days) in [
+            (amfnengine::DayCountType::Dc30ISDA, days_isda),
+            (amfnengine::DayCountType::Dc30EISDA, days_e_isda),
+        ] {
+            assert_eq!(
+                factor(date1, date2, day_count_basis),
+                Decimal::from(days) / Decimal::from(360),
+                "{:?} {:?} {:?}",
+                date1,
+                date2,
+                day_count_basis
+            );
+        }
+    }
+}
+
+#[test]
+fn mnemonics_round_trip() {
+    for (day_count_basis, mnemonic, short) in [
+        (amfnengine::DayCountType::Actual360, "actual-360", "Act/360"),
+        (
+            amfnengine::DayCountType::Actual365F,
+            "actual-365F",
+            "Act/365F",
+        ),
+        (amfnengine::DayCountType::Actual364, "actual-364", "Act/364"),
+        (amfnengine::DayCountType::Dc30ISDA, "30-ISDA", "30/ISDA"),
+        (amfnengine::DayCountType::Dc30EISDA, "30E-ISDA", "30E/ISDA"),
+        (
+            amfnengine::DayCountType::Business252,
+            "business-252",
+            "Bus/252",
+        ),
+    ] {
+        assert_eq!(
+            CoreUtility::get_day_count_basis_mnemonic(day_count_basis),
+            mnemonic
+        );
+        assert_eq!(
+            CoreUtility::get_day_count_basis_mnemonic_short(day_count_basis),
+            short
+        );
+        assert_eq!(CoreUtility::get_day_count_basis(mnemonic), day_count_basis);
+    }
+}
@@ -0,0 +1,196 @@
+// Copyright (c) 2021 ShiftLeft Software
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+mod common;
+
+use common::decimal;
+
+use rust_decimal::prelude::*;
+
+use amfnengine::core::{ElemDefaultScenario, ElemPrepayment};
+use amfnengine::engine::CalcEngine;
+use amfnengine::{ListTrait, PrepaymentType};
+
+/// The default values of the payments of the selected cashflow in
+/// date order: defaulted principal, recovery, loss and delinquent
+/// balance.
+///
+/// # Arguments
+///
+/// * `calc_engine` - The engine.
+///
+/// # Return
+///
+/// * See description.
+fn defaults(calc_engine: &CalcEngine) -> Vec<(Decimal, Decimal, Decimal, Decimal)> {
+    let calc_mgr = calc_engine.calc_mgr();
+    let list_am = calc_mgr.list_cashflow().list_amortization().unwrap();
+
+    let mut defaults: Vec<(Decimal, Decimal, Decimal, Decimal)> = Vec::new();
+    let mut index: usize = 0;
+    while list_am.get_element(index) {
+        if list_am.elem_type() == amfnengine::ExtensionType::PrincipalChange && index > 0 {
+            defaults.push((
+                list_am.defaulted_principal(),
+                list_am.recovery(),
+                list_am.loss(),
+                list_am.delinquent_balance(),
+            ));
+        }
+        index += 1;
+    }
+
+    defaults
+}
+
+#[test]
+fn cdr_to_mdr() {
+    // MDR = 1 - (1 - CDR) ^ (1 / 12): 6% CDR is 0.5143% MDR
+    // (calculated in Decimal to 16 places)
+    let default_scenario = ElemDefaultScenario::new(decimal("6"), decimal("40"), 0);
+
+    assert_eq!(
+        default_scenario.mdr(12).round_dp(16),
+        decimal("0.0051430128318229")
+    );
+    assert_eq!(default_scenario.mdr(1), decimal("0.06"));
+    assert_eq!(default_scenario.mdr(0), Decimal::zero());
+
+    // 0.2% CDR (the first month of 100% PSA as a default rate)
+    let default_scenario = ElemDefaultScenario::new(decimal("0.2"), decimal("40"), 0);
+
+    assert_eq!(
+        default_scenario.mdr(12).round_dp(16),
+        decimal("0.0001668196399456")
+    );
+
+    // A CDR of 100% or more defaults the whole balance
+    for cdr in ["100", "150"] {
+        let default_scenario = ElemDefaultScenario::new(decimal(cdr), decimal("40"), 0);
+
+        assert_eq!(default_scenario.mdr(12), Decimal::one(), "{}", cdr);
+    }
+    let default_scenario = ElemDefaultScenario::new(decimal("-1"), decimal("40"), 0);
+
+    assert_eq!(default_scenario.mdr(12), Decimal::zero());
+}
+
+#[test]
+fn loss_severity() {
+    // 40% severity recovers 60% of the liquidated principal
+    let default_scenario = ElemDefaultScenario::new(decimal("6"), decimal("40"), 0);
+
+    assert_eq!(default_scenario.recovery(decimal("1000")), decimal("600"));
+
+    // The severity is limited to 0% to 100%
+    let default_scenario = ElemDefaultScenario::new(decimal("6"), decimal("120"), 0);
+
+    assert_eq!(default_scenario.recovery(decimal("1000")), Decimal::zero());
+    let default_scenario = ElemDefaultScenario::new(decimal("6"), decimal("-20"), 0);
+
+    assert_eq!(default_scenario.recovery(decimal("1000")), decimal("1000"));
+}
+
+#[test]
+fn defaults_liquidated_after_recovery_lag() {
+    // 200,000 at 6% over 360 months paying 1,199.10 with 6% CDR,
+    // 40% severity and a recovery lag of 2 periods: the first
+    // period defaults 199,800.90 x 0.5143% after scheduled principal
+    // which is liquidated two periods later
+    let calc_engine = common::load_engine("loan.json");
+
+    calc_engine.set_default_scenario(Some(ElemDefaultScenario::new(
+        decimal("6"),
+        decimal("40"),
+        2,
+    )));
+    let result = calc_engine.balance_cashflow().unwrap();
+    let defaults = defaults(&calc_engine);
+
+    assert_eq!(
+        defaults[..4],
+        [
+            (
+                decimal("1027.58"),
+                Decimal::zero(),
+                Decimal::zero(),
+                decimal("1027.58")
+            ),
+            (
+                decimal("1021.27"),
+                Decimal::zero(),
+                Decimal::zero(),
+                decimal("2048.85")
+            ),
+            (
+                decimal("1014.99"),
+                decimal("616.55"),
+                decimal("411.03"),
+                decimal("2036.26")
+            ),
+            (
+                decimal("1008.75"),
+                decimal("612.76"),
+                decimal("408.51"),
+                decimal("2023.74")
+            ),
+        ]
+    );
+
+    // Every default is recovered, lost or still delinquent
+    assert_eq!(
+        result.recovery_total() + result.loss_total() + result.delinquent_balance(),
+        result.defaulted_principal_total()
+    );
+    assert_eq!(result.delinquent_balance(), defaults.last().unwrap().3);
+    assert_eq!(
+        (result.scheduled_principal_total()
+            + result.defaulted_principal_total()
+            + result.balance())
+        .round_dp(2),
+        decimal("200000")
+    );
+}
+
+#[test]
+fn defaults_precede_prepayments() {
+    // The prepayment rate applies to the performing balance after
+    // the defaulted principal is removed: (199,800.90 - 1,027.58)
+    // x 0.016682% (100% PSA in the first month)
+    let calc_engine = common::load_engine("loan.json");
+
+    calc_engine.set_default_scenario(Some(ElemDefaultScenario::new(
+        decimal("6"),
+        decimal("40"),
+        0,
+    )));
+    calc_engine.set_prepayment(Some(ElemPrepayment::new(
+        PrepaymentType::Psa,
+        decimal("100"),
+        &[],
+    )));
+    let result = calc_engine.balance_cashflow().unwrap();
+
+    let calc_mgr = calc_engine.calc_mgr();
+    let list_am = calc_mgr.list_cashflow().list_amortization().unwrap();
+    let mut index: usize = 1;
+    while list_am.get_element(index)
+        && list_am.elem_type() != amfnengine::ExtensionType::PrincipalChange
+    {
+        index += 1;
+    }
+
+    assert_eq!(list_am.defaulted_principal(), decimal("1027.58"));
+    assert_eq!(list_am.prepaid_principal(), decimal("33.16"));
+    assert_eq!(list_am.surviving_balance(), decimal("198740.16"));
+
+    // Without a recovery lag the defaults are liquidated as they occur
+    assert_eq!(list_am.recovery(), decimal("616.55"));
+    assert_eq!(list_am.loss(), decimal("411.03"));
+    assert_eq!(result.delinquent_balance(), Decimal::zero());
+}
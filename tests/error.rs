@@ -0,0 +1,93 @@
+// Copyright (c) 2021 ShiftLeft Software
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+mod common;
+
+use std::error::Error;
+
+use amfnengine::core::ElemError;
+use amfnengine::engine::{CalcEngine, CalcJsonDeserialize};
+use amfnengine::ErrorType;
+
+const EXPRESSION: &str = "round(1199.10 + intTerm, 2)";
+
+#[test]
+fn balance_error_context() {
+    let calc_engine = common::load_engine("error_context.json");
+
+    let error = match calc_engine.balance_cashflow() {
+        Err(e) => e,
+        Ok(_o) => panic!("Balance succeeded"),
+    };
+
+    assert_eq!(error.kind(), ErrorType::InvalidSymbol);
+    assert_eq!(error.cashflow_name(), "Mortgage");
+    assert_eq!(error.event_index(), 2);
+    assert_eq!(error.column_name(), "Value-expr");
+    assert_eq!(error.expression(), EXPRESSION);
+    assert_eq!(error.position(), 16);
+    assert_eq!(&error.expression()[error.position()..][..7], "intTerm");
+    assert!(error.pointer().is_empty());
+    assert!(error.message().is_empty());
+
+    assert_eq!(
+        error.to_string(),
+        format!(
+            "InvalidSymbol error (cashflow \"Mortgage\", event 2, column \"Value-expr\", \
+             expression \"{}\" at position 16)",
+            EXPRESSION
+        )
+    );
+
+    // The calculations report the same context
+    let error_yield = match calc_engine.calculate_yield() {
+        Err(e) => e,
+        Ok(_o) => panic!("Calculate yield succeeded"),
+    };
+    assert_eq!(error_yield, error);
+}
+
+#[test]
+fn deserialize_error_context() {
+    let input = common::read_data("loan.json").replace("\"2024-02-01\"", "\"2024-02-30\"");
+    let calc_engine = CalcEngine::new();
+
+    let error = CalcJsonDeserialize::new(calc_engine.calc_manager())
+        .deserialize(input)
+        .unwrap_err();
+
+    assert_eq!(error.kind(), ErrorType::Date);
+    assert_eq!(error.cashflow_name(), "Loan");
+    assert_eq!(error.event_index(), 2);
+    assert!(error.column_name().is_empty());
+    assert!(error.expression().is_empty());
+    assert_eq!(error.position(), usize::MAX);
+    assert_eq!(error.to_string(), "Date error (cashflow \"Loan\", event 2)");
+}
+
+#[test]
+fn standard_error() {
+    let error: Box<dyn Error> = Box::new(ElemError::from(ErrorType::Json));
+
+    assert_eq!(error.to_string(), "Json error");
+    assert!(error.source().is_none());
+
+    let mut error = ElemError::new(ErrorType::Json);
+    error.set_pointer("/cashflows/0");
+    error.set_message("missing name");
+    assert_eq!(
+        error.to_string(),
+        "Json error (pointer \"/cashflows/0\"): missing name"
+    );
+
+    // The context set first is kept
+    error.set_expression("1 + a", 4);
+    error.set_expression("a", 0);
+    assert_eq!(error.expression(), "1 + a");
+    assert_eq!(error.position(), 4);
+}
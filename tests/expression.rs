@@ -0,0 +1,360 @@
+// Copyright (c) 2021 ShiftLeft Software
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+mod common;
+
+use common::decimal;
+
+use std::sync::Arc;
+
+use amfnengine::core::{CoreUtility, ElemSymbol};
+use amfnengine::engine::{CalcEngine, CalcUtility};
+use amfnengine::{ErrorType, RoundType, TokenType};
+
+fn evaluate(calc_engine: &CalcEngine, expression: &str) -> ElemSymbol {
+    calc_engine.evaluate_expression(None, expression, false)
+}
+
+#[test]
+fn operator_precedence_and_associativity() {
+    let calc_engine = common::load_engine("loan.json");
+
+    for (expression, expected) in [
+        ("2 + 3 * 4", 14),
+        ("(2 + 3) * 4", 20),
+        ("1 + 2 * 3 - 4 / 2", 5),
+        ("10 - 4 - 3", 3),
+        ("100 / 10 / 5", 2),
+        ("7 mod 3", 1),
+        ("2 exp 10", 1024),
+        ("-2 + 5", 3),
+        ("2 > 1 + 3", 0),
+        ("not 0", 1),
+        ("if(1 < 2, 10, 20)", 10),
+    ] {
+        let result = evaluate(&calc_engine, expression);
+
+        assert_eq!(result.sym_type(), TokenType::Integer, "{}", expression);
+        assert_eq!(result.sym_integeri(), expected, "{}", expression);
+    }
+
+    assert_eq!(
+        evaluate(&calc_engine, "1.5 * 2").sym_decimal(),
+        decimal("3")
+    );
+    assert_eq!(evaluate(&calc_engine, "\"a\" + \"b\"").sym_string(), "ab");
+}
+
+#[test]
+fn compiled_once_and_cached() {
+    let calc_engine = common::load_engine("loan.json");
+    let expression = "1 + 2 * 3";
+
+    assert_eq!(evaluate(&calc_engine, expression).sym_integer(), 7);
+    let compiled = calc_engine
+        .calc_manager()
+        .read()
+        .unwrap()
+        .compiled_expression(expression)
+        .unwrap();
+
+    assert_eq!(evaluate(&calc_engine, expression).sym_integer(), 7);
+    assert!(Arc::ptr_eq(
+        &compiled,
+        &calc_engine
+            .calc_manager()
+            .read()
+            .unwrap()
+            .compiled_expression(expression)
+            .unwrap()
+    ));
+}
+
+#[test]
+fn compile_errors_report_position() {
+    let calc_engine = common::load_engine("loan.json");
+
+    for (expression, kind, position) in [
+        ("(1 + 2", ErrorType::RightParen, 6),
+        ("1 + 2)", ErrorType::LeftParen, 5),
+        ("1 + \"a\"", ErrorType::InvalidOperand, 2),
+        ("foo(1)", ErrorType::Function, 0),
+    ] {
+        let error = calc_engine
+            .check_expression(None, None, expression, false)
+            .unwrap_err();
+
+        assert_eq!(error.kind(), kind, "{}", expression);
+        assert_eq!(error.position(), position, "{}", expression);
+        assert!(evaluate(&calc_engine, expression)
+            .sym_string()
+            .starts_with(amfnengine::ERROR_PREFIX));
+    }
+}
+
+#[test]
+fn formatted_expression_evaluates_the_same() {
+    let calc_engine = common::load_engine("loan.json");
+
+    for expression in [
+        "1+2*3",
+        "(2+3)*4",
+        "if(1<2,round(2.345),0)",
+        "pmt(0.08/12,10,10000)",
+    ] {
+        let formatted =
+            CalcUtility::format_expression(&calc_engine.calc_mgr(), expression).unwrap();
+
+        assert_eq!(
+            CalcUtility::format_expression(&calc_engine.calc_mgr(), formatted.as_str()).unwrap(),
+            formatted
+        );
+        let result = evaluate(&calc_engine, formatted.as_str());
+        let expected = evaluate(&calc_engine, expression);
+
+        assert_eq!(result.sym_type(), expected.sym_type(), "{}", formatted);
+        assert_eq!(
+            result.sym_integer(),
+            expected.sym_integer(),
+            "{}",
+            formatted
+        );
+        assert_eq!(
+            result.sym_decimal(),
+            expected.sym_decimal(),
+            "{}",
+            formatted
+        );
+    }
+}
+
+#[test]
+fn formatted_expression_canonical_form() {
+    let calc_engine = common::load_engine("loan.json");
+
+    for (expression, expected) in [
+        // Only the parentheses required by precedence and associativity
+        ("(1 + 2) * 3", "(1 + 2) * 3"),
+        ("1 + (2 * 3)", "1 + 2 * 3"),
+        ("(1 - 2) - 3", "1 - 2 - 3"),
+        ("1 - (2 - 3)", "1 - (2 - 3)"),
+        ("10 / (2 * 5)", "10 / (2 * 5)"),
+        ("(2 exp 3) exp 2", "2 exp 3 exp 2"),
+        ("2 exp (3 exp 2)", "2 exp (3 exp 2)"),
+        ("-(1 + 2)", "-(1 + 2)"),
+        ("(not 1) and 0", "not 1 and 0"),
+        ("not (1 and 0)", "not (1 and 0)"),
+        // Spacing and built-in function names
+        ("1 +  \t 2", "1 + 2"),
+        ("Round( 1.234 ,2 )", "round(1.234, 2)"),
+        ("IF(1,2,3)", "if(1, 2, 3)"),
+        ("x[1]+x[2+1]", "x[1] + x[2 + 1]"),
+        ("1.50 + 2.0", "1.50 + 2.0"),
+        // Sequences and nested if functions
+        ("set(x, 1), x + 1", "set(x, 1),\nx + 1"),
+        ("if(1 < 2, 10, 20)", "if(1 < 2, 10, 20)"),
+        (
+            "if(1, if(0, if(1, 1, 2), 3), 4)",
+            "if(1,\n    if(0,\n        if(1, 1, 2),\n        3),\n    4)",
+        ),
+    ] {
+        let formatted =
+            CalcUtility::format_expression(&calc_engine.calc_mgr(), expression).unwrap();
+
+        assert_eq!(formatted, expected, "{}", expression);
+        assert_eq!(
+            CalcUtility::format_expression(&calc_engine.calc_mgr(), formatted.as_str()).unwrap(),
+            formatted
+        );
+    }
+}
+
+#[test]
+fn format_expression_errors() {
+    let calc_engine = common::load_engine("loan.json");
+
+    for (expression, kind, position) in [
+        ("", ErrorType::Incomplete, 0),
+        ("1 +", ErrorType::MissingOperand, 3),
+        ("(1 + 2", ErrorType::RightParen, 6),
+        ("1 + 2)", ErrorType::LeftParen, 5),
+        ("round(", ErrorType::RightParen, 6),
+    ] {
+        let error =
+            CalcUtility::format_expression(&calc_engine.calc_mgr(), expression).unwrap_err();
+
+        assert_eq!(error.kind(), kind, "{}", expression);
+        assert_eq!(error.position(), position, "{}", expression);
+    }
+}
+
+#[test]
+fn round_half_even() {
+    let calc_engine = common::load_engine("loan.json");
+
+    for (expression, expected) in [
+        ("round(2.345)", "2.34"),
+        ("round(2.355)", "2.36"),
+        ("round(2.3451)", "2.35"),
+        ("round(-2.345)", "-2.34"),
+        ("round(2.5, 0)", "2"),
+        ("round(3.5, 0)", "4"),
+        ("round(12.3456, 3)", "12.346"),
+        ("roundfraction(2.345, 0.05)", "2.35"),
+        ("roundfraction(2.375, 0.05)", "2.40"),
+        ("roundfraction(2.325, 0.05)", "2.30"),
+    ] {
+        assert_eq!(
+            evaluate(&calc_engine, expression).sym_decimal(),
+            decimal(expected),
+            "{}",
+            expression
+        );
+    }
+
+    assert_eq!(
+        CoreUtility::round(decimal("1.005"), 2, RoundType::BiasUp),
+        decimal("1.01")
+    );
+    assert_eq!(
+        CoreUtility::round_fraction(decimal("7.3"), decimal("0.25"), RoundType::Truncate),
+        decimal("7.25")
+    );
+}
+
+/// Add a user-defined function to the user preferences.
+///
+/// # Arguments
+///
+/// * `calc_engine` - The engine.
+/// * `name` - Name of the function.
+/// * `parameters` - Names of the function parameters.
+/// * `expression` - Expression body of the function.
+fn add_function(calc_engine: &CalcEngine, name: &str, parameters: &[&str], expression: &str) {
+    let parameters: Vec<String> = parameters.iter().map(|o| String::from(*o)).collect();
+
+    assert!(calc_engine
+        .calc_mgr_mut()
+        .preferences_mut()
+        .list_function_mut()
+        .add_function(name, "", &parameters, expression));
+}
+
+/// Evaluate an expression and return the error recorded
+/// in the evaluation trace.
+///
+/// # Arguments
+///
+/// * `calc_engine` - The engine.
+/// * `expression` - The expression to evaluate.
+///
+/// # Return
+///
+/// * See description.
+fn evaluate_error(calc_engine: &CalcEngine, expression: &str) -> ErrorType {
+    let (result, trace) = calc_engine.evaluate_expression_trace(None, expression, false);
+
+    assert!(
+        result.sym_string().starts_with(amfnengine::ERROR_PREFIX),
+        "{}",
+        expression
+    );
+    trace.unwrap().error()
+}
+
+#[test]
+fn user_function_binds_parameters() {
+    let calc_engine = common::load_engine("loan.json");
+    add_function(
+        &calc_engine,
+        "addRate",
+        &["decBase", "decMargin"],
+        "decBase + decMargin",
+    );
+    add_function(
+        &calc_engine,
+        "monthly",
+        &["decRate"],
+        "set(decMonthly, addRate(decRate, 0.5) / 12), round(decMonthly, 4)",
+    );
+
+    assert_eq!(
+        evaluate(&calc_engine, "addRate(5.25, 2)").sym_decimal(),
+        decimal("7.25")
+    );
+    assert_eq!(
+        evaluate(&calc_engine, "monthly(5.5)").sym_decimal(),
+        decimal("0.5")
+    );
+
+    // Symbols set within the body are not visible to the caller
+    assert_eq!(
+        evaluate_error(&calc_engine, "monthly(5.5), decMonthly"),
+        ErrorType::InvalidSymbol
+    );
+
+    // The number of arguments must match the parameters
+    assert_eq!(
+        evaluate_error(&calc_engine, "addRate(1)"),
+        ErrorType::Incomplete
+    );
+    assert_eq!(
+        evaluate_error(&calc_engine, "addRate(1, 2, 3)"),
+        ErrorType::RightParen
+    );
+}
+
+#[test]
+fn user_function_recursion() {
+    let calc_engine = common::load_engine("loan.json");
+    add_function(
+        &calc_engine,
+        "factorial",
+        &["intN"],
+        "if(intN <= 1, 1, intN * factorial(intN - 1))",
+    );
+    add_function(&calc_engine, "forever", &["intN"], "forever(intN + 1)");
+
+    assert_eq!(
+        evaluate(&calc_engine, "factorial(10)").sym_integer(),
+        3628800
+    );
+
+    assert_eq!(
+        evaluate_error(&calc_engine, "forever(1)"),
+        ErrorType::Recursion
+    );
+}
+
+#[test]
+fn deeply_nested_expression_is_limit_error() {
+    let calc_engine = common::load_engine("loan.json");
+    let levels = 100_000;
+
+    for expression in [
+        format!("{}1{}", "(1 + ".repeat(levels), ")".repeat(levels)),
+        format!("{}1{}", "abs(".repeat(levels), ")".repeat(levels)),
+        format!("1{}", " + 1".repeat(levels)),
+        format!("{}1", "-".repeat(levels)),
+    ] {
+        let error = calc_engine
+            .check_expression(None, None, expression.as_str(), false)
+            .unwrap_err();
+
+        assert_eq!(error.kind(), ErrorType::Limit);
+        assert!(evaluate(&calc_engine, expression.as_str())
+            .sym_string()
+            .starts_with(amfnengine::ERROR_PREFIX));
+    }
+
+    let expression = format!("{}1{}", "(1 + ".repeat(50), ")".repeat(50));
+    assert_eq!(
+        evaluate(&calc_engine, expression.as_str()).sym_integeri(),
+        51
+    );
+}
@@ -0,0 +1,142 @@
+// Copyright (c) 2021 ShiftLeft Software
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+mod common;
+
+use amfnengine::core::{ElemExprTrace, ListParameter};
+use amfnengine::engine::CalcEngine;
+use amfnengine::{ErrorType, NodeType, TokenType};
+
+/// Evaluate an expression with the integer parameter intN
+/// and return the evaluation trace.
+///
+/// # Arguments
+///
+/// * `calc_engine` - The engine.
+/// * `expression` - The expression to evaluate.
+/// * `value` - Value of intN.
+///
+/// # Return
+///
+/// * See description.
+fn evaluate_trace(calc_engine: &CalcEngine, expression: &str, value: usize) -> ElemExprTrace {
+    let mut list_parameter = ListParameter::new();
+    assert!(list_parameter.add_parameter("intN", "", "", false));
+    assert!(list_parameter.set_integer(value));
+
+    let (result, trace) =
+        calc_engine.evaluate_expression_trace(Option::from(&list_parameter), expression, false);
+    let trace = trace.unwrap();
+
+    if trace.error() == ErrorType::None {
+        assert_eq!(trace.result().sym_type(), result.sym_type());
+    } else {
+        assert!(result.sym_string().starts_with(amfnengine::ERROR_PREFIX));
+    }
+    trace
+}
+
+#[test]
+fn trace_shows_branch_taken() {
+    let calc_engine = common::load_engine("loan.json");
+    let expression = "if(intN > 5, \"big\", \"small\")";
+
+    for (value, branch) in [(7, "big"), (3, "small")] {
+        let trace = evaluate_trace(&calc_engine, expression, value);
+
+        assert_eq!(trace.node_type(), NodeType::Function);
+        assert_eq!(trace.text(), "if");
+        assert_eq!(trace.position(), 0);
+        assert_eq!(trace.result().sym_string(), branch);
+        assert_eq!(trace.error(), ErrorType::None);
+
+        // Condition and the branch taken only
+        let nodes = trace.nodes();
+        assert_eq!(nodes.len(), 2);
+        assert_eq!(nodes[0].node_type(), NodeType::Operator);
+        assert_eq!(nodes[0].text(), ">");
+        assert_eq!(nodes[0].result().sym_integer(), (value > 5) as usize);
+
+        let operands = nodes[0].nodes();
+        assert_eq!(operands.len(), 2);
+        assert_eq!(operands[0].node_type(), NodeType::Symbol);
+        assert_eq!(operands[0].text(), "intN");
+        assert_eq!(operands[0].position(), 3);
+        assert_eq!(operands[0].result().sym_integer(), value);
+        assert_eq!(operands[1].node_type(), NodeType::Constant);
+        assert_eq!(operands[1].result().sym_integer(), 5);
+
+        assert_eq!(nodes[1].node_type(), NodeType::Constant);
+        assert_eq!(nodes[1].result().sym_string(), branch);
+    }
+}
+
+#[test]
+fn trace_sequence_and_format() {
+    let calc_engine = common::load_engine("loan.json");
+    let trace = evaluate_trace(&calc_engine, "set(intX, intN + 0), intX * 3", 2);
+
+    assert_eq!(trace.node_type(), NodeType::Sequence);
+    assert_eq!(trace.result().sym_type(), TokenType::Integer);
+    assert_eq!(trace.result().sym_integer(), 6);
+    assert_eq!(trace.nodes().len(), 2);
+    assert_eq!(trace.nodes()[0].text(), "set");
+    assert_eq!(trace.nodes()[0].result().sym_integer(), 2);
+
+    assert_eq!(
+        trace.format(0),
+        concat!(
+            ", @0 => 6\n",
+            "  set @0 => 2\n",
+            "    + @15 => 2\n",
+            "      intN @10 => 2\n",
+            "      0 @17 => 0\n",
+            "  * @26 => 6\n",
+            "    intX @21 => 2\n",
+            "    3 @28 => 3\n",
+        )
+    );
+}
+
+#[test]
+fn trace_records_errors() {
+    let calc_engine = common::load_engine("loan.json");
+
+    let trace = evaluate_trace(&calc_engine, "1 + round(\"a\")", 0);
+    assert_eq!(trace.error(), ErrorType::Decimal);
+    assert_eq!(trace.nodes()[0].error(), ErrorType::None);
+    assert_eq!(trace.nodes()[0].result().sym_integer(), 1);
+    assert_eq!(trace.nodes()[1].text(), "round");
+    assert_eq!(trace.nodes()[1].position(), 4);
+    assert_eq!(trace.nodes()[1].error(), ErrorType::Decimal);
+
+    let trace = evaluate_trace(&calc_engine, "missing + 1", 0);
+    assert_eq!(trace.error(), ErrorType::InvalidSymbol);
+    assert_eq!(trace.nodes().len(), 1);
+    assert_eq!(trace.nodes()[0].text(), "missing");
+
+    // No trace when the expression does not compile
+    let (result, trace) = calc_engine.evaluate_expression_trace(None, "(1 + ", false);
+    assert!(result.sym_string().starts_with(amfnengine::ERROR_PREFIX));
+    assert!(trace.is_none());
+
+    // Tracing does not change the result
+    let expression = "if(intN > 5, round(2.345), 0)";
+    let mut list_parameter = ListParameter::new();
+    assert!(list_parameter.add_parameter("intN", "", "", false));
+    assert!(list_parameter.set_integer(7));
+    assert_eq!(
+        calc_engine
+            .evaluate_expression_trace(Option::from(&list_parameter), expression, false)
+            .0
+            .sym_decimal(),
+        calc_engine
+            .evaluate_expression(Option::from(&list_parameter), expression, false)
+            .sym_decimal()
+    );
+}
@@ -0,0 +1,165 @@
+// Copyright (c) 2021 ShiftLeft Software
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+mod common;
+
+use common::{date, decimal};
+
+use rust_decimal::prelude::*;
+
+use amfnengine::core::{CoreFinancial, ElemDatedAmount};
+use amfnengine::ErrorType;
+
+#[test]
+fn spreadsheet_reference_values() {
+    // The examples of the Microsoft Excel function documentation
+    let calc_engine = common::load_engine("loan.json");
+
+    for (expression, expected, digits) in [
+        ("pmt(0.08 / 12, 10, 10000)", "-1037.03", 2),
+        ("pmt(0.06 / 12, 18 * 12, 0, 50000)", "-129.08", 2),
+        ("ipmt(0.1 / 12, 1, 36, 8000)", "-66.67", 2),
+        ("ipmt(0.1, 3, 3, 8000)", "-292.45", 2),
+        ("ppmt(0.1 / 12, 1, 24, 2000)", "-75.62", 2),
+        ("ppmt(0.08, 10, 10, 200000)", "-27598.05", 2),
+        ("pv(0.08 / 12, 12 * 20, 500, 0, 0)", "-59777.15", 2),
+        ("fv(0.06 / 12, 10, -200, -500, 1)", "2581.40", 2),
+        ("fv(0.12 / 12, 12, -1000)", "12682.50", 2),
+        ("fv(0.11 / 12, 35, -2000, 0, 1)", "82846.25", 2),
+        ("nper(0.12 / 12, -100, -1000, 10000, 1)", "59.6738657", 7),
+        ("nper(0.12 / 12, -100, -1000, 10000)", "60.0821229", 7),
+        ("nper(0.12 / 12, -100, -1000)", "-9.5785940", 7),
+        ("rate(4 * 12, -200, 8000)", "0.0077", 4),
+        ("npv(0.1, -10000, 3000, 4200, 6800)", "1188.44", 2),
+        (
+            "npv(0.08, 8000, 9200, 10000, 12000, 14500) - 40000",
+            "1922.06",
+            2,
+        ),
+        (
+            "xnpv(0.09, list(-10000, 2750, 4250, 3250, 2750), \
+             list(20080101, 20080301, 20081030, 20090215, 20090401))",
+            "2086.65",
+            2,
+        ),
+        ("effect(0.0525, 4)", "0.0535427", 7),
+        ("nominal(0.053543, 4)", "0.0525003", 7),
+    ] {
+        let result = calc_engine.evaluate_expression(None, expression, false);
+
+        assert_eq!(
+            result.sym_decimal().round_dp(digits),
+            decimal(expected),
+            "{}",
+            expression
+        );
+    }
+}
+
+#[test]
+fn payments_due_at_the_beginning_of_the_period() {
+    // PMT(8%/12, 10, 10000, 0, 1) = -1030.16
+    assert_eq!(
+        CoreFinancial::pmt(
+            decimal("0.08") / decimal("12"),
+            decimal("10"),
+            decimal("10000"),
+            Decimal::zero(),
+            true
+        )
+        .unwrap()
+        .round_dp(2),
+        decimal("-1030.16")
+    );
+    // The first payment in advance has no interest
+    assert_eq!(
+        CoreFinancial::ipmt(
+            decimal("0.01"),
+            Decimal::one(),
+            decimal("12"),
+            decimal("1000"),
+            Decimal::zero(),
+            true
+        )
+        .unwrap(),
+        Decimal::zero()
+    );
+}
+
+#[test]
+fn zero_rate_and_invalid_arguments() {
+    assert_eq!(
+        CoreFinancial::pmt(
+            Decimal::zero(),
+            decimal("10"),
+            decimal("1000"),
+            Decimal::zero(),
+            false
+        )
+        .unwrap(),
+        decimal("-100")
+    );
+    assert!(CoreFinancial::pmt(
+        decimal("0.01"),
+        Decimal::zero(),
+        decimal("1000"),
+        Decimal::zero(),
+        false
+    )
+    .is_err());
+    assert!(CoreFinancial::ipmt(
+        decimal("0.01"),
+        decimal("13"),
+        decimal("12"),
+        decimal("1000"),
+        Decimal::zero(),
+        false
+    )
+    .is_err());
+    assert!(CoreFinancial::npv(decimal("-1"), &[decimal("100")]).is_err());
+}
+
+#[test]
+fn xnpv_values_and_dates() {
+    let calc_engine = common::load_engine("loan.json");
+
+    // XNPV(10%, {-1000, 300, 400, 500}, {1/1/2020, 7/1/2020, 1/1/2021, 12/31/2021})
+    let result = calc_engine.evaluate_expression(
+        None,
+        "xnpv(0.1, list(-1000, 300, 400.00, 500), list(20200101, 20200701, 20210101, 20211231))",
+        false,
+    );
+    assert_eq!(result.sym_decimal().round_dp(2), decimal("62.84"));
+
+    // The values and dates must have the same length
+    for expression in [
+        "xnpv(0.1, list(-1000, 300, 400), list(20200101, 20200701))",
+        "xnpv(0.1, -1000, 20200101)",
+    ] {
+        let (result, trace) = calc_engine.evaluate_expression_trace(None, expression, false);
+
+        assert!(
+            result.sym_string().starts_with(amfnengine::ERROR_PREFIX),
+            "{}",
+            expression
+        );
+        assert_eq!(trace.unwrap().error(), ErrorType::List, "{}", expression);
+    }
+
+    // No amount can be dated before the first amount
+    let amounts = [
+        ElemDatedAmount::new(date(2020, 7, 1), decimal("-1000")),
+        ElemDatedAmount::new(date(2020, 1, 1), decimal("1100")),
+    ];
+    assert_eq!(
+        CoreFinancial::xnpv(decimal("0.1"), &amounts)
+            .unwrap_err()
+            .kind(),
+        ErrorType::Date
+    );
+}
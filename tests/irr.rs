@@ -0,0 +1,130 @@
+// Copyright (c) 2021 ShiftLeft Software
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+mod common;
+
+use common::decimal;
+
+use chrono::NaiveDate;
+use rust_decimal::prelude::*;
+
+use amfnengine::core::{CoreIrr, ElemDatedAmount};
+use amfnengine::FrequencyType;
+
+fn tolerance() -> Decimal {
+    decimal("0.0000000001")
+}
+
+/// Dated amounts from a list of (year, month, day, amount).
+///
+/// # Arguments
+///
+/// * `values` - Dates and amounts.
+///
+/// # Return
+///
+/// * See description.
+fn amounts(values: &[(i32, u32, u32, &str)]) -> Vec<ElemDatedAmount> {
+    values
+        .iter()
+        .map(|(year, month, day, amount)| {
+            ElemDatedAmount::new(
+                NaiveDate::from_ymd_opt(*year, *month, *day).unwrap(),
+                decimal(amount),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn irr_matches_spreadsheet_reference() {
+    // Microsoft Excel IRR documentation: five years of income
+    // on a 70,000 investment returns 8.66%
+    let values = amounts(&[
+        (2020, 1, 1, "-70000"),
+        (2021, 1, 1, "12000"),
+        (2022, 1, 1, "15000"),
+        (2023, 1, 1, "18000"),
+        (2024, 1, 1, "21000"),
+        (2025, 1, 1, "26000"),
+    ]);
+
+    let result = CoreIrr::irr(&values, FrequencyType::OneYear, 365, tolerance()).unwrap();
+
+    assert_eq!(result.periodic_rate().round_dp(9), decimal("0.086630948"));
+    assert_eq!(result.sign_changes(), 1);
+
+    // Four years of income returns -2.12%
+    let result = CoreIrr::irr(&values[..5], FrequencyType::OneYear, 365, tolerance()).unwrap();
+
+    assert_eq!(result.periodic_rate().round_dp(9), decimal("-0.021244848"));
+}
+
+#[test]
+fn irr_monthly_loan() {
+    // 100,000 over 360 months at 6% (0.5% per month) pays 599.55
+    let mut values = amounts(&[(2024, 1, 1, "-100000")]);
+    let mut date = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+    for _ in 0..360 {
+        date = date.checked_add_months(chrono::Months::new(1)).unwrap();
+        values.push(ElemDatedAmount::new(date, decimal("599.55")));
+    }
+
+    let result = CoreIrr::irr(&values, FrequencyType::OneMonth, 360, tolerance()).unwrap();
+
+    assert_eq!(result.periodic_rate().round_dp(6), decimal("0.005000"));
+}
+
+#[test]
+fn irr_reports_every_root() {
+    // -100, +230, -132 has roots of 10% and 20%
+    let values = amounts(&[
+        (2020, 1, 1, "-100"),
+        (2021, 1, 1, "230"),
+        (2022, 1, 1, "-132"),
+    ]);
+
+    let result = CoreIrr::irr(&values, FrequencyType::OneYear, 365, tolerance()).unwrap();
+
+    assert_eq!(result.sign_changes(), 2);
+    assert_eq!(
+        result
+            .roots()
+            .iter()
+            .map(|o| o.round_dp(8))
+            .collect::<Vec<Decimal>>(),
+        vec![decimal("0.1"), decimal("0.2")]
+    );
+    assert_eq!(result.periodic_rate().round_dp(8), decimal("0.1"));
+}
+
+#[test]
+fn xirr_matches_spreadsheet_reference() {
+    // Microsoft Excel XIRR documentation: 37.34%
+    let values = amounts(&[
+        (2008, 1, 1, "-10000"),
+        (2008, 3, 1, "2750"),
+        (2008, 10, 30, "4250"),
+        (2009, 2, 15, "3250"),
+        (2009, 4, 1, "2750"),
+    ]);
+
+    let result = CoreIrr::xirr(&values, 365, tolerance()).unwrap();
+
+    assert_eq!(result.effective_rate().round_dp(8), decimal("0.37336253"));
+    assert_eq!(result.frequency(), FrequencyType::OneYear);
+}
+
+#[test]
+fn irr_without_sign_change_fails() {
+    let values = amounts(&[(2020, 1, 1, "100"), (2021, 1, 1, "100")]);
+
+    assert!(CoreIrr::irr(&values, FrequencyType::OneYear, 365, tolerance()).is_err());
+    assert!(CoreIrr::xirr(&values, 365, tolerance()).is_err());
+    assert!(CoreIrr::xirr(&[], 365, tolerance()).is_err());
+}
@@ -0,0 +1,97 @@
+// Copyright (c) 2021 ShiftLeft Software
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+mod common;
+
+use amfnengine::engine::{CalcEngine, CalcJsonDeserialize, CalcJsonValidate};
+
+const INTEREST_CHANGE: &str = "/cashflows/0/event-list/1/extension/interest-change";
+
+/// Deserialize a document into a new engine.
+///
+/// # Arguments
+///
+/// * `input` - Serialized Json.
+///
+/// # Return
+///
+/// * See description.
+fn deserialize(input: &str) -> Result<(), amfnengine::core::ElemError> {
+    let calc_engine = CalcEngine::new();
+
+    CalcJsonDeserialize::new(calc_engine.calc_manager()).deserialize(String::from(input))
+}
+
+#[test]
+fn valid_document() {
+    let input = common::read_data("loan.json");
+
+    assert!(CalcJsonValidate::shared()
+        .validate(input.as_str())
+        .is_empty());
+    assert!(deserialize(input.as_str()).is_ok());
+}
+
+#[test]
+fn validator_is_shared() {
+    assert!(std::ptr::eq(
+        CalcJsonValidate::shared(),
+        CalcJsonValidate::shared()
+    ));
+}
+
+#[test]
+fn optional_properties_and_enum_case_accepted() {
+    let input = common::read_data("loan.json")
+        .replace(",\n\t\t\t\"resources\": []", "")
+        .replace("\"periodic\"", "\"Periodic\"")
+        .replace("\t\t\t\t\t\t\t\"days-in-year\": 360,\n", "");
+
+    assert!(!input.contains("resources"));
+    assert!(!input.contains("days-in-year"));
+    assert!(CalcJsonValidate::shared()
+        .validate(input.as_str())
+        .is_empty());
+    assert!(deserialize(input.as_str()).is_ok());
+}
+
+#[test]
+fn invalid_enum_reports_pointer_and_message() {
+    let input = common::read_data("loan.json").replace("\"periodic\"", "\"actual-999\"");
+
+    let error = deserialize(input.as_str()).unwrap_err();
+
+    assert_eq!(error.kind(), amfnengine::ErrorType::Json);
+    assert_eq!(
+        error.pointer(),
+        format!("{}/day-count-basis", INTEREST_CHANGE)
+    );
+    assert!(error.message().starts_with("\"actual-999\" is not one of"));
+    assert!(error.to_string().contains(error.pointer()));
+    assert!(error.to_string().contains(error.message()));
+}
+
+#[test]
+fn every_violation_reported() {
+    let calc_engine = CalcEngine::new();
+    let input = common::read_data("loan.json")
+        .replace("\"interest-method\"", "\"interest-methods\"")
+        .replace("\"intervals\": 1,", "\"intervals\": \"1\",");
+
+    let violations = CalcJsonDeserialize::new(calc_engine.calc_manager())
+        .validate_only(input.as_str())
+        .unwrap_err();
+
+    assert!(violations.iter().any(|o| o.pointer()
+        == format!("{}/interest-methods", INTEREST_CHANGE)
+        && o.message() == "unexpected property \"interest-methods\""));
+    assert!(violations
+        .iter()
+        .any(|o| o.pointer() == "/cashflows/0/event-list/0/intervals"
+            && o.message() == "expected number but found string"));
+}
@@ -0,0 +1,223 @@
+// Copyright (c) 2021 ShiftLeft Software
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+mod common;
+
+use common::{date, decimal};
+
+use rust_decimal::prelude::*;
+
+use amfnengine::core::ListSummary;
+use amfnengine::engine::CalcEngine;
+use amfnengine::{ListTrait, TokenType};
+
+/// Load and balance the loan with the payment event
+/// given the event type "Payment".
+///
+/// # Return
+///
+/// * See description.
+fn loan() -> CalcEngine {
+    let calc_engine = common::load_engine("loan.json");
+
+    {
+        let mut calc_mgr = calc_engine.calc_mgr_mut();
+        let list_event = calc_mgr.list_cashflow_mut().list_event_mut().unwrap();
+        assert!(list_event.get_element(2));
+        assert!(list_event.set_event_type("Payment"));
+    }
+
+    calc_engine.balance_cashflow().unwrap();
+    calc_engine
+}
+
+/// Evaluate summary expressions against the balanced loan.
+///
+/// # Arguments
+///
+/// * `calc_engine` - The engine.
+/// * `summaries` - Names and result expressions of the summary items.
+///
+/// # Return
+///
+/// * See description.
+fn summary(calc_engine: &CalcEngine, summaries: &[(&str, &str)]) -> ListSummary {
+    {
+        let mut calc_mgr = calc_engine.calc_mgr_mut();
+        let list_descriptor = calc_mgr.preferences_mut().list_descriptor_mut();
+        let names: Vec<&str> = summaries.iter().map(|o| o.0).collect();
+
+        assert!(list_descriptor.add_descriptor(
+            amfnengine::GROUP_GENERAL,
+            amfnengine::NAME_SUMMARY,
+            amfnengine::TYPE_CUSTOM,
+            "",
+            names.join("|"),
+            "",
+            false,
+            false
+        ));
+        for (name, expression) in summaries.iter() {
+            assert!(list_descriptor.add_descriptor(
+                amfnengine::GROUP_SUMMARY,
+                name,
+                amfnengine::TYPE_CUSTOM,
+                "",
+                format!("{}~{}", name, expression),
+                "",
+                false,
+                false
+            ));
+        }
+    }
+
+    calc_engine.parse_summary()
+}
+
+/// Get the result of a summary item.
+///
+/// # Arguments
+///
+/// * `list_summary` - The summary list.
+/// * `name` - Name of the summary item.
+///
+/// # Return
+///
+/// * See description.
+fn result(list_summary: &ListSummary, name: &str) -> String {
+    let mut index = 0;
+
+    while list_summary.get_element(index) {
+        if list_summary.name() == name {
+            return String::from(list_summary.result());
+        }
+        index += 1;
+    }
+
+    panic!("Summary {} not found", name);
+}
+
+#[test]
+fn aggregate_amortization_column() {
+    let calc_engine = loan();
+    let list_summary = summary(
+        &calc_engine,
+        &[
+            ("Sum", "sum(amlist(\"Interest\", 20250101, 20251231))"),
+            ("Count", "count(amlist(\"Interest\", 20250101, 20251231))"),
+            ("Avg", "avg(amlist(\"Interest\", 20250101, 20251231))"),
+            ("First", "first(amlist(\"Balance\", 20250101, 20251231))"),
+            ("Last", "last(amlist(\"Balance\", 20250101, 20251231))"),
+        ],
+    );
+
+    // Totals of the payments in 2025 taken from the amortization list
+    let mut interest: Vec<Decimal> = Vec::new();
+    let mut balance: Vec<Decimal> = Vec::new();
+    {
+        let calc_mgr = calc_engine.calc_mgr();
+        let list_am = calc_mgr.list_cashflow().list_amortization().unwrap();
+        let mut index = 0;
+        while list_am.get_element(index) {
+            if list_am.event_date() >= date(2025, 1, 1)
+                && list_am.event_date() <= date(2025, 12, 31)
+            {
+                interest.push(list_am.interest());
+                balance.push(list_am.balance());
+            }
+            index += 1;
+        }
+    }
+    let sum: Decimal = interest.iter().sum();
+
+    assert_eq!(interest.len(), 12);
+    assert_eq!(
+        decimal(result(&list_summary, "Sum").as_str()),
+        sum.round_dp(6)
+    );
+    assert_eq!(result(&list_summary, "Count"), "12");
+    assert_eq!(
+        decimal(result(&list_summary, "Avg").as_str()),
+        (sum / Decimal::from(12)).round_dp(6)
+    );
+    assert_eq!(
+        decimal(result(&list_summary, "First").as_str()),
+        balance[0].round_dp(6)
+    );
+    assert_eq!(
+        decimal(result(&list_summary, "Last").as_str()),
+        balance[11].round_dp(6)
+    );
+}
+
+#[test]
+fn filter_by_event_type_and_condition() {
+    let calc_engine = loan();
+    let list_summary = summary(
+        &calc_engine,
+        &[
+            ("All", "count(amlist(\"Value\"))"),
+            ("Payment", "count(amlist(\"Value\", 0, 0, \"Payment\"))"),
+            ("Other", "count(amlist(\"Value\", 0, 0, \"Fee\"))"),
+            (
+                "Filter",
+                "count(filter(amlist(\"Interest\", 20240101, 20241231), decX, decX >= 996))",
+            ),
+            ("List", "sum(list(1, 2.5, 3))"),
+        ],
+    );
+
+    assert_eq!(result(&list_summary, "All"), "362");
+    assert_eq!(result(&list_summary, "Payment"), "360");
+    assert_eq!(result(&list_summary, "Other"), "0");
+    let mut count = 0;
+    {
+        let calc_mgr = calc_engine.calc_mgr();
+        let list_am = calc_mgr.list_cashflow().list_amortization().unwrap();
+        let mut index = 0;
+        while list_am.get_element(index) {
+            if list_am.event_date() <= date(2024, 12, 31) && list_am.interest() >= decimal("996") {
+                count += 1;
+            }
+            index += 1;
+        }
+    }
+    assert!(count > 0 && count < 11);
+    assert_eq!(result(&list_summary, "Filter"), count.to_string());
+    assert_eq!(result(&list_summary, "List"), "6.50");
+}
+
+#[test]
+fn aggregate_errors() {
+    let calc_engine = loan();
+    let list_summary = summary(
+        &calc_engine,
+        &[
+            ("Column", "sum(amlist(\"Bogus\"))"),
+            ("Empty", "avg(filter(amlist(\"Interest\"), decX, decX < 0))"),
+            ("NotList", "sum(5)"),
+        ],
+    );
+
+    for name in ["Column", "Empty", "NotList"] {
+        assert!(
+            result(&list_summary, name).starts_with(amfnengine::ERROR_PREFIX),
+            "{}",
+            name
+        );
+    }
+
+    // Aggregates of an empty list
+    let result = calc_engine.evaluate_expression(None, "count(list())", false);
+    assert_eq!(result.sym_type(), TokenType::Integer);
+    assert_eq!(result.sym_integer(), 0);
+    assert!(calc_engine
+        .evaluate_expression(None, "first(list())", false)
+        .sym_string()
+        .starts_with(amfnengine::ERROR_PREFIX));
+}
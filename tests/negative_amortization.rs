@@ -0,0 +1,106 @@
+// Copyright (c) 2021 ShiftLeft Software
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+mod common;
+
+use common::{date, decimal};
+
+use chrono::NaiveDate;
+use rust_decimal::prelude::*;
+
+use amfnengine::engine::CalcEngine;
+use amfnengine::{ExtensionType, ListTrait};
+
+/// Balance a cashflow of the option ARM document and return the date,
+/// value, deferred interest and balance of its payments in date order.
+///
+/// # Arguments
+///
+/// * `name` - Name of the cashflow.
+///
+/// # Return
+///
+/// * See description.
+fn payments(name: &str) -> Vec<(NaiveDate, Decimal, Decimal, Decimal)> {
+    let calc_engine: CalcEngine = common::load_engine("option_arm.json");
+    assert!(calc_engine.calc_mgr().select_cashflow_by_name(name));
+    calc_engine.balance_cashflow().unwrap();
+
+    let calc_mgr = calc_engine.calc_mgr();
+    let list_am = calc_mgr.list_cashflow().list_amortization().unwrap();
+
+    let mut payments = Vec::new();
+    let mut index: usize = 0;
+    while list_am.get_element(index) {
+        if list_am.elem_type() == ExtensionType::PrincipalChange && index > 0 {
+            payments.push((
+                list_am.event_date(),
+                list_am.value(),
+                calc_engine.round_currency(list_am.deferred_interest()),
+                calc_engine.round_currency(list_am.balance()),
+            ));
+        }
+        index += 1;
+    }
+
+    payments
+}
+
+#[test]
+fn payment_cap_defers_interest() {
+    // 100,000 at 6% with a 599.55 payment, the rate resets to 10% on
+    // 2025-01-01 and the payment can change by at most 7.5%
+    let payments = payments("Capped");
+    assert_eq!(payments.len(), 360);
+
+    assert_eq!(payments[10].0, date(2024, 12, 1));
+    assert_eq!(payments[10].1, decimal("599.55"));
+
+    // The fully amortizing payment of 876.50 is capped at 599.55 * 1.075
+    assert_eq!(payments[11].0, date(2025, 1, 1));
+    assert_eq!(payments[11].1, decimal("644.52"));
+    assert_eq!(payments[11].2, Decimal::zero());
+    assert_eq!(payments[11].3, decimal("98727.02"));
+
+    // Interest of 98,727.02 * 10% / 12 = 822.73 exceeds the payment
+    assert_eq!(payments[12].1, decimal("644.52"));
+    assert_eq!(payments[12].2, decimal("178.21"));
+    assert_eq!(payments[12].3, decimal("98905.23"));
+
+    assert_eq!(payments[13].2, decimal("179.69"));
+    assert_eq!(payments[13].3, decimal("99084.92"));
+
+    // Without a negative amortization cap the balance keeps growing
+    assert_eq!(payments[18].3, decimal("100006.08"));
+    assert!(payments[11..].iter().all(|o| o.1 == decimal("644.52")));
+    assert!(payments[359].3 > payments[18].3);
+}
+
+#[test]
+fn neg_am_cap_forces_recast() {
+    // As above with the balance limited to 100% of the original principal
+    let payments = payments("Recast");
+
+    assert_eq!(payments[17].0, date(2025, 7, 1));
+    assert_eq!(payments[17].1, decimal("644.52"));
+    assert_eq!(payments[17].2, decimal("185.76"));
+    assert_eq!(payments[17].3, decimal("99818.78"));
+
+    // The capped payment would leave a balance of 100,006.08, so the
+    // payment is recast to amortize 99,818.78 at 10% over 342 periods
+    assert_eq!(payments[18].0, date(2025, 8, 1));
+    assert_eq!(payments[18].1, decimal("883.54"));
+    assert_eq!(payments[18].2, Decimal::zero());
+    assert_eq!(payments[18].3, decimal("99767.06"));
+
+    assert!(payments[18..].iter().all(|o| o.1 == decimal("883.54")));
+    assert!(payments[18..].iter().all(|o| o.2 == Decimal::zero()));
+
+    // The rounded recast payment overpays by the accumulated residual
+    assert_eq!(payments[359].3, decimal("-3.94"));
+}
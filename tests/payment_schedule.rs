@@ -0,0 +1,256 @@
+// Copyright (c) 2021 ShiftLeft Software
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+mod common;
+
+use common::decimal;
+
+use chrono::NaiveDate;
+use rust_decimal::prelude::*;
+
+use amfnengine::core::{ElemBalanceResult, ElemPaymentStep};
+use amfnengine::engine::CalcEngine;
+use amfnengine::ListTrait;
+
+/// Returns the payments of the selected cashflow in date order.
+///
+/// # Arguments
+///
+/// * `calc_engine` - The engine.
+///
+/// # Return
+///
+/// * See description.
+fn payments(calc_engine: &CalcEngine) -> Vec<Decimal> {
+    let calc_mgr = calc_engine.calc_mgr();
+    let list_am = calc_mgr.list_cashflow().list_amortization().unwrap();
+
+    let mut payments: Vec<Decimal> = Vec::new();
+    let mut index: usize = 0;
+    while list_am.get_element(index) {
+        if list_am.elem_type() == amfnengine::ExtensionType::PrincipalChange && index > 0 {
+            payments.push(list_am.value());
+        }
+        index += 1;
+    }
+
+    payments
+}
+
+fn assert_paid_off(calc_engine: &CalcEngine, result: &ElemBalanceResult) {
+    assert_eq!(
+        calc_engine.round_currency(result.balance() + result.acc_balance()),
+        Decimal::zero()
+    );
+    for payment in payments(calc_engine) {
+        assert_eq!(payment, calc_engine.round_currency(payment));
+    }
+}
+
+#[test]
+fn graduated_payments_pay_off() {
+    let calc_engine = common::load_engine("loan.json");
+
+    // 200,000 at 6% over 360 months, 7.5% increases every 12 months for 5 years
+    let result = calc_engine
+        .create_graduated_payments(
+            "Graduated",
+            decimal("200000"),
+            decimal("6"),
+            NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+            amfnengine::FrequencyType::OneMonth,
+            360,
+            12,
+            5,
+            decimal("7.5"),
+        )
+        .unwrap();
+
+    assert_eq!(result.result_decimal(), decimal("889.82"));
+    assert_paid_off(&calc_engine, &result);
+
+    let payments = payments(&calc_engine);
+    assert_eq!(payments.len(), 360);
+    assert_eq!(payments[0], decimal("889.82"));
+    assert_eq!(payments[12], decimal("956.56"));
+    assert_eq!(payments[60], decimal("1277.45"));
+    assert_eq!(payments[358], decimal("1277.45"));
+    // The final payment settles the rounding residual
+    assert_eq!(payments[359], decimal("1285.21"));
+}
+
+#[test]
+fn level_step_matches_payment() {
+    let calc_engine = common::load_engine("loan.json");
+
+    let result = calc_engine
+        .create_stepped_payments(
+            "Level",
+            decimal("200000"),
+            decimal("6"),
+            NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+            amfnengine::FrequencyType::OneMonth,
+            &[ElemPaymentStep::new(360, Decimal::zero())],
+        )
+        .unwrap();
+
+    // Standard 30 year payment of 1,199.10
+    assert_eq!(result.result_decimal(), decimal("1199.10"));
+    assert_paid_off(&calc_engine, &result);
+
+    let payments = payments(&calc_engine);
+    assert_eq!(payments.len(), 360);
+    assert_eq!(payments[358], decimal("1199.10"));
+}
+
+#[test]
+fn single_payment() {
+    let calc_engine = common::load_engine("loan.json");
+
+    let result = calc_engine
+        .create_stepped_payments(
+            "Single",
+            decimal("1000"),
+            decimal("12"),
+            NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+            amfnengine::FrequencyType::OneMonth,
+            &[ElemPaymentStep::new(1, Decimal::zero())],
+        )
+        .unwrap();
+
+    assert_eq!(result.result_decimal(), decimal("1010.00"));
+    assert_paid_off(&calc_engine, &result);
+}
+
+#[test]
+fn interest_only_then_balloon() {
+    let calc_engine = common::load_engine("loan.json");
+
+    // 100,000 at 6%, 12 interest-only months then a 30 year
+    // amortization with a balloon at 10 years
+    let result = calc_engine
+        .create_loan_structure(
+            "Balloon",
+            decimal("100000"),
+            decimal("6"),
+            NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+            amfnengine::FrequencyType::OneMonth,
+            12,
+            360,
+            120,
+        )
+        .unwrap();
+
+    assert_eq!(result.result_decimal(), decimal("86389.79"));
+    assert_paid_off(&calc_engine, &result);
+
+    let payments = payments(&calc_engine);
+    assert_eq!(payments.len(), 120);
+    assert_eq!(payments[0], decimal("500.00"));
+    assert_eq!(payments[11], decimal("500.00"));
+    assert_eq!(payments[12], decimal("599.55"));
+    assert_eq!(payments[118], decimal("599.55"));
+    assert_eq!(payments[119], decimal("86389.79"));
+}
+
+#[test]
+fn bullet() {
+    let calc_engine = common::load_engine("loan.json");
+
+    // No interim payments, so interest compounds until maturity
+    let result = calc_engine
+        .create_loan_structure(
+            "Bullet",
+            decimal("10000"),
+            decimal("12"),
+            NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+            amfnengine::FrequencyType::OneMonth,
+            0,
+            0,
+            12,
+        )
+        .unwrap();
+
+    // 10,000 * 1.01^12
+    assert_eq!(result.result_decimal(), decimal("11268.25"));
+    assert_paid_off(&calc_engine, &result);
+    assert_eq!(payments(&calc_engine), vec![decimal("11268.25")]);
+}
+
+#[test]
+fn fully_amortizing() {
+    let calc_engine = common::load_engine("loan.json");
+
+    let result = calc_engine
+        .create_loan_structure(
+            "Amortizing",
+            decimal("200000"),
+            decimal("6"),
+            NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+            amfnengine::FrequencyType::OneMonth,
+            0,
+            360,
+            360,
+        )
+        .unwrap();
+
+    // The balloon is the final level payment plus the rounding residual
+    assert_eq!(result.result_decimal(), decimal("1200.16"));
+    assert_paid_off(&calc_engine, &result);
+
+    let payments = payments(&calc_engine);
+    assert_eq!(payments.len(), 360);
+    assert_eq!(payments[0], decimal("1199.10"));
+    assert_eq!(payments[358], decimal("1199.10"));
+}
+
+#[test]
+fn loan_structure_periods_out_of_range() {
+    let calc_engine = common::load_engine("loan.json");
+
+    // (interest-only, amortization, maturity)
+    for (io_periods, amortization_periods, maturity_periods) in [
+        (12, 0, 12),
+        (13, 0, 12),
+        (11, 360, 12),
+        (0, 0, 0),
+        (12, 100, 120),
+    ] {
+        let result = calc_engine.create_loan_structure(
+            "Invalid",
+            decimal("100000"),
+            decimal("6"),
+            NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+            amfnengine::FrequencyType::OneMonth,
+            io_periods,
+            amortization_periods,
+            maturity_periods,
+        );
+
+        match result {
+            Err(e) => assert_eq!(e.kind(), amfnengine::ErrorType::CalcPeriods),
+            Ok(_o) => panic!("periods accepted"),
+        }
+    }
+
+    // The last valid interest-only period is followed by the balloon
+    let result = calc_engine
+        .create_loan_structure(
+            "InterestOnly",
+            decimal("100000"),
+            decimal("6"),
+            NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+            amfnengine::FrequencyType::OneMonth,
+            11,
+            0,
+            12,
+        )
+        .unwrap();
+
+    assert_eq!(result.result_decimal(), decimal("100500.00"));
+}
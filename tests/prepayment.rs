@@ -0,0 +1,140 @@
+// Copyright (c) 2021 ShiftLeft Software
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+mod common;
+
+use common::decimal;
+
+use rust_decimal::prelude::*;
+
+use amfnengine::core::ElemPrepayment;
+use amfnengine::{ListTrait, PrepaymentType};
+
+#[test]
+fn psa_ramp() {
+    // 100% PSA rises 0.2% CPR a month to 6% at month 30
+    let prepayment = ElemPrepayment::new(PrepaymentType::Psa, decimal("100"), &[]);
+
+    for (age, expected) in [
+        (1, "0.2"),
+        (15, "3.0"),
+        (30, "6.0"),
+        (31, "6.0"),
+        (360, "6.0"),
+    ] {
+        assert_eq!(prepayment.cpr(age), decimal(expected), "{}", age);
+    }
+
+    // 150% PSA is one and a half times the ramp
+    let prepayment = ElemPrepayment::new(PrepaymentType::Psa, decimal("150"), &[]);
+
+    assert_eq!(prepayment.cpr(10), decimal("3.0"));
+    assert_eq!(prepayment.cpr(30), decimal("9.0"));
+}
+
+#[test]
+fn cpr_to_smm() {
+    // SMM = 1 - (1 - CPR) ^ (1 / 12): 6% CPR is 0.5143% SMM
+    // (calculated in Decimal to 16 places)
+    let prepayment = ElemPrepayment::new(PrepaymentType::Cpr, decimal("6"), &[]);
+
+    assert_eq!(
+        prepayment.smm(1, 12).round_dp(16),
+        decimal("0.0051430128318229")
+    );
+    assert_eq!(prepayment.smm(1, 1), decimal("0.06"));
+    assert_eq!(prepayment.smm(1, 0), Decimal::zero());
+
+    let prepayment = ElemPrepayment::new(PrepaymentType::Psa, decimal("100"), &[]);
+
+    assert_eq!(
+        prepayment.smm(1, 12).round_dp(16),
+        decimal("0.0001668196399456")
+    );
+    assert_eq!(
+        prepayment.smm(15, 12).round_dp(16),
+        decimal("0.0025350486138367")
+    );
+    assert_eq!(prepayment.smm(30, 12), prepayment.smm(1000, 12));
+
+    // A CPR of 100% or more prepays the whole balance
+    let prepayment = ElemPrepayment::new(PrepaymentType::Cpr, decimal("120"), &[]);
+
+    assert_eq!(prepayment.smm(1, 12), Decimal::one());
+}
+
+#[test]
+fn smm_and_vector_models() {
+    let prepayment = ElemPrepayment::new(PrepaymentType::Smm, decimal("0.5"), &[]);
+
+    assert_eq!(prepayment.smm(1, 12), decimal("0.005"));
+    assert_eq!(prepayment.smm(100, 4), decimal("0.005"));
+
+    // The last rate of the vector applies to the later periods
+    let prepayment = ElemPrepayment::new(
+        PrepaymentType::Vector,
+        decimal("0"),
+        &[decimal("2"), decimal("4"), decimal("6")],
+    );
+
+    assert_eq!(prepayment.cpr(1), decimal("2"));
+    assert_eq!(prepayment.cpr(3), decimal("6"));
+    assert_eq!(prepayment.cpr(12), decimal("6"));
+    assert_eq!(prepayment.smm(12, 12).round_dp(8), decimal("0.00514301"));
+
+    let prepayment = ElemPrepayment::new(PrepaymentType::None, decimal("6"), &[]);
+
+    assert_eq!(prepayment.smm(1, 12), Decimal::zero());
+}
+
+#[test]
+fn psa_cashflow() {
+    // 200,000 at 6% over 360 months paying 1,199.10: the first
+    // period pays 1,000 interest and 199.10 scheduled principal
+    // and prepays 199,800.90 x 0.016682% (0.2% CPR)
+    let calc_engine = common::load_engine("loan.json");
+
+    calc_engine.set_prepayment(Some(ElemPrepayment::new(
+        PrepaymentType::Psa,
+        decimal("100"),
+        &[],
+    )));
+    let result = calc_engine.balance_cashflow().unwrap();
+
+    let calc_mgr = calc_engine.calc_mgr();
+    let list_am = calc_mgr.list_cashflow().list_amortization().unwrap();
+    let mut prepaid: Vec<Decimal> = Vec::new();
+    let mut index: usize = 0;
+    while list_am.get_element(index) {
+        if list_am.elem_type() == amfnengine::ExtensionType::PrincipalChange && index > 0 {
+            if prepaid.is_empty() {
+                assert_eq!(list_am.interest(), decimal("1000"));
+                assert_eq!(list_am.scheduled_principal(), decimal("199.10"));
+                assert_eq!(list_am.surviving_balance(), decimal("199767.57"));
+            }
+            prepaid.push(list_am.prepaid_principal());
+        }
+        index += 1;
+    }
+
+    assert_eq!(
+        prepaid[..4],
+        [
+            decimal("33.33"),
+            decimal("66.64"),
+            decimal("99.93"),
+            decimal("133.15")
+        ]
+    );
+    assert_eq!(result.prepaid_principal_total(), prepaid.iter().sum());
+    assert_eq!(
+        (result.scheduled_principal_total() + result.prepaid_principal_total() + result.balance())
+            .round_dp(2),
+        decimal("200000")
+    );
+}
@@ -0,0 +1,123 @@
+// Copyright (c) 2021 ShiftLeft Software
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+mod common;
+
+use common::{date, decimal};
+
+use chrono::NaiveDate;
+use rust_decimal::prelude::*;
+
+use amfnengine::engine::CalcEngine;
+use amfnengine::{ErrorType, ExtensionType, ListTrait};
+
+/// Returns the date, rate, index value and balance of the interest
+/// change and payment rows of the selected cashflow in date order.
+///
+/// # Arguments
+///
+/// * `calc_engine` - The engine.
+/// * `elem_type` - The type of row to return.
+///
+/// # Return
+///
+/// * See description.
+fn rows(
+    calc_engine: &CalcEngine,
+    elem_type: ExtensionType,
+) -> Vec<(NaiveDate, Decimal, Option<Decimal>, Decimal)> {
+    let calc_mgr = calc_engine.calc_mgr();
+    let list_am = calc_mgr.list_cashflow().list_amortization().unwrap();
+
+    let mut rows = Vec::new();
+    let mut index: usize = 0;
+    while list_am.get_element(index) {
+        if list_am.elem_type() == elem_type {
+            rows.push((
+                list_am.event_date(),
+                list_am.value(),
+                list_am.index_value(),
+                calc_engine.round_currency(list_am.balance()),
+            ));
+        }
+        index += 1;
+    }
+
+    rows
+}
+
+#[test]
+fn rate_resets_from_index() {
+    let calc_engine = common::load_engine("arm.json");
+    assert!(calc_engine.calc_mgr().select_cashflow_by_name("ARM"));
+    calc_engine.balance_cashflow().unwrap();
+
+    // SOFR + 2.00 reset every 3 months with a 5 day lookback,
+    // 1.00 periodic cap and floor, 9.00 lifetime cap and 6.50 lifetime floor
+    let expected = [
+        // 5.00 + 2.00
+        (date(2024, 1, 1), "7.00", "5.00"),
+        // 7.50 is observed after the lookback date of 2024-03-27
+        (date(2024, 4, 1), "7.00", "5.00"),
+        // 8.00 + 2.00 is limited by the periodic cap to 7.00 + 1.00
+        (date(2024, 7, 1), "8.00", "8.00"),
+        // 3.00 + 2.00 is limited by the periodic floor to 8.00 - 1.00
+        (date(2024, 10, 1), "7.00", "3.00"),
+        // 3.50 + 2.00 is limited by the periodic floor to 6.00, then
+        // by the lifetime floor to 6.50
+        (date(2025, 1, 1), "6.50", "3.50"),
+    ];
+
+    let rates = rows(&calc_engine, ExtensionType::InterestChange);
+    assert_eq!(rates.len(), 15);
+    for (index, (event_date, rate, index_value, _balance)) in rates.iter().enumerate() {
+        // The rate is unchanged until the next reset
+        let (reset_date, reset_rate, reset_index) = expected[index / 3];
+        if index % 3 == 0 {
+            assert_eq!(*event_date, reset_date);
+        }
+        assert_eq!(*rate, decimal(reset_rate));
+        assert_eq!(*index_value, Some(decimal(reset_index)));
+    }
+}
+
+#[test]
+fn balances_follow_reset_rates() {
+    let calc_engine = common::load_engine("arm.json");
+    assert!(calc_engine.calc_mgr().select_cashflow_by_name("ARM"));
+    calc_engine.balance_cashflow().unwrap();
+
+    // Monthly interest of balance * rate / 12 less the 1,000 payment
+    let expected = [
+        "99583.33", "99164.24", "98742.69", "98318.69", "97892.22", "97463.26", "97113.01",
+        "96760.43", "96405.50", "95967.87", "95527.68", "95084.92", "94599.97", "94112.38",
+        "93622.16",
+    ];
+
+    let payments = rows(&calc_engine, ExtensionType::PrincipalChange);
+    let balances: Vec<Decimal> = payments[1..].iter().map(|o| o.3).collect();
+    let expected: Vec<Decimal> = expected.iter().map(|o| decimal(o)).collect();
+    assert_eq!(balances, expected);
+}
+
+#[test]
+fn index_not_observed() {
+    let calc_engine = common::load_engine("arm.json");
+    assert!(calc_engine
+        .calc_mgr()
+        .select_cashflow_by_name("ARM-Lookback"));
+
+    // The 30 day lookback is before the first SOFR observation
+    match calc_engine.balance_cashflow() {
+        Err(e) => {
+            assert_eq!(e.kind(), ErrorType::RateIndex);
+            assert_eq!(e.column_name(), "Index");
+        }
+        Ok(_o) => panic!("unobserved index accepted"),
+    }
+}
@@ -0,0 +1,177 @@
+// Copyright (c) 2021 ShiftLeft Software
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+mod common;
+
+use common::decimal;
+
+use amfnengine::core::{ElemBalanceResult, ElemSolverSettings};
+use amfnengine::engine::CalcEngine;
+use amfnengine::ListTrait;
+
+/// Load and balance the loan with the event to calculate selected.
+///
+/// # Arguments
+///
+/// * `event_index` - Index of the event to calculate.
+///
+/// # Return
+///
+/// * See description.
+fn loan(event_index: usize) -> CalcEngine {
+    let calc_engine = common::load_engine("loan.json");
+
+    calc_engine.balance_cashflow().unwrap();
+    assert!(calc_engine
+        .calc_mgr()
+        .list_cashflow()
+        .list_event()
+        .unwrap()
+        .get_element(event_index));
+
+    calc_engine
+}
+
+/// Calculate the value of an event of the loan.
+///
+/// # Arguments
+///
+/// * `event_index` - Index of the event to calculate.
+/// * `solver_settings` - Solver settings.
+///
+/// # Return
+///
+/// * See description.
+fn calculate_value(event_index: usize, solver_settings: &ElemSolverSettings) -> ElemBalanceResult {
+    match loan(event_index).calculate_value_with_settings(solver_settings) {
+        Err(e) => panic!("{}", e),
+        Ok(o) => o,
+    }
+}
+
+#[test]
+fn defaults_reproduce_baseline() {
+    let solver_settings = ElemSolverSettings::new();
+
+    // 200,000.00 over 360 months with 1,199.10 payments
+    for (event_index, value, balance) in [(1, "5.999992", "0.02"), (2, "1199.10", "1.06")] {
+        let result = match loan(event_index).calculate_value() {
+            Err(e) => panic!("{}", e),
+            Ok(o) => o,
+        };
+        assert_eq!(result.result_decimal(), decimal(value));
+        assert_eq!(result.balance().round_dp(2), decimal(balance));
+        assert_eq!(result.residual(), decimal(balance));
+
+        let result_settings = calculate_value(event_index, &solver_settings);
+        assert_eq!(result_settings.result_decimal(), result.result_decimal());
+        assert_eq!(result_settings.iterations(), result.iterations());
+    }
+
+    let result = match loan(2).calculate_periods() {
+        Err(e) => panic!("{}", e),
+        Ok(o) => o,
+    };
+    assert_eq!(result.result_integer(), 360);
+
+    let result = match loan(2).calculate_yield() {
+        Err(e) => panic!("{}", e),
+        Ok(o) => o,
+    };
+    assert_eq!(result.result_yield(), decimal("5.999992"));
+}
+
+#[test]
+fn iteration_cap_changes_result() {
+    let baseline = calculate_value(1, &ElemSolverSettings::new());
+    let mut solver_settings = ElemSolverSettings::new();
+    solver_settings.set_max_iterations(Option::from(2));
+
+    for event_index in [1, 2] {
+        let result = calculate_value(event_index, &solver_settings);
+
+        assert!(result.iterations() <= 2);
+        assert!(result.residual().abs() > decimal("1000.00"));
+    }
+
+    let result = calculate_value(1, &solver_settings);
+    assert_ne!(result.result_decimal(), baseline.result_decimal());
+    assert!(baseline.iterations() > 2);
+
+    let result = match loan(2).calculate_periods_with_settings(&solver_settings) {
+        Err(e) => panic!("{}", e),
+        Ok(o) => o,
+    };
+    assert!(result.iterations() <= 2);
+    assert_ne!(result.result_integer(), 360);
+}
+
+#[test]
+fn tolerance_stops_early() {
+    let baseline = calculate_value(1, &ElemSolverSettings::new());
+
+    let mut solver_settings = ElemSolverSettings::new();
+    solver_settings.set_abs_tolerance(Option::from(decimal("1000.00")));
+    let result = calculate_value(1, &solver_settings);
+
+    assert!(result.iterations() < baseline.iterations());
+    assert!(result.residual().abs() <= decimal("1000.00"));
+    assert_ne!(result.result_decimal(), baseline.result_decimal());
+
+    let mut solver_settings = ElemSolverSettings::new();
+    solver_settings.set_rel_tolerance(Option::from(decimal("0.005")));
+    let result = calculate_value(1, &solver_settings);
+
+    assert!(result.iterations() < baseline.iterations());
+    assert_ne!(result.result_decimal(), baseline.result_decimal());
+
+    // A tolerance tighter than the default is not reached earlier
+    let mut solver_settings = ElemSolverSettings::new();
+    solver_settings.set_abs_tolerance(Option::from(decimal("0.005")));
+    let result = calculate_value(1, &solver_settings);
+
+    assert_eq!(result.result_decimal(), baseline.result_decimal());
+    assert_eq!(result.iterations(), baseline.iterations());
+}
+
+#[test]
+fn preferences_settings_are_used() {
+    let calc_engine = loan(1);
+    let mut solver_settings = ElemSolverSettings::new();
+    solver_settings.set_max_iterations(Option::from(2));
+    calc_engine
+        .calc_mgr_mut()
+        .preferences_mut()
+        .set_solver_settings(solver_settings);
+
+    let result = match calc_engine.calculate_value() {
+        Err(e) => panic!("{}", e),
+        Ok(o) => o,
+    };
+    assert_eq!(
+        result.result_decimal(),
+        calculate_value(1, &solver_settings).result_decimal()
+    );
+
+    // Settings of the parameter take precedence
+    let mut solver_settings = ElemSolverSettings::new();
+    solver_settings.set_max_iterations(Option::from(100));
+    let calc_engine = loan(1);
+    calc_engine
+        .calc_mgr_mut()
+        .preferences_mut()
+        .set_solver_settings(solver_settings);
+    let mut solver_settings = ElemSolverSettings::new();
+    solver_settings.set_max_iterations(Option::from(2));
+
+    let result = match calc_engine.calculate_value_with_settings(&solver_settings) {
+        Err(e) => panic!("{}", e),
+        Ok(o) => o,
+    };
+    assert!(result.iterations() <= 2);
+}